@@ -36,6 +36,36 @@ impl From<Message> for ChatCompletionRequestMessage {
     }
 }
 
+/// Tokens the provider billed for a chat, from its `usage` field. A provider
+/// that leaves the field out reports nothing.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Usage {
+    pub prompt_tokens: u32,
+    pub completion_tokens: u32,
+}
+
+impl Usage {
+    #[must_use]
+    pub const fn total(self) -> u32 {
+        self.prompt_tokens.saturating_add(self.completion_tokens)
+    }
+}
+
+impl std::ops::AddAssign for Usage {
+    fn add_assign(&mut self, other: Self) {
+        self.prompt_tokens = self.prompt_tokens.saturating_add(other.prompt_tokens);
+        self.completion_tokens =
+            self.completion_tokens.saturating_add(other.completion_tokens);
+    }
+}
+
+/// The model's answer, and what every request behind it cost.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reply {
+    pub text: String,
+    pub usage: Usage,
+}
+
 #[must_use]
 pub fn strip_speaker_prefix<'a>(reply: &'a str, speakers: &[&str]) -> &'a str {
     let mut stripped = reply.trim();
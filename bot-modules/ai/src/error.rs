@@ -3,6 +3,8 @@ use std::fmt;
 use async_openai::error::OpenAIError;
 use zayden_core::error::{HandlerError, Respond};
 
+use crate::chat::Usage;
+
 #[derive(Debug)]
 pub enum AiError {
     OpenAI(OpenAIError),
@@ -62,6 +64,26 @@ impl From<reqwest::Error> for AiError {
 
 impl Respond for AiError {}
 
+/// A streamed reply that failed, with what the rounds before the failure
+/// had already been billed.
+#[derive(Debug)]
+pub struct StreamError {
+    pub error: AiError,
+    pub usage: Usage,
+}
+
+impl fmt::Display for StreamError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.error.fmt(f)
+    }
+}
+
+impl std::error::Error for StreamError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
 impl From<AiError> for HandlerError {
     fn from(e: AiError) -> Self {
         Self::from_respond(e)
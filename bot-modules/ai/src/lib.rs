@@ -1,6 +1,8 @@
 pub mod chat;
 pub mod error;
 pub mod openai;
+pub mod pager;
 pub mod persona;
+pub mod tools;
 
 pub use error::AiError as Error;
@@ -4,26 +4,45 @@ use async_openai::Client;
 use async_openai::config::OpenAIConfig;
 use async_openai::error::OpenAIError;
 use async_openai::types::chat::{
+    ChatCompletionMessageToolCall,
+    ChatCompletionMessageToolCallChunk,
+    ChatCompletionMessageToolCalls,
+    ChatCompletionRequestAssistantMessageArgs,
+    ChatCompletionRequestMessage,
+    ChatCompletionRequestToolMessageArgs,
+    ChatCompletionResponseMessage,
+    ChatCompletionStreamOptions,
+    CompletionUsage,
     CreateChatCompletionRequest,
     CreateChatCompletionRequestArgs,
+    CreateChatCompletionStreamResponse,
+    FunctionCall,
 };
+use futures::{Stream, StreamExt, stream};
 use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
 use serde::Deserialize;
 use zayden_app::services::http::ClientBuilderExt;
 
-use crate::chat::Message;
-use crate::error::AiError as Error;
+use crate::chat::{Message, Reply, Usage};
+use crate::error::{AiError as Error, StreamError};
+use crate::tools::{MAX_CALLS_PER_ROUND, MAX_TOOL_ROUNDS, ToolRegistry};
 
 const HTTP_REFERER: &str = "https://zayden.discord.bot";
 const APP_TITLE: &str = "Zayden";
 
+/// Between what the model said before a lookup and what it says after.
+const ROUND_BREAK: &str = "\n\n";
+
 const MAX_ATTEMPTS: u32 = 2;
 const RETRY_BACKOFF: Duration = Duration::from_secs(1);
 
 #[derive(Debug)]
 pub struct AiClient {
     client: Client<OpenAIConfig>,
+    /// For streamed replies, which outlast the plain client's total timeout.
+    stream_client: Client<OpenAIConfig>,
     model: String,
+    temperature: Option<f32>,
 }
 
 impl AiClient {
@@ -42,34 +61,287 @@ impl AiClient {
         );
 
         let http_client = reqwest::ClientBuilder::new()
-            .default_headers(headers)
+            .default_headers(headers.clone())
             .with_timeouts()
             .build()?;
+        let stream_http_client = reqwest::ClientBuilder::new()
+            .default_headers(headers)
+            .with_stream_timeouts()
+            .build()?;
 
         Ok(Self {
-            client: Client::with_config(config).with_http_client(http_client),
+            client: Client::with_config(config.clone())
+                .with_http_client(http_client),
+            stream_client: Client::with_config(config)
+                .with_http_client(stream_http_client),
             model: model.to_owned(),
+            temperature: None,
         })
     }
 
+    /// Samples at `temperature` rather than the provider's default.
+    #[must_use]
+    pub const fn with_temperature(mut self, temperature: f32) -> Self {
+        self.temperature = Some(temperature);
+        self
+    }
+
     pub async fn chat(
         &self,
         messages: Vec<Message>,
         max_tokens: u32,
-    ) -> Result<String, Error> {
+    ) -> Result<Reply, Error> {
         let messages: Vec<_> = messages.into_iter().map(Into::into).collect();
 
-        let request = CreateChatCompletionRequestArgs::default()
-            .model(&self.model)
-            .messages(messages)
-            .max_tokens(max_tokens)
-            .build()?;
+        let request = self.request(messages, max_tokens).build()?;
+        let (message, usage) = self.complete(request).await?;
+
+        Ok(Reply { text: message.content.ok_or(Error::NoContent)?, usage })
+    }
+
+    /// As [`Self::chat`], letting the model call `tools` before it answers.
+    /// It gets [`MAX_TOOL_ROUNDS`] rounds of calls; after that the tools are
+    /// withheld and it has to answer with what it found. The usage covers
+    /// every round.
+    pub async fn chat_with_tools(
+        &self,
+        messages: Vec<Message>,
+        max_tokens: u32,
+        tools: &ToolRegistry,
+    ) -> Result<Reply, Error> {
+        if tools.is_empty() {
+            return self.chat(messages, max_tokens).await;
+        }
+
+        let definitions = tools.definitions()?;
+        let mut messages: Vec<ChatCompletionRequestMessage> =
+            messages.into_iter().map(Into::into).collect();
+        let mut usage = Usage::default();
+
+        for _ in 0..MAX_TOOL_ROUNDS {
+            let request = self
+                .request(messages.clone(), max_tokens)
+                .tools(definitions.clone())
+                .build()?;
+
+            let (reply, round) = self.complete(request).await?;
+            usage += round;
+
+            let Some(calls) = reply.tool_calls.filter(|calls| !calls.is_empty())
+            else {
+                let text = reply.content.ok_or(Error::NoContent)?;
+                return Ok(Reply { text, usage });
+            };
+
+            messages.push(
+                ChatCompletionRequestAssistantMessageArgs::default()
+                    .tool_calls(calls.clone())
+                    .build()?
+                    .into(),
+            );
+            answer_calls(tools, calls, &mut messages).await?;
+        }
+
+        let request = self.request(messages, max_tokens).build()?;
+        let (reply, round) = self.complete(request).await?;
+        usage += round;
+
+        Ok(Reply { text: reply.content.ok_or(Error::NoContent)?, usage })
+    }
+
+    /// As [`Self::chat_with_tools`], streaming the answer: each piece of text
+    /// goes to `on_delta` as the provider sends it, and the reply's text is
+    /// all of them together. Text from a round that ends in lookups is kept,
+    /// set apart from the next round's by a blank line. A request that fails
+    /// before any text arrives is retried like any other; once text has been
+    /// handed out, a failure ends the reply with an error, and what was sent
+    /// so far stands. The error carries what the rounds before it cost.
+    pub async fn stream_with_tools(
+        &self,
+        messages: Vec<Message>,
+        max_tokens: u32,
+        tools: &ToolRegistry,
+        on_delta: impl FnMut(&str) + Send,
+    ) -> Result<Reply, StreamError> {
+        let mut reply = Reply { text: String::new(), usage: Usage::default() };
+
+        match self
+            .stream_rounds(messages, max_tokens, tools, on_delta, &mut reply)
+            .await
+        {
+            Ok(()) => Ok(reply),
+            Err(error) => Err(StreamError { error, usage: reply.usage }),
+        }
+    }
+
+    /// The rounds of [`Self::stream_with_tools`], adding each onto `reply` as
+    /// it finishes.
+    async fn stream_rounds(
+        &self,
+        messages: Vec<Message>,
+        max_tokens: u32,
+        tools: &ToolRegistry,
+        mut on_delta: impl FnMut(&str) + Send,
+        reply: &mut Reply,
+    ) -> Result<(), Error> {
+        let definitions =
+            if tools.is_empty() { None } else { Some(tools.definitions()?) };
+        let mut messages: Vec<ChatCompletionRequestMessage> =
+            messages.into_iter().map(Into::into).collect();
+        let mut needs_break = false;
+
+        for round in 0..=MAX_TOOL_ROUNDS {
+            let mut args = self.request(messages.clone(), max_tokens);
+            args.stream_options(ChatCompletionStreamOptions {
+                include_usage: Some(true),
+                include_obfuscation: None,
+            });
+            if let Some(definitions) = &definitions
+                && round < MAX_TOOL_ROUNDS
+            {
+                args.tools(definitions.clone());
+            }
+
+            let mut on_round_delta = |text: &str| {
+                if std::mem::take(&mut needs_break) {
+                    on_delta(ROUND_BREAK);
+                }
+                on_delta(text);
+            };
+            let streamed =
+                self.stream_round(args.build()?, &mut on_round_delta).await?;
+
+            if !streamed.text.is_empty() {
+                if !reply.text.is_empty() {
+                    reply.text.push_str(ROUND_BREAK);
+                }
+                reply.text.push_str(&streamed.text);
+            }
+            reply.usage += streamed.usage;
+
+            if streamed.calls.is_empty() {
+                break;
+            }
+            needs_break = !reply.text.is_empty();
+
+            let calls = streamed.calls.into_iter().map(ToolCallParts::into_call);
+            let calls: Vec<_> = calls.collect();
+
+            let mut assistant = ChatCompletionRequestAssistantMessageArgs::default();
+            assistant.tool_calls(calls.clone());
+            if !streamed.text.is_empty() {
+                assistant.content(streamed.text);
+            }
+            messages.push(assistant.build()?.into());
+            answer_calls(tools, calls, &mut messages).await?;
+        }
+
+        if reply.text.trim().is_empty() {
+            return Err(Error::NoContent);
+        }
+
+        Ok(())
+    }
+
+    /// One streamed request, to the end of the stream.
+    async fn stream_round(
+        &self,
+        request: CreateChatCompletionRequest,
+        on_delta: &mut (impl FnMut(&str) + Send),
+    ) -> Result<StreamedRound, Error> {
+        let mut events = self.open_stream(request).await?;
+        let mut round = StreamedRound::default();
+
+        while let Some(chunk) = events.next().await {
+            let chunk = chunk.map_err(classify)?;
+
+            if let Some(usage) = chunk.usage {
+                round.usage = billed(usage);
+            }
 
+            let Some(choice) = chunk.choices.into_iter().next() else {
+                continue;
+            };
+
+            if let Some(text) = choice.delta.content.filter(|t| !t.is_empty()) {
+                on_delta(&text);
+                round.text.push_str(&text);
+            }
+
+            for call in choice.delta.tool_calls.into_iter().flatten() {
+                round.add_call_chunk(call);
+            }
+        }
+
+        Ok(round)
+    }
+
+    fn request(
+        &self,
+        messages: Vec<ChatCompletionRequestMessage>,
+        max_tokens: u32,
+    ) -> CreateChatCompletionRequestArgs {
+        let mut args = CreateChatCompletionRequestArgs::default();
+        args.model(&self.model).messages(messages).max_tokens(max_tokens);
+
+        if let Some(temperature) = self.temperature {
+            args.temperature(temperature);
+        }
+
+        args
+    }
+
+    /// Sends `request`, retrying once if the failure looks transient.
+    async fn complete(
+        &self,
+        request: CreateChatCompletionRequest,
+    ) -> Result<(ChatCompletionResponseMessage, Usage), Error> {
+        self.retrying(|| self.send(request.clone())).await
+    }
+
+    /// Opens a stream for `request`, retrying as [`Self::complete`] does.
+    /// Providers report a refused request as the stream's first event, so
+    /// that is read here too; nothing has been handed out yet, so a retry
+    /// can't repeat text.
+    async fn open_stream(
+        &self,
+        request: CreateChatCompletionRequest,
+    ) -> Result<
+        impl Stream<Item = Result<CreateChatCompletionStreamResponse, OpenAIError>>
+        + Unpin,
+        Error,
+    > {
+        self.retrying(|| {
+            let request = request.clone();
+            async move {
+                let mut events = self
+                    .stream_client
+                    .chat()
+                    .create_stream(request)
+                    .await
+                    .map_err(classify)?;
+
+                match events.next().await {
+                    Some(Err(e)) => Err(classify(e)),
+                    first => Ok(stream::iter(first).chain(events)),
+                }
+            }
+        })
+        .await
+    }
+
+    async fn retrying<T, F>(
+        &self,
+        mut attempt_once: impl FnMut() -> F,
+    ) -> Result<T, Error>
+    where
+        F: Future<Output = Result<T, Error>>,
+    {
         let mut attempt = 1;
 
         loop {
-            let error = match self.send(request.clone()).await {
-                Ok(content) => return Ok(content),
+            let error = match attempt_once().await {
+                Ok(reply) => return Ok(reply),
                 Err(e) => e,
             };
 
@@ -92,15 +364,113 @@ impl AiClient {
     async fn send(
         &self,
         request: CreateChatCompletionRequest,
-    ) -> Result<String, Error> {
+    ) -> Result<(ChatCompletionResponseMessage, Usage), Error> {
         let response = self.client.chat().create(request).await.map_err(classify)?;
+        let usage = response.usage.map_or_else(Usage::default, billed);
 
-        response
+        let message = response
             .choices
             .into_iter()
             .next()
-            .and_then(|c| c.message.content)
-            .ok_or(Error::NoContent)
+            .map(|c| c.message)
+            .ok_or(Error::NoContent)?;
+
+        Ok((message, usage))
+    }
+}
+
+/// Answers every call in `calls` onto `messages`, even the ones that aren't
+/// run.
+async fn answer_calls(
+    tools: &ToolRegistry,
+    calls: Vec<ChatCompletionMessageToolCalls>,
+    messages: &mut Vec<ChatCompletionRequestMessage>,
+) -> Result<(), Error> {
+    for (index, call) in calls.into_iter().enumerate() {
+        let (id, output) = match call {
+            ChatCompletionMessageToolCalls::Function(call)
+                if index < MAX_CALLS_PER_ROUND =>
+            {
+                let output =
+                    tools.call(&call.function.name, &call.function.arguments).await;
+                (call.id, output)
+            },
+            ChatCompletionMessageToolCalls::Function(call) => {
+                (call.id, String::from("Error: too many lookups at once; not run."))
+            },
+            ChatCompletionMessageToolCalls::Custom(call) => {
+                (call.id, String::from("Error: only function tools are supported."))
+            },
+        };
+
+        messages.push(
+            ChatCompletionRequestToolMessageArgs::default()
+                .tool_call_id(id)
+                .content(output)
+                .build()?
+                .into(),
+        );
+    }
+
+    Ok(())
+}
+
+const fn billed(usage: CompletionUsage) -> Usage {
+    Usage {
+        prompt_tokens: usage.prompt_tokens,
+        completion_tokens: usage.completion_tokens,
+    }
+}
+
+/// What one streamed request produced.
+#[derive(Default)]
+struct StreamedRound {
+    text: String,
+    calls: Vec<ToolCallParts>,
+    usage: Usage,
+}
+
+impl StreamedRound {
+    /// Tool calls arrive in pieces, each naming the call it belongs to by
+    /// position, in order; the id and name come once, the arguments a bit at
+    /// a time. A piece for a position out of order is dropped.
+    fn add_call_chunk(&mut self, chunk: ChatCompletionMessageToolCallChunk) {
+        let index = usize::try_from(chunk.index).unwrap_or(usize::MAX);
+        if index == self.calls.len() {
+            self.calls.push(ToolCallParts::default());
+        }
+
+        let Some(call) = self.calls.get_mut(index) else {
+            return;
+        };
+
+        if let Some(id) = chunk.id {
+            call.id = id;
+        }
+        if let Some(function) = chunk.function {
+            if let Some(name) = function.name {
+                call.name.push_str(&name);
+            }
+            if let Some(arguments) = function.arguments {
+                call.arguments.push_str(&arguments);
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+struct ToolCallParts {
+    id: String,
+    name: String,
+    arguments: String,
+}
+
+impl ToolCallParts {
+    fn into_call(self) -> ChatCompletionMessageToolCalls {
+        ChatCompletionMessageToolCalls::Function(ChatCompletionMessageToolCall {
+            id: self.id,
+            function: FunctionCall { name: self.name, arguments: self.arguments },
+        })
     }
 }
 
@@ -0,0 +1,89 @@
+//! Laying a reply that is still being written out over Discord messages.
+//! Text past [`MESSAGE_CHARS`] moves on to another message, and a page is
+//! only sent again when its content changed.
+
+/// Most characters Discord takes in one message.
+pub const MESSAGE_CHARS: usize = 2000;
+
+/// `text` cut into messages of at most [`MESSAGE_CHARS`], breaking at a line
+/// or a space late enough in the page where there is one. A page only
+/// depends on the text up to its end, so pages already full never change as
+/// more text arrives.
+#[must_use]
+pub fn pages(text: &str) -> Vec<&str> {
+    let mut pages = Vec::new();
+    let mut rest = text;
+
+    while let Some((page, next)) = split(rest) {
+        pages.push(page);
+        rest = next;
+    }
+
+    pages.push(rest);
+    pages.into_iter().map(str::trim).filter(|page| !page.is_empty()).collect()
+}
+
+/// The first page of `text` and what follows it, or `None` if it all fits.
+fn split(text: &str) -> Option<(&str, &str)> {
+    let (end, _) = text.char_indices().nth(MESSAGE_CHARS)?;
+    let head = text.get(..end)?;
+
+    let late = |at: &usize| *at > end / 2;
+    let at = head
+        .rfind('\n')
+        .filter(late)
+        .or_else(|| head.rfind(' ').filter(late))
+        .unwrap_or(end);
+
+    Some((text.get(..at)?, text.get(at..)?))
+}
+
+/// What has to happen on Discord for a page to show the latest text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PageUpdate {
+    /// A page that isn't posted yet.
+    Send(String),
+    /// A posted page, by position, whose content changed.
+    Edit(usize, String),
+}
+
+/// The pages of one reply, as last posted.
+#[derive(Debug, Clone, Default)]
+pub struct Pager {
+    shown: Vec<String>,
+}
+
+impl Pager {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The updates that bring the posted pages up to `text`. They are taken
+    /// as done, so the next call only reports what changes after this.
+    pub fn update(&mut self, text: &str) -> Vec<PageUpdate> {
+        let mut updates = Vec::new();
+
+        for (index, page) in pages(text).into_iter().enumerate() {
+            match self.shown.get_mut(index) {
+                Some(shown) if shown == page => {},
+                Some(shown) => {
+                    page.clone_into(shown);
+                    updates.push(PageUpdate::Edit(index, page.to_string()));
+                },
+                None => {
+                    self.shown.push(page.to_string());
+                    updates.push(PageUpdate::Send(page.to_string()));
+                },
+            }
+        }
+
+        updates
+    }
+
+    /// Whether anything has been posted yet.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.shown.is_empty()
+    }
+}
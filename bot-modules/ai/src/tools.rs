@@ -0,0 +1,116 @@
+//! The tools offered to the model on a chat, and running the calls it makes.
+//! Modules supply the tools; see [`zayden_core::tool`].
+
+use std::time::Duration;
+
+use async_openai::types::chat::{
+    ChatCompletionToolArgs,
+    ChatCompletionTools,
+    FunctionObjectArgs,
+};
+use tracing::{debug, warn};
+use zayden_core::tool::schema;
+use zayden_core::{AiTool, ToolArgs};
+
+use crate::error::AiError as Error;
+
+/// Most rounds of tool calls before the model has to answer with what it has.
+pub const MAX_TOOL_ROUNDS: usize = 3;
+
+/// Calls past this many in one round are answered without being run.
+pub const MAX_CALLS_PER_ROUND: usize = 3;
+
+/// How long one call may take before the model is told it timed out.
+pub const TOOL_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Longest output handed back to the model, in characters.
+const MAX_OUTPUT_CHARS: usize = 2000;
+
+#[derive(Default)]
+pub struct ToolRegistry {
+    tools: Vec<Box<dyn AiTool>>,
+}
+
+impl ToolRegistry {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a tool. A second tool with the same name is ignored.
+    pub fn register(&mut self, tool: impl AiTool + 'static) -> &mut Self {
+        if self.get(tool.name()).is_some() {
+            warn!(tool = tool.name(), "AI tool registered twice; ignoring");
+            return self;
+        }
+
+        self.tools.push(Box::new(tool));
+        self
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.tools.is_empty()
+    }
+
+    #[must_use]
+    pub fn names(&self) -> Vec<&'static str> {
+        self.tools.iter().map(|tool| tool.name()).collect()
+    }
+
+    fn get(&self, name: &str) -> Option<&dyn AiTool> {
+        self.tools.iter().find(|tool| tool.name() == name).map(AsRef::as_ref)
+    }
+
+    pub(crate) fn definitions(&self) -> Result<Vec<ChatCompletionTools>, Error> {
+        self.tools
+            .iter()
+            .map(|tool| {
+                let function = FunctionObjectArgs::default()
+                    .name(tool.name())
+                    .description(tool.description())
+                    .parameters(schema(tool.params()))
+                    .build()?;
+
+                let tool =
+                    ChatCompletionToolArgs::default().function(function).build()?;
+
+                Ok(ChatCompletionTools::Function(tool))
+            })
+            .collect()
+    }
+
+    /// Runs one call and returns what to tell the model. Failures are told
+    /// to the model too, so it can say it couldn't look something up.
+    pub async fn call(&self, name: &str, arguments: &str) -> String {
+        let Some(tool) = self.get(name) else {
+            return format!("Error: there is no tool called `{name}`.");
+        };
+
+        let args = match ToolArgs::parse(arguments) {
+            Ok(args) => args,
+            Err(e) => return format!("Error: {e}"),
+        };
+
+        debug!(tool = name, arguments, "running AI tool");
+
+        let output = match tokio::time::timeout(TOOL_TIMEOUT, tool.call(&args)).await
+        {
+            Ok(Ok(output)) => output,
+            Ok(Err(e)) => format!("Error: {e}"),
+            Err(_elapsed) => {
+                warn!(tool = name, "AI tool timed out");
+                String::from("Error: the lookup timed out.")
+            },
+        };
+
+        truncate(output)
+    }
+}
+
+fn truncate(output: String) -> String {
+    match output.char_indices().nth(MAX_OUTPUT_CHARS) {
+        Some((end, _)) => format!("{}…", output.get(..end).unwrap_or_default()),
+        None => output,
+    }
+}
@@ -2,7 +2,7 @@ use std::sync::Arc;
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::time::Duration;
 
-use ai::chat::{Message, Role};
+use ai::chat::{Message, Role, Usage};
 use ai::error::AiError;
 use ai::openai::AiClient;
 use async_openai::error::OpenAIError;
@@ -151,12 +151,43 @@ async fn chat_parses_a_well_formed_openrouter_completion() {
 
     let client =
         AiClient::new("test-key", &base_url, "test-model").expect("build client");
-    let content = client
+    let reply = client
         .chat(vec![Message::new(Role::User, "hi")], 16)
         .await
         .expect("well-formed response should parse");
 
-    assert_eq!(content, "Hello there!");
+    assert_eq!(reply.text, "Hello there!");
+    assert_eq!(reply.usage, Usage::default(), "a null usage bills nothing");
+}
+
+#[tokio::test]
+async fn chat_reports_the_tokens_the_provider_billed() {
+    let body = r#"{
+        "id": "chatcmpl-test123",
+        "object": "chat.completion",
+        "created": 1700000000,
+        "model": "test-model",
+        "choices": [
+            {
+                "index": 0,
+                "message": { "role": "assistant", "content": "Hello there!" },
+                "finish_reason": "stop"
+            }
+        ],
+        "usage": { "prompt_tokens": 12, "completion_tokens": 3, "total_tokens": 15 }
+    }"#;
+    let base_url =
+        spawn_mock_server("HTTP/1.1 200 OK", body).await.expect("start mock server");
+
+    let client =
+        AiClient::new("test-key", &base_url, "test-model").expect("build client");
+    let reply = client
+        .chat(vec![Message::new(Role::User, "hi")], 16)
+        .await
+        .expect("well-formed response should parse");
+
+    assert_eq!(reply.usage, Usage { prompt_tokens: 12, completion_tokens: 3 });
+    assert_eq!(reply.usage.total(), 15);
 }
 
 #[tokio::test]
@@ -276,12 +307,12 @@ async fn an_aborted_upstream_is_retried() {
 
     let client =
         AiClient::new("test-key", &base_url, "test-model").expect("build client");
-    let content = client
+    let reply = client
         .chat(vec![Message::new(Role::User, "hi")], 16)
         .await
         .expect("the second attempt should succeed");
 
-    assert_eq!(content, "Hello there!");
+    assert_eq!(reply.text, "Hello there!");
     assert_eq!(served.load(Ordering::SeqCst), 2, "expected exactly one retry");
 }
 
@@ -0,0 +1,73 @@
+//! Paging a streamed reply: long text spills over into more messages, pages
+//! already full never change, and only what changed is posted again.
+
+use ai::pager::{MESSAGE_CHARS, PageUpdate, Pager, pages};
+
+fn words(count: usize) -> String {
+    vec!["word"; count].join(" ")
+}
+
+#[test]
+fn short_text_is_one_page() {
+    assert_eq!(pages("  Hello there!  "), ["Hello there!"]);
+    assert!(pages("   ").is_empty(), "blank text posts nothing");
+}
+
+#[test]
+fn long_text_breaks_at_a_space_within_the_limit() {
+    let text = words(1000);
+
+    let pages = pages(&text);
+
+    assert!(pages.len() > 1);
+    for page in &pages {
+        assert!(page.chars().count() <= MESSAGE_CHARS);
+        assert!(page.starts_with("word") && page.ends_with("word"), "{page}");
+    }
+    assert_eq!(pages.join(" "), text, "no text is lost at the breaks");
+}
+
+#[test]
+fn text_without_spaces_breaks_at_the_limit() {
+    let text = "é".repeat(MESSAGE_CHARS + 10);
+
+    let pages = pages(&text);
+
+    assert_eq!(pages.len(), 2);
+    assert_eq!(pages.first().map(|p| p.chars().count()), Some(MESSAGE_CHARS));
+    assert_eq!(pages.get(1).map(|p| p.chars().count()), Some(10));
+}
+
+#[test]
+fn full_pages_stay_put_as_text_arrives() {
+    let text = words(1000);
+    let first = pages(&text).first().map(ToString::to_string);
+
+    let longer = format!("{text} and then some more");
+
+    assert_eq!(pages(&longer).first().map(ToString::to_string), first);
+}
+
+#[test]
+fn the_pager_sends_new_pages_and_edits_changed_ones() {
+    let mut pager = Pager::new();
+    assert!(pager.is_empty());
+
+    assert_eq!(pager.update("Hel"), [PageUpdate::Send("Hel".to_string())]);
+    assert_eq!(pager.update("Hel"), [], "nothing changed");
+    assert_eq!(pager.update("Hello"), [PageUpdate::Edit(0, "Hello".to_string())]);
+
+    let long = words(500);
+    let updates = pager.update(&long);
+
+    assert!(matches!(updates.first(), Some(PageUpdate::Edit(0, _))));
+    assert!(matches!(updates.get(1), Some(PageUpdate::Send(_))));
+    assert_eq!(updates.len(), 2);
+
+    let more = format!("{long} word");
+    let updates = pager.update(&more);
+    assert!(
+        matches!(updates.as_slice(), [PageUpdate::Edit(1, _)]),
+        "only the last page changed: {updates:?}"
+    );
+}
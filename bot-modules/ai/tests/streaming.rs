@@ -0,0 +1,378 @@
+//! Streamed replies against a mock OpenAI-compatible server sending
+//! server-sent events: text arrives a piece at a time, lookups still run
+//! between rounds, and a provider that fails partway keeps what it sent and
+//! reports what it had billed.
+
+use std::sync::Arc;
+
+use ai::chat::{Message, Role, Usage};
+use ai::error::{AiError, StreamError};
+use ai::openai::AiClient;
+use ai::tools::ToolRegistry;
+use async_trait::async_trait;
+use serde_json::{Value, json};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::Mutex;
+use zayden_core::{AiTool, ToolArgs, ToolError};
+
+/// Reads one request and returns its body.
+async fn read_body(socket: &mut TcpStream) -> Option<Vec<u8>> {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 4096];
+
+    loop {
+        let n = match socket.read(&mut chunk).await {
+            Ok(0) | Err(_) => return None,
+            Ok(n) => n,
+        };
+        buf.extend_from_slice(chunk.get(..n)?);
+
+        let Some(header_end) = buf.windows(4).position(|w| w == b"\r\n\r\n") else {
+            continue;
+        };
+
+        let headers = String::from_utf8_lossy(buf.get(..header_end)?);
+        let content_length: usize = headers
+            .lines()
+            .find_map(|line| {
+                line.to_ascii_lowercase()
+                    .strip_prefix("content-length:")
+                    .map(|v| v.trim().parse().unwrap_or(0))
+            })
+            .unwrap_or(0);
+
+        let body_start = header_end + 4;
+        if buf.len().saturating_sub(body_start) >= content_length {
+            return buf.get(body_start..).map(<[u8]>::to_vec);
+        }
+    }
+}
+
+/// Answers each request with the next list of events in `responses`, as one
+/// event stream, and records every request body it was sent.
+async fn spawn_event_server(
+    responses: Vec<Vec<String>>,
+) -> Option<(String, Arc<Mutex<Vec<Value>>>)> {
+    let listener = TcpListener::bind("127.0.0.1:0").await.ok()?;
+    let addr = listener.local_addr().ok()?;
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let recorded = Arc::clone(&requests);
+
+    tokio::spawn(async move {
+        loop {
+            let Ok((mut socket, _)) = listener.accept().await else {
+                break;
+            };
+
+            let Some(body) = read_body(&mut socket).await else {
+                continue;
+            };
+
+            let index = {
+                let mut requests = recorded.lock().await;
+                requests.push(serde_json::from_slice(&body).unwrap_or(Value::Null));
+                requests.len() - 1
+            };
+
+            let Some(events) = responses.get(index).or_else(|| responses.last())
+            else {
+                break;
+            };
+
+            let body: String =
+                events.iter().map(|event| format!("data: {event}\n\n")).collect();
+            let response = format!(
+                "HTTP/1.1 200 OK\r\ncontent-type: text/event-stream\r\ncontent-length: {}\r\n\r\n{body}",
+                body.len()
+            );
+            let _ = socket.write_all(response.as_bytes()).await;
+            let _ = socket.shutdown().await;
+        }
+    });
+
+    Some((format!("http://{addr}"), requests))
+}
+
+fn chunk(delta: &Value) -> String {
+    json!({
+        "id": "chatcmpl-test",
+        "object": "chat.completion.chunk",
+        "created": 1_700_000_000,
+        "model": "test-model",
+        "choices": [{ "index": 0, "delta": delta, "finish_reason": null }]
+    })
+    .to_string()
+}
+
+fn text(content: &str) -> String {
+    chunk(&json!({ "content": content }))
+}
+
+/// The closing chunk a provider sends when asked to include usage.
+fn usage(prompt_tokens: u32, completion_tokens: u32) -> String {
+    json!({
+        "id": "chatcmpl-test",
+        "object": "chat.completion.chunk",
+        "created": 1_700_000_000,
+        "model": "test-model",
+        "choices": [],
+        "usage": {
+            "prompt_tokens": prompt_tokens,
+            "completion_tokens": completion_tokens,
+            "total_tokens": prompt_tokens + completion_tokens,
+        }
+    })
+    .to_string()
+}
+
+fn done() -> String {
+    String::from("[DONE]")
+}
+
+struct Weather;
+
+#[async_trait]
+impl AiTool for Weather {
+    fn name(&self) -> &'static str {
+        "test_weather"
+    }
+
+    fn description(&self) -> &'static str {
+        "Today's weather."
+    }
+
+    async fn call(&self, _args: &ToolArgs) -> Result<String, ToolError> {
+        Ok(String::from("Sunny, 21C"))
+    }
+}
+
+fn client(base_url: &str) -> AiClient {
+    AiClient::new("test-key", base_url, "test-model").expect("build client")
+}
+
+#[tokio::test]
+async fn text_arrives_in_order_and_makes_up_the_reply() {
+    let events = vec![text("Hel"), text("lo "), text("there!"), usage(9, 4), done()];
+    let (base_url, requests) =
+        spawn_event_server(vec![events]).await.expect("start mock server");
+
+    let mut deltas = Vec::new();
+    let reply = client(&base_url)
+        .stream_with_tools(
+            vec![Message::new(Role::User, "hi")],
+            16,
+            &ToolRegistry::new(),
+            |delta| deltas.push(delta.to_string()),
+        )
+        .await
+        .expect("a well-formed stream should parse");
+
+    assert_eq!(deltas, ["Hel", "lo ", "there!"]);
+    assert_eq!(reply.text, "Hello there!");
+    assert_eq!(reply.usage, Usage { prompt_tokens: 9, completion_tokens: 4 });
+
+    let requests = requests.lock().await;
+    let request = requests.first().expect("one request");
+    assert_eq!(request["stream"], json!(true));
+    assert_eq!(
+        request["stream_options"]["include_usage"],
+        json!(true),
+        "usage has to be asked for on a stream"
+    );
+}
+
+#[tokio::test]
+async fn lookups_run_between_streamed_rounds() {
+    let call = chunk(&json!({
+        "tool_calls": [{
+            "index": 0,
+            "id": "call_0",
+            "type": "function",
+            "function": { "name": "test_weather", "arguments": "" }
+        }]
+    }));
+    let arguments = chunk(&json!({
+        "tool_calls": [{ "index": 0, "function": { "arguments": "{}" } }]
+    }));
+    let (base_url, requests) = spawn_event_server(vec![
+        vec![call, arguments, usage(20, 5), done()],
+        vec![text("Sunny, "), text("21 degrees."), usage(30, 6), done()],
+    ])
+    .await
+    .expect("start mock server");
+
+    let mut tools = ToolRegistry::new();
+    tools.register(Weather);
+
+    let mut deltas = Vec::new();
+    let reply = client(&base_url)
+        .stream_with_tools(
+            vec![Message::new(Role::User, "weather?")],
+            64,
+            &tools,
+            |delta| deltas.push(delta.to_string()),
+        )
+        .await
+        .expect("the second round answers");
+
+    assert_eq!(reply.text, "Sunny, 21 degrees.");
+    assert_eq!(deltas.concat(), reply.text, "only the answer is streamed");
+    assert_eq!(reply.usage, Usage { prompt_tokens: 50, completion_tokens: 11 });
+
+    let requests = requests.lock().await;
+    let followup = requests.get(1).expect("a second round");
+    let messages = followup["messages"].as_array().expect("messages");
+    let assistant = messages.get(1).expect("the call is replayed");
+    assert_eq!(assistant["tool_calls"][0]["id"], "call_0");
+    assert_eq!(assistant["tool_calls"][0]["function"]["arguments"], "{}");
+    let output = messages.get(2).expect("the call is answered");
+    assert_eq!(output["tool_call_id"], "call_0");
+    assert_eq!(output["content"], "Sunny, 21C");
+}
+
+#[tokio::test]
+async fn text_before_a_lookup_is_kept_apart_and_replayed() {
+    let call = chunk(&json!({
+        "tool_calls": [{
+            "index": 0,
+            "id": "call_0",
+            "type": "function",
+            "function": { "name": "test_weather", "arguments": "{}" }
+        }]
+    }));
+    let (base_url, requests) = spawn_event_server(vec![
+        vec![text("Let me check."), call, usage(20, 5), done()],
+        vec![text("Sunny, 21 degrees."), usage(30, 6), done()],
+    ])
+    .await
+    .expect("start mock server");
+
+    let mut tools = ToolRegistry::new();
+    tools.register(Weather);
+
+    let mut deltas = Vec::new();
+    let reply = client(&base_url)
+        .stream_with_tools(
+            vec![Message::new(Role::User, "weather?")],
+            64,
+            &tools,
+            |delta| deltas.push(delta.to_string()),
+        )
+        .await
+        .expect("the second round answers");
+
+    assert_eq!(reply.text, "Let me check.\n\nSunny, 21 degrees.");
+    assert_eq!(deltas.concat(), reply.text);
+
+    let requests = requests.lock().await;
+    let followup = requests.get(1).expect("a second round");
+    let assistant = followup["messages"].get(1).expect("the call is replayed");
+    assert_eq!(assistant["content"], "Let me check.");
+    assert_eq!(assistant["tool_calls"][0]["id"], "call_0");
+}
+
+#[tokio::test]
+async fn a_provider_failing_partway_keeps_what_it_sent() {
+    let failure = json!({
+        "error": { "message": "upstream went away", "code": 502 }
+    })
+    .to_string();
+    let (base_url, requests) =
+        spawn_event_server(vec![vec![text("Half an "), text("answer"), failure]])
+            .await
+            .expect("start mock server");
+
+    let mut deltas = Vec::new();
+    let result = client(&base_url)
+        .stream_with_tools(
+            vec![Message::new(Role::User, "hi")],
+            16,
+            &ToolRegistry::new(),
+            |delta| deltas.push(delta.to_string()),
+        )
+        .await;
+
+    assert!(
+        matches!(
+            result,
+            Err(StreamError {
+                error: AiError::Provider { code: Some(502), .. },
+                ..
+            })
+        ),
+        "expected the provider's error, got {result:?}"
+    );
+    assert_eq!(deltas.concat(), "Half an answer");
+    assert_eq!(
+        requests.lock().await.len(),
+        1,
+        "text was already handed out, so the request is not retried"
+    );
+}
+
+#[tokio::test]
+async fn a_stream_with_no_text_is_no_content() {
+    let (base_url, _requests) = spawn_event_server(vec![vec![usage(5, 0), done()]])
+        .await
+        .expect("start mock server");
+
+    let result = client(&base_url)
+        .stream_with_tools(
+            vec![Message::new(Role::User, "hi")],
+            16,
+            &ToolRegistry::new(),
+            |_| {},
+        )
+        .await;
+
+    assert!(
+        matches!(
+            result,
+            Err(StreamError {
+                error: AiError::NoContent,
+                usage: Usage { prompt_tokens: 5, completion_tokens: 0 },
+            })
+        ),
+        "got {result:?}"
+    );
+}
+
+#[tokio::test]
+async fn a_failure_after_a_lookup_reports_what_was_billed() {
+    let call = chunk(&json!({
+        "tool_calls": [{
+            "index": 0,
+            "id": "call_0",
+            "type": "function",
+            "function": { "name": "test_weather", "arguments": "{}" }
+        }]
+    }));
+    let failure = json!({
+        "error": { "message": "upstream went away", "code": 502 }
+    })
+    .to_string();
+    let (base_url, _requests) = spawn_event_server(vec![
+        vec![call, usage(20, 5), done()],
+        vec![text("Sunny"), failure],
+    ])
+    .await
+    .expect("start mock server");
+
+    let mut tools = ToolRegistry::new();
+    tools.register(Weather);
+
+    let result = client(&base_url)
+        .stream_with_tools(
+            vec![Message::new(Role::User, "weather?")],
+            64,
+            &tools,
+            |_| {},
+        )
+        .await;
+
+    let Err(failed) = result else {
+        panic!("the second round fails, got {result:?}");
+    };
+    assert_eq!(failed.usage, Usage { prompt_tokens: 20, completion_tokens: 5 });
+}
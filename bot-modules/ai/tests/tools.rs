@@ -0,0 +1,419 @@
+//! The tool-call loop against a mock OpenAI-compatible server: the model asks
+//! for lookups, gets their output back, and answers once it has what it needs.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use ai::chat::{Message, Role, Usage};
+use ai::openai::AiClient;
+use ai::tools::{MAX_TOOL_ROUNDS, ToolRegistry};
+use async_trait::async_trait;
+use serde_json::{Value, json};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::Mutex;
+use zayden_core::{AiTool, ToolArgs, ToolError, ToolParam};
+
+/// Reads one request and returns its body.
+async fn read_body(socket: &mut TcpStream) -> Option<Vec<u8>> {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 4096];
+
+    loop {
+        let n = match socket.read(&mut chunk).await {
+            Ok(0) | Err(_) => return None,
+            Ok(n) => n,
+        };
+        buf.extend_from_slice(chunk.get(..n)?);
+
+        let Some(header_end) = buf.windows(4).position(|w| w == b"\r\n\r\n") else {
+            continue;
+        };
+
+        let headers = String::from_utf8_lossy(buf.get(..header_end)?);
+        let content_length: usize = headers
+            .lines()
+            .find_map(|line| {
+                line.to_ascii_lowercase()
+                    .strip_prefix("content-length:")
+                    .map(|v| v.trim().parse().unwrap_or(0))
+            })
+            .unwrap_or(0);
+
+        let body_start = header_end + 4;
+        if buf.len().saturating_sub(body_start) >= content_length {
+            return buf.get(body_start..).map(<[u8]>::to_vec);
+        }
+    }
+}
+
+/// Answers each request with the next body in `responses`, repeating the last
+/// once the list runs out, and records every request body it was sent.
+async fn spawn_recording_server(
+    responses: Vec<String>,
+) -> Option<(String, Arc<Mutex<Vec<Value>>>)> {
+    let listener = TcpListener::bind("127.0.0.1:0").await.ok()?;
+    let addr = listener.local_addr().ok()?;
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let recorded = Arc::clone(&requests);
+
+    tokio::spawn(async move {
+        loop {
+            let Ok((mut socket, _)) = listener.accept().await else {
+                break;
+            };
+
+            let Some(body) = read_body(&mut socket).await else {
+                continue;
+            };
+
+            let index = {
+                let mut requests = recorded.lock().await;
+                requests.push(serde_json::from_slice(&body).unwrap_or(Value::Null));
+                requests.len() - 1
+            };
+
+            let Some(body) = responses.get(index).or_else(|| responses.last())
+            else {
+                break;
+            };
+
+            let response = format!(
+                "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\r\n{body}",
+                body.len()
+            );
+            let _ = socket.write_all(response.as_bytes()).await;
+            let _ = socket.shutdown().await;
+        }
+    });
+
+    Some((format!("http://{addr}"), requests))
+}
+
+fn completion(message: &Value, finish_reason: &str) -> String {
+    json!({
+        "id": "chatcmpl-test",
+        "object": "chat.completion",
+        "created": 1_700_000_000,
+        "model": "test-model",
+        "choices": [
+            { "index": 0, "message": message, "finish_reason": finish_reason }
+        ],
+        "usage": null
+    })
+    .to_string()
+}
+
+/// `body` with the provider's `usage` filled in.
+fn billed(body: &str, prompt_tokens: u32, completion_tokens: u32) -> String {
+    let mut body: Value = serde_json::from_str(body).unwrap_or(Value::Null);
+    body["usage"] = json!({
+        "prompt_tokens": prompt_tokens,
+        "completion_tokens": completion_tokens,
+        "total_tokens": prompt_tokens + completion_tokens,
+    });
+    body.to_string()
+}
+
+fn answer(content: &str) -> String {
+    completion(&json!({ "role": "assistant", "content": content }), "stop")
+}
+
+/// A reply asking for `calls`, each a tool name and its JSON arguments.
+fn tool_calls(calls: &[(&str, &str)]) -> String {
+    let calls: Vec<Value> = calls
+        .iter()
+        .enumerate()
+        .map(|(i, (name, arguments))| {
+            json!({
+                "id": format!("call_{i}"),
+                "type": "function",
+                "function": { "name": name, "arguments": arguments }
+            })
+        })
+        .collect();
+
+    completion(
+        &json!({ "role": "assistant", "content": null, "tool_calls": calls }),
+        "tool_calls",
+    )
+}
+
+/// The tool messages sent back in a request, as `(call id, content)`.
+fn tool_results(request: &Value) -> Vec<(String, String)> {
+    request["messages"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|message| message["role"] == "tool")
+        .map(|message| {
+            (
+                message["tool_call_id"].as_str().unwrap_or_default().to_owned(),
+                message["content"].as_str().unwrap_or_default().to_owned(),
+            )
+        })
+        .collect()
+}
+
+struct Echo;
+
+#[async_trait]
+impl AiTool for Echo {
+    fn name(&self) -> &'static str {
+        "test_echo"
+    }
+
+    fn description(&self) -> &'static str {
+        "Repeats a name back."
+    }
+
+    fn params(&self) -> &'static [ToolParam] {
+        const PARAMS: &[ToolParam] = &[ToolParam::required("name", "Anything.")];
+        PARAMS
+    }
+
+    async fn call(&self, args: &ToolArgs) -> Result<String, ToolError> {
+        Ok(format!("echo: {}", args.required("name")?))
+    }
+}
+
+struct Count;
+
+#[async_trait]
+impl AiTool for Count {
+    fn name(&self) -> &'static str {
+        "test_count"
+    }
+
+    fn description(&self) -> &'static str {
+        "Returns a number."
+    }
+
+    async fn call(&self, _args: &ToolArgs) -> Result<String, ToolError> {
+        Ok(String::from("42"))
+    }
+}
+
+struct Slow;
+
+#[async_trait]
+impl AiTool for Slow {
+    fn name(&self) -> &'static str {
+        "test_slow"
+    }
+
+    fn description(&self) -> &'static str {
+        "Never finishes in time."
+    }
+
+    async fn call(&self, _args: &ToolArgs) -> Result<String, ToolError> {
+        tokio::time::sleep(Duration::from_secs(3600)).await;
+        Ok(String::from("too late"))
+    }
+}
+
+fn registry() -> ToolRegistry {
+    let mut tools = ToolRegistry::new();
+    tools.register(Echo).register(Count);
+    tools
+}
+
+#[tokio::test]
+async fn lookups_feed_back_into_the_conversation_until_the_model_answers() {
+    let (base_url, requests) = spawn_recording_server(vec![
+        tool_calls(&[("test_echo", r#"{"name":"Lamball"}"#)]),
+        tool_calls(&[("test_count", "")]),
+        answer("Lamball, and 42."),
+    ])
+    .await
+    .expect("start mock server");
+
+    let client =
+        AiClient::new("test-key", &base_url, "test-model").expect("build client");
+    let reply = client
+        .chat_with_tools(vec![Message::new(Role::User, "hi")], 64, &registry())
+        .await
+        .expect("the model answers after two lookups");
+
+    assert_eq!(reply.text, "Lamball, and 42.");
+
+    let requests = requests.lock().await;
+    let [first, second, third] = requests.as_slice() else {
+        panic!("expected three requests, got {}", requests.len());
+    };
+
+    let offered: Vec<&str> = first["tools"]
+        .as_array()
+        .expect("tools are offered")
+        .iter()
+        .filter_map(|tool| tool["function"]["name"].as_str())
+        .collect();
+    assert_eq!(offered, ["test_echo", "test_count"]);
+
+    assert_eq!(tool_results(second), [(
+        String::from("call_0"),
+        String::from("echo: Lamball")
+    )]);
+    // Earlier results stay in the conversation.
+    assert_eq!(tool_results(third), [
+        (String::from("call_0"), String::from("echo: Lamball")),
+        (String::from("call_0"), String::from("42")),
+    ]);
+}
+
+#[tokio::test]
+async fn usage_covers_every_round() {
+    let (base_url, _requests) = spawn_recording_server(vec![
+        billed(&tool_calls(&[("test_count", "")]), 40, 5),
+        billed(&answer("42."), 60, 3),
+    ])
+    .await
+    .expect("start mock server");
+
+    let client =
+        AiClient::new("test-key", &base_url, "test-model").expect("build client");
+    let reply = client
+        .chat_with_tools(vec![Message::new(Role::User, "hi")], 64, &registry())
+        .await
+        .expect("the model answers after one lookup");
+
+    assert_eq!(reply.usage, Usage { prompt_tokens: 100, completion_tokens: 8 });
+}
+
+#[tokio::test]
+async fn a_failed_lookup_is_reported_to_the_model() {
+    let (base_url, requests) = spawn_recording_server(vec![
+        tool_calls(&[("test_missing", "{}"), ("test_echo", "{}")]),
+        answer("No idea."),
+    ])
+    .await
+    .expect("start mock server");
+
+    let client =
+        AiClient::new("test-key", &base_url, "test-model").expect("build client");
+    let reply = client
+        .chat_with_tools(vec![Message::new(Role::User, "hi")], 64, &registry())
+        .await
+        .expect("a failed lookup doesn't fail the chat");
+
+    assert_eq!(reply.text, "No idea.");
+
+    let requests = requests.lock().await;
+    let [_, second] = requests.as_slice() else {
+        panic!("expected two requests, got {}", requests.len());
+    };
+
+    let results = tool_results(second);
+    let [(_, missing), (_, invalid)] = results.as_slice() else {
+        panic!("expected two tool results");
+    };
+    assert!(missing.contains("no tool called `test_missing`"));
+    assert!(invalid.contains("`name` is required"));
+}
+
+#[tokio::test]
+async fn the_model_answers_without_tools_once_its_rounds_run_out() {
+    let mut responses = vec![tool_calls(&[("test_count", "")]); MAX_TOOL_ROUNDS];
+    responses.push(answer("Enough."));
+
+    let (base_url, requests) =
+        spawn_recording_server(responses).await.expect("start mock server");
+
+    let client =
+        AiClient::new("test-key", &base_url, "test-model").expect("build client");
+    let reply = client
+        .chat_with_tools(vec![Message::new(Role::User, "hi")], 64, &registry())
+        .await
+        .expect("the final request is answered");
+
+    assert_eq!(reply.text, "Enough.");
+
+    let requests = requests.lock().await;
+    let Some((last, rounds)) = requests.split_last() else {
+        panic!("no requests were made");
+    };
+
+    assert_eq!(rounds.len(), MAX_TOOL_ROUNDS);
+    assert!(rounds.iter().all(|request| request.get("tools").is_some()));
+    assert!(last.get("tools").is_none());
+}
+
+#[tokio::test]
+async fn calls_past_the_per_round_cap_are_not_run() {
+    let (base_url, requests) = spawn_recording_server(vec![
+        tool_calls(&[
+            ("test_count", ""),
+            ("test_count", ""),
+            ("test_count", ""),
+            ("test_count", ""),
+        ]),
+        answer("Done."),
+    ])
+    .await
+    .expect("start mock server");
+
+    let client =
+        AiClient::new("test-key", &base_url, "test-model").expect("build client");
+    client
+        .chat_with_tools(vec![Message::new(Role::User, "hi")], 64, &registry())
+        .await
+        .expect("the model answers");
+
+    let requests = requests.lock().await;
+    let [_, second] = requests.as_slice() else {
+        panic!("expected two requests, got {}", requests.len());
+    };
+
+    // Every call is still answered, or the provider rejects the conversation.
+    let results = tool_results(second);
+    let [run @ .., (_, skipped)] = results.as_slice() else {
+        panic!("expected tool results");
+    };
+    assert_eq!(run.len(), 3);
+    assert!(run.iter().all(|(_, content)| content == "42"));
+    assert!(skipped.contains("not run"));
+}
+
+#[tokio::test]
+async fn no_tools_means_a_plain_chat() {
+    let (base_url, requests) = spawn_recording_server(vec![answer("Hello.")])
+        .await
+        .expect("start mock server");
+
+    let client =
+        AiClient::new("test-key", &base_url, "test-model").expect("build client");
+    let reply = client
+        .chat_with_tools(
+            vec![Message::new(Role::User, "hi")],
+            64,
+            &ToolRegistry::new(),
+        )
+        .await
+        .expect("plain chat");
+
+    assert_eq!(reply.text, "Hello.");
+
+    let requests = requests.lock().await;
+    let [request] = requests.as_slice() else {
+        panic!("expected one request, got {}", requests.len());
+    };
+    assert!(request.get("tools").is_none());
+}
+
+#[tokio::test(start_paused = true)]
+async fn a_slow_lookup_times_out() {
+    let mut tools = ToolRegistry::new();
+    tools.register(Slow);
+
+    let output = tools.call("test_slow", "").await;
+
+    assert_eq!(output, "Error: the lookup timed out.");
+}
+
+#[tokio::test]
+async fn a_second_tool_with_the_same_name_is_ignored() {
+    let mut tools = registry();
+    tools.register(Echo);
+
+    assert_eq!(tools.names(), ["test_echo", "test_count"]);
+}
@@ -0,0 +1,211 @@
+//! What each rule looks for in a message. Everything here is pure; the
+//! handler in `message_create.rs` feeds it the message and the author's
+//! recent history.
+
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+use regex::Regex;
+use tracing::warn;
+use zayden_app::config::{AutomodAction, AutomodRule, AutomodRules, WordFilter};
+use zayden_app::services::scam_domains::ScamDomains;
+
+use crate::history::MessageHistory;
+
+/// Hosts whose paths are server invites.
+const INVITE_PREFIXES: [&str; 4] =
+    ["discord.gg/", "discord.com/invite/", "discordapp.com/invite/", "dsc.gg/"];
+
+/// User, role and channel-less mentions: `<@id>`, `<@!id>` and `<@&id>`, plus
+/// `@everyone` and `@here`.
+#[must_use]
+pub fn mention_count(content: &str) -> usize {
+    let tagged = content
+        .match_indices("<@")
+        .filter(|(start, _)| {
+            let rest = content.get(start + 2..).unwrap_or_default();
+            let rest = rest.strip_prefix(['!', '&']).unwrap_or(rest);
+            let digits = rest.chars().take_while(char::is_ascii_digit).count();
+
+            digits > 0 && rest.get(digits..).is_some_and(|r| r.starts_with('>'))
+        })
+        .count();
+
+    tagged + content.matches("@everyone").count() + content.matches("@here").count()
+}
+
+#[must_use]
+pub fn has_invite(content: &str) -> bool {
+    let lower = content.to_lowercase();
+
+    INVITE_PREFIXES.iter().any(|prefix| {
+        lower.match_indices(prefix).any(|(start, _)| {
+            lower
+                .get(start + prefix.len()..)
+                .and_then(|code| code.chars().next())
+                .is_some_and(|c| c.is_ascii_alphanumeric())
+        })
+    })
+}
+
+/// The hosts of every `http://` and `https://` link, lowercased.
+#[must_use]
+pub fn link_hosts(content: &str) -> Vec<String> {
+    let lower = content.to_lowercase();
+
+    lower
+        .match_indices("http")
+        .filter_map(|(start, _)| {
+            let rest = lower.get(start..)?;
+            let rest = rest
+                .strip_prefix("https://")
+                .or_else(|| rest.strip_prefix("http://"))?;
+
+            let authority = rest
+                .split(|c: char| {
+                    c.is_whitespace()
+                        || matches!(c, '/' | '?' | '#' | '>' | ')' | ']')
+                })
+                .next()?;
+            let host = authority.rsplit('@').next()?;
+            let host = host.split(':').next()?.trim_end_matches('.');
+
+            (!host.is_empty()).then(|| host.to_string())
+        })
+        .collect()
+}
+
+/// The share of capitals among the message's letters, as a percentage, if
+/// it has at least `min_letters` of them.
+#[must_use]
+pub fn caps_percent(content: &str, min_letters: u32) -> Option<u32> {
+    let (letters, capitals) = content.chars().filter(|c| c.is_alphabetic()).fold(
+        (0_u32, 0_u32),
+        |(letters, capitals), c| {
+            (
+                letters.saturating_add(1),
+                capitals.saturating_add(u32::from(c.is_uppercase())),
+            )
+        },
+    );
+
+    (letters > 0 && letters >= min_letters)
+        .then(|| capitals.saturating_mul(100) / letters)
+}
+
+/// The message's words, lowercased and single-spaced, with a space either
+/// side so whole words and phrases can be found with `contains`.
+fn normalized_words(content: &str) -> String {
+    let words = content
+        .split(|c: char| !c.is_alphanumeric() && c != '\'')
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    format!(" {words} ")
+}
+
+/// Compiled filters, by pattern. A pattern that fails to compile is kept as
+/// `None` so it is only reported once.
+static COMPILED: LazyLock<Mutex<HashMap<String, Option<Arc<Regex>>>>> =
+    LazyLock::new(Mutex::default);
+
+/// Patterns kept compiled before the cache is cleared.
+const MAX_COMPILED: usize = 1_024;
+
+fn compiled(filter: &WordFilter) -> Option<Arc<Regex>> {
+    let Ok(mut cache) = COMPILED.lock() else {
+        return filter.compile().ok().map(Arc::new);
+    };
+
+    if let Some(regex) = cache.get(&filter.pattern) {
+        return regex.clone();
+    }
+
+    if cache.len() >= MAX_COMPILED {
+        cache.clear();
+    }
+
+    let regex = match filter.compile() {
+        Ok(regex) => Some(Arc::new(regex)),
+        Err(e) => {
+            warn!(pattern = %filter.pattern, error = %e, "automod filter does not compile");
+            None
+        },
+    };
+    cache.insert(filter.pattern.clone(), regex.clone());
+
+    regex
+}
+
+#[must_use]
+pub fn filter_matches(filter: &WordFilter, content: &str) -> bool {
+    if filter.regex {
+        return compiled(filter).is_some_and(|regex| regex.is_match(content));
+    }
+
+    let words = normalized_words(content);
+    filter.words().iter().any(|word| words.contains(&format!(" {word} ")))
+}
+
+/// The first rule `content` breaks, most serious first, and what it does.
+/// `history` already includes this message.
+#[must_use]
+pub fn check(
+    content: &str,
+    rules: &AutomodRules,
+    history: &MessageHistory,
+    now: Instant,
+    scam: &ScamDomains,
+) -> Option<(AutomodRule, AutomodAction)> {
+    if let Some(action) = rules.scam_links
+        && link_hosts(content).iter().any(|host| scam.contains(host))
+    {
+        return Some((AutomodRule::ScamLinks, action));
+    }
+
+    if let Some(action) = rules.invites
+        && has_invite(content)
+    {
+        return Some((AutomodRule::Invites, action));
+    }
+
+    if let Some(filter) =
+        rules.filters.iter().find(|filter| filter_matches(filter, content))
+    {
+        return Some((AutomodRule::Filter, filter.action));
+    }
+
+    if let Some(rule) = rules.mentions
+        && u32::try_from(mention_count(content)).unwrap_or(u32::MAX)
+            > rule.max_mentions
+    {
+        return Some((AutomodRule::Mentions, rule.action));
+    }
+
+    if let Some(rule) = rules.duplicates
+        && !content.trim().is_empty()
+        && history.repeats(now, Duration::from_secs(rule.per_seconds.into()))
+            > rule.max_messages
+    {
+        return Some((AutomodRule::Duplicates, rule.action));
+    }
+
+    if let Some(rule) = rules.rate
+        && history.count(now, Duration::from_secs(rule.per_seconds.into()))
+            > rule.max_messages
+    {
+        return Some((AutomodRule::Rate, rule.action));
+    }
+
+    if let Some(rule) = rules.caps
+        && caps_percent(content, rule.min_length)
+            .is_some_and(|percent| percent > rule.max_percent)
+    {
+        return Some((AutomodRule::Caps, rule.action));
+    }
+
+    None
+}
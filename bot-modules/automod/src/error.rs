@@ -0,0 +1,13 @@
+pub type Result<T> = std::result::Result<T, AutomodError>;
+
+#[derive(Debug, thiserror::Error)]
+pub enum AutomodError {
+    #[error(transparent)]
+    Discord(#[from] serenity::Error),
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error(transparent)]
+    Honeypot(#[from] honeypot::HoneypotError),
+    #[error("internal error: {0}")]
+    Internal(String),
+}
@@ -0,0 +1,80 @@
+//! Each member's recent messages, for the rate and duplicate rules.
+
+use std::collections::VecDeque;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::time::{Duration, Instant};
+
+use zayden_app::config::AutomodRules;
+
+/// Messages remembered per member. Past this, a member is well over any rate
+/// limit a guild can set.
+const MAX_ENTRIES: usize = 64;
+
+/// Identifies a message's text ignoring case and spacing, so trivially varied
+/// spam still counts as a duplicate.
+#[must_use]
+pub fn fingerprint(content: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+
+    for word in content.split_whitespace() {
+        word.to_lowercase().hash(&mut hasher);
+    }
+
+    hasher.finish()
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct MessageHistory {
+    entries: VecDeque<(Instant, u64)>,
+}
+
+impl MessageHistory {
+    /// Adds a message sent at `at`, forgetting anything older than the
+    /// longest window a rule can look back over.
+    pub fn record(&mut self, at: Instant, fingerprint: u64) {
+        let max_window =
+            Duration::from_secs(AutomodRules::MAX_WINDOW_SECONDS.into());
+
+        while self.entries.front().is_some_and(|(sent, _)| {
+            at.saturating_duration_since(*sent) > max_window
+        }) {
+            self.entries.pop_front();
+        }
+
+        if self.entries.len() >= MAX_ENTRIES {
+            self.entries.pop_front();
+        }
+
+        self.entries.push_back((at, fingerprint));
+    }
+
+    fn within(
+        &self,
+        now: Instant,
+        window: Duration,
+    ) -> impl Iterator<Item = &(Instant, u64)> {
+        self.entries
+            .iter()
+            .filter(move |(sent, _)| now.saturating_duration_since(*sent) <= window)
+    }
+
+    /// Messages in the last `window`.
+    #[must_use]
+    pub fn count(&self, now: Instant, window: Duration) -> u32 {
+        u32::try_from(self.within(now, window).count()).unwrap_or(u32::MAX)
+    }
+
+    /// Messages in the last `window` with the same text as the latest one,
+    /// the latest included.
+    #[must_use]
+    pub fn repeats(&self, now: Instant, window: Duration) -> u32 {
+        let Some((_, latest)) = self.entries.back() else {
+            return 0;
+        };
+
+        u32::try_from(
+            self.within(now, window).filter(|(_, print)| print == latest).count(),
+        )
+        .unwrap_or(u32::MAX)
+    }
+}
@@ -0,0 +1,7 @@
+pub mod checks;
+pub mod error;
+pub mod history;
+pub mod message_create;
+
+pub use error::{AutomodError, Result};
+pub use message_create::{AutomodHit, message_create, report};
@@ -0,0 +1,190 @@
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+use honeypot::guard::GUARD;
+use honeypot::{ExemptionPolicy, is_exempt};
+use jiff::SignedDuration;
+use moka::sync::Cache;
+use serenity::all::{
+    ChannelId,
+    Context,
+    CreateMessage,
+    GuildId,
+    Http,
+    Mentionable,
+    Message,
+    Timestamp,
+    UserId,
+};
+use tracing::{debug, warn};
+use zayden_app::config::{AutomodAction, AutomodRule, AutomodSettingsRow};
+use zayden_app::services::scam_domains::ScamDomains;
+use zayden_core::as_u64;
+
+use crate::checks::check;
+use crate::error::{AutomodError, Result};
+use crate::history::{MessageHistory, fingerprint};
+
+/// Members quiet this long have their history dropped.
+const HISTORY_IDLE: Duration = Duration::from_mins(2);
+/// How long after a warning or timeout further hits only delete, so one
+/// flood is one infraction rather than dozens.
+const ESCALATION_COOLDOWN: Duration = Duration::from_mins(1);
+
+static HISTORY: LazyLock<Cache<(GuildId, UserId), Arc<Mutex<MessageHistory>>>> =
+    LazyLock::new(|| {
+        Cache::builder().time_to_idle(HISTORY_IDLE).max_capacity(16_384).build()
+    });
+
+static ESCALATED: LazyLock<Cache<(GuildId, UserId), ()>> = LazyLock::new(|| {
+    Cache::builder().time_to_live(ESCALATION_COOLDOWN).max_capacity(4_096).build()
+});
+
+#[derive(Debug, Clone)]
+pub struct AutomodHit {
+    pub user_id: UserId,
+    pub username: String,
+    pub guild_id: GuildId,
+    pub channel_id: ChannelId,
+    pub rule: AutomodRule,
+    /// What was done, after the escalation cooldown.
+    pub action: AutomodAction,
+    /// How long the member was timed out for, if they were.
+    pub timeout: Option<SignedDuration>,
+}
+
+fn reason(rule: AutomodRule) -> String {
+    format!("Automod: {}", rule.label())
+}
+
+/// Runs the guild's rules against `msg`, deleting it and acting on its
+/// author if one catches it.
+pub async fn message_create(
+    ctx: &Context,
+    msg: &Message,
+    settings: &AutomodSettingsRow,
+    scam_domains: &ScamDomains,
+) -> Result<Option<AutomodHit>> {
+    let Some(guild_id) = msg.guild_id else {
+        return Ok(None);
+    };
+
+    if !settings.enabled || msg.author.bot() {
+        return Ok(None);
+    }
+
+    let author_id = msg.author.id;
+    let now = Instant::now();
+
+    let history = HISTORY.get_with((guild_id, author_id), Arc::default);
+    let caught = {
+        let Ok(mut history) = history.lock() else {
+            return Err(AutomodError::Internal(
+                "message history poisoned".to_string(),
+            ));
+        };
+        history.record(now, fingerprint(&msg.content));
+
+        check(&msg.content, &settings.rules, &history, now, scam_domains)
+    };
+
+    let Some((rule, action)) = caught else {
+        return Ok(None);
+    };
+
+    let facts = GUARD.facts(ctx, guild_id).await?;
+    let member_roles: &[_] = msg.member.as_ref().map_or(&[], |member| &member.roles);
+
+    if is_exempt(author_id, member_roles, &facts, &ExemptionPolicy::from(settings)) {
+        debug!(%guild_id, %author_id, rule = rule.label(), "automod spared an exempt member");
+        return Ok(None);
+    }
+
+    let reason = reason(rule);
+    msg.delete(&ctx.http, Some(&reason)).await?;
+
+    let action = if action == AutomodAction::Delete
+        || ESCALATED.entry((guild_id, author_id)).or_insert(()).is_fresh()
+    {
+        action
+    } else {
+        AutomodAction::Delete
+    };
+
+    let timeout = (action == AutomodAction::Timeout).then(|| {
+        SignedDuration::from_mins(i64::from(
+            settings
+                .timeout_minutes
+                .clamp(1, AutomodSettingsRow::MAX_TIMEOUT_MINUTES),
+        ))
+    });
+
+    if let Some(timeout) = timeout {
+        let mut member = guild_id.member(&ctx.http, author_id).await?;
+
+        let until = Timestamp::from_unix_timestamp(
+            jiff::Timestamp::now().as_second().saturating_add(timeout.as_secs()),
+        )
+        .map_err(|e| AutomodError::Internal(e.to_string()))?;
+        member.disable_communication_until(&ctx.http, until).await?;
+    }
+
+    let channel_id = msg.channel_id.expect_channel();
+
+    warn!(
+        %guild_id,
+        %author_id,
+        username = %msg.author.name,
+        %channel_id,
+        rule = rule.label(),
+        action = action.as_str(),
+        "automod caught a message",
+    );
+
+    Ok(Some(AutomodHit {
+        user_id: author_id,
+        username: msg.author.name.to_string(),
+        guild_id,
+        channel_id,
+        rule,
+        action,
+        timeout,
+    }))
+}
+
+/// Posts the hit to the guild's log channel, if it has one.
+pub async fn report(
+    http: &Http,
+    settings: &AutomodSettingsRow,
+    hit: &AutomodHit,
+) -> Result<()> {
+    let Some(channel) = settings.log_channel_id else {
+        return Ok(());
+    };
+
+    let outcome = match hit.action {
+        AutomodAction::Delete => "deleted".to_string(),
+        AutomodAction::Warn => "deleted and warned them".to_string(),
+        AutomodAction::Timeout => {
+            format!(
+                "deleted and timed them out for {} minutes",
+                settings.timeout_minutes
+            )
+        },
+    };
+
+    ChannelId::new(as_u64(channel))
+        .send_message(
+            http,
+            CreateMessage::new().content(format!(
+                "Automod: a message from {} ({}) in {} broke the {} rule; {outcome}.",
+                hit.user_id.mention(),
+                hit.user_id,
+                hit.channel_id.mention(),
+                hit.rule.label(),
+            )),
+        )
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,280 @@
+//! What each automod rule catches, and the order they are tried in.
+//!
+//! Deleting and timing out need Discord; `check` is where the handler decides
+//! which rule, if any, a message broke, so that is what is pinned here.
+
+use std::time::{Duration, Instant};
+
+use automod::checks::{
+    caps_percent,
+    check,
+    filter_matches,
+    has_invite,
+    link_hosts,
+    mention_count,
+};
+use automod::history::{MessageHistory, fingerprint};
+use zayden_app::config::{
+    AutomodAction,
+    AutomodRule,
+    AutomodRules,
+    CapsRule,
+    MentionRule,
+    RateRule,
+    WordFilter,
+};
+use zayden_app::services::scam_domains::ScamDomains;
+
+fn scam() -> ScamDomains {
+    ScamDomains::from_content("steamcommunity-gift.ru\ndiscord-nitro.gift\n")
+}
+
+fn words(pattern: &str) -> WordFilter {
+    WordFilter {
+        pattern: pattern.to_string(),
+        regex: false,
+        action: AutomodAction::Delete,
+    }
+}
+
+fn regex(pattern: &str) -> WordFilter {
+    WordFilter { regex: true, ..words(pattern) }
+}
+
+/// `history` with `content` recorded once, as the handler does before
+/// checking it.
+fn single(content: &str, now: Instant) -> MessageHistory {
+    let mut history = MessageHistory::default();
+    history.record(now, fingerprint(content));
+    history
+}
+
+#[test]
+fn mentions_count_users_roles_and_everyone() {
+    assert_eq!(mention_count("<@1> <@!2> <@&3> @everyone @here"), 5);
+}
+
+#[test]
+fn mentions_ignore_malformed_tags() {
+    assert_eq!(mention_count("<@> <@abc> <@12 email@example.com"), 0);
+}
+
+#[test]
+fn invites_need_a_code() {
+    assert!(has_invite("join us at https://discord.gg/abc123"));
+    assert!(has_invite("DISCORD.COM/INVITE/xyz"));
+    assert!(!has_invite("discord.gg/ is the invite host"));
+    assert!(!has_invite("discord.com/channels/1/2"));
+}
+
+#[test]
+fn link_hosts_strip_paths_ports_and_credentials() {
+    assert_eq!(
+        link_hosts(
+            "see https://User@Login.Example.com:8080/path?q=1 and http://a.b."
+        ),
+        vec!["login.example.com".to_string(), "a.b".to_string()],
+    );
+}
+
+#[test]
+fn link_hosts_skip_text_without_a_scheme() {
+    assert!(link_hosts("httpd is a server, example.com is not a link").is_empty());
+}
+
+#[test]
+fn caps_percent_needs_enough_letters() {
+    assert_eq!(caps_percent("HELLO world", 10), Some(50));
+    assert_eq!(caps_percent("HI", 10), None);
+    assert_eq!(caps_percent("1234 !!", 0), None);
+}
+
+#[test]
+fn word_lists_match_whole_words_ignoring_case() {
+    let filter = words("bad, very bad phrase");
+
+    assert!(filter_matches(&filter, "That is BAD."));
+    assert!(filter_matches(&filter, "a very  bad\nphrase indeed"));
+    assert!(!filter_matches(&filter, "badger"));
+}
+
+#[test]
+fn regex_filters_ignore_case() {
+    assert!(filter_matches(&regex(r"fr[e3]{2}\s*nitro"), "FREE NITRO here"));
+    assert!(!filter_matches(&regex(r"fr[e3]{2}\s*nitro"), "nitro is free"));
+}
+
+#[test]
+fn invalid_regex_filters_match_nothing() {
+    assert!(!filter_matches(&regex("(unclosed"), "(unclosed"));
+}
+
+#[test]
+fn no_rules_catch_nothing() {
+    let now = Instant::now();
+    let content = "@everyone https://discord.gg/abc";
+
+    assert_eq!(
+        check(
+            content,
+            &AutomodRules::default(),
+            &single(content, now),
+            now,
+            &scam()
+        ),
+        None,
+    );
+}
+
+#[test]
+fn scam_links_are_caught_on_subdomains() {
+    let now = Instant::now();
+    let content = "free skins https://login.steamcommunity-gift.ru/claim";
+    let rules = AutomodRules {
+        scam_links: Some(AutomodAction::Timeout),
+        ..AutomodRules::default()
+    };
+
+    assert_eq!(
+        check(content, &rules, &single(content, now), now, &scam()),
+        Some((AutomodRule::ScamLinks, AutomodAction::Timeout)),
+    );
+}
+
+#[test]
+fn scam_links_outrank_invites_and_mentions() {
+    let now = Instant::now();
+    let content = "@everyone https://discord-nitro.gift https://discord.gg/abc";
+    let rules = AutomodRules {
+        scam_links: Some(AutomodAction::Warn),
+        invites: Some(AutomodAction::Delete),
+        mentions: Some(MentionRule {
+            max_mentions: 0,
+            action: AutomodAction::Delete,
+        }),
+        ..AutomodRules::default()
+    };
+
+    assert_eq!(
+        check(content, &rules, &single(content, now), now, &scam()),
+        Some((AutomodRule::ScamLinks, AutomodAction::Warn)),
+    );
+}
+
+#[test]
+fn filters_use_their_own_action() {
+    let now = Instant::now();
+    let content = "buy cheap followers";
+    let rules = AutomodRules {
+        filters: vec![words("unrelated"), WordFilter {
+            action: AutomodAction::Warn,
+            ..words("cheap followers")
+        }],
+        ..AutomodRules::default()
+    };
+
+    assert_eq!(
+        check(content, &rules, &single(content, now), now, &scam()),
+        Some((AutomodRule::Filter, AutomodAction::Warn)),
+    );
+}
+
+#[test]
+fn mentions_at_the_limit_pass() {
+    let now = Instant::now();
+    let rules = AutomodRules {
+        mentions: Some(MentionRule {
+            max_mentions: 2,
+            action: AutomodAction::Delete,
+        }),
+        ..AutomodRules::default()
+    };
+
+    let at_limit = "<@1> <@2>";
+    assert_eq!(check(at_limit, &rules, &single(at_limit, now), now, &scam()), None);
+
+    let over = "<@1> <@2> <@3>";
+    assert_eq!(
+        check(over, &rules, &single(over, now), now, &scam()),
+        Some((AutomodRule::Mentions, AutomodAction::Delete)),
+    );
+}
+
+#[test]
+fn repeats_past_the_limit_are_duplicates_before_rate() {
+    let start = Instant::now();
+    let rules = AutomodRules {
+        rate: Some(RateRule {
+            max_messages: 2,
+            per_seconds: 10,
+            action: AutomodAction::Delete,
+        }),
+        duplicates: Some(RateRule {
+            max_messages: 2,
+            per_seconds: 10,
+            action: AutomodAction::Warn,
+        }),
+        ..AutomodRules::default()
+    };
+
+    let mut history = MessageHistory::default();
+    let mut caught = None;
+    for second in 0..3 {
+        let now = start + Duration::from_secs(second);
+        history.record(now, fingerprint("spam"));
+        caught = check("spam", &rules, &history, now, &scam());
+    }
+
+    assert_eq!(caught, Some((AutomodRule::Duplicates, AutomodAction::Warn)));
+}
+
+#[test]
+fn varied_messages_past_the_limit_break_the_rate_rule() {
+    let start = Instant::now();
+    let rules = AutomodRules {
+        rate: Some(RateRule {
+            max_messages: 2,
+            per_seconds: 10,
+            action: AutomodAction::Delete,
+        }),
+        duplicates: Some(AutomodRules::DEFAULT_DUPLICATES),
+        ..AutomodRules::default()
+    };
+
+    let mut history = MessageHistory::default();
+    let mut caught = Vec::new();
+    for (second, content) in ["one", "two", "three"].into_iter().enumerate() {
+        let now =
+            start + Duration::from_secs(u64::try_from(second).unwrap_or_default());
+        history.record(now, fingerprint(content));
+        caught.push(check(content, &rules, &history, now, &scam()));
+    }
+
+    assert_eq!(caught, vec![
+        None,
+        None,
+        Some((AutomodRule::Rate, AutomodAction::Delete))
+    ],);
+}
+
+#[test]
+fn shouting_is_caught_last() {
+    let now = Instant::now();
+    let content = "THIS IS ALL CAPS";
+    let rules = AutomodRules {
+        caps: Some(CapsRule {
+            action: AutomodAction::Delete,
+            ..AutomodRules::DEFAULT_CAPS
+        }),
+        ..AutomodRules::default()
+    };
+
+    assert_eq!(
+        check(content, &rules, &single(content, now), now, &scam()),
+        Some((AutomodRule::Caps, AutomodAction::Delete)),
+    );
+    assert_eq!(
+        check("Normal Case", &rules, &single(content, now), now, &scam()),
+        None
+    );
+}
@@ -0,0 +1,58 @@
+//! The per-member message history the rate and duplicate rules count over.
+
+use std::time::{Duration, Instant};
+
+use automod::history::{MessageHistory, fingerprint};
+
+#[test]
+fn fingerprints_ignore_case_and_spacing() {
+    assert_eq!(fingerprint("Buy  NOW\n"), fingerprint("buy now"));
+    assert_ne!(fingerprint("buy now"), fingerprint("buy later"));
+}
+
+#[test]
+fn counts_only_messages_inside_the_window() {
+    let start = Instant::now();
+    let mut history = MessageHistory::default();
+
+    for second in [0, 5, 9] {
+        history.record(start + Duration::from_secs(second), fingerprint("hi"));
+    }
+
+    let now = start + Duration::from_secs(9);
+    assert_eq!(history.count(now, Duration::from_secs(5)), 2);
+    assert_eq!(history.count(now, Duration::from_secs(30)), 3);
+}
+
+#[test]
+fn repeats_count_matches_of_the_latest_message() {
+    let start = Instant::now();
+    let mut history = MessageHistory::default();
+
+    for (second, content) in [(0, "spam"), (1, "hello"), (2, "SPAM")] {
+        history.record(start + Duration::from_secs(second), fingerprint(content));
+    }
+
+    let now = start + Duration::from_secs(2);
+    assert_eq!(history.repeats(now, Duration::from_secs(10)), 2);
+    assert_eq!(history.count(now, Duration::from_secs(10)), 3);
+}
+
+#[test]
+fn empty_history_has_no_repeats() {
+    let history = MessageHistory::default();
+
+    assert_eq!(history.repeats(Instant::now(), Duration::from_secs(10)), 0);
+}
+
+#[test]
+fn forgets_messages_older_than_the_longest_window() {
+    let start = Instant::now();
+    let mut history = MessageHistory::default();
+
+    history.record(start, fingerprint("old"));
+    let now = start + Duration::from_secs(120);
+    history.record(now, fingerprint("new"));
+
+    assert_eq!(history.count(now, Duration::from_secs(3600)), 1);
+}
@@ -6,5 +6,6 @@ pub mod error;
 pub mod loadouts;
 pub mod raid_guides;
 pub mod slash_commands;
+pub mod tools;
 pub use endgame_analysis::EndgameAnalysisError;
 pub use error::{DestinyError, Result};
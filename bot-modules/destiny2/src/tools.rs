@@ -0,0 +1,63 @@
+//! Perk lookups for the AI chat, from the stored compendium.
+
+use std::fmt::Write;
+
+use async_trait::async_trait;
+use sqlx::PgPool;
+use zayden_core::{AiTool, ToolArgs, ToolError, ToolParam};
+
+use crate::db::compendium as perk_db;
+
+/// Most close matches listed when there is no exact one.
+const MAX_MATCHES: usize = 5;
+
+pub struct PerkLookup {
+    pool: PgPool,
+}
+
+impl PerkLookup {
+    #[must_use]
+    pub const fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl AiTool for PerkLookup {
+    fn name(&self) -> &'static str {
+        "destiny2_perk"
+    }
+
+    fn description(&self) -> &'static str {
+        "Look up what a Destiny 2 weapon or armor perk does, by name."
+    }
+
+    fn params(&self) -> &'static [ToolParam] {
+        const PARAMS: &[ToolParam] =
+            &[ToolParam::required("name", "The perk's name, e.g. Kill Clip.")];
+        PARAMS
+    }
+
+    async fn call(&self, args: &ToolArgs) -> Result<String, ToolError> {
+        let query = args.required("name")?.to_lowercase();
+
+        if let Some(perk) =
+            perk_db::find(&self.pool, &query).await.map_err(ToolError::failed)?
+        {
+            return Ok(format!("{}: {}", perk.name, perk.description));
+        }
+
+        let perks =
+            perk_db::search(&self.pool, &query).await.map_err(ToolError::failed)?;
+        if perks.is_empty() {
+            return Err(ToolError::NotFound(format!("perk `{query}`")));
+        }
+
+        let mut out = String::from("No exact match. Closest perks:");
+        for perk in perks.iter().take(MAX_MATCHES) {
+            let _ = write!(out, "\n{}: {}", perk.name, perk.description);
+        }
+
+        Ok(out)
+    }
+}
@@ -0,0 +1,18 @@
+SELECT
+    user_id,
+    value
+FROM
+    gambling_season_archive
+WHERE
+    season_id = $1
+    AND leaderboard = $2
+    AND (
+        ($3 IS TRUE)
+        OR (user_id = ANY($4))
+    )
+ORDER BY
+    placement
+LIMIT
+    $5
+OFFSET
+    $6
@@ -0,0 +1,24 @@
+WITH
+numbered_users AS (
+    SELECT
+        user_id,
+        ROW_NUMBER() OVER (
+            ORDER BY
+                placement
+        ) AS rn
+    FROM
+        gambling_season_archive
+    WHERE
+        season_id = $1
+        AND leaderboard = $2
+        AND (
+            ($3 IS TRUE)
+            OR (user_id = ANY($4))
+        )
+)
+
+SELECT rn
+FROM
+    numbered_users
+WHERE
+    user_id = $5
@@ -0,0 +1,27 @@
+SELECT
+    s.user_id,
+    CASE
+        WHEN $1 = 'season_higherlower' THEN s.higher_or_lower_score::BIGINT
+        ELSE s.coins
+    END AS "value!"
+FROM
+    gambling_season_stats s
+JOIN
+    gambling_seasons z
+    ON s.season_id = z.id
+WHERE
+    z.archived_at IS NULL
+    AND now() >= z.starts_at
+    AND now() < z.ends_at
+    AND (
+        ($2 IS TRUE)
+        OR (s.user_id = ANY($3))
+    )
+    AND ($1 <> 'season_higherlower' OR s.higher_or_lower_score > 0)
+ORDER BY
+    2 DESC,
+    s.user_id
+LIMIT
+    $4
+OFFSET
+    $5
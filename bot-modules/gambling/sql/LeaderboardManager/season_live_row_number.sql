@@ -0,0 +1,33 @@
+WITH
+numbered_users AS (
+    SELECT
+        s.user_id,
+        ROW_NUMBER() OVER (
+            ORDER BY
+                CASE
+                    WHEN $1 = 'season_higherlower' THEN s.higher_or_lower_score::BIGINT
+                    ELSE s.coins
+                END DESC,
+                s.user_id
+        ) AS rn
+    FROM
+        gambling_season_stats s
+    JOIN
+        gambling_seasons z
+        ON s.season_id = z.id
+    WHERE
+        z.archived_at IS NULL
+        AND now() >= z.starts_at
+        AND now() < z.ends_at
+        AND (
+            ($2 IS TRUE)
+            OR (s.user_id = ANY($3))
+        )
+        AND ($1 <> 'season_higherlower' OR s.higher_or_lower_score > 0)
+)
+
+SELECT rn
+FROM
+    numbered_users
+WHERE
+    user_id = $4
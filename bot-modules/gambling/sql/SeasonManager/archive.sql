@@ -0,0 +1,33 @@
+INSERT INTO
+    gambling_season_archive (season_id, leaderboard, placement, user_id, value)
+SELECT
+    season_id,
+    'season_coins',
+    ROW_NUMBER() OVER (
+        ORDER BY
+            coins DESC,
+            user_id
+    ),
+    user_id,
+    coins
+FROM
+    gambling_season_stats
+WHERE
+    season_id = $1
+UNION ALL
+SELECT
+    season_id,
+    'season_higherlower',
+    ROW_NUMBER() OVER (
+        ORDER BY
+            higher_or_lower_score DESC,
+            user_id
+    ),
+    user_id,
+    higher_or_lower_score
+FROM
+    gambling_season_stats
+WHERE
+    season_id = $1
+    AND higher_or_lower_score > 0
+ON CONFLICT DO NOTHING;
@@ -0,0 +1,20 @@
+SELECT
+    a.user_id,
+    a.leaderboard,
+    a.placement,
+    r.coins,
+    r.gems,
+    r.item_id,
+    r.title
+FROM
+    gambling_season_rewards r
+JOIN
+    gambling_season_archive a
+    ON a.season_id = r.season_id
+    AND a.leaderboard = r.leaderboard
+    AND a.placement BETWEEN r.placement_min AND r.placement_max
+WHERE
+    r.season_id = $1
+ORDER BY
+    a.leaderboard,
+    a.placement;
@@ -0,0 +1,164 @@
+use levels::CardManager;
+use serenity::all::{
+    CommandInteraction,
+    CommandOptionType,
+    Context,
+    CreateCommand,
+    CreateCommandOption,
+    CreateEmbed,
+    EditInteractionResponse,
+    ResolvedOption,
+    ResolvedValue,
+};
+use sqlx::PgPool;
+use zayden_core::{parse_options, parse_subcommand};
+use zayden_graphics::card::{CARD_BACKGROUNDS, DEFAULT_BACKGROUND, background};
+
+use super::Commands;
+use crate::commands::inventory::InventoryManager;
+use crate::shop::CARD_ITEM_PREFIX;
+use crate::{GamblingError, Result};
+
+/// Parses `#5865F2`, `5865f2` or `#fff` into a 24-bit RGB value.
+#[must_use]
+pub fn parse_colour(value: &str) -> Option<u32> {
+    let hex = value.trim().trim_start_matches('#');
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    let expanded = match hex.len() {
+        3 => hex.chars().flat_map(|c| [c, c]).collect(),
+        6 => hex.to_string(),
+        _ => return None,
+    };
+
+    u32::from_str_radix(&expanded, 16).ok()
+}
+
+impl Commands {
+    pub async fn card(
+        ctx: &Context,
+        interaction: &CommandInteraction,
+        options: Vec<ResolvedOption<'_>>,
+        pool: &PgPool,
+    ) -> Result<()> {
+        interaction.defer_ephemeral(&ctx.http).await?;
+
+        let (name, options) = parse_subcommand(options)?;
+        let mut options = parse_options(options);
+
+        let content = match name {
+            "background" => {
+                let Some(ResolvedValue::String(id)) = options.remove("background")
+                else {
+                    return Err(GamblingError::internal("card background missing"));
+                };
+
+                let chosen = background(id);
+
+                if chosen != DEFAULT_BACKGROUND {
+                    let item_id = format!("{CARD_ITEM_PREFIX}{}", chosen.id);
+                    let owned =
+                        InventoryManager::inventory_items(pool, interaction.user.id)
+                            .await?
+                            .0
+                            .iter()
+                            .any(|item| {
+                                item.item_id == item_id && item.quantity > 0
+                            });
+
+                    if !owned {
+                        return Err(GamblingError::BackgroundLocked(chosen.name));
+                    }
+                }
+
+                CardManager::set_background(pool, interaction.user.id, chosen.id)
+                    .await?;
+
+                format!("Your card background is now **{}**.", chosen.name)
+            },
+            "accent" => {
+                let Some(ResolvedValue::String(value)) = options.remove("colour")
+                else {
+                    return Err(GamblingError::internal("card colour missing"));
+                };
+
+                let accent = parse_colour(value).ok_or_else(|| {
+                    GamblingError::InvalidColour(value.to_string())
+                })?;
+
+                CardManager::set_accent(pool, interaction.user.id, accent).await?;
+
+                format!("Your card accent is now `#{accent:06X}`.")
+            },
+            "reset" => {
+                CardManager::reset(pool, interaction.user.id).await?;
+
+                String::from("Your card is back to the default look.")
+            },
+            _ => return Err(GamblingError::internal("unknown card subcommand")),
+        };
+
+        let settings = CardManager::settings(pool, interaction.user.id).await?;
+
+        let embed = CreateEmbed::new()
+            .title("Profile Card")
+            .description(content)
+            .colour(settings.theme().accent);
+
+        interaction
+            .edit_response(&ctx.http, EditInteractionResponse::new().embed(embed))
+            .await?;
+
+        Ok(())
+    }
+
+    pub fn register_card<'a>() -> CreateCommand<'a> {
+        let mut background_opt = CreateCommandOption::new(
+            CommandOptionType::String,
+            "background",
+            "The background to use",
+        )
+        .required(true);
+
+        for background in CARD_BACKGROUNDS {
+            background_opt =
+                background_opt.add_string_choice(background.name, background.id);
+        }
+
+        let background = CreateCommandOption::new(
+            CommandOptionType::SubCommand,
+            "background",
+            "Change the background of your /rank and /profile cards",
+        )
+        .add_sub_option(background_opt);
+
+        let accent = CreateCommandOption::new(
+            CommandOptionType::SubCommand,
+            "accent",
+            "Change the accent colour of your /rank and /profile cards",
+        )
+        .add_sub_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "colour",
+                "A hex colour, e.g. #5865F2",
+            )
+            .max_length(7)
+            .required(true),
+        );
+
+        let reset = CreateCommandOption::new(
+            CommandOptionType::SubCommand,
+            "reset",
+            "Reset your card to the default look",
+        );
+
+        CreateCommand::new("card")
+            .description("Customise your /rank and /profile cards")
+            .add_option(background)
+            .add_option(accent)
+            .add_option(reset)
+    }
+}
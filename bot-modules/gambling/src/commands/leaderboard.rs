@@ -8,6 +8,7 @@ use serenity::all::{
     CreateCommand,
     CreateCommandOption,
     CreateEmbed,
+    CreateEmbedAuthor,
     CreateEmbedFooter,
     EditInteractionResponse,
     ResolvedOption,
@@ -19,7 +20,8 @@ use zayden_core::cache::GuildMembersCache;
 use zayden_core::{EmojiCacheData, as_i64, parse_options};
 
 use super::Commands;
-use crate::common::leaderboard::{get_row_number, get_rows};
+use crate::common::leaderboard::{get_row_number, get_rows, is_seasonal};
+use crate::season::{SEASON_COINS, SEASON_HIGHERLOWER, SeasonManager};
 use crate::shop::{EGGPLANT, LOTTO_TICKET};
 use crate::{GamblingError, Result};
 
@@ -44,6 +46,37 @@ impl Commands {
             _ => false,
         };
 
+        let season = match options.remove("season") {
+            Some(ResolvedValue::Integer(season)) => Some(
+                i32::try_from(season).map_err(|_e| GamblingError::InvalidAmount)?,
+            ),
+            _ => None,
+        };
+
+        let season_row = if let Some(season_id) = season {
+            if !is_seasonal(leaderboard) {
+                return Err(GamblingError::NotSeasonal);
+            }
+
+            let row = SeasonManager::get(pool, season_id)
+                .await?
+                .ok_or(GamblingError::SeasonNotFound(season_id))?;
+
+            if row.archived_at.is_none() {
+                return Err(GamblingError::SeasonNotArchived(season_id));
+            }
+
+            Some(row)
+        } else if is_seasonal(leaderboard) {
+            Some(
+                SeasonManager::active(pool)
+                    .await?
+                    .ok_or(GamblingError::NoActiveSeason)?,
+            )
+        } else {
+            None
+        };
+
         let users = if global {
             None
         } else {
@@ -69,7 +102,7 @@ impl Commands {
             Some(users)
         };
 
-        let rows = get_rows(leaderboard, pool, users.as_deref(), 1).await?;
+        let rows = get_rows(leaderboard, pool, season, users.as_deref(), 1).await?;
 
         let emojis = {
             let data_lock = ctx.data::<RwLock<Data>>();
@@ -84,24 +117,39 @@ impl Commands {
             .collect::<Result<Vec<_>>>()?
             .join("\n\n");
 
-        let embed = CreateEmbed::new()
+        let key = season.map_or_else(
+            || leaderboard.to_string(),
+            |season_id| format!("{leaderboard}, season {season_id}"),
+        );
+
+        let mut embed = CreateEmbed::new()
             .title(format!(
-                "🏁 {}Leaderboard ({leaderboard})",
+                "🏁 {}Leaderboard ({key})",
                 if global { "Global " } else { "" }
             ))
             .description(desc)
             .footer(CreateEmbedFooter::new("Page 1"))
             .colour(Colour::TEAL);
 
+        if let Some(row) = season_row {
+            embed = embed.author(CreateEmbedAuthor::new(row.name));
+        }
+
         let mut response = EditInteractionResponse::new().embed(embed).button(
             CreateButton::new("leaderboard_previous")
                 .label("<")
                 .style(ButtonStyle::Secondary),
         );
 
-        if get_row_number(leaderboard, pool, users.as_deref(), interaction.user.id)
-            .await?
-            .is_some()
+        if get_row_number(
+            leaderboard,
+            pool,
+            season,
+            users.as_deref(),
+            interaction.user.id,
+        )
+        .await?
+        .is_some()
         {
             response = response.button(
                 CreateButton::new("leaderboard_user")
@@ -139,12 +187,22 @@ impl Commands {
                 .add_string_choice(EGGPLANT.name, "eggplants")
                 .add_string_choice(LOTTO_TICKET.name, "lottotickets")
                 .add_string_choice("Higher or Lower", "higherlower")
-                .add_string_choice("Weekly Higher or Lower", "weekly_higherlower"),
+                .add_string_choice("Weekly Higher or Lower", "weekly_higherlower")
+                .add_string_choice("Season Coins", SEASON_COINS)
+                .add_string_choice("Season Higher or Lower", SEASON_HIGHERLOWER),
             )
             .add_option(CreateCommandOption::new(
                 CommandOptionType::Boolean,
                 "global",
                 "Whether to show global scores",
             ))
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::Integer,
+                    "season",
+                    "Browse the final standings of a past season",
+                )
+                .min_int_value(1),
+            )
     }
 }
@@ -1,4 +1,5 @@
 pub mod blackjack;
+pub mod card;
 pub mod coinflip;
 pub mod craft;
 pub mod daily;
@@ -14,6 +15,7 @@ pub mod prestige;
 pub mod profile;
 pub mod roll;
 pub mod rps;
+pub mod season;
 pub mod send;
 pub mod shop;
 pub mod tictactoe;
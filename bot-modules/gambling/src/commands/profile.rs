@@ -1,12 +1,14 @@
 use std::fmt::Write as _;
 
 use jiff::Timestamp;
-use levels::{LevelsRow, level_up_xp};
+use levels::card::CARD_FILENAME;
+use levels::{CardKind, CardManager, LevelsRow, RankRow, level_up_xp, render_card};
 use serenity::all::{
     Colour,
     CommandInteraction,
     CommandOptionType,
     Context,
+    CreateAttachment,
     CreateCommand,
     CreateCommandOption,
     CreateEmbed,
@@ -17,7 +19,9 @@ use serenity::all::{
 };
 use sqlx::PgPool;
 use tokio::sync::RwLock;
+use tracing::warn;
 use zayden_core::{EmojiCache, EmojiCacheData, FormatNum, as_i64};
+use zayden_graphics::{CardData, CardStat};
 
 use super::Commands;
 use crate::commands::inventory::InventoryManager;
@@ -29,6 +33,7 @@ use crate::{
     MaxBet,
     Prestige,
     Result,
+    SeasonManager,
     ShopItem,
 };
 
@@ -64,10 +69,56 @@ impl ProfileRow {
         .await
     }
 
+    #[must_use]
+    pub fn card(&self, name: String, rank: Option<i64>) -> CardData {
+        let level = LevelsRow::level(self);
+
+        CardData {
+            name,
+            subtitle: String::from("Gambling profile"),
+            level,
+            xp: self.xp(),
+            xp_needed: level_up_xp(level),
+            rank,
+            stats: vec![
+                CardStat::new("Coins", self.coins.format()),
+                CardStat::new("Gems", self.gems.format()),
+                CardStat::new("Prestige", self.prestige().format()),
+            ],
+        }
+    }
+
     pub fn into_embed<'a>(
         self,
         inventory: &GamblingItems,
         emojis: &EmojiCache,
+    ) -> Result<CreateEmbed<'a>> {
+        let coin = emojis.emoji("heads").map_err(|n| {
+            GamblingError::Internal(format!("emoji '{n}' not in cache"))
+        })?;
+
+        let embed = CreateEmbed::new()
+            .field(format!("Coins <:coin:{coin}>"), self.coins_str(), false)
+            .field("Gems 💎", self.gems_str(), false)
+            .field(
+                format!("Level {}", LevelsRow::level(&self).format()),
+                format!(
+                    "{} / {} xp",
+                    self.xp().format(),
+                    level_up_xp(LevelsRow::level(&self)).format()
+                ),
+                false,
+            );
+
+        self.with_details(embed, inventory, emojis)
+    }
+
+    /// The fields the rendered card doesn't cover.
+    pub fn with_details<'a>(
+        &self,
+        embed: CreateEmbed<'a>,
+        inventory: &GamblingItems,
+        emojis: &EmojiCache,
     ) -> Result<CreateEmbed<'a>> {
         let mut betting_max = self.max_bet_str();
         if self.prestige() != 0 {
@@ -94,27 +145,10 @@ impl ProfileRow {
             lines.join("\n")
         };
 
-        let coin = emojis.emoji("heads").map_err(|n| {
-            GamblingError::Internal(format!("emoji '{n}' not in cache"))
-        })?;
-
-        let embed = CreateEmbed::new()
-            .field(format!("Coins <:coin:{coin}>"), self.coins_str(), false)
-            .field("Gems 💎", self.gems_str(), false)
-            .field(
-                format!("Level {}", LevelsRow::level(&self).format()),
-                format!(
-                    "{} / {} xp",
-                    self.xp().format(),
-                    level_up_xp(LevelsRow::level(&self)).format()
-                ),
-                false,
-            )
+        Ok(embed
             .field("Betting Maximum", betting_max, false)
             .field("Loot", loot_str, false)
-            .colour(Colour::TEAL);
-
-        Ok(embed)
+            .colour(Colour::TEAL))
     }
 }
 
@@ -182,6 +216,7 @@ impl Commands {
         interaction: &CommandInteraction,
         mut options: Vec<ResolvedOption<'_>>,
         pool: &PgPool,
+        client: &reqwest::Client,
     ) -> Result<()> {
         interaction.defer(&ctx.http).await?;
 
@@ -204,16 +239,50 @@ impl Commands {
             data.emojis()
         };
 
-        let mut embed =
-            row.into_embed(&inventory_row, &emojis)?.title(user.display_name());
+        let rank = RankRow::user_rank(pool, user.id).await?;
+        let settings = CardManager::settings(pool, user.id).await?;
+        let card = row.card(user.display_name().to_string(), rank);
+
+        let png = match render_card(
+            client,
+            user,
+            CardKind::Profile,
+            &card,
+            settings.theme(),
+        )
+        .await
+        {
+            Ok(png) => Some(png),
+            Err(e) => {
+                warn!(error = %e, "profile card render failed, sending embed");
+                None
+            },
+        };
+
+        let mut embed = match png {
+            Some(_) => {
+                row.with_details(CreateEmbed::new(), &inventory_row, &emojis)?
+            },
+            None => row.into_embed(&inventory_row, &emojis)?,
+        }
+        .title(user.display_name());
+
+        let titles = SeasonManager::titles(pool, user.id).await?;
+        if !titles.is_empty() {
+            embed = embed.field("Season Titles", titles.join("\n"), false);
+        }
+
+        let mut response = EditInteractionResponse::new();
 
-        if let Some(avatar) = user.avatar_url() {
+        if let Some(png) = png {
+            embed = embed.image(format!("attachment://{CARD_FILENAME}"), None);
+            response =
+                response.new_attachment(CreateAttachment::bytes(png, CARD_FILENAME));
+        } else if let Some(avatar) = user.avatar_url() {
             embed = embed.thumbnail(avatar, None);
         }
 
-        interaction
-            .edit_response(&ctx.http, EditInteractionResponse::new().embed(embed))
-            .await?;
+        interaction.edit_response(&ctx.http, response.embed(embed)).await?;
 
         Ok(())
     }
@@ -0,0 +1,341 @@
+use std::collections::HashMap;
+
+use jiff::Timestamp;
+use jiff::civil::Date;
+use jiff::tz::TimeZone;
+use serenity::all::{
+    Colour,
+    CommandInteraction,
+    CommandOptionType,
+    Context,
+    CreateCommand,
+    CreateCommandOption,
+    CreateEmbed,
+    EditInteractionResponse,
+    Permissions,
+    ResolvedOption,
+    ResolvedValue,
+};
+use sqlx::PgPool;
+use zayden_core::{FormatNum, parse_options, parse_subcommand};
+
+use super::Commands;
+use crate::season::{
+    SEASON_COINS,
+    SEASON_HIGHERLOWER,
+    SeasonManager,
+    SeasonReward,
+    SeasonRow,
+};
+use crate::{GamblingError, Result, SHOP_ITEMS};
+
+const RECENT_SEASONS: i64 = 10;
+
+fn parse_date(value: &str) -> Result<Timestamp> {
+    value
+        .parse::<Date>()
+        .and_then(|date| date.to_zoned(TimeZone::UTC))
+        .map(|zoned| zoned.timestamp())
+        .map_err(|_e| GamblingError::InvalidSeasonDate(value.to_string()))
+}
+
+impl Commands {
+    pub async fn season(
+        ctx: &Context,
+        interaction: &CommandInteraction,
+        options: Vec<ResolvedOption<'_>>,
+        pool: &PgPool,
+    ) -> Result<()> {
+        interaction.defer_ephemeral(&ctx.http).await?;
+
+        let (name, options) = parse_subcommand(options)?;
+        let mut options = parse_options(options);
+
+        let content = match name {
+            "create" => {
+                let Some(ResolvedValue::String(season_name)) =
+                    options.remove("name")
+                else {
+                    return Err(GamblingError::internal("season name missing"));
+                };
+                let Some(ResolvedValue::String(start)) = options.remove("start")
+                else {
+                    return Err(GamblingError::internal("season start missing"));
+                };
+                let Some(ResolvedValue::String(end)) = options.remove("end") else {
+                    return Err(GamblingError::internal("season end missing"));
+                };
+
+                let starts_at = parse_date(start)?;
+                let ends_at = parse_date(end)?;
+                if ends_at <= starts_at {
+                    return Err(GamblingError::InvalidSeasonDate(end.to_string()));
+                }
+
+                let season =
+                    SeasonManager::create(pool, season_name, starts_at, ends_at)
+                        .await?
+                        .ok_or(GamblingError::SeasonOverlap)?;
+
+                format!("Created season {}", season.as_desc())
+            },
+            "end" => {
+                let season_id = season_option(&mut options)?;
+
+                let season = SeasonManager::get(pool, season_id)
+                    .await?
+                    .ok_or(GamblingError::SeasonNotFound(season_id))?;
+
+                if season.starts_at.to_jiff() > Timestamp::now() {
+                    return Err(GamblingError::SeasonNotStarted(season_id));
+                }
+
+                if !SeasonManager::end_now(pool, season_id).await? {
+                    return Err(GamblingError::SeasonArchived(season_id));
+                }
+
+                format!(
+                    "Season `#{season_id}` will be archived and paid out within the next few minutes."
+                )
+            },
+            "reward" => {
+                let season_id = season_option(&mut options)?;
+
+                let season = SeasonManager::get(pool, season_id)
+                    .await?
+                    .ok_or(GamblingError::SeasonNotFound(season_id))?;
+
+                if season.archived_at.is_some() {
+                    return Err(GamblingError::SeasonArchived(season_id));
+                }
+
+                let Some(ResolvedValue::String(leaderboard)) =
+                    options.remove("leaderboard")
+                else {
+                    return Err(GamblingError::internal(
+                        "reward leaderboard missing",
+                    ));
+                };
+
+                let placement_min = int_option(&mut options, "from")?.unwrap_or(1);
+                let placement_max =
+                    int_option(&mut options, "to")?.unwrap_or(placement_min);
+                if placement_max < placement_min {
+                    return Err(GamblingError::InvalidAmount);
+                }
+
+                let item_id = match options.remove("item") {
+                    Some(ResolvedValue::String(item)) => {
+                        let item =
+                            SHOP_ITEMS.iter().find(|i| i.id == item).ok_or_else(
+                                || GamblingError::UnknownItem(item.to_string()),
+                            )?;
+                        Some(item.id.to_string())
+                    },
+                    _ => None,
+                };
+
+                let reward = SeasonReward {
+                    leaderboard: leaderboard.to_string(),
+                    placement_min,
+                    placement_max,
+                    coins: amount_option(&mut options, "coins"),
+                    gems: amount_option(&mut options, "gems"),
+                    item_id,
+                    title: match options.remove("title") {
+                        Some(ResolvedValue::String(title)) => {
+                            Some(title.to_string())
+                        },
+                        _ => None,
+                    },
+                };
+
+                if !SeasonManager::add_reward(pool, season.id, &reward).await? {
+                    return Err(GamblingError::SeasonArchived(season_id));
+                }
+
+                format!(
+                    "Added a reward for #{placement_min}-#{placement_max} on {leaderboard} in **{}** ({} coins, {} gems)",
+                    season.name,
+                    reward.coins.format(),
+                    reward.gems.format()
+                )
+            },
+            "list" => {
+                let seasons = SeasonManager::recent(pool, RECENT_SEASONS).await?;
+
+                if seasons.is_empty() {
+                    String::from("No seasons have been created yet.")
+                } else {
+                    seasons
+                        .iter()
+                        .map(SeasonRow::as_desc)
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                }
+            },
+            _ => return Err(GamblingError::internal("unknown season subcommand")),
+        };
+
+        let embed = CreateEmbed::new()
+            .title("Gambling Seasons")
+            .description(content)
+            .colour(Colour::TEAL);
+
+        interaction
+            .edit_response(&ctx.http, EditInteractionResponse::new().embed(embed))
+            .await?;
+
+        Ok(())
+    }
+
+    pub fn register_season<'a>() -> CreateCommand<'a> {
+        let season = || {
+            CreateCommandOption::new(
+                CommandOptionType::Integer,
+                "season",
+                "The season number",
+            )
+            .min_int_value(1)
+            .required(true)
+        };
+
+        let create = CreateCommandOption::new(
+            CommandOptionType::SubCommand,
+            "create",
+            "Schedule a new season",
+        )
+        .add_sub_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "name",
+                "The season name",
+            )
+            .max_length(64)
+            .required(true),
+        )
+        .add_sub_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "start",
+                "Start date (UTC), e.g. 2026-01-01",
+            )
+            .required(true),
+        )
+        .add_sub_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "end",
+                "End date (UTC), e.g. 2026-03-01",
+            )
+            .required(true),
+        );
+
+        let end = CreateCommandOption::new(
+            CommandOptionType::SubCommand,
+            "end",
+            "End a running season now and pay out its rewards",
+        )
+        .add_sub_option(season());
+
+        let reward = CreateCommandOption::new(
+            CommandOptionType::SubCommand,
+            "reward",
+            "Add a reward for a range of final placements",
+        )
+        .add_sub_option(season())
+        .add_sub_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "leaderboard",
+                "The seasonal leaderboard",
+            )
+            .add_string_choice("Season Coins", SEASON_COINS)
+            .add_string_choice("Season Higher or Lower", SEASON_HIGHERLOWER)
+            .required(true),
+        )
+        .add_sub_option(
+            CreateCommandOption::new(
+                CommandOptionType::Integer,
+                "from",
+                "The best placement rewarded (default 1)",
+            )
+            .min_int_value(1),
+        )
+        .add_sub_option(
+            CreateCommandOption::new(
+                CommandOptionType::Integer,
+                "to",
+                "The worst placement rewarded (default: same as from)",
+            )
+            .min_int_value(1),
+        )
+        .add_sub_option(
+            CreateCommandOption::new(
+                CommandOptionType::Integer,
+                "coins",
+                "Coins for each player in range",
+            )
+            .min_int_value(0),
+        )
+        .add_sub_option(
+            CreateCommandOption::new(
+                CommandOptionType::Integer,
+                "gems",
+                "Gems for each player in range",
+            )
+            .min_int_value(0),
+        )
+        .add_sub_option(CreateCommandOption::new(
+            CommandOptionType::String,
+            "item",
+            "Shop item id given to each player in range",
+        ))
+        .add_sub_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "title",
+                "Profile title awarded to each player in range",
+            )
+            .max_length(32),
+        );
+
+        let list = CreateCommandOption::new(
+            CommandOptionType::SubCommand,
+            "list",
+            "Show recent seasons",
+        );
+
+        CreateCommand::new("season")
+            .description("Manage gambling seasons")
+            .default_member_permissions(Permissions::ADMINISTRATOR)
+            .add_option(create)
+            .add_option(end)
+            .add_option(reward)
+            .add_option(list)
+    }
+}
+
+fn season_option(options: &mut HashMap<&str, ResolvedValue<'_>>) -> Result<i32> {
+    int_option(options, "season")?
+        .ok_or_else(|| GamblingError::internal("season option missing"))
+}
+
+fn int_option(
+    options: &mut HashMap<&str, ResolvedValue<'_>>,
+    name: &str,
+) -> Result<Option<i32>> {
+    match options.remove(name) {
+        Some(ResolvedValue::Integer(value)) => {
+            i32::try_from(value).map(Some).map_err(|_e| GamblingError::InvalidAmount)
+        },
+        _ => Ok(None),
+    }
+}
+
+fn amount_option(options: &mut HashMap<&str, ResolvedValue<'_>>, name: &str) -> i64 {
+    match options.remove(name) {
+        Some(ResolvedValue::Integer(value)) => value.max(0),
+        _ => 0,
+    }
+}
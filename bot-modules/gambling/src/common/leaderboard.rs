@@ -3,6 +3,7 @@ use serenity::all::{Mentionable, UserId};
 use sqlx::{FromRow, PgPool};
 use zayden_core::{EmojiCache, FormatNum, as_i64, as_u64};
 
+use crate::season::{SEASON_COINS, SEASON_HIGHERLOWER};
 use crate::shop::{EGGPLANT, LOTTO_TICKET};
 use crate::{Coins, Gems, Result};
 
@@ -252,6 +253,92 @@ impl LeaderboardManager {
         .await
         .map(Option::flatten)
     }
+
+    pub async fn season(
+        pool: &PgPool,
+        leaderboard: &str,
+        global: bool,
+        users: &[i64],
+        page_num: i64,
+    ) -> sqlx::Result<Vec<SeasonValueRow>> {
+        let offset = (page_num - 1) * LIMIT;
+
+        sqlx::query_file_as!(
+            SeasonValueRow,
+            "sql/LeaderboardManager/season_live.sql",
+            leaderboard,
+            global,
+            users,
+            LIMIT,
+            offset
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn season_row_number(
+        pool: &PgPool,
+        leaderboard: &str,
+        global: bool,
+        users: &[i64],
+        id: UserId,
+    ) -> sqlx::Result<Option<i64>> {
+        sqlx::query_file_scalar!(
+            "sql/LeaderboardManager/season_live_row_number.sql",
+            leaderboard,
+            global,
+            users,
+            as_i64(id.get())
+        )
+        .fetch_optional(pool)
+        .await
+        .map(Option::flatten)
+    }
+
+    pub async fn season_archive(
+        pool: &PgPool,
+        season_id: i32,
+        leaderboard: &str,
+        global: bool,
+        users: &[i64],
+        page_num: i64,
+    ) -> sqlx::Result<Vec<SeasonValueRow>> {
+        let offset = (page_num - 1) * LIMIT;
+
+        sqlx::query_file_as!(
+            SeasonValueRow,
+            "sql/LeaderboardManager/season_archive.sql",
+            season_id,
+            leaderboard,
+            global,
+            users,
+            LIMIT,
+            offset
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn season_archive_row_number(
+        pool: &PgPool,
+        season_id: i32,
+        leaderboard: &str,
+        global: bool,
+        users: &[i64],
+        id: UserId,
+    ) -> sqlx::Result<Option<i64>> {
+        sqlx::query_file_scalar!(
+            "sql/LeaderboardManager/season_archive_row_number.sql",
+            season_id,
+            leaderboard,
+            global,
+            users,
+            as_i64(id.get())
+        )
+        .fetch_optional(pool)
+        .await
+        .map(Option::flatten)
+    }
 }
 
 #[derive(FromRow)]
@@ -310,6 +397,15 @@ pub struct WeeklyHigherLowerRow {
     pub weekly_higher_or_lower_score: i32,
 }
 
+/// One placement on a seasonal leaderboard, either live or archived. `value`
+/// is net coins for `season_coins` and the best streak for
+/// `season_higherlower`.
+#[derive(FromRow)]
+pub struct SeasonValueRow {
+    pub user_id: i64,
+    pub value: i64,
+}
+
 pub enum LeaderboardRow {
     Coins(CoinsRow),
     Gems(GemsRow),
@@ -317,6 +413,8 @@ pub enum LeaderboardRow {
     LottoTickets(LottoTicketRow),
     HigherLower(HigherLowerRow),
     WeeklyHigherLower(WeeklyHigherLowerRow),
+    SeasonCoins(SeasonValueRow),
+    SeasonHigherLower(SeasonValueRow),
 }
 
 impl LeaderboardRow {
@@ -329,6 +427,9 @@ impl LeaderboardRow {
             Self::LottoTickets(row) => UserId::new(as_u64(row.user_id)),
             Self::HigherLower(row) => UserId::new(as_u64(row.user_id)),
             Self::WeeklyHigherLower(row) => UserId::new(as_u64(row.user_id)),
+            Self::SeasonCoins(row) | Self::SeasonHigherLower(row) => {
+                UserId::new(as_u64(row.user_id))
+            },
         }
     }
 
@@ -356,21 +457,79 @@ impl LeaderboardRow {
             Self::WeeklyHigherLower(row) => {
                 row.weekly_higher_or_lower_score.to_string()
             },
+            Self::SeasonCoins(row) => row.value.format(),
+            Self::SeasonHigherLower(row) => row.value.to_string(),
         };
 
         Ok(format!("{place} - {} - {data}", self.user_id().mention()))
     }
 }
 
+/// Splits a leaderboard key as shown in the embed title, e.g.
+/// `season_coins, season 3`, into the leaderboard and the archived season.
+#[must_use]
+pub fn split_season(leaderboard: &str) -> (&str, Option<i32>) {
+    leaderboard
+        .split_once(", season ")
+        .and_then(|(name, season)| Some((name, Some(season.parse().ok()?))))
+        .unwrap_or((leaderboard, None))
+}
+
+#[must_use]
+pub fn is_seasonal(leaderboard: &str) -> bool {
+    leaderboard == SEASON_COINS || leaderboard == SEASON_HIGHERLOWER
+}
+
+async fn season_rows(
+    leaderboard: &str,
+    pool: &PgPool,
+    season: Option<i32>,
+    global: bool,
+    users: &[i64],
+    page_num: i64,
+) -> sqlx::Result<Vec<LeaderboardRow>> {
+    let rows = match season {
+        Some(season_id) => {
+            LeaderboardManager::season_archive(
+                pool,
+                season_id,
+                leaderboard,
+                global,
+                users,
+                page_num,
+            )
+            .await?
+        },
+        None => {
+            LeaderboardManager::season(pool, leaderboard, global, users, page_num)
+                .await?
+        },
+    };
+
+    let variant = if leaderboard == SEASON_HIGHERLOWER {
+        LeaderboardRow::SeasonHigherLower
+    } else {
+        LeaderboardRow::SeasonCoins
+    };
+
+    Ok(rows.into_iter().map(variant).collect())
+}
+
 pub async fn get_rows(
     leaderboard: &str,
     pool: &PgPool,
+    season: Option<i32>,
     users: Option<&[i64]>,
     page_num: i64,
 ) -> sqlx::Result<Vec<LeaderboardRow>> {
     let global = users.is_none();
     let users = users.unwrap_or_default();
 
+    if is_seasonal(leaderboard) {
+        return season_rows(leaderboard, pool, season, global, users, page_num)
+            .await;
+    }
+
     match leaderboard {
         "coins" => LeaderboardManager::coins(pool, global, users, page_num).await,
         "gems" => LeaderboardManager::gems(pool, global, users, page_num).await,
@@ -394,12 +553,39 @@ pub async fn get_rows(
 pub async fn get_row_number(
     leaderboard: &str,
     pool: &PgPool,
+    season: Option<i32>,
     users: Option<&[i64]>,
     user: UserId,
 ) -> sqlx::Result<Option<i64>> {
     let global = users.is_none();
     let users = users.unwrap_or_default();
 
+    if is_seasonal(leaderboard) {
+        return match season {
+            Some(season_id) => {
+                LeaderboardManager::season_archive_row_number(
+                    pool,
+                    season_id,
+                    leaderboard,
+                    global,
+                    users,
+                    user,
+                )
+                .await
+            },
+            None => {
+                LeaderboardManager::season_row_number(
+                    pool,
+                    leaderboard,
+                    global,
+                    users,
+                    user,
+                )
+                .await
+            },
+        };
+    }
+
     match leaderboard {
         "coins" => {
             LeaderboardManager::coins_row_number(pool, global, users, user).await
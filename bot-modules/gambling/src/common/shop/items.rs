@@ -369,7 +369,52 @@ const UNIVERSE: ShopItem<'static> = ShopItem::new(
 .add_cost(1000, ShopCurrency::Production);
 // endregion
 
-pub struct ShopItems<'a>([ShopItem<'a>; 18]);
+// region: Cards
+// Ids are `card_` + the zayden-graphics background id they unlock.
+pub const CARD_ITEM_PREFIX: &str = "card_";
+
+const CARD_SUNSET: ShopItem<'static> = ShopItem::new(
+    "card_sunset",
+    "Sunset Background",
+    Emoji::Str("🌅"),
+    "A warm sunset background for your /rank and /profile cards",
+    250_000,
+    ShopCurrency::Coins,
+    ShopPage::Cards,
+);
+
+const CARD_OCEAN: ShopItem<'static> = ShopItem::new(
+    "card_ocean",
+    "Ocean Background",
+    Emoji::Str("🌊"),
+    "A deep ocean background for your /rank and /profile cards",
+    250_000,
+    ShopCurrency::Coins,
+    ShopPage::Cards,
+);
+
+const CARD_FOREST: ShopItem<'static> = ShopItem::new(
+    "card_forest",
+    "Forest Background",
+    Emoji::Str("🌲"),
+    "A forest green background for your /rank and /profile cards",
+    250_000,
+    ShopCurrency::Coins,
+    ShopPage::Cards,
+);
+
+const CARD_AURORA: ShopItem<'static> = ShopItem::new(
+    "card_aurora",
+    "Aurora Background",
+    Emoji::Str("🌌"),
+    "A shimmering aurora background for your /rank and /profile cards",
+    25,
+    ShopCurrency::Gems,
+    ShopPage::Cards,
+);
+// endregion
+
+pub struct ShopItems<'a>([ShopItem<'a>; 22]);
 
 impl ShopItems<'_> {
     #[must_use]
@@ -407,4 +452,8 @@ pub const SHOP_ITEMS: ShopItems<'static> = ShopItems([
     SOLAR_SYSTEM,
     GALAXY,
     UNIVERSE,
+    CARD_SUNSET,
+    CARD_OCEAN,
+    CARD_FOREST,
+    CARD_AURORA,
 ]);
@@ -8,12 +8,20 @@ pub enum ShopPage {
     Boost2,
     Mine1,
     Mine2,
+    Cards,
 }
 
 impl ShopPage {
     #[must_use]
-    pub const fn pages() -> [Self; 5] {
-        [Self::Item, Self::Boost1, Self::Boost2, Self::Mine1, Self::Mine2]
+    pub const fn pages() -> [Self; 6] {
+        [
+            Self::Item,
+            Self::Boost1,
+            Self::Boost2,
+            Self::Mine1,
+            Self::Mine2,
+            Self::Cards,
+        ]
     }
 
     #[must_use]
@@ -41,6 +49,7 @@ impl Display for ShopPage {
             Self::Boost2 => write!(f, "Boost 2"),
             Self::Mine1 => write!(f, "Mine 1"),
             Self::Mine2 => write!(f, "Mine 2"),
+            Self::Cards => write!(f, "Cards"),
         }
     }
 }
@@ -55,6 +64,7 @@ impl FromStr for ShopPage {
             "Boost 2" => Ok(Self::Boost2),
             "Mine 1" => Ok(Self::Mine1),
             "Mine 2" => Ok(Self::Mine2),
+            "Cards" => Ok(Self::Cards),
             _ => Err(()),
         }
     }
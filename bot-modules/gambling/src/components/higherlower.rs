@@ -28,6 +28,7 @@ use crate::{
     GameDelta,
     GameRow,
     Result,
+    SeasonManager,
     StatsManager,
     card_deck,
     card_to_num,
@@ -304,12 +305,11 @@ impl HigherLower {
 
         let mut tx = pool.begin().await?;
 
-        StatsManager::higherlower(
-            &mut tx,
-            interaction.user.id,
-            i32::try_from(self.score).unwrap_or(i32::MAX),
-        )
-        .await?;
+        let score = i32::try_from(self.score).unwrap_or(i32::MAX);
+
+        StatsManager::higherlower(&mut tx, interaction.user.id, score).await?;
+        SeasonManager::record_higherlower(&mut tx, interaction.user.id, score)
+            .await?;
 
         tx.commit().await?;
 
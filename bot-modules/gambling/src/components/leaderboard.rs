@@ -10,7 +10,7 @@ use sqlx::PgPool;
 use tokio::sync::RwLock;
 use zayden_core::{EmojiCacheData, GuildMembersCache, as_i64};
 
-use crate::common::leaderboard::{get_row_number, get_rows};
+use crate::common::leaderboard::{get_row_number, get_rows, split_season};
 use crate::{GamblingError, Leaderboard, Result};
 
 impl Leaderboard {
@@ -62,6 +62,8 @@ impl Leaderboard {
             },
         )?;
 
+        let (leaderboard, season) = split_season(leaderboard);
+
         let mut page_number: i64 = embed
             .footer
             .as_ref()
@@ -113,6 +115,7 @@ impl Leaderboard {
                 let row_num = get_row_number(
                     leaderboard,
                     pool,
+                    season,
                     users.as_deref(),
                     interaction.user.id,
                 )
@@ -127,7 +130,8 @@ impl Leaderboard {
         }
 
         let rows =
-            get_rows(leaderboard, pool, users.as_deref(), page_number).await?;
+            get_rows(leaderboard, pool, season, users.as_deref(), page_number)
+                .await?;
 
         if rows.is_empty() {
             return Err(GamblingError::internal("No entries for this page"));
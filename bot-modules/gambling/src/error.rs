@@ -35,6 +35,17 @@ pub enum GamblingError {
     InsufficientItemQuantity(i64),
     NotEnoughMiners { required: i64, current: i64 },
     NotYourGame,
+    NoActiveSeason,
+    NotSeasonal,
+    SeasonNotFound(i32),
+    SeasonNotArchived(i32),
+    SeasonArchived(i32),
+    SeasonNotStarted(i32),
+    SeasonOverlap,
+    InvalidSeasonDate(String),
+    UnknownItem(String),
+    InvalidColour(String),
+    BackgroundLocked(&'static str),
 
     Serenity(serenity::Error),
     Sqlx(sqlx::Error),
@@ -117,6 +128,42 @@ impl std::fmt::Display for GamblingError {
             Self::NotYourGame => {
                 write!(f, "This isn't your game.")
             },
+            Self::NoActiveSeason => {
+                write!(f, "There is no gambling season running right now.")
+            },
+            Self::NotSeasonal => write!(
+                f,
+                "Only the season leaderboards are archived. Pick `Season Coins` or `Season Higher or Lower`."
+            ),
+            Self::SeasonNotFound(id) => write!(f, "Season `#{id}` doesn't exist."),
+            Self::SeasonNotArchived(id) => write!(
+                f,
+                "Season `#{id}` hasn't finished yet. Leave out `season` to see the live standings."
+            ),
+            Self::SeasonArchived(id) => write!(
+                f,
+                "Season `#{id}` has already ended and its rewards have been paid out."
+            ),
+            Self::SeasonNotStarted(id) => write!(
+                f,
+                "Season `#{id}` hasn't started yet, so there's nothing to end."
+            ),
+            Self::SeasonOverlap => {
+                write!(f, "That season overlaps one that hasn't finished yet.")
+            },
+            Self::InvalidSeasonDate(date) => write!(
+                f,
+                "`{date}` isn't a valid date. Use the format `YYYY-MM-DD`, ending after the start."
+            ),
+            Self::UnknownItem(item) => write!(f, "`{item}` isn't a shop item id."),
+            Self::InvalidColour(colour) => write!(
+                f,
+                "`{colour}` isn't a valid colour. Use a hex code like `#5865F2`."
+            ),
+            Self::BackgroundLocked(name) => write!(
+                f,
+                "You don't own the **{name}** background yet. Buy it from the shop first."
+            ),
 
             Self::Serenity(e) => write!(f, "serenity: {e:?}"),
             Self::Sqlx(e) => write!(f, "sqlx: {e:?}"),
@@ -152,7 +199,18 @@ impl std::error::Error for GamblingError {
             | Self::ItemNotInInventory
             | Self::InsufficientItemQuantity(_)
             | Self::NotEnoughMiners { .. }
-            | Self::NotYourGame => None,
+            | Self::NotYourGame
+            | Self::NoActiveSeason
+            | Self::NotSeasonal
+            | Self::SeasonNotFound(_)
+            | Self::SeasonNotArchived(_)
+            | Self::SeasonArchived(_)
+            | Self::SeasonNotStarted(_)
+            | Self::SeasonOverlap
+            | Self::InvalidSeasonDate(_)
+            | Self::UnknownItem(_)
+            | Self::InvalidColour(_)
+            | Self::BackgroundLocked(_) => None,
         }
     }
 }
@@ -183,7 +241,18 @@ impl Respond for GamblingError {
             | Self::ItemNotInInventory
             | Self::InsufficientItemQuantity(_)
             | Self::NotEnoughMiners { .. }
-            | Self::NotYourGame => Some(Cow::Owned(self.to_string())),
+            | Self::NotYourGame
+            | Self::NoActiveSeason
+            | Self::NotSeasonal
+            | Self::SeasonNotFound(_)
+            | Self::SeasonNotArchived(_)
+            | Self::SeasonArchived(_)
+            | Self::SeasonNotStarted(_)
+            | Self::SeasonOverlap
+            | Self::InvalidSeasonDate(_)
+            | Self::UnknownItem(_)
+            | Self::InvalidColour(_)
+            | Self::BackgroundLocked(_) => Some(Cow::Owned(self.to_string())),
         }
     }
 }
@@ -240,7 +309,18 @@ impl From<GamblingError> for HandlerError {
             | GamblingError::ItemNotInInventory
             | GamblingError::InsufficientItemQuantity(_)
             | GamblingError::NotEnoughMiners { .. }
-            | GamblingError::NotYourGame) => Self::from_respond(e),
+            | GamblingError::NotYourGame
+            | GamblingError::NoActiveSeason
+            | GamblingError::NotSeasonal
+            | GamblingError::SeasonNotFound(_)
+            | GamblingError::SeasonNotArchived(_)
+            | GamblingError::SeasonArchived(_)
+            | GamblingError::SeasonNotStarted(_)
+            | GamblingError::SeasonOverlap
+            | GamblingError::InvalidSeasonDate(_)
+            | GamblingError::UnknownItem(_)
+            | GamblingError::InvalidColour(_)
+            | GamblingError::BackgroundLocked(_)) => Self::from_respond(e),
         }
     }
 }
@@ -14,7 +14,7 @@ use zayden_core::{CronJob, FormatNum, as_u64};
 
 use crate::{GEM, GamblingManager};
 
-const CHANNEL_ID: ChannelId = ChannelId::new(1_383_573_049_563_156_502);
+pub(crate) const CHANNEL_ID: ChannelId = ChannelId::new(1_383_573_049_563_156_502);
 
 pub struct HigherLowerManager;
 
@@ -17,6 +17,7 @@ pub mod game_cache;
 pub mod games;
 pub mod goals;
 pub mod models;
+pub mod season;
 pub mod stamina;
 pub mod utils;
 
@@ -81,6 +82,7 @@ pub use models::{
     StatsManager,
     out_of_stamina,
 };
+pub use season::{SeasonCron, SeasonManager, SeasonReward, SeasonRow};
 pub use stamina::{StaminaCron, StaminaManager};
 
 const START_AMOUNT: i64 = 1000;
@@ -3,7 +3,7 @@ use sqlx::{FromRow, PgConnection, PgPool};
 use zayden_core::as_i64;
 
 use super::{Coins, Gems, MaxBet};
-use crate::{Prestige, START_AMOUNT};
+use crate::{Prestige, START_AMOUNT, SeasonManager};
 
 #[derive(Debug, Clone, FromRow)]
 pub struct GameRow {
@@ -62,7 +62,7 @@ impl GameRow {
 
         Self::insert_missing(&mut *conn, user_id).await?;
 
-        sqlx::query_as!(
+        let commit = sqlx::query_as!(
             GameCommit,
             "UPDATE gambling SET
                 coins = coins + $2,
@@ -75,8 +75,14 @@ impl GameRow {
             delta.coins,
             delta.gems,
         )
-        .fetch_optional(conn)
-        .await
+        .fetch_optional(&mut *conn)
+        .await?;
+
+        if commit.is_some() {
+            SeasonManager::record_game(conn, id, delta).await?;
+        }
+
+        Ok(commit)
     }
 
     pub async fn commit(
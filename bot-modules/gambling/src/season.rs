@@ -0,0 +1,434 @@
+use futures::TryStreamExt;
+use jiff_cron;
+use jiff_sqlx::{Timestamp, ToSqlx};
+use serenity::all::{CreateMessage, Mentionable, UserId};
+use sqlx::postgres::PgQueryResult;
+use sqlx::{FromRow, PgConnection, PgPool, Postgres, Transaction};
+use zayden_core::{CronJob, FormatNum, as_i64, as_u64};
+
+use crate::games::higherlower::CHANNEL_ID;
+use crate::{GEM, GamblingManager, GameDelta};
+
+pub const SEASON_COINS: &str = "season_coins";
+pub const SEASON_HIGHERLOWER: &str = "season_higherlower";
+
+#[derive(Debug, Clone, FromRow)]
+pub struct SeasonRow {
+    pub id: i32,
+    pub name: String,
+    pub starts_at: Timestamp,
+    pub ends_at: Timestamp,
+    pub archived_at: Option<Timestamp>,
+}
+
+impl SeasonRow {
+    #[must_use]
+    pub fn is_active(&self, now: jiff::Timestamp) -> bool {
+        self.archived_at.is_none()
+            && self.starts_at.to_jiff() <= now
+            && now < self.ends_at.to_jiff()
+    }
+
+    #[must_use]
+    pub fn as_desc(&self) -> String {
+        let status = if self.archived_at.is_some() {
+            "archived"
+        } else if self.is_active(jiff::Timestamp::now()) {
+            "active"
+        } else if self.starts_at.to_jiff() > jiff::Timestamp::now() {
+            "upcoming"
+        } else {
+            "ending"
+        };
+
+        format!(
+            "`#{}` **{}** — <t:{}:d> → <t:{}:d> ({status})",
+            self.id,
+            self.name,
+            self.starts_at.to_jiff().as_second(),
+            self.ends_at.to_jiff().as_second(),
+        )
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SeasonReward {
+    pub leaderboard: String,
+    pub placement_min: i32,
+    pub placement_max: i32,
+    pub coins: i64,
+    pub gems: i64,
+    pub item_id: Option<String>,
+    pub title: Option<String>,
+}
+
+#[derive(Debug, FromRow)]
+pub struct EarnedReward {
+    pub user_id: i64,
+    pub leaderboard: String,
+    pub placement: i32,
+    pub coins: i64,
+    pub gems: i64,
+    pub item_id: Option<String>,
+    pub title: Option<String>,
+}
+
+impl EarnedReward {
+    #[must_use]
+    pub const fn user_id(&self) -> UserId {
+        UserId::new(as_u64(self.user_id))
+    }
+
+    #[must_use]
+    pub fn as_desc(&self) -> String {
+        let mut parts = Vec::new();
+        if self.coins > 0 {
+            parts.push(format!("{} coins", self.coins.format()));
+        }
+        if self.gems > 0 {
+            parts.push(format!("{} {GEM}", self.gems.format()));
+        }
+        if let Some(item) = &self.item_id {
+            parts.push(format!("`{item}`"));
+        }
+        if let Some(title) = &self.title {
+            parts.push(format!("the title **{title}**"));
+        }
+
+        format!(
+            "{} placed #{} on {} and earned {}",
+            self.user_id().mention(),
+            self.placement,
+            self.leaderboard,
+            parts.join(", ")
+        )
+    }
+}
+
+pub struct SeasonManager;
+
+impl SeasonManager {
+    pub async fn active(pool: &PgPool) -> sqlx::Result<Option<SeasonRow>> {
+        sqlx::query_as!(
+            SeasonRow,
+            r#"SELECT id, name,
+                starts_at as "starts_at: jiff_sqlx::Timestamp",
+                ends_at as "ends_at: jiff_sqlx::Timestamp",
+                archived_at as "archived_at: jiff_sqlx::Timestamp"
+            FROM gambling_seasons
+            WHERE archived_at IS NULL AND now() >= starts_at AND now() < ends_at
+            ORDER BY starts_at
+            LIMIT 1"#
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn get(pool: &PgPool, id: i32) -> sqlx::Result<Option<SeasonRow>> {
+        sqlx::query_as!(
+            SeasonRow,
+            r#"SELECT id, name,
+                starts_at as "starts_at: jiff_sqlx::Timestamp",
+                ends_at as "ends_at: jiff_sqlx::Timestamp",
+                archived_at as "archived_at: jiff_sqlx::Timestamp"
+            FROM gambling_seasons
+            WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn recent(pool: &PgPool, limit: i64) -> sqlx::Result<Vec<SeasonRow>> {
+        sqlx::query_as!(
+            SeasonRow,
+            r#"SELECT id, name,
+                starts_at as "starts_at: jiff_sqlx::Timestamp",
+                ends_at as "ends_at: jiff_sqlx::Timestamp",
+                archived_at as "archived_at: jiff_sqlx::Timestamp"
+            FROM gambling_seasons
+            ORDER BY starts_at DESC
+            LIMIT $1"#,
+            limit
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Inserts a season unless it overlaps one that has not been archived yet.
+    /// Overlapping seasons would double-count every game, so `None` is
+    /// returned instead of a row in that case.
+    pub async fn create(
+        pool: &PgPool,
+        name: &str,
+        starts_at: jiff::Timestamp,
+        ends_at: jiff::Timestamp,
+    ) -> sqlx::Result<Option<SeasonRow>> {
+        sqlx::query_as!(
+            SeasonRow,
+            r#"INSERT INTO gambling_seasons (name, starts_at, ends_at)
+            SELECT $1, $2, $3
+            WHERE NOT EXISTS (
+                SELECT 1 FROM gambling_seasons
+                WHERE archived_at IS NULL AND starts_at < $3 AND $2 < ends_at
+            )
+            RETURNING id, name,
+                starts_at as "starts_at: jiff_sqlx::Timestamp",
+                ends_at as "ends_at: jiff_sqlx::Timestamp",
+                archived_at as "archived_at: jiff_sqlx::Timestamp""#,
+            name,
+            starts_at.to_sqlx() as Timestamp,
+            ends_at.to_sqlx() as Timestamp,
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Pulls the end of a running season forward to now, so the next
+    /// [`SeasonCron`] tick archives it. `false` if the season hasn't started
+    /// or has already ended.
+    pub async fn end_now(pool: &PgPool, id: i32) -> sqlx::Result<bool> {
+        let result = sqlx::query!(
+            "UPDATE gambling_seasons SET ends_at = GREATEST(starts_at + interval '1 second', now())
+            WHERE id = $1 AND archived_at IS NULL AND starts_at <= now() AND ends_at > now()",
+            id
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// `false` if the season has already been archived, since its rewards
+    /// were paid out then.
+    pub async fn add_reward(
+        pool: &PgPool,
+        season_id: i32,
+        reward: &SeasonReward,
+    ) -> sqlx::Result<bool> {
+        let result = sqlx::query!(
+            "INSERT INTO gambling_season_rewards
+                (season_id, leaderboard, placement_min, placement_max, coins, gems, item_id, title)
+            SELECT $1, $2, $3, $4, $5, $6, $7, $8
+            WHERE EXISTS (
+                SELECT 1 FROM gambling_seasons WHERE id = $1 AND archived_at IS NULL
+            )",
+            season_id,
+            reward.leaderboard,
+            reward.placement_min,
+            reward.placement_max,
+            reward.coins,
+            reward.gems,
+            reward.item_id,
+            reward.title,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Adds a committed wager to the running season, if there is one.
+    pub async fn record_game(
+        conn: &mut PgConnection,
+        id: UserId,
+        delta: &GameDelta,
+    ) -> sqlx::Result<PgQueryResult> {
+        sqlx::query!(
+            "INSERT INTO gambling_season_stats (season_id, user_id, coins, gems)
+            SELECT id, $1, $2, $3
+            FROM gambling_seasons
+            WHERE archived_at IS NULL AND now() >= starts_at AND now() < ends_at
+            ON CONFLICT (season_id, user_id) DO UPDATE SET
+                coins = gambling_season_stats.coins + EXCLUDED.coins,
+                gems = gambling_season_stats.gems + EXCLUDED.gems",
+            as_i64(id.get()),
+            delta.coins,
+            delta.gems,
+        )
+        .execute(conn)
+        .await
+    }
+
+    pub async fn record_higherlower(
+        conn: &mut PgConnection,
+        id: UserId,
+        score: i32,
+    ) -> sqlx::Result<PgQueryResult> {
+        sqlx::query!(
+            "INSERT INTO gambling_season_stats (season_id, user_id, higher_or_lower_score)
+            SELECT id, $1, $2
+            FROM gambling_seasons
+            WHERE archived_at IS NULL AND now() >= starts_at AND now() < ends_at
+            ON CONFLICT (season_id, user_id) DO UPDATE SET
+                higher_or_lower_score = GREATEST(
+                    gambling_season_stats.higher_or_lower_score,
+                    EXCLUDED.higher_or_lower_score
+                )",
+            as_i64(id.get()),
+            score,
+        )
+        .execute(conn)
+        .await
+    }
+
+    /// Seasons past their end that still need archiving. Rows are locked so
+    /// two bot processes can never pay out the same season twice.
+    pub async fn ended(conn: &mut PgConnection) -> sqlx::Result<Vec<SeasonRow>> {
+        sqlx::query_as!(
+            SeasonRow,
+            r#"SELECT id, name,
+                starts_at as "starts_at: jiff_sqlx::Timestamp",
+                ends_at as "ends_at: jiff_sqlx::Timestamp",
+                archived_at as "archived_at: jiff_sqlx::Timestamp"
+            FROM gambling_seasons
+            WHERE archived_at IS NULL AND ends_at <= now()
+            ORDER BY ends_at
+            FOR UPDATE SKIP LOCKED"#
+        )
+        .fetch_all(conn)
+        .await
+    }
+
+    pub async fn archive(
+        conn: &mut PgConnection,
+        season_id: i32,
+    ) -> sqlx::Result<()> {
+        sqlx::query_file!("sql/SeasonManager/archive.sql", season_id)
+            .execute(&mut *conn)
+            .await?;
+
+        sqlx::query!(
+            "UPDATE gambling_seasons SET archived_at = now() WHERE id = $1",
+            season_id
+        )
+        .execute(conn)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn earned_rewards(
+        conn: &mut PgConnection,
+        season_id: i32,
+    ) -> sqlx::Result<Vec<EarnedReward>> {
+        sqlx::query_file_as!(
+            EarnedReward,
+            "sql/SeasonManager/earned_rewards.sql",
+            season_id
+        )
+        .fetch_all(conn)
+        .await
+    }
+
+    pub async fn grant(
+        conn: &mut PgConnection,
+        season_id: i32,
+        reward: &EarnedReward,
+    ) -> sqlx::Result<()> {
+        let user_id = reward.user_id();
+
+        if reward.coins > 0 {
+            GamblingManager::add_coins(&mut *conn, user_id, reward.coins).await?;
+        }
+
+        if reward.gems > 0 {
+            GamblingManager::add_gems(&mut *conn, user_id, reward.gems).await?;
+        }
+
+        if let Some(item_id) = &reward.item_id {
+            sqlx::query!(
+                "INSERT INTO gambling_inventory (user_id, item_id, quantity)
+                VALUES ($1, $2, 1)
+                ON CONFLICT (user_id, item_id) DO UPDATE
+                SET quantity = gambling_inventory.quantity + 1",
+                reward.user_id,
+                item_id
+            )
+            .execute(&mut *conn)
+            .await?;
+        }
+
+        if let Some(title) = &reward.title {
+            sqlx::query!(
+                "INSERT INTO gambling_titles (user_id, season_id, title)
+                VALUES ($1, $2, $3)
+                ON CONFLICT DO NOTHING",
+                reward.user_id,
+                season_id,
+                title
+            )
+            .execute(conn)
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    pub async fn titles(pool: &PgPool, id: UserId) -> sqlx::Result<Vec<String>> {
+        sqlx::query_scalar!(
+            "SELECT t.title
+            FROM gambling_titles t
+            JOIN gambling_seasons s ON s.id = t.season_id
+            WHERE t.user_id = $1
+            ORDER BY s.ends_at DESC, t.title",
+            as_i64(id.get())
+        )
+        .fetch(pool)
+        .try_collect()
+        .await
+    }
+}
+
+pub struct SeasonCron;
+
+impl SeasonCron {
+    pub fn cron_job() -> Result<CronJob, jiff_cron::error::Error> {
+        Ok(CronJob::new("gambling_season", "0 */15 * * * * *")?.set_action(
+            |ctx, pool| async move {
+                if let Err(e) = (async {
+                    let mut tx: Transaction<'_, Postgres> = pool.begin().await?;
+
+                    let mut lines = Vec::new();
+                    for season in SeasonManager::ended(&mut tx).await? {
+                        SeasonManager::archive(&mut tx, season.id).await?;
+
+                        lines.push(format!(
+                            "🏁 **{}** has ended! Browse the final standings with `/leaderboard season:{}`",
+                            season.name, season.id
+                        ));
+
+                        for reward in
+                            SeasonManager::earned_rewards(&mut tx, season.id).await?
+                        {
+                            SeasonManager::grant(&mut tx, season.id, &reward).await?;
+                            lines.push(reward.as_desc());
+                        }
+                    }
+
+                    tx.commit().await?;
+
+                    if lines.is_empty() {
+                        return Ok(());
+                    }
+
+                    for chunk in lines.chunks(20) {
+                        CHANNEL_ID
+                            .widen()
+                            .send_message(
+                                &ctx.http,
+                                CreateMessage::new().content(chunk.join("\n")),
+                            )
+                            .await?;
+                    }
+
+                    Ok::<(), Box<dyn std::error::Error + Send + Sync>>(())
+                })
+                .await
+                {
+                    tracing::error!(error = ?e, "gambling_season cron job failed");
+                }
+            },
+        ))
+    }
+}
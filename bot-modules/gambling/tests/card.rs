@@ -0,0 +1,43 @@
+//! The `/card` command: accent parsing and the link between card backgrounds
+//! and the shop items that unlock them.
+
+use gambling::commands::card::parse_colour;
+use gambling::shop::CARD_ITEM_PREFIX;
+use gambling::{SHOP_ITEMS, ShopPage};
+use zayden_graphics::card::{CARD_BACKGROUNDS, DEFAULT_BACKGROUND, background};
+
+#[test]
+fn parses_hex_colours() {
+    assert_eq!(parse_colour("#5865F2"), Some(0x0058_65f2));
+    assert_eq!(parse_colour("5865f2"), Some(0x0058_65f2));
+    assert_eq!(parse_colour(" #fff "), Some(0x00ff_ffff));
+    assert_eq!(parse_colour("#12345"), None);
+    assert_eq!(parse_colour("#gggggg"), None);
+    assert_eq!(parse_colour("+12345"), None);
+    assert_eq!(parse_colour(""), None);
+}
+
+#[test]
+fn every_paid_background_is_sold_in_the_cards_shop() {
+    for bg in CARD_BACKGROUNDS.iter().filter(|bg| **bg != DEFAULT_BACKGROUND) {
+        let id = format!("{CARD_ITEM_PREFIX}{}", bg.id);
+        let item = SHOP_ITEMS.get(&id).unwrap_or_else(|| {
+            panic!("no shop item unlocks the {} background", bg.id)
+        });
+
+        assert_eq!(item.category, ShopPage::Cards);
+        assert!(!item.useable, "{id} should not be consumed by /inventory use");
+    }
+}
+
+#[test]
+fn every_cards_item_unlocks_a_background() {
+    for item in SHOP_ITEMS.iter().filter(|item| item.category == ShopPage::Cards) {
+        let id = item
+            .id
+            .strip_prefix(CARD_ITEM_PREFIX)
+            .expect("card items carry the card_ prefix");
+
+        assert_eq!(background(id).id, id, "{} unlocks no background", item.id);
+    }
+}
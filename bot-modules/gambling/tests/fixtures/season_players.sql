@@ -0,0 +1,15 @@
+-- Seed data for `tests/season.rs`.
+--
+-- `gambling_season_stats.user_id` references `gambling (user_id)`, which in
+-- turn references `users (id)`, so every player needs a row in both tables.
+INSERT INTO users(id, username)
+VALUES
+    (100, 'season-first'),
+(200, 'season-second'),
+(300, 'season-third');
+
+INSERT INTO gambling(user_id)
+VALUES
+    (100),
+(200),
+(300);
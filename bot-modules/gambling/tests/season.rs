@@ -0,0 +1,161 @@
+//! Tests for gambling seasons: live accumulation, the end-of-season archive and
+//! the placements that earn rewards.
+//!
+//! `SeasonCron` archives and pays out inside a single transaction, so the
+//! pieces it composes are exercised here directly against a migrated database.
+
+use gambling::common::leaderboard::split_season;
+use gambling::season::{SEASON_COINS, SEASON_HIGHERLOWER};
+use gambling::{GameDelta, SeasonManager, SeasonReward};
+use jiff::{SignedDuration, Timestamp};
+use serenity::all::UserId;
+use sqlx::PgPool;
+
+#[test]
+fn split_season_reads_archived_titles() {
+    assert_eq!(split_season("season_coins, season 3"), ("season_coins", Some(3)));
+    assert_eq!(split_season("season_coins"), ("season_coins", None));
+    assert_eq!(split_season("coins"), ("coins", None));
+}
+
+async fn running_season(pool: &PgPool) -> sqlx::Result<i32> {
+    let now = Timestamp::now();
+    let season = SeasonManager::create(
+        pool,
+        "Test Season",
+        now - SignedDuration::from_hours(1),
+        now + SignedDuration::from_hours(1),
+    )
+    .await?
+    .ok_or_else(|| sqlx::Error::Protocol("season was not created".to_string()))?;
+
+    Ok(season.id)
+}
+
+/// A second season overlapping one that hasn't been archived would count every
+/// game twice, so `create` refuses it.
+#[sqlx::test(migrations = "../../migrations")]
+async fn overlapping_seasons_are_rejected(pool: PgPool) -> sqlx::Result<()> {
+    running_season(&pool).await?;
+
+    let now = Timestamp::now();
+    let overlap = SeasonManager::create(
+        &pool,
+        "Overlap",
+        now,
+        now + SignedDuration::from_hours(2),
+    )
+    .await?;
+
+    assert!(overlap.is_none());
+
+    Ok(())
+}
+
+/// Only a running season can be ended early.
+#[sqlx::test(migrations = "../../migrations")]
+async fn an_upcoming_season_cannot_be_ended(pool: PgPool) -> sqlx::Result<()> {
+    let now = Timestamp::now();
+    let season = SeasonManager::create(
+        &pool,
+        "Upcoming",
+        now + SignedDuration::from_hours(1),
+        now + SignedDuration::from_hours(2),
+    )
+    .await?
+    .ok_or_else(|| sqlx::Error::Protocol("season was not created".to_string()))?;
+
+    assert!(!SeasonManager::end_now(&pool, season.id).await?);
+
+    Ok(())
+}
+
+/// An archived season has already paid out, so a reward added later would
+/// never be given.
+#[sqlx::test(migrations = "../../migrations")]
+async fn rewards_cannot_be_added_to_an_archived_season(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let season_id = running_season(&pool).await?;
+
+    let mut conn = pool.acquire().await?;
+    SeasonManager::archive(&mut conn, season_id).await?;
+    drop(conn);
+
+    let reward = SeasonReward {
+        leaderboard: SEASON_COINS.to_string(),
+        placement_min: 1,
+        placement_max: 1,
+        coins: 100,
+        ..SeasonReward::default()
+    };
+
+    assert!(!SeasonManager::add_reward(&pool, season_id, &reward).await?);
+    assert!(!SeasonManager::end_now(&pool, season_id).await?);
+
+    Ok(())
+}
+
+#[sqlx::test(migrations = "../../migrations", fixtures("season_players"))]
+async fn archive_ranks_and_rewards_placements(pool: PgPool) -> sqlx::Result<()> {
+    let season_id = running_season(&pool).await?;
+
+    let mut conn = pool.acquire().await?;
+    SeasonManager::record_game(&mut conn, UserId::new(100), &GameDelta::coins(500))
+        .await?;
+    SeasonManager::record_game(&mut conn, UserId::new(200), &GameDelta::coins(900))
+        .await?;
+    SeasonManager::record_game(&mut conn, UserId::new(300), &GameDelta::coins(-50))
+        .await?;
+    SeasonManager::record_higherlower(&mut conn, UserId::new(300), 7).await?;
+    drop(conn);
+
+    let reward = SeasonReward {
+        leaderboard: SEASON_COINS.to_string(),
+        placement_min: 1,
+        placement_max: 2,
+        gems: 5,
+        title: Some("High Roller".to_string()),
+        ..SeasonReward::default()
+    };
+    SeasonManager::add_reward(&pool, season_id, &reward).await?;
+
+    assert!(SeasonManager::end_now(&pool, season_id).await?);
+
+    let mut tx = pool.begin().await?;
+    let ended = SeasonManager::ended(&mut tx).await?;
+    assert_eq!(ended.iter().map(|s| s.id).collect::<Vec<_>>(), vec![season_id]);
+
+    SeasonManager::archive(&mut tx, season_id).await?;
+
+    let earned = SeasonManager::earned_rewards(&mut tx, season_id).await?;
+    for reward in &earned {
+        SeasonManager::grant(&mut tx, season_id, reward).await?;
+    }
+    tx.commit().await?;
+
+    let winners =
+        earned.iter().map(|r| (r.user_id, r.placement)).collect::<Vec<_>>();
+    assert_eq!(winners, vec![(200, 1), (100, 2)]);
+
+    // Only players with a streak appear on the higher-or-lower archive.
+    let streaks = sqlx::query_scalar!(
+        "SELECT user_id FROM gambling_season_archive WHERE season_id = $1 AND leaderboard = $2",
+        season_id,
+        SEASON_HIGHERLOWER
+    )
+    .fetch_all(&pool)
+    .await?;
+    assert_eq!(streaks, vec![300]);
+
+    assert_eq!(SeasonManager::titles(&pool, UserId::new(200)).await?, vec![
+        "High Roller".to_string()
+    ]);
+    assert!(SeasonManager::titles(&pool, UserId::new(300)).await?.is_empty());
+
+    // An archived season is never picked up again.
+    let mut conn = pool.acquire().await?;
+    assert!(SeasonManager::ended(&mut conn).await?.is_empty());
+
+    Ok(())
+}
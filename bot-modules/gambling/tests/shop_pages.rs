@@ -11,7 +11,7 @@ use std::str::FromStr;
 use gambling::ShopPage;
 
 const FIRST: ShopPage = ShopPage::Item;
-const LAST: ShopPage = ShopPage::Mine2;
+const LAST: ShopPage = ShopPage::Cards;
 
 /// Consecutive `(page, next_page)` pairs, without indexing.
 fn adjacent_pages() -> impl Iterator<Item = (ShopPage, ShopPage)> {
@@ -1,5 +1,5 @@
 use serenity::all::{GuildId, Mentionable, UserId};
-use zayden_app::config::{Cooldowns, GreetingsSettingsRow, SettingsStore};
+use zayden_app::config::{Actor, Cooldowns, GreetingsSettingsRow, SettingsStore};
 use zayden_core::as_i64;
 
 use crate::error::{GreetingsError, Result};
@@ -92,12 +92,13 @@ impl GreetingsSettings {
         guild_id: GuildId,
         morning: &str,
         night: &str,
+        actor: Actor,
     ) -> Result<()> {
         let morning = parse_message(morning)?;
         let night = parse_message(night)?;
 
         store
-            .update(as_i64(guild_id.get()), |row| {
+            .update_by(as_i64(guild_id.get()), actor, |row| {
                 row.morning_message = morning;
                 row.night_message = night;
             })
@@ -111,11 +112,12 @@ impl GreetingsSettings {
         guild_id: GuildId,
         requested: Cooldowns,
         floor: Cooldowns,
+        actor: Actor,
     ) -> Result<Cooldowns> {
         let clamped = requested.clamp_to(floor);
 
         store
-            .update(as_i64(guild_id.get()), |row| {
+            .update_by(as_i64(guild_id.get()), actor, |row| {
                 row.user_cooldown_secs = clamped.user_secs;
                 row.guild_cooldown_secs = clamped.guild_secs;
             })
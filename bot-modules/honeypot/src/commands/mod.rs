@@ -9,12 +9,23 @@ use serenity::all::{
     GuildId,
     Permissions,
     ResolvedValue,
+    User,
+};
+use tracing::debug;
+use zayden_app::config::{Actor, HoneypotSettingsRow, SharedAction};
+use zayden_app::services::threats;
+use zayden_core::{
+    InvocationCtx,
+    as_i64,
+    optional_option,
+    parse_options,
+    parse_subcommand,
+    required_option,
 };
-use zayden_core::{InvocationCtx, parse_options, parse_subcommand, required_option};
 
 use crate::error::{HoneypotError, Result};
 use crate::policy::is_staff;
-use crate::settings::HoneypotSettings;
+use crate::settings::{HoneypotConfig, HoneypotSettings};
 
 pub struct Honeypot;
 
@@ -48,6 +59,44 @@ impl Honeypot {
                 "status",
                 "Show the current honeypot configuration",
             ))
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::SubCommand,
+                    "allow",
+                    "Clear a user the shared threat list flagged or banned here",
+                )
+                .add_sub_option(
+                    CreateCommandOption::new(
+                        CommandOptionType::User,
+                        "user",
+                        "The user to whitelist",
+                    )
+                    .required(true),
+                )
+                .add_sub_option(
+                    CreateCommandOption::new(
+                        CommandOptionType::String,
+                        "note",
+                        "Why they were cleared",
+                    )
+                    .max_length(200),
+                ),
+            )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::SubCommand,
+                    "revoke",
+                    "Take a user off the shared threat list whitelist",
+                )
+                .add_sub_option(
+                    CreateCommandOption::new(
+                        CommandOptionType::User,
+                        "user",
+                        "The user to remove from the whitelist",
+                    )
+                    .required(true),
+                ),
+            )
     }
 
     pub async fn run(cx: &InvocationCtx<'_>) -> Result<()> {
@@ -62,6 +111,8 @@ impl Honeypot {
             "set" => set(cx, guild_id, options).await,
             "disable" => disable(cx, guild_id).await,
             "status" => status(cx, guild_id).await,
+            "allow" => allow(cx, guild_id, options).await,
+            "revoke" => revoke(cx, guild_id, options).await,
             _ => Err(HoneypotError::UnknownSubcommand(name.to_string())),
         }
     }
@@ -89,7 +140,13 @@ async fn set(
 
     cx.interaction.defer_ephemeral(&cx.ctx.http).await?;
 
-    HoneypotSettings::arm(&cx.app.settings.honeypot, guild_id, channel_id).await?;
+    HoneypotSettings::arm(
+        &cx.app.settings.honeypot,
+        guild_id,
+        channel_id,
+        Actor::command(cx.interaction.user.id.get()),
+    )
+    .await?;
 
     cx.interaction
         .edit_response(
@@ -110,7 +167,12 @@ async fn set(
 async fn disable(cx: &InvocationCtx<'_>, guild_id: GuildId) -> Result<()> {
     cx.interaction.defer_ephemeral(&cx.ctx.http).await?;
 
-    HoneypotSettings::disarm(&cx.app.settings.honeypot, guild_id).await?;
+    HoneypotSettings::disarm(
+        &cx.app.settings.honeypot,
+        guild_id,
+        Actor::command(cx.interaction.user.id.get()),
+    )
+    .await?;
 
     cx.interaction
         .edit_response(
@@ -125,11 +187,20 @@ async fn disable(cx: &InvocationCtx<'_>, guild_id: GuildId) -> Result<()> {
 async fn status(cx: &InvocationCtx<'_>, guild_id: GuildId) -> Result<()> {
     cx.interaction.defer_ephemeral(&cx.ctx.http).await?;
 
-    let config = HoneypotSettings::get(&cx.app.settings.honeypot, guild_id).await?;
+    let row = cx.app.settings.honeypot.get(as_i64(guild_id.get())).await?;
+    let config = HoneypotConfig::from(row.as_ref());
+
+    let traps = row.traps.len();
+    let content = match config.channel_id {
+        None if traps == 0 => {
+            "Honeypot is disabled. Use `/honeypot set` to arm it.".to_string()
+        },
+        channel_id => {
+            let armed = channel_id.map_or_else(
+                || "Honeypot has no honeypot channel".to_string(),
+                |channel_id| format!("Honeypot is armed on <#{channel_id}>"),
+            );
 
-    let content = config.channel_id.map_or_else(
-        || "Honeypot is disabled. Use `/honeypot set` to arm it.".to_string(),
-        |channel_id| {
             let mut exemptions = vec!["the server owner".to_string()];
             if config.exempt_admins {
                 exemptions.push("admins and Manage Server holders".to_string());
@@ -139,13 +210,109 @@ async fn status(cx: &InvocationCtx<'_>, guild_id: GuildId) -> Result<()> {
             }
 
             format!(
-                "Honeypot is armed on <#{channel_id}>.\n**Exempt:** {}\n\n\
-                 Change the exemptions from the dashboard.",
+                "{armed}.\n**More traps:** {traps}\n**Exempt:** {}\n\
+                 **Shared threat list:** {}\n\n\
+                 Change the traps, exemptions and sharing from the dashboard.",
                 exemptions.join(", "),
+                sharing(&row),
             )
         },
+    };
+
+    cx.interaction
+        .edit_response(&cx.ctx.http, EditInteractionResponse::new().content(content))
+        .await?;
+
+    Ok(())
+}
+
+fn sharing(row: &HoneypotSettingsRow) -> String {
+    if !row.share_hits {
+        return "not shared".to_string();
+    }
+
+    if row.shared_threshold <= 0 {
+        return "shared; joins are not screened".to_string();
+    }
+
+    let action = match row.shared_action() {
+        SharedAction::Flag => "flagged",
+        SharedAction::Ban => "banned",
+    };
+
+    format!(
+        "shared; joins seen in {} or more other servers are {action}",
+        row.shared_threshold,
+    )
+}
+
+async fn allow(
+    cx: &InvocationCtx<'_>,
+    guild_id: GuildId,
+    mut options: HashMap<&str, ResolvedValue<'_>>,
+) -> Result<()> {
+    let (user, _): (&User, _) = required_option(&mut options, "user")?;
+    let note: &str = optional_option(&mut options, "note").unwrap_or_default();
+
+    cx.interaction.defer_ephemeral(&cx.ctx.http).await?;
+
+    let banned = threats::whitelist(
+        &cx.app.db,
+        as_i64(guild_id.get()),
+        as_i64(user.id.get()),
+        note.trim(),
+        as_i64(cx.interaction.user.id.get()),
+    )
+    .await?;
+
+    let mut content = format!(
+        "<@{}> is whitelisted: the shared threat list will no longer flag or \
+         ban them here.",
+        user.id,
     );
 
+    if banned {
+        match guild_id
+            .unban(&cx.ctx.http, user.id, Some("Honeypot: whitelisted"))
+            .await
+        {
+            Ok(()) => content.push_str(" Their shared-list ban has been lifted."),
+            // Already unbanned by hand.
+            Err(e) => {
+                debug!(%guild_id, user_id = %user.id, error = %e, "unban failed");
+            },
+        }
+    }
+
+    cx.interaction
+        .edit_response(&cx.ctx.http, EditInteractionResponse::new().content(content))
+        .await?;
+
+    Ok(())
+}
+
+async fn revoke(
+    cx: &InvocationCtx<'_>,
+    guild_id: GuildId,
+    mut options: HashMap<&str, ResolvedValue<'_>>,
+) -> Result<()> {
+    let (user, _): (&User, _) = required_option(&mut options, "user")?;
+
+    cx.interaction.defer_ephemeral(&cx.ctx.http).await?;
+
+    let removed = threats::unwhitelist(
+        &cx.app.db,
+        as_i64(guild_id.get()),
+        as_i64(user.id.get()),
+    )
+    .await?;
+
+    let content = if removed {
+        format!("<@{}> is no longer whitelisted.", user.id)
+    } else {
+        format!("<@{}> wasn't whitelisted.", user.id)
+    };
+
     cx.interaction
         .edit_response(&cx.ctx.http, EditInteractionResponse::new().content(content))
         .await?;
@@ -3,11 +3,17 @@ pub mod error;
 pub mod guard;
 pub mod message_create;
 pub mod policy;
+pub mod role_update;
 pub mod settings;
+pub mod shared;
+pub mod trap;
 
 pub use commands::Honeypot;
 pub use error::{HoneypotError, Result};
 pub use message_create::{BAN_REASON, HoneypotHit, HoneypotOutcome, message_create};
 pub use policy::{ExemptionPolicy, GuildFacts, is_exempt};
+pub use role_update::{ROLE_TRAP_REASON, role_update};
 pub use serenity::all::{ChannelId, GuildId, RoleId};
 pub use settings::{HoneypotConfig, HoneypotSettings, HoneypotStore};
+pub use shared::{SHARED_BAN_REASON, SharedMatch};
+pub use trap::report;
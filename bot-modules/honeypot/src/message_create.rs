@@ -1,23 +1,25 @@
 use std::sync::Arc;
-use std::time::Duration;
 
 use serenity::all::{ChannelId, Context, GuildId, Message, RoleId, UserId};
-use tracing::{debug, error, warn};
-use zayden_app::config::HoneypotSettingsRow;
-use zayden_core::as_u64;
-use zayden_core::retry::{RetryBudget, retry_transient};
+use tracing::{debug, warn};
+use zayden_app::config::{HoneypotSettingsRow, HoneypotTrap};
+use zayden_app::services::threats::message_hash;
+use zayden_core::{as_i64, as_u64};
 
 use crate::error::Result;
 use crate::guard::GUARD;
 use crate::policy::{self, ExemptionPolicy, GuildFacts};
+use crate::trap::{self, keeps_member};
 
 pub const BAN_REASON: &str = "Honeypot: posted in the honeypot channel";
-const UNBAN_RETRY: RetryBudget = RetryBudget::new(3, Duration::from_millis(250));
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum HoneypotOutcome {
     SoftBanned,
     BanStanding,
+    Banned,
+    TimedOut,
+    Quarantined,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -60,6 +62,11 @@ pub struct HoneypotHit {
     pub username: String,
     pub guild_id: GuildId,
     pub outcome: HoneypotOutcome,
+    /// The trap that went off.
+    pub trap: HoneypotTrap,
+    /// For the shared threat list; see [`message_hash`]. Bait roles have no
+    /// message.
+    pub message_hash: Option<String>,
 }
 
 pub async fn message_create(
@@ -72,9 +79,9 @@ pub async fn message_create(
     };
 
     let channel_id = msg.channel_id.expect_channel();
-    if !is_decoy_hit(channel_id, settings.channel_id) {
+    let Some(trap) = settings.channel_trap(as_i64(channel_id.get())) else {
         return Ok(None);
-    }
+    };
 
     let author_id = msg.author.id;
 
@@ -94,54 +101,48 @@ pub async fn message_create(
 
     let member_roles: &[_] = msg.member.as_ref().map_or(&[], |member| &member.roles);
     let policy = ExemptionPolicy::from(settings.as_ref());
-    let purge_seconds = settings.purge_seconds_u32();
-
-    let purge_seconds =
-        match decide(author_id, member_roles, &facts, &policy, purge_seconds) {
-            Action::Spare => {
-                debug!(%guild_id, %author_id, "honeypot post from an exempt member");
-                GUARD.release(guild_id, author_id).await;
-                return Ok(None);
-            },
-            Action::Ban { purge_seconds } => purge_seconds,
-        };
-
-    if let Err(e) =
-        guild_id.ban(&ctx.http, author_id, purge_seconds, Some(BAN_REASON)).await
+
+    if decide(author_id, member_roles, &facts, &policy, trap.purge_seconds_u32())
+        == Action::Spare
     {
+        debug!(%guild_id, %author_id, "honeypot post from an exempt member");
         GUARD.release(guild_id, author_id).await;
-        return Err(e.into());
+        return Ok(None);
     }
 
-    let unban = retry_transient(UNBAN_RETRY, || {
-        guild_id.unban(&ctx.http, author_id, Some(BAN_REASON))
-    })
-    .await;
-
-    let outcome = outcome_of(&unban);
-    match &unban {
-        Ok(()) => warn!(
-            %guild_id,
-            %author_id,
-            username = %msg.author.name,
-            %channel_id,
-            "honeypot soft-banned a member",
-        ),
-        Err(e) => error!(
-            %guild_id,
-            %author_id,
-            username = %msg.author.name,
-            %channel_id,
-            error = %e,
-            attempts = UNBAN_RETRY.attempts,
-            "honeypot unban failed after retries: the ban is still standing",
-        ),
+    let outcome = match trap::spring(
+        &ctx.http, guild_id, author_id, &trap, BAN_REASON,
+    )
+    .await
+    {
+        Ok(outcome) => outcome,
+        Err(e) => {
+            GUARD.release(guild_id, author_id).await;
+            return Err(e);
+        },
+    };
+
+    // A ban purges the post; a timeout or quarantine leaves it up.
+    if keeps_member(trap.action) {
+        msg.delete(&ctx.http, Some(BAN_REASON)).await?;
     }
 
+    warn!(
+        %guild_id,
+        %author_id,
+        username = %msg.author.name,
+        %channel_id,
+        action = trap.action.as_str(),
+        ?outcome,
+        "honeypot channel trap went off",
+    );
+
     Ok(Some(HoneypotHit {
         user_id: author_id,
         username: msg.author.name.to_string(),
         guild_id,
         outcome,
+        trap,
+        message_hash: Some(message_hash(&msg.content)),
     }))
 }
@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 
 use serenity::all::{Permissions, RoleId, UserId};
-use zayden_app::config::HoneypotSettingsRow;
+use zayden_app::config::{AutomodSettingsRow, HoneypotSettingsRow};
 use zayden_core::as_u64;
 
 #[derive(Debug, Clone)]
@@ -26,6 +26,15 @@ impl From<&HoneypotSettingsRow> for ExemptionPolicy {
     }
 }
 
+impl From<&AutomodSettingsRow> for ExemptionPolicy {
+    fn from(row: &AutomodSettingsRow) -> Self {
+        Self {
+            exempt_admins: row.exempt_admins,
+            exempt_role_id: row.exempt_role_id.map(|id| RoleId::new(as_u64(id))),
+        }
+    }
+}
+
 #[must_use]
 pub fn guild_permissions(
     member_roles: &[RoleId],
@@ -0,0 +1,209 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use serenity::all::audit_log::{Action as AuditAction, Change, MemberAction};
+use serenity::all::{
+    Context,
+    GuildId,
+    GuildMemberUpdateEvent,
+    Http,
+    RoleId,
+    UserId,
+};
+use tokio::time::sleep;
+use tracing::{debug, warn};
+use zayden_app::config::HoneypotSettingsRow;
+use zayden_core::{as_i64, as_u64};
+
+use crate::error::Result;
+use crate::guard::GUARD;
+use crate::message_create::{Action, HoneypotHit, decide};
+use crate::policy::ExemptionPolicy;
+use crate::trap::{self, keeps_member};
+
+pub const ROLE_TRAP_REASON: &str = "Honeypot: took a bait role";
+
+/// How old a role grant in the audit log may be and still be the one this
+/// update reports.
+const GRANT_WINDOW_SECS: i64 = 30;
+/// Discord can deliver the member update before the audit log entry for it
+/// is readable, so a missing grant is looked for again this many times.
+const GRANT_ATTEMPTS: u32 = 3;
+const GRANT_BACKOFF: Duration = Duration::from_secs(1);
+
+/// Who gave a member a bait role, going by the guild's audit log.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Assigner {
+    /// The member, or a bot acting for them, such as a role menu.
+    Themselves,
+    /// Another member, who knows what the role is.
+    Staff,
+}
+
+/// The bait role among `bait` that the audit log shows `user_id` was just
+/// given, and who gave it. `None` if the log can't be read or has no recent
+/// grant of one, such as when the member held it before it was made a trap.
+async fn grant(
+    http: &Http,
+    guild_id: GuildId,
+    user_id: UserId,
+    bait: &[RoleId],
+) -> Option<(RoleId, Assigner)> {
+    let action = AuditAction::Member(MemberAction::RoleUpdate);
+
+    for attempt in 1..=GRANT_ATTEMPTS {
+        let logs =
+            match guild_id.audit_logs(http, Some(action), None, None, None).await {
+                Ok(logs) => logs,
+                Err(e) => {
+                    debug!(%guild_id, error = ?e, "could not read the audit log");
+                    return None;
+                },
+            };
+
+        let now = jiff::Timestamp::now().as_second();
+        let found = logs
+            .entries
+            .iter()
+            .filter(|entry| {
+                entry.target_id.is_some_and(|id| id.get() == user_id.get())
+                    && now - entry.id.created_at().unix_timestamp()
+                        <= GRANT_WINDOW_SECS
+            })
+            .find_map(|entry| {
+                let role = entry.changes.iter().flatten().find_map(|change| {
+                    let Change::RolesAdded { new: Some(roles), .. } = change else {
+                        return None;
+                    };
+                    roles.iter().map(|role| role.id).find(|id| bait.contains(id))
+                })?;
+                Some((role, entry.user_id))
+            });
+
+        if let Some((role, giver)) = found {
+            let by_bot =
+                logs.users.iter().any(|user| user.id == giver && user.bot());
+
+            let assigner = if giver == user_id || by_bot {
+                Assigner::Themselves
+            } else {
+                Assigner::Staff
+            };
+            return Some((role, assigner));
+        }
+
+        if attempt < GRANT_ATTEMPTS {
+            sleep(GRANT_BACKOFF).await;
+        }
+    }
+
+    None
+}
+
+/// Springs a bait-role trap when a member is given one of the guild's bait
+/// roles, usually by picking it from a self-assignable role menu. Which role
+/// was added, and by whom, comes from the guild's audit log, so members who
+/// held a role before it was made a trap are left alone, and nothing happens
+/// without the View Audit Log permission. A role handed out by staff never
+/// springs.
+pub async fn role_update(
+    ctx: &Context,
+    event: &GuildMemberUpdateEvent,
+    settings: &Arc<HoneypotSettingsRow>,
+) -> Result<Option<HoneypotHit>> {
+    if event.user.bot() {
+        return Ok(None);
+    }
+
+    let bait: Vec<RoleId> = event
+        .roles
+        .iter()
+        .copied()
+        .filter(|role| settings.role_trap(&[as_i64(role.get())]).is_some())
+        .collect();
+    if bait.is_empty() {
+        return Ok(None);
+    }
+
+    let guild_id = event.guild_id;
+    let user_id = event.user.id;
+
+    let Some((role, assigner)) = grant(&ctx.http, guild_id, user_id, &bait).await
+    else {
+        debug!(%guild_id, %user_id, "no recent bait role grant in the audit log");
+        return Ok(None);
+    };
+    if assigner == Assigner::Staff {
+        debug!(%guild_id, %user_id, "bait role given by staff; not springing");
+        return Ok(None);
+    }
+
+    let Some(trap) = settings.role_trap(&[as_i64(role.get())]).cloned() else {
+        return Ok(None);
+    };
+
+    if !GUARD.claim(guild_id, user_id).await {
+        debug!(%guild_id, %user_id, "honeypot already actioned this user");
+        return Ok(None);
+    }
+
+    let facts = match GUARD.facts(ctx, guild_id).await {
+        Ok(facts) => facts,
+        Err(e) => {
+            GUARD.release(guild_id, user_id).await;
+            return Err(e);
+        },
+    };
+
+    let policy = ExemptionPolicy::from(settings.as_ref());
+
+    if decide(user_id, &event.roles, &facts, &policy, trap.purge_seconds_u32())
+        == Action::Spare
+    {
+        debug!(%guild_id, %user_id, "bait role taken by an exempt member");
+        GUARD.release(guild_id, user_id).await;
+        return Ok(None);
+    }
+
+    let outcome =
+        match trap::spring(&ctx.http, guild_id, user_id, &trap, ROLE_TRAP_REASON)
+            .await
+        {
+            Ok(outcome) => outcome,
+            Err(e) => {
+                GUARD.release(guild_id, user_id).await;
+                return Err(e);
+            },
+        };
+
+    // Left with the bait, they would look untouched to the next moderator.
+    if keeps_member(trap.action) {
+        ctx.http
+            .remove_member_role(
+                guild_id,
+                user_id,
+                RoleId::new(as_u64(trap.target_id)),
+                Some(ROLE_TRAP_REASON),
+            )
+            .await?;
+    }
+
+    warn!(
+        %guild_id,
+        %user_id,
+        username = %event.user.name,
+        role_id = trap.target_id,
+        action = trap.action.as_str(),
+        ?outcome,
+        "honeypot bait role went off",
+    );
+
+    Ok(Some(HoneypotHit {
+        user_id,
+        username: event.user.name.to_string(),
+        guild_id,
+        outcome,
+        trap,
+        message_hash: None,
+    }))
+}
@@ -1,5 +1,5 @@
 use serenity::all::{ChannelId, GuildId, RoleId};
-use zayden_app::config::{HoneypotSettingsRow, SettingsStore};
+use zayden_app::config::{Actor, HoneypotSettingsRow, SettingsStore};
 use zayden_core::{as_i64, as_u64};
 
 use crate::error::{HoneypotError, Result};
@@ -117,9 +117,10 @@ impl HoneypotSettings {
         store: &HoneypotStore,
         guild_id: GuildId,
         channel_id: ChannelId,
+        actor: Actor,
     ) -> Result<HoneypotConfig> {
         let row = store
-            .update(as_i64(guild_id.get()), |row| {
+            .update_by(as_i64(guild_id.get()), actor, |row| {
                 HoneypotConfig::arm_row(row, channel_id);
             })
             .await?;
@@ -130,9 +131,11 @@ impl HoneypotSettings {
     pub async fn disarm(
         store: &HoneypotStore,
         guild_id: GuildId,
+        actor: Actor,
     ) -> Result<HoneypotConfig> {
-        let row =
-            store.update(as_i64(guild_id.get()), HoneypotConfig::disarm_row).await?;
+        let row = store
+            .update_by(as_i64(guild_id.get()), actor, HoneypotConfig::disarm_row)
+            .await?;
 
         Ok(HoneypotConfig::from(row.as_ref()))
     }
@@ -141,9 +144,11 @@ impl HoneypotSettings {
         store: &HoneypotStore,
         guild_id: GuildId,
         config: HoneypotConfig,
+        actor: Actor,
     ) -> Result<HoneypotConfig> {
-        let row =
-            store.update(as_i64(guild_id.get()), |row| config.apply(row)).await?;
+        let row = store
+            .update_by(as_i64(guild_id.get()), actor, |row| config.apply(row))
+            .await?;
 
         Ok(HoneypotConfig::from(row.as_ref()))
     }
@@ -0,0 +1,103 @@
+//! Screening joins against the shared threat list. A guild that shares its
+//! hits can flag or ban members who tripped honeypots in enough other sharing
+//! guilds; see [`zayden_app::services::threats`].
+
+use serenity::all::{
+    ChannelId,
+    CreateMessage,
+    GuildId,
+    Http,
+    Member,
+    Mentionable,
+    UserId,
+};
+use sqlx::PgPool;
+use tracing::warn;
+use zayden_app::config::{HoneypotSettingsRow, SharedAction};
+use zayden_app::services::threats;
+use zayden_core::{as_i64, as_u64};
+
+use crate::error::Result;
+
+pub const SHARED_BAN_REASON: &str =
+    "Honeypot: tripped honeypots in other servers (shared threat list)";
+
+#[derive(Debug, Clone)]
+pub struct SharedMatch {
+    pub user_id: UserId,
+    pub username: String,
+    pub guild_id: GuildId,
+    /// Other sharing guilds the member tripped a honeypot in.
+    pub guilds: i64,
+    pub action: SharedAction,
+}
+
+/// Flags or bans a joining member the shared list has seen in at least the
+/// guild's threshold of other sharing guilds, unless the guild has
+/// whitelisted them.
+pub async fn member_join(
+    http: &Http,
+    member: &Member,
+    settings: &HoneypotSettingsRow,
+    pool: &PgPool,
+) -> Result<Option<SharedMatch>> {
+    if member.user.bot() || !settings.share_hits || settings.shared_threshold <= 0 {
+        return Ok(None);
+    }
+
+    let guild_id = as_i64(member.guild_id.get());
+    let user_id = as_i64(member.user.id.get());
+
+    let guilds = threats::shared_guild_count(pool, guild_id, user_id).await?;
+    if !settings.screens(guilds)
+        || threats::is_whitelisted(pool, guild_id, user_id).await?
+    {
+        return Ok(None);
+    }
+
+    let action = settings.shared_action();
+    if action == SharedAction::Ban {
+        member
+            .guild_id
+            .ban(http, member.user.id, 0, Some(SHARED_BAN_REASON))
+            .await?;
+    }
+
+    threats::record_screening(pool, guild_id, user_id, action, guilds).await?;
+
+    warn!(
+        guild_id = %member.guild_id,
+        user_id = %member.user.id,
+        guilds,
+        action = action.as_str(),
+        "shared threat list matched a joining member",
+    );
+
+    if let Some(channel) = settings.log_channel_id {
+        let outcome = match action {
+            SharedAction::Flag => "Keep an eye on them",
+            SharedAction::Ban => "They were banned",
+        };
+
+        ChannelId::new(as_u64(channel))
+            .send_message(
+                http,
+                CreateMessage::new().content(format!(
+                    "{} ({}) joined after tripping honeypots in {guilds} other \
+                     server(s) on the shared threat list. {outcome}; if they are \
+                     legitimate, clear them with `/honeypot allow`.",
+                    member.mention(),
+                    member.user.id,
+                )),
+            )
+            .await?;
+    }
+
+    Ok(Some(SharedMatch {
+        user_id: member.user.id,
+        username: member.user.name.to_string(),
+        guild_id: member.guild_id,
+        guilds,
+        action,
+    }))
+}
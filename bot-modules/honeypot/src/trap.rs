@@ -0,0 +1,146 @@
+//! Carrying out a trap's action, and reporting hits to the log channel.
+
+use std::time::Duration;
+
+use serenity::all::{
+    ChannelId,
+    CreateMessage,
+    GuildId,
+    Http,
+    Mentionable,
+    RoleId,
+    Timestamp,
+    UserId,
+};
+use tracing::error;
+use zayden_app::config::{HoneypotSettingsRow, HoneypotTrap, TrapAction, TrapKind};
+use zayden_core::as_u64;
+use zayden_core::retry::{RetryBudget, retry_transient};
+
+use crate::error::{HoneypotError, Result};
+use crate::message_create::{HoneypotHit, HoneypotOutcome, outcome_of};
+
+const UNBAN_RETRY: RetryBudget = RetryBudget::new(3, Duration::from_millis(250));
+
+/// Whether the action leaves the member in the guild, so whatever set the
+/// trap off (the bait post or the bait role) has to be cleaned up by hand.
+#[must_use]
+pub const fn keeps_member(action: TrapAction) -> bool {
+    matches!(action, TrapAction::Timeout | TrapAction::Quarantine)
+}
+
+/// Applies `trap`'s action to `user_id`.
+pub async fn spring(
+    http: &Http,
+    guild_id: GuildId,
+    user_id: UserId,
+    trap: &HoneypotTrap,
+    reason: &str,
+) -> Result<HoneypotOutcome> {
+    match trap.action {
+        TrapAction::SoftBan => {
+            guild_id
+                .ban(http, user_id, trap.purge_seconds_u32(), Some(reason))
+                .await?;
+
+            let unban = retry_transient(UNBAN_RETRY, || {
+                guild_id.unban(http, user_id, Some(reason))
+            })
+            .await;
+
+            if let Err(e) = &unban {
+                error!(
+                    %guild_id,
+                    %user_id,
+                    error = %e,
+                    attempts = UNBAN_RETRY.attempts,
+                    "honeypot unban failed after retries: the ban is still standing",
+                );
+            }
+
+            Ok(outcome_of(&unban))
+        },
+        TrapAction::Ban => {
+            guild_id
+                .ban(http, user_id, trap.purge_seconds_u32(), Some(reason))
+                .await?;
+
+            Ok(HoneypotOutcome::Banned)
+        },
+        TrapAction::Timeout => {
+            let mut member = guild_id.member(http, user_id).await?;
+
+            let until = Timestamp::from_unix_timestamp(
+                jiff::Timestamp::now()
+                    .as_second()
+                    .saturating_add(trap.timeout().as_secs()),
+            )
+            .map_err(|e| HoneypotError::Internal(e.to_string()))?;
+            member.disable_communication_until(http, until).await?;
+
+            Ok(HoneypotOutcome::TimedOut)
+        },
+        TrapAction::Quarantine => {
+            let role = trap.quarantine_role_id.ok_or_else(|| {
+                HoneypotError::Internal("quarantine trap without a role".to_string())
+            })?;
+
+            http.add_member_role(
+                guild_id,
+                user_id,
+                RoleId::new(as_u64(role)),
+                Some(reason),
+            )
+            .await?;
+
+            Ok(HoneypotOutcome::Quarantined)
+        },
+    }
+}
+
+/// Posts the hit to the guild's log channel, if it has one.
+pub async fn report(
+    http: &Http,
+    settings: &HoneypotSettingsRow,
+    hit: &HoneypotHit,
+) -> Result<()> {
+    let Some(channel) = settings.log_channel_id else {
+        return Ok(());
+    };
+
+    let trigger = match hit.trap.kind {
+        TrapKind::Channel => {
+            format!(
+                "posted in {}",
+                ChannelId::new(as_u64(hit.trap.target_id)).mention()
+            )
+        },
+        TrapKind::Role => {
+            format!(
+                "took the bait role {}",
+                RoleId::new(as_u64(hit.trap.target_id)).mention()
+            )
+        },
+    };
+
+    let outcome = match hit.outcome {
+        HoneypotOutcome::SoftBanned => "soft-banned",
+        HoneypotOutcome::BanStanding => "banned (the soft-ban's unban failed)",
+        HoneypotOutcome::Banned => "banned",
+        HoneypotOutcome::TimedOut => "timed out",
+        HoneypotOutcome::Quarantined => "quarantined",
+    };
+
+    ChannelId::new(as_u64(channel))
+        .send_message(
+            http,
+            CreateMessage::new().content(format!(
+                "Honeypot: {} ({}) {trigger} and was {outcome}.",
+                hit.user_id.mention(),
+                hit.user_id,
+            )),
+        )
+        .await?;
+
+    Ok(())
+}
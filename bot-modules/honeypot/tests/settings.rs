@@ -21,13 +21,12 @@ const GUILD: u64 = 100;
 const CHANNEL: u64 = 555_000_000_000_000_001;
 const ROLE: u64 = 777_000_000_000_000_002;
 
-const fn armed_row() -> HoneypotSettingsRow {
+fn armed_row() -> HoneypotSettingsRow {
     HoneypotSettingsRow {
-        guild_id: as_i64(GUILD),
         channel_id: Some(as_i64(CHANNEL)),
         exempt_admins: true,
         exempt_role_id: Some(as_i64(ROLE)),
-        purge_seconds: HoneypotSettingsRow::DEFAULT_PURGE_SECONDS,
+        ..HoneypotSettingsRow::empty(as_i64(GUILD))
     }
 }
 
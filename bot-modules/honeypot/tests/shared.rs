@@ -0,0 +1,74 @@
+//! The shared threat list's pure rules: when a joining member is acted on,
+//! how the sharing form fields parse, and what counts as the same bait
+//! message across servers.
+//!
+//! The screening itself needs a database and Discord, so as in `decision.rs`
+//! what is pinned is the decision it routes on.
+
+use zayden_app::config::{HoneypotSettingsRow, SettingsRow, SharedAction};
+use zayden_app::services::threats::message_hash;
+
+const GUILD: i64 = 100;
+
+fn sharing(threshold: i32) -> HoneypotSettingsRow {
+    HoneypotSettingsRow {
+        share_hits: true,
+        shared_threshold: threshold,
+        ..HoneypotSettingsRow::empty(GUILD)
+    }
+}
+
+#[test]
+fn joins_are_screened_from_the_threshold_up() {
+    let row = sharing(2);
+
+    assert!(!row.screens(0));
+    assert!(!row.screens(1));
+    assert!(row.screens(2));
+    assert!(row.screens(5));
+}
+
+#[test]
+fn a_zero_threshold_shares_without_acting() {
+    assert!(!sharing(0).screens(25));
+}
+
+// Screening is reciprocal: a server that keeps its own hits to itself doesn't
+// act on anyone else's.
+#[test]
+fn a_server_that_does_not_share_never_screens() {
+    let row = HoneypotSettingsRow { share_hits: false, ..sharing(1) };
+
+    assert!(!row.screens(10));
+}
+
+#[test]
+fn the_threshold_field_clamps_and_falls_back_to_off() {
+    assert_eq!(HoneypotSettingsRow::parse_shared_threshold(" 3 "), 3);
+    assert_eq!(HoneypotSettingsRow::parse_shared_threshold("-4"), 0);
+    assert_eq!(HoneypotSettingsRow::parse_shared_threshold("oops"), 0);
+    assert_eq!(
+        HoneypotSettingsRow::parse_shared_threshold("1000"),
+        HoneypotSettingsRow::MAX_SHARED_THRESHOLD,
+    );
+}
+
+#[test]
+fn an_unknown_action_reads_as_the_one_that_cannot_hurt() {
+    assert_eq!(SharedAction::parse("ban"), SharedAction::Ban);
+    assert_eq!(SharedAction::parse(" flag "), SharedAction::Flag);
+    assert_eq!(SharedAction::parse("nuke"), SharedAction::Flag);
+    assert_eq!(
+        HoneypotSettingsRow::empty(GUILD).shared_action(),
+        SharedAction::Flag
+    );
+}
+
+#[test]
+fn the_same_spam_hashes_alike_across_servers() {
+    let hash = message_hash("Free Nitro at example.com");
+
+    assert_eq!(hash, message_hash("  free   nitro\nAT example.com "));
+    assert_ne!(hash, message_hash("Free Nitro at example.org"));
+    assert_eq!(hash.len(), 32);
+}
@@ -0,0 +1,117 @@
+//! Which trap a channel post or a new role sets off, and how the trap form
+//! fields parse.
+//!
+//! The honeypot channel stays the guild's soft-ban trap; everything else is
+//! an extra trap with its own action. Carrying the action out needs Discord,
+//! so as in `decision.rs` what is pinned is the trap the handlers route on.
+
+use sqlx::types::Json;
+use zayden_app::config::{
+    HoneypotSettingsRow,
+    HoneypotTrap,
+    SettingsRow,
+    TrapAction,
+    TrapKind,
+};
+
+const GUILD: i64 = 100;
+const HONEYPOT_CHANNEL: i64 = 555;
+const TRAP_CHANNEL: i64 = 556;
+const BAIT_ROLE: i64 = 12;
+const QUARANTINE_ROLE: i64 = 13;
+const PURGE: i32 = 3600;
+
+fn trap(kind: TrapKind, target_id: i64, action: TrapAction) -> HoneypotTrap {
+    HoneypotTrap {
+        kind,
+        target_id,
+        action,
+        purge_seconds: 0,
+        timeout_minutes: HoneypotTrap::DEFAULT_TIMEOUT_MINUTES,
+        quarantine_role_id: Some(QUARANTINE_ROLE),
+    }
+}
+
+fn row() -> HoneypotSettingsRow {
+    HoneypotSettingsRow {
+        channel_id: Some(HONEYPOT_CHANNEL),
+        purge_seconds: PURGE,
+        traps: Json(vec![
+            trap(TrapKind::Channel, TRAP_CHANNEL, TrapAction::Timeout),
+            trap(TrapKind::Role, BAIT_ROLE, TrapAction::Quarantine),
+        ]),
+        ..HoneypotSettingsRow::empty(GUILD)
+    }
+}
+
+#[test]
+fn the_honeypot_channel_is_a_soft_ban_with_the_guild_purge() {
+    let trap = row().channel_trap(HONEYPOT_CHANNEL).expect("armed channel");
+
+    assert_eq!(trap.action, TrapAction::SoftBan);
+    assert_eq!(trap.purge_seconds, PURGE);
+}
+
+#[test]
+fn an_extra_trap_channel_keeps_its_own_action() {
+    let trap = row().channel_trap(TRAP_CHANNEL).expect("trap channel");
+
+    assert_eq!(trap.action, TrapAction::Timeout);
+}
+
+#[test]
+fn other_channels_set_nothing_off() {
+    assert_eq!(row().channel_trap(1), None);
+    assert_eq!(
+        HoneypotSettingsRow::empty(GUILD).channel_trap(HONEYPOT_CHANNEL),
+        None
+    );
+}
+
+// Kind and target are matched together: a channel trap's id among a member's
+// roles, or a bait role's id as a channel, must not set anything off.
+#[test]
+fn kinds_do_not_cross() {
+    let row = row();
+
+    assert_eq!(row.channel_trap(BAIT_ROLE), None);
+    assert_eq!(row.role_trap(&[TRAP_CHANNEL]), None);
+}
+
+#[test]
+fn a_bait_role_among_the_members_roles_is_found() {
+    let row = row();
+
+    let trap = row.role_trap(&[1, BAIT_ROLE, 2]).expect("bait role");
+    assert_eq!(trap.action, TrapAction::Quarantine);
+    assert_eq!(row.role_trap(&[1, 2]), None);
+    assert_eq!(row.role_trap(&[]), None);
+}
+
+#[test]
+fn trap_kinds_and_actions_round_trip() {
+    for kind in TrapKind::ALL {
+        assert_eq!(TrapKind::parse(kind.as_str()), Some(kind));
+    }
+    for action in TrapAction::ALL {
+        assert_eq!(TrapAction::parse(action.as_str()), Some(action));
+    }
+
+    assert_eq!(TrapAction::parse(" ban "), Some(TrapAction::Ban));
+    assert_eq!(TrapAction::parse("kick"), None);
+    assert_eq!(TrapKind::parse(""), None);
+}
+
+#[test]
+fn timeouts_are_clamped_to_what_discord_allows() {
+    assert_eq!(HoneypotTrap::parse_timeout_minutes("60"), 60);
+    assert_eq!(HoneypotTrap::parse_timeout_minutes("0"), 1);
+    assert_eq!(
+        HoneypotTrap::parse_timeout_minutes("999999"),
+        HoneypotTrap::MAX_TIMEOUT_MINUTES
+    );
+    assert_eq!(
+        HoneypotTrap::parse_timeout_minutes("soon"),
+        HoneypotTrap::DEFAULT_TIMEOUT_MINUTES
+    );
+}
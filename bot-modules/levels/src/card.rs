@@ -0,0 +1,201 @@
+use std::sync::{Arc, LazyLock};
+use std::time::Duration;
+
+use moka::future::Cache;
+use serenity::all::{User, UserId};
+use sqlx::PgPool;
+use tracing::warn;
+use zayden_core::as_i64;
+use zayden_graphics::card::{CARD_LIMITS, CardAvatarSlot, DEFAULT_ACCENT};
+use zayden_graphics::{
+    AVATAR_MAX_BYTES,
+    CardData,
+    CardTheme,
+    GraphicsError,
+    Overlay,
+    Renderer,
+    decode_avatar,
+};
+
+pub const CARD_FILENAME: &str = "card.png";
+
+const RETENTION: Duration = Duration::from_secs(60 * 60);
+const CAPACITY: u64 = 4_096;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CardKind {
+    Rank,
+    Profile,
+}
+
+static RENDERED: LazyLock<Cache<(CardKind, UserId), (u64, Arc<[u8]>)>> =
+    LazyLock::new(|| {
+        Cache::builder().time_to_idle(RETENTION).max_capacity(CAPACITY).build()
+    });
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CardSettings {
+    pub background: String,
+    pub accent: i32,
+}
+
+impl Default for CardSettings {
+    fn default() -> Self {
+        Self {
+            background: String::from(zayden_graphics::card::DEFAULT_BACKGROUND.id),
+            accent: DEFAULT_ACCENT.cast_signed(),
+        }
+    }
+}
+
+impl CardSettings {
+    #[must_use]
+    pub fn theme(&self) -> CardTheme<'_> {
+        CardTheme {
+            background: &self.background,
+            accent: self.accent.cast_unsigned(),
+        }
+    }
+}
+
+pub struct CardManager;
+
+impl CardManager {
+    pub async fn settings(pool: &PgPool, id: UserId) -> sqlx::Result<CardSettings> {
+        let row = sqlx::query_as!(
+            CardSettings,
+            "SELECT background, accent FROM profile_cards WHERE user_id = $1",
+            as_i64(id.get())
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(row.unwrap_or_default())
+    }
+
+    pub async fn set_background(
+        pool: &PgPool,
+        id: UserId,
+        background: &str,
+    ) -> sqlx::Result<()> {
+        sqlx::query!(
+            "INSERT INTO profile_cards (user_id, background)
+            VALUES ($1, $2)
+            ON CONFLICT (user_id) DO UPDATE SET background = EXCLUDED.background",
+            as_i64(id.get()),
+            background
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn set_accent(
+        pool: &PgPool,
+        id: UserId,
+        accent: u32,
+    ) -> sqlx::Result<()> {
+        sqlx::query!(
+            "INSERT INTO profile_cards (user_id, accent)
+            VALUES ($1, $2)
+            ON CONFLICT (user_id) DO UPDATE SET accent = EXCLUDED.accent",
+            as_i64(id.get()),
+            (accent & 0x00ff_ffff).cast_signed()
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn reset(pool: &PgPool, id: UserId) -> sqlx::Result<()> {
+        sqlx::query!(
+            "DELETE FROM profile_cards WHERE user_id = $1",
+            as_i64(id.get())
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+}
+
+fn avatar_url(user: &User, size: u32) -> String {
+    user.avatar.map_or_else(
+        || {
+            let index = (user.id.get() >> 22) % 6;
+            format!("https://cdn.discordapp.com/embed/avatars/{index}.png")
+        },
+        |hash| {
+            format!(
+                "https://cdn.discordapp.com/avatars/{}/{hash}.png?size={size}",
+                user.id
+            )
+        },
+    )
+}
+
+async fn avatar(
+    http: &reqwest::Client,
+    url: &str,
+    slot: CardAvatarSlot,
+) -> Option<Overlay> {
+    let response = http.get(url).send().await.ok()?;
+    if !response.status().is_success() {
+        warn!(status = %response.status(), "card avatar fetch failed");
+        return None;
+    }
+
+    if response.content_length().is_some_and(|len| len > AVATAR_MAX_BYTES as u64) {
+        warn!("card avatar exceeds the byte cap");
+        return None;
+    }
+
+    let bytes = response.bytes().await.ok()?;
+
+    match decode_avatar(&bytes, slot.size) {
+        Ok(pixmap) => Some(Overlay { pixmap, x: slot.x, y: slot.y }),
+        Err(e) => {
+            warn!(error = %e, "card avatar decode failed");
+            None
+        },
+    }
+}
+
+/// Renders `data` as a PNG card, reusing the last render for this user and
+/// kind while nothing shown on it has changed.
+pub async fn render_card(
+    http: &reqwest::Client,
+    user: &User,
+    kind: CardKind,
+    data: &CardData,
+    theme: CardTheme<'_>,
+) -> Result<Vec<u8>, GraphicsError> {
+    let svg = zayden_graphics::card::render(data, theme);
+
+    let url = avatar_url(user, svg.avatar.size.next_power_of_two().clamp(16, 256));
+    let fingerprint = data.fingerprint(theme, Some(&url));
+
+    if let Some((cached, png)) = RENDERED.get(&(kind, user.id)).await
+        && cached == fingerprint
+    {
+        return Ok(png.to_vec());
+    }
+
+    let overlay = avatar(http, &url, svg.avatar).await;
+    // A card missing its avatar is still worth sending, but not worth keeping.
+    let complete = overlay.is_some();
+
+    let png = Renderer::shared()?
+        .render(svg.markup, svg.canvas, overlay.into_iter().collect(), CARD_LIMITS)
+        .await?;
+
+    if complete {
+        RENDERED
+            .insert((kind, user.id), (fingerprint, Arc::from(png.as_slice())))
+            .await;
+    }
+
+    Ok(png)
+}
@@ -1,6 +1,7 @@
 use serenity::all::{
     CommandInteraction,
     CommandOptionType,
+    CreateAttachment,
     CreateCommand,
     CreateCommandOption,
     CreateEmbed,
@@ -10,9 +11,20 @@ use serenity::all::{
     ResolvedValue,
 };
 use sqlx::PgPool;
+use tracing::warn;
 use zayden_core::parse_options;
+use zayden_graphics::CardData;
 
-use crate::{LevelsRow, RankRow, Result, level_up_xp};
+use crate::card::CARD_FILENAME;
+use crate::{
+    CardKind,
+    CardManager,
+    LevelsRow,
+    RankRow,
+    Result,
+    level_up_xp,
+    render_card,
+};
 
 pub struct Rank;
 
@@ -22,6 +34,7 @@ impl Rank {
         interaction: &CommandInteraction,
         options: Vec<ResolvedOption<'_>>,
         pool: &PgPool,
+        client: &reqwest::Client,
     ) -> Result<()> {
         let mut options = parse_options(options);
 
@@ -57,12 +70,38 @@ impl Rank {
 
         let level = row.level();
         let xp_for_next_level = level_up_xp(level);
+        let xp = row.xp();
+
+        let card = CardData {
+            name: user.display_name().to_string(),
+            subtitle: format!("{scope_label} rank"),
+            level,
+            xp,
+            xp_needed: xp_for_next_level,
+            rank: user_rank,
+            stats: Vec::new(),
+        };
+        let settings = CardManager::settings(pool, user.id).await?;
+
+        match render_card(client, user, CardKind::Rank, &card, settings.theme())
+            .await
+        {
+            Ok(png) => {
+                let file = CreateAttachment::bytes(png, CARD_FILENAME);
+                interaction
+                    .edit_response(
+                        http,
+                        EditInteractionResponse::new().new_attachment(file),
+                    )
+                    .await?;
+                return Ok(());
+            },
+            Err(e) => warn!(error = %e, "rank card render failed, sending embed"),
+        }
 
         let user_rank =
             user_rank.map_or_else(|| String::from("N/A"), |rank| format!("{rank}"));
 
-        let xp = row.xp();
-
         let embed = CreateEmbed::new()
             .title(format!("{scope_label} XP stats for {}", user.name))
             .description(format!(
@@ -1,7 +1,9 @@
+pub mod card;
 pub mod commands;
 pub mod common;
 pub mod components;
 pub mod error;
+pub use card::{CardKind, CardManager, CardSettings, render_card};
 pub use commands::{Rank, Xp};
 pub use common::levels::LeaderboardScope;
 pub use components::LevelsCustomId;
@@ -11,6 +13,7 @@ pub mod message_create;
 pub use message_create::message_create;
 
 pub mod manager;
+pub mod tools;
 pub use manager::{
     FullLevelRow,
     GuildLevelRow,
@@ -0,0 +1,102 @@
+//! Rank lookups for the AI chat. Only people already in the conversation can
+//! be looked up: the author, or someone they mentioned.
+
+use async_trait::async_trait;
+use serenity::all::{GuildId, UserId};
+use sqlx::PgPool;
+use zayden_core::{AiTool, ToolArgs, ToolError, ToolParam};
+
+use crate::{RankRow, level_up_xp};
+
+pub struct RankLookup {
+    pool: PgPool,
+    guild_id: Option<GuildId>,
+    /// Display name and id of who may be looked up; the author first.
+    people: Vec<(String, UserId)>,
+}
+
+impl RankLookup {
+    #[must_use]
+    pub const fn new(
+        pool: PgPool,
+        guild_id: Option<GuildId>,
+        people: Vec<(String, UserId)>,
+    ) -> Self {
+        Self { pool, guild_id, people }
+    }
+}
+
+#[async_trait]
+impl AiTool for RankLookup {
+    fn name(&self) -> &'static str {
+        "levels_rank"
+    }
+
+    fn description(&self) -> &'static str {
+        "Look up the level, XP and leaderboard rank of the person you're \
+         talking to, or of someone they mentioned."
+    }
+
+    fn params(&self) -> &'static [ToolParam] {
+        const PARAMS: &[ToolParam] = &[ToolParam::optional(
+            "name",
+            "Display name of a mentioned member. Leave out for the person \
+             you're talking to.",
+        )];
+        PARAMS
+    }
+
+    async fn call(&self, args: &ToolArgs) -> Result<String, ToolError> {
+        let person = match args.get("name") {
+            Some(name) => self
+                .people
+                .iter()
+                .find(|(display, _)| display.eq_ignore_ascii_case(name))
+                .ok_or_else(|| {
+                    ToolError::NotFound(format!(
+                        "`{name}`; only the author and members they mentioned \
+                         can be looked up"
+                    ))
+                })?,
+            None => self.people.first().ok_or_else(|| {
+                ToolError::NotFound(String::from("the person you're talking to"))
+            })?,
+        };
+        let (name, user_id) = (person.0.as_str(), person.1);
+
+        let (row, rank, scope) = match self.guild_id {
+            Some(guild_id) => {
+                let row = RankRow::guild_get(&self.pool, guild_id, user_id)
+                    .await
+                    .map_err(ToolError::failed)?;
+                let rank = RankRow::guild_user_rank(&self.pool, guild_id, user_id)
+                    .await
+                    .map_err(ToolError::failed)?;
+                (row, rank, "server")
+            },
+            None => {
+                let row = RankRow::get(&self.pool, user_id)
+                    .await
+                    .map_err(ToolError::failed)?;
+                let rank = RankRow::user_rank(&self.pool, user_id)
+                    .await
+                    .map_err(ToolError::failed)?;
+                (row, rank, "global")
+            },
+        };
+
+        let Some(row) = row else {
+            return Ok(format!("{name} hasn't earned any XP yet."));
+        };
+
+        let rank =
+            rank.map_or_else(|| String::from("unranked"), |r| format!("#{r}"));
+
+        Ok(format!(
+            "{name}: level {}, {}/{} XP to the next level, {scope} rank {rank}",
+            row.level,
+            row.xp,
+            level_up_xp(row.level),
+        ))
+    }
+}
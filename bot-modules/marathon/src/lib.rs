@@ -12,4 +12,5 @@ pub mod news;
 pub mod parse;
 pub mod schedule_rules;
 pub mod source;
+pub mod tools;
 pub mod transport;
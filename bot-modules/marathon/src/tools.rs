@@ -0,0 +1,80 @@
+//! Weapon lookups for the AI chat.
+
+use std::fmt::Write;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use zayden_core::{AiTool, ToolArgs, ToolError, ToolParam};
+
+use crate::client::MarathonClient;
+use crate::commands::find_entity;
+use crate::model::Weapon;
+
+pub struct WeaponLookup {
+    client: Arc<MarathonClient>,
+}
+
+impl WeaponLookup {
+    #[must_use]
+    pub const fn new(client: Arc<MarathonClient>) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl AiTool for WeaponLookup {
+    fn name(&self) -> &'static str {
+        "marathon_weapon"
+    }
+
+    fn description(&self) -> &'static str {
+        "Look up a Marathon weapon by name: type, ammo, damage, fire rate, \
+         magazine, reload, range and other stats."
+    }
+
+    fn params(&self) -> &'static [ToolParam] {
+        const PARAMS: &[ToolParam] =
+            &[ToolParam::required("name", "The weapon's name.")];
+        PARAMS
+    }
+
+    async fn call(&self, args: &ToolArgs) -> Result<String, ToolError> {
+        let query = args.required("name")?;
+
+        let weapons = self.client.weapons().await.map_err(ToolError::failed)?;
+        let weapon =
+            find_entity(&weapons, query, |w| w.slug.as_str(), |w| w.name.as_str())
+                .ok_or_else(|| ToolError::NotFound(format!("weapon `{query}`")))?;
+
+        Ok(describe(weapon))
+    }
+}
+
+fn describe(weapon: &Weapon) -> String {
+    let mut out = weapon.name.clone();
+
+    let fields = [
+        ("Type", &weapon.weapon_type),
+        ("Ammo", &weapon.ammo_type),
+        ("Damage", &weapon.damage),
+        ("Fire rate", &weapon.fire_rate),
+        ("Magazine", &weapon.magazine_size),
+        ("Reload", &weapon.reload_speed),
+        ("Range", &weapon.range),
+    ];
+    for (label, value) in fields {
+        if let Some(value) = value {
+            let _ = write!(out, "\n{label}: {value}");
+        }
+    }
+
+    for stat in &weapon.stats {
+        let _ = write!(out, "\n{}: {}", stat.name, stat.value);
+    }
+
+    if let Some(description) = &weapon.description {
+        let _ = write!(out, "\n{description}");
+    }
+
+    out
+}
@@ -6,7 +6,7 @@ use serenity::all::{
     EditInteractionResponse,
     ResolvedValue,
 };
-use zayden_app::config::MusicSettingsRow;
+use zayden_app::config::{Actor, MusicSettingsRow};
 use zayden_app::entitlement::{EntitlementScope, Tier};
 use zayden_core::{as_i64, optional_option};
 
@@ -53,9 +53,10 @@ pub(super) async fn run(
     }
 
     let guild_id = as_i64(ctx.guild_id.get());
+    let actor = Actor::command(ctx.interaction.user.id.get());
     let updated = ctx
         .settings
-        .update(guild_id, |row| {
+        .update_by(guild_id, actor, |row| {
             if let Some(volume) = default_volume {
                 row.default_volume = i16::try_from(volume).unwrap_or(100);
             }
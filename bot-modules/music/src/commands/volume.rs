@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 
 use serenity::all::{EditInteractionResponse, ResolvedValue};
+use zayden_app::config::Actor;
 
 use super::MusicCtx;
 use crate::error::{MusicError, Result};
@@ -46,8 +47,9 @@ pub(super) async fn run(
         }
     }
 
+    let actor = Actor::command(ctx.interaction.user.id.get());
     ctx.settings
-        .update(zayden_core::as_i64(ctx.guild_id.get()), |row| {
+        .update_by(zayden_core::as_i64(ctx.guild_id.get()), actor, |row| {
             row.default_volume = i16::from(volume);
         })
         .await?;
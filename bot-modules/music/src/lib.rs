@@ -10,6 +10,7 @@ pub mod player;
 pub mod queue;
 pub mod radio;
 pub mod resolve;
+pub mod tools;
 pub mod track;
 pub mod voice;
 
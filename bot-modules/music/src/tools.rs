@@ -0,0 +1,78 @@
+//! Now-playing lookups for the AI chat.
+
+use std::fmt::Write;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serenity::all::GuildId;
+use zayden_core::{AiTool, ToolArgs, ToolError};
+
+use crate::embeds::format_duration;
+use crate::{LoopMode, MusicManager};
+
+pub struct NowPlayingLookup {
+    music: Arc<MusicManager>,
+    guild_id: GuildId,
+}
+
+impl NowPlayingLookup {
+    #[must_use]
+    pub const fn new(music: Arc<MusicManager>, guild_id: GuildId) -> Self {
+        Self { music, guild_id }
+    }
+}
+
+#[async_trait]
+impl AiTool for NowPlayingLookup {
+    fn name(&self) -> &'static str {
+        "music_now_playing"
+    }
+
+    fn description(&self) -> &'static str {
+        "What the music player in this server is playing right now, and how \
+         many tracks are queued after it."
+    }
+
+    async fn call(&self, _args: &ToolArgs) -> Result<String, ToolError> {
+        let Some(player) = self.music.get(self.guild_id) else {
+            return Ok(String::from("Nothing is playing."));
+        };
+        let player = player.lock().await;
+
+        let Some(now) = &player.current else {
+            return Ok(String::from("Nothing is playing."));
+        };
+
+        let mut out = format!("Playing: {} ({})", now.track.title, now.track.url);
+
+        if now.track.is_live {
+            out.push_str("\nLive stream");
+        } else {
+            let elapsed = format_duration(now.started_at.elapsed());
+            match now.track.duration {
+                Some(total) => {
+                    let _ = write!(
+                        out,
+                        "\nProgress: {elapsed} / {}",
+                        format_duration(total)
+                    );
+                },
+                None => {
+                    let _ = write!(out, "\nProgress: {elapsed}");
+                },
+            }
+        }
+
+        let _ = write!(out, "\nRequested by: <@{}>", now.track.requested_by);
+
+        match player.loop_mode {
+            LoopMode::Off => {},
+            LoopMode::Track => out.push_str("\nLooping the track"),
+            LoopMode::Queue => out.push_str("\nLooping the queue"),
+        }
+
+        let _ = write!(out, "\nTracks queued: {}", player.queue.len());
+
+        Ok(out)
+    }
+}
@@ -13,6 +13,7 @@ pub mod parse;
 pub mod progress;
 pub mod save;
 pub mod source;
+pub mod tools;
 pub mod transport;
 pub mod typechart;
 pub mod upload;
@@ -0,0 +1,191 @@
+//! Pal and breeding lookups for the AI chat.
+
+use std::fmt::Write;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use zayden_core::{AiTool, ToolArgs, ToolError, ToolParam};
+
+use crate::client::PalworldClient;
+use crate::commands::find_pal;
+use crate::model::Pal;
+
+/// Most parent pairs listed for a breeding target.
+const MAX_PAIRS: usize = 10;
+
+pub struct PalLookup {
+    client: Arc<PalworldClient>,
+}
+
+impl PalLookup {
+    #[must_use]
+    pub const fn new(client: Arc<PalworldClient>) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl AiTool for PalLookup {
+    fn name(&self) -> &'static str {
+        "palworld_pal"
+    }
+
+    fn description(&self) -> &'static str {
+        "Look up a Palworld pal by name: Paldex number, elements, base stats, \
+         work suitability, partner skill and drops."
+    }
+
+    fn params(&self) -> &'static [ToolParam] {
+        const PARAMS: &[ToolParam] =
+            &[ToolParam::required("name", "The pal's name, e.g. Lamball.")];
+        PARAMS
+    }
+
+    async fn call(&self, args: &ToolArgs) -> Result<String, ToolError> {
+        let query = args.required("name")?;
+
+        let pals = self.client.pals().await.map_err(ToolError::failed)?;
+        let key = find_pal(&pals, query)
+            .ok_or_else(|| ToolError::NotFound(format!("pal `{query}`")))?
+            .key
+            .clone();
+
+        let pal = self.client.pal(&key).await.map_err(ToolError::failed)?;
+
+        Ok(describe(&pal))
+    }
+}
+
+fn describe(pal: &Pal) -> String {
+    let mut out = format!("{} (Paldex #{})", pal.name, pal.paldex_no);
+
+    if !pal.elements.is_empty() {
+        let elements: Vec<_> = pal.elements.iter().map(|e| e.label()).collect();
+        let _ = write!(out, "\nElements: {}", elements.join(", "));
+    }
+
+    if let Some(stats) = &pal.stats {
+        let _ = write!(
+            out,
+            "\nStats: HP {}, melee attack {}, ranged attack {}, defense {}, \
+             stamina {}",
+            stats.hp,
+            stats.attack_melee,
+            stats.attack_ranged,
+            stats.defense,
+            stats.stamina,
+        );
+    }
+
+    if !pal.suitability.is_empty() {
+        let work: Vec<_> = pal
+            .suitability
+            .iter()
+            .map(|s| format!("{} {}", s.kind, s.level))
+            .collect();
+        let _ = write!(out, "\nWork suitability: {}", work.join(", "));
+    }
+
+    if let Some(skill) = &pal.partner_skill {
+        let _ = write!(out, "\nPartner skill: {}", skill.name);
+        if let Some(description) = &skill.description {
+            let _ = write!(out, " - {description}");
+        }
+    }
+
+    if let Some(rank) = pal.breeding_rank {
+        let _ = write!(out, "\nBreeding rank: {rank}");
+    }
+
+    if !pal.drops.is_empty() {
+        let _ = write!(out, "\nDrops: {}", pal.drops.join(", "));
+    }
+
+    if let Some(description) = &pal.description {
+        let _ = write!(out, "\n{description}");
+    }
+
+    out
+}
+
+pub struct BreedingLookup {
+    client: Arc<PalworldClient>,
+}
+
+impl BreedingLookup {
+    #[must_use]
+    pub const fn new(client: Arc<PalworldClient>) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl AiTool for BreedingLookup {
+    fn name(&self) -> &'static str {
+        "palworld_breeding"
+    }
+
+    fn description(&self) -> &'static str {
+        "Palworld breeding. Give parent_a and parent_b to get the child they \
+         breed, or target to get parent pairs that breed it."
+    }
+
+    fn params(&self) -> &'static [ToolParam] {
+        const PARAMS: &[ToolParam] = &[
+            ToolParam::optional("parent_a", "First parent's name."),
+            ToolParam::optional("parent_b", "Second parent's name."),
+            ToolParam::optional("target", "The pal to breed."),
+        ];
+        PARAMS
+    }
+
+    async fn call(&self, args: &ToolArgs) -> Result<String, ToolError> {
+        let pals = self.client.pals().await.map_err(ToolError::failed)?;
+        let index = self.client.breeding_index().await.map_err(ToolError::failed)?;
+
+        let find = |query: &str| {
+            find_pal(&pals, query)
+                .ok_or_else(|| ToolError::NotFound(format!("pal `{query}`")))
+        };
+        let display = |key: &str| {
+            find_pal(&pals, key).map_or_else(|| key.to_string(), |p| p.name.clone())
+        };
+
+        if let (Some(a), Some(b)) = (args.get("parent_a"), args.get("parent_b")) {
+            let (a, b) = (find(a)?, find(b)?);
+
+            let child = index.breed(&a.key, &b.key).ok_or_else(|| {
+                ToolError::NotFound(format!(
+                    "breeding result for {} and {}",
+                    a.name, b.name
+                ))
+            })?;
+
+            return Ok(format!("{} + {} = {}", a.name, b.name, display(child)));
+        }
+
+        let Some(target) = args.get("target") else {
+            return Err(ToolError::InvalidArguments(String::from(
+                "give both parents, or a target",
+            )));
+        };
+        let target = find(target)?;
+
+        let pairs = index.breed_for(&target.key);
+        if pairs.is_empty() {
+            return Ok(format!("{} can't be bred.", target.name));
+        }
+
+        let mut out = format!("{} has {} parent pairs", target.name, pairs.len());
+        if index.is_unique_child(&target.key) {
+            out.push_str(" (a unique combination)");
+        }
+        out.push(':');
+
+        for pair in pairs.iter().take(MAX_PAIRS) {
+            let _ = write!(out, "\n{} + {}", display(&pair.a), display(&pair.b));
+        }
+
+        Ok(out)
+    }
+}
@@ -0,0 +1,219 @@
+use std::collections::HashMap;
+
+use serenity::all::{
+    CommandOptionType,
+    CreateCommand,
+    CreateCommandOption,
+    EditInteractionResponse,
+    GuildId,
+    Permissions,
+    ResolvedValue,
+};
+use zayden_app::config::RaidSettingsRow;
+use zayden_app::services::lockdown as lockdown_state;
+use zayden_core::{
+    InvocationCtx,
+    as_i64,
+    optional_option,
+    parse_options,
+    parse_subcommand,
+};
+
+use crate::error::{RaidError, Result};
+use crate::lockdown::{self, joiner_outcome};
+
+pub struct Lockdown;
+
+impl Lockdown {
+    pub fn register() -> CreateCommand<'static> {
+        CreateCommand::new("lockdown")
+            .description("Lock the server down during a raid")
+            .default_member_permissions(Permissions::MANAGE_GUILD)
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::SubCommand,
+                    "start",
+                    "Pause verification, raise the verification level and slow channels",
+                )
+                .add_sub_option(
+                    CreateCommandOption::new(
+                        CommandOptionType::Integer,
+                        "minutes",
+                        "End it automatically after this long. Leave out to end it by hand.",
+                    )
+                    .min_int_value(1)
+                    .max_int_value(RaidSettingsRow::MAX_LOCKDOWN_MINUTES.unsigned_abs().into()),
+                )
+                .add_sub_option(
+                    CreateCommandOption::new(
+                        CommandOptionType::String,
+                        "reason",
+                        "Shown to staff in the alert channel",
+                    )
+                    .max_length(200),
+                ),
+            )
+            .add_option(CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "end",
+                "End the lockdown and undo its changes",
+            ))
+            .add_option(CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "status",
+                "Show whether the server is locked down, and the raid settings",
+            ))
+    }
+
+    pub async fn run(cx: &InvocationCtx<'_>) -> Result<()> {
+        let (name, sub_options) = parse_subcommand(cx.interaction.data.options())?;
+        let options = parse_options(sub_options);
+
+        let guild_id = cx.interaction.guild_id.ok_or(RaidError::MissingGuildId)?;
+        require_manage_guild(cx)?;
+
+        match name {
+            "start" => start(cx, guild_id, options).await,
+            "end" => end(cx, guild_id).await,
+            "status" => status(cx, guild_id).await,
+            _ => Err(RaidError::UnknownSubcommand(name.to_string())),
+        }
+    }
+}
+
+fn require_manage_guild(cx: &InvocationCtx<'_>) -> Result<()> {
+    let perms = cx.interaction.member.as_ref().and_then(|member| member.permissions);
+
+    if perms.is_some_and(|perms| perms.administrator() || perms.manage_guild()) {
+        Ok(())
+    } else {
+        Err(RaidError::NotPrivileged)
+    }
+}
+
+async fn start(
+    cx: &InvocationCtx<'_>,
+    guild_id: GuildId,
+    mut options: HashMap<&str, ResolvedValue<'_>>,
+) -> Result<()> {
+    let minutes = optional_option::<i64, _>(&mut options, "minutes")
+        .and_then(|minutes| i32::try_from(minutes).ok())
+        .map(|minutes| minutes.clamp(1, RaidSettingsRow::MAX_LOCKDOWN_MINUTES));
+    let reason = optional_option::<&str, _>(&mut options, "reason")
+        .map(str::trim)
+        .filter(|reason| !reason.is_empty())
+        .unwrap_or("started by a moderator");
+
+    cx.interaction.defer_ephemeral(&cx.ctx.http).await?;
+
+    let settings = cx.app.settings.raid.get(as_i64(guild_id.get())).await?;
+
+    if !lockdown::enter(
+        &cx.ctx.http,
+        &cx.app.db,
+        guild_id,
+        &settings,
+        reason,
+        Some(cx.interaction.user.id),
+        minutes,
+    )
+    .await?
+    {
+        return Err(RaidError::AlreadyLocked);
+    }
+
+    let length = minutes.map_or_else(
+        || "until you run `/lockdown end`".to_string(),
+        |minutes| format!("for {minutes} minutes"),
+    );
+
+    cx.interaction
+        .edit_response(
+            &cx.ctx.http,
+            EditInteractionResponse::new().content(format!(
+                "Lockdown started {length}. The verify panel is paused and \
+                 suspicious joiners are {}.",
+                joiner_outcome(&settings),
+            )),
+        )
+        .await?;
+
+    Ok(())
+}
+
+async fn end(cx: &InvocationCtx<'_>, guild_id: GuildId) -> Result<()> {
+    cx.interaction.defer_ephemeral(&cx.ctx.http).await?;
+
+    let settings = cx.app.settings.raid.get(as_i64(guild_id.get())).await?;
+
+    let ended = lockdown::exit(
+        &cx.ctx.http,
+        &cx.app.db,
+        guild_id,
+        &settings,
+        Some(cx.interaction.user.id),
+    )
+    .await?
+    .ok_or(RaidError::NotLocked)?;
+
+    cx.interaction
+        .edit_response(
+            &cx.ctx.http,
+            EditInteractionResponse::new().content(format!(
+                "Lockdown ended. {} joiner(s) were actioned during it; \
+                 verification and slowmode are back to how they were.",
+                ended.joiners_actioned,
+            )),
+        )
+        .await?;
+
+    Ok(())
+}
+
+async fn status(cx: &InvocationCtx<'_>, guild_id: GuildId) -> Result<()> {
+    cx.interaction.defer_ephemeral(&cx.ctx.http).await?;
+
+    let id = as_i64(guild_id.get());
+    let settings = cx.app.settings.raid.get(id).await?;
+
+    let lockdown = match lockdown_state::active(&cx.app.db, id).await? {
+        None => "The server is not locked down.".to_string(),
+        Some(active) => {
+            let ends = active.ends_at.map_or_else(
+                || "until ended by hand".to_string(),
+                |ends| format!("until <t:{}:t>", ends.as_second()),
+            );
+
+            format!(
+                "**Locked down** since <t:{}:R>, {ends}: {}. {} joiner(s) \
+                 actioned so far.",
+                active.started_at.as_second(),
+                active.reason,
+                active.joiners_actioned,
+            )
+        },
+    };
+
+    let detection = if settings.enabled {
+        format!(
+            "more than {} joins in {} seconds starts a lockdown",
+            settings.join_threshold, settings.join_window_seconds,
+        )
+    } else {
+        "off; only `/lockdown start` locks the server down".to_string()
+    };
+
+    cx.interaction
+        .edit_response(
+            &cx.ctx.http,
+            EditInteractionResponse::new().content(format!(
+                "{lockdown}\n**Raid detection:** {detection}\n\
+                 **Suspicious joiners:** {}\n\n\
+                 Change the thresholds and heuristics from the dashboard.",
+                joiner_outcome(&settings),
+            )),
+        )
+        .await?;
+
+    Ok(())
+}
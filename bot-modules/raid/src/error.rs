@@ -0,0 +1,56 @@
+use std::borrow::Cow;
+
+use zayden_core::error::{HandlerError, Respond};
+
+pub type Result<T> = std::result::Result<T, RaidError>;
+
+#[derive(Debug, thiserror::Error)]
+pub enum RaidError {
+    #[error(transparent)]
+    Discord(#[from] serenity::Error),
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error("This command can only be used in a server.")]
+    MissingGuildId,
+    #[error("You need the Manage Server permission to lock the server down.")]
+    NotPrivileged,
+    #[error("The server is already in lockdown. End it with `/lockdown end` first.")]
+    AlreadyLocked,
+    #[error("The server isn't in lockdown.")]
+    NotLocked,
+    #[error("Unknown lockdown subcommand: {0}")]
+    UnknownSubcommand(String),
+    #[error("internal error: {0}")]
+    Internal(String),
+}
+
+impl Respond for RaidError {
+    fn user_message(&self) -> Option<Cow<'_, str>> {
+        match self {
+            Self::MissingGuildId
+            | Self::NotPrivileged
+            | Self::AlreadyLocked
+            | Self::NotLocked
+            | Self::UnknownSubcommand(_) => Some(Cow::Owned(self.to_string())),
+            Self::Discord(_) | Self::Database(_) | Self::Internal(_) => None,
+        }
+    }
+}
+
+impl From<RaidError> for HandlerError {
+    fn from(e: RaidError) -> Self {
+        Self::from_respond(e)
+    }
+}
+
+impl From<HandlerError> for RaidError {
+    fn from(e: HandlerError) -> Self {
+        match e {
+            HandlerError::Discord(e) => Self::Discord(e),
+            HandlerError::Database(e) => Self::Database(e),
+            HandlerError::Module { source, .. } => {
+                Self::Internal(source.to_string())
+            },
+        }
+    }
+}
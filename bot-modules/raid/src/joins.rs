@@ -0,0 +1,85 @@
+//! Each guild's recent joins, for the join-rate trigger and the similar-name
+//! heuristic.
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use zayden_app::config::RaidSettingsRow;
+
+/// Joins remembered per guild. A raid past this is caught long before the
+/// oldest are needed.
+const MAX_JOINS: usize = 1_024;
+
+/// How far back joiners' names are compared.
+pub const NAME_WINDOW: Duration = Duration::from_mins(5);
+
+/// Names shorter than this, once reduced to letters, are too common to count
+/// as similar.
+const MIN_SKELETON_CHARS: usize = 3;
+
+/// A name reduced to its lowercased letters, so `raider_01` and `Raider22`
+/// match.
+#[must_use]
+pub fn name_skeleton(name: &str) -> String {
+    name.chars().filter(|c| c.is_alphabetic()).flat_map(char::to_lowercase).collect()
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct JoinTracker {
+    joins: VecDeque<(Instant, String)>,
+}
+
+impl JoinTracker {
+    /// Adds a join by `name` at `at`, forgetting joins older than both the
+    /// longest join window and [`NAME_WINDOW`].
+    pub fn record(&mut self, at: Instant, name: &str) {
+        let max_window = NAME_WINDOW.max(Duration::from_secs(
+            RaidSettingsRow::MAX_JOIN_WINDOW_SECONDS.unsigned_abs().into(),
+        ));
+
+        while self.joins.front().is_some_and(|(joined, _)| {
+            at.saturating_duration_since(*joined) > max_window
+        }) {
+            self.joins.pop_front();
+        }
+
+        if self.joins.len() >= MAX_JOINS {
+            self.joins.pop_front();
+        }
+
+        self.joins.push_back((at, name_skeleton(name)));
+    }
+
+    fn within(
+        &self,
+        now: Instant,
+        window: Duration,
+    ) -> impl Iterator<Item = &(Instant, String)> {
+        self.joins.iter().filter(move |(joined, _)| {
+            now.saturating_duration_since(*joined) <= window
+        })
+    }
+
+    /// Joins in the last `window`.
+    #[must_use]
+    pub fn count(&self, now: Instant, window: Duration) -> u32 {
+        u32::try_from(self.within(now, window).count()).unwrap_or(u32::MAX)
+    }
+
+    /// Other joiners in the last [`NAME_WINDOW`] whose names match `name`'s
+    /// skeleton. Expects `name` to have been recorded already.
+    #[must_use]
+    pub fn similar_names(&self, now: Instant, name: &str) -> u32 {
+        let skeleton = name_skeleton(name);
+        if skeleton.chars().count() < MIN_SKELETON_CHARS {
+            return 0;
+        }
+
+        let matches = self
+            .within(now, NAME_WINDOW)
+            .filter(|(_, other)| *other == skeleton)
+            .count();
+
+        u32::try_from(matches.saturating_sub(1)).unwrap_or(u32::MAX)
+    }
+}
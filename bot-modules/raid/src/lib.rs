@@ -0,0 +1,11 @@
+pub mod commands;
+pub mod error;
+pub mod joins;
+pub mod lockdown;
+pub mod member_join;
+pub mod screen;
+
+pub use commands::Lockdown;
+pub use error::{RaidError, Result};
+pub use member_join::{RaidJoin, member_join};
+pub use screen::RaidSignal;
@@ -0,0 +1,290 @@
+//! Entering and leaving lockdown on Discord. The lockdown itself, and what it
+//! changed, live in [`zayden_app::services::lockdown`].
+
+use serenity::all::{
+    ChannelId,
+    ChannelType,
+    CreateMessage,
+    EditChannel,
+    EditGuild,
+    GuildId,
+    Http,
+    UserId,
+    VerificationLevel,
+};
+use serenity::nonmax::NonMaxU16;
+use sqlx::PgPool;
+use tracing::{info, warn};
+use zayden_app::config::{RaidAction, RaidSettingsRow, SettingsStore};
+use zayden_app::services::lockdown::{self, Lockdown, SlowmodeChange};
+use zayden_core::{as_i64, as_u64};
+
+use crate::error::Result;
+
+const END_REASON: &str = "Lockdown ended";
+
+/// Locks the guild down for `minutes`, or until ended by hand when `None`.
+/// Returns `false` if it already was. Discord changes that fail, usually for
+/// missing permissions, are logged and skipped so the rest still apply.
+pub async fn enter(
+    http: &Http,
+    pool: &PgPool,
+    guild_id: GuildId,
+    settings: &RaidSettingsRow,
+    reason: &str,
+    started_by: Option<UserId>,
+    minutes: Option<i32>,
+) -> Result<bool> {
+    let id = as_i64(guild_id.get());
+    let started_by = started_by.map(|user| as_i64(user.get()));
+
+    if !lockdown::start(pool, id, minutes, reason, started_by).await? {
+        return Ok(false);
+    }
+
+    let audit_reason = format!("Lockdown: {reason}");
+
+    if settings.raise_verification
+        && let Err(e) = raise_verification(http, pool, guild_id, &audit_reason).await
+    {
+        warn!(%guild_id, error = %e, "lockdown could not raise the verification level");
+    }
+
+    if settings.slowmode_seconds > 0
+        && let Err(e) = slow_channels(
+            http,
+            pool,
+            guild_id,
+            settings.slowmode_seconds,
+            &audit_reason,
+        )
+        .await
+    {
+        warn!(%guild_id, error = %e, "lockdown could not list channels for slowmode");
+    }
+
+    info!(%guild_id, reason, ?minutes, "lockdown started");
+
+    let length = minutes.map_or_else(
+        || "until a moderator runs `/lockdown end`".to_string(),
+        |minutes| format!("for {minutes} minutes"),
+    );
+    alert(
+        http,
+        settings,
+        format!(
+            "**Lockdown started:** {reason}. It lasts {length}. The verify panel \
+             is paused and suspicious joiners are {}.",
+            joiner_outcome(settings),
+        ),
+    )
+    .await?;
+
+    Ok(true)
+}
+
+/// Ends the guild's lockdown and undoes what it changed. `None` if it wasn't
+/// locked down.
+pub async fn exit(
+    http: &Http,
+    pool: &PgPool,
+    guild_id: GuildId,
+    settings: &RaidSettingsRow,
+    ended_by: Option<UserId>,
+) -> Result<Option<Lockdown>> {
+    let Some((ended, channels)) =
+        lockdown::end(pool, as_i64(guild_id.get())).await?
+    else {
+        return Ok(None);
+    };
+
+    if let Some(previous) = ended.previous_verification_level {
+        let level =
+            VerificationLevel::from(u8::try_from(previous).unwrap_or_default());
+
+        if let Err(e) = guild_id
+            .edit(
+                http,
+                EditGuild::new()
+                    .verification_level(level)
+                    .audit_log_reason(END_REASON),
+            )
+            .await
+        {
+            warn!(%guild_id, error = %e, "could not restore the verification level");
+        }
+    }
+
+    for change in &channels {
+        restore_slowmode(http, *change).await;
+    }
+
+    info!(%guild_id, restored_channels = channels.len(), "lockdown ended");
+
+    let by = ended_by.map_or_else(
+        || "its time ran out".to_string(),
+        |user| format!("ended by <@{user}>"),
+    );
+    alert(
+        http,
+        settings,
+        format!(
+            "**Lockdown over** ({by}). {} suspicious joiner(s) were caught; \
+             verification and slowmode are back to how they were.",
+            ended.joiners_actioned,
+        ),
+    )
+    .await?;
+
+    Ok(Some(ended))
+}
+
+/// Ends every timed lockdown that has run out. Returns how many ended.
+pub async fn sweep(
+    http: &Http,
+    pool: &PgPool,
+    store: &SettingsStore<RaidSettingsRow>,
+) -> Result<usize> {
+    let mut ended = 0;
+
+    for guild in lockdown::expired(pool).await? {
+        let settings = store.get(guild).await?;
+        let guild_id = GuildId::new(as_u64(guild));
+
+        match exit(http, pool, guild_id, &settings, None).await {
+            Ok(Some(_)) => ended += 1,
+            Ok(None) => {},
+            Err(e) => {
+                warn!(%guild_id, error = %e, "could not end an expired lockdown")
+            },
+        }
+    }
+
+    Ok(ended)
+}
+
+async fn raise_verification(
+    http: &Http,
+    pool: &PgPool,
+    guild_id: GuildId,
+    reason: &str,
+) -> Result<()> {
+    let guild = guild_id.to_partial_guild(http).await?;
+    let current = u8::from(guild.verification_level);
+
+    if current >= u8::from(VerificationLevel::High) {
+        return Ok(());
+    }
+
+    // Recorded first: if the edit fails, ending the lockdown sets the level
+    // it already has.
+    lockdown::record_verification_level(
+        pool,
+        as_i64(guild_id.get()),
+        i16::from(current),
+    )
+    .await?;
+
+    guild_id
+        .edit(
+            http,
+            EditGuild::new()
+                .verification_level(VerificationLevel::High)
+                .audit_log_reason(reason),
+        )
+        .await?;
+
+    Ok(())
+}
+
+async fn slow_channels(
+    http: &Http,
+    pool: &PgPool,
+    guild_id: GuildId,
+    seconds: i32,
+    reason: &str,
+) -> Result<()> {
+    let target = u16::try_from(seconds).unwrap_or(u16::MAX);
+    let Some(slowmode) = NonMaxU16::new(target) else {
+        return Ok(());
+    };
+
+    for (channel_id, channel) in guild_id.channels(http).await? {
+        if channel.base.kind != ChannelType::Text {
+            continue;
+        }
+
+        let previous = channel.base.rate_limit_per_user.map_or(0, NonMaxU16::get);
+        if previous >= target {
+            continue;
+        }
+
+        lockdown::record_slowmode(pool, as_i64(guild_id.get()), SlowmodeChange {
+            channel_id: as_i64(channel_id.get()),
+            previous_slowmode: i32::from(previous),
+        })
+        .await?;
+
+        if let Err(e) = channel_id
+            .edit(
+                http,
+                EditChannel::new()
+                    .rate_limit_per_user(slowmode)
+                    .audit_log_reason(reason),
+            )
+            .await
+        {
+            warn!(%guild_id, %channel_id, error = %e, "lockdown could not set slowmode");
+        }
+    }
+
+    Ok(())
+}
+
+async fn restore_slowmode(http: &Http, change: SlowmodeChange) {
+    let channel_id = ChannelId::new(as_u64(change.channel_id));
+    let previous = u16::try_from(change.previous_slowmode)
+        .ok()
+        .and_then(NonMaxU16::new)
+        .unwrap_or(NonMaxU16::ZERO);
+
+    // A channel deleted during the lockdown has nothing to restore.
+    if let Err(e) = channel_id
+        .edit(
+            http,
+            EditChannel::new()
+                .rate_limit_per_user(previous)
+                .audit_log_reason(END_REASON),
+        )
+        .await
+    {
+        warn!(%channel_id, error = %e, "could not restore slowmode");
+    }
+}
+
+pub(crate) fn joiner_outcome(settings: &RaidSettingsRow) -> String {
+    match settings.joiner_action() {
+        RaidAction::None => "reported".to_string(),
+        RaidAction::Timeout => {
+            format!("timed out for {} minutes", settings.timeout_minutes)
+        },
+        RaidAction::Kick => "kicked".to_string(),
+    }
+}
+
+/// Posts to the guild's alert channel, if it has one.
+pub(crate) async fn alert(
+    http: &Http,
+    settings: &RaidSettingsRow,
+    content: String,
+) -> Result<()> {
+    let Some(channel) = settings.alert_channel_id else {
+        return Ok(());
+    };
+
+    ChannelId::new(as_u64(channel))
+        .send_message(http, CreateMessage::new().content(content))
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,164 @@
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+use jiff::SignedDuration;
+use moka::sync::Cache;
+use serenity::all::{GuildId, Http, Member, Mentionable, Timestamp, UserId};
+use sqlx::PgPool;
+use tracing::warn;
+use zayden_app::config::{RaidAction, RaidSettingsRow};
+use zayden_app::services::lockdown as lockdown_state;
+use zayden_core::as_i64;
+
+use crate::error::{RaidError, Result};
+use crate::joins::JoinTracker;
+use crate::lockdown::{self, alert};
+use crate::screen::{Joiner, RaidSignal, signals};
+
+pub const JOINER_REASON: &str = "Raid protection: suspicious join during lockdown";
+
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+/// Guilds without a join this long have their history dropped.
+const JOINS_IDLE: Duration = Duration::from_mins(10);
+
+static JOINS: LazyLock<Cache<GuildId, Arc<Mutex<JoinTracker>>>> =
+    LazyLock::new(|| {
+        Cache::builder().time_to_idle(JOINS_IDLE).max_capacity(4_096).build()
+    });
+
+/// A joiner actioned during a lockdown.
+#[derive(Debug, Clone)]
+pub struct RaidJoin {
+    pub user_id: UserId,
+    pub username: String,
+    pub guild_id: GuildId,
+    pub action: RaidAction,
+    pub signals: Vec<RaidSignal>,
+    /// How long the joiner was timed out for, if they were.
+    pub timeout: Option<SignedDuration>,
+}
+
+fn account_age_days(user_id: UserId) -> u64 {
+    let created = user_id.created_at().unix_timestamp();
+    let age = jiff::Timestamp::now().as_second().saturating_sub(created);
+
+    u64::try_from(age / SECONDS_PER_DAY).unwrap_or_default()
+}
+
+/// Counts the join towards the guild's join rate, starting a lockdown past
+/// its threshold, and during a lockdown screens the joiner.
+pub async fn member_join(
+    http: &Http,
+    member: &Member,
+    settings: &RaidSettingsRow,
+    pool: &PgPool,
+) -> Result<Option<RaidJoin>> {
+    if member.user.bot() || !settings.enabled {
+        return Ok(None);
+    }
+
+    let guild_id = member.guild_id;
+    let now = Instant::now();
+    let window =
+        Duration::from_secs(settings.join_window_seconds.unsigned_abs().into());
+
+    let tracker = JOINS.get_with(guild_id, Arc::default);
+    let (joins, similar_names) = {
+        let Ok(mut tracker) = tracker.lock() else {
+            return Err(RaidError::Internal("join tracker poisoned".to_string()));
+        };
+        tracker.record(now, &member.user.name);
+
+        (tracker.count(now, window), tracker.similar_names(now, &member.user.name))
+    };
+
+    let locked = if lockdown_state::is_active(pool, as_i64(guild_id.get())).await? {
+        true
+    } else if i64::from(joins) > i64::from(settings.join_threshold) {
+        let reason =
+            format!("{joins} joins in {} seconds", settings.join_window_seconds);
+        let minutes =
+            (settings.lockdown_minutes > 0).then_some(settings.lockdown_minutes);
+
+        // `false` only when another join started it first.
+        lockdown::enter(http, pool, guild_id, settings, &reason, None, minutes)
+            .await?;
+        true
+    } else {
+        false
+    };
+
+    if !locked {
+        return Ok(None);
+    }
+
+    let joiner = Joiner {
+        account_age_days: account_age_days(member.user.id),
+        has_avatar: member.user.avatar.is_some(),
+        similar_names,
+    };
+    let signals = signals(&joiner, settings);
+    if signals.is_empty() {
+        return Ok(None);
+    }
+
+    let action = settings.joiner_action();
+    let timeout = SignedDuration::from_mins(i64::from(
+        settings.timeout_minutes.clamp(1, RaidSettingsRow::MAX_TIMEOUT_MINUTES),
+    ));
+
+    match action {
+        RaidAction::None => {},
+        RaidAction::Timeout => {
+            let until = Timestamp::from_unix_timestamp(
+                jiff::Timestamp::now().as_second().saturating_add(timeout.as_secs()),
+            )
+            .map_err(|e| RaidError::Internal(e.to_string()))?;
+
+            member.clone().disable_communication_until(http, until).await?;
+        },
+        RaidAction::Kick => {
+            guild_id.kick(http, member.user.id, Some(JOINER_REASON)).await?;
+        },
+    }
+
+    let labels = signals.iter().map(|signal| signal.label()).collect::<Vec<_>>();
+
+    warn!(
+        %guild_id,
+        user_id = %member.user.id,
+        username = %member.user.name,
+        signals = ?labels,
+        action = action.as_str(),
+        "suspicious join during lockdown",
+    );
+
+    // Joiners are only counted, so a raid doesn't flood the alert channel;
+    // the lockdown's end reports how many there were. Without an action the
+    // first is still alerted on, as nothing else shows the raid is real.
+    let count = lockdown_state::count_actioned(pool, as_i64(guild_id.get())).await?;
+    if action == RaidAction::None && count == 1 {
+        alert(
+            http,
+            settings,
+            format!(
+                "{} ({}) joined during the lockdown: {}. Later suspicious \
+                 joiners are only counted until it ends.",
+                member.mention(),
+                member.user.id,
+                labels.join(", "),
+            ),
+        )
+        .await?;
+    }
+
+    Ok(Some(RaidJoin {
+        user_id: member.user.id,
+        username: member.user.name.to_string(),
+        guild_id,
+        action,
+        signals,
+        timeout: (action == RaidAction::Timeout).then_some(timeout),
+    }))
+}
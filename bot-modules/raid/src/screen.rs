@@ -0,0 +1,57 @@
+//! What makes a joiner look like part of a raid. Pure, so the handler in
+//! `member_join.rs` only gathers the facts.
+
+use zayden_app::config::RaidSettingsRow;
+
+/// Other recent joiners with the same name skeleton before a name counts as
+/// similar.
+pub const SIMILAR_NAME_MATCHES: u32 = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RaidSignal {
+    NewAccount,
+    DefaultAvatar,
+    SimilarName,
+}
+
+impl RaidSignal {
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::NewAccount => "new account",
+            Self::DefaultAvatar => "default avatar",
+            Self::SimilarName => "name like other joiners'",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Joiner {
+    pub account_age_days: u64,
+    pub has_avatar: bool,
+    /// Other recent joiners with the same name skeleton.
+    pub similar_names: u32,
+}
+
+/// The guild's enabled heuristics the joiner trips.
+#[must_use]
+pub fn signals(joiner: &Joiner, settings: &RaidSettingsRow) -> Vec<RaidSignal> {
+    let mut signals = Vec::new();
+
+    if settings.min_account_age_days > 0
+        && joiner.account_age_days
+            < u64::from(settings.min_account_age_days.unsigned_abs())
+    {
+        signals.push(RaidSignal::NewAccount);
+    }
+
+    if settings.flag_default_avatar && !joiner.has_avatar {
+        signals.push(RaidSignal::DefaultAvatar);
+    }
+
+    if settings.flag_similar_names && joiner.similar_names >= SIMILAR_NAME_MATCHES {
+        signals.push(RaidSignal::SimilarName);
+    }
+
+    signals
+}
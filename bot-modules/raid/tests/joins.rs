@@ -0,0 +1,67 @@
+//! The per-guild join tracker behind the join-rate trigger and the
+//! similar-name heuristic.
+
+use std::time::{Duration, Instant};
+
+use raid::joins::{JoinTracker, NAME_WINDOW, name_skeleton};
+
+#[test]
+fn skeletons_keep_only_lowercased_letters() {
+    assert_eq!(name_skeleton("Raider_01"), "raider");
+    assert_eq!(name_skeleton("raider22"), name_skeleton("RAIDER.9"));
+    assert_eq!(name_skeleton("1234"), "");
+}
+
+#[test]
+fn counts_only_joins_inside_the_window() {
+    let start = Instant::now();
+    let mut tracker = JoinTracker::default();
+
+    for second in [0, 4, 8, 9] {
+        tracker.record(start + Duration::from_secs(second), "member");
+    }
+
+    let now = start + Duration::from_secs(9);
+    assert_eq!(tracker.count(now, Duration::from_secs(5)), 3);
+    assert_eq!(tracker.count(now, Duration::from_secs(60)), 4);
+}
+
+#[test]
+fn similar_names_exclude_the_joiner() {
+    let start = Instant::now();
+    let mut tracker = JoinTracker::default();
+
+    for (second, name) in
+        [(0, "raider_1"), (1, "alice"), (2, "Raider2"), (3, "raider-3")]
+    {
+        tracker.record(start + Duration::from_secs(second), name);
+    }
+
+    let now = start + Duration::from_secs(3);
+    assert_eq!(tracker.similar_names(now, "raider-3"), 2);
+    assert_eq!(tracker.similar_names(now, "alice"), 0);
+}
+
+#[test]
+fn short_skeletons_never_match() {
+    let start = Instant::now();
+    let mut tracker = JoinTracker::default();
+
+    for name in ["ab1", "AB2", "ab_3"] {
+        tracker.record(start, name);
+    }
+
+    assert_eq!(tracker.similar_names(start, "ab_3"), 0);
+}
+
+#[test]
+fn similar_names_forget_old_joins() {
+    let start = Instant::now();
+    let mut tracker = JoinTracker::default();
+
+    tracker.record(start, "raider1");
+    let later = start + NAME_WINDOW + Duration::from_secs(1);
+    tracker.record(later, "raider2");
+
+    assert_eq!(tracker.similar_names(later, "raider2"), 0);
+}
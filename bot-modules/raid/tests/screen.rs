@@ -0,0 +1,78 @@
+//! Which raid heuristics a joiner trips under a guild's settings.
+
+use raid::RaidSignal;
+use raid::screen::{Joiner, SIMILAR_NAME_MATCHES, signals};
+use zayden_app::config::{RaidAction, RaidSettingsRow};
+
+fn settings() -> RaidSettingsRow {
+    RaidSettingsRow {
+        guild_id: 1,
+        enabled: true,
+        join_threshold: RaidSettingsRow::DEFAULT_JOIN_THRESHOLD,
+        join_window_seconds: RaidSettingsRow::DEFAULT_JOIN_WINDOW_SECONDS,
+        lockdown_minutes: RaidSettingsRow::DEFAULT_LOCKDOWN_MINUTES,
+        min_account_age_days: 7,
+        flag_default_avatar: true,
+        flag_similar_names: true,
+        joiner_action: RaidAction::Timeout.as_str().to_string(),
+        timeout_minutes: RaidSettingsRow::DEFAULT_TIMEOUT_MINUTES,
+        raise_verification: true,
+        slowmode_seconds: RaidSettingsRow::DEFAULT_SLOWMODE_SECONDS,
+        alert_channel_id: None,
+    }
+}
+
+const ESTABLISHED: Joiner =
+    Joiner { account_age_days: 400, has_avatar: true, similar_names: 0 };
+
+#[test]
+fn established_members_trip_nothing() {
+    assert!(signals(&ESTABLISHED, &settings()).is_empty());
+}
+
+#[test]
+fn fresh_faceless_lookalikes_trip_everything() {
+    let joiner = Joiner {
+        account_age_days: 0,
+        has_avatar: false,
+        similar_names: SIMILAR_NAME_MATCHES,
+    };
+
+    assert_eq!(signals(&joiner, &settings()), [
+        RaidSignal::NewAccount,
+        RaidSignal::DefaultAvatar,
+        RaidSignal::SimilarName
+    ]);
+}
+
+#[test]
+fn account_age_is_exclusive_and_zero_turns_it_off() {
+    let mut settings = settings();
+    let joiner = Joiner { account_age_days: 7, ..ESTABLISHED };
+    assert!(signals(&joiner, &settings).is_empty());
+
+    let joiner = Joiner { account_age_days: 6, ..ESTABLISHED };
+    assert_eq!(signals(&joiner, &settings), [RaidSignal::NewAccount]);
+
+    settings.min_account_age_days = 0;
+    assert!(
+        signals(&Joiner { account_age_days: 0, ..ESTABLISHED }, &settings)
+            .is_empty()
+    );
+}
+
+#[test]
+fn disabled_heuristics_are_ignored() {
+    let mut settings = settings();
+    settings.flag_default_avatar = false;
+    settings.flag_similar_names = false;
+
+    let joiner = Joiner { has_avatar: false, similar_names: 10, ..ESTABLISHED };
+    assert!(signals(&joiner, &settings).is_empty());
+}
+
+#[test]
+fn one_lookalike_is_not_enough() {
+    let joiner = Joiner { similar_names: SIMILAR_NAME_MATCHES - 1, ..ESTABLISHED };
+    assert!(signals(&joiner, &settings()).is_empty());
+}
@@ -18,7 +18,10 @@ use zayden_core::{
 };
 
 mod add;
+mod panel;
+mod remove;
 
+pub use self::panel::RolePanelCommand;
 use crate::error::{ReactionRoleError, Result};
 
 pub struct ReactionRoleCommand;
@@ -51,6 +54,10 @@ impl ReactionRoleCommand {
                 Self::add(http, pool, guild_id, channel_id, reaction, options)
                     .await?;
             },
+            "remove" => {
+                Self::remove(http, pool, guild_id, channel_id, reaction, options)
+                    .await?;
+            },
             _ => {
                 return Err(ReactionRoleError::Internal(format!(
                     "unexpected subcommand: {name}"
@@ -98,9 +105,37 @@ impl ReactionRoleCommand {
             "The message id of the reaction role message",
         ));
 
+        let remove = CreateCommandOption::new(
+            CommandOptionType::SubCommand,
+            "remove",
+            "Removes a reaction role",
+        )
+        .add_sub_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "emoji",
+                "The emoji of the reaction role",
+            )
+            .required(true),
+        )
+        .add_sub_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "message_id",
+                "The message id of the reaction role message",
+            )
+            .required(true),
+        )
+        .add_sub_option(CreateCommandOption::new(
+            CommandOptionType::Channel,
+            "channel",
+            "The channel the message is in",
+        ));
+
         CreateCommand::new("reaction_role")
-            .description("Adds a reaction role")
+            .description("Manage reaction roles")
             .default_member_permissions(Permissions::MANAGE_MESSAGES)
             .add_option(add)
+            .add_option(remove)
     }
 }
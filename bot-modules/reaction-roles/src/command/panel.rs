@@ -0,0 +1,444 @@
+use std::collections::HashMap;
+use std::fmt::Write as _;
+
+use serenity::all::{
+    CommandInteraction,
+    CommandOptionType,
+    CreateCommand,
+    CreateCommandOption,
+    CreateEmbed,
+    EditInteractionResponse,
+    GenericInteractionChannel,
+    GuildId,
+    Http,
+    Mentionable,
+    Permissions,
+    ResolvedValue,
+    Role,
+};
+use sqlx::PgPool;
+use zayden_core::{
+    optional_option,
+    parse_options,
+    parse_subcommand,
+    required_option,
+};
+
+use crate::panel::mode::{PanelKind, PanelMode};
+use crate::panel::{NewEntry, PanelFields, RolePanelManager, RolePanelRow, sync};
+use crate::{ReactionRoleError, Result};
+
+fn fields<'a>(options: &mut HashMap<&str, ResolvedValue<'a>>) -> PanelFields<'a> {
+    PanelFields {
+        title: optional_option(options, "title"),
+        description: optional_option(options, "description"),
+        kind: optional_option::<&str, _>(options, "kind")
+            .and_then(|kind| kind.parse().ok()),
+        mode: optional_option::<&str, _>(options, "mode")
+            .and_then(|mode| mode.parse().ok()),
+        role_duration_minutes: optional_option::<i64, _>(
+            options,
+            "duration_minutes",
+        )
+        .and_then(|minutes| i32::try_from(minutes).ok()),
+    }
+}
+
+async fn find(
+    pool: &PgPool,
+    guild_id: GuildId,
+    options: &mut HashMap<&str, ResolvedValue<'_>>,
+) -> Result<RolePanelRow> {
+    let id: i64 = required_option(options, "panel")?;
+    let id = i32::try_from(id).map_err(|_e| ReactionRoleError::PanelNotFound)?;
+
+    RolePanelManager::get(pool, guild_id, id)
+        .await?
+        .ok_or(ReactionRoleError::PanelNotFound)
+}
+
+async fn describe(pool: &PgPool, panel: &RolePanelRow) -> Result<String> {
+    let entries = RolePanelManager::entries(pool, panel.id).await?;
+    let requirements = RolePanelManager::requirements(pool, panel.id).await?;
+
+    let mut out = format!(
+        "{} · {} · <#{}>\n",
+        panel.kind.label(),
+        panel.mode.label(),
+        panel.channel_id
+    );
+
+    if let Some(minutes) = panel.role_duration_minutes {
+        let _ = writeln!(out, "Roles expire after {minutes} minutes");
+    }
+
+    for requirement in &requirements {
+        let _ = writeln!(
+            out,
+            "{} {}",
+            if requirement.forbidden { "Forbidden:" } else { "Requires:" },
+            requirement.role().mention()
+        );
+    }
+
+    if entries.is_empty() {
+        out.push_str("No roles yet\n");
+    } else {
+        let roles = entries
+            .iter()
+            .map(|entry| entry.role().mention().to_string())
+            .collect::<Vec<_>>()
+            .join(" ");
+        let _ = writeln!(out, "Roles: {roles}");
+    }
+
+    Ok(out)
+}
+
+pub struct RolePanelCommand;
+
+impl RolePanelCommand {
+    pub async fn run(
+        http: &Http,
+        interaction: &CommandInteraction,
+        pool: &PgPool,
+    ) -> Result<()> {
+        interaction.defer_ephemeral(http).await?;
+
+        let guild_id =
+            interaction.guild_id.ok_or(ReactionRoleError::MissingGuildId)?;
+
+        let (name, options) = parse_subcommand(interaction.data.options())?;
+        let mut options = parse_options(options);
+
+        let content = match name {
+            "create" => {
+                let title: &str = required_option(&mut options, "title")?;
+                let channel_id = optional_option(&mut options, "channel").map_or(
+                    interaction.channel_id,
+                    |channel: &GenericInteractionChannel| channel.id(),
+                );
+
+                let panel = RolePanelManager::create(
+                    pool,
+                    guild_id,
+                    channel_id,
+                    title,
+                    fields(&mut options),
+                )
+                .await?;
+                sync::sync(http, pool, &panel).await?;
+
+                format!(
+                    "Posted role panel **{}** (id {}). Add roles with `/role_panel add_role`.",
+                    panel.title, panel.id
+                )
+            },
+            "edit" => {
+                let panel = find(pool, guild_id, &mut options).await?;
+                let panel =
+                    RolePanelManager::edit(pool, panel.id, fields(&mut options))
+                        .await?;
+                sync::sync(http, pool, &panel).await?;
+
+                format!("Updated role panel **{}**.", panel.title)
+            },
+            "delete" => {
+                let panel = find(pool, guild_id, &mut options).await?;
+                RolePanelManager::delete(pool, guild_id, panel.id).await?;
+                sync::delete(http, &panel).await?;
+
+                format!("Deleted role panel **{}**.", panel.title)
+            },
+            "list" => {
+                let panels = RolePanelManager::list(pool, guild_id).await?;
+
+                if panels.is_empty() {
+                    interaction
+                        .edit_response(
+                            http,
+                            EditInteractionResponse::new().content(
+                                "No role panels yet. Post one with `/role_panel create`.",
+                            ),
+                        )
+                        .await?;
+
+                    return Ok(());
+                }
+
+                let mut embed = CreateEmbed::new().title("Role Panels");
+                for panel in panels.iter().take(25) {
+                    embed = embed.field(
+                        format!("{} · {}", panel.id, panel.title),
+                        describe(pool, panel)
+                            .await?
+                            .chars()
+                            .take(1024)
+                            .collect::<String>(),
+                        false,
+                    );
+                }
+
+                interaction
+                    .edit_response(http, EditInteractionResponse::new().embed(embed))
+                    .await?;
+
+                return Ok(());
+            },
+            "add_role" => {
+                let panel = find(pool, guild_id, &mut options).await?;
+                let role: &Role = required_option(&mut options, "role")?;
+
+                let entry = NewEntry {
+                    role: role.id,
+                    label: optional_option(&mut options, "label")
+                        .unwrap_or(&role.name),
+                    emoji: optional_option(&mut options, "emoji"),
+                    description: optional_option(&mut options, "description"),
+                };
+
+                if !RolePanelManager::add_entry(pool, panel.id, entry).await? {
+                    return Err(ReactionRoleError::PanelFull);
+                }
+                sync::sync(http, pool, &panel).await?;
+
+                format!("{} is on **{}**.", role.mention(), panel.title)
+            },
+            "remove_role" => {
+                let panel = find(pool, guild_id, &mut options).await?;
+                let role: &Role = required_option(&mut options, "role")?;
+
+                if !RolePanelManager::remove_entry(pool, panel.id, role.id).await? {
+                    return Err(ReactionRoleError::RoleNotOnPanel);
+                }
+                sync::sync(http, pool, &panel).await?;
+
+                format!("Removed {} from **{}**.", role.mention(), panel.title)
+            },
+            "require" => {
+                let panel = find(pool, guild_id, &mut options).await?;
+                let role: &Role = required_option(&mut options, "role")?;
+                let forbidden =
+                    optional_option(&mut options, "forbidden").unwrap_or(false);
+
+                let applies = RolePanelManager::toggle_requirement(
+                    pool, panel.id, role.id, forbidden,
+                )
+                .await?;
+
+                match (applies, forbidden) {
+                    (true, false) => format!(
+                        "Members now need {} to use **{}**.",
+                        role.mention(),
+                        panel.title
+                    ),
+                    (true, true) => format!(
+                        "Members with {} can no longer use **{}**.",
+                        role.mention(),
+                        panel.title
+                    ),
+                    (false, _) => format!(
+                        "{} no longer affects who can use **{}**.",
+                        role.mention(),
+                        panel.title
+                    ),
+                }
+            },
+            "refresh" => {
+                let panel = find(pool, guild_id, &mut options).await?;
+                sync::sync(http, pool, &panel).await?;
+
+                format!("Refreshed role panel **{}**.", panel.title)
+            },
+            _ => {
+                return Err(ReactionRoleError::Internal(format!(
+                    "unexpected subcommand: {name}"
+                )));
+            },
+        };
+
+        interaction
+            .edit_response(http, EditInteractionResponse::new().content(content))
+            .await?;
+
+        Ok(())
+    }
+
+    pub fn register<'a>() -> CreateCommand<'a> {
+        let panel_option = || {
+            CreateCommandOption::new(
+                CommandOptionType::Integer,
+                "panel",
+                "The panel id, as shown by /role_panel list",
+            )
+            .required(true)
+            .min_int_value(1)
+        };
+
+        let role_option = |description: &'a str| {
+            CreateCommandOption::new(CommandOptionType::Role, "role", description)
+                .required(true)
+        };
+
+        let settings = |option: CreateCommandOption<'a>| {
+            let mut kind = CreateCommandOption::new(
+                CommandOptionType::String,
+                "kind",
+                "Buttons or a select menu",
+            );
+            for k in PanelKind::ALL {
+                kind = kind.add_string_choice(k.label(), k.id());
+            }
+
+            let mut mode = CreateCommandOption::new(
+                CommandOptionType::String,
+                "mode",
+                "What picking a role does",
+            );
+            for m in PanelMode::ALL {
+                mode = mode.add_string_choice(m.label(), m.id());
+            }
+
+            option
+                .add_sub_option(
+                    CreateCommandOption::new(
+                        CommandOptionType::String,
+                        "description",
+                        "Text shown above the roles",
+                    )
+                    .max_length(4000),
+                )
+                .add_sub_option(kind)
+                .add_sub_option(mode)
+                .add_sub_option(
+                    CreateCommandOption::new(
+                        CommandOptionType::Integer,
+                        "duration_minutes",
+                        "Take roles back after this long (0 to keep them)",
+                    )
+                    .min_int_value(0),
+                )
+        };
+
+        let create = settings(
+            CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "create",
+                "Post a new role panel",
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "title",
+                    "The panel's title",
+                )
+                .required(true)
+                .max_length(256),
+            )
+            .add_sub_option(CreateCommandOption::new(
+                CommandOptionType::Channel,
+                "channel",
+                "Where to post the panel (default: here)",
+            )),
+        );
+
+        let edit = settings(
+            CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "edit",
+                "Change a role panel's settings",
+            )
+            .add_sub_option(panel_option())
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "title",
+                    "The panel's title",
+                )
+                .max_length(256),
+            ),
+        );
+
+        let delete = CreateCommandOption::new(
+            CommandOptionType::SubCommand,
+            "delete",
+            "Delete a role panel and its message",
+        )
+        .add_sub_option(panel_option());
+
+        let list = CreateCommandOption::new(
+            CommandOptionType::SubCommand,
+            "list",
+            "List the role panels",
+        );
+
+        let add_role = CreateCommandOption::new(
+            CommandOptionType::SubCommand,
+            "add_role",
+            "Offer a role on a panel, or change how it's shown",
+        )
+        .add_sub_option(panel_option())
+        .add_sub_option(role_option("The role to offer"))
+        .add_sub_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "label",
+                "Button or option text (default: the role name)",
+            )
+            .max_length(80),
+        )
+        .add_sub_option(CreateCommandOption::new(
+            CommandOptionType::String,
+            "emoji",
+            "An emoji shown next to the label",
+        ))
+        .add_sub_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "description",
+                "Shown under the option in select menus",
+            )
+            .max_length(100),
+        );
+
+        let remove_role = CreateCommandOption::new(
+            CommandOptionType::SubCommand,
+            "remove_role",
+            "Stop offering a role on a panel",
+        )
+        .add_sub_option(panel_option())
+        .add_sub_option(role_option("The role to remove"));
+
+        let require = CreateCommandOption::new(
+            CommandOptionType::SubCommand,
+            "require",
+            "Toggle a role members must have, or must not have, to use a panel",
+        )
+        .add_sub_option(panel_option())
+        .add_sub_option(role_option("The role"))
+        .add_sub_option(CreateCommandOption::new(
+            CommandOptionType::Boolean,
+            "forbidden",
+            "Block members with this role instead of requiring it",
+        ));
+
+        let refresh = CreateCommandOption::new(
+            CommandOptionType::SubCommand,
+            "refresh",
+            "Re-post a panel whose message was deleted",
+        )
+        .add_sub_option(panel_option());
+
+        CreateCommand::new("role_panel")
+            .description("Manage button and select-menu role panels")
+            .default_member_permissions(Permissions::MANAGE_ROLES)
+            .add_option(create)
+            .add_option(edit)
+            .add_option(delete)
+            .add_option(list)
+            .add_option(add_role)
+            .add_option(remove_role)
+            .add_option(require)
+            .add_option(refresh)
+    }
+}
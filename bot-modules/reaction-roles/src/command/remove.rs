@@ -0,0 +1,49 @@
+use std::collections::HashMap;
+
+use serenity::all::{
+    GenericChannelId,
+    GuildId,
+    Http,
+    MessageId,
+    ReactionType,
+    ResolvedValue,
+};
+use sqlx::PgPool;
+use tracing::warn;
+use zayden_core::required_option;
+
+use super::ReactionRoleCommand;
+use crate::{ReactionRole, ReactionRoleError, Result};
+
+impl ReactionRoleCommand {
+    pub(super) async fn remove(
+        http: &Http,
+        pool: &PgPool,
+        guild_id: GuildId,
+        channel_id: GenericChannelId,
+        reaction: ReactionType,
+        mut options: HashMap<&str, ResolvedValue<'_>>,
+    ) -> Result<()> {
+        let id: &str = required_option(&mut options, "message_id")?;
+        let message_id = id
+            .parse()
+            .map(MessageId::new)
+            .map_err(|_e| ReactionRoleError::InvalidMessageId(id.to_string()))?;
+
+        let emoji = reaction.to_string();
+
+        if ReactionRole::row(pool, message_id, &emoji).await?.is_none() {
+            return Err(ReactionRoleError::MappingNotFound);
+        }
+
+        ReactionRole::delete(pool, guild_id, channel_id, message_id, &emoji).await?;
+
+        if let Err(e) =
+            channel_id.delete_reaction_emoji(http, message_id, reaction).await
+        {
+            warn!(error = ?e, "failed to clear reaction-role reaction");
+        }
+
+        Ok(())
+    }
+}
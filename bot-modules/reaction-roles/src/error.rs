@@ -1,6 +1,6 @@
 use std::borrow::Cow;
 
-use serenity::all::ReactionConversionError;
+use serenity::all::{Mentionable, ReactionConversionError, RoleId};
 use zayden_core::CoreError;
 use zayden_core::error::{HandlerError, Respond};
 
@@ -13,6 +13,13 @@ pub enum ReactionRoleError {
     InvalidMessageId(String),
     UnsupportedEmoji(String),
     DuplicateMapping(String),
+    MappingNotFound,
+    PanelNotFound,
+    PanelFull,
+    RoleNotOnPanel,
+    AlreadyClaimed,
+    MissingRequiredRole(RoleId),
+    ForbiddenRole(RoleId),
     Internal(String),
     ReactionConversionError(ReactionConversionError),
     Serenity(serenity::Error),
@@ -31,6 +38,23 @@ impl std::fmt::Display for ReactionRoleError {
             Self::DuplicateMapping(emoji) => {
                 write!(f, "{emoji} is already mapped on that message")
             },
+            Self::MappingNotFound => {
+                write!(f, "That emoji isn't mapped to a role on that message")
+            },
+            Self::PanelNotFound => write!(f, "That role panel no longer exists"),
+            Self::PanelFull => {
+                write!(f, "A role panel can offer at most 25 roles")
+            },
+            Self::RoleNotOnPanel => write!(f, "That role isn't on this panel"),
+            Self::AlreadyClaimed => {
+                write!(f, "You've already used this panel")
+            },
+            Self::MissingRequiredRole(role) => {
+                write!(f, "You need {} to use this panel", role.mention())
+            },
+            Self::ForbiddenRole(role) => {
+                write!(f, "Members with {} can't use this panel", role.mention())
+            },
             Self::ReactionConversionError(_) => {
                 write!(f, "Failed to convert emoji to reaction")
             },
@@ -52,6 +76,13 @@ impl std::error::Error for ReactionRoleError {
             | Self::InvalidMessageId(_)
             | Self::UnsupportedEmoji(_)
             | Self::DuplicateMapping(_)
+            | Self::MappingNotFound
+            | Self::PanelNotFound
+            | Self::PanelFull
+            | Self::RoleNotOnPanel
+            | Self::AlreadyClaimed
+            | Self::MissingRequiredRole(_)
+            | Self::ForbiddenRole(_)
             | Self::Internal(_) => None,
         }
     }
@@ -68,7 +99,14 @@ impl Respond for ReactionRoleError {
             | Self::MissingUserId
             | Self::InvalidMessageId(_)
             | Self::UnsupportedEmoji(_)
-            | Self::DuplicateMapping(_) => Some(Cow::Owned(self.to_string())),
+            | Self::DuplicateMapping(_)
+            | Self::MappingNotFound
+            | Self::PanelNotFound
+            | Self::PanelFull
+            | Self::RoleNotOnPanel
+            | Self::AlreadyClaimed
+            | Self::MissingRequiredRole(_)
+            | Self::ForbiddenRole(_) => Some(Cow::Owned(self.to_string())),
         }
     }
 }
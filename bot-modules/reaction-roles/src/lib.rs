@@ -2,11 +2,17 @@ mod command;
 pub mod emoji;
 pub mod error;
 pub mod manager;
+pub mod panel;
 mod reaction;
+pub mod temp_role;
 
-pub use command::ReactionRoleCommand;
+pub use command::{ReactionRoleCommand, RolePanelCommand};
 pub use emoji::ParsedEmoji;
 pub use error::{ReactionRoleError, Result};
 pub use manager::ReactionRole;
+pub use panel::mode::{PanelKind, PanelMode};
+pub use panel::render::PANEL_PREFIX;
+pub use panel::{RolePanelComponent, RolePanelManager};
 pub use reaction::ReactionRoleReaction;
 pub use serenity::all::{GenericChannelId, GuildId, MessageId, RoleId};
+pub use temp_role::TemporaryRoleManager;
@@ -0,0 +1,148 @@
+use serenity::all::{
+    ComponentInteraction,
+    ComponentInteractionDataKind,
+    EditInteractionResponse,
+    GuildId,
+    Http,
+    Member,
+    Mentionable,
+    RoleId,
+};
+use sqlx::PgPool;
+
+use super::mode::{PanelMode, RoleChanges, Selection, check_requirements};
+use super::render::parse_custom_id;
+use super::{PanelEntry, RolePanelManager, RolePanelRow};
+use crate::{ReactionRoleError, Result, TemporaryRoleManager};
+
+fn mentions(roles: &[RoleId]) -> String {
+    roles
+        .iter()
+        .map(|role| role.mention().to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+pub struct RolePanelComponent;
+
+impl RolePanelComponent {
+    pub async fn run(
+        http: &Http,
+        interaction: &ComponentInteraction,
+        pool: &PgPool,
+    ) -> Result<()> {
+        interaction.defer_ephemeral(http).await?;
+
+        let guild_id =
+            interaction.guild_id.ok_or(ReactionRoleError::MissingGuildId)?;
+        let member =
+            interaction.member.as_ref().ok_or(ReactionRoleError::MissingGuildId)?;
+
+        let (panel_id, button) = parse_custom_id(&interaction.data.custom_id)
+            .ok_or(ReactionRoleError::PanelNotFound)?;
+
+        let panel = RolePanelManager::get(pool, guild_id, panel_id)
+            .await?
+            .ok_or(ReactionRoleError::PanelNotFound)?;
+
+        let held = member.roles.iter().copied().collect::<Vec<_>>();
+
+        let requirements = RolePanelManager::requirements(pool, panel.id).await?;
+        check_requirements(&held, &requirements)?;
+
+        let panel_roles = RolePanelManager::entries(pool, panel.id)
+            .await?
+            .iter()
+            .map(PanelEntry::role)
+            .collect::<Vec<_>>();
+
+        let menu_roles: Vec<RoleId>;
+        let selection = match (button, &interaction.data.kind) {
+            (Some(role), _) => Selection::Button(role),
+            (None, ComponentInteractionDataKind::StringSelect { values }) => {
+                menu_roles = values
+                    .iter()
+                    .filter_map(|value| value.parse::<u64>().ok())
+                    .filter(|id| *id != 0)
+                    .map(RoleId::new)
+                    .collect();
+                Selection::Menu(&menu_roles)
+            },
+            (None, _) => {
+                return Err(ReactionRoleError::Internal(format!(
+                    "unexpected role panel interaction: {}",
+                    interaction.data.custom_id
+                )));
+            },
+        };
+
+        let changes = panel.mode.plan(&panel_roles, &held, selection);
+
+        // Claimed before the roles are given so a double click can't use the
+        // panel twice, and released if they couldn't be.
+        let claimed = panel.mode == PanelMode::VerifyOnce && !changes.is_empty();
+        if claimed
+            && !RolePanelManager::claim(pool, panel.id, member.user.id).await?
+        {
+            return Err(ReactionRoleError::AlreadyClaimed);
+        }
+
+        if let Err(e) =
+            Self::apply(http, pool, guild_id, member, &panel, &changes).await
+        {
+            if claimed {
+                RolePanelManager::release(pool, panel.id, member.user.id).await?;
+            }
+            return Err(e);
+        }
+
+        let content = match (changes.add.is_empty(), changes.remove.is_empty()) {
+            (true, true) => String::from("Your roles are already up to date."),
+            (false, true) => format!("Added {}.", mentions(&changes.add)),
+            (true, false) => format!("Removed {}.", mentions(&changes.remove)),
+            (false, false) => format!(
+                "Added {}. Removed {}.",
+                mentions(&changes.add),
+                mentions(&changes.remove)
+            ),
+        };
+
+        interaction
+            .edit_response(http, EditInteractionResponse::new().content(content))
+            .await?;
+
+        Ok(())
+    }
+
+    async fn apply(
+        http: &Http,
+        pool: &PgPool,
+        guild_id: GuildId,
+        member: &Member,
+        panel: &RolePanelRow,
+        changes: &RoleChanges,
+    ) -> Result<()> {
+        for role in &changes.add {
+            member.add_role(http, *role, Some("Role panel")).await?;
+
+            if let Some(minutes) = panel.role_duration_minutes {
+                TemporaryRoleManager::grant(
+                    pool,
+                    guild_id,
+                    member.user.id,
+                    *role,
+                    minutes,
+                )
+                .await?;
+            }
+        }
+
+        for role in &changes.remove {
+            member.remove_role(http, *role, Some("Role panel")).await?;
+            TemporaryRoleManager::revoke(pool, guild_id, member.user.id, *role)
+                .await?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,493 @@
+mod component;
+pub mod mode;
+pub mod render;
+pub mod sync;
+
+use serenity::all::{GenericChannelId, GuildId, MessageId, RoleId, UserId};
+use sqlx::PgPool;
+use zayden_core::{as_i64, as_u64};
+
+pub use self::component::RolePanelComponent;
+use self::mode::{PanelKind, PanelMode, PanelRequirement};
+
+/// A message holds at most 25 buttons or select options.
+pub const MAX_PANEL_ROLES: i16 = 25;
+
+#[derive(Debug, Clone)]
+pub struct RolePanelRow {
+    pub id: i32,
+    pub guild_id: i64,
+    pub channel_id: i64,
+    pub message_id: Option<i64>,
+    pub title: String,
+    pub description: Option<String>,
+    pub kind: PanelKind,
+    pub mode: PanelMode,
+    pub role_duration_minutes: Option<i32>,
+}
+
+impl RolePanelRow {
+    #[must_use]
+    pub const fn guild_id(&self) -> GuildId {
+        GuildId::new(as_u64(self.guild_id))
+    }
+
+    #[must_use]
+    pub const fn channel_id(&self) -> GenericChannelId {
+        GenericChannelId::new(as_u64(self.channel_id))
+    }
+
+    #[must_use]
+    pub fn message_id(&self) -> Option<MessageId> {
+        self.message_id.map(|id| MessageId::new(as_u64(id)))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PanelEntry {
+    pub role_id: i64,
+    pub position: i16,
+    pub label: String,
+    pub emoji: Option<String>,
+    pub description: Option<String>,
+}
+
+impl PanelEntry {
+    #[must_use]
+    pub const fn role(&self) -> RoleId {
+        RoleId::new(as_u64(self.role_id))
+    }
+}
+
+/// Settings for a new panel, or the changes to an existing one. In an edit,
+/// `None` leaves a field unchanged and a zero duration makes roles permanent.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PanelFields<'a> {
+    pub title: Option<&'a str>,
+    pub description: Option<&'a str>,
+    pub kind: Option<PanelKind>,
+    pub mode: Option<PanelMode>,
+    pub role_duration_minutes: Option<i32>,
+}
+
+/// A role to offer on a panel.
+#[derive(Debug, Clone, Copy)]
+pub struct NewEntry<'a> {
+    pub role: RoleId,
+    pub label: &'a str,
+    pub emoji: Option<&'a str>,
+    pub description: Option<&'a str>,
+}
+
+pub struct RolePanelManager;
+
+impl RolePanelManager {
+    pub async fn list(
+        pool: &PgPool,
+        guild_id: GuildId,
+    ) -> sqlx::Result<Vec<RolePanelRow>> {
+        sqlx::query_as!(
+            RolePanelRow,
+            r#"SELECT
+                id,
+                guild_id,
+                channel_id,
+                message_id,
+                title,
+                description,
+                kind AS "kind: PanelKind",
+                mode AS "mode: PanelMode",
+                role_duration_minutes
+            FROM role_panels
+            WHERE guild_id = $1
+            ORDER BY id"#,
+            as_i64(guild_id.get())
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn get(
+        pool: &PgPool,
+        guild_id: GuildId,
+        id: i32,
+    ) -> sqlx::Result<Option<RolePanelRow>> {
+        sqlx::query_as!(
+            RolePanelRow,
+            r#"SELECT
+                id,
+                guild_id,
+                channel_id,
+                message_id,
+                title,
+                description,
+                kind AS "kind: PanelKind",
+                mode AS "mode: PanelMode",
+                role_duration_minutes
+            FROM role_panels
+            WHERE guild_id = $1 AND id = $2"#,
+            as_i64(guild_id.get()),
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    #[expect(
+        trivial_casts,
+        reason = "not a cast: `as T` is sqlx's bind-param type-override syntax, required for the custom role_panel_kind and role_panel_mode pgtypes"
+    )]
+    pub async fn create(
+        pool: &PgPool,
+        guild_id: GuildId,
+        channel_id: GenericChannelId,
+        title: &str,
+        fields: PanelFields<'_>,
+    ) -> sqlx::Result<RolePanelRow> {
+        let guild_id = as_i64(guild_id.get());
+
+        let mut tx = pool.begin().await?;
+
+        sqlx::query!(
+            "INSERT INTO guilds (id) VALUES ($1) ON CONFLICT (id) DO NOTHING",
+            guild_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        let row = sqlx::query_as!(
+            RolePanelRow,
+            r#"INSERT INTO role_panels (guild_id, channel_id, title, description, kind, mode, role_duration_minutes)
+            VALUES ($1, $2, $3, $4, COALESCE($5, 'buttons'::role_panel_kind), COALESCE($6, 'toggle'::role_panel_mode), NULLIF($7, 0))
+            RETURNING
+                id,
+                guild_id,
+                channel_id,
+                message_id,
+                title,
+                description,
+                kind AS "kind: PanelKind",
+                mode AS "mode: PanelMode",
+                role_duration_minutes"#,
+            guild_id,
+            as_i64(channel_id.get()),
+            title,
+            fields.description,
+            fields.kind as Option<PanelKind>,
+            fields.mode as Option<PanelMode>,
+            fields.role_duration_minutes
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(row)
+    }
+
+    #[expect(
+        trivial_casts,
+        reason = "not a cast: `as T` is sqlx's bind-param type-override syntax, required for the custom role_panel_kind and role_panel_mode pgtypes"
+    )]
+    pub async fn edit(
+        pool: &PgPool,
+        id: i32,
+        fields: PanelFields<'_>,
+    ) -> sqlx::Result<RolePanelRow> {
+        sqlx::query_as!(
+            RolePanelRow,
+            r#"UPDATE role_panels SET
+                title = COALESCE($2, title),
+                description = COALESCE($3, description),
+                kind = COALESCE($4, kind),
+                mode = COALESCE($5, mode),
+                role_duration_minutes = CASE WHEN $6::integer IS NULL THEN role_duration_minutes ELSE NULLIF($6, 0) END,
+                dirty = true
+            WHERE id = $1
+            RETURNING
+                id,
+                guild_id,
+                channel_id,
+                message_id,
+                title,
+                description,
+                kind AS "kind: PanelKind",
+                mode AS "mode: PanelMode",
+                role_duration_minutes"#,
+            id,
+            fields.title,
+            fields.description,
+            fields.kind as Option<PanelKind>,
+            fields.mode as Option<PanelMode>,
+            fields.role_duration_minutes
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Deletes the panel, returning it so its message can be removed too.
+    pub async fn delete(
+        pool: &PgPool,
+        guild_id: GuildId,
+        id: i32,
+    ) -> sqlx::Result<Option<RolePanelRow>> {
+        sqlx::query_as!(
+            RolePanelRow,
+            r#"DELETE FROM role_panels
+            WHERE guild_id = $1 AND id = $2
+            RETURNING
+                id,
+                guild_id,
+                channel_id,
+                message_id,
+                title,
+                description,
+                kind AS "kind: PanelKind",
+                mode AS "mode: PanelMode",
+                role_duration_minutes"#,
+            as_i64(guild_id.get()),
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Panels whose message is missing or out of date.
+    pub async fn dirty(pool: &PgPool) -> sqlx::Result<Vec<RolePanelRow>> {
+        sqlx::query_as!(
+            RolePanelRow,
+            r#"SELECT
+                id,
+                guild_id,
+                channel_id,
+                message_id,
+                title,
+                description,
+                kind AS "kind: PanelKind",
+                mode AS "mode: PanelMode",
+                role_duration_minutes
+            FROM role_panels
+            WHERE dirty
+            ORDER BY id"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn mark_dirty(pool: &PgPool, id: i32) -> sqlx::Result<()> {
+        sqlx::query!("UPDATE role_panels SET dirty = true WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn mark_synced(
+        pool: &PgPool,
+        id: i32,
+        message_id: MessageId,
+    ) -> sqlx::Result<()> {
+        sqlx::query!(
+            "UPDATE role_panels SET message_id = $2, dirty = false WHERE id = $1",
+            id,
+            as_i64(message_id.get())
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn entries(pool: &PgPool, id: i32) -> sqlx::Result<Vec<PanelEntry>> {
+        sqlx::query_as!(
+            PanelEntry,
+            "SELECT role_id, position, label, emoji, description
+            FROM role_panel_entries
+            WHERE panel_id = $1
+            ORDER BY position",
+            id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Adds `entry` to the end of the panel, or updates it in place if the
+    /// role is already offered. Returns `false` when the panel is full.
+    pub async fn add_entry(
+        pool: &PgPool,
+        id: i32,
+        entry: NewEntry<'_>,
+    ) -> sqlx::Result<bool> {
+        let role = as_i64(entry.role.get());
+
+        let mut tx = pool.begin().await?;
+
+        let updated = sqlx::query!(
+            "UPDATE role_panel_entries SET label = $3, emoji = $4, description = $5
+            WHERE panel_id = $1 AND role_id = $2",
+            id,
+            role,
+            entry.label,
+            entry.emoji,
+            entry.description
+        )
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+        let stored = if updated > 0 {
+            true
+        } else {
+            let inserted = sqlx::query!(
+                "INSERT INTO role_panel_entries (panel_id, role_id, position, label, emoji, description)
+                SELECT $1, $2, COALESCE(MAX(position) + 1, 0), $3, $4, $5
+                FROM role_panel_entries
+                WHERE panel_id = $1
+                HAVING COUNT(*) < $6",
+                id,
+                role,
+                entry.label,
+                entry.emoji,
+                entry.description,
+                i64::from(MAX_PANEL_ROLES)
+            )
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+
+            inserted == 1
+        };
+
+        if stored {
+            sqlx::query!("UPDATE role_panels SET dirty = true WHERE id = $1", id)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(stored)
+    }
+
+    /// Removes `role` from the panel and closes the gap it leaves.
+    pub async fn remove_entry(
+        pool: &PgPool,
+        id: i32,
+        role: RoleId,
+    ) -> sqlx::Result<bool> {
+        let mut tx = pool.begin().await?;
+
+        let Some(position) = sqlx::query_scalar!(
+            "DELETE FROM role_panel_entries WHERE panel_id = $1 AND role_id = $2
+            RETURNING position",
+            id,
+            as_i64(role.get())
+        )
+        .fetch_optional(&mut *tx)
+        .await?
+        else {
+            return Ok(false);
+        };
+
+        sqlx::query!(
+            "UPDATE role_panel_entries SET position = position - 1
+            WHERE panel_id = $1 AND position > $2",
+            id,
+            position
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query!("UPDATE role_panels SET dirty = true WHERE id = $1", id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+
+        Ok(true)
+    }
+
+    pub async fn requirements(
+        pool: &PgPool,
+        id: i32,
+    ) -> sqlx::Result<Vec<PanelRequirement>> {
+        sqlx::query_as!(
+            PanelRequirement,
+            "SELECT role_id, forbidden FROM role_panel_requirements
+            WHERE panel_id = $1
+            ORDER BY forbidden, role_id",
+            id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Adds `role` as a requirement of the given kind, or removes it if that
+    /// exact requirement already exists. Returns whether it applies afterwards.
+    pub async fn toggle_requirement(
+        pool: &PgPool,
+        id: i32,
+        role: RoleId,
+        forbidden: bool,
+    ) -> sqlx::Result<bool> {
+        let role = as_i64(role.get());
+
+        let removed = sqlx::query!(
+            "DELETE FROM role_panel_requirements
+            WHERE panel_id = $1 AND role_id = $2 AND forbidden = $3",
+            id,
+            role,
+            forbidden
+        )
+        .execute(pool)
+        .await?
+        .rows_affected();
+
+        if removed > 0 {
+            return Ok(false);
+        }
+
+        sqlx::query!(
+            "INSERT INTO role_panel_requirements (panel_id, role_id, forbidden)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (panel_id, role_id) DO UPDATE SET forbidden = EXCLUDED.forbidden",
+            id,
+            role,
+            forbidden
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(true)
+    }
+
+    /// Records that `user` used a verify-once panel. Returns `false` if they
+    /// already had.
+    pub async fn claim(pool: &PgPool, id: i32, user: UserId) -> sqlx::Result<bool> {
+        let inserted = sqlx::query!(
+            "INSERT INTO role_panel_claims (panel_id, user_id) VALUES ($1, $2)
+            ON CONFLICT DO NOTHING",
+            id,
+            as_i64(user.get())
+        )
+        .execute(pool)
+        .await?
+        .rows_affected();
+
+        Ok(inserted == 1)
+    }
+
+    /// Takes back a [`Self::claim`] whose roles couldn't be given, so the
+    /// member can try again.
+    pub async fn release(pool: &PgPool, id: i32, user: UserId) -> sqlx::Result<()> {
+        sqlx::query!(
+            "DELETE FROM role_panel_claims WHERE panel_id = $1 AND user_id = $2",
+            id,
+            as_i64(user.get())
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+}
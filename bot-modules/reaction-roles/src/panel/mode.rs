@@ -0,0 +1,232 @@
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+use serenity::all::RoleId;
+use zayden_core::as_u64;
+
+use crate::{ReactionRoleError, Result};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "role_panel_kind", rename_all = "snake_case")]
+pub enum PanelKind {
+    Buttons,
+    Select,
+}
+
+impl PanelKind {
+    pub const ALL: [Self; 2] = [Self::Buttons, Self::Select];
+
+    #[must_use]
+    pub const fn id(self) -> &'static str {
+        match self {
+            Self::Buttons => "buttons",
+            Self::Select => "select",
+        }
+    }
+
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Buttons => "Buttons",
+            Self::Select => "Select menu",
+        }
+    }
+}
+
+impl FromStr for PanelKind {
+    type Err = ();
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Self::ALL.into_iter().find(|kind| kind.id() == s).ok_or(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "role_panel_mode", rename_all = "snake_case")]
+pub enum PanelMode {
+    /// Clicking a role adds it, clicking again removes it.
+    Toggle,
+    AddOnly,
+    RemoveOnly,
+    /// At most one of the panel's roles at a time.
+    Unique,
+    /// Roles can be picked once and never removed through the panel.
+    VerifyOnce,
+}
+
+impl PanelMode {
+    pub const ALL: [Self; 5] = [
+        Self::Toggle,
+        Self::AddOnly,
+        Self::RemoveOnly,
+        Self::Unique,
+        Self::VerifyOnce,
+    ];
+
+    #[must_use]
+    pub const fn id(self) -> &'static str {
+        match self {
+            Self::Toggle => "toggle",
+            Self::AddOnly => "add_only",
+            Self::RemoveOnly => "remove_only",
+            Self::Unique => "unique",
+            Self::VerifyOnce => "verify_once",
+        }
+    }
+
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Toggle => "Toggle",
+            Self::AddOnly => "Add only",
+            Self::RemoveOnly => "Remove only",
+            Self::Unique => "Unique",
+            Self::VerifyOnce => "Verify once",
+        }
+    }
+
+    /// Shown in the panel's footer so members know what a click does.
+    #[must_use]
+    pub const fn hint(self) -> &'static str {
+        match self {
+            Self::Toggle => "Pick a role to get it, pick it again to drop it.",
+            Self::AddOnly => "Pick a role to get it.",
+            Self::RemoveOnly => "Pick a role to drop it.",
+            Self::Unique => "You can hold one of these roles at a time.",
+            Self::VerifyOnce => "You can only use this panel once.",
+        }
+    }
+
+    /// The roles to add and remove for a member holding `held` who made
+    /// `selection` on a panel offering `panel_roles`. Roles outside the panel
+    /// are never touched.
+    #[must_use]
+    pub fn plan(
+        self,
+        panel_roles: &[RoleId],
+        held: &[RoleId],
+        selection: Selection<'_>,
+    ) -> RoleChanges {
+        let holds = |role: &RoleId| held.contains(role);
+        let held_on_panel = panel_roles.iter().copied().filter(holds);
+
+        let picked = match selection {
+            Selection::Button(role) => vec![role],
+            Selection::Menu(roles) => roles.to_vec(),
+        };
+        let picked = picked
+            .into_iter()
+            .filter(|role| panel_roles.contains(role))
+            .collect::<Vec<_>>();
+
+        let mut changes = RoleChanges::default();
+
+        match (self, selection) {
+            (Self::Toggle, Selection::Button(_)) => {
+                for role in picked {
+                    if holds(&role) {
+                        changes.remove.push(role);
+                    } else {
+                        changes.add.push(role);
+                    }
+                }
+            },
+            // A menu submits the complete choice, so it replaces what the
+            // member held from this panel.
+            (Self::Toggle, Selection::Menu(_)) => {
+                changes.add = picked.iter().copied().filter(|r| !holds(r)).collect();
+                changes.remove =
+                    held_on_panel.filter(|role| !picked.contains(role)).collect();
+            },
+            (Self::AddOnly | Self::VerifyOnce, _) => {
+                changes.add = picked.into_iter().filter(|r| !holds(r)).collect();
+            },
+            (Self::RemoveOnly, _) => {
+                changes.remove = picked.into_iter().filter(holds).collect();
+            },
+            (Self::Unique, _) => match picked.first().copied() {
+                Some(role)
+                    if holds(&role) && matches!(selection, Selection::Button(_)) =>
+                {
+                    changes.remove.push(role);
+                },
+                Some(role) => {
+                    if !holds(&role) {
+                        changes.add.push(role);
+                    }
+                    changes.remove = held_on_panel.filter(|r| *r != role).collect();
+                },
+                None => changes.remove = held_on_panel.collect(),
+            },
+        }
+
+        changes
+    }
+}
+
+impl Display for PanelMode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+impl FromStr for PanelMode {
+    type Err = ();
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Self::ALL.into_iter().find(|mode| mode.id() == s).ok_or(())
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Selection<'a> {
+    Button(RoleId),
+    Menu(&'a [RoleId]),
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct RoleChanges {
+    pub add: Vec<RoleId>,
+    pub remove: Vec<RoleId>,
+}
+
+impl RoleChanges {
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.add.is_empty() && self.remove.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PanelRequirement {
+    pub role_id: i64,
+    pub forbidden: bool,
+}
+
+impl PanelRequirement {
+    #[must_use]
+    pub const fn role(&self) -> RoleId {
+        RoleId::new(as_u64(self.role_id))
+    }
+}
+
+/// Fails on the first required role the member lacks or forbidden role they
+/// hold.
+pub fn check_requirements(
+    held: &[RoleId],
+    requirements: &[PanelRequirement],
+) -> Result<()> {
+    for requirement in requirements {
+        let role = requirement.role();
+
+        match (requirement.forbidden, held.contains(&role)) {
+            (false, false) => {
+                return Err(ReactionRoleError::MissingRequiredRole(role));
+            },
+            (true, true) => return Err(ReactionRoleError::ForbiddenRole(role)),
+            (false, true) | (true, false) => {},
+        }
+    }
+
+    Ok(())
+}
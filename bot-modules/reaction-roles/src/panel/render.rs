@@ -0,0 +1,150 @@
+use std::fmt::Write as _;
+
+use serenity::all::{
+    ButtonStyle,
+    CreateActionRow,
+    CreateButton,
+    CreateComponent,
+    CreateEmbed,
+    CreateEmbedFooter,
+    CreateSelectMenu,
+    CreateSelectMenuKind,
+    CreateSelectMenuOption,
+    ReactionType,
+    RoleId,
+};
+
+use super::mode::{PanelKind, PanelMode};
+use super::{PanelEntry, RolePanelRow};
+
+/// Buttons are `{PANEL_PREFIX}{panel id}:{role id}`, a panel's select menu is
+/// `{PANEL_PREFIX}{panel id}`.
+pub const PANEL_PREFIX: &str = "role_panel:";
+
+const BUTTONS_PER_ROW: usize = 5;
+
+#[must_use]
+pub fn button_id(panel: i32, role: RoleId) -> String {
+    format!("{PANEL_PREFIX}{panel}:{role}")
+}
+
+#[must_use]
+pub fn menu_id(panel: i32) -> String {
+    format!("{PANEL_PREFIX}{panel}")
+}
+
+/// The panel and, for a button, the role a component id refers to.
+#[must_use]
+pub fn parse_custom_id(custom_id: &str) -> Option<(i32, Option<RoleId>)> {
+    let rest = custom_id.strip_prefix(PANEL_PREFIX)?;
+
+    match rest.split_once(':') {
+        Some((panel, role)) => {
+            let role = role.parse::<u64>().ok().filter(|id| *id != 0)?;
+            Some((panel.parse().ok()?, Some(RoleId::new(role))))
+        },
+        None => Some((rest.parse().ok()?, None)),
+    }
+}
+
+fn emoji(entry: &PanelEntry) -> Option<ReactionType> {
+    entry.emoji.as_deref().and_then(|emoji| ReactionType::try_from(emoji).ok())
+}
+
+#[must_use]
+pub fn panel_embed(
+    panel: &RolePanelRow,
+    entries: &[PanelEntry],
+) -> CreateEmbed<'static> {
+    let mut footer = panel.mode.hint().to_string();
+    if let Some(minutes) = panel.role_duration_minutes {
+        let _ = write!(footer, " Roles expire after {minutes} minutes.");
+    }
+
+    let mut embed = CreateEmbed::new()
+        .title(panel.title.clone())
+        .footer(CreateEmbedFooter::new(footer));
+
+    let description = match (&panel.description, entries.is_empty()) {
+        (Some(description), _) => Some(description.clone()),
+        (None, true) => Some(String::from("No roles have been added yet.")),
+        (None, false) => None,
+    };
+
+    if let Some(description) = description {
+        embed = embed.description(description);
+    }
+
+    embed
+}
+
+#[must_use]
+pub fn panel_components(
+    panel: &RolePanelRow,
+    entries: &[PanelEntry],
+) -> Vec<CreateComponent<'static>> {
+    if entries.is_empty() {
+        return Vec::new();
+    }
+
+    match panel.kind {
+        PanelKind::Buttons => entries
+            .chunks(BUTTONS_PER_ROW)
+            .map(|row| {
+                let buttons = row
+                    .iter()
+                    .map(|entry| {
+                        let mut button =
+                            CreateButton::new(button_id(panel.id, entry.role()))
+                                .label(entry.label.clone())
+                                .style(ButtonStyle::Secondary);
+
+                        if let Some(emoji) = emoji(entry) {
+                            button = button.emoji(emoji);
+                        }
+
+                        button
+                    })
+                    .collect::<Vec<_>>();
+
+                CreateComponent::ActionRow(CreateActionRow::buttons(buttons))
+            })
+            .collect(),
+        PanelKind::Select => {
+            let options = entries
+                .iter()
+                .map(|entry| {
+                    let mut option = CreateSelectMenuOption::new(
+                        entry.label.clone(),
+                        entry.role().to_string(),
+                    );
+
+                    if let Some(description) = &entry.description {
+                        option = option.description(description.clone());
+                    }
+                    if let Some(emoji) = emoji(entry) {
+                        option = option.emoji(emoji);
+                    }
+
+                    option
+                })
+                .collect::<Vec<_>>();
+
+            let max = if panel.mode == PanelMode::Unique {
+                1
+            } else {
+                u8::try_from(entries.len()).unwrap_or(25)
+            };
+
+            let menu = CreateSelectMenu::new(
+                menu_id(panel.id),
+                CreateSelectMenuKind::String { options: options.into() },
+            )
+            .placeholder("Choose your roles")
+            .min_values(0)
+            .max_values(max);
+
+            vec![CreateComponent::ActionRow(CreateActionRow::SelectMenu(menu))]
+        },
+    }
+}
@@ -0,0 +1,131 @@
+use serenity::all::{
+    CreateMessage,
+    DiscordJsonError,
+    EditMessage,
+    ErrorResponse,
+    Http,
+    HttpError,
+    JsonErrorCode,
+};
+use sqlx::PgPool;
+use tracing::warn;
+use zayden_core::is_not_found;
+
+use super::render::{panel_components, panel_embed};
+use super::{RolePanelManager, RolePanelRow};
+use crate::{Result, TemporaryRoleManager};
+
+const fn is_unknown_message(e: &serenity::Error) -> bool {
+    matches!(
+        e,
+        serenity::Error::Http(HttpError::UnsuccessfulRequest(ErrorResponse {
+            error: DiscordJsonError { code: JsonErrorCode::UnknownMessage, .. },
+            ..
+        }))
+    )
+}
+
+/// Posts the panel's message, or edits it in place if it still exists.
+pub async fn sync(http: &Http, pool: &PgPool, panel: &RolePanelRow) -> Result<()> {
+    let entries = RolePanelManager::entries(pool, panel.id).await?;
+    let embed = panel_embed(panel, &entries);
+    let components = panel_components(panel, &entries);
+
+    if let Some(message_id) = panel.message_id() {
+        match panel
+            .channel_id()
+            .edit_message(
+                http,
+                message_id,
+                EditMessage::new()
+                    .embed(embed.clone())
+                    .components(components.clone()),
+            )
+            .await
+        {
+            Ok(_) => {
+                RolePanelManager::mark_synced(pool, panel.id, message_id).await?;
+                return Ok(());
+            },
+            // Deleted by hand; post a fresh one below.
+            Err(e) if is_unknown_message(&e) => {},
+            Err(e) => return Err(e.into()),
+        }
+    }
+
+    let message = panel
+        .channel_id()
+        .send_message(http, CreateMessage::new().embed(embed).components(components))
+        .await?;
+
+    RolePanelManager::mark_synced(pool, panel.id, message.id).await?;
+
+    Ok(())
+}
+
+/// Deletes the panel and its message.
+pub async fn delete(http: &Http, panel: &RolePanelRow) -> Result<()> {
+    let Some(message_id) = panel.message_id() else {
+        return Ok(());
+    };
+
+    match panel
+        .channel_id()
+        .delete_message(http, message_id, Some("Role panel deleted"))
+        .await
+    {
+        Ok(()) => Ok(()),
+        Err(e) if is_unknown_message(&e) => Ok(()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Re-renders panels edited from the dashboard and takes back temporary roles
+/// that have run out. Failures are logged per item so one broken panel or
+/// departed member doesn't hold up the rest.
+pub async fn sweep(http: &Http, pool: &PgPool) -> Result<()> {
+    for panel in RolePanelManager::dirty(pool).await? {
+        if let Err(e) = sync(http, pool, &panel).await {
+            warn!(error = ?e, panel = panel.id, "failed to sync role panel");
+        }
+    }
+
+    for grant in TemporaryRoleManager::expired(pool).await? {
+        match http
+            .remove_member_role(
+                grant.guild_id(),
+                grant.user_id(),
+                grant.role_id(),
+                Some("Temporary role expired"),
+            )
+            .await
+        {
+            // A 404 means the member left or the role is gone: nothing to take.
+            Ok(()) => {},
+            Err(e) if is_not_found(&e) => {},
+            Err(e) => {
+                let given_up = TemporaryRoleManager::fail(pool, &grant).await?;
+
+                warn!(
+                    error = ?e,
+                    guild = grant.guild_id,
+                    user = grant.user_id,
+                    role = grant.role_id,
+                    given_up,
+                    "failed to remove expired temporary role"
+                );
+                continue;
+            },
+        }
+
+        TemporaryRoleManager::revoke(
+            pool,
+            grant.guild_id(),
+            grant.user_id(),
+            grant.role_id(),
+        )
+        .await?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,123 @@
+use serenity::all::{GuildId, RoleId, UserId};
+use sqlx::PgPool;
+use zayden_core::{as_i64, as_u64};
+
+/// A removal that keeps failing is given up on after this many tries.
+pub const MAX_FAILURES: i32 = 3;
+
+/// How long a failed removal waits before it's retried.
+const RETRY_MINUTES: i32 = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TemporaryRole {
+    pub guild_id: i64,
+    pub user_id: i64,
+    pub role_id: i64,
+}
+
+impl TemporaryRole {
+    #[must_use]
+    pub const fn guild_id(&self) -> GuildId {
+        GuildId::new(as_u64(self.guild_id))
+    }
+
+    #[must_use]
+    pub const fn user_id(&self) -> UserId {
+        UserId::new(as_u64(self.user_id))
+    }
+
+    #[must_use]
+    pub const fn role_id(&self) -> RoleId {
+        RoleId::new(as_u64(self.role_id))
+    }
+}
+
+pub struct TemporaryRoleManager;
+
+impl TemporaryRoleManager {
+    /// Schedules `role` to be taken from `user` after `minutes`, restarting
+    /// the clock if it was already temporary.
+    pub async fn grant(
+        pool: &PgPool,
+        guild_id: GuildId,
+        user_id: UserId,
+        role_id: RoleId,
+        minutes: i32,
+    ) -> sqlx::Result<()> {
+        sqlx::query!(
+            "INSERT INTO temporary_roles (guild_id, user_id, role_id, expires_at)
+            VALUES ($1, $2, $3, now() + make_interval(mins => $4))
+            ON CONFLICT (guild_id, user_id, role_id) DO UPDATE SET
+                expires_at = EXCLUDED.expires_at,
+                failures = 0,
+                retry_at = NULL",
+            as_i64(guild_id.get()),
+            as_i64(user_id.get()),
+            as_i64(role_id.get()),
+            minutes
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn revoke(
+        pool: &PgPool,
+        guild_id: GuildId,
+        user_id: UserId,
+        role_id: RoleId,
+    ) -> sqlx::Result<()> {
+        sqlx::query!(
+            "DELETE FROM temporary_roles
+            WHERE guild_id = $1 AND user_id = $2 AND role_id = $3",
+            as_i64(guild_id.get()),
+            as_i64(user_id.get()),
+            as_i64(role_id.get())
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Roles that have run out, leaving out failed removals until their
+    /// retry is due.
+    pub async fn expired(pool: &PgPool) -> sqlx::Result<Vec<TemporaryRole>> {
+        sqlx::query_as!(
+            TemporaryRole,
+            "SELECT guild_id, user_id, role_id FROM temporary_roles
+            WHERE expires_at <= now() AND (retry_at IS NULL OR retry_at <= now())
+            ORDER BY expires_at
+            LIMIT 100"
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Records a failed removal and schedules a retry. Returns `true` once it
+    /// has failed [`MAX_FAILURES`] times, when the row is dropped.
+    pub async fn fail(pool: &PgPool, role: &TemporaryRole) -> sqlx::Result<bool> {
+        let failures = sqlx::query_scalar!(
+            "UPDATE temporary_roles SET
+                failures = failures + 1,
+                retry_at = now() + make_interval(mins => $4)
+            WHERE guild_id = $1 AND user_id = $2 AND role_id = $3
+            RETURNING failures",
+            role.guild_id,
+            role.user_id,
+            role.role_id,
+            RETRY_MINUTES,
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        let given_up = failures.is_some_and(|failures| failures >= MAX_FAILURES);
+        if given_up {
+            Self::revoke(pool, role.guild_id(), role.user_id(), role.role_id())
+                .await?;
+        }
+
+        Ok(given_up)
+    }
+}
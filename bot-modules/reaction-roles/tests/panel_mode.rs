@@ -0,0 +1,138 @@
+use reaction_roles::panel::mode::{
+    PanelMode,
+    PanelRequirement,
+    RoleChanges,
+    Selection,
+    check_requirements,
+};
+use reaction_roles::panel::render::{button_id, menu_id, parse_custom_id};
+use reaction_roles::{PanelKind, ReactionRoleError};
+use serenity::all::RoleId;
+
+const RED: RoleId = RoleId::new(1);
+const GREEN: RoleId = RoleId::new(2);
+const BLUE: RoleId = RoleId::new(3);
+const OUTSIDE: RoleId = RoleId::new(99);
+
+const PANEL: &[RoleId] = &[RED, GREEN, BLUE];
+
+fn changes(add: &[RoleId], remove: &[RoleId]) -> RoleChanges {
+    RoleChanges { add: add.to_vec(), remove: remove.to_vec() }
+}
+
+#[test]
+fn toggle_button_flips_the_clicked_role() {
+    let mode = PanelMode::Toggle;
+
+    assert_eq!(mode.plan(PANEL, &[], Selection::Button(RED)), changes(&[RED], &[]));
+    assert_eq!(
+        mode.plan(PANEL, &[RED, GREEN], Selection::Button(RED)),
+        changes(&[], &[RED])
+    );
+}
+
+/// A select menu submits the member's whole choice, so anything from the
+/// panel they left unselected is taken away.
+#[test]
+fn toggle_menu_replaces_the_panel_roles() {
+    let plan = PanelMode::Toggle.plan(
+        PANEL,
+        &[RED, OUTSIDE],
+        Selection::Menu(&[GREEN, BLUE]),
+    );
+
+    assert_eq!(plan, changes(&[GREEN, BLUE], &[RED]));
+}
+
+#[test]
+fn add_only_and_remove_only_go_one_way() {
+    let held = [RED];
+
+    assert_eq!(
+        PanelMode::AddOnly.plan(PANEL, &held, Selection::Button(RED)),
+        changes(&[], &[])
+    );
+    assert_eq!(
+        PanelMode::AddOnly.plan(PANEL, &held, Selection::Button(GREEN)),
+        changes(&[GREEN], &[])
+    );
+    assert_eq!(
+        PanelMode::RemoveOnly.plan(PANEL, &held, Selection::Button(GREEN)),
+        changes(&[], &[])
+    );
+    assert_eq!(
+        PanelMode::RemoveOnly.plan(PANEL, &held, Selection::Button(RED)),
+        changes(&[], &[RED])
+    );
+}
+
+#[test]
+fn unique_swaps_the_held_panel_role() {
+    let mode = PanelMode::Unique;
+
+    assert_eq!(
+        mode.plan(PANEL, &[RED, OUTSIDE], Selection::Button(GREEN)),
+        changes(&[GREEN], &[RED])
+    );
+    assert_eq!(
+        mode.plan(PANEL, &[RED], Selection::Button(RED)),
+        changes(&[], &[RED])
+    );
+    assert_eq!(mode.plan(PANEL, &[RED], Selection::Menu(&[])), changes(&[], &[RED]));
+}
+
+#[test]
+fn verify_once_never_removes() {
+    assert_eq!(
+        PanelMode::VerifyOnce.plan(PANEL, &[RED], Selection::Menu(&[GREEN])),
+        changes(&[GREEN], &[])
+    );
+}
+
+/// A forged component id must not hand out roles the panel doesn't offer.
+#[test]
+fn roles_outside_the_panel_are_ignored() {
+    for mode in PanelMode::ALL {
+        let plan = mode.plan(PANEL, &[], Selection::Button(OUTSIDE));
+        assert!(plan.is_empty(), "{mode} acted on a role outside the panel");
+    }
+}
+
+#[test]
+fn requirements_gate_the_panel() {
+    let requirements =
+        [PanelRequirement { role_id: 10, forbidden: false }, PanelRequirement {
+            role_id: 20,
+            forbidden: true,
+        }];
+    let member = RoleId::new(10);
+    let muted = RoleId::new(20);
+
+    assert!(check_requirements(&[member], &requirements).is_ok());
+    assert!(matches!(
+        check_requirements(&[], &requirements),
+        Err(ReactionRoleError::MissingRequiredRole(role)) if role == member
+    ));
+    assert!(matches!(
+        check_requirements(&[member, muted], &requirements),
+        Err(ReactionRoleError::ForbiddenRole(role)) if role == muted
+    ));
+}
+
+#[test]
+fn custom_ids_round_trip() {
+    assert_eq!(parse_custom_id(&button_id(7, GREEN)), Some((7, Some(GREEN))));
+    assert_eq!(parse_custom_id(&menu_id(7)), Some((7, None)));
+    assert_eq!(parse_custom_id("role_panel:x"), None);
+    assert_eq!(parse_custom_id("suggestions_status:1"), None);
+}
+
+#[test]
+fn kinds_and_modes_parse_from_their_ids() {
+    for kind in PanelKind::ALL {
+        assert_eq!(kind.id().parse::<PanelKind>(), Ok(kind));
+    }
+    for mode in PanelMode::ALL {
+        assert_eq!(mode.id().parse::<PanelMode>(), Ok(mode));
+    }
+}
@@ -9,24 +9,24 @@ use serenity::all::{
     InputTextStyle,
 };
 
-use crate::{Result, Suggestions};
+use crate::{Result, STATUS_PREFIX, SuggestionStatus, Suggestions};
 
 impl Suggestions {
     pub async fn components(
         http: &Http,
         interaction: &ComponentInteraction,
-        accepted: bool,
+        status: SuggestionStatus,
     ) -> Result<()> {
         let response = CreateInputText::new(InputTextStyle::Paragraph, "response")
             .placeholder("Response to the suggestion");
 
-        let id = if accepted { "suggestions_accept" } else { "suggestions_reject" };
-
-        let modal = CreateModal::new(id, "Suggestion Response").components(vec![
-            CreateModalComponent::Label(CreateLabel::input_text(
-                "Response", response,
-            )),
-        ]);
+        let modal = CreateModal::new(
+            format!("{STATUS_PREFIX}{}", status.id()),
+            format!("Mark as {}", status.label()),
+        )
+        .components(vec![CreateModalComponent::Label(
+            CreateLabel::input_text("Response", response),
+        )]);
 
         interaction
             .create_response(http, CreateInteractionResponse::Modal(modal))
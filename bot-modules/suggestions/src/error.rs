@@ -9,6 +9,8 @@ pub type Result<T> = std::result::Result<T, SuggestionsError>;
 pub enum SuggestionsError {
     MissingSuggesionChannel,
     InvalidModalStructure,
+    NotConfigured,
+    PossibleDuplicates(String),
     Internal(String),
     Zayden(CoreError),
 }
@@ -22,6 +24,13 @@ impl std::fmt::Display for SuggestionsError {
             Self::InvalidModalStructure => {
                 write!(f, "invalid suggestions modal structure")
             },
+            Self::NotConfigured => {
+                write!(f, "This server has no suggestions forum set up.")
+            },
+            Self::PossibleDuplicates(links) => write!(
+                f,
+                "Similar suggestions already exist:\n{links}\nVote on one of those, or submit again with `force` if yours is different."
+            ),
             Self::Internal(msg) => write!(f, "internal error: {msg}"),
             Self::Zayden(e) => e.fmt(f),
         }
@@ -34,6 +43,8 @@ impl std::error::Error for SuggestionsError {
             Self::Zayden(e) => Some(e),
             Self::MissingSuggesionChannel
             | Self::InvalidModalStructure
+            | Self::NotConfigured
+            | Self::PossibleDuplicates(_)
             | Self::Internal(_) => None,
         }
     }
@@ -42,7 +53,9 @@ impl std::error::Error for SuggestionsError {
 impl Respond for SuggestionsError {
     fn user_message(&self) -> Option<Cow<'_, str>> {
         match self {
-            Self::MissingSuggesionChannel => Some(Cow::Owned(self.to_string())),
+            Self::MissingSuggesionChannel
+            | Self::NotConfigured
+            | Self::PossibleDuplicates(_) => Some(Cow::Owned(self.to_string())),
             Self::InvalidModalStructure | Self::Internal(_) => None,
             Self::Zayden(e) => e.user_message(),
         }
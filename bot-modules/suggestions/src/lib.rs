@@ -4,11 +4,20 @@ pub mod manager;
 mod modal;
 mod reaction;
 pub mod slash_command;
+mod status;
+mod submit;
+pub mod suggestion;
 
 use error::Result;
 pub use error::SuggestionsError;
 pub use manager::SuggestionsGuildRow;
 pub use reaction::{ReviewAction, ReviewThresholds, review_action};
 pub use slash_command::FetchSuggestions;
+pub use status::SuggestionStatus;
+pub use submit::Suggest;
+pub use suggestion::SuggestionManager;
+
+/// Review buttons and their response modals are `{STATUS_PREFIX}{status id}`.
+pub const STATUS_PREFIX: &str = "suggestions_status:";
 
 pub struct Suggestions;
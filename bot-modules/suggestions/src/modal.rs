@@ -10,14 +10,18 @@ use serenity::all::{
     ModalInteraction,
     ThreadId,
 };
+use sqlx::PgPool;
+use tracing::warn;
 
-use crate::{Result, Suggestions, SuggestionsError};
+use crate::suggestion::SuggestionManager;
+use crate::{Result, SuggestionStatus, Suggestions, SuggestionsError};
 
 impl Suggestions {
     pub async fn modal(
         http: &Http,
         modal: &ModalInteraction,
-        accepted: bool,
+        pool: &PgPool,
+        status: SuggestionStatus,
     ) -> Result<()> {
         let response = match modal.data.components.first() {
             Some(ModalComponent::Label(label)) => match &label.component {
@@ -56,15 +60,7 @@ impl Suggestions {
             .parse::<ThreadId>()
             .map_err(|_e| SuggestionsError::InvalidModalStructure)?;
 
-        let prefix = if accepted { "[Accepted] - " } else { "[Rejected] - " };
-
-        let name = if old_title.starts_with("[Accepted] - ")
-            || old_title.starts_with("[Rejected] - ")
-        {
-            format!("{prefix}{}", old_title.get(11..).unwrap_or(old_title))
-        } else {
-            format!("{prefix}{old_title}").chars().take(100).collect::<String>()
-        };
+        let name = status.thread_name(old_title);
 
         channel_id.edit(http, EditThread::new().name(&name).archived(false)).await?;
 
@@ -92,6 +88,8 @@ impl Suggestions {
                             .title(name)
                             .url(old_url)
                             .description(old_description)
+                            .colour(status.colour())
+                            .field("Status", status.label(), false)
                             .field("Team Response", response, false)
                             .author(old_author.into())
                             .footer(old_footer.into()),
@@ -100,20 +98,53 @@ impl Suggestions {
             )
             .await?;
 
-        let title =
-            if accepted { "Suggestion Accepted" } else { "Suggestion Rejected" };
+        let row = SuggestionManager::set_status(
+            pool,
+            channel_id,
+            status,
+            response,
+            modal.user.id,
+        )
+        .await?;
+
+        let title = format!("Suggestion {}", status.label());
 
         channel_id
             .widen()
             .send_message(
                 http,
-                CreateMessage::new()
-                    .embed(CreateEmbed::new().title(title).description(response)),
+                CreateMessage::new().embed(
+                    CreateEmbed::new()
+                        .title(title.as_str())
+                        .description(response)
+                        .colour(status.colour()),
+                ),
             )
             .await?
             .pin(http, Some("Mod response pinned"))
             .await?;
 
+        if let Some(row) = row {
+            let dm = CreateEmbed::new()
+                .title(title)
+                .url(old_url)
+                .description(format!(
+                    "Your suggestion **{}** is now {}.",
+                    row.title,
+                    status.label()
+                ))
+                .colour(status.colour())
+                .field("Team Response", response, false);
+
+            if let Err(e) = row
+                .author()
+                .direct_message(http, CreateMessage::new().embed(dm))
+                .await
+            {
+                warn!(error = %e, author = %row.author(), "failed to DM suggestion status");
+            }
+        }
+
         Ok(())
     }
 }
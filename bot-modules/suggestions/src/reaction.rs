@@ -15,11 +15,20 @@ use serenity::all::{
     Message,
     Reaction,
     ReactionType,
+    ThreadId,
 };
 use sqlx::PgPool;
 use tracing::debug;
 
-use crate::{Result, Suggestions, SuggestionsError, SuggestionsGuildRow};
+use crate::suggestion::{NewSuggestion, SuggestionManager};
+use crate::{
+    Result,
+    STATUS_PREFIX,
+    SuggestionStatus,
+    Suggestions,
+    SuggestionsError,
+    SuggestionsGuildRow,
+};
 
 impl Suggestions {
     pub async fn reaction(
@@ -78,6 +87,24 @@ impl Suggestions {
                 }
             });
 
+        // Votes only count on the post itself, whose id is the thread's.
+        if message.id.get() == channel.id.get() {
+            let thread_id = ThreadId::new(channel.id.get());
+
+            SuggestionManager::record(pool, NewSuggestion {
+                guild_id,
+                channel_id: channel.parent_id.unwrap_or(channel.id),
+                thread_id,
+                author: message.author.id,
+                anonymous: false,
+                title: &channel.base.name,
+                content: &message.content,
+            })
+            .await?;
+            SuggestionManager::set_votes(pool, thread_id, pos_count, neg_count)
+                .await?;
+        }
+
         let thresholds = row.thresholds();
 
         let mut messages = review_channel_id.widen().messages_iter(http).boxed();
@@ -229,24 +256,31 @@ fn create_embed<'a>(
         .author(CreateEmbedAuthor::new(&message.author.name))
         .footer(CreateEmbedFooter::new(format!("👍 {pos_count} · 👎 {neg_count}")));
 
-    if let Some(team_response) = embed_fields.first() {
-        embed = embed.field(
-            team_response.name.clone(),
-            team_response.value.clone(),
-            team_response.inline,
-        );
+    for field in embed_fields {
+        embed = embed.field(field.name.clone(), field.value.clone(), field.inline);
     }
 
     embed
 }
 
-fn create_components<'a>() -> CreateComponent<'a> {
-    CreateComponent::ActionRow(CreateActionRow::buttons(vec![
-        CreateButton::new("suggestions_accept")
-            .label("Accept")
-            .style(ButtonStyle::Success),
-        CreateButton::new("suggestions_reject")
-            .label("Reject")
-            .style(ButtonStyle::Danger),
-    ]))
+pub(crate) fn create_components<'a>() -> CreateComponent<'a> {
+    CreateComponent::ActionRow(CreateActionRow::buttons(
+        SuggestionStatus::REVIEW
+            .into_iter()
+            .map(|status| {
+                let style = match status {
+                    SuggestionStatus::Accepted => ButtonStyle::Success,
+                    SuggestionStatus::Denied => ButtonStyle::Danger,
+                    SuggestionStatus::Implemented => ButtonStyle::Primary,
+                    SuggestionStatus::Open | SuggestionStatus::UnderReview => {
+                        ButtonStyle::Secondary
+                    },
+                };
+
+                CreateButton::new(format!("{STATUS_PREFIX}{}", status.id()))
+                    .label(status.label())
+                    .style(style)
+            })
+            .collect::<Vec<_>>(),
+    ))
 }
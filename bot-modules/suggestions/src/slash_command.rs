@@ -1,6 +1,3 @@
-use std::time;
-
-use futures::{StreamExt, stream};
 use serenity::all::{
     CommandInteraction,
     CommandOptionType,
@@ -10,16 +7,14 @@ use serenity::all::{
     CreateMessage,
     EditInteractionResponse,
     Http,
-    Mentionable,
     Permissions,
-    ReactionTypes,
     ResolvedOption,
     ResolvedValue,
 };
 use sqlx::PgPool;
 use zayden_core::{CoreError as ZaydenError, parse_options};
 
-use crate::{Result, SuggestionsError, SuggestionsGuildRow};
+use crate::{Result, SuggestionManager, SuggestionsError, SuggestionsGuildRow};
 
 pub struct FetchSuggestions;
 
@@ -30,8 +25,6 @@ impl FetchSuggestions {
         options: Vec<ResolvedOption<'_>>,
         pool: &PgPool,
     ) -> Result<()> {
-        let start_time = time::Instant::now();
-
         let guild_id = interaction.guild_id.ok_or(ZaydenError::MissingGuildId)?;
 
         let mut options = parse_options(options);
@@ -45,56 +38,29 @@ impl FetchSuggestions {
                 .ok_or(SuggestionsError::MissingSuggesionChannel)?,
         };
 
-        let active_guild_threads = guild_id.get_active_threads(http).await?;
-        let threads_iter = active_guild_threads
-            .threads
-            .into_iter()
-            .filter(|thread| thread.parent_id == channel_id)
-            .chain(
-                channel_id
-                    .get_archived_public_threads(http, None, None)
-                    .await?
-                    .threads,
-            );
-
-        let mut reaction_counts = stream::iter(threads_iter)
-            .then(|thread| async {
-                let count = thread
-                    .id
-                    .widen()
-                    .reaction_users(
-                        http,
-                        thread.id.get().into(),
-                        '👍',
-                        Some(ReactionTypes::Normal),
-                        None,
-                        None,
-                    )
-                    .await
-                    .map_or(0, |r| r.len());
-
-                (thread, count)
-            })
-            .collect::<Vec<_>>()
-            .await;
-
-        reaction_counts.sort_by_key(|b| std::cmp::Reverse(b.1));
-
-        let elapsed_time = start_time.elapsed();
-
-        let fields_iter = reaction_counts.into_iter().take(10).enumerate().map(
-            |(i, (thread, count))| {
-                (
-                    format!("{}. 👍: {}", i + 1, count),
-                    format!("Link: {}", thread.mention()),
-                    false,
-                )
-            },
-        );
+        let suggestions = SuggestionManager::top(pool, channel_id, 10).await?;
+
+        let fields_iter = suggestions.iter().enumerate().map(|(i, suggestion)| {
+            (
+                format!(
+                    "{}. 👍: {} · 👎: {}",
+                    i + 1,
+                    suggestion.upvotes,
+                    suggestion.downvotes
+                ),
+                format!(
+                    "{} ({})\nLink: <#{}>",
+                    suggestion.title,
+                    suggestion.status.label(),
+                    suggestion.thread_id
+                ),
+                false,
+            )
+        });
 
         let embed = CreateEmbed::new()
             .title("Top 10 suggestions")
-            .description("Here are the top 10 suggestions, sorted by votes.")
+            .description("Here are the top 10 suggestions that have not been denied, sorted by votes.")
             .fields(fields_iter);
 
         interaction
@@ -106,10 +72,7 @@ impl FetchSuggestions {
         interaction
             .edit_response(
                 http,
-                EditInteractionResponse::new().content(format!(
-                    "Suggestions fetched. Took {} seconds",
-                    elapsed_time.as_secs()
-                )),
+                EditInteractionResponse::new().content("Suggestions fetched."),
             )
             .await?;
 
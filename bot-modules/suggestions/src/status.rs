@@ -0,0 +1,109 @@
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, sqlx::Type)]
+#[sqlx(type_name = "suggestion_status", rename_all = "snake_case")]
+pub enum SuggestionStatus {
+    Open,
+    UnderReview,
+    Accepted,
+    Implemented,
+    Denied,
+}
+
+impl SuggestionStatus {
+    pub const ALL: [Self; 5] = [
+        Self::Open,
+        Self::UnderReview,
+        Self::Accepted,
+        Self::Implemented,
+        Self::Denied,
+    ];
+    /// Statuses staff can move a suggestion to from the review channel.
+    pub const REVIEW: [Self; 4] =
+        [Self::UnderReview, Self::Accepted, Self::Implemented, Self::Denied];
+
+    #[must_use]
+    pub const fn id(self) -> &'static str {
+        match self {
+            Self::Open => "open",
+            Self::UnderReview => "under_review",
+            Self::Accepted => "accepted",
+            Self::Implemented => "implemented",
+            Self::Denied => "denied",
+        }
+    }
+
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Open => "Open",
+            Self::UnderReview => "Under Review",
+            Self::Accepted => "Accepted",
+            Self::Implemented => "Implemented",
+            Self::Denied => "Denied",
+        }
+    }
+
+    /// Prepended to the forum post's name; open suggestions carry none.
+    #[must_use]
+    pub const fn prefix(self) -> &'static str {
+        match self {
+            Self::Open => "",
+            Self::UnderReview => "[Under Review] - ",
+            Self::Accepted => "[Accepted] - ",
+            Self::Implemented => "[Implemented] - ",
+            Self::Denied => "[Denied] - ",
+        }
+    }
+
+    #[must_use]
+    pub const fn colour(self) -> u32 {
+        match self {
+            Self::Open => 0x0099_aab5,
+            Self::UnderReview => 0x00f1_c40f,
+            Self::Accepted => 0x002e_cc71,
+            Self::Implemented => 0x0034_98db,
+            Self::Denied => 0x00e7_4c3c,
+        }
+    }
+
+    /// Removes any status prefix this or an earlier version put on `name`.
+    #[must_use]
+    pub fn strip_prefix(name: &str) -> &str {
+        const LEGACY: [&str; 1] = ["[Rejected] - "];
+
+        Self::ALL
+            .iter()
+            .map(|status| status.prefix())
+            .filter(|prefix| !prefix.is_empty())
+            .chain(LEGACY)
+            .find_map(|prefix| name.strip_prefix(prefix))
+            .unwrap_or(name)
+    }
+
+    /// The forum post name for a suggestion titled `title`, within Discord's
+    /// 100 character limit.
+    #[must_use]
+    pub fn thread_name(self, title: &str) -> String {
+        format!("{}{}", self.prefix(), Self::strip_prefix(title))
+            .chars()
+            .take(100)
+            .collect()
+    }
+}
+
+impl Display for SuggestionStatus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+impl FromStr for SuggestionStatus {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL.into_iter().find(|status| status.id() == s).ok_or(())
+    }
+}
@@ -0,0 +1,234 @@
+use serenity::all::{
+    AutoArchiveDuration,
+    CommandInteraction,
+    CommandOptionType,
+    CreateCommand,
+    CreateCommandOption,
+    CreateEmbed,
+    CreateEmbedAuthor,
+    CreateEmbedFooter,
+    CreateForumPost,
+    CreateMessage,
+    EditInteractionResponse,
+    Http,
+    Message,
+    ReactionType,
+    ResolvedOption,
+    ThreadId,
+};
+use sqlx::PgPool;
+use tracing::debug;
+use zayden_core::{CoreError, optional_option, parse_options, required_option};
+
+use crate::suggestion::{
+    NewSuggestion,
+    SuggestionManager,
+    SuggestionTitle,
+    find_duplicates,
+};
+use crate::{Result, Suggestions, SuggestionsError, SuggestionsGuildRow};
+
+fn duplicate_links(duplicates: &[&SuggestionTitle]) -> String {
+    duplicates
+        .iter()
+        .map(|dupe| format!("- <#{}>", dupe.thread_id))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+pub struct Suggest;
+
+impl Suggest {
+    pub async fn run(
+        http: &Http,
+        interaction: &CommandInteraction,
+        pool: &PgPool,
+        options: Vec<ResolvedOption<'_>>,
+    ) -> Result<()> {
+        interaction.defer_ephemeral(http).await?;
+
+        let guild_id = interaction.guild_id.ok_or(CoreError::MissingGuildId)?;
+
+        let mut options = parse_options(options);
+        let title: &str = required_option(&mut options, "title")?;
+        let content: &str = required_option(&mut options, "content")?;
+        let anonymous = optional_option(&mut options, "anonymous").unwrap_or(false);
+        let force = optional_option(&mut options, "force").unwrap_or(false);
+
+        let channel_id = SuggestionsGuildRow::get(pool, guild_id)
+            .await?
+            .and_then(|row| row.channel_id())
+            .ok_or(SuggestionsError::NotConfigured)?;
+
+        if !force {
+            let existing = SuggestionManager::titles(pool, guild_id).await?;
+            let duplicates = find_duplicates(title, &existing);
+
+            if !duplicates.is_empty() {
+                return Err(SuggestionsError::PossibleDuplicates(duplicate_links(
+                    &duplicates,
+                )));
+            }
+        }
+
+        let author = if anonymous {
+            CreateEmbedAuthor::new("Anonymous")
+        } else {
+            CreateEmbedAuthor::new(interaction.user.display_name())
+                .icon_url(interaction.user.face())
+        };
+
+        let embed = CreateEmbed::new()
+            .title(title)
+            .description(content)
+            .author(author)
+            .footer(CreateEmbedFooter::new("Vote with 👍 or 👎 below"));
+
+        let thread = channel_id
+            .create_forum_post(
+                http,
+                CreateForumPost::new(title, CreateMessage::new().embed(embed))
+                    .auto_archive_duration(AutoArchiveDuration::OneWeek),
+            )
+            .await?;
+
+        let thread_id = ThreadId::new(thread.id.get());
+
+        // Before the votes go on: the bot's own reactions and starter message
+        // would otherwise record the post as the bot's.
+        SuggestionManager::record(pool, NewSuggestion {
+            guild_id,
+            channel_id,
+            thread_id,
+            author: interaction.user.id,
+            anonymous,
+            title,
+            content,
+        })
+        .await?;
+
+        // The starter message shares the thread's id.
+        for vote in ['👍', '👎'] {
+            thread_id
+                .widen()
+                .create_reaction(
+                    http,
+                    thread_id.get().into(),
+                    ReactionType::from(vote),
+                )
+                .await?;
+        }
+
+        interaction
+            .edit_response(
+                http,
+                EditInteractionResponse::new()
+                    .content(format!("Suggestion posted: <#{thread_id}>")),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    pub fn register<'a>() -> CreateCommand<'a> {
+        CreateCommand::new("suggest")
+            .description("Submit a suggestion")
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "title",
+                    "A short summary of your suggestion",
+                )
+                .required(true)
+                .max_length(100),
+            )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "content",
+                    "Your suggestion in detail",
+                )
+                .required(true)
+                .max_length(4000),
+            )
+            .add_option(CreateCommandOption::new(
+                CommandOptionType::Boolean,
+                "anonymous",
+                "Hide your name from other members (staff can still see it)",
+            ))
+            .add_option(CreateCommandOption::new(
+                CommandOptionType::Boolean,
+                "force",
+                "Post even if similar suggestions exist",
+            ))
+    }
+}
+
+impl Suggestions {
+    /// Records posts made directly in the suggestions forum and points out
+    /// any similar suggestions in the new thread.
+    pub async fn message_create(
+        http: &Http,
+        message: &Message,
+        pool: &PgPool,
+    ) -> Result<()> {
+        // Only a forum post's starter message shares its channel's id. Posts
+        // by the bot come from /suggest, which records them itself.
+        if message.id.get() != message.channel_id.get() || message.author.bot() {
+            return Ok(());
+        }
+
+        let Some(guild_id) = message.guild_id else {
+            return Ok(());
+        };
+
+        let Some(channel_id) = SuggestionsGuildRow::get(pool, guild_id)
+            .await?
+            .and_then(|row| row.channel_id())
+        else {
+            return Ok(());
+        };
+
+        let Some(thread) = message.channel(http).await?.guild() else {
+            return Ok(());
+        };
+
+        if thread.parent_id != Some(channel_id) {
+            debug!(channel_id = %message.channel_id, "post is not in the suggestions forum; ignoring");
+            return Ok(());
+        }
+
+        let thread_id = ThreadId::new(thread.id.get());
+        let title: &str = &thread.base.name;
+
+        let existing = SuggestionManager::titles(pool, guild_id).await?;
+        let duplicates = find_duplicates(title, &existing);
+
+        SuggestionManager::record(pool, NewSuggestion {
+            guild_id,
+            channel_id,
+            thread_id,
+            author: message.author.id,
+            anonymous: false,
+            title,
+            content: &message.content,
+        })
+        .await?;
+
+        if !duplicates.is_empty() {
+            thread_id
+                .widen()
+                .send_message(
+                    http,
+                    CreateMessage::new().embed(
+                        CreateEmbed::new()
+                            .title("Possible duplicates")
+                            .description(duplicate_links(&duplicates)),
+                    ),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,278 @@
+use std::collections::HashSet;
+
+use serenity::all::{ChannelId, GuildId, ThreadId, UserId};
+use sqlx::PgPool;
+use zayden_core::{as_i64, as_u64};
+
+use crate::SuggestionStatus;
+
+/// Titles at least this similar to a new suggestion are reported as possible
+/// duplicates.
+pub const DUPLICATE_THRESHOLD: f64 = 0.6;
+pub const MAX_DUPLICATES: usize = 5;
+
+#[derive(Debug, Clone)]
+pub struct SuggestionRow {
+    pub thread_id: i64,
+    pub guild_id: i64,
+    pub author_id: i64,
+    pub anonymous: bool,
+    pub title: String,
+    pub content: String,
+    pub upvotes: i32,
+    pub downvotes: i32,
+    pub status: SuggestionStatus,
+    pub response: Option<String>,
+}
+
+impl SuggestionRow {
+    #[must_use]
+    pub const fn thread_id(&self) -> ThreadId {
+        ThreadId::new(as_u64(self.thread_id))
+    }
+
+    #[must_use]
+    pub const fn author(&self) -> UserId {
+        UserId::new(as_u64(self.author_id))
+    }
+
+    #[must_use]
+    pub const fn score(&self) -> i32 {
+        self.upvotes - self.downvotes
+    }
+}
+
+/// A suggestion as first seen: a forum post, or a `/suggest` submission.
+#[derive(Debug, Clone, Copy)]
+pub struct NewSuggestion<'a> {
+    pub guild_id: GuildId,
+    pub channel_id: ChannelId,
+    pub thread_id: ThreadId,
+    pub author: UserId,
+    pub anonymous: bool,
+    pub title: &'a str,
+    pub content: &'a str,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SuggestionTitle {
+    pub thread_id: i64,
+    pub title: String,
+}
+
+fn words(title: &str) -> HashSet<String> {
+    SuggestionStatus::strip_prefix(title)
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Jaccard similarity of the two titles' word sets, ignoring case,
+/// punctuation and status prefixes.
+#[must_use]
+pub fn title_similarity(a: &str, b: &str) -> f64 {
+    let a = words(a);
+    let b = words(b);
+
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+
+    let shared = a.intersection(&b).count();
+    let total = a.union(&b).count();
+
+    #[expect(
+        clippy::cast_precision_loss,
+        reason = "word counts of a 100 character title are far below 2^52"
+    )]
+    let similarity = shared as f64 / total as f64;
+
+    similarity
+}
+
+/// Existing suggestions whose titles look like `title`, most similar first.
+#[must_use]
+pub fn find_duplicates<'a>(
+    title: &str,
+    existing: &'a [SuggestionTitle],
+) -> Vec<&'a SuggestionTitle> {
+    let mut matches = existing
+        .iter()
+        .map(|candidate| (title_similarity(title, &candidate.title), candidate))
+        .filter(|(similarity, _)| *similarity >= DUPLICATE_THRESHOLD)
+        .collect::<Vec<_>>();
+
+    matches.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+    matches
+        .into_iter()
+        .take(MAX_DUPLICATES)
+        .map(|(_, candidate)| candidate)
+        .collect()
+}
+
+pub struct SuggestionManager;
+
+impl SuggestionManager {
+    pub async fn get(
+        pool: &PgPool,
+        thread_id: ThreadId,
+    ) -> sqlx::Result<Option<SuggestionRow>> {
+        sqlx::query_as!(
+            SuggestionRow,
+            r#"SELECT
+                thread_id,
+                guild_id,
+                author_id,
+                anonymous,
+                title,
+                content,
+                upvotes,
+                downvotes,
+                status AS "status: SuggestionStatus",
+                response
+            FROM suggestions
+            WHERE thread_id = $1"#,
+            as_i64(thread_id.get())
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Records `suggestion` unless it is already known.
+    pub async fn record(
+        pool: &PgPool,
+        suggestion: NewSuggestion<'_>,
+    ) -> sqlx::Result<()> {
+        let guild_id = as_i64(suggestion.guild_id.get());
+
+        let mut tx = pool.begin().await?;
+
+        sqlx::query!(
+            "INSERT INTO guilds (id) VALUES ($1) ON CONFLICT (id) DO NOTHING",
+            guild_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query!(
+            "INSERT INTO suggestions (thread_id, guild_id, channel_id, author_id, anonymous, title, content)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            ON CONFLICT (thread_id) DO NOTHING",
+            as_i64(suggestion.thread_id.get()),
+            guild_id,
+            as_i64(suggestion.channel_id.get()),
+            as_i64(suggestion.author.get()),
+            suggestion.anonymous,
+            SuggestionStatus::strip_prefix(suggestion.title),
+            suggestion.content
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    pub async fn set_votes(
+        pool: &PgPool,
+        thread_id: ThreadId,
+        upvotes: i32,
+        downvotes: i32,
+    ) -> sqlx::Result<()> {
+        sqlx::query!(
+            "UPDATE suggestions SET upvotes = $2, downvotes = $3, updated_at = now()
+            WHERE thread_id = $1",
+            as_i64(thread_id.get()),
+            upvotes,
+            downvotes
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    #[expect(
+        trivial_casts,
+        reason = "not a cast: `as T` is sqlx's bind-param type-override syntax, required for the custom suggestion_status pgtype"
+    )]
+    pub async fn set_status(
+        pool: &PgPool,
+        thread_id: ThreadId,
+        status: SuggestionStatus,
+        response: &str,
+        staff: UserId,
+    ) -> sqlx::Result<Option<SuggestionRow>> {
+        sqlx::query_as!(
+            SuggestionRow,
+            r#"UPDATE suggestions
+            SET status = $2, response = $3, responded_by = $4, updated_at = now()
+            WHERE thread_id = $1
+            RETURNING
+                thread_id,
+                guild_id,
+                author_id,
+                anonymous,
+                title,
+                content,
+                upvotes,
+                downvotes,
+                status AS "status: SuggestionStatus",
+                response"#,
+            as_i64(thread_id.get()),
+            status as SuggestionStatus,
+            response,
+            as_i64(staff.get())
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Titles of the guild's suggestions that are still worth pointing at:
+    /// everything except denied ones.
+    pub async fn titles(
+        pool: &PgPool,
+        guild_id: GuildId,
+    ) -> sqlx::Result<Vec<SuggestionTitle>> {
+        sqlx::query_as!(
+            SuggestionTitle,
+            "SELECT thread_id, title FROM suggestions
+            WHERE guild_id = $1 AND status <> 'denied'",
+            as_i64(guild_id.get())
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn top(
+        pool: &PgPool,
+        channel_id: ChannelId,
+        limit: i64,
+    ) -> sqlx::Result<Vec<SuggestionRow>> {
+        sqlx::query_as!(
+            SuggestionRow,
+            r#"SELECT
+                thread_id,
+                guild_id,
+                author_id,
+                anonymous,
+                title,
+                content,
+                upvotes,
+                downvotes,
+                status AS "status: SuggestionStatus",
+                response
+            FROM suggestions
+            WHERE channel_id = $1 AND status <> 'denied'
+            ORDER BY upvotes - downvotes DESC, created_at
+            LIMIT $2"#,
+            as_i64(channel_id.get()),
+            limit
+        )
+        .fetch_all(pool)
+        .await
+    }
+}
@@ -0,0 +1,60 @@
+//! Duplicate detection for new suggestions.
+//!
+//! Titles are compared by their word sets, so word order, case, punctuation
+//! and status prefixes must not hide a duplicate, while titles that merely
+//! share a word or two must not be flagged.
+
+use suggestions::suggestion::{
+    MAX_DUPLICATES,
+    SuggestionTitle,
+    find_duplicates,
+    title_similarity,
+};
+
+fn title(thread_id: i64, title: &str) -> SuggestionTitle {
+    SuggestionTitle { thread_id, title: title.to_string() }
+}
+
+#[test]
+fn identical_titles_match_regardless_of_case_and_punctuation() {
+    let similarity = title_similarity("Add a music queue!", "add a MUSIC queue");
+    assert!((similarity - 1.0).abs() < f64::EPSILON);
+}
+
+#[test]
+fn status_prefixes_are_ignored() {
+    let similarity =
+        title_similarity("[Accepted] - Add a music queue", "Add a music queue");
+    assert!((similarity - 1.0).abs() < f64::EPSILON);
+}
+
+#[test]
+fn unrelated_titles_do_not_match() {
+    assert!(title_similarity("Add a music queue", "Remove the NSFW channel") < 0.2);
+    assert!(title_similarity("", "Add a music queue").abs() < f64::EPSILON);
+}
+
+#[test]
+fn duplicates_are_sorted_by_similarity() {
+    let existing = vec![
+        title(1, "Add music queue"),
+        title(2, "Weekly movie nights"),
+        title(3, "Add a music queue command"),
+    ];
+
+    let duplicates = find_duplicates("add a music queue", &existing);
+    let ids = duplicates.iter().map(|d| d.thread_id).collect::<Vec<_>>();
+
+    assert_eq!(ids, vec![3, 1]);
+}
+
+#[test]
+fn at_most_max_duplicates_are_reported() {
+    let existing =
+        (0..10).map(|i| title(i, "Add a music queue")).collect::<Vec<_>>();
+
+    assert_eq!(
+        find_duplicates("Add a music queue", &existing).len(),
+        MAX_DUPLICATES
+    );
+}
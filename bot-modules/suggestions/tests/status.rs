@@ -0,0 +1,58 @@
+//! Suggestion statuses and the forum post names they produce.
+//!
+//! The post name is the only place a status is visible to members browsing
+//! the forum, and duplicate detection compares names, so moving a suggestion
+//! between statuses must swap the prefix rather than stack a new one on top.
+
+use suggestions::SuggestionStatus;
+
+#[test]
+fn open_suggestions_carry_no_prefix() {
+    assert_eq!(
+        SuggestionStatus::Open.thread_name("Add a music queue"),
+        "Add a music queue"
+    );
+}
+
+#[test]
+fn status_changes_replace_the_previous_prefix() {
+    let name = SuggestionStatus::UnderReview.thread_name("Add a music queue");
+    assert_eq!(name, "[Under Review] - Add a music queue");
+
+    let name = SuggestionStatus::Accepted.thread_name(&name);
+    assert_eq!(name, "[Accepted] - Add a music queue");
+
+    let name = SuggestionStatus::Open.thread_name(&name);
+    assert_eq!(name, "Add a music queue");
+}
+
+#[test]
+fn legacy_rejected_prefix_is_stripped() {
+    // Posts reviewed before statuses existed were renamed "[Rejected] - ...".
+    assert_eq!(
+        SuggestionStatus::strip_prefix("[Rejected] - Add a music queue"),
+        "Add a music queue"
+    );
+    assert_eq!(
+        SuggestionStatus::Denied.thread_name("[Rejected] - Add a music queue"),
+        "[Denied] - Add a music queue"
+    );
+}
+
+#[test]
+fn thread_names_fit_discords_limit() {
+    let title = "x".repeat(100);
+    let name = SuggestionStatus::Implemented.thread_name(&title);
+
+    assert_eq!(name.chars().count(), 100);
+    assert!(name.starts_with("[Implemented] - "));
+}
+
+#[test]
+fn ids_round_trip() {
+    for status in SuggestionStatus::ALL {
+        assert_eq!(status.id().parse::<SuggestionStatus>(), Ok(status));
+    }
+
+    assert!("rejected".parse::<SuggestionStatus>().is_err());
+}
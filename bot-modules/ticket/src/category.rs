@@ -0,0 +1,331 @@
+use futures::TryStreamExt;
+use serenity::all::{GuildId, RoleId};
+use sqlx::PgPool;
+use zayden_core::{as_i64, as_u64};
+
+/// A string select holds at most 25 options.
+pub const MAX_CATEGORIES: usize = 25;
+/// A modal holds at most five inputs.
+pub const MAX_QUESTIONS: i16 = 5;
+pub const MAX_LABEL_LEN: usize = 45;
+
+pub const DEFAULT_NAME_PATTERN: &str = "{number} - {user} - {summary}";
+
+#[derive(Debug, Clone)]
+pub struct TicketCategoryRow {
+    pub id: i32,
+    pub guild_id: i64,
+    pub name: String,
+    pub description: Option<String>,
+    pub name_pattern: String,
+    pub auto_close_hours: Option<i32>,
+    pub sla_minutes: Option<i32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CategoryQuestion {
+    pub position: i16,
+    pub label: String,
+    pub placeholder: Option<String>,
+    pub paragraph: bool,
+    pub required: bool,
+}
+
+impl CategoryQuestion {
+    /// The modal input id this question is answered under.
+    #[must_use]
+    pub fn custom_id(&self) -> String {
+        format!("q{}", self.position)
+    }
+}
+
+/// Settings for a new category, or the changes to an existing one. In an edit,
+/// `None` leaves a field unchanged and a zero hour/minute count disables it.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CategoryFields<'a> {
+    pub description: Option<&'a str>,
+    pub name_pattern: Option<&'a str>,
+    pub auto_close_hours: Option<i32>,
+    pub sla_minutes: Option<i32>,
+}
+
+/// Fills `{number}`, `{user}`, `{category}` and `{summary}` in `pattern`,
+/// truncated to Discord's 100 character thread name limit.
+#[must_use]
+pub fn format_thread_name(
+    pattern: &str,
+    number: i32,
+    user: &str,
+    category: &str,
+    summary: &str,
+) -> String {
+    let summary = summary.lines().next().unwrap_or_default();
+
+    let name = pattern
+        .replace("{number}", &number.to_string())
+        .replace("{user}", user)
+        .replace("{category}", category)
+        .replace("{summary}", summary);
+
+    let name = name.trim();
+    let name = if name.is_empty() { category } else { name };
+
+    name.chars().take(100).collect()
+}
+
+pub struct TicketCategoryManager;
+
+impl TicketCategoryManager {
+    pub async fn list(
+        pool: &PgPool,
+        guild_id: GuildId,
+    ) -> sqlx::Result<Vec<TicketCategoryRow>> {
+        sqlx::query_as!(
+            TicketCategoryRow,
+            "SELECT id, guild_id, name, description, name_pattern, auto_close_hours, sla_minutes
+            FROM ticket_categories
+            WHERE guild_id = $1
+            ORDER BY name",
+            as_i64(guild_id.get())
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn get(
+        pool: &PgPool,
+        guild_id: GuildId,
+        id: i32,
+    ) -> sqlx::Result<Option<TicketCategoryRow>> {
+        sqlx::query_as!(
+            TicketCategoryRow,
+            "SELECT id, guild_id, name, description, name_pattern, auto_close_hours, sla_minutes
+            FROM ticket_categories
+            WHERE guild_id = $1 AND id = $2",
+            as_i64(guild_id.get()),
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn find(
+        pool: &PgPool,
+        guild_id: GuildId,
+        name: &str,
+    ) -> sqlx::Result<Option<TicketCategoryRow>> {
+        sqlx::query_as!(
+            TicketCategoryRow,
+            "SELECT id, guild_id, name, description, name_pattern, auto_close_hours, sla_minutes
+            FROM ticket_categories
+            WHERE guild_id = $1 AND lower(name) = lower($2)",
+            as_i64(guild_id.get()),
+            name
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Returns `None` when the guild already has a category with this name.
+    pub async fn create(
+        pool: &PgPool,
+        guild_id: GuildId,
+        name: &str,
+        fields: CategoryFields<'_>,
+    ) -> sqlx::Result<Option<TicketCategoryRow>> {
+        let guild_id = as_i64(guild_id.get());
+
+        let mut tx = pool.begin().await?;
+
+        sqlx::query!(
+            "INSERT INTO guilds (id) VALUES ($1) ON CONFLICT (id) DO NOTHING",
+            guild_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        let row = sqlx::query_as!(
+            TicketCategoryRow,
+            "INSERT INTO ticket_categories (guild_id, name, description, name_pattern, auto_close_hours, sla_minutes)
+            VALUES ($1, $2, $3, COALESCE($4, $7), NULLIF($5, 0), NULLIF($6, 0))
+            ON CONFLICT (guild_id, lower(name)) DO NOTHING
+            RETURNING id, guild_id, name, description, name_pattern, auto_close_hours, sla_minutes",
+            guild_id,
+            name,
+            fields.description,
+            fields.name_pattern,
+            fields.auto_close_hours,
+            fields.sla_minutes,
+            DEFAULT_NAME_PATTERN
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(row)
+    }
+
+    pub async fn edit(
+        pool: &PgPool,
+        id: i32,
+        fields: CategoryFields<'_>,
+    ) -> sqlx::Result<TicketCategoryRow> {
+        sqlx::query_as!(
+            TicketCategoryRow,
+            "UPDATE ticket_categories SET
+                description = COALESCE($2, description),
+                name_pattern = COALESCE($3, name_pattern),
+                auto_close_hours = CASE WHEN $4::integer IS NULL THEN auto_close_hours ELSE NULLIF($4, 0) END,
+                sla_minutes = CASE WHEN $5::integer IS NULL THEN sla_minutes ELSE NULLIF($5, 0) END
+            WHERE id = $1
+            RETURNING id, guild_id, name, description, name_pattern, auto_close_hours, sla_minutes",
+            id,
+            fields.description,
+            fields.name_pattern,
+            fields.auto_close_hours,
+            fields.sla_minutes
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &PgPool, id: i32) -> sqlx::Result<()> {
+        sqlx::query!("DELETE FROM ticket_categories WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn roles(pool: &PgPool, id: i32) -> sqlx::Result<Vec<RoleId>> {
+        sqlx::query_scalar!(
+            "SELECT role_id FROM ticket_category_roles WHERE category_id = $1 ORDER BY role_id",
+            id
+        )
+        .fetch(pool)
+        .map_ok(|id| RoleId::new(as_u64(id)))
+        .try_collect()
+        .await
+    }
+
+    /// Adds `role` to the category's pings, or removes it if already present.
+    /// Returns whether the role is pinged afterwards.
+    pub async fn toggle_role(
+        pool: &PgPool,
+        id: i32,
+        role: RoleId,
+    ) -> sqlx::Result<bool> {
+        let role = as_i64(role.get());
+
+        let removed = sqlx::query!(
+            "DELETE FROM ticket_category_roles WHERE category_id = $1 AND role_id = $2",
+            id,
+            role
+        )
+        .execute(pool)
+        .await?
+        .rows_affected();
+
+        if removed > 0 {
+            return Ok(false);
+        }
+
+        sqlx::query!(
+            "INSERT INTO ticket_category_roles (category_id, role_id) VALUES ($1, $2)
+            ON CONFLICT DO NOTHING",
+            id,
+            role
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(true)
+    }
+
+    pub async fn questions(
+        pool: &PgPool,
+        id: i32,
+    ) -> sqlx::Result<Vec<CategoryQuestion>> {
+        sqlx::query_as!(
+            CategoryQuestion,
+            "SELECT position, label, placeholder, paragraph, required
+            FROM ticket_category_questions
+            WHERE category_id = $1
+            ORDER BY position",
+            id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Appends a question to the category's form. Returns `false` when the
+    /// form is already full.
+    pub async fn add_question(
+        pool: &PgPool,
+        id: i32,
+        label: &str,
+        placeholder: Option<&str>,
+        paragraph: bool,
+        required: bool,
+    ) -> sqlx::Result<bool> {
+        let inserted = sqlx::query!(
+            "INSERT INTO ticket_category_questions (category_id, position, label, placeholder, paragraph, required)
+            SELECT $1, COALESCE(MAX(position) + 1, 0), $2, $3, $4, $5
+            FROM ticket_category_questions
+            WHERE category_id = $1
+            HAVING COUNT(*) < $6",
+            id,
+            label,
+            placeholder,
+            paragraph,
+            required,
+            i64::from(MAX_QUESTIONS)
+        )
+        .execute(pool)
+        .await?
+        .rows_affected();
+
+        Ok(inserted == 1)
+    }
+
+    /// Removes the question at `position` and closes the gap it leaves.
+    pub async fn remove_question(
+        pool: &PgPool,
+        id: i32,
+        position: i16,
+    ) -> sqlx::Result<bool> {
+        let mut tx = pool.begin().await?;
+
+        let removed = sqlx::query!(
+            "DELETE FROM ticket_category_questions WHERE category_id = $1 AND position = $2",
+            id,
+            position
+        )
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+        if removed == 0 {
+            return Ok(false);
+        }
+
+        // One row at a time, lowest first, so no shift collides with the
+        // primary key mid-statement.
+        for next in (position + 1)..MAX_QUESTIONS {
+            sqlx::query!(
+                "UPDATE ticket_category_questions SET position = $3
+                WHERE category_id = $1 AND position = $2",
+                id,
+                next,
+                next - 1
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(true)
+    }
+}
@@ -5,17 +5,72 @@ use serenity::all::{
     ComponentInteraction,
     ComponentInteractionDataKind,
     CreateEmbed,
+    CreateInputText,
     CreateInteractionResponse,
     CreateInteractionResponseMessage,
+    CreateLabel,
     CreateModal,
     CreateModalComponent,
-    EditThread,
     Http,
+    InputTextStyle,
 };
 use sqlx::PgPool;
 use zayden_core::CoreError as ZaydenError;
 
-use crate::{Result, TicketError, TicketGuildRow, TicketStores};
+use crate::category::CategoryQuestion;
+use crate::{
+    Result,
+    TicketCategoryManager,
+    TicketCategoryRow,
+    TicketError,
+    TicketGuildRow,
+    TicketStores,
+    lifecycle,
+};
+
+pub const CATEGORY_FORM_PREFIX: &str = "ticket_form:";
+
+/// The form for `category`: its own questions, or a single free-text box when
+/// it has none.
+#[must_use]
+pub fn category_modal<'a>(
+    category: &TicketCategoryRow,
+    questions: &'a [CategoryQuestion],
+) -> CreateModal<'a> {
+    let components = if questions.is_empty() {
+        vec![CreateModalComponent::Label(CreateLabel::input_text(
+            "Describe the issue:",
+            CreateInputText::new(InputTextStyle::Paragraph, "ticket_body"),
+        ))]
+    } else {
+        questions
+            .iter()
+            .map(|question| {
+                let style = if question.paragraph {
+                    InputTextStyle::Paragraph
+                } else {
+                    InputTextStyle::Short
+                };
+
+                let mut input = CreateInputText::new(style, question.custom_id())
+                    .required(question.required);
+                if let Some(placeholder) = question.placeholder.as_deref() {
+                    input = input.placeholder(placeholder);
+                }
+
+                CreateModalComponent::Label(CreateLabel::input_text(
+                    question.label.as_str(),
+                    input,
+                ))
+            })
+            .collect()
+    };
+
+    let title: String = category.name.chars().take(45).collect();
+
+    CreateModal::new(format!("{CATEGORY_FORM_PREFIX}{}", category.id), title)
+        .components(components)
+}
 
 pub struct TicketComponent;
 
@@ -35,26 +90,58 @@ impl TicketComponent {
         Ok(())
     }
 
-    pub async fn support_close(
+    pub async fn ticket_panel(
         http: &Http,
         interaction: &ComponentInteraction,
+        pool: &PgPool,
     ) -> Result<()> {
-        let channel = &interaction.channel;
-
-        let new_channel_name: String = format!(
-            "[Closed] - {}",
-            channel.base().name.as_deref().unwrap_or_default()
-        )
-        .chars()
-        .take(100)
-        .collect();
-
-        channel
-            .id()
-            .expect_thread()
-            .edit(http, EditThread::new().name(new_channel_name).archived(true))
+        let guild_id = interaction.guild_id.ok_or(ZaydenError::MissingGuildId)?;
+
+        let ComponentInteractionDataKind::StringSelect { values } =
+            &interaction.data.kind
+        else {
+            return Err(TicketError::Internal(
+                "TicketComponent::ticket_panel: expected StringSelect interaction"
+                    .into(),
+            ));
+        };
+
+        let id = values
+            .first()
+            .and_then(|raw| raw.parse::<i32>().ok())
+            .ok_or(TicketError::CategoryNotFound)?;
+
+        let category = TicketCategoryManager::get(pool, guild_id, id)
+            .await?
+            .ok_or(TicketError::CategoryNotFound)?;
+        let questions = TicketCategoryManager::questions(pool, id).await?;
+
+        interaction
+            .create_response(
+                http,
+                CreateInteractionResponse::Modal(category_modal(
+                    &category, &questions,
+                )),
+            )
             .await?;
 
+        Ok(())
+    }
+
+    pub async fn support_claim(
+        http: &Http,
+        interaction: &ComponentInteraction,
+        pool: &PgPool,
+    ) -> Result<()> {
+        let guild_id = interaction.guild_id.ok_or(ZaydenError::MissingGuildId)?;
+        let member =
+            interaction.member.as_ref().ok_or(ZaydenError::MissingGuildId)?;
+
+        lifecycle::ensure_staff(pool, guild_id, member).await?;
+
+        let thread = interaction.channel.id().expect_thread();
+        lifecycle::claim(http, pool, guild_id, thread, interaction.user.id).await?;
+
         interaction
             .create_response(http, CreateInteractionResponse::Acknowledge)
             .await?;
@@ -62,6 +149,26 @@ impl TicketComponent {
         Ok(())
     }
 
+    pub async fn support_close(
+        http: &Http,
+        interaction: &ComponentInteraction,
+    ) -> Result<()> {
+        let reason = CreateInputText::new(InputTextStyle::Paragraph, "reason")
+            .placeholder("Why is this ticket being closed?")
+            .required(false);
+
+        let modal = CreateModal::new("support_close_reason", "Close Ticket")
+            .components(vec![CreateModalComponent::Label(CreateLabel::input_text(
+                "Reason", reason,
+            ))]);
+
+        interaction
+            .create_response(http, CreateInteractionResponse::Modal(modal))
+            .await?;
+
+        Ok(())
+    }
+
     pub async fn support_faq(
         http: &Http,
         interaction: &ComponentInteraction,
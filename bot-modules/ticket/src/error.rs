@@ -1,14 +1,27 @@
 use std::borrow::Cow;
 
+use serenity::all::{Mentionable, UserId};
 use zayden_core::CoreError as ZaydenError;
 use zayden_core::error::{HandlerError, Respond};
 
+use crate::category::{MAX_CATEGORIES, MAX_LABEL_LEN, MAX_QUESTIONS};
+
 pub type Result<T> = std::result::Result<T, TicketError>;
 
 #[derive(Debug)]
 pub enum TicketError {
     NotInSupportChannel,
     SupportNotFound,
+    NotSupportStaff,
+    AlreadyClaimed(UserId),
+    AlreadyClosed,
+    CategoryNotFound,
+    CategoryExists,
+    CategoryLimit,
+    NoCategories,
+    QuestionLimit,
+    QuestionNotFound,
+    LabelTooLong,
     Internal(String),
 
     ZaydenCore(ZaydenError),
@@ -21,6 +34,38 @@ impl std::fmt::Display for TicketError {
                 write!(f, "This command only works in the support channel.")
             },
             Self::SupportNotFound => write!(f, "Support message not found"),
+            Self::NotSupportStaff => {
+                write!(f, "Only support staff can do that.")
+            },
+            Self::AlreadyClaimed(user_id) => write!(
+                f,
+                "This ticket has already been claimed by {}.",
+                user_id.mention()
+            ),
+            Self::AlreadyClosed => write!(f, "This ticket is already closed."),
+            Self::CategoryNotFound => {
+                write!(f, "That ticket category doesn't exist.")
+            },
+            Self::CategoryExists => {
+                write!(f, "A ticket category with that name already exists.")
+            },
+            Self::CategoryLimit => write!(
+                f,
+                "This server already has the maximum of {MAX_CATEGORIES} ticket categories."
+            ),
+            Self::NoCategories => write!(
+                f,
+                "Create a ticket category with `/ticket category create` first."
+            ),
+            Self::QuestionLimit => write!(
+                f,
+                "A ticket form can hold at most {MAX_QUESTIONS} questions."
+            ),
+            Self::QuestionNotFound => write!(f, "That question doesn't exist."),
+            Self::LabelTooLong => write!(
+                f,
+                "Question labels can be at most {MAX_LABEL_LEN} characters."
+            ),
             Self::Internal(msg) => write!(f, "internal error: {msg}"),
             Self::ZaydenCore(e) => e.fmt(f),
         }
@@ -33,6 +78,16 @@ impl std::error::Error for TicketError {
             Self::ZaydenCore(e) => Some(e),
             Self::NotInSupportChannel
             | Self::SupportNotFound
+            | Self::NotSupportStaff
+            | Self::AlreadyClaimed(_)
+            | Self::AlreadyClosed
+            | Self::CategoryNotFound
+            | Self::CategoryExists
+            | Self::CategoryLimit
+            | Self::NoCategories
+            | Self::QuestionLimit
+            | Self::QuestionNotFound
+            | Self::LabelTooLong
             | Self::Internal(_) => None,
         }
     }
@@ -41,9 +96,18 @@ impl std::error::Error for TicketError {
 impl Respond for TicketError {
     fn user_message(&self) -> Option<Cow<'_, str>> {
         match self {
-            Self::NotInSupportChannel | Self::SupportNotFound => {
-                Some(Cow::Owned(self.to_string()))
-            },
+            Self::NotInSupportChannel
+            | Self::SupportNotFound
+            | Self::NotSupportStaff
+            | Self::AlreadyClaimed(_)
+            | Self::AlreadyClosed
+            | Self::CategoryNotFound
+            | Self::CategoryExists
+            | Self::CategoryLimit
+            | Self::NoCategories
+            | Self::QuestionLimit
+            | Self::QuestionNotFound
+            | Self::LabelTooLong => Some(Cow::Owned(self.to_string())),
             Self::Internal(_) => None,
             Self::ZaydenCore(e) => e.user_message(),
         }
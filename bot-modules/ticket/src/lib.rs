@@ -10,20 +10,26 @@ use serenity::all::{
 };
 pub use serenity::all::{GuildId, RoleId};
 
+pub mod category;
 pub mod components;
 pub mod error;
+pub mod lifecycle;
 pub mod message_command;
 pub mod modal;
 pub mod slash_commands;
 pub mod support_guild_manager;
+pub mod support_ticket;
 pub mod ticket_manager;
+pub mod transcript;
 
+pub use category::{TicketCategoryManager, TicketCategoryRow};
 pub use components::TicketComponent;
 use error::Result;
 pub use error::TicketError;
 pub use message_command::SupportMessageCommand;
 pub use modal::TicketModal;
 pub use support_guild_manager::{SupportRoles, TicketGuildRow, TicketStores};
+pub use support_ticket::{SupportTicketManager, SupportTicketRow};
 pub use ticket_manager::TicketRow;
 
 pub struct Support;
@@ -58,7 +64,10 @@ pub async fn send_support_message(
 ) -> Result<()> {
     let mentions = mentions.iter().map(ToString::to_string).collect::<String>();
 
-    let button = CreateButton::new("support_close")
+    let claim = CreateButton::new("support_claim")
+        .label("Claim")
+        .style(ButtonStyle::Secondary);
+    let close = CreateButton::new("support_close")
         .label("Close")
         .style(ButtonStyle::Primary);
 
@@ -66,7 +75,7 @@ pub async fn send_support_message(
 
     let len = messages.len();
     let mut mentions = Some(mentions);
-    let mut button = Some(button);
+    let mut buttons = Some((claim, close));
 
     for (i, mut message) in messages.into_iter().enumerate() {
         if let Some(m) = mentions.take()
@@ -75,10 +84,10 @@ pub async fn send_support_message(
             message = message.content(m);
         }
 
-        if let Some(b) = button.take()
-            && i == len - 1
+        if i == len - 1
+            && let Some((claim, close)) = buttons.take()
         {
-            message = message.button(b);
+            message = message.button(claim).button(close);
         }
 
         thread_id.send_message(http, message).await?;
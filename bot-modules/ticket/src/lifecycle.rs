@@ -0,0 +1,317 @@
+use jiff::Timestamp;
+use serenity::all::{
+    ChannelId,
+    CreateAttachment,
+    CreateEmbed,
+    CreateMessage,
+    EditThread,
+    GuildId,
+    Http,
+    Member,
+    Mentionable,
+    Permissions,
+    RoleId,
+    ThreadId,
+    User,
+    UserId,
+};
+use sqlx::PgPool;
+use tracing::warn;
+use zayden_core::{as_i64, as_u64};
+
+use crate::support_ticket::{
+    InactiveTicket,
+    OverdueTicket,
+    SupportTicketManager,
+    SupportTicketRow,
+};
+use crate::transcript::{self, TranscriptHeader};
+use crate::{
+    Result,
+    SupportRoles,
+    TicketCategoryManager,
+    TicketError,
+    TicketStores,
+};
+
+/// Support staff hold one of the guild's support roles or can manage messages.
+pub async fn ensure_staff(
+    pool: &PgPool,
+    guild_id: GuildId,
+    member: &Member,
+) -> Result<()> {
+    let roles: Vec<RoleId> = SupportRoles::ids(pool, guild_id).await?;
+
+    let is_staff = member.roles.iter().any(|role| roles.contains(role))
+        || member.permissions.is_some_and(|p| {
+            p.contains(Permissions::MANAGE_MESSAGES)
+                || p.contains(Permissions::ADMINISTRATOR)
+        });
+
+    if is_staff { Ok(()) } else { Err(TicketError::NotSupportStaff) }
+}
+
+/// Staff can close any ticket, and whoever opened one can close their own.
+pub async fn ensure_can_close(
+    pool: &PgPool,
+    guild_id: GuildId,
+    thread: ThreadId,
+    member: &Member,
+) -> Result<()> {
+    let opener =
+        SupportTicketManager::get(pool, thread).await?.and_then(|row| row.opener());
+
+    if opener == Some(member.user.id) {
+        return Ok(());
+    }
+
+    ensure_staff(pool, guild_id, member).await
+}
+
+pub async fn claim(
+    http: &Http,
+    pool: &PgPool,
+    guild_id: GuildId,
+    thread: ThreadId,
+    claimer: UserId,
+) -> Result<SupportTicketRow> {
+    let row = SupportTicketManager::claim(pool, guild_id, thread, claimer).await?;
+
+    match row.claimer() {
+        Some(holder) if holder != claimer => {
+            return Err(TicketError::AlreadyClaimed(holder));
+        },
+        _ => {},
+    }
+
+    thread
+        .widen()
+        .send_message(
+            http,
+            CreateMessage::new().content(format!(
+                "🙋 {} has claimed this ticket.",
+                claimer.mention()
+            )),
+        )
+        .await?;
+
+    Ok(row)
+}
+
+/// Thread names are capped at 100 characters.
+#[must_use]
+pub fn closed_name(name: &str) -> String {
+    format!("[Closed] - {name}").chars().take(100).collect()
+}
+
+async fn describe(http: &Http, id: UserId) -> String {
+    id.to_user(http)
+        .await
+        .map_or_else(|_e| id.to_string(), |user| format!("{} ({id})", user.name))
+}
+
+fn mention_or(id: Option<UserId>, fallback: &str) -> String {
+    id.map_or_else(|| fallback.to_string(), |id| id.mention().to_string())
+}
+
+pub struct CloseRequest<'a> {
+    pub guild_id: GuildId,
+    pub thread: ThreadId,
+    pub thread_name: &'a str,
+    pub closer: &'a User,
+    pub reason: Option<&'a str>,
+}
+
+/// Records the close, then posts the transcript to the configured log channel
+/// and DMs it to the opener. Delivery failures are logged, not returned: the
+/// ticket is closed either way. A ticket that's already closed is left alone.
+pub async fn close(
+    http: &Http,
+    stores: TicketStores<'_>,
+    pool: &PgPool,
+    request: CloseRequest<'_>,
+) -> Result<SupportTicketRow> {
+    let CloseRequest { guild_id, thread, thread_name, closer, reason } = request;
+    let closed_at = Timestamp::now();
+
+    let row = SupportTicketManager::close(
+        pool, guild_id, thread, closer.id, reason, closed_at,
+    )
+    .await?
+    .ok_or(TicketError::AlreadyClosed)?;
+
+    let entries = transcript::fetch(http, thread).await?;
+
+    let opener = match row.opener() {
+        Some(id) => Some(describe(http, id).await),
+        None => None,
+    };
+    let claimer = match row.claimer() {
+        Some(id) => Some(describe(http, id).await),
+        None => None,
+    };
+
+    let header = TranscriptHeader {
+        title: thread_name.to_string(),
+        opener,
+        claimer,
+        closer: format!("{} ({})", closer.name, closer.id),
+        opened_at: row.opened_at.to_jiff(),
+        closed_at,
+        reason: reason.map(String::from),
+    };
+
+    let markdown = transcript::render_markdown(&header, &entries);
+    let filename = format!("transcript-{thread}.md");
+
+    let embed = CreateEmbed::new()
+        .title("Ticket closed")
+        .field("Thread", format!("{} `{thread_name}`", thread.mention()), false)
+        .field("Opened by", mention_or(row.opener(), "Unknown"), true)
+        .field("Claimed by", mention_or(row.claimer(), "Unclaimed"), true)
+        .field("Closed by", closer.mention().to_string(), true)
+        .field(
+            "Opened",
+            format!("<t:{}:f>", row.opened_at.to_jiff().as_second()),
+            true,
+        )
+        .field("Closed", format!("<t:{}:f>", closed_at.as_second()), true)
+        .field("Reason", reason.unwrap_or("No reason given"), false);
+
+    let log_channel = stores
+        .support
+        .get(as_i64(guild_id.get()))
+        .await?
+        .transcript_channel_id
+        .map(|id| ChannelId::new(as_u64(id)));
+
+    if let Some(channel) = log_channel {
+        let message = CreateMessage::new().embed(embed.clone()).add_file(
+            CreateAttachment::bytes(markdown.clone().into_bytes(), filename.clone()),
+        );
+
+        match channel.widen().send_message(http, message).await {
+            Ok(sent) => {
+                SupportTicketManager::set_transcript(pool, thread, sent.id).await?;
+            },
+            Err(e) => {
+                warn!(error = %e, %guild_id, %thread, "failed to post ticket transcript");
+            },
+        }
+    }
+
+    if let Some(opener) = row.opener() {
+        let message = CreateMessage::new()
+            .embed(embed)
+            .add_file(CreateAttachment::bytes(markdown.into_bytes(), filename));
+
+        if let Err(e) = opener.direct_message(http, message).await {
+            warn!(error = %e, %opener, %thread, "failed to DM ticket transcript");
+        }
+    }
+
+    Ok(row)
+}
+
+async fn remind(http: &Http, pool: &PgPool, ticket: &OverdueTicket) -> Result<()> {
+    let thread = ThreadId::new(as_u64(ticket.thread_id));
+    let guild_id = GuildId::new(as_u64(ticket.guild_id));
+
+    // Marked first so a thread that can no longer be posted in isn't retried
+    // on every sweep.
+    SupportTicketManager::mark_reminded(pool, thread).await?;
+
+    let mut roles = TicketCategoryManager::roles(pool, ticket.category_id).await?;
+    if roles.is_empty() {
+        roles = SupportRoles::ids(pool, guild_id).await?;
+    }
+
+    let mentions =
+        roles.iter().map(|role| role.mention().to_string()).collect::<String>();
+
+    thread
+        .widen()
+        .send_message(
+            http,
+            CreateMessage::new().content(format!(
+                "⏰ {mentions} This ticket has been waiting over {} minutes for a reply.",
+                ticket.sla_minutes
+            )),
+        )
+        .await?;
+
+    Ok(())
+}
+
+async fn auto_close(
+    http: &Http,
+    stores: TicketStores<'_>,
+    pool: &PgPool,
+    ticket: &InactiveTicket,
+    bot: &User,
+) -> Result<()> {
+    let thread_id = ThreadId::new(as_u64(ticket.thread_id));
+    let guild_id = GuildId::new(as_u64(ticket.guild_id));
+    let reason = format!(
+        "Closed automatically after {} hours of inactivity",
+        ticket.auto_close_hours
+    );
+
+    let thread = match thread_id.to_thread(http, Some(guild_id)).await {
+        Ok(thread) => thread,
+        Err(e) => {
+            // The thread is gone; record the close so it isn't retried.
+            warn!(error = %e, %thread_id, "inactive ticket thread is unavailable");
+            SupportTicketManager::close(
+                pool,
+                guild_id,
+                thread_id,
+                bot.id,
+                Some(&reason),
+                Timestamp::now(),
+            )
+            .await?;
+            return Ok(());
+        },
+    };
+    let thread_name = thread.base.name.as_str();
+
+    close(http, stores, pool, CloseRequest {
+        guild_id,
+        thread: thread_id,
+        thread_name,
+        closer: bot,
+        reason: Some(&reason),
+    })
+    .await?;
+
+    thread_id
+        .edit(http, EditThread::new().name(closed_name(thread_name)).archived(true))
+        .await?;
+
+    Ok(())
+}
+
+/// Reminds staff about categorised tickets waiting past their SLA and closes
+/// those idle past their auto-close timer. One ticket failing doesn't stop the
+/// rest.
+pub async fn sweep(
+    http: &Http,
+    stores: TicketStores<'_>,
+    pool: &PgPool,
+    bot: &User,
+) -> Result<()> {
+    for ticket in SupportTicketManager::overdue(pool).await? {
+        if let Err(e) = remind(http, pool, &ticket).await {
+            warn!(error = %e, thread_id = ticket.thread_id, "failed to send ticket reminder");
+        }
+    }
+
+    for ticket in SupportTicketManager::inactive(pool).await? {
+        if let Err(e) = auto_close(http, stores, pool, &ticket, bot).await {
+            warn!(error = %e, thread_id = ticket.thread_id, "failed to auto-close ticket");
+        }
+    }
+
+    Ok(())
+}
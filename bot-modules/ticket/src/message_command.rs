@@ -15,6 +15,7 @@ use zayden_core::CoreError;
 
 use crate::{
     Result,
+    SupportTicketManager,
     TicketError,
     TicketGuildRow,
     TicketStores,
@@ -76,6 +77,14 @@ impl SupportMessageCommand {
             .await?;
 
         TicketGuildRow::increment_thread_id(stores.ticket, guild_id).await?;
+        SupportTicketManager::open(
+            pool,
+            guild_id,
+            thread.id,
+            message.author.id,
+            None,
+        )
+        .await?;
 
         let issue = CreateEmbed::new().title("Issue").description(&message.content);
 
@@ -7,20 +7,29 @@ use serenity::all::{
     CreateInteractionResponseMessage,
     CreateMessage,
     CreateThread,
+    EditInteractionResponse,
+    EditThread,
     Http,
     Mentionable,
     ModalInteraction,
 };
 use sqlx::PgPool;
-use zayden_core::{CoreError, parse_modal_components};
+use zayden_core::{CoreError, as_i64, parse_modal_components};
 
+use crate::category::format_thread_name;
+use crate::components::CATEGORY_FORM_PREFIX;
+use crate::lifecycle::{self, CloseRequest};
 use crate::ticket_manager::TicketRow;
 use crate::{
     Result,
+    SupportRoles,
+    SupportTicketManager,
+    TicketCategoryManager,
     TicketError,
     TicketGuildRow,
     TicketStores,
     send_support_message,
+    support_mentions,
     thread_name,
     to_title_case,
 };
@@ -106,6 +115,14 @@ impl TicketModal {
             .await?;
 
         TicketGuildRow::increment_thread_id(stores.ticket, guild_id).await?;
+        SupportTicketManager::open(
+            pool,
+            guild_id,
+            thread.id,
+            interaction.user.id,
+            None,
+        )
+        .await?;
 
         let mentions = if role_ids.is_empty() {
             let owner_id = guild_id.to_partial_guild(http).await?.owner_id;
@@ -136,4 +153,169 @@ impl TicketModal {
 
         Ok(())
     }
+
+    pub async fn category(
+        http: &Http,
+        interaction: &ModalInteraction,
+        stores: TicketStores<'_>,
+        pool: &PgPool,
+    ) -> Result<()> {
+        let guild_id = interaction.guild_id.ok_or(CoreError::MissingGuildId)?;
+
+        let id = interaction
+            .data
+            .custom_id
+            .strip_prefix(CATEGORY_FORM_PREFIX)
+            .and_then(|id| id.parse::<i32>().ok())
+            .ok_or(TicketError::CategoryNotFound)?;
+
+        let category = TicketCategoryManager::get(pool, guild_id, id)
+            .await?
+            .ok_or(TicketError::CategoryNotFound)?;
+        let questions = TicketCategoryManager::questions(pool, id).await?;
+
+        interaction.defer_ephemeral(http).await?;
+
+        let mut data =
+            parse_modal_components(interaction.data.components.as_slice());
+
+        let answers: Vec<(String, String)> = if questions.is_empty() {
+            data.remove("ticket_body")
+                .and_then(|mut v| v.pop())
+                .map(|body| (String::from("Issue"), body.to_string()))
+                .into_iter()
+                .collect()
+        } else {
+            questions
+                .iter()
+                .filter_map(|question| {
+                    let answer =
+                        data.remove(question.custom_id().as_str())?.pop()?;
+                    (!answer.trim().is_empty())
+                        .then(|| (question.label.clone(), answer.to_string()))
+                })
+                .collect()
+        };
+
+        let summary =
+            answers.first().map(|(_, answer)| answer.as_str()).unwrap_or_default();
+
+        let number = stores.ticket.get(as_i64(guild_id.get())).await?.thread_id;
+        let thread_name = format_thread_name(
+            &category.name_pattern,
+            number,
+            interaction.user.display_name(),
+            &category.name,
+            summary,
+        );
+
+        let thread = interaction
+            .channel_id
+            .expect_channel()
+            .create_thread(
+                http,
+                CreateThread::new(&thread_name)
+                    .kind(ChannelType::PrivateThread)
+                    .auto_archive_duration(AutoArchiveDuration::OneWeek),
+            )
+            .await?;
+
+        TicketGuildRow::increment_thread_id(stores.ticket, guild_id).await?;
+        SupportTicketManager::open(
+            pool,
+            guild_id,
+            thread.id,
+            interaction.user.id,
+            Some(category.id),
+        )
+        .await?;
+
+        let mut role_ids = TicketCategoryManager::roles(pool, category.id).await?;
+        if role_ids.is_empty() {
+            role_ids = SupportRoles::ids(pool, guild_id).await?;
+        }
+
+        let owner = if role_ids.is_empty() {
+            Some(guild_id.to_partial_guild(http).await?.owner_id)
+        } else {
+            None
+        };
+
+        let mentions = support_mentions(&role_ids, interaction.user.id, owner);
+
+        let mut intro = CreateEmbed::new().title(category.name.as_str());
+        if let Some(description) = category.description.as_deref() {
+            intro = intro.description(description);
+        }
+
+        let mut messages = vec![CreateMessage::new().embed(intro)];
+        messages.extend(answers.into_iter().map(|(label, answer)| {
+            CreateMessage::new()
+                .embed(CreateEmbed::new().title(label).description(answer))
+        }));
+
+        send_support_message(http, thread.id, &mentions, messages).await?;
+
+        interaction
+            .edit_response(
+                http,
+                EditInteractionResponse::new().content(format!(
+                    "Support thread created: {}",
+                    thread.mention()
+                )),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn close_reason(
+        http: &Http,
+        interaction: &ModalInteraction,
+        stores: TicketStores<'_>,
+        pool: &PgPool,
+    ) -> Result<()> {
+        let guild_id = interaction.guild_id.ok_or(CoreError::MissingGuildId)?;
+        let member = interaction.member.as_ref().ok_or(CoreError::MissingGuildId)?;
+
+        let thread_id = interaction.channel_id.expect_thread();
+        lifecycle::ensure_can_close(pool, guild_id, thread_id, member).await?;
+
+        interaction.defer_ephemeral(http).await?;
+
+        let reason = parse_modal_components(interaction.data.components.as_slice())
+            .remove("reason")
+            .and_then(|mut v| v.pop())
+            .filter(|reason| !reason.trim().is_empty());
+
+        let thread = thread_id.to_thread(http, Some(guild_id)).await?;
+        let thread_name = thread.base.name.as_str();
+
+        lifecycle::close(http, stores, pool, CloseRequest {
+            guild_id,
+            thread: thread_id,
+            thread_name,
+            closer: &interaction.user,
+            reason: reason.as_deref(),
+        })
+        .await?;
+
+        interaction
+            .edit_response(
+                http,
+                EditInteractionResponse::new().content("Ticket closed"),
+            )
+            .await?;
+
+        thread_id
+            .edit(
+                http,
+                EditThread::new()
+                    .name(lifecycle::closed_name(thread_name))
+                    .archived(true),
+            )
+            .await?;
+
+        Ok(())
+    }
 }
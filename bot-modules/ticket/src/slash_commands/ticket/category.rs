@@ -0,0 +1,430 @@
+use std::collections::HashMap;
+use std::fmt::Write as _;
+
+use serenity::all::{
+    CommandInteraction,
+    CommandOptionType,
+    CreateCommandOption,
+    CreateEmbed,
+    EditInteractionResponse,
+    GuildId,
+    Http,
+    Mentionable,
+    ResolvedValue,
+    Role,
+};
+use sqlx::PgPool;
+use zayden_core::{
+    SubCommandOptions,
+    optional_option,
+    parse_options,
+    parse_subcommand,
+    required_option,
+};
+
+use crate::category::{
+    CategoryFields,
+    MAX_CATEGORIES,
+    MAX_LABEL_LEN,
+    TicketCategoryRow,
+};
+use crate::{Result, Ticket, TicketCategoryManager, TicketError};
+
+fn fields<'a>(options: &mut HashMap<&str, ResolvedValue<'a>>) -> CategoryFields<'a> {
+    CategoryFields {
+        description: optional_option(options, "description"),
+        name_pattern: optional_option(options, "name_pattern"),
+        auto_close_hours: optional_option::<i64, _>(options, "auto_close_hours")
+            .and_then(|h| i32::try_from(h).ok()),
+        sla_minutes: optional_option::<i64, _>(options, "sla_minutes")
+            .and_then(|m| i32::try_from(m).ok()),
+    }
+}
+
+async fn find(
+    pool: &PgPool,
+    guild_id: GuildId,
+    options: &mut HashMap<&str, ResolvedValue<'_>>,
+) -> Result<TicketCategoryRow> {
+    let name: &str = required_option(options, "category")?;
+
+    TicketCategoryManager::find(pool, guild_id, name)
+        .await?
+        .ok_or(TicketError::CategoryNotFound)
+}
+
+async fn describe(pool: &PgPool, category: &TicketCategoryRow) -> Result<String> {
+    let roles = TicketCategoryManager::roles(pool, category.id).await?;
+    let questions = TicketCategoryManager::questions(pool, category.id).await?;
+
+    let mut out = String::new();
+
+    if let Some(description) = &category.description {
+        let _ = writeln!(out, "{description}");
+    }
+
+    let _ = writeln!(out, "Thread name: `{}`", category.name_pattern);
+
+    let roles = if roles.is_empty() {
+        String::from("support roles")
+    } else {
+        roles.iter().map(|r| r.mention().to_string()).collect::<Vec<_>>().join(" ")
+    };
+    let _ = writeln!(out, "Pings: {roles}");
+
+    match category.sla_minutes {
+        Some(minutes) => {
+            let _ = writeln!(out, "Reply reminder after {minutes} minutes");
+        },
+        None => out.push_str("No reply reminder\n"),
+    }
+
+    match category.auto_close_hours {
+        Some(hours) => {
+            let _ = writeln!(out, "Closes after {hours} hours of inactivity");
+        },
+        None => out.push_str("Never closes automatically\n"),
+    }
+
+    if questions.is_empty() {
+        out.push_str("Form: a single free-text box\n");
+    } else {
+        for question in &questions {
+            let _ = writeln!(
+                out,
+                "{}. {}{}",
+                question.position + 1,
+                question.label,
+                if question.required { "" } else { " (optional)" }
+            );
+        }
+    }
+
+    Ok(out)
+}
+
+impl Ticket {
+    pub(super) async fn category(
+        http: &Http,
+        interaction: &CommandInteraction,
+        pool: &PgPool,
+        options: SubCommandOptions<'_>,
+        guild_id: GuildId,
+    ) -> Result<()> {
+        interaction.defer_ephemeral(http).await?;
+
+        let (name, options) = parse_subcommand(options)?;
+        let mut options = parse_options(options);
+
+        let content = match name {
+            "create" => {
+                let name: &str = required_option(&mut options, "name")?;
+
+                if TicketCategoryManager::list(pool, guild_id).await?.len()
+                    >= MAX_CATEGORIES
+                {
+                    return Err(TicketError::CategoryLimit);
+                }
+
+                let category = TicketCategoryManager::create(
+                    pool,
+                    guild_id,
+                    name,
+                    fields(&mut options),
+                )
+                .await?
+                .ok_or(TicketError::CategoryExists)?;
+
+                format!(
+                    "Created the **{}** ticket category. Post a panel with `/ticket panel` to use it.",
+                    category.name
+                )
+            },
+            "edit" => {
+                let category = find(pool, guild_id, &mut options).await?;
+                let category = TicketCategoryManager::edit(
+                    pool,
+                    category.id,
+                    fields(&mut options),
+                )
+                .await?;
+
+                format!("Updated the **{}** ticket category.", category.name)
+            },
+            "delete" => {
+                let category = find(pool, guild_id, &mut options).await?;
+                TicketCategoryManager::delete(pool, category.id).await?;
+
+                format!("Deleted the **{}** ticket category.", category.name)
+            },
+            "list" => {
+                let categories = TicketCategoryManager::list(pool, guild_id).await?;
+
+                if categories.is_empty() {
+                    return Err(TicketError::NoCategories);
+                }
+
+                let mut embed = CreateEmbed::new().title("Ticket Categories");
+                for category in &categories {
+                    embed = embed.field(
+                        category.name.as_str(),
+                        describe(pool, category)
+                            .await?
+                            .chars()
+                            .take(1024)
+                            .collect::<String>(),
+                        false,
+                    );
+                }
+
+                interaction
+                    .edit_response(http, EditInteractionResponse::new().embed(embed))
+                    .await?;
+
+                return Ok(());
+            },
+            "role" => {
+                let category = find(pool, guild_id, &mut options).await?;
+                let role: &Role = required_option(&mut options, "role")?;
+
+                if TicketCategoryManager::toggle_role(pool, category.id, role.id)
+                    .await?
+                {
+                    format!(
+                        "{} will be pinged for **{}** tickets.",
+                        role.mention(),
+                        category.name
+                    )
+                } else {
+                    format!(
+                        "{} will no longer be pinged for **{}** tickets.",
+                        role.mention(),
+                        category.name
+                    )
+                }
+            },
+            "question_add" => {
+                let category = find(pool, guild_id, &mut options).await?;
+                let label: &str = required_option(&mut options, "label")?;
+
+                if label.chars().count() > MAX_LABEL_LEN {
+                    return Err(TicketError::LabelTooLong);
+                }
+
+                let placeholder: Option<&str> =
+                    optional_option(&mut options, "placeholder");
+                let paragraph =
+                    optional_option(&mut options, "paragraph").unwrap_or(false);
+                let required =
+                    optional_option(&mut options, "required").unwrap_or(true);
+
+                if !TicketCategoryManager::add_question(
+                    pool,
+                    category.id,
+                    label,
+                    placeholder,
+                    paragraph,
+                    required,
+                )
+                .await?
+                {
+                    return Err(TicketError::QuestionLimit);
+                }
+
+                format!("Added \"{label}\" to the **{}** form.", category.name)
+            },
+            "question_remove" => {
+                let category = find(pool, guild_id, &mut options).await?;
+                let number: i64 = required_option(&mut options, "number")?;
+
+                let position = i16::try_from(number - 1)
+                    .map_err(|_e| TicketError::QuestionNotFound)?;
+
+                if !TicketCategoryManager::remove_question(
+                    pool,
+                    category.id,
+                    position,
+                )
+                .await?
+                {
+                    return Err(TicketError::QuestionNotFound);
+                }
+
+                format!(
+                    "Removed question {number} from the **{}** form.",
+                    category.name
+                )
+            },
+            name => {
+                return Err(TicketError::Internal(format!(
+                    "unrecognized ticket category subcommand: {name}"
+                )));
+            },
+        };
+
+        interaction
+            .edit_response(http, EditInteractionResponse::new().content(content))
+            .await?;
+
+        Ok(())
+    }
+
+    pub(super) fn register_category<'a>() -> CreateCommandOption<'a> {
+        let category_option = || {
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "category",
+                "The ticket category",
+            )
+            .required(true)
+        };
+
+        let settings = |option: CreateCommandOption<'a>| {
+            option
+                .add_sub_option(CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "description",
+                    "Shown on the panel and at the top of each ticket",
+                ))
+                .add_sub_option(CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "name_pattern",
+                    "Thread name using {number}, {user}, {category} and {summary}",
+                ))
+                .add_sub_option(
+                    CreateCommandOption::new(
+                        CommandOptionType::Integer,
+                        "sla_minutes",
+                        "Remind staff when the opener waits this long (0 to disable)",
+                    )
+                    .min_int_value(0),
+                )
+                .add_sub_option(
+                    CreateCommandOption::new(
+                        CommandOptionType::Integer,
+                        "auto_close_hours",
+                        "Close tickets idle for this long (0 to disable)",
+                    )
+                    .min_int_value(0),
+                )
+        };
+
+        let create = settings(
+            CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "create",
+                "Create a ticket category",
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "name",
+                    "The category name",
+                )
+                .required(true)
+                .max_length(100),
+            ),
+        );
+
+        let edit = settings(
+            CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "edit",
+                "Change a ticket category's settings",
+            )
+            .add_sub_option(category_option()),
+        );
+
+        let delete = CreateCommandOption::new(
+            CommandOptionType::SubCommand,
+            "delete",
+            "Delete a ticket category",
+        )
+        .add_sub_option(category_option());
+
+        let list = CreateCommandOption::new(
+            CommandOptionType::SubCommand,
+            "list",
+            "List the ticket categories",
+        );
+
+        let role = CreateCommandOption::new(
+            CommandOptionType::SubCommand,
+            "role",
+            "Toggle whether a role is pinged for a category",
+        )
+        .add_sub_option(category_option())
+        .add_sub_option(
+            CreateCommandOption::new(
+                CommandOptionType::Role,
+                "role",
+                "The role to ping",
+            )
+            .required(true),
+        );
+
+        let question_add = CreateCommandOption::new(
+            CommandOptionType::SubCommand,
+            "question_add",
+            "Add a question to a category's form",
+        )
+        .add_sub_option(category_option())
+        .add_sub_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "label",
+                "The question",
+            )
+            .required(true)
+            .max_length(45),
+        )
+        .add_sub_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "placeholder",
+                "Example answer shown in the empty box",
+            )
+            .max_length(100),
+        )
+        .add_sub_option(CreateCommandOption::new(
+            CommandOptionType::Boolean,
+            "paragraph",
+            "Allow a multi-line answer",
+        ))
+        .add_sub_option(CreateCommandOption::new(
+            CommandOptionType::Boolean,
+            "required",
+            "Whether an answer is required (default: true)",
+        ));
+
+        let question_remove = CreateCommandOption::new(
+            CommandOptionType::SubCommand,
+            "question_remove",
+            "Remove a question from a category's form",
+        )
+        .add_sub_option(category_option())
+        .add_sub_option(
+            CreateCommandOption::new(
+                CommandOptionType::Integer,
+                "number",
+                "The question number, as shown by /ticket category list",
+            )
+            .required(true)
+            .min_int_value(1)
+            .max_int_value(5),
+        );
+
+        CreateCommandOption::new(
+            CommandOptionType::SubCommandGroup,
+            "category",
+            "Manage ticket categories",
+        )
+        .add_sub_option(create)
+        .add_sub_option(edit)
+        .add_sub_option(delete)
+        .add_sub_option(list)
+        .add_sub_option(role)
+        .add_sub_option(question_add)
+        .add_sub_option(question_remove)
+    }
+}
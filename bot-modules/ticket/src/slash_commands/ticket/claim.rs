@@ -0,0 +1,54 @@
+use serenity::all::{
+    CommandInteraction,
+    EditInteractionResponse,
+    GenericInteractionChannel,
+    GuildId,
+    Http,
+};
+use sqlx::PgPool;
+
+use crate::{Result, Ticket, TicketError, TicketGuildRow, TicketStores, lifecycle};
+
+impl Ticket {
+    pub(super) async fn claim(
+        http: &Http,
+        interaction: &CommandInteraction,
+        stores: TicketStores<'_>,
+        pool: &PgPool,
+        guild_id: GuildId,
+    ) -> Result<()> {
+        interaction.defer_ephemeral(http).await?;
+
+        let support_channel_id = TicketGuildRow::get(stores, pool, guild_id)
+            .await?
+            .ok_or(TicketError::NotInSupportChannel)?
+            .channel_id()
+            .ok_or(TicketError::NotInSupportChannel)?;
+
+        let GenericInteractionChannel::Thread(channel) = &interaction.channel else {
+            return Err(TicketError::NotInSupportChannel);
+        };
+
+        if channel.parent_id != support_channel_id {
+            return Err(TicketError::NotInSupportChannel);
+        }
+
+        lifecycle::claim(
+            http,
+            pool,
+            guild_id,
+            interaction.channel_id.expect_thread(),
+            interaction.user.id,
+        )
+        .await?;
+
+        interaction
+            .edit_response(
+                http,
+                EditInteractionResponse::new().content("Ticket claimed"),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
@@ -11,6 +11,7 @@ use serenity::all::{
 };
 use sqlx::PgPool;
 
+use crate::lifecycle::{self, CloseRequest};
 use crate::{Result, Ticket, TicketError, TicketGuildRow, TicketStores};
 
 impl Ticket {
@@ -27,6 +28,11 @@ impl Ticket {
             _ => "",
         };
 
+        let reason = match options.remove("reason") {
+            Some(ResolvedValue::String(reason)) => Some(reason),
+            _ => None,
+        };
+
         if message.is_empty() {
             interaction.defer_ephemeral(http).await?;
         } else {
@@ -47,18 +53,20 @@ impl Ticket {
             return Err(TicketError::NotInSupportChannel);
         }
 
-        let new_channel_name: String = format!(
-            "[Closed] - {}",
-            channel.base().name.as_deref().unwrap_or_default()
-        )
-        .chars()
-        .take(100)
-        .collect();
+        let thread_name = channel.base().name.as_deref().unwrap_or_default();
+        let thread = interaction.channel_id.expect_thread();
 
-        interaction
-            .channel_id
-            .expect_thread()
-            .edit(http, EditThread::new().name(new_channel_name))
+        lifecycle::close(http, stores, pool, CloseRequest {
+            guild_id,
+            thread,
+            thread_name,
+            closer: &interaction.user,
+            reason,
+        })
+        .await?;
+
+        thread
+            .edit(http, EditThread::new().name(lifecycle::closed_name(thread_name)))
             .await?;
 
         interaction
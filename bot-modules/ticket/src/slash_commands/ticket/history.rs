@@ -0,0 +1,102 @@
+use std::collections::HashMap;
+use std::fmt::Write as _;
+
+use serenity::all::{
+    CommandInteraction,
+    CreateEmbed,
+    EditInteractionResponse,
+    GuildId,
+    Http,
+    Mentionable,
+    ResolvedValue,
+};
+use sqlx::PgPool;
+use zayden_core::{as_i64, as_u64};
+
+use crate::{Result, SupportTicketManager, SupportTicketRow, Ticket, TicketStores};
+
+const HISTORY_LIMIT: i64 = 10;
+
+fn describe(row: &SupportTicketRow, guild_id: GuildId, log: Option<u64>) -> String {
+    let mut out = format!(
+        "{} opened by {} <t:{}:f>",
+        row.thread_id().mention(),
+        row.opener()
+            .map_or_else(|| String::from("unknown"), |id| id.mention().to_string()),
+        row.opened_at.to_jiff().as_second()
+    );
+
+    if let Some(claimer) = row.claimer() {
+        let _ = write!(out, "\nClaimed by {}", claimer.mention());
+    }
+
+    if let Some(closed_at) = &row.closed_at {
+        let closer = row
+            .closer()
+            .map_or_else(|| String::from("unknown"), |id| id.mention().to_string());
+        let _ = write!(
+            out,
+            "\nClosed by {closer} <t:{}:f>: {}",
+            closed_at.to_jiff().as_second(),
+            row.close_reason.as_deref().unwrap_or("No reason given")
+        );
+    } else {
+        out.push_str("\nStill open");
+    }
+
+    if let (Some(channel), Some(message)) = (log, row.transcript_message_id()) {
+        let _ = write!(
+            out,
+            "\n[Transcript](https://discord.com/channels/{guild_id}/{channel}/{message})"
+        );
+    }
+
+    out
+}
+
+impl Ticket {
+    pub(super) async fn history(
+        http: &Http,
+        interaction: &CommandInteraction,
+        stores: TicketStores<'_>,
+        pool: &PgPool,
+        mut options: HashMap<&str, ResolvedValue<'_>>,
+        guild_id: GuildId,
+    ) -> Result<()> {
+        interaction.defer_ephemeral(http).await?;
+
+        let opener = match options.remove("user") {
+            Some(ResolvedValue::User(user, _)) => Some(user.id),
+            _ => None,
+        };
+
+        let rows =
+            SupportTicketManager::history(pool, guild_id, opener, HISTORY_LIMIT)
+                .await?;
+
+        let log = stores
+            .support
+            .get(as_i64(guild_id.get()))
+            .await?
+            .transcript_channel_id
+            .map(as_u64);
+
+        let description = if rows.is_empty() {
+            String::from("No tickets found.")
+        } else {
+            rows.iter()
+                .map(|row| describe(row, guild_id, log))
+                .collect::<Vec<_>>()
+                .join("\n\n")
+        };
+
+        let embed =
+            CreateEmbed::new().title("Ticket History").description(description);
+
+        interaction
+            .edit_response(http, EditInteractionResponse::new().embed(embed))
+            .await?;
+
+        Ok(())
+    }
+}
@@ -1,7 +1,11 @@
+mod category;
+mod claim;
 mod close;
 mod create;
 mod fixed;
+mod history;
 mod open;
+mod panel;
 mod remove;
 
 use serenity::all::{
@@ -29,9 +33,17 @@ impl Ticket {
         let guild_id = interaction.guild_id.ok_or(ZaydenError::MissingGuildId)?;
 
         let (name, options) = parse_subcommand(options)?;
+
+        if name == "category" {
+            return Self::category(http, interaction, pool, options, guild_id).await;
+        }
+
         let options = parse_options(options);
 
         match name {
+            "claim" => {
+                Self::claim(http, interaction, stores, pool, guild_id).await?;
+            },
             "close" => {
                 Self::close(http, interaction, stores, pool, options, guild_id)
                     .await?;
@@ -41,9 +53,16 @@ impl Ticket {
                 Self::fixed(http, interaction, stores, pool, options, guild_id)
                     .await?;
             },
+            "history" => {
+                Self::history(http, interaction, stores, pool, options, guild_id)
+                    .await?;
+            },
             "open" => {
                 Self::open(http, interaction, stores, pool, guild_id).await?;
             },
+            "panel" => {
+                Self::panel(http, interaction, pool, options, guild_id).await?;
+            },
             "remove" => {
                 Self::remove(http, interaction, pool, options).await?;
             },
@@ -58,6 +77,12 @@ impl Ticket {
     }
 
     pub fn register<'a>() -> CreateCommand<'a> {
+        let claim = CreateCommandOption::new(
+            CommandOptionType::SubCommand,
+            "claim",
+            "Claim the ticket",
+        );
+
         let close = CreateCommandOption::new(
             CommandOptionType::SubCommand,
             "close",
@@ -70,6 +95,14 @@ impl Ticket {
                 "Message to send before closing the ticket",
             )
             .required(false),
+        )
+        .add_sub_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "reason",
+                "Reason recorded in the transcript",
+            )
+            .required(false),
         );
 
         let create = CreateCommandOption::new(
@@ -107,19 +140,64 @@ impl Ticket {
             .required(false),
         );
 
+        let history = CreateCommandOption::new(
+            CommandOptionType::SubCommand,
+            "history",
+            "List recent tickets",
+        )
+        .add_sub_option(
+            CreateCommandOption::new(
+                CommandOptionType::User,
+                "user",
+                "Only show tickets opened by this user",
+            )
+            .required(false),
+        );
+
         let open = CreateCommandOption::new(
             CommandOptionType::SubCommand,
             "open",
             "Open the ticket",
         );
 
+        let panel = CreateCommandOption::new(
+            CommandOptionType::SubCommand,
+            "panel",
+            "Post a panel for opening tickets by category",
+        )
+        .add_sub_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "title",
+                "The title of the panel embed",
+            )
+            .required(true),
+        )
+        .add_sub_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "description",
+                "The description of the panel embed",
+            )
+            .required(true),
+        )
+        .add_sub_option(CreateCommandOption::new(
+            CommandOptionType::String,
+            "placeholder",
+            "Text shown in the empty category menu",
+        ));
+
         CreateCommand::new("ticket")
             .description("Ticket management commands")
             .default_member_permissions(Permissions::MANAGE_MESSAGES)
+            .add_option(Self::register_category())
+            .add_option(claim)
             .add_option(close)
             .add_option(create)
             .add_option(fixed)
+            .add_option(history)
             .add_option(open)
+            .add_option(panel)
 
         // CreateCommand::new("Ticket Remove").kind(CommandType::Message),
     }
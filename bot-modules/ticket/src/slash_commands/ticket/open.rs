@@ -8,7 +8,14 @@ use serenity::all::{
 };
 use sqlx::PgPool;
 
-use crate::{Result, Ticket, TicketError, TicketGuildRow, TicketStores};
+use crate::{
+    Result,
+    SupportTicketManager,
+    Ticket,
+    TicketError,
+    TicketGuildRow,
+    TicketStores,
+};
 
 impl Ticket {
     pub(super) async fn open(
@@ -46,6 +53,9 @@ impl Ticket {
             .edit(http, EditThread::new().name(new_channel_name))
             .await?;
 
+        SupportTicketManager::reopen(pool, interaction.channel_id.expect_thread())
+            .await?;
+
         interaction
             .edit_response(
                 http,
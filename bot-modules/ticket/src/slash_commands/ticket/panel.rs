@@ -0,0 +1,80 @@
+use std::collections::HashMap;
+
+use serenity::all::{
+    CommandInteraction,
+    CreateEmbed,
+    CreateMessage,
+    CreateSelectMenu,
+    CreateSelectMenuKind,
+    CreateSelectMenuOption,
+    EditInteractionResponse,
+    GuildId,
+    Http,
+    ResolvedValue,
+};
+use sqlx::PgPool;
+use zayden_core::{optional_option, required_option};
+
+use crate::{Result, Ticket, TicketCategoryManager, TicketError};
+
+impl Ticket {
+    pub(super) async fn panel(
+        http: &Http,
+        interaction: &CommandInteraction,
+        pool: &PgPool,
+        mut options: HashMap<&str, ResolvedValue<'_>>,
+        guild_id: GuildId,
+    ) -> Result<()> {
+        let title: &str = required_option(&mut options, "title")?;
+        let description: &str = required_option(&mut options, "description")?;
+        let placeholder: Option<&str> = optional_option(&mut options, "placeholder");
+
+        interaction.defer_ephemeral(http).await?;
+
+        let categories = TicketCategoryManager::list(pool, guild_id).await?;
+
+        if categories.is_empty() {
+            return Err(TicketError::NoCategories);
+        }
+
+        let options = categories
+            .iter()
+            .map(|category| {
+                let mut option = CreateSelectMenuOption::new(
+                    category.name.chars().take(100).collect::<String>(),
+                    category.id.to_string(),
+                );
+                if let Some(description) = &category.description {
+                    option = option.description(
+                        description.chars().take(100).collect::<String>(),
+                    );
+                }
+                option
+            })
+            .collect::<Vec<_>>();
+
+        let menu =
+            CreateSelectMenu::new("ticket_panel", CreateSelectMenuKind::String {
+                options: options.into(),
+            })
+            .placeholder(placeholder.unwrap_or("Choose a category"));
+
+        let embed = CreateEmbed::new()
+            .title(title)
+            .description(description.replace("\\n", "\n"));
+
+        interaction
+            .channel_id
+            .send_message(http, CreateMessage::new().embed(embed).select_menu(menu))
+            .await?;
+
+        interaction
+            .edit_response(
+                http,
+                EditInteractionResponse::new().content("Ticket panel created"),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,317 @@
+use jiff_sqlx::{Timestamp, ToSqlx};
+use serenity::all::{GuildId, MessageId, ThreadId, UserId};
+use sqlx::PgPool;
+use zayden_core::{as_i64, as_u64};
+
+#[derive(Debug, Clone)]
+pub struct SupportTicketRow {
+    pub thread_id: i64,
+    pub guild_id: i64,
+    pub opener_id: Option<i64>,
+    pub claimed_by: Option<i64>,
+    pub opened_at: Timestamp,
+    pub closed_at: Option<Timestamp>,
+    pub closed_by: Option<i64>,
+    pub close_reason: Option<String>,
+    pub transcript_message_id: Option<i64>,
+}
+
+impl SupportTicketRow {
+    #[must_use]
+    pub const fn thread_id(&self) -> ThreadId {
+        ThreadId::new(as_u64(self.thread_id))
+    }
+
+    #[must_use]
+    pub fn opener(&self) -> Option<UserId> {
+        self.opener_id.map(|id| UserId::new(as_u64(id)))
+    }
+
+    #[must_use]
+    pub fn claimer(&self) -> Option<UserId> {
+        self.claimed_by.map(|id| UserId::new(as_u64(id)))
+    }
+
+    #[must_use]
+    pub fn closer(&self) -> Option<UserId> {
+        self.closed_by.map(|id| UserId::new(as_u64(id)))
+    }
+
+    #[must_use]
+    pub fn transcript_message_id(&self) -> Option<MessageId> {
+        self.transcript_message_id.map(|id| MessageId::new(as_u64(id)))
+    }
+}
+
+/// An open categorised ticket whose opener has waited past the category's SLA.
+#[derive(Debug, Clone)]
+pub struct OverdueTicket {
+    pub thread_id: i64,
+    pub guild_id: i64,
+    pub category_id: i32,
+    pub sla_minutes: i32,
+}
+
+/// An open categorised ticket idle past the category's auto-close timer.
+#[derive(Debug, Clone)]
+pub struct InactiveTicket {
+    pub thread_id: i64,
+    pub guild_id: i64,
+    pub auto_close_hours: i32,
+}
+
+pub struct SupportTicketManager;
+
+impl SupportTicketManager {
+    pub async fn open(
+        pool: &PgPool,
+        guild_id: GuildId,
+        thread_id: ThreadId,
+        opener: UserId,
+        category: Option<i32>,
+    ) -> sqlx::Result<()> {
+        sqlx::query!(
+            "INSERT INTO support_tickets (thread_id, guild_id, opener_id, category_id)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (thread_id) DO NOTHING",
+            as_i64(thread_id.get()),
+            as_i64(guild_id.get()),
+            as_i64(opener.get()),
+            category
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn get(
+        pool: &PgPool,
+        thread_id: ThreadId,
+    ) -> sqlx::Result<Option<SupportTicketRow>> {
+        sqlx::query_as!(
+            SupportTicketRow,
+            r#"SELECT
+                thread_id,
+                guild_id,
+                opener_id,
+                claimed_by,
+                opened_at AS "opened_at: Timestamp",
+                closed_at AS "closed_at: Timestamp",
+                closed_by,
+                close_reason,
+                transcript_message_id
+            FROM support_tickets
+            WHERE thread_id = $1"#,
+            as_i64(thread_id.get())
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Records `claimer` unless someone else already holds the ticket.
+    /// Returns the row as it stands afterwards.
+    pub async fn claim(
+        pool: &PgPool,
+        guild_id: GuildId,
+        thread_id: ThreadId,
+        claimer: UserId,
+    ) -> sqlx::Result<SupportTicketRow> {
+        sqlx::query_as!(
+            SupportTicketRow,
+            r#"INSERT INTO support_tickets (thread_id, guild_id, claimed_by, claimed_at)
+            VALUES ($1, $2, $3, now())
+            ON CONFLICT (thread_id) DO UPDATE SET
+                claimed_by = COALESCE(support_tickets.claimed_by, EXCLUDED.claimed_by),
+                claimed_at = COALESCE(support_tickets.claimed_at, EXCLUDED.claimed_at)
+            RETURNING
+                thread_id,
+                guild_id,
+                opener_id,
+                claimed_by,
+                opened_at AS "opened_at: Timestamp",
+                closed_at AS "closed_at: Timestamp",
+                closed_by,
+                close_reason,
+                transcript_message_id"#,
+            as_i64(thread_id.get()),
+            as_i64(guild_id.get()),
+            as_i64(claimer.get())
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Returns `None` if the ticket was already closed, leaving who closed it,
+    /// when and why as they were.
+    pub async fn close(
+        pool: &PgPool,
+        guild_id: GuildId,
+        thread_id: ThreadId,
+        closer: UserId,
+        reason: Option<&str>,
+        closed_at: jiff::Timestamp,
+    ) -> sqlx::Result<Option<SupportTicketRow>> {
+        sqlx::query_as!(
+            SupportTicketRow,
+            r#"INSERT INTO support_tickets (thread_id, guild_id, closed_at, closed_by, close_reason)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (thread_id) DO UPDATE SET
+                closed_at = EXCLUDED.closed_at,
+                closed_by = EXCLUDED.closed_by,
+                close_reason = EXCLUDED.close_reason
+            WHERE support_tickets.closed_at IS NULL
+            RETURNING
+                thread_id,
+                guild_id,
+                opener_id,
+                claimed_by,
+                opened_at AS "opened_at: Timestamp",
+                closed_at AS "closed_at: Timestamp",
+                closed_by,
+                close_reason,
+                transcript_message_id"#,
+            as_i64(thread_id.get()),
+            as_i64(guild_id.get()),
+            closed_at.to_sqlx() as Timestamp,
+            as_i64(closer.get()),
+            reason
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn reopen(pool: &PgPool, thread_id: ThreadId) -> sqlx::Result<()> {
+        sqlx::query!(
+            "UPDATE support_tickets
+            SET closed_at = NULL, closed_by = NULL, close_reason = NULL, last_activity_at = now()
+            WHERE thread_id = $1",
+            as_i64(thread_id.get())
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn set_transcript(
+        pool: &PgPool,
+        thread_id: ThreadId,
+        message_id: MessageId,
+    ) -> sqlx::Result<()> {
+        sqlx::query!(
+            "UPDATE support_tickets SET transcript_message_id = $2 WHERE thread_id = $1",
+            as_i64(thread_id.get()),
+            as_i64(message_id.get())
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn history(
+        pool: &PgPool,
+        guild_id: GuildId,
+        opener: Option<UserId>,
+        limit: i64,
+    ) -> sqlx::Result<Vec<SupportTicketRow>> {
+        sqlx::query_as!(
+            SupportTicketRow,
+            r#"SELECT
+                thread_id,
+                guild_id,
+                opener_id,
+                claimed_by,
+                opened_at AS "opened_at: Timestamp",
+                closed_at AS "closed_at: Timestamp",
+                closed_by,
+                close_reason,
+                transcript_message_id
+            FROM support_tickets
+            WHERE guild_id = $1 AND ($2::bigint IS NULL OR opener_id = $2)
+            ORDER BY opened_at DESC
+            LIMIT $3"#,
+            as_i64(guild_id.get()),
+            opener.map(|id| as_i64(id.get())),
+            limit
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Records a message in the ticket thread. A message from the opener
+    /// starts the reply clock; one from anyone else stops it.
+    pub async fn touch(
+        pool: &PgPool,
+        thread_id: ThreadId,
+        author: UserId,
+    ) -> sqlx::Result<()> {
+        sqlx::query!(
+            "UPDATE support_tickets SET
+                last_activity_at = now(),
+                awaiting_reply_since = CASE
+                    WHEN opener_id = $2 THEN COALESCE(awaiting_reply_since, now())
+                    ELSE NULL
+                END,
+                sla_reminded_at = CASE WHEN opener_id = $2 THEN sla_reminded_at ELSE NULL END
+            WHERE thread_id = $1 AND closed_at IS NULL",
+            as_i64(thread_id.get()),
+            as_i64(author.get())
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn overdue(pool: &PgPool) -> sqlx::Result<Vec<OverdueTicket>> {
+        sqlx::query_as!(
+            OverdueTicket,
+            r#"SELECT
+                t.thread_id,
+                t.guild_id,
+                c.id AS category_id,
+                c.sla_minutes AS "sla_minutes!"
+            FROM support_tickets t
+            JOIN ticket_categories c ON c.id = t.category_id
+            WHERE t.closed_at IS NULL
+                AND t.sla_reminded_at IS NULL
+                AND c.sla_minutes IS NOT NULL
+                AND t.awaiting_reply_since + make_interval(mins => c.sla_minutes) < now()"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn mark_reminded(
+        pool: &PgPool,
+        thread_id: ThreadId,
+    ) -> sqlx::Result<()> {
+        sqlx::query!(
+            "UPDATE support_tickets SET sla_reminded_at = now() WHERE thread_id = $1",
+            as_i64(thread_id.get())
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn inactive(pool: &PgPool) -> sqlx::Result<Vec<InactiveTicket>> {
+        sqlx::query_as!(
+            InactiveTicket,
+            r#"SELECT
+                t.thread_id,
+                t.guild_id,
+                c.auto_close_hours AS "auto_close_hours!"
+            FROM support_tickets t
+            JOIN ticket_categories c ON c.id = t.category_id
+            WHERE t.closed_at IS NULL
+                AND c.auto_close_hours IS NOT NULL
+                AND t.last_activity_at + make_interval(hours => c.auto_close_hours) < now()"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+}
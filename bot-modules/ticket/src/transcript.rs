@@ -0,0 +1,163 @@
+use std::fmt::Write as _;
+
+use futures::{StreamExt, TryStreamExt};
+use jiff::Timestamp;
+use serenity::all::{Http, Message, ThreadId, UserId};
+
+use crate::Result;
+
+/// Upper bound on the messages read into one transcript.
+pub const TRANSCRIPT_MAX_MESSAGES: usize = 5_000;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TranscriptEmbed {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub fields: Vec<(String, String)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TranscriptEntry {
+    pub author: String,
+    pub author_id: UserId,
+    pub sent_at: Timestamp,
+    pub content: String,
+    pub attachments: Vec<(String, String)>,
+    pub embeds: Vec<TranscriptEmbed>,
+}
+
+impl From<&Message> for TranscriptEntry {
+    fn from(message: &Message) -> Self {
+        Self {
+            author: message.author.name.to_string(),
+            author_id: message.author.id,
+            sent_at: Timestamp::from_second(message.timestamp.unix_timestamp())
+                .unwrap_or_default(),
+            content: message.content.to_string(),
+            attachments: message
+                .attachments
+                .iter()
+                .map(|a| (a.filename.to_string(), a.url.to_string()))
+                .collect(),
+            embeds: message
+                .embeds
+                .iter()
+                .map(|e| TranscriptEmbed {
+                    title: e.title.as_deref().map(String::from),
+                    description: e.description.as_deref().map(String::from),
+                    fields: e
+                        .fields
+                        .iter()
+                        .map(|f| (f.name.to_string(), f.value.to_string()))
+                        .collect(),
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TranscriptHeader {
+    pub title: String,
+    pub opener: Option<String>,
+    pub claimer: Option<String>,
+    pub closer: String,
+    pub opened_at: Timestamp,
+    pub closed_at: Timestamp,
+    pub reason: Option<String>,
+}
+
+/// Reads every message in `thread`, oldest first.
+pub async fn fetch(http: &Http, thread: ThreadId) -> Result<Vec<TranscriptEntry>> {
+    let mut entries: Vec<TranscriptEntry> = thread
+        .widen()
+        .messages_iter(http)
+        .take(TRANSCRIPT_MAX_MESSAGES)
+        .map_ok(|message| TranscriptEntry::from(&message))
+        .try_collect()
+        .await?;
+
+    entries.reverse();
+
+    Ok(entries)
+}
+
+fn when(at: Timestamp) -> String {
+    at.strftime("%Y-%m-%d %H:%M:%S UTC").to_string()
+}
+
+fn quote(out: &mut String, text: &str) {
+    for line in text.lines() {
+        let _ = writeln!(out, "> {line}");
+    }
+}
+
+#[must_use]
+pub fn render_markdown(
+    header: &TranscriptHeader,
+    entries: &[TranscriptEntry],
+) -> String {
+    let mut out = String::with_capacity(entries.len() * 128 + 512);
+
+    let _ = writeln!(out, "# Transcript: {}", header.title);
+    out.push('\n');
+    let _ = writeln!(
+        out,
+        "- Opened by: {}",
+        header.opener.as_deref().unwrap_or("Unknown")
+    );
+    let _ = writeln!(
+        out,
+        "- Claimed by: {}",
+        header.claimer.as_deref().unwrap_or("Unclaimed")
+    );
+    let _ = writeln!(out, "- Closed by: {}", header.closer);
+    let _ = writeln!(out, "- Opened: {}", when(header.opened_at));
+    let _ = writeln!(out, "- Closed: {}", when(header.closed_at));
+    let _ = writeln!(
+        out,
+        "- Reason: {}",
+        header.reason.as_deref().unwrap_or("No reason given")
+    );
+    let _ = writeln!(out, "- Messages: {}", entries.len());
+
+    for entry in entries {
+        out.push('\n');
+        let _ = writeln!(
+            out,
+            "### {} ({}) - {}",
+            entry.author,
+            entry.author_id,
+            when(entry.sent_at)
+        );
+
+        if !entry.content.is_empty() {
+            out.push('\n');
+            out.push_str(&entry.content);
+            out.push('\n');
+        }
+
+        for embed in &entry.embeds {
+            out.push('\n');
+            if let Some(title) = &embed.title {
+                let _ = writeln!(out, "> **{title}**");
+            }
+            if let Some(description) = &embed.description {
+                quote(&mut out, description);
+            }
+            for (name, value) in &embed.fields {
+                let _ = writeln!(out, "> *{name}*");
+                quote(&mut out, value);
+            }
+        }
+
+        if !entry.attachments.is_empty() {
+            out.push('\n');
+            for (name, url) in &entry.attachments {
+                let _ = writeln!(out, "- Attachment: [{name}]({url})");
+            }
+        }
+    }
+
+    out
+}
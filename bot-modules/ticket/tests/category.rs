@@ -0,0 +1,50 @@
+//! Thread names built from a ticket category's naming pattern.
+//!
+//! The category tables themselves need a live `PgPool`; see
+//! `support_mentions.rs` for why there is no harness for those yet.
+
+use ticket::category::{DEFAULT_NAME_PATTERN, format_thread_name};
+
+#[test]
+fn default_pattern_matches_the_legacy_name() {
+    assert_eq!(
+        format_thread_name(DEFAULT_NAME_PATTERN, 7, "Oscar", "Bug", "Crash on load"),
+        ticket::thread_name(7, "Oscar", "Crash on load")
+    );
+}
+
+#[test]
+fn every_placeholder_is_filled() {
+    let name = format_thread_name(
+        "[{category}] #{number} {user}: {summary}",
+        12,
+        "Oscar",
+        "Billing",
+        "Refund please",
+    );
+
+    assert_eq!(name, "[Billing] #12 Oscar: Refund please");
+}
+
+#[test]
+fn summary_uses_only_the_first_line() {
+    let name = format_thread_name("{summary}", 1, "Oscar", "Bug", "First\nSecond");
+
+    assert_eq!(name, "First");
+}
+
+#[test]
+fn names_are_capped_at_100_characters() {
+    let summary = "x".repeat(200);
+    let name = format_thread_name("{summary}", 1, "Oscar", "Bug", &summary);
+
+    assert_eq!(name.chars().count(), 100);
+}
+
+#[test]
+fn empty_result_falls_back_to_the_category() {
+    assert_eq!(
+        format_thread_name("{summary}", 1, "Oscar", "Appeal", "  "),
+        "Appeal"
+    );
+}
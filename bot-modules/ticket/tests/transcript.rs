@@ -0,0 +1,105 @@
+//! The Markdown transcript posted to the log channel and DMed to the opener
+//! when a support ticket closes.
+//!
+//! Fetching the thread's messages needs a live gateway, so these tests start
+//! from already-converted `TranscriptEntry` values.
+
+use jiff::Timestamp;
+use serenity::all::UserId;
+use ticket::transcript::{
+    TranscriptEmbed,
+    TranscriptEntry,
+    TranscriptHeader,
+    render_markdown,
+};
+
+const OPENED: i64 = 1_700_000_000;
+
+fn at(offset: i64) -> Timestamp {
+    Timestamp::from_second(OPENED + offset).expect("a valid timestamp")
+}
+
+fn header() -> TranscriptHeader {
+    TranscriptHeader {
+        title: String::from("0001 - Oscar - Can't log in"),
+        opener: Some(String::from("oscar (1)")),
+        claimer: Some(String::from("staff (2)")),
+        closer: String::from("staff (2)"),
+        opened_at: at(0),
+        closed_at: at(3_600),
+        reason: Some(String::from("Password reset")),
+    }
+}
+
+fn entry(author: &str, id: u64, offset: i64, content: &str) -> TranscriptEntry {
+    TranscriptEntry {
+        author: String::from(author),
+        author_id: UserId::new(id),
+        sent_at: at(offset),
+        content: String::from(content),
+        attachments: Vec::new(),
+        embeds: Vec::new(),
+    }
+}
+
+#[test]
+fn header_lists_ticket_metadata() {
+    let markdown = render_markdown(&header(), &[]);
+
+    assert!(markdown.starts_with("# Transcript: 0001 - Oscar - Can't log in\n"));
+    assert!(markdown.contains("- Opened by: oscar (1)\n"));
+    assert!(markdown.contains("- Claimed by: staff (2)\n"));
+    assert!(markdown.contains("- Closed by: staff (2)\n"));
+    assert!(markdown.contains("- Opened: 2023-11-14 22:13:20 UTC\n"));
+    assert!(markdown.contains("- Closed: 2023-11-14 23:13:20 UTC\n"));
+    assert!(markdown.contains("- Reason: Password reset\n"));
+    assert!(markdown.contains("- Messages: 0\n"));
+}
+
+#[test]
+fn missing_metadata_falls_back_to_placeholders() {
+    let header =
+        TranscriptHeader { opener: None, claimer: None, reason: None, ..header() };
+
+    let markdown = render_markdown(&header, &[]);
+
+    assert!(markdown.contains("- Opened by: Unknown\n"));
+    assert!(markdown.contains("- Claimed by: Unclaimed\n"));
+    assert!(markdown.contains("- Reason: No reason given\n"));
+}
+
+#[test]
+fn messages_keep_their_order() {
+    let entries = [
+        entry("oscar", 1, 10, "I can't log in"),
+        entry("staff", 2, 20, "Have you tried resetting it?"),
+    ];
+
+    let markdown = render_markdown(&header(), &entries);
+
+    let first = markdown.find("I can't log in").expect("first message");
+    let second = markdown.find("Have you tried").expect("second message");
+    assert!(first < second);
+    assert!(markdown.contains("### oscar (1) - 2023-11-14 22:13:30 UTC\n"));
+    assert!(markdown.contains("- Messages: 2\n"));
+}
+
+#[test]
+fn attachments_and_embeds_are_kept() {
+    let mut message = entry("oscar", 1, 10, "");
+    message.attachments =
+        vec![(String::from("log.txt"), String::from("https://cdn.example/log.txt"))];
+    message.embeds = vec![TranscriptEmbed {
+        title: Some(String::from("Issue")),
+        description: Some(String::from("line one\nline two")),
+        fields: vec![(String::from("Version"), String::from("1.2"))],
+    }];
+
+    let markdown = render_markdown(&header(), &[message]);
+
+    assert!(
+        markdown.contains("- Attachment: [log.txt](https://cdn.example/log.txt)\n")
+    );
+    assert!(markdown.contains("> **Issue**\n> line one\n> line two\n"));
+    assert!(markdown.contains("> *Version*\n> 1.2\n"));
+}
@@ -0,0 +1,354 @@
+//! The image CAPTCHA in front of the verify button. A member gets one code at
+//! a time, answers it in a modal, and after `captcha_max_attempts` wrong
+//! answers is locked out and logged or kicked, as the guild chooses.
+
+use std::hash::{BuildHasher, RandomState};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serenity::all::{
+    ButtonStyle,
+    ChannelId,
+    Colour,
+    ComponentInteraction,
+    CreateActionRow,
+    CreateAttachment,
+    CreateButton,
+    CreateComponent,
+    CreateEmbed,
+    CreateInputText,
+    CreateInteractionResponse,
+    CreateInteractionResponseMessage,
+    CreateLabel,
+    CreateMessage,
+    CreateModal,
+    CreateModalComponent,
+    Http,
+    InputTextStyle,
+    Member,
+    Mentionable,
+    ModalInteraction,
+    UserId,
+};
+use sqlx::PgPool;
+use zayden_app::config::{CaptchaFailAction, RolesSettingsRow, SettingsStore};
+use zayden_core::{as_i64, as_u64, parse_modal_components};
+use zayden_graphics::Renderer;
+use zayden_graphics::captcha::{self, CAPTCHA_LENGTH, CAPTCHA_LIMITS};
+
+use crate::{Panel, Result, VerifyError, ensure_not_locked_down};
+
+/// The button under the image that opens the answer modal.
+pub const ANSWER: &str = "verify_captcha_answer";
+
+/// The answer modal.
+pub const MODAL: &str = "verify_captcha";
+
+const FILENAME: &str = "captcha.png";
+const FIELD: &str = "code";
+
+/// How long a code can be answered after it is shown.
+const CODE_TTL_MINUTES: i32 = 10;
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// Checks the account-age minimum and any lockout before a challenge is
+/// handed out.
+pub(crate) async fn check_gate(
+    pool: &PgPool,
+    settings: &RolesSettingsRow,
+    member: &Member,
+) -> Result<()> {
+    let min_days = settings.captcha_min_account_age_days;
+    if min_days > 0
+        && account_age_days(member.user.id) < u64::from(min_days.unsigned_abs())
+    {
+        return Err(VerifyError::AccountTooNew { days: min_days });
+    }
+
+    if let Some(minutes) = locked_for(pool, member).await? {
+        return Err(VerifyError::LockedOut { minutes });
+    }
+
+    Ok(())
+}
+
+fn account_age_days(user_id: UserId) -> u64 {
+    let created = u64::try_from(user_id.created_at().unix_timestamp()).unwrap_or(0);
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs());
+
+    now.saturating_sub(created) / SECONDS_PER_DAY
+}
+
+/// Randomness without a dependency: every `RandomState` is freshly keyed.
+fn seed(member: &Member) -> u64 {
+    RandomState::new().hash_one((member.guild_id, member.user.id, SystemTime::now()))
+}
+
+/// Minutes left on the member's lockout, rounded up.
+async fn locked_for(pool: &PgPool, member: &Member) -> Result<Option<i64>> {
+    let minutes = sqlx::query_scalar!(
+        r#"SELECT
+            CEIL(EXTRACT(EPOCH FROM locked_until - now()) / 60)::bigint AS "minutes!"
+        FROM verify_captchas
+        WHERE guild_id = $1 AND user_id = $2 AND locked_until > now()"#,
+        as_i64(member.guild_id.get()),
+        as_i64(member.user.id.get()),
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(minutes.map(|minutes| minutes.max(1)))
+}
+
+async fn pending_code(pool: &PgPool, member: &Member) -> Result<Option<String>> {
+    let code = sqlx::query_scalar!(
+        r#"SELECT code AS "code!" FROM verify_captchas
+        WHERE guild_id = $1 AND user_id = $2
+            AND code IS NOT NULL
+            AND issued_at > now() - make_interval(mins => $3)"#,
+        as_i64(member.guild_id.get()),
+        as_i64(member.user.id.get()),
+        CODE_TTL_MINUTES,
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(code)
+}
+
+/// A new image for the member, replacing any code they had. Wrong answers
+/// so far still count.
+pub(crate) async fn challenge(
+    pool: &PgPool,
+    member: &Member,
+    notice: Option<String>,
+) -> Result<CreateInteractionResponseMessage<'static>> {
+    let captcha = captcha::generate(seed(member));
+
+    let png = Renderer::shared()?
+        .render(captcha.markup, captcha.canvas, Vec::new(), CAPTCHA_LIMITS)
+        .await?;
+
+    sqlx::query!(
+        "INSERT INTO verify_captchas (guild_id, user_id, code)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (guild_id, user_id) DO UPDATE SET
+            code = EXCLUDED.code,
+            issued_at = now()",
+        as_i64(member.guild_id.get()),
+        as_i64(member.user.id.get()),
+        captcha.code,
+    )
+    .execute(pool)
+    .await?;
+
+    let embed = CreateEmbed::new()
+        .description(format!(
+            "Type the {CAPTCHA_LENGTH} characters in the image to verify. The \
+             code expires in {CODE_TTL_MINUTES} minutes."
+        ))
+        .image(format!("attachment://{FILENAME}"))
+        .colour(Colour::DARK_GREEN);
+
+    let answer =
+        CreateButton::new(ANSWER).label("Enter code").style(ButtonStyle::Success);
+
+    let mut message = CreateInteractionResponseMessage::new()
+        .embed(embed)
+        .add_file(CreateAttachment::bytes(png, FILENAME))
+        .components(vec![CreateComponent::ActionRow(CreateActionRow::buttons(
+            vec![answer],
+        ))])
+        .ephemeral(true);
+
+    if let Some(notice) = notice {
+        message = message.content(notice);
+    }
+
+    Ok(message)
+}
+
+/// The "Enter code" button.
+pub async fn open(
+    http: &Http,
+    interaction: &ComponentInteraction,
+    pool: &PgPool,
+) -> Result<()> {
+    let Some(member) = interaction.member.as_ref() else {
+        return Err(VerifyError::NotGuildMember);
+    };
+
+    if pending_code(pool, member).await?.is_none() {
+        return Err(VerifyError::CaptchaExpired);
+    }
+
+    let input = CreateInputText::new(InputTextStyle::Short, FIELD)
+        .min_length(u16::try_from(CAPTCHA_LENGTH).unwrap_or(u16::MAX))
+        .max_length(u16::try_from(CAPTCHA_LENGTH * 2).unwrap_or(u16::MAX))
+        .required(true);
+
+    let modal = CreateModal::new(MODAL, "Verification").components(vec![
+        CreateModalComponent::Label(CreateLabel::input_text(
+            "Characters in the image",
+            input,
+        )),
+    ]);
+
+    interaction
+        .create_response(http, CreateInteractionResponse::Modal(modal))
+        .await?;
+
+    Ok(())
+}
+
+/// Counts a wrong answer. Returns the attempts used, or `None` once they have
+/// run out and the member is locked out.
+async fn record_miss(
+    pool: &PgPool,
+    member: &Member,
+    settings: &RolesSettingsRow,
+) -> Result<Option<i32>> {
+    let attempts = sqlx::query_scalar!(
+        "UPDATE verify_captchas SET
+            attempts = CASE WHEN attempts + 1 >= $3 THEN 0 ELSE attempts + 1 END,
+            locked_until = CASE
+                WHEN attempts + 1 >= $3 THEN now() + make_interval(mins => $4)
+                ELSE locked_until
+            END,
+            code = NULL
+        WHERE guild_id = $1 AND user_id = $2
+        RETURNING attempts",
+        as_i64(member.guild_id.get()),
+        as_i64(member.user.id.get()),
+        settings.captcha_max_attempts,
+        settings.captcha_lockout_minutes,
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok((attempts > 0).then_some(attempts))
+}
+
+async fn fail(
+    http: &Http,
+    member: &Member,
+    settings: &RolesSettingsRow,
+) -> Result<()> {
+    let action = settings.captcha_fail_action();
+
+    if let Some(channel) = settings.captcha_log_channel_id {
+        let outcome = match action {
+            CaptchaFailAction::Log => "They were locked out",
+            CaptchaFailAction::Kick => "They were kicked and locked out",
+        };
+
+        ChannelId::new(as_u64(channel))
+            .send_message(
+                http,
+                CreateMessage::new().content(format!(
+                    "{} ({}) failed the verification CAPTCHA {} times. {outcome} \
+                     for {} minutes.",
+                    member.mention(),
+                    member.user.id,
+                    settings.captcha_max_attempts,
+                    settings.captcha_lockout_minutes,
+                )),
+            )
+            .await?;
+    }
+
+    if action == CaptchaFailAction::Kick {
+        member
+            .guild_id
+            .kick(http, member.user.id, Some("Failed the verification CAPTCHA"))
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// The answer modal. A right answer carries on to the rules or the role; a
+/// wrong one shows a fresh image until the attempts run out.
+pub async fn submit(
+    http: &Http,
+    interaction: &ModalInteraction,
+    store: &SettingsStore<RolesSettingsRow>,
+    pool: &PgPool,
+) -> Result<()> {
+    let Some(member) = interaction.member.as_ref() else {
+        return Err(VerifyError::NotGuildMember);
+    };
+
+    let settings = store.get(as_i64(member.guild_id.get())).await?;
+    ensure_not_locked_down(pool, as_i64(member.guild_id.get())).await?;
+
+    if let Some(minutes) = locked_for(pool, member).await? {
+        return Err(VerifyError::LockedOut { minutes });
+    }
+
+    let Some(code) = pending_code(pool, member).await? else {
+        return Err(VerifyError::CaptchaExpired);
+    };
+
+    let inputs = parse_modal_components(&interaction.data.components);
+    let answer = inputs.get(FIELD).and_then(|values| values.first());
+
+    if answer.is_some_and(|answer| captcha::matches(&code, answer)) {
+        sqlx::query!(
+            "DELETE FROM verify_captchas WHERE guild_id = $1 AND user_id = $2",
+            as_i64(member.guild_id.get()),
+            as_i64(member.user.id.get()),
+        )
+        .execute(pool)
+        .await?;
+
+        let message = Panel::proceed(http, pool, member, &settings).await?;
+
+        interaction
+            .create_response(http, CreateInteractionResponse::Message(message))
+            .await?;
+
+        return Ok(());
+    }
+
+    if let Some(attempts) = record_miss(pool, member, &settings).await? {
+        let left = settings.captcha_max_attempts.saturating_sub(attempts);
+        let message = challenge(
+            pool,
+            member,
+            Some(format!(
+                "That wasn't right. Here is a new image; {left} attempt(s) left."
+            )),
+        )
+        .await?;
+
+        interaction
+            .create_response(http, CreateInteractionResponse::Message(message))
+            .await?;
+
+        return Ok(());
+    }
+
+    // Answer before acting, so the member still sees why if they are kicked.
+    interaction
+        .create_response(
+            http,
+            CreateInteractionResponse::Message(
+                CreateInteractionResponseMessage::new()
+                    .content(
+                        VerifyError::LockedOut {
+                            minutes: i64::from(
+                                settings.captcha_lockout_minutes.max(1),
+                            ),
+                        }
+                        .to_string(),
+                    )
+                    .ephemeral(true),
+            ),
+        )
+        .await?;
+
+    fail(http, member, &settings).await
+}
@@ -14,6 +14,35 @@ pub enum VerifyError {
     )]
     RoleNotConfigured,
 
+    #[error(
+        "The server rules changed while you were reading them. Click Verify again to see the current rules."
+    )]
+    RulesChanged,
+
+    #[error(
+        "Not all of your answers were right. Read the rules again and click Verify to retry."
+    )]
+    QuizFailed,
+
+    #[error(
+        "Your Discord account must be at least {days} day(s) old to verify here."
+    )]
+    AccountTooNew { days: i32 },
+
+    #[error("Too many wrong answers. Try again in {minutes} minute(s).")]
+    LockedOut { minutes: i64 },
+
+    #[error("This CAPTCHA has expired. Click Verify for a new one.")]
+    CaptchaExpired,
+
+    #[error(
+        "Verification is paused while the server is in lockdown. Try again later."
+    )]
+    LockedDown,
+
+    #[error(transparent)]
+    Graphics(#[from] zayden_graphics::GraphicsError),
+
     #[error(transparent)]
     Discord(#[from] serenity::Error),
 
@@ -24,10 +53,15 @@ pub enum VerifyError {
 impl Respond for VerifyError {
     fn user_message(&self) -> Option<Cow<'_, str>> {
         match self {
-            Self::NotGuildMember | Self::RoleNotConfigured => {
-                Some(Cow::Owned(self.to_string()))
-            },
-            Self::Discord(_) | Self::Sqlx(_) => None,
+            Self::NotGuildMember
+            | Self::RoleNotConfigured
+            | Self::RulesChanged
+            | Self::QuizFailed
+            | Self::AccountTooNew { .. }
+            | Self::LockedOut { .. }
+            | Self::CaptchaExpired
+            | Self::LockedDown => Some(Cow::Owned(self.to_string())),
+            Self::Discord(_) | Self::Sqlx(_) | Self::Graphics(_) => None,
         }
     }
 }
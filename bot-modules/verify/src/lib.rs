@@ -1,3 +1,4 @@
+pub mod captcha;
 pub mod error;
 pub use error::{Result, VerifyError};
 use serenity::all::{
@@ -5,19 +6,37 @@ use serenity::all::{
     Colour,
     CommandInteraction,
     ComponentInteraction,
+    CreateActionRow,
     CreateButton,
     CreateCommand,
+    CreateComponent,
     CreateEmbed,
+    CreateInputText,
     CreateInteractionResponse,
     CreateInteractionResponseMessage,
+    CreateLabel,
     CreateMessage,
+    CreateModal,
+    CreateModalComponent,
     GuildId,
     Http,
+    InputTextStyle,
+    Member,
+    ModalInteraction,
     Permissions,
     RoleId,
 };
+use sqlx::PgPool;
 use zayden_app::config::{RolesSettingsRow, SettingsStore};
-use zayden_core::{as_i64, as_u64};
+use zayden_app::services::lockdown;
+use zayden_app::services::rules::{self, MAX_QUIZ_QUESTIONS, RulesSnapshot};
+use zayden_core::{as_i64, as_u64, parse_modal_components};
+
+/// Prefix of the accept button's id; the rules version shown follows it.
+pub const ACCEPT: &str = "verify_accept:";
+
+/// Prefix of the quiz modal's id; the accepted rules version follows it.
+pub const QUIZ: &str = "verify_quiz:";
 
 pub async fn verified_role(
     store: &SettingsStore<RolesSettingsRow>,
@@ -31,6 +50,90 @@ pub async fn verified_role(
         .ok_or(VerifyError::RoleNotConfigured)
 }
 
+/// Fails with [`VerifyError::LockedDown`] while the guild is in a raid
+/// lockdown, so no step of verification can hand out the role.
+pub(crate) async fn ensure_not_locked_down(
+    pool: &PgPool,
+    guild_id: i64,
+) -> Result<()> {
+    if lockdown::is_active(pool, guild_id).await? {
+        return Err(VerifyError::LockedDown);
+    }
+
+    Ok(())
+}
+
+/// The rules version carried by an accept button or quiz modal id.
+fn shown_version(custom_id: &str) -> Option<i32> {
+    custom_id
+        .strip_prefix(ACCEPT)
+        .or_else(|| custom_id.strip_prefix(QUIZ))?
+        .parse()
+        .ok()
+}
+
+fn rules_embed(rules: &RulesSnapshot) -> CreateEmbed<'static> {
+    let fields = rules
+        .rules
+        .iter()
+        .map(|rule| (rule.title.clone(), rule.body.clone(), false));
+
+    let mut embed = CreateEmbed::new()
+        .colour(Colour::new(rules.colour.cast_unsigned()))
+        .title(rules.title.clone())
+        .fields(fields);
+
+    if let Some(description) = &rules.description {
+        embed = embed.description(description.clone());
+    }
+
+    embed
+}
+
+fn rules_prompt(rules: &RulesSnapshot) -> CreateInteractionResponseMessage<'static> {
+    let accept = CreateButton::new(format!("{ACCEPT}{}", rules.version))
+        .label("I accept the rules")
+        .style(ButtonStyle::Success);
+
+    CreateInteractionResponseMessage::new()
+        .content("Read the server rules, then accept them to get verified.")
+        .embed(rules_embed(rules))
+        .components(vec![CreateComponent::ActionRow(CreateActionRow::buttons(
+            vec![accept],
+        ))])
+        .ephemeral(true)
+}
+
+fn verified_message() -> CreateInteractionResponseMessage<'static> {
+    CreateInteractionResponseMessage::new()
+        .content("You have been verified.")
+        .embeds(Vec::new())
+        .components(Vec::new())
+        .ephemeral(true)
+}
+
+async fn grant(
+    http: &Http,
+    pool: &PgPool,
+    member: &Member,
+    role: RoleId,
+    version: Option<i32>,
+) -> Result<()> {
+    if let Some(version) = version {
+        rules::record_acceptance(
+            pool,
+            as_i64(member.guild_id.get()),
+            as_i64(member.user.id.get()),
+            version,
+        )
+        .await?;
+    }
+
+    member.add_role(http, role, Some("Verified user")).await?;
+
+    Ok(())
+}
+
 pub struct Panel;
 
 impl Panel {
@@ -70,27 +173,207 @@ impl Panel {
             .description("Send a verification panel/button in this channel")
     }
 
+    /// The verify button. Depending on the guild, the member first answers a
+    /// CAPTCHA and then sees the current rules, unless they already accepted
+    /// this version.
     pub async fn run_component(
         http: &Http,
         interaction: &ComponentInteraction,
         store: &SettingsStore<RolesSettingsRow>,
+        pool: &PgPool,
+    ) -> Result<()> {
+        let Some(member) = interaction.member.as_ref() else {
+            return Err(VerifyError::NotGuildMember);
+        };
+
+        let guild_id = as_i64(member.guild_id.get());
+        let settings = store.get(guild_id).await?;
+        if settings.verified_role_id.is_none() {
+            return Err(VerifyError::RoleNotConfigured);
+        }
+
+        ensure_not_locked_down(pool, guild_id).await?;
+
+        let message = if settings.captcha_enabled {
+            captcha::check_gate(pool, &settings, member).await?;
+            captcha::challenge(pool, member, None).await?
+        } else {
+            Self::proceed(http, pool, member, &settings).await?
+        };
+
+        interaction
+            .create_response(http, CreateInteractionResponse::Message(message))
+            .await?;
+
+        Ok(())
+    }
+
+    /// Everything after the CAPTCHA: the rules when the guild requires them
+    /// and the member hasn't accepted this version, otherwise the role.
+    pub(crate) async fn proceed(
+        http: &Http,
+        pool: &PgPool,
+        member: &Member,
+        settings: &RolesSettingsRow,
+    ) -> Result<CreateInteractionResponseMessage<'static>> {
+        let role = settings
+            .verified_role_id
+            .map(|id| RoleId::new(as_u64(id)))
+            .ok_or(VerifyError::RoleNotConfigured)?;
+
+        let guild_id = as_i64(member.guild_id.get());
+
+        let snapshot = if settings.require_rules {
+            rules::current(pool, guild_id).await?
+        } else {
+            None
+        };
+
+        if let Some(snapshot) = snapshot {
+            let accepted = rules::accepted_version(
+                pool,
+                guild_id,
+                as_i64(member.user.id.get()),
+            )
+            .await?;
+
+            if accepted != Some(snapshot.version) {
+                return Ok(rules_prompt(&snapshot));
+            }
+        }
+
+        grant(http, pool, member, role, None).await?;
+
+        Ok(verified_message())
+    }
+
+    /// The accept button under the rules. Opens the quiz when the guild has
+    /// one, and verifies the member straight away otherwise.
+    pub async fn accept(
+        http: &Http,
+        interaction: &ComponentInteraction,
+        store: &SettingsStore<RolesSettingsRow>,
+        pool: &PgPool,
+    ) -> Result<()> {
+        let Some(member) = interaction.member.as_ref() else {
+            return Err(VerifyError::NotGuildMember);
+        };
+
+        let guild_id = as_i64(member.guild_id.get());
+        let role = verified_role(store, member.guild_id).await?;
+        ensure_not_locked_down(pool, guild_id).await?;
+
+        let Some(snapshot) = rules::current(pool, guild_id).await? else {
+            grant(http, pool, member, role, None).await?;
+
+            interaction
+                .create_response(
+                    http,
+                    CreateInteractionResponse::UpdateMessage(verified_message()),
+                )
+                .await?;
+
+            return Ok(());
+        };
+
+        if shown_version(&interaction.data.custom_id) != Some(snapshot.version) {
+            interaction
+                .create_response(
+                    http,
+                    CreateInteractionResponse::UpdateMessage(
+                        rules_prompt(&snapshot).content(
+                            "The rules changed since you opened them. Read the \
+                             current rules, then accept them to get verified.",
+                        ),
+                    ),
+                )
+                .await?;
+
+            return Ok(());
+        }
+
+        let quiz = rules::quiz(pool, guild_id).await?;
+
+        if quiz.is_empty() {
+            grant(http, pool, member, role, Some(snapshot.version)).await?;
+
+            interaction
+                .create_response(
+                    http,
+                    CreateInteractionResponse::UpdateMessage(verified_message()),
+                )
+                .await?;
+
+            return Ok(());
+        }
+
+        let inputs = quiz
+            .iter()
+            .take(MAX_QUIZ_QUESTIONS)
+            .map(|question| {
+                CreateModalComponent::Label(CreateLabel::input_text(
+                    question.question.clone(),
+                    CreateInputText::new(
+                        InputTextStyle::Short,
+                        format!("q{}", question.id),
+                    ),
+                ))
+            })
+            .collect::<Vec<_>>();
+
+        let modal =
+            CreateModal::new(format!("{QUIZ}{}", snapshot.version), "Rules quiz")
+                .components(inputs);
+
+        interaction
+            .create_response(http, CreateInteractionResponse::Modal(modal))
+            .await?;
+
+        Ok(())
+    }
+
+    /// The quiz modal. Every answer must match one the admins accept.
+    pub async fn quiz_submit(
+        http: &Http,
+        interaction: &ModalInteraction,
+        store: &SettingsStore<RolesSettingsRow>,
+        pool: &PgPool,
     ) -> Result<()> {
         let Some(member) = interaction.member.as_ref() else {
             return Err(VerifyError::NotGuildMember);
         };
 
+        let guild_id = as_i64(member.guild_id.get());
         let role = verified_role(store, member.guild_id).await?;
+        ensure_not_locked_down(pool, guild_id).await?;
+
+        let version = shown_version(&interaction.data.custom_id);
+        let current = rules::current(pool, guild_id).await?.map(|s| s.version);
+        if current.is_some() && current != version {
+            return Err(VerifyError::RulesChanged);
+        }
+
+        let inputs = parse_modal_components(&interaction.data.components);
+        let passed =
+            rules::quiz(pool, guild_id).await?.iter().take(MAX_QUIZ_QUESTIONS).all(
+                |question| {
+                    inputs
+                        .get(format!("q{}", question.id).as_str())
+                        .and_then(|values| values.first())
+                        .is_some_and(|answer| question.accepts(answer))
+                },
+            );
 
-        member.add_role(http, role, Some("Verified user")).await?;
+        if !passed {
+            return Err(VerifyError::QuizFailed);
+        }
+
+        grant(http, pool, member, role, current).await?;
 
         interaction
             .create_response(
                 http,
-                CreateInteractionResponse::Message(
-                    CreateInteractionResponseMessage::new()
-                        .content("You have been verified.")
-                        .ephemeral(true),
-                ),
+                CreateInteractionResponse::UpdateMessage(verified_message()),
             )
             .await?;
 
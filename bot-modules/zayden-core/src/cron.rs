@@ -68,10 +68,13 @@ impl CronJob {
         self.action_fn = Self::action_fn(f);
         self
     }
-}
 
-fn next_run(job: &CronJob, now: &Zoned) -> Option<Zoned> {
-    job.schedule.after(now.clone()).find(|t| job.schedule.includes(t.clone()))
+    /// The first time after `now` the job fires, or `None` once a one-shot
+    /// has fired.
+    #[must_use]
+    pub fn next_run(&self, now: &Zoned) -> Option<Zoned> {
+        self.schedule.after(now.clone()).find(|t| self.schedule.includes(t.clone()))
+    }
 }
 
 pub fn prune_exhausted(jobs: &mut Vec<CronJob>, now: &Zoned) {
@@ -80,19 +83,32 @@ pub fn prune_exhausted(jobs: &mut Vec<CronJob>, now: &Zoned) {
 
 #[must_use]
 pub fn earliest_pending(jobs: &[CronJob], now: &Zoned) -> Vec<(Zoned, ActionFn)> {
-    let mut pending: Vec<(Zoned, ActionFn)> = Vec::new();
+    earliest_pending_jobs(jobs, now)
+        .into_iter()
+        .map(|(run_time, job)| (run_time, Arc::clone(&job.action_fn)))
+        .collect()
+}
+
+/// [`earliest_pending`], keeping the jobs themselves for callers that need
+/// their id or schedule.
+#[must_use]
+pub fn earliest_pending_jobs<'a>(
+    jobs: &'a [CronJob],
+    now: &Zoned,
+) -> Vec<(Zoned, &'a CronJob)> {
+    let mut pending: Vec<(Zoned, &CronJob)> = Vec::new();
 
     for job in jobs {
-        let Some(run_time) = next_run(job, now) else {
+        let Some(run_time) = job.next_run(now) else {
             continue;
         };
 
         match pending.first().map(|(t, _)| run_time.cmp(t)) {
             Some(Ordering::Less) | None => {
-                pending = vec![(run_time, Arc::clone(&job.action_fn))];
+                pending = vec![(run_time, job)];
             },
             Some(Ordering::Equal) => {
-                pending.push((run_time, Arc::clone(&job.action_fn)));
+                pending.push((run_time, job));
             },
             Some(Ordering::Greater) => {},
         }
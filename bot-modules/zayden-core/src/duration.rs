@@ -0,0 +1,66 @@
+//! Durations as moderators type them in commands: `30m`, `12h`, `7d`, or runs
+//! of them like `1d12h`.
+
+use jiff::SignedDuration;
+
+const UNITS: [(i64, &str); 4] =
+    [(24 * 60 * 60, "day"), (60 * 60, "hour"), (60, "minute"), (1, "second")];
+
+/// Parses runs of a whole number and a unit: `s`, `m`, `h`, `d` or `w`.
+/// Whitespace is ignored. `None` for a bare number, any other unit, a zero
+/// total or one that overflows.
+#[must_use]
+pub fn parse_duration(input: &str) -> Option<SignedDuration> {
+    let mut total: i64 = 0;
+    let mut number: Option<i64> = None;
+
+    for c in input.chars().filter(|c| !c.is_whitespace()) {
+        if let Some(digit) = c.to_digit(10) {
+            number = Some(
+                number
+                    .unwrap_or_default()
+                    .checked_mul(10)?
+                    .checked_add(digit.into())?,
+            );
+            continue;
+        }
+
+        let unit: i64 = match c.to_ascii_lowercase() {
+            's' => 1,
+            'm' => 60,
+            'h' => 60 * 60,
+            'd' => 24 * 60 * 60,
+            'w' => 7 * 24 * 60 * 60,
+            _ => return None,
+        };
+
+        total = total.checked_add(number.take()?.checked_mul(unit)?)?;
+    }
+
+    if number.is_some() || total == 0 {
+        return None;
+    }
+
+    Some(SignedDuration::from_secs(total))
+}
+
+/// Spells a duration out in days, hours, minutes and seconds, skipping the
+/// units that are zero: `1 day 30 minutes`.
+#[must_use]
+pub fn format_duration(duration: SignedDuration) -> String {
+    let mut secs = duration.as_secs().max(0);
+    let mut parts = Vec::new();
+
+    for (size, name) in UNITS {
+        let count = secs / size;
+        if count > 0 {
+            parts.push(format!(
+                "{count} {name}{}",
+                if count == 1 { "" } else { "s" }
+            ));
+            secs %= size;
+        }
+    }
+
+    if parts.is_empty() { "0 seconds".to_string() } else { parts.join(" ") }
+}
@@ -22,7 +22,14 @@ pub mod cdn;
 pub use cdn::{is_discord_cdn, refresh_attachment_url};
 
 pub mod cron;
-pub use cron::{ActionFn, CronJob, CronJobData, earliest_pending, prune_exhausted};
+pub use cron::{
+    ActionFn,
+    CronJob,
+    CronJobData,
+    earliest_pending,
+    earliest_pending_jobs,
+    prune_exhausted,
+};
 
 pub mod dispatch_map;
 pub use dispatch_map::{DispatchMap, OverlapError};
@@ -33,6 +40,7 @@ pub use modals::{parse_modal_components, parse_text_components};
 pub mod retry;
 pub use retry::{
     RetryBudget,
+    is_not_found,
     is_transient,
     retry,
     retry_transient,
@@ -47,12 +55,18 @@ pub use error::{CoreError, HandlerError, Respond};
 pub mod format_num;
 pub use format_num::FormatNum;
 
+pub mod duration;
+pub use duration::{format_duration, parse_duration};
+
 pub mod scope;
 pub use scope::{CommandMetadata, CommandScope, IdMatch};
 
 pub mod tier;
 pub use tier::{guild_owner, invalidate_guild_owner, server_tier};
 
+pub mod tool;
+pub use tool::{AiTool, ToolArgs, ToolError, ToolParam};
+
 pub mod snowflake;
 pub use snowflake::{as_i64, as_u64};
 
@@ -35,6 +35,19 @@ pub fn is_transient(error: &serenity::Error) -> bool {
     matches!(http, HttpError::Request(_))
 }
 
+/// Whether Discord answered 404: the member, role, ban or channel is already
+/// gone.
+#[must_use]
+pub fn is_not_found(error: &serenity::Error) -> bool {
+    matches!(
+        error,
+        serenity::Error::Http(HttpError::UnsuccessfulRequest(ErrorResponse {
+            status_code,
+            ..
+        })) if *status_code == StatusCode::NOT_FOUND
+    )
+}
+
 pub async fn retry<T, E, F, Fut>(
     budget: RetryBudget,
     is_retryable: impl Fn(&E) -> bool,
@@ -0,0 +1,136 @@
+//! Lookups a module offers the AI chat. A tool only reads: the model calls it
+//! mid-conversation to answer from real data, and whatever it returns is
+//! handed straight back to the model as text.
+
+use std::fmt;
+
+use async_trait::async_trait;
+use serde_json::{Map, Value, json};
+
+/// One argument of a tool. Every argument is a string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ToolParam {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub required: bool,
+}
+
+impl ToolParam {
+    #[must_use]
+    pub const fn required(name: &'static str, description: &'static str) -> Self {
+        Self { name, description, required: true }
+    }
+
+    #[must_use]
+    pub const fn optional(name: &'static str, description: &'static str) -> Self {
+        Self { name, description, required: false }
+    }
+}
+
+#[async_trait]
+pub trait AiTool: Send + Sync {
+    /// Unique among the tools offered together; `snake_case`, prefixed with
+    /// the module.
+    fn name(&self) -> &'static str;
+
+    /// What the tool answers, written for the model deciding whether to call
+    /// it.
+    fn description(&self) -> &'static str;
+
+    fn params(&self) -> &'static [ToolParam] {
+        &[]
+    }
+
+    async fn call(&self, args: &ToolArgs) -> Result<String, ToolError>;
+}
+
+/// The JSON schema the model is given for a tool's arguments.
+#[must_use]
+pub fn schema(params: &[ToolParam]) -> Value {
+    let properties: Map<String, Value> = params
+        .iter()
+        .map(|param| {
+            (
+                param.name.to_string(),
+                json!({ "type": "string", "description": param.description }),
+            )
+        })
+        .collect();
+
+    let required: Vec<&str> = params
+        .iter()
+        .filter(|param| param.required)
+        .map(|param| param.name)
+        .collect();
+
+    json!({
+        "type": "object",
+        "properties": properties,
+        "required": required,
+    })
+}
+
+/// The arguments a model passed, as the JSON object text it sent.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ToolArgs(Map<String, Value>);
+
+impl ToolArgs {
+    /// Models sometimes send nothing at all for a tool without arguments, so
+    /// blank input is an empty object.
+    pub fn parse(raw: &str) -> Result<Self, ToolError> {
+        if raw.trim().is_empty() {
+            return Ok(Self::default());
+        }
+
+        match serde_json::from_str(raw) {
+            Ok(Value::Object(map)) => Ok(Self(map)),
+            Ok(_) | Err(_) => Err(ToolError::InvalidArguments(format!(
+                "expected a JSON object, got `{raw}`"
+            ))),
+        }
+    }
+
+    /// A string argument, trimmed. Blank counts as missing.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.0
+            .get(name)
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+    }
+
+    pub fn required(&self, name: &str) -> Result<&str, ToolError> {
+        self.get(name).ok_or_else(|| {
+            ToolError::InvalidArguments(format!("`{name}` is required"))
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ToolError {
+    InvalidArguments(String),
+    NotFound(String),
+    Failed(String),
+}
+
+impl ToolError {
+    #[must_use]
+    pub fn failed(e: impl fmt::Display) -> Self {
+        Self::Failed(e.to_string())
+    }
+}
+
+impl fmt::Display for ToolError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidArguments(reason) => {
+                write!(f, "invalid arguments: {reason}")
+            },
+            Self::NotFound(what) => write!(f, "not found: {what}"),
+            Self::Failed(reason) => write!(f, "lookup failed: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for ToolError {}
@@ -21,7 +21,12 @@ use std::str::FromStr;
 use jiff::civil::date;
 use jiff::tz::TimeZone;
 use jiff_cron::Schedule;
-use zayden_core::{CronJob, earliest_pending, prune_exhausted};
+use zayden_core::{
+    CronJob,
+    earliest_pending,
+    earliest_pending_jobs,
+    prune_exhausted,
+};
 
 /// `"0 0 17 * * Fri *"` — gambling's `lotto` / `higherlower` schedule.
 const FRIDAY_1700: &str = "0 0 17 * * Fri *";
@@ -216,3 +221,32 @@ fn a_recurring_job_survives_the_wrong_weekday_window() {
 
     assert_eq!(jobs.len(), 3, "no weekday-restricted job may be pruned");
 }
+
+/// The admin console records each run's id and next run time, so the job-level
+/// selection must agree with `earliest_pending` and hand back the jobs.
+#[test]
+fn pending_jobs_keep_their_ids_and_next_run() {
+    let jobs = [
+        job!("stamina", EVERY_10_MIN),
+        job!("lotto", FRIDAY_1700),
+        job!("higherlower", FRIDAY_1700),
+    ];
+    let friday = utc!(2026, 8, 7, 16, 59, 0);
+
+    let pending = earliest_pending_jobs(&jobs, &friday);
+
+    let ids = pending.iter().map(|(_, job)| job.id.as_str()).collect::<Vec<_>>();
+    assert_eq!(ids, ["stamina", "lotto", "higherlower"]);
+    assert_eq!(pending.len(), earliest_pending(&jobs, &friday).len());
+
+    let (run_time, stamina) = &pending[0];
+    assert_eq!(stamina.next_run(run_time), Some(utc!(2026, 8, 7, 17, 10, 0)));
+}
+
+/// A fired one-shot has no next run to report.
+#[test]
+fn a_fired_one_shot_has_no_next_run() {
+    let job = job!("lfg_1", FIRED_ONE_SHOT);
+
+    assert_eq!(job.next_run(&utc!(2026, 8, 6, 12, 0, 0)), None);
+}
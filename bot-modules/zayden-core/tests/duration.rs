@@ -0,0 +1,47 @@
+//! Parsing and spelling out the durations moderators pass to timed commands.
+
+use jiff::SignedDuration;
+use zayden_core::{format_duration, parse_duration};
+
+#[test]
+fn parses_single_units() {
+    assert_eq!(parse_duration("45s"), Some(SignedDuration::from_secs(45)));
+    assert_eq!(parse_duration("30m"), Some(SignedDuration::from_mins(30)));
+    assert_eq!(parse_duration("12H"), Some(SignedDuration::from_hours(12)));
+    assert_eq!(parse_duration("7d"), Some(SignedDuration::from_hours(7 * 24)));
+    assert_eq!(parse_duration("2w"), Some(SignedDuration::from_hours(14 * 24)));
+}
+
+#[test]
+fn parses_runs_of_units() {
+    assert_eq!(parse_duration("1d12h"), Some(SignedDuration::from_hours(36)));
+    assert_eq!(parse_duration(" 1h 30m "), Some(SignedDuration::from_mins(90)));
+}
+
+#[test]
+fn rejects_ambiguous_or_empty_input() {
+    assert_eq!(parse_duration(""), None);
+    assert_eq!(parse_duration("30"), None);
+    assert_eq!(parse_duration("1h30"), None);
+    assert_eq!(parse_duration("h"), None);
+    assert_eq!(parse_duration("0m"), None);
+    assert_eq!(parse_duration("3y"), None);
+    assert_eq!(parse_duration("-5m"), None);
+}
+
+#[test]
+fn rejects_overflow() {
+    assert_eq!(parse_duration("99999999999999999999w"), None);
+    assert_eq!(parse_duration("9223372036854775807w"), None);
+}
+
+#[test]
+fn formats_non_zero_units() {
+    assert_eq!(format_duration(SignedDuration::from_mins(90)), "1 hour 30 minutes");
+    assert_eq!(format_duration(SignedDuration::from_hours(48)), "2 days");
+    assert_eq!(
+        format_duration(SignedDuration::from_secs(24 * 60 * 60 + 1)),
+        "1 day 1 second"
+    );
+    assert_eq!(format_duration(SignedDuration::ZERO), "0 seconds");
+}
@@ -0,0 +1,64 @@
+//! Reading the arguments a model passes to an AI tool, and describing them
+//! to it.
+
+use serde_json::json;
+use zayden_core::tool::schema;
+use zayden_core::{ToolArgs, ToolError, ToolParam};
+
+#[test]
+fn blank_arguments_are_an_empty_object() {
+    assert_eq!(ToolArgs::parse(""), Ok(ToolArgs::default()));
+    assert_eq!(ToolArgs::parse("  "), Ok(ToolArgs::default()));
+}
+
+#[test]
+fn reads_trimmed_string_arguments() {
+    let args = ToolArgs::parse(r#"{"name": " Lamball ", "blank": " "}"#)
+        .expect("an object parses");
+
+    assert_eq!(args.get("name"), Some("Lamball"));
+    assert_eq!(args.get("blank"), None);
+    assert_eq!(args.get("missing"), None);
+}
+
+#[test]
+fn a_missing_required_argument_is_an_error() {
+    let args = ToolArgs::parse("{}").expect("an object parses");
+
+    assert!(matches!(args.required("name"), Err(ToolError::InvalidArguments(_))));
+}
+
+#[test]
+fn anything_but_an_object_is_rejected() {
+    assert!(matches!(
+        ToolArgs::parse("[1, 2]"),
+        Err(ToolError::InvalidArguments(_))
+    ));
+    assert!(matches!(
+        ToolArgs::parse("not json"),
+        Err(ToolError::InvalidArguments(_))
+    ));
+}
+
+#[test]
+fn schema_lists_every_param_and_only_the_required_ones_as_required() {
+    let params = [
+        ToolParam::required("name", "The pal's name"),
+        ToolParam::optional("target", "The child to breed for"),
+    ];
+
+    assert_eq!(
+        schema(&params),
+        json!({
+            "type": "object",
+            "properties": {
+                "name": { "type": "string", "description": "The pal's name" },
+                "target": {
+                    "type": "string",
+                    "description": "The child to breed for",
+                },
+            },
+            "required": ["name"],
+        })
+    );
+}
@@ -0,0 +1,170 @@
+use std::fmt::Write as _;
+
+use crate::renderer::{Canvas, RasterLimits};
+
+pub const CAPTCHA_CANVAS: Canvas = Canvas { width: 320, height: 120 };
+pub const CAPTCHA_LIMITS: RasterLimits = RasterLimits {
+    max_pixels: CAPTCHA_CANVAS.width * CAPTCHA_CANVAS.height,
+    max_dim: CAPTCHA_CANVAS.width,
+};
+
+pub const CAPTCHA_LENGTH: usize = 6;
+
+/// Letters and digits that stay distinct once distorted: no `0`/`O`,
+/// `1`/`I`/`L` or `2`/`Z`.
+pub const CAPTCHA_ALPHABET: &str = "ABCDEFGHJKMNPQRSTUVWXY3456789";
+
+const GLYPH_STEP: f64 = 46.0;
+const GLYPH_LEFT: f64 = 32.0;
+const NOISE_CURVES: usize = 7;
+const NOISE_DOTS: usize = 90;
+
+/// A small deterministic generator, so a challenge can be reproduced from its
+/// seed in tests. SplitMix64.
+struct Noise(u64);
+
+impl Noise {
+    const fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        let n = u64::try_from(n).unwrap_or(u64::MAX).max(1);
+        usize::try_from(self.next() % n).unwrap_or(0)
+    }
+
+    /// A value in `lo..hi`.
+    fn range(&mut self, lo: f64, hi: f64) -> f64 {
+        // 21 bits are plenty for pixel offsets and colours.
+        let unit = f64::from(u32::try_from(self.next() >> 43).unwrap_or(0))
+            / f64::from(1u32 << 21);
+        (hi - lo).mul_add(unit, lo)
+    }
+
+    fn shade(&mut self, lo: u8, hi: u8) -> String {
+        let mut channel = || {
+            let span = usize::from(hi.saturating_sub(lo)) + 1;
+            lo.saturating_add(u8::try_from(self.below(span)).unwrap_or(0))
+        };
+        format!("#{:02x}{:02x}{:02x}", channel(), channel(), channel())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Captcha {
+    pub code: String,
+    pub markup: String,
+    pub canvas: Canvas,
+}
+
+/// A fresh challenge: a random code drawn as rotated, jittered glyphs under a
+/// turbulence warp, with curves and speckle over and under the text.
+#[must_use]
+pub fn generate(seed: u64) -> Captcha {
+    let mut noise = Noise(seed);
+    let alphabet = CAPTCHA_ALPHABET.chars().collect::<Vec<_>>();
+
+    let code = (0..CAPTCHA_LENGTH)
+        .filter_map(|_| alphabet.get(noise.below(alphabet.len())).copied())
+        .collect::<String>();
+
+    let markup = render(&code, &mut noise);
+
+    Captcha { code, markup, canvas: CAPTCHA_CANVAS }
+}
+
+fn curve(markup: &mut String, noise: &mut Noise, width: f64, colour: &str) {
+    let Canvas { width: w, height: h } = CAPTCHA_CANVAS;
+    let (w, h) = (f64::from(w), f64::from(h));
+
+    let _ = write!(
+        markup,
+        r#"<path d="M{:.1} {:.1} C{:.1} {:.1} {:.1} {:.1} {:.1} {:.1}" fill="none" stroke="{colour}" stroke-width="{width:.1}" stroke-linecap="round"/>"#,
+        noise.range(-10.0, w * 0.2),
+        noise.range(0.0, h),
+        noise.range(w * 0.2, w * 0.5),
+        noise.range(-h * 0.3, h * 1.3),
+        noise.range(w * 0.5, w * 0.8),
+        noise.range(-h * 0.3, h * 1.3),
+        noise.range(w * 0.8, w + 10.0),
+        noise.range(0.0, h),
+    );
+}
+
+fn render(code: &str, noise: &mut Noise) -> String {
+    let Canvas { width, height } = CAPTCHA_CANVAS;
+
+    let mut markup = String::with_capacity(4096);
+    let _ = write!(
+        markup,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}">"#,
+    );
+    let _ = write!(
+        markup,
+        r#"<defs><filter id="warp" x="-10%" y="-10%" width="120%" height="120%"><feTurbulence type="fractalNoise" baseFrequency="{:.3} {:.3}" numOctaves="2" seed="{}"/><feDisplacementMap in="SourceGraphic" scale="{:.1}" xChannelSelector="R" yChannelSelector="G"/></filter></defs>"#,
+        noise.range(0.012, 0.022),
+        noise.range(0.03, 0.06),
+        noise.below(10_000),
+        noise.range(10.0, 16.0),
+    );
+    let _ = write!(
+        markup,
+        r#"<rect width="{width}" height="{height}" fill="{}"/>"#,
+        noise.shade(0xe4, 0xf4),
+    );
+
+    for _ in 0..NOISE_CURVES {
+        let colour = noise.shade(0x90, 0xc8);
+        let width = noise.range(1.0, 2.5);
+        curve(&mut markup, noise, width, &colour);
+    }
+
+    for _ in 0..NOISE_DOTS {
+        let _ = write!(
+            markup,
+            r#"<circle cx="{:.1}" cy="{:.1}" r="{:.1}" fill="{}"/>"#,
+            noise.range(0.0, f64::from(width)),
+            noise.range(0.0, f64::from(height)),
+            noise.range(0.6, 2.0),
+            noise.shade(0x40, 0xb0),
+        );
+    }
+
+    markup.push_str(r#"<g filter="url(#warp)">"#);
+
+    let mut x = GLYPH_LEFT;
+    for glyph in code.chars() {
+        let y = noise.range(70.0, 90.0);
+        let _ = write!(
+            markup,
+            r#"<text x="{x:.1}" y="{y:.1}" font-size="{:.0}" font-weight="bold" text-anchor="middle" fill="{}" transform="rotate({:.1} {x:.1} {y:.1}) skewX({:.1})">{glyph}</text>"#,
+            noise.range(42.0, 54.0),
+            noise.shade(0x10, 0x50),
+            noise.range(-28.0, 28.0),
+            noise.range(-8.0, 8.0),
+        );
+        x += GLYPH_STEP + noise.range(-6.0, 6.0);
+    }
+
+    // A stroke through the text, in the text's own weight, so it can't be
+    // filtered out by thickness.
+    let strike = noise.shade(0x10, 0x50);
+    curve(&mut markup, noise, 3.5, &strike);
+
+    markup.push_str("</g></svg>");
+
+    markup
+}
+
+/// Whether `given` is `code`, ignoring case and whitespace.
+#[must_use]
+pub fn matches(code: &str, given: &str) -> bool {
+    let given =
+        given.chars().filter(|c| !c.is_whitespace()).flat_map(char::to_uppercase);
+
+    !code.is_empty() && code.chars().eq(given)
+}
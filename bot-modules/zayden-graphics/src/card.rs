@@ -0,0 +1,294 @@
+use std::fmt::Write as _;
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+use crate::renderer::{Canvas, RasterLimits};
+
+pub const CARD_CANVAS: Canvas = Canvas { width: 800, height: 250 };
+pub const CARD_LIMITS: RasterLimits = RasterLimits {
+    max_pixels: CARD_CANVAS.width * CARD_CANVAS.height,
+    max_dim: CARD_CANVAS.width,
+};
+
+pub const CARD_AVATAR_PX: u32 = 160;
+pub const DEFAULT_ACCENT: u32 = 0x0058_65f2;
+
+const AVATAR_X: i32 = 40;
+const AVATAR_Y: i32 = 45;
+const CONTENT_X: u32 = 230;
+const CONTENT_RIGHT: u32 = 760;
+const STAT_COLUMN: u32 = 180;
+const MAX_NAME_CHARS: usize = 22;
+const MAX_STATS: usize = 3;
+
+const COLOUR_TEXT: &str = "#f2f3f5";
+const COLOUR_MUTED: &str = "#b5bac1";
+const COLOUR_TRACK: &str = "#1e1f22";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CardBackground {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub top: &'static str,
+    pub bottom: &'static str,
+}
+
+pub const DEFAULT_BACKGROUND: CardBackground = CardBackground {
+    id: "default",
+    name: "Midnight",
+    top: "#313338",
+    bottom: "#1e1f22",
+};
+
+pub const CARD_BACKGROUNDS: [CardBackground; 5] = [
+    DEFAULT_BACKGROUND,
+    CardBackground {
+        id: "sunset",
+        name: "Sunset",
+        top: "#f7797d",
+        bottom: "#4a1942",
+    },
+    CardBackground { id: "ocean", name: "Ocean", top: "#2193b0", bottom: "#0b2a4a" },
+    CardBackground {
+        id: "forest",
+        name: "Forest",
+        top: "#3a7d44",
+        bottom: "#0f2417",
+    },
+    CardBackground {
+        id: "aurora",
+        name: "Aurora",
+        top: "#00c9a7",
+        bottom: "#3d1a78",
+    },
+];
+
+#[must_use]
+pub fn background(id: &str) -> CardBackground {
+    CARD_BACKGROUNDS
+        .iter()
+        .find(|background| background.id == id)
+        .copied()
+        .unwrap_or(DEFAULT_BACKGROUND)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CardTheme<'a> {
+    pub background: &'a str,
+    pub accent: u32,
+}
+
+impl Default for CardTheme<'_> {
+    fn default() -> Self {
+        Self { background: DEFAULT_BACKGROUND.id, accent: DEFAULT_ACCENT }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CardStat {
+    pub label: String,
+    pub value: String,
+}
+
+impl CardStat {
+    #[must_use]
+    pub fn new(label: impl Into<String>, value: impl Into<String>) -> Self {
+        Self { label: label.into(), value: value.into() }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct CardData {
+    pub name: String,
+    pub subtitle: String,
+    pub level: i32,
+    pub xp: i32,
+    pub xp_needed: i32,
+    pub rank: Option<i64>,
+    pub stats: Vec<CardStat>,
+}
+
+impl CardData {
+    /// Identifies everything that ends up in the image, so a cached PNG can be
+    /// reused until any of it changes. Only stable within one process.
+    #[must_use]
+    pub fn fingerprint(&self, theme: CardTheme<'_>, avatar: Option<&str>) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.hash(&mut hasher);
+        theme.hash(&mut hasher);
+        avatar.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    #[must_use]
+    pub fn progress(&self) -> f64 {
+        if self.xp_needed <= 0 {
+            return 0.0;
+        }
+
+        (f64::from(self.xp) / f64::from(self.xp_needed)).clamp(0.0, 1.0)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CardAvatarSlot {
+    pub x: i32,
+    pub y: i32,
+    pub size: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CardSvg {
+    pub markup: String,
+    pub canvas: Canvas,
+    pub avatar: CardAvatarSlot,
+}
+
+#[must_use]
+pub fn render(data: &CardData, theme: CardTheme<'_>) -> CardSvg {
+    let Canvas { width, height } = CARD_CANVAS;
+    let bg = background(theme.background);
+    let accent = format!("#{:06x}", theme.accent & 0x00ff_ffff);
+
+    let mut markup = String::with_capacity(2048);
+    let _ = write!(
+        markup,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}">"#,
+    );
+    let _ = write!(
+        markup,
+        r#"<defs><linearGradient id="bg" x1="0" y1="0" x2="1" y2="1"><stop offset="0" stop-color="{}"/><stop offset="1" stop-color="{}"/></linearGradient></defs>"#,
+        bg.top, bg.bottom,
+    );
+    let _ = write!(
+        markup,
+        r#"<rect width="{width}" height="{height}" rx="24" fill="url(#bg)"/>"#,
+    );
+    let _ = write!(
+        markup,
+        r#"<rect x="0" y="0" width="10" height="{height}" fill="{accent}"/>"#,
+    );
+
+    let avatar = CardAvatarSlot { x: AVATAR_X, y: AVATAR_Y, size: CARD_AVATAR_PX };
+    let radius = CARD_AVATAR_PX / 2;
+    let _ = write!(
+        markup,
+        r#"<circle cx="{}" cy="{}" r="{}" fill="{COLOUR_TRACK}" stroke="{accent}" stroke-width="6"/>"#,
+        i64::from(AVATAR_X) + i64::from(radius),
+        i64::from(AVATAR_Y) + i64::from(radius),
+        radius + 3,
+    );
+
+    let _ = write!(
+        markup,
+        r#"<text x="{CONTENT_X}" y="75" font-size="36" font-weight="bold" fill="{COLOUR_TEXT}">{}</text>"#,
+        escape_xml(&clean_name(&data.name)),
+    );
+    let _ = write!(
+        markup,
+        r#"<text x="{CONTENT_X}" y="105" font-size="18" fill="{COLOUR_MUTED}">{}</text>"#,
+        escape_xml(&data.subtitle),
+    );
+
+    let rank = data.rank.map_or_else(
+        || String::from("Unranked"),
+        |rank| format!("#{}", group_digits(rank)),
+    );
+    let _ = write!(
+        markup,
+        r#"<text x="{CONTENT_RIGHT}" y="75" font-size="36" font-weight="bold" text-anchor="end" fill="{accent}">{rank}</text>"#,
+    );
+
+    let mut x = CONTENT_X;
+    for stat in data.stats.iter().take(MAX_STATS) {
+        let _ = write!(
+            markup,
+            r#"<text x="{x}" y="135" font-size="14" fill="{COLOUR_MUTED}">{}</text><text x="{x}" y="160" font-size="22" fill="{COLOUR_TEXT}">{}</text>"#,
+            escape_xml(&stat.label),
+            escape_xml(&stat.value),
+        );
+        x += STAT_COLUMN;
+    }
+
+    let _ = write!(
+        markup,
+        r#"<text x="{CONTENT_X}" y="192" font-size="18" fill="{COLOUR_TEXT}">Level {}</text>"#,
+        group_digits(i64::from(data.level)),
+    );
+    let _ = write!(
+        markup,
+        r#"<text x="{CONTENT_RIGHT}" y="192" font-size="16" text-anchor="end" fill="{COLOUR_MUTED}">{} / {} XP</text>"#,
+        group_digits(i64::from(data.xp)),
+        group_digits(i64::from(data.xp_needed)),
+    );
+
+    let track = f64::from(CONTENT_RIGHT - CONTENT_X);
+    let _ = write!(
+        markup,
+        r#"<rect x="{CONTENT_X}" y="202" width="{track}" height="24" rx="12" fill="{COLOUR_TRACK}"/>"#,
+    );
+    let filled = track * data.progress();
+    if filled > 0.0 {
+        let _ = write!(
+            markup,
+            r#"<rect x="{CONTENT_X}" y="202" width="{:.2}" height="24" rx="12" fill="{accent}"/>"#,
+            filled.max(24.0),
+        );
+    }
+
+    markup.push_str("</svg>");
+
+    CardSvg { markup, canvas: CARD_CANVAS, avatar }
+}
+
+fn clean_name(name: &str) -> String {
+    let cleaned = name
+        .chars()
+        .filter(|c| !c.is_control())
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    if cleaned.chars().count() > MAX_NAME_CHARS {
+        let kept: String =
+            cleaned.chars().take(MAX_NAME_CHARS.saturating_sub(1)).collect();
+        format!("{}\u{2026}", kept.trim_end())
+    } else {
+        cleaned
+    }
+}
+
+fn escape_xml(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+
+    for c in value.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            other => out.push(other),
+        }
+    }
+
+    out
+}
+
+fn group_digits(value: i64) -> String {
+    let digits = value.unsigned_abs().to_string();
+    let mut out = String::with_capacity(digits.len() + digits.len() / 3 + 1);
+
+    if value < 0 {
+        out.push('-');
+    }
+
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            out.push(',');
+        }
+        out.push(c);
+    }
+
+    out
+}
@@ -1,8 +1,12 @@
+pub mod captcha;
+pub mod card;
 pub mod error;
 pub mod fonts;
 pub mod image;
 pub mod renderer;
 
+pub use captcha::Captcha;
+pub use card::{CardData, CardStat, CardTheme};
 pub use error::GraphicsError;
 pub use image::{AVATAR_MAX_BYTES, decode_avatar};
 pub use renderer::{Canvas, Overlay, RENDER_BUDGET_MP, RasterLimits, Renderer};
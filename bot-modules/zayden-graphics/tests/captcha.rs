@@ -0,0 +1,58 @@
+//! Coverage for the verify CAPTCHA image and answer check.
+//!
+//! As in `card.rs`, the renderer has an empty font database, so the glyphs
+//! draw nothing; what is checked is that the markup, filter included, is valid
+//! SVG of the advertised size.
+
+use std::sync::Arc;
+
+use zayden_graphics::captcha::{
+    CAPTCHA_ALPHABET,
+    CAPTCHA_CANVAS,
+    CAPTCHA_LENGTH,
+    CAPTCHA_LIMITS,
+    generate,
+    matches,
+};
+use zayden_graphics::renderer::Renderer;
+use zayden_graphics::usvg::fontdb;
+
+#[test]
+fn codes_use_the_unambiguous_alphabet() {
+    for seed in 0..64 {
+        let captcha = generate(seed);
+
+        assert_eq!(captcha.code.chars().count(), CAPTCHA_LENGTH);
+        assert!(captcha.code.chars().all(|c| CAPTCHA_ALPHABET.contains(c)));
+    }
+}
+
+#[test]
+fn a_seed_reproduces_its_challenge() {
+    assert_eq!(generate(7), generate(7));
+    assert_ne!(generate(7).code, generate(8).code);
+}
+
+#[tokio::test]
+async fn captcha_markup_rasterises_at_its_canvas() {
+    let captcha = generate(42);
+    assert_eq!(captcha.canvas, CAPTCHA_CANVAS);
+
+    let renderer =
+        Renderer::with_fonts(Arc::new(fontdb::Database::new()), "sans-serif".into());
+    let png = renderer
+        .render(captcha.markup, captcha.canvas, Vec::new(), CAPTCHA_LIMITS)
+        .await
+        .expect("the captcha should fit its own limits");
+
+    assert_eq!(png.get(..8), Some(&b"\x89PNG\r\n\x1a\n"[..]));
+}
+
+#[test]
+fn answers_ignore_case_and_spaces() {
+    assert!(matches("AB3CDE", "ab3cde"));
+    assert!(matches("AB3CDE", " AB3 CDE "));
+    assert!(!matches("AB3CDE", "AB3CD"));
+    assert!(!matches("AB3CDE", "AB3CDEF"));
+    assert!(!matches("", ""));
+}
@@ -0,0 +1,98 @@
+//! Coverage for the `/rank` and `/profile` card markup.
+//!
+//! Like `render.rs`, the renderer here has an empty font database: the text
+//! is parsed but draws nothing, which is enough to prove the markup is valid
+//! SVG of the advertised size.
+
+use std::sync::Arc;
+
+use zayden_graphics::card::{
+    CARD_CANVAS,
+    CARD_LIMITS,
+    DEFAULT_BACKGROUND,
+    background,
+    render,
+};
+use zayden_graphics::renderer::Renderer;
+use zayden_graphics::usvg::fontdb;
+use zayden_graphics::{CardData, CardStat, CardTheme};
+
+fn data() -> CardData {
+    CardData {
+        name: String::from("Oscar"),
+        subtitle: String::from("Server rank"),
+        level: 12,
+        xp: 300,
+        xp_needed: 1_076,
+        rank: Some(4),
+        stats: vec![CardStat::new("Coins", "1,000")],
+    }
+}
+
+#[tokio::test]
+async fn card_markup_rasterises_at_the_card_canvas() {
+    let svg = render(&data(), CardTheme::default());
+    assert_eq!(svg.canvas, CARD_CANVAS);
+
+    let renderer =
+        Renderer::with_fonts(Arc::new(fontdb::Database::new()), "sans-serif".into());
+    let png = renderer
+        .render(svg.markup, svg.canvas, Vec::new(), CARD_LIMITS)
+        .await
+        .expect("the card should fit its own limits");
+
+    assert_eq!(png.get(..8), Some(&b"\x89PNG\r\n\x1a\n"[..]));
+}
+
+#[test]
+fn names_are_escaped_and_truncated() {
+    let mut card = data();
+    card.name = String::from("<script>&\"a very long display name indeed\"");
+
+    let markup = render(&card, CardTheme::default()).markup;
+
+    assert!(!markup.contains("<script>"));
+    assert!(markup.contains("&lt;script&gt;&amp;"));
+    assert!(markup.contains('\u{2026}'));
+}
+
+#[test]
+fn unknown_background_falls_back_to_default() {
+    assert_eq!(background("does-not-exist"), DEFAULT_BACKGROUND);
+    assert_eq!(background("ocean").id, "ocean");
+}
+
+#[test]
+fn progress_is_clamped() {
+    let mut card = data();
+
+    card.xp = -5;
+    assert!(card.progress().abs() < f64::EPSILON);
+
+    card.xp = card.xp_needed * 2;
+    assert!((card.progress() - 1.0).abs() < f64::EPSILON);
+
+    card.xp_needed = 0;
+    assert!(card.progress().abs() < f64::EPSILON);
+}
+
+#[test]
+fn fingerprint_tracks_everything_drawn() {
+    let card = data();
+    let theme = CardTheme::default();
+    let base = card.fingerprint(theme, Some("avatar-a"));
+
+    assert_eq!(base, card.fingerprint(theme, Some("avatar-a")));
+    assert_ne!(base, card.fingerprint(theme, Some("avatar-b")));
+    assert_ne!(
+        base,
+        card.fingerprint(
+            CardTheme { accent: 0x00ff_0000, ..theme },
+            Some("avatar-a")
+        )
+    );
+
+    let mut richer = card;
+    richer.stats = vec![CardStat::new("Coins", "2,000")];
+    assert_ne!(base, richer.fingerprint(theme, Some("avatar-a")));
+}
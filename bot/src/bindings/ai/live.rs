@@ -0,0 +1,134 @@
+//! Showing a reply while the model is still writing it. The first page
+//! answers the message and later pages follow in the channel; pages are
+//! brought up to date at most once per [`EDIT_INTERVAL`], which keeps even a
+//! long reply well inside Discord's edit rate limit.
+
+use std::time::Duration;
+
+use ai::chat::strip_speaker_prefix;
+use ai::pager::{PageUpdate, Pager};
+use serenity::all::{Context, CreateMessage, EditMessage, Message, MessageId};
+use tokio::sync::mpsc::UnboundedReceiver;
+use tokio::time::{MissedTickBehavior, interval};
+
+use crate::Result;
+
+const EDIT_INTERVAL: Duration = Duration::from_millis(1500);
+
+/// Ends a reply that stopped partway, because the provider gave up on it or
+/// Discord stopped taking its pages, so it doesn't read as finished.
+const CUT_OFF: &str = "\n\n-# Reply cut off before it finished.";
+
+pub(super) struct LiveReply<'a> {
+    ctx: &'a Context,
+    message: &'a Message,
+    /// Names the model might open its text with, stripped before showing.
+    speakers: &'a [&'a str],
+    pager: Pager,
+    posted: Vec<MessageId>,
+    /// The text the posted pages last caught up with.
+    shown: String,
+}
+
+impl<'a> LiveReply<'a> {
+    pub(super) fn new(
+        ctx: &'a Context,
+        message: &'a Message,
+        speakers: &'a [&'a str],
+    ) -> Self {
+        Self {
+            ctx,
+            message,
+            speakers,
+            pager: Pager::new(),
+            posted: Vec::new(),
+            shown: String::new(),
+        }
+    }
+
+    /// Shows the text from `deltas` as it arrives, until the sender is
+    /// dropped. Returns all of it.
+    pub(super) async fn follow(
+        &mut self,
+        mut deltas: UnboundedReceiver<String>,
+    ) -> Result<String> {
+        let mut text = String::new();
+        let mut unshown = false;
+
+        let mut ticks = interval(EDIT_INTERVAL);
+        ticks.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            tokio::select! {
+                delta = deltas.recv() => match delta {
+                    Some(delta) => {
+                        text.push_str(&delta);
+                        unshown = true;
+                    },
+                    None => return Ok(text),
+                },
+                _ = ticks.tick(), if unshown => {
+                    self.show(&text).await?;
+                    unshown = false;
+                },
+            }
+        }
+    }
+
+    /// Brings the posted pages up to `text`.
+    pub(super) async fn show(&mut self, text: &str) -> Result<()> {
+        let full = text;
+        let text = strip_speaker_prefix(text, self.speakers);
+        let channel = self.message.channel_id;
+
+        for update in self.pager.update(text) {
+            match update {
+                PageUpdate::Send(content) => {
+                    let posted = if self.posted.is_empty() {
+                        self.message.reply(&self.ctx.http, content).await?
+                    } else {
+                        channel
+                            .send_message(
+                                &self.ctx.http,
+                                CreateMessage::new().content(content),
+                            )
+                            .await?
+                    };
+                    self.posted.push(posted.id);
+                },
+                PageUpdate::Edit(index, content) => {
+                    let Some(&id) = self.posted.get(index) else {
+                        continue;
+                    };
+                    channel
+                        .edit_message(
+                            &self.ctx.http,
+                            id,
+                            EditMessage::new().content(content),
+                        )
+                        .await?;
+                },
+            }
+        }
+
+        full.clone_into(&mut self.shown);
+        Ok(())
+    }
+
+    /// Marks `text`, as far as it got, as cut off. Nothing is posted if none
+    /// of it was shown yet.
+    pub(super) async fn cut_off(&mut self, text: &str) -> Result<()> {
+        if self.pager.is_empty() {
+            return Ok(());
+        }
+
+        self.show(&format!("{text}{CUT_OFF}")).await
+    }
+
+    /// Marks what was last shown as cut off, for when Discord stopped taking
+    /// the pages before the whole reply was up.
+    pub(super) async fn cut_short(&mut self) -> Result<()> {
+        let shown = std::mem::take(&mut self.shown);
+        self.cut_off(&shown).await
+    }
+}
@@ -1,14 +1,22 @@
+mod live;
+mod tools;
+
 use std::sync::OnceLock;
 
-use ai::chat::{Message as ChatMessage, Role, strip_speaker_prefix};
+use ai::chat::{Message as ChatMessage, Role, Usage};
 use ai::openai::AiClient;
-use ai::persona::Persona;
+use ai::persona::{Persona, custom_system_prompt};
+use ai::tools::ToolRegistry;
 use serenity::all::{Context, CurrentUser, Message, UserId};
-use tracing::debug;
+use tokio::sync::mpsc;
+use tracing::{debug, error};
+use zayden_app::config::{AiSettingsRow, AiTrigger, CustomPersona};
 use zayden_app::entitlement::Tier;
+use zayden_app::services::ai_usage::{self, AiQuota, QuotaLimit};
 use zayden_app::state::AppState;
 use zayden_core::{as_i64, server_tier};
 
+use self::live::LiveReply;
 use crate::{BotError, Result};
 
 static IDENTITY: OnceLock<Identity> = OnceLock::new();
@@ -101,12 +109,12 @@ impl Ai {
         }
     }
 
-    fn speakers(
-        persona: Persona,
-        message: &Message,
+    fn speakers<'a>(
+        name: &'a str,
+        message: &'a Message,
         me: Option<UserId>,
-    ) -> Vec<&str> {
-        let mut names = vec![persona.name()];
+    ) -> Vec<&'a str> {
+        let mut names = vec![name];
         let mut next = Some(message);
 
         while let Some(message) = next {
@@ -142,21 +150,32 @@ impl Ai {
         parsed_content
     }
 
+    /// Streams a reply to `message`. Returns what it was billed even when it
+    /// fails partway, so those tokens still count.
     async fn reply(
         ctx: &Context,
         message: &Message,
         api_key: &str,
         endpoint: &str,
         params: &ChatParams<'_>,
-    ) -> Result<()> {
+        tools: &ToolRegistry,
+        custom: Option<&CustomPersona>,
+    ) -> (Usage, Result<()>) {
         let identity = Self::identity();
         let persona = identity.map_or_else(Persona::default, |id| id.persona);
         let me = identity.map(|id| id.user_id);
 
-        let mut messages = vec![ChatMessage::new(
-            Role::System,
-            persona.system_prompt(params.word_limit),
-        )];
+        let system_prompt = match custom {
+            Some(custom) => custom_system_prompt(
+                custom,
+                custom.word_limit.min(params.word_limit),
+                !tools.is_empty(),
+            ),
+            None if tools.is_empty() => persona.system_prompt(params.word_limit),
+            None => persona.system_prompt_with_lookups(params.word_limit),
+        };
+
+        let mut messages = vec![ChatMessage::new(Role::System, system_prompt)];
 
         let mut history = Self::process_referenced_messages(message, me);
         history.reverse();
@@ -169,14 +188,112 @@ impl Ai {
             Self::attributed_content(message, me),
         ));
 
-        let client =
-            AiClient::new(api_key, endpoint, params.model).map_err(BotError::Ai)?;
-        let text = client.chat(messages, params.max_tokens).await?;
+        let mut client = match AiClient::new(api_key, endpoint, params.model) {
+            Ok(client) => client,
+            Err(e) => return (Usage::default(), Err(BotError::Ai(e))),
+        };
+        if let Some(custom) = custom {
+            client = client.with_temperature(custom.temperature);
+        }
 
-        let speakers = Self::speakers(persona, message, me);
+        let name = custom.map_or_else(|| persona.name(), |c| c.name.as_str());
+        let speakers = Self::speakers(name, message, me);
+        let mut live = LiveReply::new(ctx, message, &speakers);
+
+        let (deltas, received) = mpsc::unbounded_channel();
+        let (reply, followed) = tokio::join!(
+            client.stream_with_tools(
+                messages,
+                params.max_tokens,
+                tools,
+                move |delta| {
+                    let _ = deltas.send(delta.to_owned());
+                },
+            ),
+            live.follow(received),
+        );
 
-        message.reply(&ctx.http, strip_speaker_prefix(&text, &speakers)).await?;
-        Ok(())
+        match reply {
+            Ok(reply) => {
+                let shown = match followed {
+                    Ok(_) => live.show(&reply.text).await,
+                    Err(e) => {
+                        if let Err(mark) = live.cut_short().await {
+                            error!(error = ?mark, "failed to mark a cut-off AI reply");
+                        }
+                        Err(e)
+                    },
+                };
+                (reply.usage, shown)
+            },
+            Err(e) => {
+                let marked = match followed {
+                    Ok(text) => live.cut_off(&text).await,
+                    Err(_) => live.cut_short().await,
+                };
+                if let Err(mark) = marked {
+                    error!(error = ?mark, "failed to mark a cut-off AI reply");
+                }
+                (e.usage, Err(e.error.into()))
+            },
+        }
+    }
+
+    /// Whether `message` calls for a reply under the guild's trigger mode. A
+    /// mention always does.
+    fn triggered(
+        message: &Message,
+        settings: &AiSettingsRow,
+        mentioned: bool,
+    ) -> bool {
+        if mentioned {
+            return true;
+        }
+
+        match settings.trigger() {
+            AiTrigger::Mention => false,
+            AiTrigger::Reply => {
+                let me = Self::identity().map(|id| id.user_id);
+                message
+                    .referenced_message
+                    .as_deref()
+                    .is_some_and(|replied| Self::is_own(replied, me))
+            },
+            AiTrigger::Keyword => settings.matches_keyword(&message.content),
+            AiTrigger::Channel => settings.channel_id.is_some(),
+        }
+    }
+
+    fn quota_notice(limit: QuotaLimit, server_tier: Tier) -> String {
+        let notice = match limit {
+            QuotaLimit::UserDaily => {
+                "You've used up your chat allowance for today. It resets at \
+                 midnight UTC."
+            },
+            QuotaLimit::GuildDaily => {
+                "This server has used up today's chat allowance. It resets at \
+                 midnight UTC."
+            },
+            QuotaLimit::GuildMonthly => {
+                "This server has used up this month's chat allowance. It resets \
+                 on the 1st, UTC."
+            },
+        };
+
+        // A member's own allowance follows their own plan, not the server's.
+        if server_tier != Tier::Free || limit == QuotaLimit::UserDaily {
+            return notice.to_string();
+        }
+
+        let free = AiQuota::for_tier(Tier::Free);
+        let pro = AiQuota::for_tier(Tier::Pro);
+        let times = if limit == QuotaLimit::GuildDaily {
+            pro.guild_daily / free.guild_daily
+        } else {
+            pro.guild_monthly / free.guild_monthly
+        };
+
+        format!("{notice} Pro servers get about {times} times as much.")
     }
 
     pub async fn run(
@@ -189,7 +306,8 @@ impl Ai {
             return Ok(());
         };
 
-        let settings = app.settings.ai.get(as_i64(guild_id.get())).await?;
+        let guild = as_i64(guild_id.get());
+        let settings = app.settings.ai.get(guild).await?;
 
         if !settings.responds_in(as_i64(message.channel_id.get())) {
             debug!(
@@ -213,11 +331,14 @@ impl Ai {
             return Ok(());
         }
 
-        if !message.mentions_me(ctx).await.unwrap_or(false) {
+        let mentioned = message.mentions_me(ctx).await.unwrap_or(false);
+
+        if !Self::triggered(message, &settings, mentioned) {
             debug!(
                 channel_id = %message.channel_id,
                 author_id = %message.author.id,
-                "message does not mention the bot; ignoring"
+                trigger = settings.trigger().as_str(),
+                "message does not trigger the bot; ignoring"
             );
             return Ok(());
         }
@@ -226,6 +347,29 @@ impl Ai {
         let server_tier = server_tier(&ctx.http, &app.entitlements, guild_id).await;
         let tier = author_tier.max(server_tier);
 
+        let author = as_i64(message.author.id.get());
+
+        let quota = ai_usage::quota(&app.db, guild, server_tier, tier).await?;
+        let spent = ai_usage::spent(&app.db, guild, author).await?;
+
+        if let Some(limit) = spent.exceeded(quota) {
+            debug!(
+                %guild_id,
+                author_id = %message.author.id,
+                ?limit,
+                "AI quota reached; not replying"
+            );
+
+            // Only a direct mention is told, so keyword and channel modes
+            // don't answer every message with the notice.
+            if mentioned {
+                message
+                    .reply(&ctx.http, Self::quota_notice(limit, server_tier))
+                    .await?;
+            }
+            return Ok(());
+        }
+
         let params = ChatParams::for_tier(app, tier);
         debug!(
             author_id = %message.author.id,
@@ -237,16 +381,46 @@ impl Ai {
             "generating AI reply"
         );
 
-        if let Err(e) = Self::reply(
+        let tools = tools::registry(ctx, app, message).await;
+
+        // Custom personas are a Pro feature of the server, whoever is asking.
+        let custom = if server_tier >= Tier::Pro {
+            settings.personas.for_channel(as_i64(message.channel_id.get()))
+        } else {
+            None
+        };
+
+        let (usage, replied) = Self::reply(
             ctx,
             message,
             &app.ai_provider_key,
             &app.ai_api_endpoint,
             &params,
+            &tools,
+            custom,
+        )
+        .await;
+
+        // A reply that failed partway was still billed for its earlier rounds.
+        if let Err(e) = replied {
+            error!(error = ?e, channel_id = %message.channel_id, "AI reply failed");
+
+            if usage.total() == 0 {
+                return Ok(());
+            }
+        }
+
+        if let Err(e) = ai_usage::record(
+            &app.db,
+            guild,
+            author,
+            params.model,
+            usage.prompt_tokens,
+            usage.completion_tokens,
         )
         .await
         {
-            tracing::error!(error = ?e, channel_id = %message.channel_id, "AI reply failed");
+            error!(error = ?e, %guild_id, "failed to record AI usage");
         }
 
         Ok(())
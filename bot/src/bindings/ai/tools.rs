@@ -0,0 +1,57 @@
+//! The lookups offered to the model on a reply.
+
+use std::sync::Arc;
+
+use ai::tools::ToolRegistry;
+use destiny2::tools::PerkLookup;
+use levels::tools::RankLookup;
+use marathon::tools::WeaponLookup;
+use music::tools::NowPlayingLookup;
+use palworld::tools::{BreedingLookup, PalLookup};
+use serenity::all::{Context, Message};
+use tokio::sync::RwLock;
+use zayden_app::state::AppState;
+
+use crate::BotState;
+
+/// Levels can only be looked up for the author and the members they
+/// mentioned, under the names the model sees them by.
+pub(super) async fn registry(
+    ctx: &Context,
+    app: &AppState,
+    message: &Message,
+) -> ToolRegistry {
+    let (palworld, marathon, music) = {
+        let data = ctx.data::<RwLock<BotState>>();
+        let state = data.read().await;
+        (
+            Arc::clone(&state.palworld),
+            Arc::clone(&state.marathon),
+            Arc::clone(&state.music),
+        )
+    };
+
+    let mut people =
+        vec![(message.author.display_name().to_owned(), message.author.id)];
+    people.extend(
+        message
+            .mentions
+            .iter()
+            .filter(|user| !user.bot())
+            .map(|user| (user.display_name().to_owned(), user.id)),
+    );
+
+    let mut tools = ToolRegistry::new();
+    tools
+        .register(PalLookup::new(Arc::clone(&palworld)))
+        .register(BreedingLookup::new(palworld))
+        .register(WeaponLookup::new(marathon))
+        .register(PerkLookup::new(app.db.clone()))
+        .register(RankLookup::new(app.db.clone(), message.guild_id, people));
+
+    if let Some(guild_id) = message.guild_id {
+        tools.register(NowPlayingLookup::new(music, guild_id));
+    }
+
+    tools
+}
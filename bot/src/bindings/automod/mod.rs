@@ -0,0 +1,35 @@
+use std::sync::Arc;
+
+use automod::AutomodHit;
+use serenity::all::UserId;
+use zayden_app::config::AutomodAction;
+use zayden_app::state::AppState;
+
+use crate::bindings::moderation::{InfractionKind, NewInfraction};
+
+const AUTOMOD_POINTS: i32 = 1;
+const AUTOMOD_MODERATOR: &str = "Zayden (Automod)";
+
+/// Puts a warning or timeout on the member's record. Deletions alone are only
+/// reported.
+pub async fn record_hit(app: &Arc<AppState>, hit: &AutomodHit) -> sqlx::Result<()> {
+    let kind = match hit.action {
+        AutomodAction::Delete => return Ok(()),
+        AutomodAction::Warn => InfractionKind::Warn,
+        AutomodAction::Timeout => InfractionKind::Mute,
+    };
+
+    NewInfraction {
+        guild_id: hit.guild_id,
+        target_id: hit.user_id,
+        target_username: &hit.username,
+        kind,
+        moderator_id: UserId::new(app.zayden_id),
+        moderator_username: AUTOMOD_MODERATOR,
+        points: AUTOMOD_POINTS,
+        reason: &format!("Automod: {}", hit.rule.label()),
+        timeout: hit.timeout,
+    }
+    .record(&app.db)
+    .await
+}
@@ -0,0 +1,27 @@
+use std::borrow::Cow;
+
+use async_trait::async_trait;
+use gambling::Commands;
+use serenity::all::CreateCommand;
+use zayden_core::ctx::InvocationCtx;
+use zayden_core::error::HandlerError;
+use zayden_core::module::ModuleCommand;
+
+pub struct Card;
+
+#[async_trait]
+impl ModuleCommand for Card {
+    fn name(&self) -> Cow<'static, str> {
+        Cow::Borrowed("card")
+    }
+
+    fn definition(&self) -> CreateCommand<'static> {
+        Commands::register_card()
+    }
+
+    async fn run(&self, cx: &InvocationCtx<'_>) -> Result<(), HandlerError> {
+        let options = cx.interaction.data.options();
+        Commands::card(cx.ctx, cx.interaction, options, &cx.app.db).await?;
+        Ok(())
+    }
+}
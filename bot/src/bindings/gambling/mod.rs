@@ -1,4 +1,5 @@
 mod blackjack;
+mod card;
 mod coinflip;
 mod craft;
 mod daily;
@@ -14,12 +15,16 @@ mod prestige;
 mod profile;
 mod roll;
 mod rps;
+mod season;
 mod send;
 mod shop;
 mod tictactoe;
 mod work;
 
+use std::sync::OnceLock;
+
 pub use blackjack::Blackjack;
+pub use card::Card;
 pub use coinflip::Coinflip;
 pub use craft::Craft;
 pub use daily::Daily;
@@ -35,7 +40,9 @@ pub use prestige::Prestige;
 pub use profile::Profile;
 pub use roll::Roll;
 pub use rps::RockPaperScissors;
+pub use season::Season;
 pub use send::Send;
+use serenity::all::GuildId;
 pub use shop::Shop;
 pub use tictactoe::TicTacToe;
 pub use work::Work;
@@ -43,10 +50,22 @@ pub use work::Work;
 use crate::RegistryBuilder;
 use crate::registry::OverlapError;
 
-pub fn register(builder: &mut RegistryBuilder) -> Result<(), OverlapError> {
+static ZAYDEN_GUILD: OnceLock<GuildId> = OnceLock::new();
+
+pub(super) fn zayden_guild() -> Option<GuildId> {
+    ZAYDEN_GUILD.get().copied()
+}
+
+pub fn register(
+    builder: &mut RegistryBuilder,
+    zayden_guild: u64,
+) -> Result<(), OverlapError> {
+    ZAYDEN_GUILD.get_or_init(|| GuildId::new(zayden_guild));
+
     builder
         .add_command(Blackjack)
         .add_component(Blackjack)?
+        .add_command(Card)
         .add_command(Coinflip)
         .add_command(Craft)
         .add_command(Daily)
@@ -65,6 +84,7 @@ pub fn register(builder: &mut RegistryBuilder) -> Result<(), OverlapError> {
         .add_command(Profile)
         .add_command(Roll)
         .add_command(RockPaperScissors)
+        .add_command(Season)
         .add_command(Send)
         .add_command(Shop)
         .add_component(Shop)?
@@ -23,8 +23,14 @@ impl ModuleCommand for Profile {
 
     async fn run(&self, cx: &InvocationCtx<'_>) -> Result<(), HandlerError> {
         let options = cx.interaction.data.options();
-        Commands::profile::<BotState>(cx.ctx, cx.interaction, options, &cx.app.db)
-            .await?;
+        Commands::profile::<BotState>(
+            cx.ctx,
+            cx.interaction,
+            options,
+            &cx.app.db,
+            &cx.app.http,
+        )
+        .await?;
         Ok(())
     }
 }
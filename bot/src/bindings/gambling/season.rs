@@ -0,0 +1,34 @@
+use std::borrow::Cow;
+
+use async_trait::async_trait;
+use gambling::Commands;
+use serenity::all::CreateCommand;
+use zayden_core::ctx::InvocationCtx;
+use zayden_core::error::HandlerError;
+use zayden_core::module::ModuleCommand;
+use zayden_core::scope::CommandScope;
+
+use super::zayden_guild;
+
+pub struct Season;
+
+#[async_trait]
+impl ModuleCommand for Season {
+    fn name(&self) -> Cow<'static, str> {
+        Cow::Borrowed("season")
+    }
+
+    fn definition(&self) -> CreateCommand<'static> {
+        Commands::register_season()
+    }
+
+    fn scope(&self) -> CommandScope {
+        CommandScope::Guilds(Cow::Owned(zayden_guild().into_iter().collect()))
+    }
+
+    async fn run(&self, cx: &InvocationCtx<'_>) -> Result<(), HandlerError> {
+        let options = cx.interaction.data.options();
+        Commands::season(cx.ctx, cx.interaction, options, &cx.app.db).await?;
+        Ok(())
+    }
+}
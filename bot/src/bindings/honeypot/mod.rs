@@ -2,15 +2,25 @@ use std::borrow::Cow;
 use std::sync::Arc;
 
 use async_trait::async_trait;
-use honeypot::{BAN_REASON, HoneypotHit, HoneypotOutcome};
-use serenity::all::{CreateCommand, UserId};
+use honeypot::{
+    BAN_REASON,
+    HoneypotHit,
+    HoneypotOutcome,
+    ROLE_TRAP_REASON,
+    SHARED_BAN_REASON,
+    SharedMatch,
+};
+use serenity::all::{CreateCommand, CreateEmbed, CreateMessage, Http, UserId};
+use zayden_app::config::{SharedAction, TrapKind};
+use zayden_app::services::threats;
 use zayden_app::state::AppState;
+use zayden_core::as_i64;
 use zayden_core::ctx::InvocationCtx;
 use zayden_core::error::HandlerError;
 use zayden_core::module::ModuleCommand;
 
 use crate::RegistryBuilder;
-use crate::bindings::moderation::{InfractionKind, NewInfraction};
+use crate::bindings::moderation::{InfractionKind, NewInfraction, appeal};
 
 const HONEYPOT_POINTS: i32 = 0;
 const HONEYPOT_MODERATOR: &str = "Zayden (Honeypot)";
@@ -41,10 +51,32 @@ impl ModuleCommand for Honeypot {
     }
 }
 
-pub async fn record_hit(app: &Arc<AppState>, hit: &HoneypotHit) -> sqlx::Result<()> {
+pub async fn record_hit(
+    http: &Http,
+    app: &Arc<AppState>,
+    hit: &HoneypotHit,
+) -> sqlx::Result<()> {
+    threats::record_hit(
+        &app.db,
+        as_i64(hit.guild_id.get()),
+        as_i64(hit.user_id.get()),
+        hit.message_hash.as_deref(),
+    )
+    .await?;
+
     let kind = match hit.outcome {
         HoneypotOutcome::SoftBanned => InfractionKind::SoftBan,
-        HoneypotOutcome::BanStanding => InfractionKind::Ban,
+        HoneypotOutcome::BanStanding | HoneypotOutcome::Banned => {
+            InfractionKind::Ban
+        },
+        HoneypotOutcome::TimedOut | HoneypotOutcome::Quarantined => {
+            InfractionKind::Mute
+        },
+    };
+
+    let reason = match hit.trap.kind {
+        TrapKind::Channel => BAN_REASON,
+        TrapKind::Role => ROLE_TRAP_REASON,
     };
 
     NewInfraction {
@@ -55,7 +87,75 @@ pub async fn record_hit(app: &Arc<AppState>, hit: &HoneypotHit) -> sqlx::Result<
         moderator_id: UserId::new(app.zayden_id),
         moderator_username: HONEYPOT_MODERATOR,
         points: HONEYPOT_POINTS,
-        reason: BAN_REASON,
+        reason,
+        timeout: (hit.outcome == HoneypotOutcome::TimedOut)
+            .then(|| hit.trap.timeout()),
+    }
+    .record(&app.db)
+    .await?;
+
+    if hit.outcome == HoneypotOutcome::SoftBanned {
+        offer_appeal(http, app, hit).await?;
+    }
+
+    Ok(())
+}
+
+/// Tells a soft-banned member why they were removed, with a way to appeal.
+/// Only sent when the guild takes appeals; the member has already left, so
+/// the DM only arrives if they share another server with the bot.
+async fn offer_appeal(
+    http: &Http,
+    app: &Arc<AppState>,
+    hit: &HoneypotHit,
+) -> sqlx::Result<()> {
+    let Some(button) = appeal::button_for(app, hit.guild_id).await? else {
+        return Ok(());
+    };
+
+    let guild_name = hit
+        .guild_id
+        .to_partial_guild(http)
+        .await
+        .map_or_else(|_| "a server".to_string(), |guild| guild.name.to_string());
+
+    let embed =
+        CreateEmbed::new().title("You have been softbanned").description(format!(
+            "You were removed from {guild_name} by its spam trap. If this was a \
+             mistake, you can appeal below."
+        ));
+
+    let _ = hit
+        .user_id
+        .direct_message(
+            http,
+            CreateMessage::new().embed(embed).components(vec![button]),
+        )
+        .await;
+
+    Ok(())
+}
+
+/// Puts a ban the shared threat list made on the member's record. Flags are
+/// only reported.
+pub async fn record_shared_ban(
+    app: &Arc<AppState>,
+    matched: &SharedMatch,
+) -> sqlx::Result<()> {
+    if matched.action != SharedAction::Ban {
+        return Ok(());
+    }
+
+    NewInfraction {
+        guild_id: matched.guild_id,
+        target_id: matched.user_id,
+        target_username: &matched.username,
+        kind: InfractionKind::Ban,
+        moderator_id: UserId::new(app.zayden_id),
+        moderator_username: HONEYPOT_MODERATOR,
+        points: HONEYPOT_POINTS,
+        reason: SHARED_BAN_REASON,
+        timeout: None,
     }
     .record(&app.db)
     .await
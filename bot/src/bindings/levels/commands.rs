@@ -52,8 +52,14 @@ impl ModuleCommand for Rank {
 
     async fn run(&self, cx: &InvocationCtx<'_>) -> Result<(), HandlerError> {
         let options = cx.interaction.data.options();
-        levels::Rank::rank(&cx.ctx.http, cx.interaction, options, &cx.app.db)
-            .await?;
+        levels::Rank::rank(
+            &cx.ctx.http,
+            cx.interaction,
+            options,
+            &cx.app.db,
+            &cx.app.http,
+        )
+        .await?;
         Ok(())
     }
 }
@@ -4,6 +4,7 @@ use crate::registry::OverlapError;
 use crate::{CommandRegistry, RegistryBuilder};
 
 pub mod ai;
+pub mod automod;
 pub mod destiny2;
 pub mod family;
 pub mod gambling;
@@ -18,6 +19,8 @@ pub mod misc;
 pub mod moderation;
 pub mod music;
 pub mod palworld;
+pub mod premium;
+pub mod raid;
 pub mod reaction_roles;
 pub mod suggestions;
 pub mod temp_voice;
@@ -26,11 +29,12 @@ pub mod verify;
 
 pub fn build_registry(
     llamad2_guild: u64,
+    zayden_guild: u64,
 ) -> Result<Arc<CommandRegistry>, OverlapError> {
     let mut builder = RegistryBuilder::new();
     destiny2::register(&mut builder);
     family::register(&mut builder)?;
-    gambling::register(&mut builder)?;
+    gambling::register(&mut builder, zayden_guild)?;
     gold_star::register(&mut builder);
     greetings::register(&mut builder);
     honeypot::register(&mut builder);
@@ -39,14 +43,16 @@ pub fn build_registry(
     llamad2::register(&mut builder, llamad2_guild);
     marathon::register(&mut builder);
     misc::register(&mut builder);
-    moderation::register(&mut builder);
+    moderation::register(&mut builder)?;
     palworld::register(&mut builder)?;
+    premium::register(&mut builder);
+    raid::register(&mut builder);
     music::register(&mut builder)?;
     ticket::register(&mut builder)?;
     verify::register(&mut builder)?;
     suggestions::register(&mut builder)?;
     temp_voice::register(&mut builder)?;
-    reaction_roles::register(&mut builder);
+    reaction_roles::register(&mut builder)?;
 
     let registry = builder.build();
 
@@ -0,0 +1,458 @@
+//! Appeals: the button on a ban or mute DM, the modal the member fills in,
+//! and the approve/deny buttons on the staff post. The records live in
+//! [`zayden_app::services::appeals`].
+
+use std::borrow::Cow;
+
+use async_trait::async_trait;
+use serenity::all::{
+    ButtonStyle,
+    ChannelId,
+    CreateActionRow,
+    CreateButton,
+    CreateComponent,
+    CreateEmbed,
+    CreateInputText,
+    CreateInteractionResponse,
+    CreateInteractionResponseMessage,
+    CreateLabel,
+    CreateMessage,
+    CreateModal,
+    CreateModalComponent,
+    EditMember,
+    GuildId,
+    Http,
+    InputTextStyle,
+    Permissions,
+    UserId,
+};
+use zayden_app::services::appeals::{self, Appeal, AppealStatus, Punishment};
+use zayden_app::state::AppState;
+use zayden_core::error::CoreError;
+use zayden_core::{
+    ComponentCtx,
+    HandlerError,
+    IdMatch,
+    ModalCtx,
+    ModuleComponent,
+    ModuleModal,
+    as_i64,
+    as_u64,
+    is_not_found,
+    parse_modal_components,
+};
+
+/// The DM button, `appeal:{guild_id}`.
+const APPEAL: &str = "appeal:";
+/// The appeal form, `appeal_modal:{guild_id}:{infraction_id}`.
+const MODAL: &str = "appeal_modal:";
+const APPROVE: &str = "appeal_approve:";
+const DENY: &str = "appeal_deny:";
+
+const STATEMENT: &str = "statement";
+const MAX_STATEMENT: u16 = 1000;
+
+fn button(guild_id: GuildId) -> CreateComponent<'static> {
+    CreateComponent::ActionRow(CreateActionRow::buttons(vec![
+        CreateButton::new(format!("{APPEAL}{guild_id}"))
+            .label("Appeal")
+            .style(ButtonStyle::Secondary),
+    ]))
+}
+
+/// The button added to a ban or mute DM, if `guild_id` takes appeals.
+pub(crate) async fn button_for(
+    app: &AppState,
+    guild_id: GuildId,
+) -> sqlx::Result<Option<CreateComponent<'static>>> {
+    let settings = app.settings.appeals.get(as_i64(guild_id.get())).await?;
+    Ok(settings.is_open().then(|| button(guild_id)))
+}
+
+fn ephemeral(content: impl Into<String>) -> CreateInteractionResponse<'static> {
+    CreateInteractionResponse::Message(
+        CreateInteractionResponseMessage::new()
+            .ephemeral(true)
+            .content(content.into()),
+    )
+}
+
+fn parse_id<T: std::str::FromStr>(
+    custom_id: &str,
+    prefix: &str,
+) -> Result<T, CoreError> {
+    custom_id
+        .strip_prefix(prefix)
+        .and_then(|id| id.parse().ok())
+        .ok_or_else(|| CoreError::invalid_option(custom_id))
+}
+
+/// The guild's appeals channel, if it takes appeals.
+async fn appeals_channel(
+    app: &AppState,
+    guild_id: i64,
+) -> sqlx::Result<Option<i64>> {
+    let settings = app.settings.appeals.get(guild_id).await?;
+    Ok(settings.channel_id.filter(|_| settings.is_open()))
+}
+
+pub(super) struct AppealButton;
+
+#[async_trait]
+impl ModuleComponent for AppealButton {
+    fn id_match(&self) -> IdMatch {
+        IdMatch::Prefix(Cow::Borrowed(APPEAL))
+    }
+
+    async fn run(&self, cx: &ComponentCtx<'_>) -> Result<(), HandlerError> {
+        let guild_id: u64 = parse_id(&cx.interaction.data.custom_id, APPEAL)?;
+        let guild = as_i64(guild_id);
+
+        if appeals_channel(&cx.app, guild).await?.is_none() {
+            cx.interaction
+                .create_response(
+                    &cx.ctx.http,
+                    ephemeral("This server isn't taking appeals right now."),
+                )
+                .await?;
+            return Ok(());
+        }
+
+        let user_id = as_i64(cx.interaction.user.id.get());
+        let response = match appeals::appealable(&cx.app.db, guild, user_id).await? {
+            None => ephemeral("You have nothing to appeal in this server."),
+            Some(appealable) if appealable.appealed => ephemeral(format!(
+                "You have already appealed your {}.",
+                appealable.punishment.as_str()
+            )),
+            Some(appealable) => {
+                let input =
+                    CreateInputText::new(InputTextStyle::Paragraph, STATEMENT)
+                        .min_length(10)
+                        .max_length(MAX_STATEMENT)
+                        .required(true);
+
+                CreateInteractionResponse::Modal(
+                    CreateModal::new(
+                        format!("{MODAL}{guild_id}:{}", appealable.infraction_id),
+                        format!("Appeal your {}", appealable.punishment.as_str()),
+                    )
+                    .components(vec![
+                        CreateModalComponent::Label(CreateLabel::input_text(
+                            "Why should it be lifted?",
+                            input,
+                        )),
+                    ]),
+                )
+            },
+        };
+
+        cx.interaction.create_response(&cx.ctx.http, response).await?;
+
+        Ok(())
+    }
+}
+
+pub(super) struct AppealModal;
+
+#[async_trait]
+impl ModuleModal for AppealModal {
+    fn id_match(&self) -> IdMatch {
+        IdMatch::Prefix(Cow::Borrowed(MODAL))
+    }
+
+    async fn run(&self, cx: &ModalCtx<'_>) -> Result<(), HandlerError> {
+        let custom_id = cx.interaction.data.custom_id.as_str();
+        let (guild_id, infraction_id) = custom_id
+            .strip_prefix(MODAL)
+            .and_then(|ids| ids.split_once(':'))
+            .and_then(|(guild, infraction)| {
+                Some((guild.parse::<u64>().ok()?, infraction.parse::<i32>().ok()?))
+            })
+            .ok_or_else(|| CoreError::invalid_option(custom_id))?;
+        let guild = as_i64(guild_id);
+
+        let Some(channel_id) = appeals_channel(&cx.app, guild).await? else {
+            cx.interaction
+                .create_response(
+                    &cx.ctx.http,
+                    ephemeral("This server isn't taking appeals right now."),
+                )
+                .await?;
+            return Ok(());
+        };
+
+        // The punishment may have been lifted while the form was open.
+        let user_id = as_i64(cx.interaction.user.id.get());
+        let current = appeals::appealable(&cx.app.db, guild, user_id).await?;
+        if current.is_none_or(|current| current.infraction_id != infraction_id) {
+            cx.interaction
+                .create_response(
+                    &cx.ctx.http,
+                    ephemeral("You have nothing to appeal in this server."),
+                )
+                .await?;
+            return Ok(());
+        }
+
+        let inputs = parse_modal_components(&cx.interaction.data.components);
+        let statement = inputs
+            .get(STATEMENT)
+            .and_then(|values| values.first())
+            .map_or("", |statement| statement.trim());
+
+        let appeal = match appeals::open(
+            &cx.app.db,
+            guild,
+            user_id,
+            infraction_id,
+            statement,
+        )
+        .await?
+        {
+            Some(id) => appeals::get(&cx.app.db, guild, id).await?,
+            None => None,
+        };
+        let Some(appeal) = appeal else {
+            cx.interaction
+                .create_response(
+                    &cx.ctx.http,
+                    ephemeral("You have already appealed this."),
+                )
+                .await?;
+            return Ok(());
+        };
+
+        let message = ChannelId::new(as_u64(channel_id))
+            .send_message(
+                &cx.ctx.http,
+                CreateMessage::new()
+                    .embed(staff_embed(&appeal))
+                    .components(vec![decision_buttons(appeal.id)]),
+            )
+            .await;
+
+        let message = match message {
+            Ok(message) => message,
+            Err(e) => {
+                appeals::withdraw(&cx.app.db, appeal.id).await?;
+                return Err(e.into());
+            },
+        };
+
+        appeals::set_message(
+            &cx.app.db,
+            appeal.id,
+            channel_id,
+            as_i64(message.id.get()),
+        )
+        .await?;
+
+        cx.interaction
+            .create_response(
+                &cx.ctx.http,
+                ephemeral(
+                    "Your appeal has been sent to the staff. You'll get a DM when \
+                     they decide.",
+                ),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+fn staff_embed(appeal: &Appeal) -> CreateEmbed<'static> {
+    CreateEmbed::new()
+        .title(format!("Appeal #{}", appeal.id))
+        .field("Member", format!("<@{0}> ({0})", appeal.user_id), true)
+        .field(
+            "Case",
+            format!("#{} · {}", appeal.infraction_id, appeal.punishment.as_str()),
+            true,
+        )
+        .field("Reason", appeal.reason.clone(), false)
+        .field("Statement", appeal.statement.clone(), false)
+}
+
+fn decision_buttons(id: i32) -> CreateComponent<'static> {
+    CreateComponent::ActionRow(CreateActionRow::buttons(vec![
+        CreateButton::new(format!("{APPROVE}{id}"))
+            .label("Approve")
+            .style(ButtonStyle::Success),
+        CreateButton::new(format!("{DENY}{id}"))
+            .label("Deny")
+            .style(ButtonStyle::Danger),
+    ]))
+}
+
+/// Undoes the punishment on Discord. A soft-ban's ban was lifted straight
+/// away, so there is nothing to undo, and a 404 means it is already gone:
+/// the member was unbanned by hand or has left.
+async fn lift(
+    http: &Http,
+    guild_id: GuildId,
+    appeal: &Appeal,
+    note: &str,
+) -> serenity::Result<()> {
+    let user_id = UserId::new(as_u64(appeal.user_id));
+
+    let lifted = match appeal.punishment {
+        Punishment::Ban => guild_id.unban(http, user_id, Some(note)).await,
+        Punishment::SoftBan => Ok(()),
+        Punishment::Mute => guild_id
+            .edit_member(
+                http,
+                user_id,
+                EditMember::new().enable_communication().audit_log_reason(note),
+            )
+            .await
+            .map(|_member| ()),
+    };
+
+    match lifted {
+        Err(e) if is_not_found(&e) => Ok(()),
+        lifted => lifted,
+    }
+}
+
+async fn decide(cx: &ComponentCtx<'_>, approved: bool) -> Result<(), HandlerError> {
+    let guild_id = cx.interaction.guild_id.ok_or(CoreError::MissingGuildId)?;
+    let prefix = if approved { APPROVE } else { DENY };
+    let id: i32 = parse_id(&cx.interaction.data.custom_id, prefix)?;
+
+    let Some(appeal) = appeals::get(&cx.app.db, as_i64(guild_id.get()), id).await?
+    else {
+        cx.interaction
+            .create_response(
+                &cx.ctx.http,
+                ephemeral("That appeal no longer exists."),
+            )
+            .await?;
+        return Ok(());
+    };
+
+    let (required, permission) = match appeal.punishment {
+        Punishment::Mute => (Permissions::MODERATE_MEMBERS, "Timeout Members"),
+        Punishment::SoftBan | Punishment::Ban => {
+            (Permissions::BAN_MEMBERS, "Ban Members")
+        },
+    };
+    let allowed = cx
+        .interaction
+        .member
+        .as_ref()
+        .and_then(|member| member.permissions)
+        .is_some_and(|perms| perms.contains(required));
+    if !allowed {
+        cx.interaction
+            .create_response(
+                &cx.ctx.http,
+                ephemeral(format!(
+                    "You need the {permission} permission to decide this appeal."
+                )),
+            )
+            .await?;
+        return Ok(());
+    }
+
+    if appeal.status != AppealStatus::Pending {
+        cx.interaction
+            .create_response(
+                &cx.ctx.http,
+                ephemeral(format!(
+                    "This appeal was already {}.",
+                    appeal.status.as_str()
+                )),
+            )
+            .await?;
+        return Ok(());
+    }
+
+    let moderator = &cx.interaction.user;
+    let verdict = if approved { "approved" } else { "denied" };
+    let note = format!("Appeal #{} {verdict} by {}", appeal.id, moderator.name);
+
+    if approved {
+        lift(&cx.ctx.http, guild_id, &appeal, &note).await?;
+    }
+
+    let decided = appeals::decide(
+        &cx.app.db,
+        &appeal,
+        approved,
+        as_i64(moderator.id.get()),
+        &note,
+    )
+    .await?;
+    if !decided {
+        cx.interaction
+            .create_response(
+                &cx.ctx.http,
+                ephemeral("Someone else decided this appeal first."),
+            )
+            .await?;
+        return Ok(());
+    }
+
+    cx.interaction
+        .create_response(
+            &cx.ctx.http,
+            CreateInteractionResponse::UpdateMessage(
+                CreateInteractionResponseMessage::new()
+                    .embed(staff_embed(&appeal).field("Decision", note, false))
+                    .components(vec![]),
+            ),
+        )
+        .await?;
+
+    let guild_name = guild_id
+        .to_partial_guild(&cx.ctx.http)
+        .await
+        .map_or_else(|_| "the server".to_string(), |guild| guild.name.to_string());
+    let outcome = if approved {
+        format!(
+            "Your appeal of your {} in {guild_name} was approved, and it has been \
+             lifted.",
+            appeal.punishment.as_str()
+        )
+    } else {
+        format!(
+            "Your appeal of your {} in {guild_name} was denied.",
+            appeal.punishment.as_str()
+        )
+    };
+
+    // The member may not share a server with the bot any more.
+    let _ = UserId::new(as_u64(appeal.user_id))
+        .direct_message(&cx.ctx.http, CreateMessage::new().content(outcome))
+        .await;
+
+    Ok(())
+}
+
+pub(super) struct ApproveAppeal;
+
+#[async_trait]
+impl ModuleComponent for ApproveAppeal {
+    fn id_match(&self) -> IdMatch {
+        IdMatch::Prefix(Cow::Borrowed(APPROVE))
+    }
+
+    async fn run(&self, cx: &ComponentCtx<'_>) -> Result<(), HandlerError> {
+        decide(cx, true).await
+    }
+}
+
+pub(super) struct DenyAppeal;
+
+#[async_trait]
+impl ModuleComponent for DenyAppeal {
+    fn id_match(&self) -> IdMatch {
+        IdMatch::Prefix(Cow::Borrowed(DENY))
+    }
+
+    async fn run(&self, cx: &ComponentCtx<'_>) -> Result<(), HandlerError> {
+        decide(cx, false).await
+    }
+}
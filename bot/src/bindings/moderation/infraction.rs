@@ -5,6 +5,7 @@ use serenity::all::{
     Context,
     CreateCommand,
     CreateCommandOption,
+    CreateComponent,
     CreateEmbed,
     CreateMessage,
     EditInteractionResponse,
@@ -25,7 +26,7 @@ use zayden_core::{
     required_option,
 };
 
-use super::{InfractionKind, InfractionRow, NO_REASON, NewInfraction};
+use super::{InfractionKind, InfractionRow, NO_REASON, NewInfraction, appeal};
 
 pub(super) struct Infraction;
 
@@ -92,6 +93,7 @@ impl ModuleCommand for Infraction {
             moderator: &cx.interaction.user,
             points,
             reason,
+            appeal: appeal::button_for(&cx.app, guild_id).await?,
         };
 
         let embed = match infraction_count {
@@ -118,10 +120,16 @@ struct Case<'a> {
     moderator: &'a User,
     points: i32,
     reason: &'a str,
+    /// The appeal button for mute and ban DMs, if the guild takes appeals.
+    appeal: Option<CreateComponent<'static>>,
 }
 
 impl Case<'_> {
-    async fn record(&self, kind: InfractionKind) -> sqlx::Result<()> {
+    async fn record(
+        &self,
+        kind: InfractionKind,
+        timeout: Option<SignedDuration>,
+    ) -> sqlx::Result<()> {
         NewInfraction {
             guild_id: self.guild_id,
             target_id: self.target.id,
@@ -131,6 +139,7 @@ impl Case<'_> {
             moderator_username: self.moderator.name.as_str(),
             points: self.points,
             reason: self.reason,
+            timeout,
         }
         .record(self.pool)
         .await
@@ -138,7 +147,7 @@ impl Case<'_> {
 }
 
 async fn warn(case: &Case<'_>) -> Result<CreateEmbed<'static>, HandlerError> {
-    case.record(InfractionKind::Warn).await?;
+    case.record(InfractionKind::Warn, None).await?;
 
     let guild_name = case.guild_id.to_partial_guild(&case.ctx.http).await?.name;
     let desc = if case.reason == NO_REASON {
@@ -150,8 +159,14 @@ async fn warn(case: &Case<'_>) -> Result<CreateEmbed<'static>, HandlerError> {
         )
     };
 
-    let _ = send_user_message(case.ctx, case.target.id, InfractionKind::Warn, desc)
-        .await;
+    let _ = send_user_message(
+        case.ctx,
+        case.target.id,
+        InfractionKind::Warn,
+        desc,
+        None,
+    )
+    .await;
 
     Ok(action_embed(case.target, "warned", case.reason))
 }
@@ -168,7 +183,7 @@ async fn mute(
     .map_err(|e| CoreError::Other(e.to_string()))?;
     member.disable_communication_until(&case.ctx.http, until).await?;
 
-    case.record(InfractionKind::Mute).await?;
+    case.record(InfractionKind::Mute, Some(duration)).await?;
 
     let guild_name = case.guild_id.to_partial_guild(&case.ctx.http).await?.name;
     let duration_str = format_duration(duration);
@@ -181,8 +196,14 @@ async fn mute(
         )
     };
 
-    let _ = send_user_message(case.ctx, case.target.id, InfractionKind::Mute, desc)
-        .await;
+    let _ = send_user_message(
+        case.ctx,
+        case.target.id,
+        InfractionKind::Mute,
+        desc,
+        case.appeal.clone(),
+    )
+    .await;
 
     Ok(action_embed(case.target, "muted", case.reason))
 }
@@ -200,21 +221,28 @@ async fn ban(case: &Case<'_>) -> Result<CreateEmbed<'static>, HandlerError> {
         )
     };
 
-    let _ =
-        send_user_message(case.ctx, case.target.id, InfractionKind::Ban, desc).await;
+    let _ = send_user_message(
+        case.ctx,
+        case.target.id,
+        InfractionKind::Ban,
+        desc,
+        case.appeal.clone(),
+    )
+    .await;
 
     member.ban(&case.ctx.http, 1, Some(case.reason)).await?;
 
-    case.record(InfractionKind::Ban).await?;
+    case.record(InfractionKind::Ban, None).await?;
 
     Ok(action_embed(case.target, "banned", case.reason))
 }
 
-async fn send_user_message(
+pub(super) async fn send_user_message(
     ctx: &Context,
     user_id: UserId,
     kind: InfractionKind,
     desc: impl Into<String>,
+    appeal: Option<CreateComponent<'static>>,
 ) -> Result<(), HandlerError> {
     let title = match kind {
         InfractionKind::Warn => "You have been warned",
@@ -226,12 +254,17 @@ async fn send_user_message(
 
     let embed = CreateEmbed::new().title(title).description(desc.into());
 
-    user_id.direct_message(&ctx.http, CreateMessage::new().embed(embed)).await?;
+    let mut message = CreateMessage::new().embed(embed);
+    if let Some(appeal) = appeal {
+        message = message.components(vec![appeal]);
+    }
+
+    user_id.direct_message(&ctx.http, message).await?;
 
     Ok(())
 }
 
-fn action_embed(
+pub(super) fn action_embed(
     target: &User,
     past_tense: &str,
     reason: &str,
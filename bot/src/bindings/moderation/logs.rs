@@ -107,10 +107,19 @@ impl ModuleCommand for Logs {
         }
 
         let fields = infractions.into_iter().map(|infraction| {
+            let lifted = infraction
+                .lift_reason
+                .map(|note| format!("\n**Lifted:** {note}"))
+                .unwrap_or_default();
+            let appeal = infraction
+                .appeal_status
+                .map(|status| format!("\n**Appeal:** {status}"))
+                .unwrap_or_default();
+
             (
                 format!("Case #{}", infraction.id),
                 format!(
-                    "**Type:** {}\n**User:** ({}) {}\n**Moderator:** ({}) {}\n**Reason:** {}",
+                    "**Type:** {}\n**User:** ({}) {}\n**Moderator:** ({}) {}\n**Reason:** {}{lifted}{appeal}",
                     infraction.infraction_type,
                     infraction.user_id,
                     infraction.username,
@@ -1,23 +1,47 @@
 use std::fmt::Display;
 
+use jiff::SignedDuration;
 use serenity::all::{GuildId, UserId};
 use sqlx::PgPool;
 use zayden_core::as_i64;
 
 use crate::RegistryBuilder;
+use crate::registry::OverlapError;
 
+pub(crate) mod appeal;
 mod infraction;
 mod logs;
-mod rules;
+pub(crate) mod rules;
+mod slowmode;
+mod tempban;
+mod temprole;
+pub(crate) mod timed;
 
+use appeal::{AppealButton, AppealModal, ApproveAppeal, DenyAppeal};
 use infraction::Infraction;
 use logs::Logs;
 use rules::RulesCommand;
+use slowmode::Slowmode;
+use tempban::Tempban;
+use temprole::Temprole;
+use timed::ModerationCommand;
 
 pub(crate) const NO_REASON: &str = "No reason provided.";
 
-pub fn register(builder: &mut RegistryBuilder) {
-    builder.add_command(Infraction).add_command(Logs).add_command(RulesCommand);
+pub fn register(builder: &mut RegistryBuilder) -> Result<(), OverlapError> {
+    builder
+        .add_command(Infraction)
+        .add_command(Logs)
+        .add_command(RulesCommand)
+        .add_command(Tempban)
+        .add_command(Temprole)
+        .add_command(Slowmode)
+        .add_command(ModerationCommand)
+        .add_component(AppealButton)?
+        .add_component(ApproveAppeal)?
+        .add_component(DenyAppeal)?
+        .add_modal(AppealModal)?;
+    Ok(())
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::Type)]
@@ -52,15 +76,24 @@ pub(crate) struct NewInfraction<'a> {
     pub moderator_username: &'a str,
     pub points: i32,
     pub reason: &'a str,
+    /// How long a timeout lasts. Once it runs out the mute can no longer be
+    /// appealed.
+    pub timeout: Option<SignedDuration>,
 }
 
 impl NewInfraction<'_> {
     pub(crate) async fn record(&self, pool: &PgPool) -> sqlx::Result<()> {
-        sqlx::query!(
+        self.insert(pool).await.map(|_id| ())
+    }
+
+    /// As [`Self::record`], returning the new infraction's id.
+    pub(crate) async fn insert(&self, pool: &PgPool) -> sqlx::Result<i32> {
+        sqlx::query_scalar!(
             "INSERT INTO infractions
                 (user_id, username, guild_id, infraction_type,
-                 moderator_id, moderator_username, points, reason)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)",
+                 moderator_id, moderator_username, points, reason, expires_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, now() + make_interval(secs => $9))
+            RETURNING id",
             as_i64(self.target_id.get()),
             self.target_username,
             as_i64(self.guild_id.get()),
@@ -69,11 +102,10 @@ impl NewInfraction<'_> {
             self.moderator_username,
             self.points,
             self.reason,
+            self.timeout.map(|timeout| timeout.as_secs_f64()),
         )
-        .execute(pool)
-        .await?;
-
-        Ok(())
+        .fetch_one(pool)
+        .await
     }
 }
 
@@ -86,6 +118,10 @@ pub(crate) struct InfractionRow {
     pub moderator_username: String,
     pub points: i32,
     pub reason: String,
+    /// Why a tempban was lifted, once it has been.
+    pub lift_reason: Option<String>,
+    /// Where the member's appeal stands, if they made one.
+    pub appeal_status: Option<String>,
 }
 
 impl InfractionRow {
@@ -107,9 +143,13 @@ impl InfractionRow {
                     moderator_id,
                     moderator_username,
                     points,
-                    reason
+                    reason,
+                    lift_reason,
+                    (SELECT status FROM appeals WHERE infraction_id = infractions.id)
+                        AS appeal_status
                 FROM infractions
                 WHERE user_id = $1
+                    AND expunged_at IS NULL
                     AND created_at > now() - INTERVAL '6 months'"#,
                 user_id
             )
@@ -126,9 +166,12 @@ impl InfractionRow {
                     moderator_id,
                     moderator_username,
                     points,
-                    reason
+                    reason,
+                    lift_reason,
+                    (SELECT status FROM appeals WHERE infraction_id = infractions.id)
+                        AS appeal_status
                 FROM infractions
-                WHERE user_id = $1"#,
+                WHERE user_id = $1 AND expunged_at IS NULL"#,
                 user_id
             )
             .fetch_all(pool)
@@ -2,7 +2,6 @@ use async_trait::async_trait;
 use serenity::all::{
     Colour,
     CommandOptionType,
-    Context,
     CreateCommand,
     CreateCommandOption,
     CreateEmbed,
@@ -14,6 +13,7 @@ use serenity::all::{
     GenericChannelId,
     GenericInteractionChannel,
     GuildId,
+    Http,
     HttpError,
     JsonErrorCode,
     MessageId,
@@ -21,6 +21,7 @@ use serenity::all::{
     ResolvedValue,
 };
 use sqlx::PgPool;
+use tracing::{debug, warn};
 use zayden_core::error::CoreError;
 use zayden_core::{
     HandlerError,
@@ -202,7 +203,7 @@ impl ModuleCommand for RulesCommand {
             "remove" => remove(&cx.app.db, guild_id, &mut options).await?,
             "reorder" => reorder(&cx.app.db, guild_id, &mut options).await?,
             "list" => list(&cx.app.db, guild_id).await?,
-            "post" => post(cx.ctx, &cx.app.db, guild_id).await?,
+            "post" => post(&cx.ctx.http, &cx.app.db, guild_id).await?,
             other => {
                 return Err(HandlerError::from_respond(CoreError::Other(format!(
                     "unexpected rules subcommand: {other}"
@@ -502,8 +503,35 @@ async fn list(pool: &PgPool, guild_id: GuildId) -> Result<String, HandlerError>
     Ok(out)
 }
 
+/// Publishes the rules of every guild that asked for it from the dashboard,
+/// exactly as `/rules post` would. Requests are claimed up front, so a
+/// failed post is logged rather than retried every sweep.
+pub(crate) async fn publish_sweep(http: &Http, pool: &PgPool) -> sqlx::Result<()> {
+    let guilds = sqlx::query_scalar!(
+        "UPDATE guild_rules SET publish_requested = false
+         WHERE publish_requested
+         RETURNING guild_id",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    for guild_id in guilds {
+        let guild_id = GuildId::new(as_u64(guild_id));
+        match post(http, pool, guild_id).await {
+            Ok(outcome) => {
+                debug!(guild = guild_id.get(), %outcome, "published rules");
+            },
+            Err(e) => {
+                warn!(error = ?e, guild = guild_id.get(), "failed to publish rules");
+            },
+        }
+    }
+
+    Ok(())
+}
+
 async fn post(
-    ctx: &Context,
+    http: &Http,
     pool: &PgPool,
     guild_id: GuildId,
 ) -> Result<String, HandlerError> {
@@ -541,11 +569,7 @@ async fn post(
     if let Some(message_id) = config.message_id {
         let message_id = MessageId::new(as_u64(message_id));
         match channel
-            .edit_message(
-                &ctx.http,
-                message_id,
-                EditMessage::new().embed(embed.clone()),
-            )
+            .edit_message(http, message_id, EditMessage::new().embed(embed.clone()))
             .await
         {
             Ok(_) => return Ok("Rules message updated.".to_string()),
@@ -567,7 +591,7 @@ async fn post(
     }
 
     let message =
-        channel.send_message(&ctx.http, CreateMessage::new().embed(embed)).await?;
+        channel.send_message(http, CreateMessage::new().embed(embed)).await?;
 
     sqlx::query!(
         "UPDATE guild_rules SET message_id = $2 WHERE guild_id = $1",
@@ -0,0 +1,163 @@
+use async_trait::async_trait;
+use serenity::all::{
+    CommandOptionType,
+    CreateCommand,
+    CreateCommandOption,
+    EditChannel,
+    GenericInteractionChannel,
+    Permissions,
+};
+use serenity::nonmax::NonMaxU16;
+use zayden_app::services::timed_actions::{self, Target};
+use zayden_core::error::CoreError;
+use zayden_core::{
+    HandlerError,
+    InvocationCtx,
+    ModuleCommand,
+    as_i64,
+    format_duration,
+    optional_option,
+    parse_options,
+    required_option,
+};
+
+use super::NO_REASON;
+use super::timed::{expires_at, parse_for, reply};
+
+/// Discord's longest slowmode, six hours.
+const MAX_SLOWMODE_SECONDS: u16 = 6 * 60 * 60;
+
+pub(super) struct Slowmode;
+
+#[async_trait]
+impl ModuleCommand for Slowmode {
+    fn module(&self) -> Option<&'static str> {
+        Some("moderation")
+    }
+
+    fn definition(&self) -> CreateCommand<'static> {
+        CreateCommand::new("slowmode")
+            .description("Set a channel's slowmode, for good or for a while")
+            .default_member_permissions(Permissions::MANAGE_CHANNELS)
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::Integer,
+                    "seconds",
+                    "Seconds between each member's messages; 0 turns it off",
+                )
+                .min_int_value(0)
+                .max_int_value(MAX_SLOWMODE_SECONDS.into())
+                .required(true),
+            )
+            .add_option(CreateCommandOption::new(
+                CommandOptionType::String,
+                "for",
+                "Put the old slowmode back after this long, e.g. 30m or 2h",
+            ))
+            .add_option(CreateCommandOption::new(
+                CommandOptionType::Channel,
+                "channel",
+                "The channel (default: this one)",
+            ))
+            .add_option(CreateCommandOption::new(
+                CommandOptionType::String,
+                "reason",
+                "The reason for the slowmode",
+            ))
+    }
+
+    async fn run(&self, cx: &InvocationCtx<'_>) -> Result<(), HandlerError> {
+        cx.interaction.defer_ephemeral(&cx.ctx.http).await?;
+
+        let guild_id = cx.interaction.guild_id.ok_or(CoreError::MissingGuildId)?;
+
+        let options = cx.interaction.data.options();
+        let mut options = parse_options(options);
+
+        let seconds: i64 = required_option(&mut options, "seconds")?;
+        let input = optional_option::<&str, _>(&mut options, "for");
+        let channel_id = optional_option::<&GenericInteractionChannel, _>(
+            &mut options,
+            "channel",
+        )
+        .map_or(cx.interaction.channel_id, GenericInteractionChannel::id)
+        .expect_channel();
+        let reason =
+            optional_option::<&str, _>(&mut options, "reason").unwrap_or(NO_REASON);
+
+        let duration = match input.map(parse_for).transpose() {
+            Ok(duration) => duration,
+            Err(message) => return reply(cx, message).await,
+        };
+
+        let seconds = u16::try_from(seconds)
+            .unwrap_or(MAX_SLOWMODE_SECONDS)
+            .min(MAX_SLOWMODE_SECONDS);
+        let slowmode = NonMaxU16::new(seconds).unwrap_or(NonMaxU16::ZERO);
+
+        let Some(channel) =
+            guild_id.channels(&cx.ctx.http).await?.remove(&channel_id)
+        else {
+            return reply(cx, "Slowmode can only be set on this server's channels.")
+                .await;
+        };
+        let previous = channel.base.rate_limit_per_user.map_or(0, NonMaxU16::get);
+
+        channel_id
+            .edit(
+                &cx.ctx.http,
+                EditChannel::new()
+                    .rate_limit_per_user(slowmode)
+                    .audit_log_reason(reason),
+            )
+            .await?;
+
+        let guild = as_i64(guild_id.get());
+        let moderator = as_i64(cx.interaction.user.id.get());
+
+        let Some(duration) = duration else {
+            let cancelled = timed_actions::cancel_slowmode(
+                &cx.app.db,
+                guild,
+                as_i64(channel_id.get()),
+                moderator,
+            )
+            .await?;
+
+            let note =
+                if cancelled { " Its pending reset was cancelled." } else { "" };
+
+            return reply(
+                cx,
+                format!("Slowmode in <#{channel_id}> is now {seconds}s.{note}"),
+            )
+            .await;
+        };
+
+        let until = expires_at(duration)?;
+
+        let id = timed_actions::schedule(
+            &cx.app.db,
+            guild,
+            Target::Slowmode {
+                channel_id: as_i64(channel_id.get()),
+                previous: i32::from(previous),
+            },
+            moderator,
+            reason,
+            until,
+        )
+        .await?;
+
+        reply(
+            cx,
+            format!(
+                "Slowmode in <#{channel_id}> is now {seconds}s for {}; it resets \
+                 <t:{}:R>. This is timed action #{id}.",
+                format_duration(duration),
+                until.as_second(),
+            ),
+        )
+        .await
+    }
+}
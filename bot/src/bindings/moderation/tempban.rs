@@ -0,0 +1,140 @@
+use async_trait::async_trait;
+use serenity::all::{
+    CommandOptionType,
+    CreateCommand,
+    CreateCommandOption,
+    CreateEmbedFooter,
+    EditInteractionResponse,
+    Permissions,
+    User,
+};
+use zayden_app::services::timed_actions::{self, Target};
+use zayden_core::error::CoreError;
+use zayden_core::{
+    HandlerError,
+    InvocationCtx,
+    ModuleCommand,
+    as_i64,
+    format_duration,
+    optional_option,
+    parse_options,
+    required_option,
+};
+
+use super::infraction::{action_embed, send_user_message};
+use super::timed::{expires_at, parse_for, reply};
+use super::{InfractionKind, NO_REASON, NewInfraction, appeal};
+
+const TEMPBAN_POINTS: i32 = 1;
+
+pub(super) struct Tempban;
+
+#[async_trait]
+impl ModuleCommand for Tempban {
+    fn module(&self) -> Option<&'static str> {
+        Some("moderation")
+    }
+
+    fn definition(&self) -> CreateCommand<'static> {
+        CreateCommand::new("tempban")
+            .description("Ban a user for a while")
+            .default_member_permissions(Permissions::BAN_MEMBERS)
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::User,
+                    "user",
+                    "The user to ban",
+                )
+                .required(true),
+            )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "for",
+                    "How long, e.g. 12h, 7d or 1d12h",
+                )
+                .required(true),
+            )
+            .add_option(CreateCommandOption::new(
+                CommandOptionType::String,
+                "reason",
+                "The reason for the ban",
+            ))
+    }
+
+    async fn run(&self, cx: &InvocationCtx<'_>) -> Result<(), HandlerError> {
+        cx.interaction.defer(&cx.ctx.http).await?;
+
+        let guild_id = cx.interaction.guild_id.ok_or(CoreError::MissingGuildId)?;
+
+        let options = cx.interaction.data.options();
+        let mut options = parse_options(options);
+
+        let user: &User = required_option(&mut options, "user")?;
+        let input: &str = required_option(&mut options, "for")?;
+        let reason =
+            optional_option::<&str, _>(&mut options, "reason").unwrap_or(NO_REASON);
+
+        let duration = match parse_for(input) {
+            Ok(duration) => duration,
+            Err(message) => return reply(cx, message).await,
+        };
+        let until = expires_at(duration)?;
+        let length = format_duration(duration);
+
+        let guild_name = guild_id.to_partial_guild(&cx.ctx.http).await?.name;
+        let desc = if reason == NO_REASON {
+            format!("You have been banned from {guild_name} for {length}.")
+        } else {
+            format!(
+                "You have been banned from {guild_name} for {length} for the \
+                 following reason:\n{reason}"
+            )
+        };
+
+        let appeal = appeal::button_for(&cx.app, guild_id).await?;
+        let _ =
+            send_user_message(cx.ctx, user.id, InfractionKind::Ban, desc, appeal)
+                .await;
+
+        guild_id.ban(&cx.ctx.http, user.id, 0, Some(reason)).await?;
+
+        let infraction_id = NewInfraction {
+            guild_id,
+            target_id: user.id,
+            target_username: user.name.as_str(),
+            kind: InfractionKind::Ban,
+            moderator_id: cx.interaction.user.id,
+            moderator_username: cx.interaction.user.name.as_str(),
+            points: TEMPBAN_POINTS,
+            reason,
+            timeout: None,
+        }
+        .insert(&cx.app.db)
+        .await?;
+
+        let id = timed_actions::schedule(
+            &cx.app.db,
+            as_i64(guild_id.get()),
+            Target::Ban {
+                user_id: as_i64(user.id.get()),
+                infraction_id: Some(infraction_id),
+            },
+            as_i64(cx.interaction.user.id.get()),
+            reason,
+            until,
+        )
+        .await?;
+
+        let embed = action_embed(user, &format!("banned for {length}"), reason)
+            .footer(CreateEmbedFooter::new(format!(
+                "Timed action #{id} · /moderation cancel to keep the ban"
+            )));
+
+        cx.interaction
+            .edit_response(&cx.ctx.http, EditInteractionResponse::new().embed(embed))
+            .await?;
+
+        Ok(())
+    }
+}
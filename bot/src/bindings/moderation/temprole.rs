@@ -0,0 +1,134 @@
+use async_trait::async_trait;
+use serenity::all::{
+    CommandOptionType,
+    CreateCommand,
+    CreateCommandOption,
+    Permissions,
+    Role,
+    User,
+};
+use zayden_app::services::timed_actions::{self, Target};
+use zayden_core::error::CoreError;
+use zayden_core::{
+    HandlerError,
+    InvocationCtx,
+    ModuleCommand,
+    as_i64,
+    format_duration,
+    optional_option,
+    parse_options,
+    required_option,
+};
+
+use super::NO_REASON;
+use super::timed::{expires_at, parse_for, reply};
+
+pub(super) struct Temprole;
+
+#[async_trait]
+impl ModuleCommand for Temprole {
+    fn module(&self) -> Option<&'static str> {
+        Some("moderation")
+    }
+
+    fn definition(&self) -> CreateCommand<'static> {
+        CreateCommand::new("temprole")
+            .description("Give a user a role for a while")
+            .default_member_permissions(Permissions::MANAGE_ROLES)
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::User,
+                    "user",
+                    "The user to give the role to",
+                )
+                .required(true),
+            )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::Role,
+                    "role",
+                    "The role to give",
+                )
+                .required(true),
+            )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "for",
+                    "How long, e.g. 12h, 7d or 1d12h",
+                )
+                .required(true),
+            )
+            .add_option(CreateCommandOption::new(
+                CommandOptionType::String,
+                "reason",
+                "Why they have the role",
+            ))
+    }
+
+    async fn run(&self, cx: &InvocationCtx<'_>) -> Result<(), HandlerError> {
+        cx.interaction.defer_ephemeral(&cx.ctx.http).await?;
+
+        let guild_id = cx.interaction.guild_id.ok_or(CoreError::MissingGuildId)?;
+
+        let options = cx.interaction.data.options();
+        let mut options = parse_options(options);
+
+        let user: &User = required_option(&mut options, "user")?;
+        let role: &Role = required_option(&mut options, "role")?;
+        let input: &str = required_option(&mut options, "for")?;
+        let reason =
+            optional_option::<&str, _>(&mut options, "reason").unwrap_or(NO_REASON);
+
+        let duration = match parse_for(input) {
+            Ok(duration) => duration,
+            Err(message) => return reply(cx, message).await,
+        };
+        let until = expires_at(duration)?;
+
+        // Taking the role away at the end would strip one they had anyway.
+        let member = guild_id.member(&cx.ctx.http, user.id).await?;
+        if member.roles.contains(&role.id) {
+            return reply(
+                cx,
+                format!(
+                    "<@{}> already has <@&{}>. Remove it first to give it for a \
+                     while.",
+                    user.id, role.id
+                ),
+            )
+            .await;
+        }
+
+        cx.ctx
+            .http
+            .add_member_role(guild_id, user.id, role.id, Some(reason))
+            .await?;
+
+        let id = timed_actions::schedule(
+            &cx.app.db,
+            as_i64(guild_id.get()),
+            Target::Role {
+                user_id: as_i64(user.id.get()),
+                role_id: as_i64(role.id.get()),
+            },
+            as_i64(cx.interaction.user.id.get()),
+            reason,
+            until,
+        )
+        .await?;
+
+        reply(
+            cx,
+            format!(
+                "Gave <@{}> <@&{}> for {}; it comes off <t:{}:R>. This is timed \
+                 action #{id}.",
+                user.id,
+                role.id,
+                format_duration(duration),
+                until.as_second(),
+            ),
+        )
+        .await
+    }
+}
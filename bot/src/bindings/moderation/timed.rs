@@ -0,0 +1,314 @@
+//! Timed moderation actions: the `/moderation` command that lists and cancels
+//! them, and the sweep that undoes them when they run out. The records live
+//! in [`zayden_app::services::timed_actions`].
+
+use async_trait::async_trait;
+use jiff::{SignedDuration, Timestamp};
+use serenity::all::{
+    ChannelId,
+    CommandOptionType,
+    CreateCommand,
+    CreateCommandOption,
+    CreateEmbed,
+    EditChannel,
+    EditInteractionResponse,
+    GuildId,
+    Http,
+    Permissions,
+    RoleId,
+    UserId,
+};
+use serenity::nonmax::NonMaxU16;
+use sqlx::PgPool;
+use tracing::warn;
+use zayden_app::services::timed_actions::{self, Target, TimedAction};
+use zayden_core::error::CoreError;
+use zayden_core::{
+    HandlerError,
+    InvocationCtx,
+    ModuleCommand,
+    as_i64,
+    as_u64,
+    is_not_found,
+    optional_option,
+    parse_duration,
+    parse_options,
+    parse_subcommand,
+    required_option,
+};
+
+/// Longest a timed action can last.
+const MAX_DURATION: SignedDuration = SignedDuration::from_hours(365 * 24);
+
+const EXPIRED_REASON: &str = "Timed action expired";
+
+/// Parses a command's `for` option, or says what's wrong with it.
+pub(super) fn parse_for(input: &str) -> Result<SignedDuration, String> {
+    match parse_duration(input) {
+        Some(duration) if duration <= MAX_DURATION => Ok(duration),
+        Some(_) => Err("Timed actions can last at most 365 days.".to_string()),
+        None => Err(format!(
+            "`{input}` isn't a duration. Use something like `30m`, `12h`, `7d` or \
+             `1d12h`."
+        )),
+    }
+}
+
+pub(super) fn expires_at(
+    duration: SignedDuration,
+) -> Result<Timestamp, HandlerError> {
+    Ok(Timestamp::now()
+        .checked_add(duration)
+        .map_err(|e| CoreError::Other(e.to_string()))?)
+}
+
+pub(super) async fn reply(
+    cx: &InvocationCtx<'_>,
+    content: impl Into<String>,
+) -> Result<(), HandlerError> {
+    cx.interaction
+        .edit_response(
+            &cx.ctx.http,
+            EditInteractionResponse::new().content(content.into()),
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// Undoes the action on Discord. A 404 counts as undone: the member was
+/// unbanned by hand or left, or the role or channel is gone.
+async fn reverse(
+    http: &Http,
+    action: &TimedAction,
+    reason: &str,
+) -> serenity::Result<()> {
+    match undo(http, action, reason).await {
+        Err(e) if is_not_found(&e) => Ok(()),
+        result => result,
+    }
+}
+
+async fn undo(
+    http: &Http,
+    action: &TimedAction,
+    reason: &str,
+) -> serenity::Result<()> {
+    let guild_id = GuildId::new(as_u64(action.guild_id));
+
+    match action.target {
+        Target::Ban { user_id, .. } => {
+            guild_id.unban(http, UserId::new(as_u64(user_id)), Some(reason)).await
+        },
+        Target::Role { user_id, role_id } => {
+            http.remove_member_role(
+                guild_id,
+                UserId::new(as_u64(user_id)),
+                RoleId::new(as_u64(role_id)),
+                Some(reason),
+            )
+            .await
+        },
+        Target::Slowmode { channel_id, previous } => {
+            let previous = u16::try_from(previous)
+                .ok()
+                .and_then(NonMaxU16::new)
+                .unwrap_or(NonMaxU16::ZERO);
+
+            ChannelId::new(as_u64(channel_id))
+                .edit(
+                    http,
+                    EditChannel::new()
+                        .rate_limit_per_user(previous)
+                        .audit_log_reason(reason),
+                )
+                .await
+                .map(|_channel| ())
+        },
+    }
+}
+
+/// Undoes every timed action that has run out. A failed one is retried a
+/// few minutes later, up to [`timed_actions::MAX_FAILURES`] times, and then
+/// shows as failed in `/moderation pending`. Returns how many were undone.
+pub(crate) async fn expiry_sweep(http: &Http, pool: &PgPool) -> sqlx::Result<usize> {
+    let mut undone = 0;
+
+    for action in timed_actions::due(pool).await? {
+        match reverse(http, &action, EXPIRED_REASON).await {
+            Ok(()) => {
+                timed_actions::complete(pool, &action, EXPIRED_REASON).await?;
+                undone += 1;
+            },
+            Err(e) => {
+                let given_up =
+                    timed_actions::fail(pool, action.id, &e.to_string()).await?;
+
+                warn!(
+                    id = action.id,
+                    guild = action.guild_id,
+                    given_up,
+                    error = %e,
+                    "failed to undo timed action"
+                );
+            },
+        }
+    }
+
+    Ok(undone)
+}
+
+fn describe(action: &TimedAction) -> String {
+    let what = match action.target {
+        Target::Ban { user_id, .. } => format!("Ban of <@{user_id}>"),
+        Target::Role { user_id, role_id } => {
+            format!("<@&{role_id}> on <@{user_id}>")
+        },
+        Target::Slowmode { channel_id, previous } => {
+            format!("Slowmode in <#{channel_id}>, back to {previous}s")
+        },
+    };
+
+    let mut description = format!(
+        "{what}\n**Ends:** <t:{}:R>\n**Moderator:** <@{}>\n**Reason:** {}",
+        action.expires_at.as_second(),
+        action.moderator_id,
+        action.reason,
+    );
+
+    if action.given_up() {
+        description.push_str(&format!(
+            "\n**Failed:** {}. Undo it with `/moderation cancel undo:True` once \
+             that's fixed.",
+            action.last_error.as_deref().unwrap_or("unknown error"),
+        ));
+    }
+
+    description
+}
+
+pub(super) struct ModerationCommand;
+
+#[async_trait]
+impl ModuleCommand for ModerationCommand {
+    fn module(&self) -> Option<&'static str> {
+        Some("moderation")
+    }
+
+    fn definition(&self) -> CreateCommand<'static> {
+        let pending = CreateCommandOption::new(
+            CommandOptionType::SubCommand,
+            "pending",
+            "List the tempbans, timed roles and slowmodes waiting to run out",
+        );
+
+        let cancel = CreateCommandOption::new(
+            CommandOptionType::SubCommand,
+            "cancel",
+            "Stop a timed action from running out",
+        )
+        .add_sub_option(
+            CreateCommandOption::new(
+                CommandOptionType::Integer,
+                "id",
+                "The action's number from /moderation pending",
+            )
+            .min_int_value(1)
+            .required(true),
+        )
+        .add_sub_option(CreateCommandOption::new(
+            CommandOptionType::Boolean,
+            "undo",
+            "Undo it now instead of keeping it for good",
+        ));
+
+        CreateCommand::new("moderation")
+            .description("Manage timed moderation actions")
+            .default_member_permissions(Permissions::MODERATE_MEMBERS)
+            .add_option(pending)
+            .add_option(cancel)
+    }
+
+    async fn run(&self, cx: &InvocationCtx<'_>) -> Result<(), HandlerError> {
+        cx.interaction.defer_ephemeral(&cx.ctx.http).await?;
+
+        let guild_id = cx.interaction.guild_id.ok_or(CoreError::MissingGuildId)?;
+
+        let (name, sub_options) = parse_subcommand(cx.interaction.data.options())?;
+        let mut options = parse_options(sub_options);
+
+        match name {
+            "pending" => pending(cx, guild_id).await,
+            "cancel" => {
+                let id: i64 = required_option(&mut options, "id")?;
+                let undo = optional_option::<bool, _>(&mut options, "undo")
+                    .unwrap_or_default();
+
+                cancel(cx, guild_id, i32::try_from(id).unwrap_or(i32::MAX), undo)
+                    .await
+            },
+            other => Err(HandlerError::from_respond(CoreError::Other(format!(
+                "unexpected moderation subcommand: {other}"
+            )))),
+        }
+    }
+}
+
+async fn pending(
+    cx: &InvocationCtx<'_>,
+    guild_id: GuildId,
+) -> Result<(), HandlerError> {
+    let actions = timed_actions::pending(&cx.app.db, as_i64(guild_id.get())).await?;
+
+    if actions.is_empty() {
+        return reply(cx, "No timed actions are waiting to run out.").await;
+    }
+
+    let fields = actions
+        .iter()
+        .map(|action| (format!("#{}", action.id), describe(action), false));
+
+    let embed = CreateEmbed::new()
+        .title("Pending timed actions")
+        .description("Cancel one with `/moderation cancel`.")
+        .fields(fields);
+
+    cx.interaction
+        .edit_response(&cx.ctx.http, EditInteractionResponse::new().embed(embed))
+        .await?;
+
+    Ok(())
+}
+
+async fn cancel(
+    cx: &InvocationCtx<'_>,
+    guild_id: GuildId,
+    id: i32,
+    undo: bool,
+) -> Result<(), HandlerError> {
+    let guild = as_i64(guild_id.get());
+    let moderator = &cx.interaction.user;
+
+    let Some(action) = timed_actions::get_pending(&cx.app.db, guild, id).await?
+    else {
+        return reply(cx, format!("There's no pending timed action #{id}.")).await;
+    };
+
+    if undo {
+        let note = format!("Lifted early by {}", moderator.name);
+        reverse(&cx.ctx.http, &action, &note).await?;
+        timed_actions::complete(&cx.app.db, &action, &note).await?;
+
+        return reply(cx, format!("Undid timed action #{id}.")).await;
+    }
+
+    timed_actions::cancel(&cx.app.db, guild, id, as_i64(moderator.id.get())).await?;
+
+    let kept = match action.target {
+        Target::Ban { .. } => "the ban is now permanent",
+        Target::Role { .. } => "they keep the role",
+        Target::Slowmode { .. } => "the slowmode stays",
+    };
+
+    reply(cx, format!("Cancelled timed action #{id}; {kept}.")).await
+}
@@ -0,0 +1,125 @@
+use async_trait::async_trait;
+use serenity::all::{
+    CommandOptionType,
+    CreateCommand,
+    CreateCommandOption,
+    EditInteractionResponse,
+};
+use zayden_app::entitlement::{GiftError, GiftGrant, TRIAL_DAYS, Tier};
+use zayden_core::error::CoreError;
+use zayden_core::{
+    HandlerError,
+    InvocationCtx,
+    ModuleCommand,
+    parse_options,
+    parse_subcommand,
+    required_option,
+};
+
+pub(super) struct Premium;
+
+#[async_trait]
+impl ModuleCommand for Premium {
+    fn module(&self) -> Option<&'static str> {
+        Some("premium")
+    }
+
+    fn definition(&self) -> CreateCommand<'static> {
+        let redeem = CreateCommandOption::new(
+            CommandOptionType::SubCommand,
+            "redeem",
+            "Redeem a gift code for premium on your account",
+        )
+        .add_sub_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "code",
+                "The gift code, e.g. ABCDE-FGHJK",
+            )
+            .required(true),
+        );
+
+        let trial = CreateCommandOption::new(
+            CommandOptionType::SubCommand,
+            "trial",
+            format!("Start this server's one-time {TRIAL_DAYS}-day premium trial"),
+        );
+
+        CreateCommand::new("premium")
+            .description("Redeem gift codes and start trials")
+            .add_option(redeem)
+            .add_option(trial)
+    }
+
+    async fn run(&self, cx: &InvocationCtx<'_>) -> Result<(), HandlerError> {
+        cx.interaction.defer_ephemeral(&cx.ctx.http).await?;
+
+        let (name, sub_options) = parse_subcommand(cx.interaction.data.options())?;
+        let mut options = parse_options(sub_options);
+
+        let result = match name {
+            "redeem" => {
+                let code: &str = required_option(&mut options, "code")?;
+                cx.app
+                    .entitlements
+                    .redeem_gift_code(cx.interaction.user.id.get(), code)
+                    .await
+                    .map(|grant| format!("Code redeemed: {}", describe(grant)))
+            },
+            "trial" => {
+                let guild_id =
+                    cx.interaction.guild_id.ok_or(CoreError::MissingGuildId)?;
+
+                let manages = cx.interaction.member.as_ref().is_some_and(|m| {
+                    m.permissions.is_some_and(|p| p.manage_guild())
+                });
+                if !manages {
+                    cx.interaction
+                        .edit_response(
+                            &cx.ctx.http,
+                            EditInteractionResponse::new().content(
+                                "You need the Manage Server permission to start a trial.",
+                            ),
+                        )
+                        .await?;
+                    return Ok(());
+                }
+
+                cx.app
+                    .entitlements
+                    .start_trial(guild_id.get(), cx.interaction.user.id.get())
+                    .await
+                    .map(|grant| format!("Trial started: {}", describe(grant)))
+            },
+            other => {
+                return Err(HandlerError::from_respond(CoreError::Other(format!(
+                    "unexpected premium subcommand: {other}"
+                ))));
+            },
+        };
+
+        let content = match result {
+            Ok(content) => content,
+            Err(GiftError::Sqlx(e)) => return Err(HandlerError::Database(e)),
+            Err(e) => e.to_string(),
+        };
+
+        cx.interaction
+            .edit_response(
+                &cx.ctx.http,
+                EditInteractionResponse::new().content(content),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+fn describe(grant: GiftGrant) -> String {
+    let tier = match grant.tier {
+        Tier::Free => "Free",
+        Tier::Pro => "Pro",
+        Tier::Ultra => "Ultra",
+    };
+    format!("**{tier}** until <t:{}:D>.", grant.expires_at.as_second())
+}
@@ -0,0 +1,9 @@
+mod command;
+
+use command::Premium;
+
+use crate::RegistryBuilder;
+
+pub fn register(builder: &mut RegistryBuilder) {
+    builder.add_command(Premium);
+}
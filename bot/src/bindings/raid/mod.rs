@@ -0,0 +1,68 @@
+use std::borrow::Cow;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use raid::RaidJoin;
+use raid::member_join::JOINER_REASON;
+use serenity::all::{CreateCommand, UserId};
+use zayden_app::config::RaidAction;
+use zayden_app::state::AppState;
+use zayden_core::ctx::InvocationCtx;
+use zayden_core::error::HandlerError;
+use zayden_core::module::ModuleCommand;
+
+use crate::RegistryBuilder;
+use crate::bindings::moderation::{InfractionKind, NewInfraction};
+
+const RAID_POINTS: i32 = 0;
+const RAID_MODERATOR: &str = "Zayden (Raid protection)";
+
+pub fn register(builder: &mut RegistryBuilder) {
+    builder.add_command(Lockdown);
+}
+
+pub struct Lockdown;
+
+#[async_trait]
+impl ModuleCommand for Lockdown {
+    fn name(&self) -> Cow<'static, str> {
+        Cow::Borrowed("lockdown")
+    }
+
+    fn module(&self) -> Option<&'static str> {
+        Some("raid")
+    }
+
+    fn definition(&self) -> CreateCommand<'static> {
+        raid::Lockdown::register()
+    }
+
+    async fn run(&self, cx: &InvocationCtx<'_>) -> Result<(), HandlerError> {
+        raid::Lockdown::run(cx).await?;
+        Ok(())
+    }
+}
+
+/// Puts a lockdown timeout or kick on the joiner's record. Joiners who were
+/// only reported are left off it.
+pub async fn record_join(app: &Arc<AppState>, join: &RaidJoin) -> sqlx::Result<()> {
+    let kind = match join.action {
+        RaidAction::None => return Ok(()),
+        RaidAction::Timeout => InfractionKind::Mute,
+        RaidAction::Kick => InfractionKind::Kick,
+    };
+
+    NewInfraction {
+        guild_id: join.guild_id,
+        target_id: join.user_id,
+        target_username: &join.username,
+        kind,
+        moderator_id: UserId::new(app.zayden_id),
+        moderator_username: RAID_MODERATOR,
+        points: RAID_POINTS,
+        reason: JOINER_REASON,
+        timeout: join.timeout,
+    }
+    .record(&app.db)
+    .await
+}
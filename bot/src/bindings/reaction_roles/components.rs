@@ -0,0 +1,22 @@
+use std::borrow::Cow;
+
+use async_trait::async_trait;
+use reaction_roles::{PANEL_PREFIX, RolePanelComponent};
+use zayden_core::ctx::ComponentCtx;
+use zayden_core::error::HandlerError;
+use zayden_core::module::ModuleComponent;
+use zayden_core::scope::IdMatch;
+
+pub(super) struct RolePanel;
+
+#[async_trait]
+impl ModuleComponent for RolePanel {
+    fn id_match(&self) -> IdMatch {
+        IdMatch::Prefix(Cow::Borrowed(PANEL_PREFIX))
+    }
+
+    async fn run(&self, cx: &ComponentCtx<'_>) -> Result<(), HandlerError> {
+        RolePanelComponent::run(&cx.ctx.http, cx.interaction, &cx.app.db).await?;
+        Ok(())
+    }
+}
@@ -1,9 +1,16 @@
-pub use slash_command::ReactionRoleCommand;
+pub use slash_command::{ReactionRoleCommand, RolePanelCommand};
 
+mod components;
 pub mod slash_command;
 
 use crate::RegistryBuilder;
+use crate::registry::OverlapError;
 
-pub fn register(builder: &mut RegistryBuilder) {
-    builder.add_command(ReactionRoleCommand);
+pub fn register(builder: &mut RegistryBuilder) -> Result<(), OverlapError> {
+    builder
+        .add_command(ReactionRoleCommand)
+        .add_command(RolePanelCommand)
+        .add_component(components::RolePanel)?;
+
+    Ok(())
 }
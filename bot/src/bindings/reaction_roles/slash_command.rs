@@ -26,3 +26,26 @@ impl ModuleCommand for ReactionRoleCommand {
         Ok(())
     }
 }
+
+pub struct RolePanelCommand;
+
+#[async_trait]
+impl ModuleCommand for RolePanelCommand {
+    fn name(&self) -> Cow<'static, str> {
+        Cow::Borrowed("role_panel")
+    }
+
+    fn definition(&self) -> CreateCommand<'static> {
+        reaction_roles::RolePanelCommand::register()
+    }
+
+    async fn run(&self, cx: &InvocationCtx<'_>) -> Result<(), HandlerError> {
+        reaction_roles::RolePanelCommand::run(
+            &cx.ctx.http,
+            cx.interaction,
+            &cx.app.db,
+        )
+        .await?;
+        Ok(())
+    }
+}
@@ -1,64 +1,86 @@
 use std::borrow::Cow;
 
 use async_trait::async_trait;
-use suggestions::Suggestions;
+use suggestions::{STATUS_PREFIX, SuggestionStatus, Suggestions, SuggestionsError};
 use zayden_core::ctx::{ComponentCtx, ModalCtx};
 use zayden_core::error::HandlerError;
 use zayden_core::module::{ModuleComponent, ModuleModal};
 use zayden_core::scope::IdMatch;
 
-pub(super) struct SuggestionsAccept;
+fn parse_status(custom_id: &str) -> Result<SuggestionStatus, SuggestionsError> {
+    let id = custom_id.strip_prefix(STATUS_PREFIX).unwrap_or_default();
+
+    id.parse().map_err(|()| {
+        SuggestionsError::Internal(format!("unknown suggestion status: {id}"))
+    })
+}
+
+pub(super) struct SuggestionsStatus;
 
 #[async_trait]
-impl ModuleComponent for SuggestionsAccept {
+impl ModuleComponent for SuggestionsStatus {
     fn id_match(&self) -> IdMatch {
-        IdMatch::Exact(Cow::Borrowed("suggestions_accept"))
+        IdMatch::Prefix(Cow::Borrowed(STATUS_PREFIX))
     }
 
     async fn run(&self, cx: &ComponentCtx<'_>) -> Result<(), HandlerError> {
-        Suggestions::components(&cx.ctx.http, cx.interaction, true).await?;
+        let status = parse_status(&cx.interaction.data.custom_id)?;
+        Suggestions::components(&cx.ctx.http, cx.interaction, status).await?;
         Ok(())
     }
 }
 
-pub(super) struct SuggestionsReject;
+pub(super) struct SuggestionsStatusModal;
 
 #[async_trait]
-impl ModuleComponent for SuggestionsReject {
+impl ModuleModal for SuggestionsStatusModal {
     fn id_match(&self) -> IdMatch {
-        IdMatch::Exact(Cow::Borrowed("suggestions_reject"))
+        IdMatch::Prefix(Cow::Borrowed(STATUS_PREFIX))
     }
 
-    async fn run(&self, cx: &ComponentCtx<'_>) -> Result<(), HandlerError> {
-        Suggestions::components(&cx.ctx.http, cx.interaction, false).await?;
+    async fn run(&self, cx: &ModalCtx<'_>) -> Result<(), HandlerError> {
+        let status = parse_status(&cx.interaction.data.custom_id)?;
+        Suggestions::modal(&cx.ctx.http, cx.interaction, &cx.app.db, status).await?;
         Ok(())
     }
 }
 
-pub(super) struct SuggestionsAcceptModal;
+// Review messages posted before statuses existed carry these buttons.
+
+pub(super) struct SuggestionsAccept;
 
 #[async_trait]
-impl ModuleModal for SuggestionsAcceptModal {
+impl ModuleComponent for SuggestionsAccept {
     fn id_match(&self) -> IdMatch {
         IdMatch::Exact(Cow::Borrowed("suggestions_accept"))
     }
 
-    async fn run(&self, cx: &ModalCtx<'_>) -> Result<(), HandlerError> {
-        Suggestions::modal(&cx.ctx.http, cx.interaction, true).await?;
+    async fn run(&self, cx: &ComponentCtx<'_>) -> Result<(), HandlerError> {
+        Suggestions::components(
+            &cx.ctx.http,
+            cx.interaction,
+            SuggestionStatus::Accepted,
+        )
+        .await?;
         Ok(())
     }
 }
 
-pub(super) struct SuggestionsRejectModal;
+pub(super) struct SuggestionsReject;
 
 #[async_trait]
-impl ModuleModal for SuggestionsRejectModal {
+impl ModuleComponent for SuggestionsReject {
     fn id_match(&self) -> IdMatch {
         IdMatch::Exact(Cow::Borrowed("suggestions_reject"))
     }
 
-    async fn run(&self, cx: &ModalCtx<'_>) -> Result<(), HandlerError> {
-        Suggestions::modal(&cx.ctx.http, cx.interaction, false).await?;
+    async fn run(&self, cx: &ComponentCtx<'_>) -> Result<(), HandlerError> {
+        Suggestions::components(
+            &cx.ctx.http,
+            cx.interaction,
+            SuggestionStatus::Denied,
+        )
+        .await?;
         Ok(())
     }
 }
@@ -1,7 +1,7 @@
 mod components;
 pub mod slash_command;
 
-pub use slash_command::FetchSuggestions;
+pub use slash_command::{FetchSuggestions, Suggest};
 
 use crate::RegistryBuilder;
 use crate::registry::OverlapError;
@@ -9,10 +9,11 @@ use crate::registry::OverlapError;
 pub fn register(builder: &mut RegistryBuilder) -> Result<(), OverlapError> {
     builder
         .add_command(FetchSuggestions)
+        .add_command(Suggest)
+        .add_component(components::SuggestionsStatus)?
         .add_component(components::SuggestionsAccept)?
         .add_component(components::SuggestionsReject)?
-        .add_modal(components::SuggestionsAcceptModal)?
-        .add_modal(components::SuggestionsRejectModal)?;
+        .add_modal(components::SuggestionsStatusModal)?;
 
     Ok(())
 }
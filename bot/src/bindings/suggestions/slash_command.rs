@@ -28,3 +28,27 @@ impl ModuleCommand for FetchSuggestions {
         Ok(())
     }
 }
+
+pub struct Suggest;
+
+#[async_trait]
+impl ModuleCommand for Suggest {
+    fn name(&self) -> Cow<'static, str> {
+        Cow::Borrowed("suggest")
+    }
+
+    fn definition(&self) -> serenity::all::CreateCommand<'static> {
+        suggestions::Suggest::register()
+    }
+
+    async fn run(&self, cx: &InvocationCtx<'_>) -> Result<(), HandlerError> {
+        suggestions::Suggest::run(
+            &cx.ctx.http,
+            cx.interaction,
+            &cx.app.db,
+            cx.interaction.data.options(),
+        )
+        .await?;
+        Ok(())
+    }
+}
@@ -10,6 +10,7 @@ use serenity::all::{
     CreateSelectMenuOption,
     InputTextStyle,
 };
+use ticket::components::CATEGORY_FORM_PREFIX;
 use ticket::{TicketComponent, TicketModal, TicketStores};
 use zayden_core::ctx::{ComponentCtx, ModalCtx};
 use zayden_core::error::HandlerError;
@@ -95,6 +96,36 @@ impl ModuleComponent for SupportClose {
     }
 }
 
+pub struct TicketPanel;
+
+#[async_trait]
+impl ModuleComponent for TicketPanel {
+    fn id_match(&self) -> IdMatch {
+        IdMatch::Exact(Cow::Borrowed("ticket_panel"))
+    }
+
+    async fn run(&self, cx: &ComponentCtx<'_>) -> Result<(), HandlerError> {
+        TicketComponent::ticket_panel(&cx.ctx.http, cx.interaction, &cx.app.db)
+            .await?;
+        Ok(())
+    }
+}
+
+pub struct SupportClaim;
+
+#[async_trait]
+impl ModuleComponent for SupportClaim {
+    fn id_match(&self) -> IdMatch {
+        IdMatch::Exact(Cow::Borrowed("support_claim"))
+    }
+
+    async fn run(&self, cx: &ComponentCtx<'_>) -> Result<(), HandlerError> {
+        TicketComponent::support_claim(&cx.ctx.http, cx.interaction, &cx.app.db)
+            .await?;
+        Ok(())
+    }
+}
+
 pub struct SupportFaq;
 
 #[async_trait]
@@ -138,3 +169,43 @@ impl ModuleModal for CreateTicketModal {
         Ok(())
     }
 }
+
+pub struct TicketCategoryModal;
+
+#[async_trait]
+impl ModuleModal for TicketCategoryModal {
+    fn id_match(&self) -> IdMatch {
+        IdMatch::Prefix(Cow::Borrowed(CATEGORY_FORM_PREFIX))
+    }
+
+    async fn run(&self, cx: &ModalCtx<'_>) -> Result<(), HandlerError> {
+        let stores = TicketStores {
+            support: &cx.app.settings.support,
+            ticket: &cx.app.settings.ticket,
+        };
+
+        TicketModal::category(&cx.ctx.http, cx.interaction, stores, &cx.app.db)
+            .await?;
+        Ok(())
+    }
+}
+
+pub struct SupportCloseModal;
+
+#[async_trait]
+impl ModuleModal for SupportCloseModal {
+    fn id_match(&self) -> IdMatch {
+        IdMatch::Exact(Cow::Borrowed("support_close_reason"))
+    }
+
+    async fn run(&self, cx: &ModalCtx<'_>) -> Result<(), HandlerError> {
+        let stores = TicketStores {
+            support: &cx.app.settings.support,
+            ticket: &cx.app.settings.ticket,
+        };
+
+        TicketModal::close_reason(&cx.ctx.http, cx.interaction, stores, &cx.app.db)
+            .await?;
+        Ok(())
+    }
+}
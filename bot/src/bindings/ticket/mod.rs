@@ -7,10 +7,14 @@ pub mod slash_commands;
 
 use components::{
     CreateTicketModal,
+    SupportClaim,
     SupportClose,
+    SupportCloseModal,
     SupportFaq,
     SupportTicket,
+    TicketCategoryModal,
     TicketCreate,
+    TicketPanel,
 };
 use slash_commands::{SupportCommand, TicketCommand};
 
@@ -19,10 +23,14 @@ pub fn register(builder: &mut RegistryBuilder) -> Result<(), OverlapError> {
         .add_command(TicketCommand)
         .add_command(SupportCommand)
         .add_component(TicketCreate)?
+        .add_component(TicketPanel)?
         .add_component(SupportTicket)?
+        .add_component(SupportClaim)?
         .add_component(SupportClose)?
         .add_component(SupportFaq)?
-        .add_modal(CreateTicketModal)?;
+        .add_modal(CreateTicketModal)?
+        .add_modal(SupportCloseModal)?
+        .add_modal(TicketCategoryModal)?;
 
     Ok(())
 }
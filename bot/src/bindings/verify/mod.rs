@@ -5,18 +5,28 @@ use serenity::all::{
     CommandOptionType,
     CreateCommand,
     CreateCommandOption,
+    GuildId,
+    Http,
     Permissions,
+    RoleId,
     User,
+    UserId,
 };
+use sqlx::PgPool;
+use tracing::{debug, warn};
+use zayden_app::services::rules;
 use zayden_core::{
     ComponentCtx,
     CoreError,
     HandlerError,
     IdMatch,
     InvocationCtx,
+    ModalCtx,
     ModuleCommand,
     ModuleComponent,
+    ModuleModal,
     SubCommandOptions,
+    as_u64,
     sole_option,
 };
 
@@ -52,6 +62,81 @@ impl ModuleComponent for Panel {
             &cx.ctx.http,
             cx.interaction,
             &cx.app.settings.roles,
+            &cx.app.db,
+        )
+        .await?;
+        Ok(())
+    }
+}
+
+pub struct CaptchaAnswer;
+
+#[async_trait]
+impl ModuleComponent for CaptchaAnswer {
+    fn id_match(&self) -> IdMatch {
+        IdMatch::Exact(Cow::Borrowed(verify::captcha::ANSWER))
+    }
+
+    async fn run(&self, cx: &ComponentCtx<'_>) -> Result<(), HandlerError> {
+        verify::captcha::open(&cx.ctx.http, cx.interaction, &cx.app.db).await?;
+        Ok(())
+    }
+}
+
+pub struct CaptchaModal;
+
+#[async_trait]
+impl ModuleModal for CaptchaModal {
+    fn id_match(&self) -> IdMatch {
+        IdMatch::Exact(Cow::Borrowed(verify::captcha::MODAL))
+    }
+
+    async fn run(&self, cx: &ModalCtx<'_>) -> Result<(), HandlerError> {
+        verify::captcha::submit(
+            &cx.ctx.http,
+            cx.interaction,
+            &cx.app.settings.roles,
+            &cx.app.db,
+        )
+        .await?;
+        Ok(())
+    }
+}
+
+pub struct AcceptRules;
+
+#[async_trait]
+impl ModuleComponent for AcceptRules {
+    fn id_match(&self) -> IdMatch {
+        IdMatch::Prefix(Cow::Borrowed(verify::ACCEPT))
+    }
+
+    async fn run(&self, cx: &ComponentCtx<'_>) -> Result<(), HandlerError> {
+        verify::Panel::accept(
+            &cx.ctx.http,
+            cx.interaction,
+            &cx.app.settings.roles,
+            &cx.app.db,
+        )
+        .await?;
+        Ok(())
+    }
+}
+
+pub struct RulesQuiz;
+
+#[async_trait]
+impl ModuleModal for RulesQuiz {
+    fn id_match(&self) -> IdMatch {
+        IdMatch::Prefix(Cow::Borrowed(verify::QUIZ))
+    }
+
+    async fn run(&self, cx: &ModalCtx<'_>) -> Result<(), HandlerError> {
+        verify::Panel::quiz_submit(
+            &cx.ctx.http,
+            cx.interaction,
+            &cx.app.settings.roles,
+            &cx.app.db,
         )
         .await?;
         Ok(())
@@ -108,7 +193,65 @@ impl ModuleCommand for ManVerify {
     }
 }
 
+/// Takes the verified role back from members who accepted rules that have
+/// since changed materially, in guilds that ask for it. Each member is only
+/// re-prompted once per rules version; clicking Verify again shows them the
+/// current rules.
+pub(crate) async fn reverify_sweep(http: &Http, pool: &PgPool) -> sqlx::Result<()> {
+    // Rules edited on the dashboard or with `/rules` only get a new version
+    // when someone next reads them, so take it here first.
+    for guild_id in rules::reverify_guilds(pool).await? {
+        rules::current(pool, guild_id).await?;
+    }
+
+    for stale in rules::stale_acceptances(pool).await? {
+        let guild_id = GuildId::new(as_u64(stale.guild_id));
+        let user_id = UserId::new(as_u64(stale.user_id));
+
+        if let Some(role) = stale.verified_role_id {
+            match http
+                .remove_member_role(
+                    guild_id,
+                    user_id,
+                    RoleId::new(as_u64(role)),
+                    Some("Server rules changed; verify again to accept them"),
+                )
+                .await
+            {
+                Ok(()) => {
+                    debug!(
+                        guild = guild_id.get(),
+                        user = user_id.get(),
+                        version = stale.version,
+                        "re-prompted member for changed rules"
+                    );
+                },
+                // The member may have left; either way there is nothing to retry.
+                Err(e) => {
+                    warn!(
+                        error = ?e,
+                        guild = guild_id.get(),
+                        user = user_id.get(),
+                        "failed to remove verified role after a rules change"
+                    );
+                },
+            }
+        }
+
+        rules::mark_reprompted(pool, stale).await?;
+    }
+
+    Ok(())
+}
+
 pub fn register(builder: &mut RegistryBuilder) -> Result<(), OverlapError> {
-    builder.add_command(Panel).add_command(ManVerify).add_component(Panel)?;
+    builder
+        .add_command(Panel)
+        .add_command(ManVerify)
+        .add_component(Panel)?
+        .add_component(CaptchaAnswer)?
+        .add_component(AcceptRules)?
+        .add_modal(CaptchaModal)?
+        .add_modal(RulesQuiz)?;
     Ok(())
 }
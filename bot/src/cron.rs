@@ -6,16 +6,13 @@ use jiff::tz::TimeZone;
 use jiff::{SignedDuration, Timestamp, Zoned};
 use serenity::all::Context;
 use sqlx::PgPool;
+use ticket::TicketStores;
 use tokio::sync::RwLock;
 use tokio::time::sleep;
 use tracing::{debug, error, info};
-use zayden_core::{
-    ActionFn,
-    CronJob,
-    CronJobData,
-    earliest_pending,
-    prune_exhausted,
-};
+use zayden_app::services::scam_domains::ScamDomains;
+use zayden_app::services::{ops, threats};
+use zayden_core::{CronJob, CronJobData, earliest_pending_jobs, prune_exhausted};
 
 use crate::{BotState, Result};
 
@@ -44,6 +41,40 @@ impl EntitlementSweepCron {
     }
 }
 
+pub struct TicketSweepCron;
+
+impl TicketSweepCron {
+    pub fn cron_job() -> std::result::Result<CronJob, jiff_cron::error::Error> {
+        CronJob::new("ticket_sweep", "0 */5 * * * * *").map(|job| {
+            job.set_action(|ctx, pool| async move {
+                let app = {
+                    let data = ctx.data::<RwLock<BotState>>();
+                    let state = data.read().await;
+                    Arc::clone(&state.app)
+                };
+
+                let bot = match ctx.http.get_current_user().await {
+                    Ok(user) => user,
+                    Err(e) => {
+                        error!(error = ?e, "ticket sweep could not fetch the bot user");
+                        return;
+                    },
+                };
+
+                let stores = TicketStores {
+                    support: &app.settings.support,
+                    ticket: &app.settings.ticket,
+                };
+
+                if let Err(e) = ticket::lifecycle::sweep(&ctx.http, stores, &pool, &bot).await
+                {
+                    error!(error = ?e, "ticket sweep failed");
+                }
+            })
+        })
+    }
+}
+
 pub async fn start_cron_jobs(ctx: Context, pool: PgPool) {
     if let Err(e) = run_cron_jobs_loop(ctx, pool).await {
         error!("Error starting cron jobs: {e:?}");
@@ -76,7 +107,7 @@ async fn run_cron_jobs_loop(ctx: Context, pool: PgPool) -> Result<()> {
         if !pending_jobs.is_empty() {
             let futures_iter = pending_jobs
                 .into_iter()
-                .map(|(_, action)| (action)(ctx.clone(), pool.clone()));
+                .map(|(_, job)| run_job(job, ctx.clone(), pool.clone()));
 
             future::join_all(futures_iter).await;
         }
@@ -85,7 +116,7 @@ async fn run_cron_jobs_loop(ctx: Context, pool: PgPool) -> Result<()> {
     }
 }
 
-async fn pending_jobs(ctx: &Context) -> Vec<(Zoned, ActionFn)> {
+async fn pending_jobs(ctx: &Context) -> Vec<(Zoned, CronJob)> {
     let data = ctx.data::<RwLock<BotState>>();
 
     let now = Timestamp::now().to_zoned(TimeZone::UTC);
@@ -93,5 +124,187 @@ async fn pending_jobs(ctx: &Context) -> Vec<(Zoned, ActionFn)> {
     let mut state = data.write().await;
 
     prune_exhausted(state.jobs_mut(), &now);
-    earliest_pending(state.jobs(), &now)
+    earliest_pending_jobs(state.jobs(), &now)
+        .into_iter()
+        .map(|(run_time, job)| (run_time, job.clone()))
+        .collect()
+}
+
+/// Runs `job` and records the run for the admin console. One-shot jobs, like
+/// LFG reminders, are not recorded.
+async fn run_job(job: CronJob, ctx: Context, pool: PgPool) {
+    let started_at = Timestamp::now();
+    (job.action_fn)(ctx, pool.clone()).await;
+    let finished_at = Timestamp::now();
+
+    let Some(next_run) = job.next_run(&finished_at.to_zoned(TimeZone::UTC)) else {
+        return;
+    };
+
+    if let Err(e) = ops::record_cron_run(
+        &pool,
+        &job.id,
+        started_at,
+        finished_at,
+        Some(next_run.timestamp()),
+    )
+    .await
+    {
+        error!(error = ?e, job = %job.id, "failed to record cron run");
+    }
+}
+
+pub struct LogPruneCron;
+
+impl LogPruneCron {
+    /// Stored warnings and errors older than this are dropped.
+    const KEEP_DAYS: i32 = 14;
+
+    pub fn cron_job() -> std::result::Result<CronJob, jiff_cron::error::Error> {
+        CronJob::new("log_prune", "0 30 4 * * * *").map(|job| {
+            job.set_action(|_ctx, pool| async move {
+                match ops::prune_logs(&pool, Self::KEEP_DAYS).await {
+                    Ok(n) if n > 0 => info!(pruned = n, "log prune completed"),
+                    Ok(_) => {},
+                    Err(e) => error!(error = ?e, "log prune failed"),
+                }
+            })
+        })
+    }
+}
+
+pub struct HoneypotHitPruneCron;
+
+impl HoneypotHitPruneCron {
+    pub fn cron_job() -> std::result::Result<CronJob, jiff_cron::error::Error> {
+        CronJob::new("honeypot_hit_prune", "0 45 4 * * * *").map(|job| {
+            job.set_action(|_ctx, pool| async move {
+                match threats::prune_hits(&pool).await {
+                    Ok(n) if n > 0 => {
+                        info!(pruned = n, "honeypot hit prune completed")
+                    },
+                    Ok(_) => {},
+                    Err(e) => error!(error = ?e, "honeypot hit prune failed"),
+                }
+            })
+        })
+    }
+}
+
+pub struct LockdownSweepCron;
+
+impl LockdownSweepCron {
+    pub fn cron_job() -> std::result::Result<CronJob, jiff_cron::error::Error> {
+        CronJob::new("lockdown_sweep", "0 * * * * * *").map(|job| {
+            job.set_action(|ctx, pool| async move {
+                let app = {
+                    let data = ctx.data::<RwLock<BotState>>();
+                    let state = data.read().await;
+                    Arc::clone(&state.app)
+                };
+
+                match raid::lockdown::sweep(&ctx.http, &pool, &app.settings.raid)
+                    .await
+                {
+                    Ok(n) if n > 0 => info!(ended = n, "lockdown sweep completed"),
+                    Ok(_) => {},
+                    Err(e) => error!(error = ?e, "lockdown sweep failed"),
+                }
+            })
+        })
+    }
+}
+
+pub struct RoleSweepCron;
+
+impl RoleSweepCron {
+    pub fn cron_job() -> std::result::Result<CronJob, jiff_cron::error::Error> {
+        CronJob::new("role_sweep", "0 * * * * * *").map(|job| {
+            job.set_action(|ctx, pool| async move {
+                if let Err(e) =
+                    reaction_roles::panel::sync::sweep(&ctx.http, &pool).await
+                {
+                    error!(error = ?e, "role sweep failed");
+                }
+            })
+        })
+    }
+}
+
+pub struct TimedActionSweepCron;
+
+impl TimedActionSweepCron {
+    pub fn cron_job() -> std::result::Result<CronJob, jiff_cron::error::Error> {
+        CronJob::new("timed_action_sweep", "0 * * * * * *").map(|job| {
+            job.set_action(|ctx, pool| async move {
+                match crate::bindings::moderation::timed::expiry_sweep(
+                    &ctx.http, &pool,
+                )
+                .await
+                {
+                    Ok(n) if n > 0 => {
+                        info!(undone = n, "timed action sweep completed")
+                    },
+                    Ok(_) => {},
+                    Err(e) => error!(error = ?e, "timed action sweep failed"),
+                }
+            })
+        })
+    }
+}
+
+pub struct RulesPublishCron;
+
+impl RulesPublishCron {
+    pub fn cron_job() -> std::result::Result<CronJob, jiff_cron::error::Error> {
+        CronJob::new("rules_publish", "0 * * * * * *").map(|job| {
+            job.set_action(|ctx, pool| async move {
+                if let Err(e) = crate::bindings::moderation::rules::publish_sweep(
+                    &ctx.http, &pool,
+                )
+                .await
+                {
+                    error!(error = ?e, "rules publish sweep failed");
+                }
+            })
+        })
+    }
+}
+
+pub struct RulesReverifyCron;
+
+impl RulesReverifyCron {
+    pub fn cron_job() -> std::result::Result<CronJob, jiff_cron::error::Error> {
+        CronJob::new("rules_reverify", "0 */5 * * * * *").map(|job| {
+            job.set_action(|ctx, pool| async move {
+                if let Err(e) =
+                    crate::bindings::verify::reverify_sweep(&ctx.http, &pool).await
+                {
+                    error!(error = ?e, "rules reverify sweep failed");
+                }
+            })
+        })
+    }
+}
+
+pub struct ScamDomainReloadCron;
+
+impl ScamDomainReloadCron {
+    pub fn cron_job(
+        domains: Arc<ScamDomains>,
+    ) -> std::result::Result<CronJob, jiff_cron::error::Error> {
+        CronJob::new("scam_domain_reload", "0 */15 * * * * *").map(|job| {
+            job.set_action(move |_ctx, _pool| {
+                let domains = Arc::clone(&domains);
+                async move {
+                    match domains.reload() {
+                        Ok(count) => debug!(count, "scam domain list reloaded"),
+                        Err(e) => {
+                            error!(error = ?e, "scam domain list reload failed")
+                        },
+                    }
+                }
+            })
+        })
+    }
 }
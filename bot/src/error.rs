@@ -8,10 +8,12 @@ pub enum BotError {
     NotInteractionAuthor,
     NegativeHours,
 
+    Automod(automod::AutomodError),
     EndgameAnalysis(destiny2::EndgameAnalysisError),
     Honeypot(honeypot::HoneypotError),
     Lfg(lfg::LfgError),
     Music(music::MusicError),
+    Raid(raid::RaidError),
     ReactionRole(reaction_roles::ReactionRoleError),
     Suggestions(suggestions::SuggestionsError),
     Ticket(ticket::TicketError),
@@ -40,9 +42,11 @@ impl std::fmt::Display for BotError {
             Self::ZaydenCore(e) => e.fmt(f),
 
             Self::EndgameAnalysis(e) => e.fmt(f),
+            Self::Automod(e) => e.fmt(f),
             Self::Honeypot(e) => e.fmt(f),
             Self::Lfg(e) => e.fmt(f),
             Self::Music(e) => e.fmt(f),
+            Self::Raid(e) => e.fmt(f),
             Self::ReactionRole(e) => e.fmt(f),
             Self::Suggestions(e) => e.fmt(f),
             Self::Ticket(e) => e.fmt(f),
@@ -60,9 +64,11 @@ impl std::error::Error for BotError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
             Self::EndgameAnalysis(e) => Some(e),
+            Self::Automod(e) => Some(e),
             Self::Honeypot(e) => Some(e),
             Self::Lfg(e) => Some(e),
             Self::Music(e) => Some(e),
+            Self::Raid(e) => Some(e),
             Self::ReactionRole(e) => Some(e),
             Self::Suggestions(e) => Some(e),
             Self::Ticket(e) => Some(e),
@@ -90,6 +96,7 @@ impl Respond for BotError {
             Self::Honeypot(e) => e.user_message(),
             Self::Lfg(e) => e.user_message(),
             Self::Music(e) => e.user_message(),
+            Self::Raid(e) => e.user_message(),
             Self::ReactionRole(e) => e.user_message(),
             Self::Suggestions(e) => e.user_message(),
             Self::Ticket(e) => e.user_message(),
@@ -98,6 +105,7 @@ impl Respond for BotError {
             Self::ZaydenCore(e) => e.user_message(),
 
             Self::Ai(_)
+            | Self::Automod(_)
             | Self::Config(_)
             | Self::Jiff(_)
             | Self::EnvVar(_)
@@ -106,6 +114,12 @@ impl Respond for BotError {
     }
 }
 
+impl From<automod::AutomodError> for BotError {
+    fn from(e: automod::AutomodError) -> Self {
+        Self::Automod(e)
+    }
+}
+
 impl From<destiny2::EndgameAnalysisError> for BotError {
     fn from(e: destiny2::EndgameAnalysisError) -> Self {
         Self::EndgameAnalysis(e)
@@ -130,6 +144,12 @@ impl From<music::MusicError> for BotError {
     }
 }
 
+impl From<raid::RaidError> for BotError {
+    fn from(e: raid::RaidError) -> Self {
+        Self::Raid(e)
+    }
+}
+
 impl From<reaction_roles::ReactionRoleError> for BotError {
     fn from(e: reaction_roles::ReactionRoleError) -> Self {
         Self::ReactionRole(e)
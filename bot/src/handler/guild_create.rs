@@ -1,10 +1,12 @@
 use std::time::Duration;
 
+use jiff::Timestamp;
 use serenity::all::{Context, CreateCommand, Guild, GuildId};
 use sqlx::PgPool;
 use tokio::sync::RwLock;
 use tokio::time::sleep;
 use tracing::{info, warn};
+use zayden_app::services::ops;
 use zayden_core::is_transient;
 
 use super::Handler;
@@ -28,6 +30,19 @@ impl Handler {
         );
         lfg_result?;
 
+        if let Err(e) = ops::record_guild_join(
+            pool,
+            guild.id.get(),
+            &guild.name,
+            guild.member_count,
+            Timestamp::from_second(guild.joined_at.unix_timestamp())
+                .unwrap_or_else(|_| Timestamp::now()),
+        )
+        .await
+        {
+            warn!(?e, guild_id = %guild.id, "failed to record guild membership");
+        }
+
         let commands = self.registry.definitions_for(guild.id);
 
         set_commands(ctx, guild.id, &commands).await?;
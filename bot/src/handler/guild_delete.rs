@@ -0,0 +1,23 @@
+use serenity::all::UnavailableGuild;
+use sqlx::PgPool;
+use zayden_app::services::ops;
+
+use super::Handler;
+use crate::Result;
+
+impl Handler {
+    pub async fn guild_delete(
+        incomplete: &UnavailableGuild,
+        pool: &PgPool,
+    ) -> Result<()> {
+        // An outage also sends GUILD_DELETE, with `unavailable` set; only a
+        // kick or the guild being deleted means the bot has left.
+        if incomplete.unavailable {
+            return Ok(());
+        }
+
+        ops::record_guild_leave(pool, incomplete.id.get()).await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,63 @@
+use std::sync::Arc;
+
+use serenity::all::{Context, Member};
+use tracing::warn;
+use zayden_app::config::SharedAction;
+use zayden_app::state::AppState;
+use zayden_core::as_i64;
+
+use super::Handler;
+use crate::Result;
+use crate::bindings::honeypot::record_shared_ban;
+use crate::bindings::raid::record_join;
+
+impl Handler {
+    pub async fn guild_member_addition(
+        ctx: &Context,
+        member: &Member,
+        app: Arc<AppState>,
+    ) -> Result<()> {
+        let guild_id = as_i64(member.guild_id.get());
+
+        // A failed screening shouldn't keep the join out of the raid count.
+        match Self::screen_shared(ctx, member, &app).await {
+            Ok(true) => return Ok(()),
+            Ok(false) => {},
+            Err(e) => {
+                warn!(error = ?e, guild_id, user_id = %member.user.id, "failed to screen a joiner against the shared threat list");
+            },
+        }
+
+        let raid_settings = app.settings.raid.get(guild_id).await?;
+
+        if let Some(join) =
+            raid::member_join(&ctx.http, member, &raid_settings, &app.db).await?
+        {
+            record_join(&app, &join).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Screens the joiner against the shared threat list. `true` if they
+    /// were banned for it.
+    async fn screen_shared(
+        ctx: &Context,
+        member: &Member,
+        app: &Arc<AppState>,
+    ) -> Result<bool> {
+        let guild_id = as_i64(member.guild_id.get());
+        let settings = app.settings.honeypot.get(guild_id).await?;
+
+        let Some(matched) =
+            honeypot::shared::member_join(&ctx.http, member, &settings, &app.db)
+                .await?
+        else {
+            return Ok(false);
+        };
+
+        record_shared_ban(app, &matched).await?;
+
+        Ok(matched.action == SharedAction::Ban)
+    }
+}
@@ -0,0 +1,27 @@
+use std::sync::Arc;
+
+use serenity::all::{Context, GuildMemberUpdateEvent};
+use zayden_app::state::AppState;
+use zayden_core::as_i64;
+
+use super::Handler;
+use crate::Result;
+use crate::bindings::honeypot::record_hit;
+
+impl Handler {
+    pub async fn guild_member_update(
+        ctx: &Context,
+        event: &GuildMemberUpdateEvent,
+        app: Arc<AppState>,
+    ) -> Result<()> {
+        let settings =
+            app.settings.honeypot.get(as_i64(event.guild_id.get())).await?;
+
+        if let Some(hit) = honeypot::role_update(ctx, event, &settings).await? {
+            record_hit(&ctx.http, &app, &hit).await?;
+            honeypot::report(&ctx.http, &settings, &hit).await?;
+        }
+
+        Ok(())
+    }
+}
@@ -4,8 +4,9 @@ use futures::FutureExt;
 use gambling::{GamblingManager, level_up_reward};
 use serenity::all::{Context, Message};
 use sqlx::PgPool;
-use ticket::TicketStores;
-use tracing::debug;
+use suggestions::Suggestions;
+use ticket::{SupportTicketManager, TicketStores};
+use tracing::{debug, warn};
 use zayden_app::state::AppState;
 use zayden_core::as_i64;
 
@@ -31,9 +32,37 @@ impl Handler {
             let settings = app.settings.honeypot.get(as_i64(guild_id.get())).await?;
 
             if let Some(hit) = honeypot::message_create(ctx, msg, &settings).await? {
-                record_hit(&app, &hit).await?;
+                record_hit(&ctx.http, &app, &hit).await?;
+                honeypot::report(&ctx.http, &settings, &hit).await?;
                 return Ok(());
             }
+
+            let settings = app.settings.automod.get(as_i64(guild_id.get())).await?;
+
+            if let Some(hit) =
+                automod::message_create(ctx, msg, &settings, &app.scam_domains)
+                    .await?
+            {
+                crate::bindings::automod::record_hit(&app, &hit).await?;
+                automod::report(&ctx.http, &settings, &hit).await?;
+                return Ok(());
+            }
+
+            // A failure here shouldn't cost the message its levels or reply.
+            if let Err(e) = SupportTicketManager::touch(
+                pool,
+                msg.channel_id.expect_thread(),
+                msg.author.id,
+            )
+            .await
+            {
+                warn!(error = ?e, channel_id = %msg.channel_id, "failed to record ticket activity");
+            }
+
+            // A no-op unless the message starts a post in the suggestions forum.
+            if let Err(e) = Suggestions::message_create(&ctx.http, msg, pool).await {
+                warn!(error = ?e, channel_id = %msg.channel_id, "failed to set up a suggestion post");
+            }
         }
 
         let stores = TicketStores {
@@ -10,6 +10,9 @@ use zayden_app::state::AppState;
 
 mod entitlement;
 mod guild_create;
+mod guild_delete;
+mod guild_member_addition;
+mod guild_member_update;
 mod interaction;
 mod message_create;
 mod presence_update;
@@ -37,6 +40,9 @@ impl EventHandler for Handler {
     ) -> Option<Box<Event>> {
         match &*event {
             Event::GuildCreate(_)
+            | Event::GuildDelete(_)
+            | Event::GuildMemberAdd(_)
+            | Event::GuildMemberUpdate(_)
             | Event::MessageCreate(_)
             | Event::ReactionAdd(_)
             | Event::ReactionRemove(_)
@@ -60,12 +66,9 @@ impl EventHandler for Handler {
             | Event::GuildAuditLogEntryCreate(_)
             | Event::GuildBanAdd(_)
             | Event::GuildBanRemove(_)
-            | Event::GuildDelete(_)
             | Event::GuildEmojisUpdate(_)
             | Event::GuildIntegrationsUpdate(_)
-            | Event::GuildMemberAdd(_)
             | Event::GuildMemberRemove(_)
-            | Event::GuildMemberUpdate(_)
             | Event::GuildMembersChunk(_)
             | Event::GuildRoleCreate(_)
             | Event::GuildRoleDelete(_)
@@ -140,6 +143,17 @@ impl EventHandler for Handler {
             FullEvent::GuildCreate { guild, .. } => {
                 Self::guild_create(self, ctx, guild, &pool).await
             },
+            FullEvent::GuildDelete { incomplete, .. } => {
+                Self::guild_delete(incomplete, &pool).await
+            },
+            FullEvent::GuildMemberAddition { new_member, .. } => {
+                let app = Arc::clone(&self.app);
+                Self::guild_member_addition(ctx, new_member, app).await
+            },
+            FullEvent::GuildMemberUpdate { event, .. } => {
+                let app = Arc::clone(&self.app);
+                Self::guild_member_update(ctx, event, app).await
+            },
             FullEvent::Message { new_message, .. } => {
                 let app = Arc::clone(&self.app);
                 Self::message_create(ctx, new_message, &pool, app).await
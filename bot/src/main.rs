@@ -71,12 +71,14 @@ async fn main() -> Result<()> {
     let songbird = Arc::clone(&bot_state_inner.songbird);
     let bot_state = Arc::new(RwLock::new(bot_state_inner));
 
-    let registry = bindings::build_registry(bot_config.llamad2_guild)
-        .map_err(|e| BotError::Other(e.to_string()))?;
+    let registry =
+        bindings::build_registry(bot_config.llamad2_guild, bot_config.zayden_guild)
+            .map_err(|e| BotError::Other(e.to_string()))?;
 
     let mut client = ClientBuilder::new(
         bot_config.discord_token.parse::<Token>().map_err(serenity::Error::Token)?,
         GatewayIntents::GUILDS
+            | GatewayIntents::GUILD_MEMBERS
             | GatewayIntents::GUILD_MESSAGES
             | GatewayIntents::GUILD_MESSAGE_REACTIONS
             | GatewayIntents::GUILD_VOICE_STATES
@@ -95,6 +97,7 @@ async fn main() -> Result<()> {
 
     logging(
         Arc::clone(&client.http),
+        pool.clone(),
         bot_config.error_log_webhook.as_deref(),
         bot_config.normal_log_webhook.as_deref(),
     )
@@ -107,6 +110,7 @@ async fn main() -> Result<()> {
 
 async fn logging(
     http: Arc<Http>,
+    pool: PgPool,
     error_log_url: Option<&str>,
     normal_log_url: Option<&str>,
 ) {
@@ -128,7 +132,8 @@ async fn logging(
         .with_writer(std::io::stdout)
         .with_filter(filter::LevelFilter::INFO);
 
-    let webhook_log = WebhookLogger::new(http, error_log_url, normal_log_url).await;
+    let webhook_log =
+        WebhookLogger::new(http, pool, error_log_url, normal_log_url).await;
 
     Registry::default().with(debug_log).with(stdout_log).with(webhook_log).init();
 }
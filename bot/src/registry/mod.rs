@@ -14,6 +14,7 @@ use serenity::all::{
 };
 use tracing::warn;
 use zayden_app::entitlement::{EntitlementScope, Tier};
+use zayden_app::services::ops;
 use zayden_app::state::AppState;
 use zayden_core::DispatchMap;
 pub use zayden_core::OverlapError;
@@ -176,8 +177,22 @@ impl CommandRegistry {
             }
         }
 
+        let pool = app.db.clone();
         let cx = InvocationCtx { ctx, interaction, app };
-        Some(cmd.run(&cx).await)
+        let result = cmd.run(&cx).await;
+
+        let command = interaction.data.name.to_string();
+        let module = cmd.module();
+        let succeeded = result.is_ok();
+        tokio::spawn(async move {
+            if let Err(e) =
+                ops::record_command(&pool, &command, module, succeeded).await
+            {
+                warn!(?e, %command, "failed to record command usage");
+            }
+        });
+
+        Some(result)
     }
 
     pub async fn run_component(
@@ -3,7 +3,14 @@ use std::sync::Arc;
 use bungie_api::{BungieClient, BungieClientBuilder};
 use dashmap::DashMap;
 use destiny2::endgame_analysis::EndgameAnalysisSheetCron;
-use gambling::{GamblingData, GameCache, HigherLower, Lotto, StaminaCron};
+use gambling::{
+    GamblingData,
+    GameCache,
+    HigherLower,
+    Lotto,
+    SeasonCron,
+    StaminaCron,
+};
 use llamad2::GoodMorningCache;
 use marathon::client::MarathonClient;
 use marathon::cron::{MarathonAnnounceCron, MarathonNewsCron};
@@ -25,7 +32,18 @@ use zayden_app::state::AppState;
 use zayden_core::cache::GuildMembersCache;
 use zayden_core::{CronJob, CronJobData, EmojiCache, EmojiCacheData};
 
-use crate::cron::EntitlementSweepCron;
+use crate::cron::{
+    EntitlementSweepCron,
+    HoneypotHitPruneCron,
+    LockdownSweepCron,
+    LogPruneCron,
+    RoleSweepCron,
+    RulesPublishCron,
+    RulesReverifyCron,
+    ScamDomainReloadCron,
+    TicketSweepCron,
+    TimedActionSweepCron,
+};
 use crate::{Result, ZAYDEN_TOKEN, zayden_token};
 
 pub struct BotState {
@@ -102,6 +120,7 @@ impl BotState {
             StaminaCron::cron_job(),
             Lotto::cron_job::<Self>(),
             HigherLower::cron_job(),
+            SeasonCron::cron_job(),
             EndgameAnalysisSheetCron::cron_job(
                 Arc::clone(&self.bungie_client),
                 self.app.google_api_key.clone(),
@@ -115,6 +134,15 @@ impl BotState {
             PalworldSaveRefreshCron::cron_job(Arc::clone(&self.palworld)),
             PalworldWarmCron::cron_job(Arc::clone(&self.palworld)),
             EntitlementSweepCron::cron_job(),
+            TicketSweepCron::cron_job(),
+            RoleSweepCron::cron_job(),
+            LockdownSweepCron::cron_job(),
+            RulesPublishCron::cron_job(),
+            RulesReverifyCron::cron_job(),
+            TimedActionSweepCron::cron_job(),
+            LogPruneCron::cron_job(),
+            HoneypotHitPruneCron::cron_job(),
+            ScamDomainReloadCron::cron_job(Arc::clone(&self.app.scam_domains)),
         ];
         for job in jobs {
             match job {
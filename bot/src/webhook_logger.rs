@@ -3,7 +3,9 @@ use std::sync::Arc;
 
 use serenity::all::{ExecuteWebhook, Http, Webhook};
 use serenity::small_fixed_array::FixedString;
+use sqlx::PgPool;
 use tracing::error;
+use zayden_app::services::ops;
 
 const fn get_avatar(level: tracing::Level) -> &'static str {
     match level {
@@ -23,6 +25,9 @@ struct Inner {
     error_logs: Option<Webhook>,
     normal_logs: Option<Webhook>,
 
+    /// Keeps WARN and ERROR events for the dashboard's admin console.
+    pool: PgPool,
+
     handle: tokio::runtime::Handle,
 }
 
@@ -47,6 +52,7 @@ async fn resolve_webhook(
 impl WebhookLogger {
     pub async fn new(
         http: Arc<Http>,
+        pool: PgPool,
         error_log_url: Option<&str>,
         normal_log_url: Option<&str>,
     ) -> Self {
@@ -65,6 +71,7 @@ impl WebhookLogger {
             bot_name,
             error_logs,
             normal_logs,
+            pool,
             handle: tokio::runtime::Handle::current(),
         }))
     }
@@ -128,8 +135,19 @@ impl<S: tracing::Subscriber> tracing_subscriber::Layer<S> for WebhookLogger {
             return;
         }
 
+        // sqlx logs its own slow statements, so storing those would feed back.
+        let store = matches!(level, tracing::Level::ERROR | tracing::Level::WARN)
+            && !target.starts_with("sqlx");
+
         let this = self.clone();
         this.0.handle.clone().spawn(async move {
+            if store
+                && let Err(e) =
+                    ops::record_log(&this.0.pool, level.as_str(), target, &message)
+                        .await
+            {
+                eprintln!("webhook_logger: failed to store log entry: {e}");
+            }
             this.send_log(level, target, message).await;
         });
     }
@@ -3,16 +3,25 @@ use leptos_meta::{Stylesheet, provide_meta_context};
 use leptos_router::components::{Route, Router, Routes};
 use leptos_router::path;
 
+use crate::ui::pages::activity::ActivityPage;
+use crate::ui::pages::admin::{AdminAiUsagePage, AdminEntitlementsPage, AdminPage};
+use crate::ui::pages::ai::AiPage;
+use crate::ui::pages::gift_codes::GiftCodesPage;
 use crate::ui::pages::greetings::GreetingsPage;
 use crate::ui::pages::guild_settings::GuildSettingsPage;
 use crate::ui::pages::guilds::GuildListPage;
 use crate::ui::pages::landing::LandingPage;
 use crate::ui::pages::levels::LevelsPage;
 use crate::ui::pages::login::LoginPage;
+use crate::ui::pages::moderation::{ModerationPage, UserInfractionsPage};
 use crate::ui::pages::modules::GuildOverviewPage;
 use crate::ui::pages::not_found::NotFound;
 use crate::ui::pages::palworld_save::PalworldSavePage;
+use crate::ui::pages::premium::PremiumSlotsPage;
 use crate::ui::pages::reaction_roles::ReactionRolesPage;
+use crate::ui::pages::rules::RulesPage;
+use crate::ui::pages::suggestions::SuggestionsPage;
+use crate::ui::pages::threats::ThreatListPage;
 use crate::ui::pages::upgrade::UpgradePage;
 
 #[derive(Clone)]
@@ -52,13 +61,28 @@ pub fn App() -> impl IntoView {
                 <Route path=path!("/") view=LandingPage/>
                 <Route path=path!("/login") view=LoginPage/>
                 <Route path=path!("/upgrade") view=UpgradePage/>
+                <Route path=path!("/premium") view=PremiumSlotsPage/>
                 <Route path=path!("/guilds") view=GuildListPage/>
                 <Route path=path!("/guild/:id") view=GuildOverviewPage/>
                 <Route path=path!("/guild/:id/settings") view=GuildSettingsPage/>
                 <Route path=path!("/guild/:id/levels") view=LevelsPage/>
                 <Route path=path!("/guild/:id/reaction-roles") view=ReactionRolesPage/>
                 <Route path=path!("/guild/:id/greetings") view=GreetingsPage/>
+                <Route path=path!("/guild/:id/ai") view=AiPage/>
+                <Route path=path!("/guild/:id/suggestions") view=SuggestionsPage/>
+                <Route path=path!("/guild/:id/moderation") view=ModerationPage/>
+                <Route
+                    path=path!("/guild/:id/moderation/user/:user")
+                    view=UserInfractionsPage
+                />
+                <Route path=path!("/guild/:id/rules") view=RulesPage/>
+                <Route path=path!("/guild/:id/threats") view=ThreatListPage/>
+                <Route path=path!("/guild/:id/activity") view=ActivityPage/>
+                <Route path=path!("/admin") view=AdminPage/>
+                <Route path=path!("/admin/entitlements") view=AdminEntitlementsPage/>
+                <Route path=path!("/admin/ai-usage") view=AdminAiUsagePage/>
                 <Route path=path!("/admin/palworld/save") view=PalworldSavePage/>
+                <Route path=path!("/admin/gift-codes") view=GiftCodesPage/>
             </Routes>
         </Router>
     }
@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SettingsChangeInfo {
+    pub(crate) id: String,
+    pub(crate) actor: String,
+    pub(crate) source: String,
+    pub(crate) section: String,
+    pub(crate) at: String,
+    pub(crate) fields: Vec<FieldChangeInfo>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct FieldChangeInfo {
+    pub(crate) field: String,
+    pub(crate) before: String,
+    pub(crate) after: String,
+}
@@ -0,0 +1,103 @@
+use serde::{Deserialize, Serialize};
+
+use super::Tier;
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct AdminOverview {
+    pub(crate) window_days: i32,
+    pub(crate) active_guilds: i64,
+    pub(crate) joined: i64,
+    pub(crate) left: i64,
+    pub(crate) growth: Vec<GrowthPoint>,
+    pub(crate) modules: Vec<ModuleUsageInfo>,
+    pub(crate) commands: Vec<CommandUsageInfo>,
+    pub(crate) logs: Vec<LogEntryInfo>,
+    pub(crate) jobs: Vec<CronJobInfo>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct GrowthPoint {
+    pub(crate) day: String,
+    pub(crate) joined: i64,
+    pub(crate) left: i64,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ModuleUsageInfo {
+    pub(crate) module: String,
+    pub(crate) uses: i64,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct CommandUsageInfo {
+    pub(crate) command: String,
+    pub(crate) module: String,
+    pub(crate) uses: i64,
+    pub(crate) failures: i64,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct LogEntryInfo {
+    pub(crate) level: String,
+    pub(crate) target: String,
+    pub(crate) message: String,
+    pub(crate) at: String,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct CronJobInfo {
+    pub(crate) id: String,
+    pub(crate) last_run: String,
+    pub(crate) duration_ms: i64,
+    pub(crate) next_run: Option<String>,
+    pub(crate) runs: i64,
+}
+
+/// The `bot_config` overrides, with blanks for "use the env var".
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ConfigOverridesView {
+    pub(crate) error_log_webhook: String,
+    pub(crate) normal_log_webhook: String,
+    pub(crate) flaresolverr_url: String,
+    pub(crate) youtube_cookies_file: String,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct EntitlementInfo {
+    pub(crate) provider: String,
+    pub(crate) external_id: String,
+    pub(crate) scope: String,
+    pub(crate) tier: Tier,
+    pub(crate) granted_at: String,
+    pub(crate) expires_at: Option<String>,
+}
+
+/// One server's spend on one model over the report's window.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct AiUsageInfo {
+    pub(crate) guild_id: String,
+    /// Empty for a server the bot has no record of joining.
+    pub(crate) guild_name: String,
+    pub(crate) model: String,
+    pub(crate) requests: i64,
+    pub(crate) prompt_tokens: i64,
+    pub(crate) completion_tokens: i64,
+    pub(crate) total_tokens: i64,
+}
+
+/// A raised AI quota. Blank limits stay on the server's tier.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct AiLimitInfo {
+    pub(crate) guild_id: String,
+    pub(crate) daily_tokens: String,
+    pub(crate) monthly_tokens: String,
+    pub(crate) set_by: String,
+    pub(crate) updated_at: String,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct AiUsageView {
+    pub(crate) window_days: i32,
+    pub(crate) rows: Vec<AiUsageInfo>,
+    pub(crate) limits: Vec<AiLimitInfo>,
+}
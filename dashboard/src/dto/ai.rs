@@ -0,0 +1,52 @@
+use serde::{Deserialize, Serialize};
+
+use crate::dto::Tier;
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct PersonaInfo {
+    pub(crate) name: String,
+    pub(crate) prompt: String,
+    pub(crate) style: String,
+    pub(crate) word_limit: u32,
+    pub(crate) temperature: String,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ChannelPersonaInfo {
+    pub(crate) channel_id: String,
+    /// Empty for the bot's own character.
+    pub(crate) persona: String,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct AiView {
+    pub(crate) tier: Tier,
+    pub(crate) next_tier: Option<Tier>,
+    /// Tokens spent today and this month, UTC, against the server's quota.
+    pub(crate) tokens_today: i64,
+    pub(crate) tokens_month: i64,
+    pub(crate) daily_limit: i64,
+    pub(crate) monthly_limit: i64,
+    pub(crate) trigger_mode: String,
+    /// One per line.
+    pub(crate) keywords: String,
+    pub(crate) personas: Vec<PersonaInfo>,
+    /// Empty for the bot's own character.
+    pub(crate) default_persona: String,
+    pub(crate) channel_personas: Vec<ChannelPersonaInfo>,
+}
+
+impl AiView {
+    pub(crate) fn has_personas(&self) -> bool {
+        self.tier >= Tier::Pro
+    }
+}
+
+/// A token count, shortened: 950, 12.3k, 1.5M.
+pub(crate) fn short_tokens(n: i64) -> String {
+    match n {
+        ..1_000 => n.to_string(),
+        ..1_000_000 => format!("{}.{}k", n / 1_000, n % 1_000 / 100),
+        _ => format!("{}.{}M", n / 1_000_000, n % 1_000_000 / 100_000),
+    }
+}
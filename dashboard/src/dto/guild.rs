@@ -11,6 +11,7 @@ pub struct GuildInfo {
 pub struct GuildSettings {
     pub(crate) support_channel_id: Option<String>,
     pub(crate) faq_channel_id: Option<String>,
+    pub(crate) transcript_channel_id: Option<String>,
     pub(crate) suggestions_channel_id: Option<String>,
     pub(crate) review_channel_id: Option<String>,
     pub(crate) suggestions_promote_threshold: String,
@@ -21,6 +22,14 @@ pub struct GuildSettings {
     pub(crate) artist_role_id: Option<String>,
     pub(crate) sleep_role_id: Option<String>,
     pub(crate) verified_role_id: Option<String>,
+    pub(crate) require_rules: bool,
+    pub(crate) reverify_on_rules_change: bool,
+    pub(crate) captcha_enabled: bool,
+    pub(crate) captcha_max_attempts: String,
+    pub(crate) captcha_lockout_minutes: String,
+    pub(crate) captcha_min_account_age_days: String,
+    pub(crate) captcha_fail_action: String,
+    pub(crate) captcha_log_channel_id: Option<String>,
     pub(crate) temp_voice_category: Option<String>,
     pub(crate) temp_voice_creator_channel: Option<String>,
     pub(crate) lfg_channel_id: Option<String>,
@@ -35,6 +44,66 @@ pub struct GuildSettings {
     pub(crate) honeypot_exempt_admins: bool,
     pub(crate) honeypot_exempt_role_id: Option<String>,
     pub(crate) honeypot_purge_seconds: String,
+    pub(crate) honeypot_share_hits: bool,
+    pub(crate) honeypot_shared_threshold: String,
+    pub(crate) honeypot_shared_action: String,
+    pub(crate) honeypot_log_channel_id: Option<String>,
+    pub(crate) honeypot_traps: Vec<HoneypotTrapInfo>,
+    pub(crate) automod_enabled: bool,
+    pub(crate) automod_exempt_admins: bool,
+    pub(crate) automod_exempt_role_id: Option<String>,
+    pub(crate) automod_log_channel_id: Option<String>,
+    pub(crate) automod_timeout_minutes: String,
+    /// Each rule's action, empty when the rule is off. An off rule's numbers
+    /// are the defaults it would start with.
+    pub(crate) automod_rate_action: String,
+    pub(crate) automod_rate_max: String,
+    pub(crate) automod_rate_seconds: String,
+    pub(crate) automod_duplicates_action: String,
+    pub(crate) automod_duplicates_max: String,
+    pub(crate) automod_duplicates_seconds: String,
+    pub(crate) automod_mentions_action: String,
+    pub(crate) automod_mentions_max: String,
+    pub(crate) automod_invites_action: String,
+    pub(crate) automod_scam_links_action: String,
+    pub(crate) automod_caps_action: String,
+    pub(crate) automod_caps_percent: String,
+    pub(crate) automod_caps_min_length: String,
+    pub(crate) automod_filters: Vec<AutomodFilterInfo>,
+    pub(crate) raid_enabled: bool,
+    pub(crate) raid_join_threshold: String,
+    pub(crate) raid_join_window_seconds: String,
+    pub(crate) raid_lockdown_minutes: String,
+    pub(crate) raid_min_account_age_days: String,
+    pub(crate) raid_flag_default_avatar: bool,
+    pub(crate) raid_flag_similar_names: bool,
+    pub(crate) raid_joiner_action: String,
+    pub(crate) raid_timeout_minutes: String,
+    pub(crate) raid_raise_verification: bool,
+    pub(crate) raid_slowmode_seconds: String,
+    pub(crate) raid_alert_channel_id: Option<String>,
+    pub(crate) appeals_enabled: bool,
+    pub(crate) appeals_channel_id: Option<String>,
     pub(crate) ai_enabled: bool,
     pub(crate) ai_channel_id: Option<String>,
 }
+
+/// A custom automod word list or regular expression.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct AutomodFilterInfo {
+    pub(crate) pattern: String,
+    pub(crate) regex: bool,
+    pub(crate) action: String,
+}
+
+/// A honeypot trap beyond the main honeypot channel.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct HoneypotTrapInfo {
+    /// `channel` or `role`.
+    pub(crate) kind: String,
+    pub(crate) target_id: String,
+    pub(crate) action: String,
+    pub(crate) purge_seconds: i32,
+    pub(crate) timeout_minutes: i32,
+    pub(crate) quarantine_role_id: Option<String>,
+}
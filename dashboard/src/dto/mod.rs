@@ -1,16 +1,41 @@
+pub mod activity;
+pub mod admin;
+pub mod ai;
 pub mod discord;
 pub mod greetings;
 pub mod guild;
 pub mod levels;
+pub mod moderation;
 pub mod modules;
 pub mod palworld_save;
+pub mod premium;
 pub mod reaction_roles;
+pub mod role_panels;
+pub mod rules;
+pub mod suggestions;
+pub mod threats;
 pub mod tier;
 
+pub use activity::{FieldChangeInfo, SettingsChangeInfo};
+pub use admin::{
+    AdminOverview,
+    AiLimitInfo,
+    AiUsageInfo,
+    AiUsageView,
+    CommandUsageInfo,
+    ConfigOverridesView,
+    CronJobInfo,
+    EntitlementInfo,
+    GrowthPoint,
+    LogEntryInfo,
+    ModuleUsageInfo,
+};
+pub use ai::{AiView, ChannelPersonaInfo, PersonaInfo};
 pub use discord::{ChannelInfo, RoleInfo, SessionUser};
 pub use greetings::{CooldownView, GreetingImageInfo, GreetingsView};
-pub use guild::{GuildInfo, GuildSettings};
+pub use guild::{AutomodFilterInfo, GuildInfo, GuildSettings, HoneypotTrapInfo};
 pub use levels::LeaderboardEntry;
+pub use moderation::{INFRACTION_KINDS, InfractionInfo, UserTimeline};
 pub use modules::ModuleView;
 pub use palworld_save::{
     PalEdit,
@@ -20,5 +45,10 @@ pub use palworld_save::{
     SavePlayer,
     SaveRoster,
 };
+pub use premium::{GiftCodeInfo, PremiumSlotsView, SlotGuildInfo};
 pub use reaction_roles::ReactionRoleInfo;
+pub use role_panels::{PanelEntryInfo, PanelRequirementInfo, RolePanelInfo};
+pub use rules::{QuizQuestionInfo, RuleInfo, RulesConfigInfo, RulesView};
+pub use suggestions::SuggestionEntry;
+pub use threats::{SharedHitInfo, ThreatListView, WhitelistEntryInfo};
 pub use tier::{Tier, UserTierInfo};
@@ -0,0 +1,76 @@
+use std::borrow::Cow;
+
+use serde::{Deserialize, Serialize};
+
+/// The `infraction_kind` enum's values, in escalation order.
+pub const INFRACTION_KINDS: [&str; 5] = ["Warn", "Mute", "Kick", "SoftBan", "Ban"];
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct InfractionInfo {
+    pub(crate) id: i32,
+    pub(crate) user_id: String,
+    pub(crate) username: String,
+    pub(crate) kind: String,
+    pub(crate) moderator_id: String,
+    pub(crate) moderator_username: String,
+    pub(crate) points: i32,
+    pub(crate) reason: String,
+    pub(crate) created_at: String,
+    pub(crate) expunged: bool,
+    pub(crate) expunge_reason: Option<String>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct UserTimeline {
+    pub(crate) user_id: String,
+    pub(crate) username: String,
+    /// Points from unexpunged infractions in the last six months, the window
+    /// `/infraction` escalates on.
+    pub(crate) active_points: i64,
+    pub(crate) infractions: Vec<InfractionInfo>,
+}
+
+/// Quotes `value` for a CSV cell when it holds a comma, quote or newline.
+/// A value a spreadsheet would read as a formula is prefixed with `'`, so a
+/// username or reason can't run one when the export is opened.
+#[must_use]
+pub fn csv_field(value: &str) -> Cow<'_, str> {
+    let value = if value.starts_with(['=', '+', '-', '@', '\t', '\r']) {
+        Cow::Owned(format!("'{value}"))
+    } else {
+        Cow::Borrowed(value)
+    };
+
+    if value.contains([',', '"', '\n', '\r']) {
+        Cow::Owned(format!("\"{}\"", value.replace('"', "\"\"")))
+    } else {
+        value
+    }
+}
+
+#[cfg(feature = "ssr")]
+#[must_use]
+pub(crate) fn infractions_csv(rows: &[InfractionInfo]) -> String {
+    let mut csv = String::from(
+        "id,created_at,user_id,username,type,points,moderator_id,\
+         moderator_username,reason,expunged,expunge_reason\n",
+    );
+    for row in rows {
+        let cells = [
+            Cow::Owned(row.id.to_string()),
+            csv_field(&row.created_at),
+            csv_field(&row.user_id),
+            csv_field(&row.username),
+            csv_field(&row.kind),
+            Cow::Owned(row.points.to_string()),
+            csv_field(&row.moderator_id),
+            csv_field(&row.moderator_username),
+            csv_field(&row.reason),
+            Cow::Borrowed(if row.expunged { "true" } else { "false" }),
+            csv_field(row.expunge_reason.as_deref().unwrap_or_default()),
+        ];
+        csv.push_str(&cells.join(","));
+        csv.push('\n');
+    }
+    csv
+}
@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+
+use super::Tier;
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct PremiumSlotsView {
+    pub(crate) tier: Tier,
+    pub(crate) limit: usize,
+    pub(crate) used: usize,
+    pub(crate) guilds: Vec<SlotGuildInfo>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SlotGuildInfo {
+    pub(crate) id: String,
+    pub(crate) name: String,
+    pub(crate) icon: Option<String>,
+    pub(crate) assigned: bool,
+    /// Set while a fresh assignment is still inside the reassignment cooldown.
+    pub(crate) locked_until: Option<String>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct GiftCodeInfo {
+    pub(crate) code: String,
+    pub(crate) tier: Tier,
+    pub(crate) duration_days: i32,
+    pub(crate) uses: i32,
+    pub(crate) max_uses: i32,
+    pub(crate) expires_at: Option<String>,
+}
@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct RolePanelInfo {
+    pub(crate) id: i32,
+    pub(crate) channel_id: String,
+    pub(crate) message_id: Option<String>,
+    pub(crate) title: String,
+    pub(crate) description: Option<String>,
+    pub(crate) kind: String,
+    pub(crate) mode: String,
+    pub(crate) role_duration_minutes: Option<i32>,
+    pub(crate) entries: Vec<PanelEntryInfo>,
+    pub(crate) requirements: Vec<PanelRequirementInfo>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct PanelEntryInfo {
+    pub(crate) role_id: String,
+    pub(crate) label: String,
+    pub(crate) emoji: Option<String>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct PanelRequirementInfo {
+    pub(crate) role_id: String,
+    pub(crate) forbidden: bool,
+}
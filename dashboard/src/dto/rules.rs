@@ -0,0 +1,226 @@
+use std::fmt::Write as _;
+
+use serde::{Deserialize, Serialize};
+
+/// A rules embed carries one field per rule, and embeds hold at most 25.
+pub const MAX_RULES: usize = 25;
+pub const MAX_TITLE_CHARS: usize = 256;
+pub const MAX_DESCRIPTION_CHARS: usize = 4096;
+pub const MAX_RULE_TITLE_CHARS: usize = 256;
+pub const MAX_RULE_BODY_CHARS: usize = 1024;
+
+/// The verify quiz is a Discord modal: at most five inputs, each labelled
+/// with at most 45 characters.
+pub const MAX_QUIZ_QUESTIONS: usize = 5;
+pub const MAX_QUESTION_CHARS: usize = 45;
+
+/// `/rules config`'s default embed colour.
+pub const DEFAULT_COLOUR: u32 = 0x00ff_0000;
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct RulesConfigInfo {
+    pub(crate) channel_id: String,
+    pub(crate) title: String,
+    pub(crate) description: String,
+    /// `#rrggbb`, as an `<input type="color">` reads and writes it.
+    pub(crate) colour: String,
+    pub(crate) posted: bool,
+    pub(crate) publish_pending: bool,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct RuleInfo {
+    pub(crate) id: i32,
+    pub(crate) title: String,
+    pub(crate) body: String,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct QuizQuestionInfo {
+    pub(crate) id: i32,
+    pub(crate) question: String,
+    /// The accepted answers, comma-separated.
+    pub(crate) answers: String,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct RulesView {
+    pub(crate) config: RulesConfigInfo,
+    pub(crate) rules: Vec<RuleInfo>,
+    pub(crate) quiz: Vec<QuizQuestionInfo>,
+}
+
+/// A hex colour such as `FF0000` or `#ff0000`, as `/rules config` accepts it.
+#[must_use]
+pub fn parse_colour(raw: &str) -> Option<u32> {
+    let hex = raw.trim().trim_start_matches('#');
+    u32::from_str_radix(hex, 16).ok().filter(|value| *value <= 0x00ff_ffff)
+}
+
+#[must_use]
+pub fn colour_hex(colour: u32) -> String {
+    format!("#{colour:06x}")
+}
+
+fn escape_char(out: &mut String, c: char) {
+    match c {
+        '&' => out.push_str("&amp;"),
+        '<' => out.push_str("&lt;"),
+        '>' => out.push_str("&gt;"),
+        '"' => out.push_str("&quot;"),
+        '\'' => out.push_str("&#39;"),
+        c => out.push(c),
+    }
+}
+
+fn escape_into(out: &mut String, text: &str) {
+    for c in text.chars() {
+        escape_char(out, c);
+    }
+}
+
+/// Delimiters Discord pairs around inline text, longest first so `**` is
+/// never read as two `*`.
+const SPANS: [(&str, &str, &str); 7] = [
+    ("**", "<strong>", "</strong>"),
+    ("__", "<u>", "</u>"),
+    ("~~", "<s>", "</s>"),
+    ("||", "<span class=\"md-spoiler\">", "</span>"),
+    ("*", "<em>", "</em>"),
+    ("_", "<em>", "</em>"),
+    ("`", "<code>", "</code>"),
+];
+
+/// `[label](https://…)`, split into its label, URL and the text after it.
+fn masked_link(text: &str) -> Option<(&str, &str, &str)> {
+    let rest = text.strip_prefix('[')?;
+    let (label, rest) = rest.split_once("](")?;
+    let (url, tail) = rest.split_once(')')?;
+    let safe = url.starts_with("https://") || url.starts_with("http://");
+    (safe && !label.is_empty() && !url.contains(char::is_whitespace))
+        .then_some((label, url, tail))
+}
+
+fn inline_into(out: &mut String, mut text: &str) {
+    'outer: while !text.is_empty() {
+        if let Some(rest) = text.strip_prefix('\\') {
+            let mut chars = rest.chars();
+            if let Some(escaped) = chars.next()
+                && escaped.is_ascii_punctuation()
+            {
+                escape_char(out, escaped);
+                text = chars.as_str();
+                continue;
+            }
+        }
+
+        if let Some((label, url, tail)) = masked_link(text) {
+            out.push_str("<a href=\"");
+            escape_into(out, url);
+            out.push_str("\" target=\"_blank\" rel=\"noopener noreferrer\">");
+            inline_into(out, label);
+            out.push_str("</a>");
+            text = tail;
+            continue;
+        }
+
+        for (marker, open, close) in SPANS {
+            let Some((inner, tail)) =
+                text.strip_prefix(marker).and_then(|after| after.split_once(marker))
+            else {
+                continue;
+            };
+            if inner.is_empty() || inner.starts_with(char::is_whitespace) {
+                continue;
+            }
+            out.push_str(open);
+            if marker == "`" {
+                escape_into(out, inner);
+            } else {
+                inline_into(out, inner);
+            }
+            out.push_str(close);
+            text = tail;
+            continue 'outer;
+        }
+
+        let mut chars = text.chars();
+        if let Some(c) = chars.next() {
+            escape_char(out, c);
+        }
+        text = chars.as_str();
+    }
+}
+
+/// Renders the Markdown subset Discord shows in embeds as HTML for the
+/// dashboard's preview. All text is escaped, and links only keep `http(s)`
+/// targets, so the result is safe to set as `inner_html`.
+#[must_use]
+pub fn discord_markdown(text: &str) -> String {
+    let mut out = String::new();
+    let mut lines = text.lines().peekable();
+    let mut in_list = false;
+
+    while let Some(line) = lines.next() {
+        let item = line.strip_prefix("- ").or_else(|| line.strip_prefix("* "));
+        if item.is_none() && in_list {
+            out.push_str("</ul>");
+            in_list = false;
+        }
+
+        if let Some(lang) = line.strip_prefix("```") {
+            let mut code = Vec::new();
+            if let Some(single) = lang.strip_suffix("```") {
+                code.push(single);
+            } else {
+                for line in lines.by_ref() {
+                    if line.trim_end() == "```" {
+                        break;
+                    }
+                    code.push(line);
+                }
+            }
+            out.push_str("<pre><code>");
+            escape_into(&mut out, &code.join("\n"));
+            out.push_str("</code></pre>");
+            continue;
+        }
+
+        if let Some(item) = item {
+            if !in_list {
+                out.push_str("<ul>");
+                in_list = true;
+            }
+            out.push_str("<li>");
+            inline_into(&mut out, item);
+            out.push_str("</li>");
+            continue;
+        }
+
+        let heading =
+            [("### ", "h3"), ("## ", "h2"), ("# ", "h1")].into_iter().find_map(
+                |(prefix, tag)| line.strip_prefix(prefix).map(|rest| (tag, rest)),
+            );
+
+        if let Some((tag, rest)) = heading {
+            let _ = write!(out, "<{tag}>");
+            inline_into(&mut out, rest);
+            let _ = write!(out, "</{tag}>");
+        } else if let Some(quote) = line.strip_prefix("> ") {
+            out.push_str("<blockquote>");
+            inline_into(&mut out, quote);
+            out.push_str("</blockquote>");
+        } else {
+            inline_into(&mut out, line);
+            if lines.peek().is_some() {
+                out.push_str("<br/>");
+            }
+        }
+    }
+
+    if in_list {
+        out.push_str("</ul>");
+    }
+
+    out
+}
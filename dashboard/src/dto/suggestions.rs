@@ -0,0 +1,14 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SuggestionEntry {
+    pub(crate) thread_id: String,
+    pub(crate) title: String,
+    /// "Anonymous" for posts made anonymously.
+    pub(crate) author: String,
+    pub(crate) status: String,
+    pub(crate) status_label: String,
+    pub(crate) upvotes: i32,
+    pub(crate) downvotes: i32,
+    pub(crate) response: Option<String>,
+}
@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+
+/// A user on the shared threat list, with their recent hits summed up.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SharedHitInfo {
+    pub(crate) user_id: String,
+    /// Sharing servers, this one included, the user tripped a honeypot in.
+    pub(crate) guilds: i64,
+    pub(crate) hits: i64,
+    pub(crate) last_hit_at: String,
+    /// The start of the last bait message's hash, enough to spot the same
+    /// spam across servers. `None` when every hit was a bait role.
+    pub(crate) message_hash: Option<String>,
+    pub(crate) hit_here: bool,
+    /// What this server did to them on joining, if anything.
+    pub(crate) screened: Option<String>,
+    pub(crate) whitelisted: bool,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct WhitelistEntryInfo {
+    pub(crate) user_id: String,
+    pub(crate) note: String,
+    pub(crate) added_by: String,
+    pub(crate) created_at: String,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ThreatListView {
+    /// Only servers that share their own hits see the list.
+    pub(crate) sharing: bool,
+    pub(crate) hits: Vec<SharedHitInfo>,
+    pub(crate) whitelist: Vec<WhitelistEntryInfo>,
+}
@@ -18,9 +18,8 @@ use leptos::config::{LeptosOptions, get_configuration};
 use leptos::prelude::provide_context;
 use leptos_axum::{LeptosRoutes, generate_route_list};
 use moka::future::Cache;
-use oauth2::basic::BasicClient;
 use oauth2::url::ParseError;
-use oauth2::{CsrfToken, EndpointNotSet, EndpointSet, Scope};
+use oauth2::{CsrfToken, Scope};
 use palworld::client::PalworldClient;
 use palworld::transport::Pelican;
 use sqlx::PgPool;
@@ -32,30 +31,30 @@ use tracing_subscriber::filter::LevelFilter;
 use tracing_subscriber::layer::SubscriberExt;
 use tracing_subscriber::util::SubscriberInitExt;
 use tracing_subscriber::{Layer, Registry, fmt};
-use zayden_app::config::BotConfig;
+use zayden_app::config::{BotConfig, PatreonConfig};
+use zayden_app::entitlement::PatreonClient;
 use zayden_app::events::listener::EventListener;
 use zayden_app::state::AppState as ZaydenAppState;
 
+use crate::state::OAuthClient;
 use crate::web::OAUTH_STATE_COOKIE;
 
 const SESSION_PRUNE_INTERVAL: Duration = Duration::from_hours(1);
 const OAUTH_STATE_TTL: Duration = Duration::from_mins(10);
+const PATREON_RECONCILE_INTERVAL: Duration = Duration::from_hours(6);
 
 #[derive(Clone)]
 pub(crate) struct WebState {
     pub(crate) app: Arc<ZaydenAppState>,
-    pub(crate) oauth_client: BasicClient<
-        EndpointSet,
-        EndpointNotSet,
-        EndpointNotSet,
-        EndpointNotSet,
-        EndpointSet,
-    >,
+    pub(crate) oauth_client: OAuthClient,
     pub(crate) http_oauth: oauth2::reqwest::Client,
     pub(crate) discord_token: String,
     pub(crate) invite_url: Option<String>,
     pub(crate) upgrade_url: Option<String>,
     pub(crate) kofi_verification_token: Option<String>,
+    pub(crate) patreon: Option<Arc<PatreonConfig>>,
+    pub(crate) patreon_oauth: Option<OAuthClient>,
+    pub(crate) patreon_client: Option<Arc<PatreonClient>>,
     pub(crate) session_cache: Cache<String, i64>,
     pub(crate) leptos_options: LeptosOptions,
     pub(crate) palworld: Arc<PalworldClient>,
@@ -86,6 +85,19 @@ impl WebState {
             pelican,
         ));
 
+        let patreon_oauth = config
+            .patreon
+            .as_ref()
+            .map(state::build_patreon_oauth_client)
+            .transpose()?;
+        let patreon_client = config.patreon.as_ref().map(|p| {
+            Arc::new(PatreonClient::new(
+                app.http.clone(),
+                p.creator_token.clone(),
+                p.campaign_id.clone(),
+            ))
+        });
+
         Ok(Self {
             app,
             palworld,
@@ -94,6 +106,9 @@ impl WebState {
             discord_token: config.discord_token.clone(),
             upgrade_url: config.upgrade_url.clone(),
             kofi_verification_token: config.kofi_verification_token.clone(),
+            patreon: config.patreon.clone().map(Arc::new),
+            patreon_oauth,
+            patreon_client,
             invite_url: config.invite_url.clone(),
             session_cache: Cache::builder()
                 .max_capacity(1024)
@@ -143,6 +158,23 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
     });
 
+    if let (Some(client), Some(config)) =
+        (web_state.patreon_client.clone(), web_state.patreon.clone())
+    {
+        let app = Arc::clone(&web_state.app);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(PATREON_RECONCILE_INTERVAL);
+            loop {
+                ticker.tick().await;
+                if let Err(e) =
+                    client.reconcile(&app.db, &app.entitlements, &config.tiers).await
+                {
+                    warn!(?e, "Patreon reconcile failed");
+                }
+            }
+        });
+    }
+
     let discord_http =
         Arc::new(twilight_http::Client::new(web_state.discord_token.clone()));
 
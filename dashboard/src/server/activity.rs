@@ -0,0 +1,144 @@
+use leptos::prelude::*;
+#[cfg(feature = "ssr")]
+use {
+    crate::dto::FieldChangeInfo,
+    crate::server::auth::{
+        app_state,
+        db_pool,
+        discord_client,
+        guild_admin_context,
+        server_err,
+    },
+    serde_json::Value,
+    std::collections::HashMap,
+    twilight_model::id::Id,
+    zayden_app::config::{Actor, ChangeSource, audit},
+};
+
+use crate::dto::SettingsChangeInfo;
+
+#[cfg(feature = "ssr")]
+const PAGE_SIZE: i64 = 20;
+
+#[cfg(feature = "ssr")]
+const DATE_FORMAT: &str = "%Y-%m-%d %H:%M UTC";
+
+#[cfg(feature = "ssr")]
+fn section_label(table: &str) -> String {
+    let label = match table {
+        "support_settings" => "Support",
+        "suggestions_settings" => "Suggestions",
+        "channels_settings" => "Channels",
+        "roles_settings" => "Roles",
+        "temp_voice_settings" => "Temp voice",
+        "lfg_settings" => "LFG",
+        "music_settings" => "Music",
+        "family_settings" => "Family",
+        "honeypot_settings" => "Honeypot",
+        "automod_settings" => "Automod",
+        "raid_settings" => "Raid protection",
+        "appeals_settings" => "Appeals",
+        "greetings_settings" => "Greetings",
+        "ai_settings" => "AI",
+        other => other,
+    };
+    label.to_string()
+}
+
+#[cfg(feature = "ssr")]
+const fn source_label(source: ChangeSource) -> &'static str {
+    match source {
+        ChangeSource::Dashboard => "dashboard",
+        ChangeSource::Command => "slash command",
+        ChangeSource::Rollback => "rollback",
+    }
+}
+
+#[cfg(feature = "ssr")]
+fn display_value(value: &Value) -> String {
+    match value {
+        Value::Null => "(not set)".to_string(),
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// The guild's settings changes, newest first.
+#[server]
+pub async fn list_settings_activity(
+    guild: String,
+    page: i32,
+) -> Result<Vec<SettingsChangeInfo>, ServerFnError> {
+    let (guild_id, _user, _token) = guild_admin_context(&guild).await?;
+    let pool = db_pool()?;
+
+    let offset = (i64::from(page).max(1) - 1) * PAGE_SIZE;
+    let changes = audit::history(&pool, guild_id.cast_unsigned(), PAGE_SIZE, offset)
+        .await
+        .map_err(server_err)?;
+
+    let http = discord_client()?;
+    let mut names: HashMap<u64, String> = HashMap::new();
+    let mut entries = Vec::with_capacity(changes.len());
+    for change in changes {
+        let actor_id = change.actor_id;
+        if !names.contains_key(&actor_id) {
+            let user = match Id::new_checked(actor_id) {
+                Some(id) => match http.user(id).await {
+                    Ok(resp) => resp.model().await.ok(),
+                    Err(_) => None,
+                },
+                None => None,
+            };
+            let name = user.map_or_else(
+                || format!("User {actor_id}"),
+                |user| user.global_name.unwrap_or(user.name),
+            );
+            names.insert(actor_id, name);
+        }
+
+        entries.push(SettingsChangeInfo {
+            id: change.id.to_string(),
+            actor: names.get(&actor_id).cloned().unwrap_or_default(),
+            source: source_label(change.source).to_string(),
+            section: section_label(&change.table),
+            at: change.created_at.strftime(DATE_FORMAT).to_string(),
+            fields: change
+                .fields()
+                .into_iter()
+                .map(|f| FieldChangeInfo {
+                    field: f.field,
+                    before: display_value(&f.before),
+                    after: display_value(&f.after),
+                })
+                .collect(),
+        });
+    }
+
+    Ok(entries)
+}
+
+/// Puts the settings row `change` touched back to how it was before it.
+#[server]
+pub async fn rollback_settings_change(
+    guild: String,
+    change: String,
+) -> Result<(), ServerFnError> {
+    let (guild_id, user_id, _token) = guild_admin_context(&guild).await?;
+    let pool = db_pool()?;
+
+    let id = change
+        .trim()
+        .parse::<i64>()
+        .map_err(|_e| ServerFnError::ServerError("invalid change".to_string()))?;
+
+    let change = audit::change(&pool, guild_id.cast_unsigned(), id)
+        .await
+        .map_err(server_err)?;
+
+    app_state()?
+        .settings
+        .rollback(&change, Actor::rollback(user_id.cast_unsigned()))
+        .await
+        .map_err(server_err)
+}
@@ -0,0 +1,367 @@
+use leptos::prelude::*;
+#[cfg(feature = "ssr")]
+use {
+    crate::dto::{
+        AiLimitInfo,
+        AiUsageInfo,
+        CommandUsageInfo,
+        CronJobInfo,
+        GrowthPoint,
+        LogEntryInfo,
+        ModuleUsageInfo,
+        Tier,
+    },
+    crate::server::auth::{WebRole, app_state, db_pool, require_role, server_err},
+    jiff::SignedDuration,
+    zayden_app::config::ConfigOverrides,
+    zayden_app::entitlement::{EntitlementScope, MANUAL_PROVIDER, grant_expiry},
+    zayden_app::services::{ai_usage, ops},
+};
+
+use crate::dto::{AdminOverview, AiUsageView, ConfigOverridesView, EntitlementInfo};
+
+#[cfg(feature = "ssr")]
+const WINDOW_DAYS: i32 = 30;
+
+#[cfg(feature = "ssr")]
+const RECENT_LOGS: i64 = 50;
+
+#[cfg(feature = "ssr")]
+const RECENT_ENTITLEMENTS: i64 = 200;
+
+#[cfg(feature = "ssr")]
+const DATE_FORMAT: &str = "%Y-%m-%d %H:%M UTC";
+
+#[cfg(feature = "ssr")]
+fn invalid(what: &str) -> ServerFnError {
+    ServerFnError::ServerError(format!("invalid {what}"))
+}
+
+#[cfg(feature = "ssr")]
+fn parse_snowflake(s: &str, what: &str) -> Result<u64, ServerFnError> {
+    s.trim().parse::<u64>().map_err(|_e| invalid(what))
+}
+
+#[cfg(feature = "ssr")]
+fn parse_days(s: &str) -> Result<i64, ServerFnError> {
+    s.trim().parse::<i64>().ok().filter(|d| *d > 0).ok_or_else(|| invalid("days"))
+}
+
+#[cfg(feature = "ssr")]
+fn blank_to_none(s: String) -> Option<String> {
+    let trimmed = s.trim();
+    (!trimmed.is_empty()).then(|| trimmed.to_string())
+}
+
+/// A token limit from a form: blank for none, otherwise a positive count.
+#[cfg(feature = "ssr")]
+fn parse_tokens(s: &str, what: &str) -> Result<Option<i64>, ServerFnError> {
+    match s.trim() {
+        "" => Ok(None),
+        tokens => tokens
+            .parse::<i64>()
+            .ok()
+            .filter(|t| *t > 0)
+            .map(Some)
+            .ok_or_else(|| invalid(what)),
+    }
+}
+
+#[cfg(feature = "ssr")]
+fn scope_label(scope: &EntitlementScope) -> String {
+    match scope {
+        EntitlementScope::User(id) => format!("User {id}"),
+        EntitlementScope::Guild(id) => format!("Server {id}"),
+        EntitlementScope::UserInGuild(user, guild) => {
+            format!("User {user} in server {guild}")
+        },
+    }
+}
+
+#[server]
+pub async fn get_admin_overview() -> Result<AdminOverview, ServerFnError> {
+    require_role(WebRole::Admin).await?;
+    let pool = db_pool()?;
+
+    let stats = ops::guild_stats(&pool, WINDOW_DAYS).await.map_err(server_err)?;
+    let growth = ops::guild_growth(&pool, WINDOW_DAYS).await.map_err(server_err)?;
+    let usage = ops::command_usage(&pool, WINDOW_DAYS).await.map_err(server_err)?;
+    let logs = ops::recent_logs(&pool, RECENT_LOGS).await.map_err(server_err)?;
+    let jobs = ops::cron_runs(&pool).await.map_err(server_err)?;
+
+    Ok(AdminOverview {
+        window_days: WINDOW_DAYS,
+        active_guilds: stats.active,
+        joined: stats.joined,
+        left: stats.left,
+        growth: growth
+            .into_iter()
+            .map(|d| GrowthPoint {
+                day: d.day.strftime("%b %d").to_string(),
+                joined: d.joined,
+                left: d.left,
+            })
+            .collect(),
+        modules: ops::usage_by_module(&usage)
+            .into_iter()
+            .map(|(module, uses)| ModuleUsageInfo { module, uses })
+            .collect(),
+        commands: usage
+            .into_iter()
+            .map(|c| CommandUsageInfo {
+                command: c.command,
+                module: c.module,
+                uses: c.uses,
+                failures: c.failures,
+            })
+            .collect(),
+        logs: logs
+            .into_iter()
+            .map(|l| LogEntryInfo {
+                level: l.level,
+                target: l.target,
+                message: l.message,
+                at: l.created_at.strftime(DATE_FORMAT).to_string(),
+            })
+            .collect(),
+        jobs: jobs
+            .into_iter()
+            .map(|j| CronJobInfo {
+                last_run: j.last_started_at.strftime(DATE_FORMAT).to_string(),
+                duration_ms: j
+                    .last_finished_at
+                    .duration_since(j.last_started_at)
+                    .as_millis()
+                    .try_into()
+                    .unwrap_or(i64::MAX),
+                next_run: j
+                    .next_run_at
+                    .map(|at| at.strftime(DATE_FORMAT).to_string()),
+                runs: j.runs,
+                id: j.job_id,
+            })
+            .collect(),
+    })
+}
+
+#[server]
+pub async fn get_config_overrides() -> Result<ConfigOverridesView, ServerFnError> {
+    require_role(WebRole::Admin).await?;
+
+    let overrides = ConfigOverrides::load(&db_pool()?).await.map_err(server_err)?;
+
+    Ok(ConfigOverridesView {
+        error_log_webhook: overrides.error_log_webhook.unwrap_or_default(),
+        normal_log_webhook: overrides.normal_log_webhook.unwrap_or_default(),
+        flaresolverr_url: overrides.flaresolverr_url.unwrap_or_default(),
+        youtube_cookies_file: overrides.youtube_cookies_file.unwrap_or_default(),
+    })
+}
+
+#[server]
+pub async fn save_config_overrides(
+    error_log_webhook: String,
+    normal_log_webhook: String,
+    flaresolverr_url: String,
+    youtube_cookies_file: String,
+) -> Result<(), ServerFnError> {
+    require_role(WebRole::Admin).await?;
+
+    ConfigOverrides {
+        error_log_webhook: blank_to_none(error_log_webhook),
+        normal_log_webhook: blank_to_none(normal_log_webhook),
+        flaresolverr_url: blank_to_none(flaresolverr_url),
+        youtube_cookies_file: blank_to_none(youtube_cookies_file),
+    }
+    .save(&db_pool()?)
+    .await
+    .map_err(server_err)
+}
+
+#[server]
+pub async fn list_entitlements() -> Result<Vec<EntitlementInfo>, ServerFnError> {
+    require_role(WebRole::Admin).await?;
+
+    let rows = app_state()?
+        .entitlements
+        .list_entitlements(RECENT_ENTITLEMENTS)
+        .await
+        .map_err(server_err)?;
+
+    Ok(rows
+        .into_iter()
+        .map(|e| EntitlementInfo {
+            scope: scope_label(&e.scope),
+            tier: Tier::from_key(e.tier.as_str()).unwrap_or(Tier::Free),
+            granted_at: e.granted_at.strftime(DATE_FORMAT).to_string(),
+            expires_at: e.expires_at.map(|at| at.strftime(DATE_FORMAT).to_string()),
+            provider: e.provider,
+            external_id: e.external_id,
+        })
+        .collect())
+}
+
+/// Grants `tier` to a user or server by hand. A blank `days` never expires;
+/// granting the same scope again replaces the earlier manual grant.
+#[server]
+pub async fn grant_entitlement(
+    scope: String,
+    target_id: String,
+    tier: String,
+    days: String,
+) -> Result<(), ServerFnError> {
+    require_role(WebRole::Admin).await?;
+
+    let target = parse_snowflake(&target_id, "id")?;
+    let scope = match scope.as_str() {
+        "user" => EntitlementScope::User(target),
+        "guild" => EntitlementScope::Guild(target),
+        _ => return Err(invalid("scope")),
+    };
+    let tier = Tier::from_key(tier.trim()).ok_or_else(|| invalid("tier"))?;
+    let expires_at = match days.trim() {
+        "" => None,
+        days => Some(grant_expiry(jiff::Timestamp::now(), parse_days(days)?)),
+    };
+
+    let external_id = scope.to_notify_payload();
+
+    app_state()?
+        .entitlements
+        .grant(
+            scope,
+            tier.as_entitlement(),
+            MANUAL_PROVIDER,
+            &external_id,
+            expires_at,
+        )
+        .await
+        .map_err(server_err)
+}
+
+#[server]
+pub async fn revoke_entitlement(
+    provider: String,
+    external_id: String,
+) -> Result<(), ServerFnError> {
+    require_role(WebRole::Admin).await?;
+
+    app_state()?
+        .entitlements
+        .revoke(&provider, &external_id)
+        .await
+        .map_err(server_err)
+}
+
+#[server]
+pub async fn extend_entitlement(
+    provider: String,
+    external_id: String,
+    days: String,
+) -> Result<(), ServerFnError> {
+    require_role(WebRole::Admin).await?;
+
+    let days = parse_days(&days)?;
+    let by = SignedDuration::from_secs(days.saturating_mul(86_400));
+
+    let extended = app_state()?
+        .entitlements
+        .extend(&provider, &external_id, by)
+        .await
+        .map_err(server_err)?;
+
+    match extended {
+        Some(_) => Ok(()),
+        None => Err(ServerFnError::ServerError(
+            "only entitlements with an expiry can be extended".to_string(),
+        )),
+    }
+}
+
+#[server]
+pub async fn get_ai_usage() -> Result<AiUsageView, ServerFnError> {
+    require_role(WebRole::Admin).await?;
+    let pool = db_pool()?;
+
+    let usage = ai_usage::usage_by_guild_and_model(&pool, WINDOW_DAYS)
+        .await
+        .map_err(server_err)?;
+    let limits = ai_usage::overrides(&pool).await.map_err(server_err)?;
+
+    let count =
+        |tokens: Option<i64>| tokens.map(|t| t.to_string()).unwrap_or_default();
+
+    Ok(AiUsageView {
+        window_days: WINDOW_DAYS,
+        rows: usage
+            .into_iter()
+            .map(|u| AiUsageInfo {
+                guild_id: u.guild_id.to_string(),
+                total_tokens: u.total_tokens(),
+                guild_name: u.guild_name,
+                model: u.model,
+                requests: u.requests,
+                prompt_tokens: u.prompt_tokens,
+                completion_tokens: u.completion_tokens,
+            })
+            .collect(),
+        limits: limits
+            .into_iter()
+            .map(|l| AiLimitInfo {
+                guild_id: l.guild_id.to_string(),
+                daily_tokens: count(l.daily_tokens),
+                monthly_tokens: count(l.monthly_tokens),
+                set_by: l.set_by.to_string(),
+                updated_at: l.updated_at.strftime(DATE_FORMAT).to_string(),
+            })
+            .collect(),
+    })
+}
+
+/// Raises a server's AI quota above its tier's. A blank limit keeps the
+/// tier's; a limit below the tier's has no effect.
+#[server]
+pub async fn raise_ai_limit(
+    guild_id: String,
+    daily_tokens: String,
+    monthly_tokens: String,
+) -> Result<(), ServerFnError> {
+    let user_id = require_role(WebRole::Admin).await?;
+
+    let guild_id = parse_snowflake(&guild_id, "server id")?;
+    let daily = parse_tokens(&daily_tokens, "daily limit")?;
+    let monthly = parse_tokens(&monthly_tokens, "monthly limit")?;
+    if daily.is_none() && monthly.is_none() {
+        return Err(ServerFnError::ServerError(
+            "set a daily or monthly limit".to_string(),
+        ));
+    }
+
+    ai_usage::set_override(
+        &db_pool()?,
+        guild_id.cast_signed(),
+        daily,
+        monthly,
+        user_id,
+    )
+    .await
+    .map_err(server_err)
+}
+
+#[server]
+pub async fn reset_ai_limit(guild_id: String) -> Result<(), ServerFnError> {
+    require_role(WebRole::Admin).await?;
+
+    let guild_id = parse_snowflake(&guild_id, "server id")?;
+    let cleared = ai_usage::clear_override(&db_pool()?, guild_id.cast_signed())
+        .await
+        .map_err(server_err)?;
+
+    if cleared {
+        Ok(())
+    } else {
+        Err(ServerFnError::ServerError(
+            "that server has no raised limit".to_string(),
+        ))
+    }
+}
@@ -0,0 +1,344 @@
+//! The AI chat page: what makes the bot answer, and the personas a Pro server
+//! writes for it. The bot reads the same `ai_settings` row, so saves here
+//! take effect on the next message.
+
+use leptos::prelude::*;
+#[cfg(feature = "ssr")]
+use {
+    crate::dto::{ChannelPersonaInfo, PersonaInfo, Tier},
+    crate::server::auth::{app_state, guild_admin_context, server_err},
+    crate::server::guild::admin_app,
+    crate::server::tier::guild_server_tier,
+    ai::chat::{Message, Role, strip_speaker_prefix},
+    ai::openai::AiClient,
+    ai::persona::custom_system_prompt,
+    sqlx::types::Json,
+    zayden_app::config::{AiPersonas, AiSettingsRow, AiTrigger, CustomPersona},
+    zayden_app::services::ai_usage::{self, QuotaLimit},
+};
+
+use crate::dto::AiView;
+
+/// Longest message the test chat sends.
+#[cfg(feature = "ssr")]
+const MAX_TEST_CHARS: usize = 500;
+
+/// Matches the bot's own budget for a Pro reply.
+#[cfg(feature = "ssr")]
+const TEST_MAX_TOKENS: u32 = 800;
+
+#[cfg(feature = "ssr")]
+fn opt_name(raw: &str) -> Option<String> {
+    let name = raw.trim();
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+/// Errors unless the server is on Pro or above.
+#[cfg(feature = "ssr")]
+async fn require_pro(guild_id: i64) -> Result<(), ServerFnError> {
+    let tier = guild_server_tier(guild_id.cast_unsigned()).await?;
+    if tier >= Tier::Pro {
+        return Ok(());
+    }
+
+    Err(ServerFnError::ServerError(format!(
+        "Custom personas need {}. This server is on {}.",
+        Tier::Pro.label(),
+        tier.label()
+    )))
+}
+
+/// `name` as one of the guild's personas, or `None` for the bot's own
+/// character when it is blank.
+#[cfg(feature = "ssr")]
+fn known(
+    personas: &AiPersonas,
+    name: &str,
+) -> Result<Option<String>, ServerFnError> {
+    let Some(name) = opt_name(name) else {
+        return Ok(None);
+    };
+
+    personas.get(&name).map(|p| Some(p.name.clone())).ok_or_else(|| {
+        ServerFnError::ServerError(format!("there is no persona called {name}"))
+    })
+}
+
+#[cfg(feature = "ssr")]
+fn persona_info(persona: &CustomPersona) -> PersonaInfo {
+    PersonaInfo {
+        name: persona.name.clone(),
+        prompt: persona.prompt.clone(),
+        style: persona.style.clone(),
+        word_limit: persona.word_limit,
+        temperature: format!("{:.1}", persona.temperature),
+    }
+}
+
+#[cfg(feature = "ssr")]
+const fn limit_message(limit: QuotaLimit) -> &'static str {
+    match limit {
+        QuotaLimit::UserDaily => {
+            "you've used up your own AI allowance for today; tests count toward it"
+        },
+        QuotaLimit::GuildDaily => {
+            "this server has used up today's AI allowance; tests count toward it"
+        },
+        QuotaLimit::GuildMonthly => {
+            "this server has used up this month's AI allowance; tests count \
+             toward it"
+        },
+    }
+}
+
+#[server]
+pub async fn get_ai_settings(guild: String) -> Result<AiView, ServerFnError> {
+    let (guild_id, user_id, _token) = guild_admin_context(&guild).await?;
+    let app = app_state()?;
+
+    let settings = app.settings.ai.get(guild_id).await.map_err(server_err)?;
+    let tier = guild_server_tier(guild_id.cast_unsigned()).await?;
+    let personas = &settings.personas.0;
+
+    let entitlement = tier.as_entitlement();
+    let quota = ai_usage::quota(&app.db, guild_id, entitlement, entitlement)
+        .await
+        .map_err(server_err)?;
+    let spent =
+        ai_usage::spent(&app.db, guild_id, user_id).await.map_err(server_err)?;
+
+    Ok(AiView {
+        tier,
+        next_tier: tier.next_paid(),
+        tokens_today: spent.guild_today,
+        tokens_month: spent.guild_month,
+        daily_limit: quota.guild_daily,
+        monthly_limit: quota.guild_monthly,
+        trigger_mode: settings.trigger().as_str().to_string(),
+        keywords: settings.keywords.join("\n"),
+        personas: personas.personas.iter().map(persona_info).collect(),
+        default_persona: personas.default.clone().unwrap_or_default(),
+        channel_personas: personas
+            .channels
+            .iter()
+            .map(|c| ChannelPersonaInfo {
+                channel_id: c.channel_id.to_string(),
+                persona: c.persona.clone().unwrap_or_default(),
+            })
+            .collect(),
+    })
+}
+
+#[server]
+pub async fn save_ai_trigger(
+    guild: String,
+    trigger_mode: String,
+    keywords: String,
+) -> Result<(), ServerFnError> {
+    let (guild_id, actor, app) = admin_app(&guild).await?;
+
+    let trigger = AiTrigger::parse(&trigger_mode);
+    let keywords = AiSettingsRow::parse_keywords(&keywords);
+
+    if trigger == AiTrigger::Keyword && keywords.is_empty() {
+        return Err(ServerFnError::ServerError(
+            "keyword mode needs at least one keyword".to_string(),
+        ));
+    }
+
+    app.settings
+        .ai
+        .update_by(guild_id, actor, |p| {
+            p.trigger_mode = trigger.as_str().to_string();
+            p.keywords = keywords;
+        })
+        .await
+        .map(|_| ())
+        .map_err(server_err)
+}
+
+#[server]
+pub async fn save_ai_persona(
+    guild: String,
+    name: String,
+    prompt: String,
+    style: String,
+    word_limit: String,
+    temperature: String,
+) -> Result<(), ServerFnError> {
+    let (guild_id, actor, app) = admin_app(&guild).await?;
+    require_pro(guild_id).await?;
+
+    let persona =
+        CustomPersona::from_form(&name, &prompt, &style, &word_limit, &temperature)
+            .map_err(server_err)?;
+
+    let current = app.settings.ai.get(guild_id).await.map_err(server_err)?;
+    let mut personas = current.personas.0.clone();
+    personas.save(persona).map_err(server_err)?;
+
+    app.settings
+        .ai
+        .update_by(guild_id, actor, |p| p.personas = Json(personas))
+        .await
+        .map(|_| ())
+        .map_err(server_err)
+}
+
+#[server]
+pub async fn remove_ai_persona(
+    guild: String,
+    name: String,
+) -> Result<(), ServerFnError> {
+    let (guild_id, actor, app) = admin_app(&guild).await?;
+
+    let current = app.settings.ai.get(guild_id).await.map_err(server_err)?;
+    let mut personas = current.personas.0.clone();
+    if !personas.remove(&name) {
+        return Err(ServerFnError::ServerError(
+            "that persona is not on this server's list".to_string(),
+        ));
+    }
+
+    app.settings
+        .ai
+        .update_by(guild_id, actor, |p| p.personas = Json(personas))
+        .await
+        .map(|_| ())
+        .map_err(server_err)
+}
+
+#[server]
+pub async fn save_ai_default_persona(
+    guild: String,
+    persona: String,
+) -> Result<(), ServerFnError> {
+    let (guild_id, actor, app) = admin_app(&guild).await?;
+
+    let current = app.settings.ai.get(guild_id).await.map_err(server_err)?;
+    let default = known(&current.personas, &persona)?;
+
+    app.settings
+        .ai
+        .update_by(guild_id, actor, |p| p.personas.default = default)
+        .await
+        .map(|_| ())
+        .map_err(server_err)
+}
+
+#[server]
+pub async fn set_ai_channel_persona(
+    guild: String,
+    channel_id: String,
+    persona: String,
+) -> Result<(), ServerFnError> {
+    let (guild_id, actor, app) = admin_app(&guild).await?;
+
+    let channel_id = channel_id
+        .trim()
+        .parse::<i64>()
+        .map_err(|_e| ServerFnError::ServerError("pick a channel".to_string()))?;
+
+    let current = app.settings.ai.get(guild_id).await.map_err(server_err)?;
+    let persona = known(&current.personas, &persona)?;
+
+    app.settings
+        .ai
+        .update_by(guild_id, actor, |p| {
+            p.personas.set_channel(channel_id, persona);
+        })
+        .await
+        .map(|_| ())
+        .map_err(server_err)
+}
+
+#[server]
+pub async fn remove_ai_channel_persona(
+    guild: String,
+    channel_id: String,
+) -> Result<(), ServerFnError> {
+    let (guild_id, actor, app) = admin_app(&guild).await?;
+
+    let channel_id = channel_id.trim().parse::<i64>().ok();
+
+    app.settings
+        .ai
+        .update_by(guild_id, actor, |p| {
+            p.personas.channels.retain(|c| Some(c.channel_id) != channel_id);
+        })
+        .await
+        .map(|_| ())
+        .map_err(server_err)
+}
+
+/// One reply from a saved persona, without tools or chat history, so admins
+/// can hear how it sounds before pointing it at a channel. Tests are metered
+/// like any other reply.
+#[server]
+pub async fn test_ai_persona(
+    guild: String,
+    persona: String,
+    message: String,
+) -> Result<String, ServerFnError> {
+    let (guild_id, user_id, _token) = guild_admin_context(&guild).await?;
+    let app = app_state()?;
+    require_pro(guild_id).await?;
+
+    let message = message.trim();
+    if message.is_empty() {
+        return Err(ServerFnError::ServerError(
+            "write a message to send".to_string(),
+        ));
+    }
+    if message.chars().count() > MAX_TEST_CHARS {
+        return Err(ServerFnError::ServerError(format!(
+            "test messages are limited to {MAX_TEST_CHARS} characters"
+        )));
+    }
+
+    let settings = app.settings.ai.get(guild_id).await.map_err(server_err)?;
+    let persona = settings.personas.get(&persona).ok_or_else(|| {
+        ServerFnError::ServerError("save the persona before testing it".to_string())
+    })?;
+
+    let tier = guild_server_tier(guild_id.cast_unsigned()).await?.as_entitlement();
+    let quota =
+        ai_usage::quota(&app.db, guild_id, tier, tier).await.map_err(server_err)?;
+    let spent =
+        ai_usage::spent(&app.db, guild_id, user_id).await.map_err(server_err)?;
+    if let Some(limit) = spent.exceeded(quota) {
+        return Err(ServerFnError::ServerError(limit_message(limit).to_string()));
+    }
+
+    let messages = vec![
+        Message::new(
+            Role::System,
+            custom_system_prompt(persona, persona.word_limit, false),
+        ),
+        Message::new(Role::User, format!("Tester: {message}")),
+    ];
+
+    let reply =
+        AiClient::new(&app.ai_provider_key, &app.ai_api_endpoint, &app.ai_model_pro)
+            .map_err(server_err)?
+            .with_temperature(persona.temperature)
+            .chat(messages, TEST_MAX_TOKENS)
+            .await
+            .map_err(server_err)?;
+
+    if let Err(e) = ai_usage::record(
+        &app.db,
+        guild_id,
+        user_id,
+        &app.ai_model_pro,
+        reply.usage.prompt_tokens,
+        reply.usage.completion_tokens,
+    )
+    .await
+    {
+        tracing::warn!(error = ?e, "failed to record AI test usage");
+    }
+
+    Ok(strip_speaker_prefix(&reply.text, &[persona.name.as_str(), "Tester"])
+        .to_string())
+}
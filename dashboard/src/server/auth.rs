@@ -75,6 +75,7 @@ pub(crate) async fn current_user_id() -> Result<i64, ServerFnError> {
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub(crate) enum WebRole {
     Admin,
+    Moderator,
 }
 
 #[cfg(feature = "ssr")]
@@ -82,16 +83,16 @@ impl WebRole {
     pub(crate) const fn as_str(self) -> &'static str {
         match self {
             Self::Admin => "admin",
+            Self::Moderator => "moderator",
         }
     }
 }
 
 #[cfg(feature = "ssr")]
-pub(crate) async fn require_role(role: WebRole) -> Result<i64, ServerFnError> {
-    let user_id = current_user_id().await?;
+async fn has_role(user_id: i64, role: WebRole) -> Result<bool, ServerFnError> {
     let pool = db_pool()?;
 
-    let granted = sqlx::query_scalar!(
+    Ok(sqlx::query_scalar!(
         "SELECT 1 FROM web_user_roles WHERE discord_user_id = $1 AND role = $2",
         user_id,
         role.as_str(),
@@ -99,15 +100,37 @@ pub(crate) async fn require_role(role: WebRole) -> Result<i64, ServerFnError> {
     .fetch_optional(&pool)
     .await
     .map_err(server_err)?
-    .is_some();
+    .is_some())
+}
+
+#[cfg(feature = "ssr")]
+pub(crate) async fn require_role(role: WebRole) -> Result<i64, ServerFnError> {
+    let user_id = current_user_id().await?;
 
-    if granted {
+    if has_role(user_id, role).await? {
         Ok(user_id)
     } else {
         Err(ServerFnError::ServerError("forbidden".to_string()))
     }
 }
 
+/// [`guild_admin_context`] for moderation pages, which additionally need the
+/// `moderator` web role (or `admin`). Returns the guild and user ids.
+#[cfg(feature = "ssr")]
+pub(crate) async fn guild_moderator_context(
+    guild_id_str: &str,
+) -> Result<(i64, i64), ServerFnError> {
+    let (guild_id, user_id, _token) = guild_admin_context(guild_id_str).await?;
+
+    if has_role(user_id, WebRole::Moderator).await?
+        || has_role(user_id, WebRole::Admin).await?
+    {
+        Ok((guild_id, user_id))
+    } else {
+        Err(ServerFnError::ServerError("forbidden".to_string()))
+    }
+}
+
 #[cfg(feature = "ssr")]
 pub(crate) async fn guild_admin_context(
     guild_id_str: &str,
@@ -143,6 +143,7 @@ pub fn with_channel_allowlist(
 #[cfg(feature = "ssr")]
 pub(crate) struct GuildContext {
     pub(crate) guild_id: Id<GuildMarker>,
+    pub(crate) user_id: u64,
     pub(crate) access_token: String,
     pub(crate) http: Arc<twilight_http::Client>,
     pub(crate) app_id: u64,
@@ -152,10 +153,11 @@ pub(crate) struct GuildContext {
 pub(crate) async fn guild_context(
     guild: &str,
 ) -> Result<GuildContext, ServerFnError> {
-    let (guild_id, _user, access_token) = guild_admin_context(guild).await?;
+    let (guild_id, user_id, access_token) = guild_admin_context(guild).await?;
 
     Ok(GuildContext {
         guild_id: Id::new(guild_id.cast_unsigned()),
+        user_id: user_id.cast_unsigned(),
         access_token,
         http: discord_client()?,
         app_id: app_state()?.zayden_id,
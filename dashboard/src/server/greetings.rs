@@ -25,6 +25,7 @@ use {
     },
     twilight_model::id::Id,
     twilight_model::id::marker::ChannelMarker,
+    zayden_app::config::Actor,
 };
 
 #[cfg(feature = "ssr")]
@@ -137,7 +138,7 @@ pub async fn save_greeting_messages(
     morning_message: String,
     night_message: String,
 ) -> Result<(), ServerFnError> {
-    let (guild_id, _user, _token) = guild_admin_context(&guild).await?;
+    let (guild_id, user_id, _token) = guild_admin_context(&guild).await?;
     let app = app_state()?;
 
     GreetingsSettings::save_messages(
@@ -145,6 +146,7 @@ pub async fn save_greeting_messages(
         GuildId::new(guild_id.cast_unsigned()),
         &morning_message,
         &night_message,
+        Actor::dashboard(user_id.cast_unsigned()),
     )
     .await
     .map_err(server_err)
@@ -179,7 +181,7 @@ pub async fn save_greeting_cooldowns(
     user_cooldown: String,
     guild_cooldown: String,
 ) -> Result<(), ServerFnError> {
-    let (guild_id, _user, _token) = guild_admin_context(&guild).await?;
+    let (guild_id, user_id, _token) = guild_admin_context(&guild).await?;
     let app = app_state()?;
 
     let guild_id = GuildId::new(guild_id.cast_unsigned());
@@ -214,6 +216,7 @@ pub async fn save_greeting_cooldowns(
         guild_id,
         requested,
         floor,
+        Actor::dashboard(user_id.cast_unsigned()),
     )
     .await
     .map(|_| ())
@@ -1,6 +1,7 @@
 use leptos::prelude::*;
 #[cfg(feature = "ssr")]
 use {
+    crate::dto::{AutomodFilterInfo, HoneypotTrapInfo},
     crate::server::auth::{
         app_state,
         bearer_client,
@@ -9,7 +10,7 @@ use {
         guild_admin_context,
         server_err,
     },
-    honeypot::{HoneypotConfig, HoneypotSettings},
+    honeypot::HoneypotConfig,
     leptos_axum::{extract, redirect},
     std::sync::Arc,
     suggestions::ReviewThresholds,
@@ -18,16 +19,37 @@ use {
     twilight_model::channel::ChannelType,
     twilight_model::guild::Permissions,
     twilight_model::id::Id,
-    zayden_app::config::MusicSettingsRow,
+    zayden_app::config::{
+        Actor,
+        AutomodAction,
+        AutomodRules,
+        AutomodSettingsRow,
+        CapsRule,
+        CaptchaFailAction,
+        HoneypotSettingsRow,
+        HoneypotTrap,
+        MentionRule,
+        MusicSettingsRow,
+        RaidAction,
+        RaidSettingsRow,
+        RateRule,
+        RolesSettingsRow,
+        SharedAction,
+        TrapAction,
+        TrapKind,
+        WordFilter,
+    },
     zayden_app::state::AppState,
 };
 
 use crate::dto::{GuildInfo, GuildSettings};
 
 #[cfg(feature = "ssr")]
-async fn admin_app(guild: &str) -> Result<(i64, Arc<AppState>), ServerFnError> {
-    let (guild_id, _user, _token) = guild_admin_context(guild).await?;
-    Ok((guild_id, app_state()?))
+pub(crate) async fn admin_app(
+    guild: &str,
+) -> Result<(i64, Actor, Arc<AppState>), ServerFnError> {
+    let (guild_id, user_id, _token) = guild_admin_context(guild).await?;
+    Ok((guild_id, Actor::dashboard(user_id.cast_unsigned()), app_state()?))
 }
 
 #[cfg(feature = "ssr")]
@@ -44,6 +66,11 @@ fn parse_role(s: &str) -> Result<RoleId, ServerFnError> {
         .map_err(|_e| ServerFnError::ServerError("invalid role".to_string()))
 }
 
+#[cfg(feature = "ssr")]
+fn action_str(action: Option<AutomodAction>) -> String {
+    action.map(AutomodAction::as_str).unwrap_or_default().to_string()
+}
+
 #[server]
 pub async fn list_manageable_guilds() -> Result<Vec<GuildInfo>, ServerFnError> {
     let pool = db_pool()?;
@@ -97,7 +124,7 @@ pub async fn get_guild_settings(
         v.map(|n| n.to_string())
     }
 
-    let (guild_id, app) = admin_app(&guild_id).await?;
+    let (guild_id, _actor, app) = admin_app(&guild_id).await?;
     let s = &app.settings;
 
     let support = s.support.get(guild_id).await.map_err(server_err)?;
@@ -110,10 +137,20 @@ pub async fn get_guild_settings(
     let music = s.music.get(guild_id).await.map_err(server_err)?;
     let honeypot = s.honeypot.get(guild_id).await.map_err(server_err)?;
     let ai = s.ai.get(guild_id).await.map_err(server_err)?;
+    let automod = s.automod.get(guild_id).await.map_err(server_err)?;
+    let raid = s.raid.get(guild_id).await.map_err(server_err)?;
+    let appeals = s.appeals.get(guild_id).await.map_err(server_err)?;
+
+    let rules = &automod.rules;
+    let rate = rules.rate.unwrap_or(AutomodRules::DEFAULT_RATE);
+    let duplicates = rules.duplicates.unwrap_or(AutomodRules::DEFAULT_DUPLICATES);
+    let mentions = rules.mentions.unwrap_or(AutomodRules::DEFAULT_MENTIONS);
+    let caps = rules.caps.unwrap_or(AutomodRules::DEFAULT_CAPS);
 
     Ok(GuildSettings {
         support_channel_id: opt_str(support.support_channel_id),
         faq_channel_id: opt_str(support.faq_channel_id),
+        transcript_channel_id: opt_str(support.transcript_channel_id),
         suggestions_channel_id: opt_str(suggestions.suggestions_channel_id),
         review_channel_id: opt_str(suggestions.review_channel_id),
         suggestions_promote_threshold: suggestions.promote_threshold.to_string(),
@@ -124,6 +161,14 @@ pub async fn get_guild_settings(
         artist_role_id: opt_str(roles.artist_role_id),
         sleep_role_id: opt_str(roles.sleep_role_id),
         verified_role_id: opt_str(roles.verified_role_id),
+        require_rules: roles.require_rules,
+        reverify_on_rules_change: roles.reverify_on_rules_change,
+        captcha_enabled: roles.captcha_enabled,
+        captcha_max_attempts: roles.captcha_max_attempts.to_string(),
+        captcha_lockout_minutes: roles.captcha_lockout_minutes.to_string(),
+        captcha_min_account_age_days: roles.captcha_min_account_age_days.to_string(),
+        captcha_fail_action: roles.captcha_fail_action().as_str().to_string(),
+        captcha_log_channel_id: opt_str(roles.captcha_log_channel_id),
         temp_voice_category: opt_str(temp_voice.temp_voice_category),
         temp_voice_creator_channel: opt_str(temp_voice.temp_voice_creator_channel),
         lfg_channel_id: opt_str(lfg.lfg_channel_id),
@@ -138,6 +183,63 @@ pub async fn get_guild_settings(
         honeypot_exempt_admins: honeypot.exempt_admins,
         honeypot_exempt_role_id: opt_str(honeypot.exempt_role_id),
         honeypot_purge_seconds: honeypot.purge_seconds.to_string(),
+        honeypot_share_hits: honeypot.share_hits,
+        honeypot_shared_threshold: honeypot.shared_threshold.to_string(),
+        honeypot_shared_action: honeypot.shared_action().as_str().to_string(),
+        honeypot_log_channel_id: opt_str(honeypot.log_channel_id),
+        honeypot_traps: honeypot
+            .traps
+            .iter()
+            .map(|trap| HoneypotTrapInfo {
+                kind: trap.kind.as_str().to_string(),
+                target_id: trap.target_id.to_string(),
+                action: trap.action.as_str().to_string(),
+                purge_seconds: trap.purge_seconds,
+                timeout_minutes: trap.timeout_minutes,
+                quarantine_role_id: opt_str(trap.quarantine_role_id),
+            })
+            .collect(),
+        automod_enabled: automod.enabled,
+        automod_exempt_admins: automod.exempt_admins,
+        automod_exempt_role_id: opt_str(automod.exempt_role_id),
+        automod_log_channel_id: opt_str(automod.log_channel_id),
+        automod_timeout_minutes: automod.timeout_minutes.to_string(),
+        automod_rate_action: action_str(rules.rate.map(|r| r.action)),
+        automod_rate_max: rate.max_messages.to_string(),
+        automod_rate_seconds: rate.per_seconds.to_string(),
+        automod_duplicates_action: action_str(rules.duplicates.map(|r| r.action)),
+        automod_duplicates_max: duplicates.max_messages.to_string(),
+        automod_duplicates_seconds: duplicates.per_seconds.to_string(),
+        automod_mentions_action: action_str(rules.mentions.map(|r| r.action)),
+        automod_mentions_max: mentions.max_mentions.to_string(),
+        automod_invites_action: action_str(rules.invites),
+        automod_scam_links_action: action_str(rules.scam_links),
+        automod_caps_action: action_str(rules.caps.map(|r| r.action)),
+        automod_caps_percent: caps.max_percent.to_string(),
+        automod_caps_min_length: caps.min_length.to_string(),
+        automod_filters: rules
+            .filters
+            .iter()
+            .map(|filter| AutomodFilterInfo {
+                pattern: filter.pattern.clone(),
+                regex: filter.regex,
+                action: filter.action.as_str().to_string(),
+            })
+            .collect(),
+        raid_enabled: raid.enabled,
+        raid_join_threshold: raid.join_threshold.to_string(),
+        raid_join_window_seconds: raid.join_window_seconds.to_string(),
+        raid_lockdown_minutes: raid.lockdown_minutes.to_string(),
+        raid_min_account_age_days: raid.min_account_age_days.to_string(),
+        raid_flag_default_avatar: raid.flag_default_avatar,
+        raid_flag_similar_names: raid.flag_similar_names,
+        raid_joiner_action: raid.joiner_action().as_str().to_string(),
+        raid_timeout_minutes: raid.timeout_minutes.to_string(),
+        raid_raise_verification: raid.raise_verification,
+        raid_slowmode_seconds: raid.slowmode_seconds.to_string(),
+        raid_alert_channel_id: opt_str(raid.alert_channel_id),
+        appeals_enabled: appeals.enabled,
+        appeals_channel_id: opt_str(appeals.channel_id),
         ai_enabled: ai.enabled,
         ai_channel_id: opt_str(ai.channel_id),
     })
@@ -148,27 +250,29 @@ pub async fn save_support_settings(
     guild: String,
     support_channel_id: String,
     faq_channel_id: String,
+    transcript_channel_id: String,
     suggestions_channel_id: String,
     review_channel_id: String,
     promote_threshold: String,
     demote_threshold: String,
 ) -> Result<(), ServerFnError> {
-    let (guild_id, app) = admin_app(&guild).await?;
+    let (guild_id, actor, app) = admin_app(&guild).await?;
 
     let thresholds = ReviewThresholds::parse(&promote_threshold, &demote_threshold);
 
     app.settings
         .support
-        .update(guild_id, |p| {
+        .update_by(guild_id, actor, |p| {
             p.support_channel_id = parse_id(&support_channel_id);
             p.faq_channel_id = parse_id(&faq_channel_id);
+            p.transcript_channel_id = parse_id(&transcript_channel_id);
         })
         .await
         .map_err(server_err)?;
 
     app.settings
         .suggestions
-        .update(guild_id, |p| {
+        .update_by(guild_id, actor, |p| {
             p.suggestions_channel_id = parse_id(&suggestions_channel_id);
             p.review_channel_id = parse_id(&review_channel_id);
             p.promote_threshold = thresholds.promote();
@@ -242,11 +346,11 @@ pub async fn save_channel_settings(
     general_channel_id: String,
     spoiler_channel_id: String,
 ) -> Result<(), ServerFnError> {
-    let (guild_id, app) = admin_app(&guild).await?;
+    let (guild_id, actor, app) = admin_app(&guild).await?;
 
     app.settings
         .channels
-        .update(guild_id, |p| {
+        .update_by(guild_id, actor, |p| {
             p.rules_channel_id = parse_id(&rules_channel_id);
             p.general_channel_id = parse_id(&general_channel_id);
             p.spoiler_channel_id = parse_id(&spoiler_channel_id);
@@ -262,15 +366,51 @@ pub async fn save_role_settings(
     artist_role_id: String,
     sleep_role_id: String,
     verified_role_id: String,
+    require_rules: String,
+    reverify_on_rules_change: String,
+    captcha_enabled: String,
+    captcha_max_attempts: String,
+    captcha_lockout_minutes: String,
+    captcha_min_account_age_days: String,
+    captcha_fail_action: String,
+    captcha_log_channel_id: String,
 ) -> Result<(), ServerFnError> {
-    let (guild_id, app) = admin_app(&guild).await?;
+    let (guild_id, actor, app) = admin_app(&guild).await?;
+
+    let require_rules = require_rules.trim() == "true";
+    let reverify_on_rules_change = reverify_on_rules_change.trim() == "true";
+    let captcha_enabled = captcha_enabled.trim() == "true";
+    let captcha_max_attempts = captcha_max_attempts
+        .trim()
+        .parse::<i32>()
+        .unwrap_or(RolesSettingsRow::DEFAULT_CAPTCHA_MAX_ATTEMPTS)
+        .clamp(1, RolesSettingsRow::MAX_CAPTCHA_ATTEMPTS);
+    let captcha_lockout_minutes = captcha_lockout_minutes
+        .trim()
+        .parse::<i32>()
+        .unwrap_or(RolesSettingsRow::DEFAULT_CAPTCHA_LOCKOUT_MINUTES)
+        .clamp(0, RolesSettingsRow::MAX_CAPTCHA_LOCKOUT_MINUTES);
+    let captcha_min_account_age_days = captcha_min_account_age_days
+        .trim()
+        .parse::<i32>()
+        .unwrap_or(0)
+        .clamp(0, RolesSettingsRow::MAX_CAPTCHA_ACCOUNT_AGE_DAYS);
+    let captcha_fail_action = CaptchaFailAction::parse(&captcha_fail_action);
 
     app.settings
         .roles
-        .update(guild_id, |p| {
+        .update_by(guild_id, actor, |p| {
             p.artist_role_id = parse_id(&artist_role_id);
             p.sleep_role_id = parse_id(&sleep_role_id);
             p.verified_role_id = parse_id(&verified_role_id);
+            p.require_rules = require_rules;
+            p.reverify_on_rules_change = reverify_on_rules_change;
+            p.captcha_enabled = captcha_enabled;
+            p.captcha_max_attempts = captcha_max_attempts;
+            p.captcha_lockout_minutes = captcha_lockout_minutes;
+            p.captcha_min_account_age_days = captcha_min_account_age_days;
+            p.captcha_fail_action = captcha_fail_action.as_str().to_string();
+            p.captcha_log_channel_id = parse_id(&captcha_log_channel_id);
         })
         .await
         .map(|_| ())
@@ -283,11 +423,11 @@ pub async fn save_temp_voice_settings(
     temp_voice_category: String,
     temp_voice_creator_channel: String,
 ) -> Result<(), ServerFnError> {
-    let (guild_id, app) = admin_app(&guild).await?;
+    let (guild_id, actor, app) = admin_app(&guild).await?;
 
     app.settings
         .temp_voice
-        .update(guild_id, |p| {
+        .update_by(guild_id, actor, |p| {
             p.temp_voice_category = parse_id(&temp_voice_category);
             p.temp_voice_creator_channel = parse_id(&temp_voice_creator_channel);
         })
@@ -304,7 +444,7 @@ pub async fn create_temp_voice_creator_channel(
     guild: String,
     temp_voice_category: String,
 ) -> Result<(), ServerFnError> {
-    let (guild_id, app) = admin_app(&guild).await?;
+    let (guild_id, actor, app) = admin_app(&guild).await?;
 
     let category = parse_id(&temp_voice_category)
         .and_then(|id| Id::new_checked(id.cast_unsigned()));
@@ -329,7 +469,7 @@ pub async fn create_temp_voice_creator_channel(
 
     app.settings
         .temp_voice
-        .update(guild_id, |p| {
+        .update_by(guild_id, actor, |p| {
             p.temp_voice_category = Some(category.get().cast_signed());
             p.temp_voice_creator_channel = Some(channel.id.get().cast_signed());
         })
@@ -343,13 +483,13 @@ pub async fn save_family_settings(
     guild: String,
     max_partners: String,
 ) -> Result<(), ServerFnError> {
-    let (guild_id, app) = admin_app(&guild).await?;
+    let (guild_id, actor, app) = admin_app(&guild).await?;
 
     let max_partners = max_partners.trim().parse::<i32>().unwrap_or(1).max(1);
 
     app.settings
         .family
-        .update(guild_id, |p| {
+        .update_by(guild_id, actor, |p| {
             p.max_partners = max_partners;
         })
         .await
@@ -365,7 +505,7 @@ pub async fn save_music_settings(
     announce_now_playing: String,
     announce_channel_id: String,
 ) -> Result<(), ServerFnError> {
-    let (guild_id, app) = admin_app(&guild).await?;
+    let (guild_id, actor, app) = admin_app(&guild).await?;
 
     let auto_disconnect_secs =
         MusicSettingsRow::parse_auto_disconnect_secs(&auto_disconnect_secs);
@@ -373,7 +513,7 @@ pub async fn save_music_settings(
 
     app.settings
         .music
-        .update(guild_id, |p| {
+        .update_by(guild_id, actor, |p| {
             p.dj_role_id = parse_id(&dj_role_id);
             p.auto_disconnect_secs = auto_disconnect_secs;
             p.announce_now_playing = announce_now_playing;
@@ -391,8 +531,9 @@ pub async fn save_honeypot_settings(
     exempt_admins: String,
     exempt_role_id: String,
     purge_seconds: String,
+    log_channel_id: String,
 ) -> Result<(), ServerFnError> {
-    let (guild_id, app) = admin_app(&guild).await?;
+    let (guild_id, actor, app) = admin_app(&guild).await?;
 
     let config = HoneypotConfig::from_form(
         &channel_id,
@@ -401,15 +542,364 @@ pub async fn save_honeypot_settings(
         &purge_seconds,
     )
     .map_err(server_err)?;
+    let log_channel_id = parse_id(&log_channel_id);
 
-    HoneypotSettings::save(
-        &app.settings.honeypot,
-        GuildId::new(guild_id.cast_unsigned()),
-        config,
-    )
-    .await
-    .map(|_| ())
-    .map_err(server_err)
+    app.settings
+        .honeypot
+        .update_by(guild_id, actor, |p| {
+            config.apply(p);
+            p.log_channel_id = log_channel_id;
+        })
+        .await
+        .map(|_| ())
+        .map_err(server_err)
+}
+
+#[server]
+pub async fn save_honeypot_sharing(
+    guild: String,
+    share_hits: String,
+    shared_threshold: String,
+    shared_action: String,
+) -> Result<(), ServerFnError> {
+    let (guild_id, actor, app) = admin_app(&guild).await?;
+
+    let share_hits = share_hits.trim() == "true";
+    let shared_threshold =
+        HoneypotSettingsRow::parse_shared_threshold(&shared_threshold);
+    let shared_action = SharedAction::parse(&shared_action);
+
+    app.settings
+        .honeypot
+        .update_by(guild_id, actor, |p| {
+            p.share_hits = share_hits;
+            p.shared_threshold = shared_threshold;
+            p.shared_action = shared_action.as_str().to_string();
+        })
+        .await
+        .map(|_| ())
+        .map_err(server_err)
+}
+
+/// Adds a trap, or replaces the one already on that channel or role.
+#[server]
+pub async fn add_honeypot_trap(
+    guild: String,
+    kind: String,
+    channel_id: String,
+    role_id: String,
+    action: String,
+    purge_seconds: String,
+    timeout_minutes: String,
+    quarantine_role_id: String,
+) -> Result<(), ServerFnError> {
+    let (guild_id, actor, app) = admin_app(&guild).await?;
+
+    let kind = TrapKind::parse(&kind).ok_or_else(|| {
+        ServerFnError::ServerError("invalid trap type".to_string())
+    })?;
+    let target_id = match kind {
+        TrapKind::Channel => parse_id(&channel_id),
+        TrapKind::Role => parse_id(&role_id),
+    };
+    let target_id = target_id.ok_or_else(|| {
+        ServerFnError::ServerError("pick a channel or role to trap".to_string())
+    })?;
+    let action = TrapAction::parse(&action)
+        .ok_or_else(|| ServerFnError::ServerError("invalid action".to_string()))?;
+    let quarantine_role_id = parse_id(&quarantine_role_id);
+
+    if action == TrapAction::Quarantine && quarantine_role_id.is_none() {
+        return Err(ServerFnError::ServerError(
+            "a quarantine trap needs a quarantine role".to_string(),
+        ));
+    }
+    if kind == TrapKind::Role && quarantine_role_id == Some(target_id) {
+        return Err(ServerFnError::ServerError(
+            "the quarantine role can't be the bait role".to_string(),
+        ));
+    }
+
+    let trap = HoneypotTrap {
+        kind,
+        target_id,
+        action,
+        purge_seconds: HoneypotSettingsRow::parse_purge_seconds(&purge_seconds),
+        timeout_minutes: HoneypotTrap::parse_timeout_minutes(&timeout_minutes),
+        quarantine_role_id,
+    };
+
+    let current = app.settings.honeypot.get(guild_id).await.map_err(server_err)?;
+    let replaces = current
+        .traps
+        .iter()
+        .any(|t| t.kind == trap.kind && t.target_id == trap.target_id);
+    if !replaces && current.traps.len() >= HoneypotSettingsRow::MAX_TRAPS {
+        return Err(ServerFnError::ServerError(format!(
+            "a server can have at most {} extra traps",
+            HoneypotSettingsRow::MAX_TRAPS
+        )));
+    }
+
+    app.settings
+        .honeypot
+        .update_by(guild_id, actor, |p| {
+            p.traps
+                .retain(|t| !(t.kind == trap.kind && t.target_id == trap.target_id));
+            p.traps.push(trap);
+        })
+        .await
+        .map(|_| ())
+        .map_err(server_err)
+}
+
+#[server]
+pub async fn remove_honeypot_trap(
+    guild: String,
+    kind: String,
+    target_id: String,
+) -> Result<(), ServerFnError> {
+    let (guild_id, actor, app) = admin_app(&guild).await?;
+
+    let target_id = parse_id(&target_id);
+
+    app.settings
+        .honeypot
+        .update_by(guild_id, actor, |p| {
+            p.traps.retain(|t| {
+                Some(t.target_id) != target_id || t.kind.as_str() != kind.trim()
+            });
+        })
+        .await
+        .map(|_| ())
+        .map_err(server_err)
+}
+
+#[server]
+pub async fn save_automod_settings(
+    guild: String,
+    enabled: String,
+    exempt_admins: String,
+    exempt_role_id: String,
+    log_channel_id: String,
+    timeout_minutes: String,
+) -> Result<(), ServerFnError> {
+    let (guild_id, actor, app) = admin_app(&guild).await?;
+
+    let enabled = enabled.trim() == "true";
+    let exempt_admins = exempt_admins.trim() == "true";
+    let timeout_minutes =
+        AutomodSettingsRow::parse_timeout_minutes(&timeout_minutes);
+
+    app.settings
+        .automod
+        .update_by(guild_id, actor, |p| {
+            p.enabled = enabled;
+            p.exempt_admins = exempt_admins;
+            p.exempt_role_id = parse_id(&exempt_role_id);
+            p.log_channel_id = parse_id(&log_channel_id);
+            p.timeout_minutes = timeout_minutes;
+        })
+        .await
+        .map(|_| ())
+        .map_err(server_err)
+}
+
+/// Saves every rule but the custom filters, which are added and removed one
+/// at a time.
+#[server]
+pub async fn save_automod_rules(
+    guild: String,
+    rate_action: String,
+    rate_max: String,
+    rate_seconds: String,
+    duplicates_action: String,
+    duplicates_max: String,
+    duplicates_seconds: String,
+    mentions_action: String,
+    mentions_max: String,
+    invites_action: String,
+    scam_links_action: String,
+    caps_action: String,
+    caps_percent: String,
+    caps_min_length: String,
+) -> Result<(), ServerFnError> {
+    let (guild_id, actor, app) = admin_app(&guild).await?;
+
+    let rate = RateRule::from_form(
+        &rate_action,
+        &rate_max,
+        &rate_seconds,
+        AutomodRules::DEFAULT_RATE,
+    );
+    let duplicates = RateRule::from_form(
+        &duplicates_action,
+        &duplicates_max,
+        &duplicates_seconds,
+        AutomodRules::DEFAULT_DUPLICATES,
+    );
+    let mentions = MentionRule::from_form(&mentions_action, &mentions_max);
+    let invites = AutomodAction::parse(&invites_action);
+    let scam_links = AutomodAction::parse(&scam_links_action);
+    let caps = CapsRule::from_form(&caps_action, &caps_percent, &caps_min_length);
+
+    app.settings
+        .automod
+        .update_by(guild_id, actor, |p| {
+            p.rules.rate = rate;
+            p.rules.duplicates = duplicates;
+            p.rules.mentions = mentions;
+            p.rules.invites = invites;
+            p.rules.scam_links = scam_links;
+            p.rules.caps = caps;
+        })
+        .await
+        .map(|_| ())
+        .map_err(server_err)
+}
+
+/// Adds a custom filter, or changes the action of the one already using that
+/// pattern.
+#[server]
+pub async fn add_automod_filter(
+    guild: String,
+    pattern: String,
+    regex: String,
+    action: String,
+) -> Result<(), ServerFnError> {
+    let (guild_id, actor, app) = admin_app(&guild).await?;
+
+    let pattern = pattern.trim().to_string();
+    if pattern.is_empty() {
+        return Err(ServerFnError::ServerError(
+            "enter some words or a pattern".to_string(),
+        ));
+    }
+    if pattern.chars().count() > AutomodRules::MAX_PATTERN_CHARS {
+        return Err(ServerFnError::ServerError(format!(
+            "filters are limited to {} characters",
+            AutomodRules::MAX_PATTERN_CHARS
+        )));
+    }
+
+    let action = AutomodAction::parse(&action)
+        .ok_or_else(|| ServerFnError::ServerError("invalid action".to_string()))?;
+    let filter = WordFilter { pattern, regex: regex.trim() == "true", action };
+
+    if filter.regex
+        && let Err(e) = filter.compile()
+    {
+        return Err(ServerFnError::ServerError(format!("invalid pattern: {e}")));
+    }
+
+    let current = app.settings.automod.get(guild_id).await.map_err(server_err)?;
+    let replaces = current.rules.filters.iter().any(|f| f.pattern == filter.pattern);
+    if !replaces && current.rules.filters.len() >= AutomodRules::MAX_FILTERS {
+        return Err(ServerFnError::ServerError(format!(
+            "a server can have at most {} filters",
+            AutomodRules::MAX_FILTERS
+        )));
+    }
+
+    app.settings
+        .automod
+        .update_by(guild_id, actor, |p| {
+            p.rules.filters.retain(|f| f.pattern != filter.pattern);
+            p.rules.filters.push(filter);
+        })
+        .await
+        .map(|_| ())
+        .map_err(server_err)
+}
+
+#[server]
+pub async fn remove_automod_filter(
+    guild: String,
+    pattern: String,
+) -> Result<(), ServerFnError> {
+    let (guild_id, actor, app) = admin_app(&guild).await?;
+
+    app.settings
+        .automod
+        .update_by(guild_id, actor, |p| {
+            p.rules.filters.retain(|f| f.pattern != pattern.trim());
+        })
+        .await
+        .map(|_| ())
+        .map_err(server_err)
+}
+
+#[server]
+pub async fn save_raid_settings(
+    guild: String,
+    enabled: String,
+    join_threshold: String,
+    join_window_seconds: String,
+    lockdown_minutes: String,
+    min_account_age_days: String,
+    flag_default_avatar: String,
+    flag_similar_names: String,
+    joiner_action: String,
+    timeout_minutes: String,
+    raise_verification: String,
+    slowmode_seconds: String,
+    alert_channel_id: String,
+) -> Result<(), ServerFnError> {
+    let (guild_id, actor, app) = admin_app(&guild).await?;
+
+    let join_threshold = RaidSettingsRow::parse_join_threshold(&join_threshold);
+    let join_window_seconds =
+        RaidSettingsRow::parse_join_window_seconds(&join_window_seconds);
+    let lockdown_minutes =
+        RaidSettingsRow::parse_lockdown_minutes(&lockdown_minutes);
+    let min_account_age_days =
+        RaidSettingsRow::parse_min_account_age_days(&min_account_age_days);
+    let timeout_minutes = RaidSettingsRow::parse_timeout_minutes(&timeout_minutes);
+    let slowmode_seconds =
+        RaidSettingsRow::parse_slowmode_seconds(&slowmode_seconds);
+    let joiner_action = RaidAction::parse(&joiner_action);
+
+    app.settings
+        .raid
+        .update_by(guild_id, actor, |p| {
+            p.enabled = enabled.trim() == "true";
+            p.join_threshold = join_threshold;
+            p.join_window_seconds = join_window_seconds;
+            p.lockdown_minutes = lockdown_minutes;
+            p.min_account_age_days = min_account_age_days;
+            p.flag_default_avatar = flag_default_avatar.trim() == "true";
+            p.flag_similar_names = flag_similar_names.trim() == "true";
+            p.joiner_action = joiner_action.as_str().to_string();
+            p.timeout_minutes = timeout_minutes;
+            p.raise_verification = raise_verification.trim() == "true";
+            p.slowmode_seconds = slowmode_seconds;
+            p.alert_channel_id = parse_id(&alert_channel_id);
+        })
+        .await
+        .map(|_| ())
+        .map_err(server_err)
+}
+
+#[server]
+pub async fn save_appeals_settings(
+    guild: String,
+    enabled: String,
+    channel_id: String,
+) -> Result<(), ServerFnError> {
+    let (guild_id, actor, app) = admin_app(&guild).await?;
+
+    let enabled = enabled.trim() == "true";
+    let channel_id = parse_id(&channel_id);
+
+    app.settings
+        .appeals
+        .update_by(guild_id, actor, |p| {
+            p.enabled = enabled;
+            p.channel_id = channel_id;
+        })
+        .await
+        .map(|_| ())
+        .map_err(server_err)
 }
 
 #[server]
@@ -418,14 +908,14 @@ pub async fn save_ai_settings(
     enabled: String,
     channel_id: String,
 ) -> Result<(), ServerFnError> {
-    let (guild_id, app) = admin_app(&guild).await?;
+    let (guild_id, actor, app) = admin_app(&guild).await?;
 
     let enabled = enabled.trim() == "true";
     let channel_id = parse_id(&channel_id);
 
     app.settings
         .ai
-        .update(guild_id, |p| {
+        .update_by(guild_id, actor, |p| {
             p.enabled = enabled;
             p.channel_id = channel_id;
         })
@@ -441,11 +931,11 @@ pub async fn save_lfg_settings(
     lfg_role_id: String,
     lfg_scheduled_thread_id: String,
 ) -> Result<(), ServerFnError> {
-    let (guild_id, app) = admin_app(&guild).await?;
+    let (guild_id, actor, app) = admin_app(&guild).await?;
 
     app.settings
         .lfg
-        .update(guild_id, |p| {
+        .update_by(guild_id, actor, |p| {
             p.lfg_channel_id = parse_id(&lfg_channel_id);
             p.lfg_role_id = parse_id(&lfg_role_id);
             p.lfg_scheduled_thread_id = parse_id(&lfg_scheduled_thread_id);
@@ -1,3 +1,6 @@
+pub mod activity;
+pub mod admin;
+pub mod ai;
 pub mod auth;
 pub mod command_permissions;
 pub mod discord;
@@ -5,7 +8,13 @@ pub mod greetings;
 pub mod guild;
 pub mod kofi;
 pub mod levels;
+pub mod moderation;
 pub mod modules;
 pub mod palworld_save;
+pub mod premium;
 pub mod reaction_roles;
+pub mod role_panels;
+pub mod rules;
+pub mod suggestions;
+pub mod threats;
 pub mod tier;
@@ -0,0 +1,433 @@
+use leptos::prelude::*;
+#[cfg(feature = "ssr")]
+use {
+    crate::dto::{INFRACTION_KINDS, moderation::infractions_csv},
+    crate::server::auth::{
+        db_pool,
+        discord_client,
+        guild_moderator_context,
+        server_err,
+    },
+    jiff_sqlx::Timestamp as SqlxTimestamp,
+    sqlx::PgPool,
+    twilight_model::id::Id,
+};
+
+use crate::dto::{InfractionInfo, UserTimeline};
+
+#[cfg(feature = "ssr")]
+const PAGE_SIZE: i64 = 25;
+
+/// Most rows a CSV export carries.
+#[cfg(feature = "ssr")]
+const EXPORT_LIMIT: i64 = 10_000;
+
+/// `infractions.reason` is a `VARCHAR(255)`.
+#[cfg(feature = "ssr")]
+const MAX_REASON_CHARS: usize = 255;
+
+#[cfg(feature = "ssr")]
+const NO_REASON: &str = "No reason provided.";
+
+#[cfg(feature = "ssr")]
+const DATE_FORMAT: &str = "%Y-%m-%d %H:%M UTC";
+
+#[cfg(feature = "ssr")]
+struct InfractionRecord {
+    id: i32,
+    user_id: i64,
+    username: String,
+    kind: String,
+    moderator_id: i64,
+    moderator_username: String,
+    points: i32,
+    reason: String,
+    created_at: SqlxTimestamp,
+    expunged: bool,
+    expunge_reason: Option<String>,
+}
+
+#[cfg(feature = "ssr")]
+impl From<InfractionRecord> for InfractionInfo {
+    fn from(row: InfractionRecord) -> Self {
+        Self {
+            id: row.id,
+            user_id: row.user_id.cast_unsigned().to_string(),
+            username: row.username,
+            kind: row.kind,
+            moderator_id: row.moderator_id.cast_unsigned().to_string(),
+            moderator_username: row.moderator_username,
+            points: row.points,
+            reason: row.reason,
+            created_at: row.created_at.to_jiff().strftime(DATE_FORMAT).to_string(),
+            expunged: row.expunged,
+            expunge_reason: row.expunge_reason,
+        }
+    }
+}
+
+#[cfg(feature = "ssr")]
+fn invalid(what: &str) -> ServerFnError {
+    ServerFnError::ServerError(format!("invalid {what}"))
+}
+
+#[cfg(feature = "ssr")]
+fn parse_kind(kind: &str) -> Result<&'static str, ServerFnError> {
+    INFRACTION_KINDS
+        .into_iter()
+        .find(|k| *k == kind.trim())
+        .ok_or_else(|| invalid("infraction type"))
+}
+
+#[cfg(feature = "ssr")]
+fn parse_points(points: &str) -> Result<i32, ServerFnError> {
+    points
+        .trim()
+        .parse::<i32>()
+        .ok()
+        .filter(|p| *p >= 1)
+        .ok_or_else(|| invalid("points"))
+}
+
+#[cfg(feature = "ssr")]
+fn parse_reason(reason: &str) -> Result<String, ServerFnError> {
+    let reason = reason.trim();
+    if reason.chars().count() > MAX_REASON_CHARS {
+        return Err(ServerFnError::ServerError(format!(
+            "reasons are limited to {MAX_REASON_CHARS} characters"
+        )));
+    }
+    Ok(if reason.is_empty() { NO_REASON } else { reason }.to_string())
+}
+
+/// `query` as an `ILIKE` pattern matching it anywhere, with its own wildcards
+/// escaped.
+#[cfg(feature = "ssr")]
+fn contains_pattern(query: &str) -> String {
+    let escaped =
+        query.trim().replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_");
+    format!("%{escaped}%")
+}
+
+#[cfg(feature = "ssr")]
+async fn search(
+    pool: &PgPool,
+    guild_id: i64,
+    query: &str,
+    kind: &str,
+    include_expunged: bool,
+    limit: i64,
+    offset: i64,
+) -> Result<Vec<InfractionInfo>, ServerFnError> {
+    let kind = if kind.trim().is_empty() { None } else { Some(parse_kind(kind)?) };
+    let query = query.trim();
+
+    let rows = sqlx::query_as!(
+        InfractionRecord,
+        r#"SELECT
+            id,
+            user_id,
+            username,
+            infraction_type::text AS "kind!",
+            moderator_id,
+            moderator_username,
+            points,
+            reason,
+            created_at AS "created_at: SqlxTimestamp",
+            expunged_at IS NOT NULL AS "expunged!",
+            expunge_reason
+        FROM infractions
+        WHERE guild_id = $1
+            AND (
+                $2 = ''
+                OR user_id::text = $2
+                OR username ILIKE $3
+                OR moderator_username ILIKE $3
+                OR reason ILIKE $3
+            )
+            AND ($4::text IS NULL OR infraction_type::text = $4)
+            AND ($5 OR expunged_at IS NULL)
+        ORDER BY created_at DESC, id DESC
+        LIMIT $6 OFFSET $7"#,
+        guild_id,
+        query,
+        contains_pattern(query),
+        kind,
+        include_expunged,
+        limit,
+        offset,
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(server_err)?;
+
+    Ok(rows.into_iter().map(InfractionInfo::from).collect())
+}
+
+/// The guild's infractions, newest first. `query` matches a user id exactly,
+/// or a username, moderator or reason anywhere; a blank `kind` matches every
+/// type.
+#[server]
+pub async fn search_infractions(
+    guild: String,
+    query: String,
+    kind: String,
+    include_expunged: bool,
+    page: i32,
+) -> Result<Vec<InfractionInfo>, ServerFnError> {
+    let (guild_id, _user) = guild_moderator_context(&guild).await?;
+
+    let offset = (i64::from(page).max(1) - 1) * PAGE_SIZE;
+
+    search(&db_pool()?, guild_id, &query, &kind, include_expunged, PAGE_SIZE, offset)
+        .await
+}
+
+/// The same search as [`search_infractions`], every page, as CSV.
+#[server]
+pub async fn export_infractions_csv(
+    guild: String,
+    query: String,
+    kind: String,
+    include_expunged: bool,
+) -> Result<String, ServerFnError> {
+    let (guild_id, _user) = guild_moderator_context(&guild).await?;
+
+    let rows = search(
+        &db_pool()?,
+        guild_id,
+        &query,
+        &kind,
+        include_expunged,
+        EXPORT_LIMIT,
+        0,
+    )
+    .await?;
+
+    Ok(infractions_csv(&rows))
+}
+
+/// Every infraction one member has in this guild, oldest first.
+#[server]
+pub async fn get_user_infractions(
+    guild: String,
+    user: String,
+) -> Result<UserTimeline, ServerFnError> {
+    let (guild_id, _user) = guild_moderator_context(&guild).await?;
+    let user_id = user.trim().parse::<i64>().map_err(|_e| invalid("user"))?;
+    let pool = db_pool()?;
+
+    let rows = sqlx::query_as!(
+        InfractionRecord,
+        r#"SELECT
+            id,
+            user_id,
+            username,
+            infraction_type::text AS "kind!",
+            moderator_id,
+            moderator_username,
+            points,
+            reason,
+            created_at AS "created_at: SqlxTimestamp",
+            expunged_at IS NOT NULL AS "expunged!",
+            expunge_reason
+        FROM infractions
+        WHERE guild_id = $1 AND user_id = $2
+        ORDER BY created_at, id"#,
+        guild_id,
+        user_id,
+    )
+    .fetch_all(&pool)
+    .await
+    .map_err(server_err)?;
+
+    let active_points = sqlx::query_scalar!(
+        r#"SELECT COALESCE(SUM(points), 0)::bigint AS "points!"
+        FROM infractions
+        WHERE guild_id = $1 AND user_id = $2
+            AND expunged_at IS NULL
+            AND created_at > now() - INTERVAL '6 months'"#,
+        guild_id,
+        user_id,
+    )
+    .fetch_one(&pool)
+    .await
+    .map_err(server_err)?;
+
+    let username = rows.last().map_or_else(
+        || format!("User {}", user_id.cast_unsigned()),
+        |row| row.username.clone(),
+    );
+
+    Ok(UserTimeline {
+        user_id: user_id.cast_unsigned().to_string(),
+        username,
+        active_points,
+        infractions: rows.into_iter().map(InfractionInfo::from).collect(),
+    })
+}
+
+/// Records an infraction by hand. Nothing is sent to the member and no
+/// timeout or ban is applied; the points count toward `/infraction`'s
+/// escalation from then on.
+#[server]
+pub async fn add_infraction(
+    guild: String,
+    user_id: String,
+    kind: String,
+    points: String,
+    reason: String,
+) -> Result<(), ServerFnError> {
+    let (guild_id, moderator_id) = guild_moderator_context(&guild).await?;
+
+    let target = user_id
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .and_then(Id::new_checked)
+        .ok_or_else(|| invalid("user"))?;
+    let kind = parse_kind(&kind)?;
+    let points = parse_points(&points)?;
+    let reason = parse_reason(&reason)?;
+
+    let http = discord_client()?;
+    let username = http
+        .user(target)
+        .await
+        .map_err(|_e| invalid("user"))?
+        .model()
+        .await
+        .map_err(server_err)?
+        .name;
+    let moderator_username =
+        match http.user(Id::new(moderator_id.cast_unsigned())).await {
+            Ok(resp) => resp.model().await.map(|u| u.name).ok(),
+            Err(_) => None,
+        }
+        .unwrap_or_else(|| format!("User {}", moderator_id.cast_unsigned()));
+
+    let pool = db_pool()?;
+    let mut tx = pool.begin().await.map_err(server_err)?;
+
+    // Both ids reference `users`, and the guild `guilds`, which only the bot
+    // seeds as members chat.
+    sqlx::query!(
+        "INSERT INTO users (id, username) VALUES ($1, $2), ($3, $4)
+        ON CONFLICT (id) DO UPDATE SET username = EXCLUDED.username",
+        target.get().cast_signed(),
+        username,
+        moderator_id,
+        moderator_username,
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(server_err)?;
+
+    sqlx::query!(
+        "INSERT INTO guilds (id) VALUES ($1) ON CONFLICT (id) DO NOTHING",
+        guild_id
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(server_err)?;
+
+    sqlx::query!(
+        "INSERT INTO infractions
+            (user_id, username, guild_id, infraction_type,
+             moderator_id, moderator_username, points, reason)
+        VALUES ($1, $2, $3, $4::text::infraction_kind, $5, $6, $7, $8)",
+        target.get().cast_signed(),
+        username,
+        guild_id,
+        kind,
+        moderator_id,
+        moderator_username,
+        points,
+        reason,
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(server_err)?;
+
+    tx.commit().await.map_err(server_err)
+}
+
+#[server]
+pub async fn edit_infraction(
+    guild: String,
+    id: i32,
+    kind: String,
+    points: String,
+    reason: String,
+) -> Result<(), ServerFnError> {
+    let (guild_id, moderator_id) = guild_moderator_context(&guild).await?;
+
+    let kind = parse_kind(&kind)?;
+    let points = parse_points(&points)?;
+    let reason = parse_reason(&reason)?;
+
+    let updated = sqlx::query!(
+        "UPDATE infractions SET
+            infraction_type = $3::text::infraction_kind,
+            points = $4,
+            reason = $5,
+            updated_at = now(),
+            updated_by = $6
+        WHERE guild_id = $1 AND id = $2 AND expunged_at IS NULL",
+        guild_id,
+        id,
+        kind,
+        points,
+        reason,
+        moderator_id,
+    )
+    .execute(&db_pool()?)
+    .await
+    .map_err(server_err)?
+    .rows_affected();
+
+    if updated == 0 {
+        return Err(ServerFnError::ServerError(
+            "that infraction does not exist or has been expunged".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Strikes an infraction from the record. It stays visible here, marked as
+/// expunged, but `/logs` hides it and it no longer counts toward escalation.
+#[server]
+pub async fn expunge_infraction(
+    guild: String,
+    id: i32,
+    reason: String,
+) -> Result<(), ServerFnError> {
+    let (guild_id, moderator_id) = guild_moderator_context(&guild).await?;
+
+    let reason = parse_reason(&reason)?;
+
+    let expunged = sqlx::query!(
+        "UPDATE infractions SET
+            expunged_at = now(),
+            expunged_by = $3,
+            expunge_reason = $4
+        WHERE guild_id = $1 AND id = $2 AND expunged_at IS NULL",
+        guild_id,
+        id,
+        moderator_id,
+        reason,
+    )
+    .execute(&db_pool()?)
+    .await
+    .map_err(server_err)?
+    .rows_affected();
+
+    if expunged == 0 {
+        return Err(ServerFnError::ServerError(
+            "that infraction does not exist or is already expunged".to_string(),
+        ));
+    }
+
+    Ok(())
+}
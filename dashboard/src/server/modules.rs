@@ -14,6 +14,7 @@ use {
     std::collections::{HashMap, HashSet},
     twilight_model::id::Id,
     twilight_model::id::marker::CommandMarker,
+    zayden_app::config::Actor,
 };
 
 use crate::dto::ModuleView;
@@ -97,6 +98,18 @@ const MODULES: &[ModuleDef] = &[
         description: "Decoy channel that soft-bans spam bots on sight.",
         backing: Backing::Commands(&["honeypot"]),
     },
+    ModuleDef {
+        id: "automod",
+        label: "Automod",
+        description: "Deletes spam, mass mentions, invites and scam links.",
+        backing: Backing::Settings,
+    },
+    ModuleDef {
+        id: "raid",
+        label: "Raid Protection",
+        description: "Locks the server down when a wave of accounts joins at once.",
+        backing: Backing::Settings,
+    },
     ModuleDef {
         id: "greetings",
         label: "Greetings",
@@ -179,9 +192,16 @@ async fn denied_commands(ctx: &GuildContext) -> HashSet<Id<CommandMarker>> {
 async fn settings_flags(
     guild_id: i64,
 ) -> Result<HashMap<&'static str, bool>, ServerFnError> {
-    let ai = app_state()?.settings.ai.get(guild_id).await.map_err(server_err)?;
+    let app = app_state()?;
+    let ai = app.settings.ai.get(guild_id).await.map_err(server_err)?;
+    let automod = app.settings.automod.get(guild_id).await.map_err(server_err)?;
+    let raid = app.settings.raid.get(guild_id).await.map_err(server_err)?;
 
-    Ok(HashMap::from([("ai", ai.enabled)]))
+    Ok(HashMap::from([
+        ("ai", ai.enabled),
+        ("automod", automod.enabled),
+        ("raid", raid.enabled),
+    ]))
 }
 
 #[server]
@@ -201,13 +221,28 @@ pub async fn list_guild_modules(
 async fn set_settings_enabled(
     module_id: &str,
     guild_id: i64,
+    actor: Actor,
     enabled: bool,
 ) -> Result<(), ServerFnError> {
     match module_id {
         "ai" => app_state()?
             .settings
             .ai
-            .update(guild_id, |row| row.enabled = enabled)
+            .update_by(guild_id, actor, |row| row.enabled = enabled)
+            .await
+            .map(|_row| ())
+            .map_err(server_err),
+        "automod" => app_state()?
+            .settings
+            .automod
+            .update_by(guild_id, actor, |row| row.enabled = enabled)
+            .await
+            .map(|_row| ())
+            .map_err(server_err),
+        "raid" => app_state()?
+            .settings
+            .raid
+            .update_by(guild_id, actor, |row| row.enabled = enabled)
             .await
             .map(|_row| ())
             .map_err(server_err),
@@ -234,6 +269,7 @@ pub async fn set_module_enabled(
             return set_settings_enabled(
                 module.id,
                 ctx.guild_id.get().cast_signed(),
+                Actor::dashboard(ctx.user_id),
                 enabled,
             )
             .await;
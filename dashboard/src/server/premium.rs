@@ -0,0 +1,221 @@
+use leptos::prelude::*;
+#[cfg(feature = "ssr")]
+use {
+    crate::dto::{SlotGuildInfo, Tier},
+    crate::server::auth::{
+        WebRole,
+        app_state,
+        current_user_id,
+        require_role,
+        server_err,
+    },
+    crate::server::guild::list_manageable_guilds,
+    rand::RngExt,
+    zayden_app::entitlement::{
+        CODE_LEN,
+        GiftCode,
+        GiftError,
+        format_code,
+        grant_expiry,
+    },
+};
+
+use crate::dto::{GiftCodeInfo, PremiumSlotsView};
+
+#[cfg(feature = "ssr")]
+const DATE_FORMAT: &str = "%Y-%m-%d %H:%M UTC";
+
+#[cfg(feature = "ssr")]
+fn parse_guild(s: &str) -> Result<u64, ServerFnError> {
+    s.trim()
+        .parse::<u64>()
+        .map_err(|_e| ServerFnError::ServerError("invalid server".to_string()))
+}
+
+#[server]
+pub async fn get_premium_slots() -> Result<PremiumSlotsView, ServerFnError> {
+    let user_id = current_user_id().await?.cast_unsigned();
+    let app = app_state()?;
+
+    let tier = app.entitlements.user_tier(user_id).await;
+    let slots = app.entitlements.slots(user_id).await.map_err(server_err)?;
+    let now = jiff::Timestamp::now();
+
+    let mut guilds: Vec<SlotGuildInfo> = list_manageable_guilds()
+        .await?
+        .into_iter()
+        .map(|g| SlotGuildInfo {
+            id: g.id,
+            name: g.name,
+            icon: g.icon,
+            assigned: false,
+            locked_until: None,
+        })
+        .collect();
+
+    for slot in &slots {
+        let id = slot.guild_id.to_string();
+        let index = match guilds.iter().position(|g| g.id == id) {
+            Some(index) => index,
+            // Still counts against the limit after the user stops managing it.
+            None => {
+                guilds.push(SlotGuildInfo {
+                    name: format!("Server {id}"),
+                    id,
+                    icon: None,
+                    assigned: false,
+                    locked_until: None,
+                });
+                guilds.len() - 1
+            },
+        };
+
+        let unlocks_at = slot.unlocks_at();
+        guilds[index].assigned = true;
+        guilds[index].locked_until =
+            (unlocks_at > now).then(|| unlocks_at.strftime(DATE_FORMAT).to_string());
+    }
+
+    guilds.sort_by(|a, b| b.assigned.cmp(&a.assigned));
+
+    Ok(PremiumSlotsView {
+        tier: Tier::from_key(tier.as_str()).unwrap_or(Tier::Free),
+        limit: tier.premium_slots(),
+        used: slots.len(),
+        guilds,
+    })
+}
+
+#[server]
+pub async fn assign_premium_slot(guild: String) -> Result<(), ServerFnError> {
+    let guild_id = parse_guild(&guild)?;
+    let user_id = current_user_id().await?.cast_unsigned();
+
+    let manages =
+        list_manageable_guilds().await?.iter().any(|g| g.id == guild.trim());
+    if !manages {
+        return Err(ServerFnError::ServerError(
+            "you can only assign slots to servers you manage".to_string(),
+        ));
+    }
+
+    app_state()?
+        .entitlements
+        .assign_slot(user_id, guild_id)
+        .await
+        .map_err(server_err)
+}
+
+#[server]
+pub async fn unassign_premium_slot(guild: String) -> Result<(), ServerFnError> {
+    let guild_id = parse_guild(&guild)?;
+    let user_id = current_user_id().await?.cast_unsigned();
+
+    app_state()?
+        .entitlements
+        .unassign_slot(user_id, guild_id)
+        .await
+        .map_err(server_err)
+}
+
+#[server]
+pub async fn redeem_gift_code(code: String) -> Result<String, ServerFnError> {
+    let user_id = current_user_id().await?.cast_unsigned();
+
+    let grant = app_state()?
+        .entitlements
+        .redeem_gift_code(user_id, &code)
+        .await
+        .map_err(server_err)?;
+
+    let tier = Tier::from_key(grant.tier.as_str()).unwrap_or(Tier::Free);
+    Ok(format!(
+        "Code redeemed: {} until {}.",
+        tier.label(),
+        grant.expires_at.strftime(DATE_FORMAT)
+    ))
+}
+
+#[server]
+pub async fn list_gift_codes() -> Result<Vec<GiftCodeInfo>, ServerFnError> {
+    require_role(WebRole::Admin).await?;
+
+    let codes = app_state()?.entitlements.gift_codes().await.map_err(server_err)?;
+
+    Ok(codes
+        .into_iter()
+        .map(|c| GiftCodeInfo {
+            code: c.code,
+            tier: Tier::from_key(c.tier.as_str()).unwrap_or(Tier::Free),
+            duration_days: c.duration_days,
+            uses: c.uses,
+            max_uses: c.max_uses,
+            expires_at: c.expires_at.map(|at| at.strftime(DATE_FORMAT).to_string()),
+        })
+        .collect())
+}
+
+#[server]
+pub async fn mint_gift_code(
+    tier: String,
+    duration_days: String,
+    max_uses: String,
+    valid_days: String,
+) -> Result<(), ServerFnError> {
+    fn number(s: &str, field: &str) -> Result<i32, ServerFnError> {
+        s.trim()
+            .parse()
+            .map_err(|_e| ServerFnError::ServerError(format!("invalid {field}")))
+    }
+
+    let created_by = require_role(WebRole::Admin).await?.cast_unsigned();
+
+    let tier = Tier::from_key(tier.trim())
+        .ok_or_else(|| ServerFnError::ServerError("invalid tier".to_string()))?;
+    let duration_days = number(&duration_days, "duration")?;
+    let max_uses = number(&max_uses, "max uses")?;
+    // Blank means the code never expires.
+    let expires_at = match valid_days.trim() {
+        "" => None,
+        days => Some(grant_expiry(
+            jiff::Timestamp::now(),
+            i64::from(number(days, "validity")?),
+        )),
+    };
+
+    let mut gift = GiftCode {
+        code: String::new(),
+        tier: tier.as_entitlement(),
+        duration_days,
+        max_uses,
+        uses: 0,
+        expires_at,
+        created_by,
+    };
+
+    // A clash on the primary key just means another draw is needed.
+    for _ in 0..3 {
+        let mut bytes = [0u8; CODE_LEN];
+        rand::rng().fill(&mut bytes[..]);
+        gift.code = format_code(bytes);
+        match app_state()?.entitlements.mint_gift_code(&gift).await {
+            Err(GiftError::Sqlx(sqlx::Error::Database(e)))
+                if e.constraint() == Some("gift_codes_pkey") => {},
+            result => return result.map_err(server_err),
+        }
+    }
+
+    Err(ServerFnError::ServerError("could not generate a unique code".to_string()))
+}
+
+#[server]
+pub async fn delete_gift_code(code: String) -> Result<(), ServerFnError> {
+    require_role(WebRole::Admin).await?;
+
+    app_state()?
+        .entitlements
+        .delete_gift_code(code.trim())
+        .await
+        .map_err(server_err)?;
+    Ok(())
+}
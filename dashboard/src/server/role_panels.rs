@@ -0,0 +1,303 @@
+//! Role panel CRUD. The dashboard only writes the database: every change
+//! marks the panel dirty and the bot's role sweep re-renders its message
+//! within a minute, so panels are only ever drawn by one renderer.
+
+use leptos::prelude::*;
+#[cfg(feature = "ssr")]
+use {
+    crate::server::auth::{
+        db_pool,
+        discord_client,
+        guild_admin_context,
+        server_err,
+    },
+    reaction_roles::panel::{NewEntry, PanelFields, RolePanelRow},
+    reaction_roles::{
+        GenericChannelId,
+        GuildId,
+        PanelKind,
+        PanelMode,
+        ParsedEmoji,
+        RoleId,
+        RolePanelManager,
+    },
+    sqlx::PgPool,
+    twilight_model::id::Id,
+};
+
+use crate::dto::{PanelEntryInfo, PanelRequirementInfo, RolePanelInfo};
+
+#[cfg(feature = "ssr")]
+fn invalid(what: &str) -> ServerFnError {
+    ServerFnError::ServerError(format!("invalid {what}"))
+}
+
+#[cfg(feature = "ssr")]
+fn parse_snowflake(label: &str, s: &str) -> Result<u64, ServerFnError> {
+    s.trim().parse::<u64>().ok().filter(|id| *id != 0).ok_or_else(|| invalid(label))
+}
+
+#[cfg(feature = "ssr")]
+fn non_empty(s: &str) -> Option<&str> {
+    let s = s.trim();
+    (!s.is_empty()).then_some(s)
+}
+
+#[cfg(feature = "ssr")]
+fn parse_minutes(s: &str) -> Result<Option<i32>, ServerFnError> {
+    non_empty(s)
+        .map(|s| {
+            s.parse::<i32>()
+                .ok()
+                .filter(|m| *m >= 0)
+                .ok_or_else(|| invalid("duration"))
+        })
+        .transpose()
+}
+
+#[cfg(feature = "ssr")]
+fn parse_fields<'a>(
+    description: &'a str,
+    kind: &str,
+    mode: &str,
+) -> Result<PanelFields<'a>, ServerFnError> {
+    Ok(PanelFields {
+        title: None,
+        description: non_empty(description),
+        kind: Some(kind.parse::<PanelKind>().map_err(|()| invalid("panel kind"))?),
+        mode: Some(mode.parse::<PanelMode>().map_err(|()| invalid("panel mode"))?),
+        role_duration_minutes: None,
+    })
+}
+
+#[cfg(feature = "ssr")]
+async fn owned_panel(
+    pool: &PgPool,
+    guild_id: i64,
+    panel_id: i32,
+) -> Result<RolePanelRow, ServerFnError> {
+    RolePanelManager::get(pool, GuildId::new(guild_id.cast_unsigned()), panel_id)
+        .await
+        .map_err(server_err)?
+        .ok_or_else(|| ServerFnError::ServerError("panel not found".to_string()))
+}
+
+#[server]
+pub async fn list_role_panels(
+    guild: String,
+) -> Result<Vec<RolePanelInfo>, ServerFnError> {
+    let (guild_id, _user, _token) = guild_admin_context(&guild).await?;
+    let pool = db_pool()?;
+
+    let panels =
+        RolePanelManager::list(&pool, GuildId::new(guild_id.cast_unsigned()))
+            .await
+            .map_err(server_err)?;
+
+    let mut out = Vec::with_capacity(panels.len());
+    for panel in panels {
+        let entries =
+            RolePanelManager::entries(&pool, panel.id).await.map_err(server_err)?;
+        let requirements = RolePanelManager::requirements(&pool, panel.id)
+            .await
+            .map_err(server_err)?;
+
+        out.push(RolePanelInfo {
+            id: panel.id,
+            channel_id: panel.channel_id().to_string(),
+            message_id: panel.message_id().map(|id| id.to_string()),
+            kind: panel.kind.id().to_string(),
+            mode: panel.mode.id().to_string(),
+            title: panel.title,
+            description: panel.description,
+            role_duration_minutes: panel.role_duration_minutes,
+            entries: entries
+                .into_iter()
+                .map(|e| PanelEntryInfo {
+                    role_id: e.role().to_string(),
+                    label: e.label,
+                    emoji: e.emoji,
+                })
+                .collect(),
+            requirements: requirements
+                .into_iter()
+                .map(|r| PanelRequirementInfo {
+                    role_id: r.role().to_string(),
+                    forbidden: r.forbidden,
+                })
+                .collect(),
+        });
+    }
+
+    Ok(out)
+}
+
+#[server]
+pub async fn create_role_panel(
+    guild: String,
+    channel_id: String,
+    title: String,
+    description: String,
+    kind: String,
+    mode: String,
+    duration_minutes: String,
+) -> Result<(), ServerFnError> {
+    let (guild_id, _user, _token) = guild_admin_context(&guild).await?;
+    let pool = db_pool()?;
+
+    let channel = parse_snowflake("channel", &channel_id)?;
+    let title = non_empty(&title).ok_or_else(|| invalid("title"))?;
+
+    let mut fields = parse_fields(&description, &kind, &mode)?;
+    fields.role_duration_minutes = parse_minutes(&duration_minutes)?;
+
+    RolePanelManager::create(
+        &pool,
+        GuildId::new(guild_id.cast_unsigned()),
+        GenericChannelId::new(channel),
+        title,
+        fields,
+    )
+    .await
+    .map_err(server_err)?;
+
+    Ok(())
+}
+
+#[server]
+pub async fn update_role_panel(
+    guild: String,
+    panel_id: i32,
+    title: String,
+    description: String,
+    kind: String,
+    mode: String,
+    duration_minutes: String,
+) -> Result<(), ServerFnError> {
+    let (guild_id, _user, _token) = guild_admin_context(&guild).await?;
+    let pool = db_pool()?;
+
+    let panel = owned_panel(&pool, guild_id, panel_id).await?;
+
+    let mut fields = parse_fields(&description, &kind, &mode)?;
+    fields.title = non_empty(&title);
+    // A blank duration on the edit form means "keep roles forever".
+    fields.role_duration_minutes =
+        Some(parse_minutes(&duration_minutes)?.unwrap_or(0));
+
+    RolePanelManager::edit(&pool, panel.id, fields).await.map_err(server_err)?;
+
+    Ok(())
+}
+
+#[server]
+pub async fn delete_role_panel(
+    guild: String,
+    panel_id: i32,
+) -> Result<(), ServerFnError> {
+    let (guild_id, _user, _token) = guild_admin_context(&guild).await?;
+    let pool = db_pool()?;
+    let http = discord_client()?;
+
+    let Some(panel) = RolePanelManager::delete(
+        &pool,
+        GuildId::new(guild_id.cast_unsigned()),
+        panel_id,
+    )
+    .await
+    .map_err(server_err)?
+    else {
+        return Err(ServerFnError::ServerError("panel not found".to_string()));
+    };
+
+    if let (Some(channel), Some(message)) = (
+        Id::new_checked(panel.channel_id().get()),
+        panel.message_id().and_then(|id| Id::new_checked(id.get())),
+    ) && let Err(e) = http.delete_message(channel, message).await
+    {
+        tracing::warn!(error = ?e, "failed to delete role panel message");
+    }
+
+    Ok(())
+}
+
+#[server]
+pub async fn add_panel_role(
+    guild: String,
+    panel_id: i32,
+    role_id: String,
+    label: String,
+    emoji: String,
+    description: String,
+) -> Result<(), ServerFnError> {
+    let (guild_id, _user, _token) = guild_admin_context(&guild).await?;
+    let pool = db_pool()?;
+
+    let panel = owned_panel(&pool, guild_id, panel_id).await?;
+    let role = parse_snowflake("role", &role_id)?;
+    let label = non_empty(&label).ok_or_else(|| invalid("label"))?;
+
+    let emoji =
+        non_empty(&emoji).map(ParsedEmoji::parse).transpose().map_err(server_err)?;
+
+    let added = RolePanelManager::add_entry(&pool, panel.id, NewEntry {
+        role: RoleId::new(role),
+        label,
+        emoji: emoji.as_ref().map(|e| e.stored.as_str()),
+        description: non_empty(&description),
+    })
+    .await
+    .map_err(server_err)?;
+
+    if !added {
+        return Err(ServerFnError::ServerError(
+            "a panel can offer at most 25 roles".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+#[server]
+pub async fn remove_panel_role(
+    guild: String,
+    panel_id: i32,
+    role_id: String,
+) -> Result<(), ServerFnError> {
+    let (guild_id, _user, _token) = guild_admin_context(&guild).await?;
+    let pool = db_pool()?;
+
+    let panel = owned_panel(&pool, guild_id, panel_id).await?;
+    let role = parse_snowflake("role", &role_id)?;
+
+    RolePanelManager::remove_entry(&pool, panel.id, RoleId::new(role))
+        .await
+        .map_err(server_err)?;
+
+    Ok(())
+}
+
+#[server]
+pub async fn toggle_panel_requirement(
+    guild: String,
+    panel_id: i32,
+    role_id: String,
+    forbidden: String,
+) -> Result<(), ServerFnError> {
+    let (guild_id, _user, _token) = guild_admin_context(&guild).await?;
+    let pool = db_pool()?;
+
+    let panel = owned_panel(&pool, guild_id, panel_id).await?;
+    let role = parse_snowflake("role", &role_id)?;
+
+    RolePanelManager::toggle_requirement(
+        &pool,
+        panel.id,
+        RoleId::new(role),
+        forbidden == "true",
+    )
+    .await
+    .map_err(server_err)?;
+
+    Ok(())
+}
@@ -0,0 +1,465 @@
+//! Rules editing. The dashboard writes `guild_rules` and `guild_rule` the same
+//! way `/rules` does; publishing only flags the guild, and the bot's rules
+//! sweep posts or refreshes the message with `/rules post`'s own logic. The
+//! verify quiz lives here too, since it is asked about these rules.
+
+use leptos::prelude::*;
+#[cfg(feature = "ssr")]
+use {
+    crate::dto::rules::{
+        DEFAULT_COLOUR,
+        MAX_DESCRIPTION_CHARS,
+        MAX_QUESTION_CHARS,
+        MAX_QUIZ_QUESTIONS,
+        MAX_RULE_BODY_CHARS,
+        MAX_RULE_TITLE_CHARS,
+        MAX_RULES,
+        MAX_TITLE_CHARS,
+        colour_hex,
+        parse_colour,
+    },
+    crate::dto::{QuizQuestionInfo, RuleInfo, RulesConfigInfo},
+    crate::server::auth::{db_pool, guild_admin_context, server_err},
+    sqlx::PgPool,
+};
+
+use crate::dto::RulesView;
+
+#[cfg(feature = "ssr")]
+const DEFAULT_TITLE: &str = "Server Rules";
+
+#[cfg(feature = "ssr")]
+fn invalid(what: &str) -> ServerFnError {
+    ServerFnError::ServerError(format!("invalid {what}"))
+}
+
+/// `value` trimmed, or an error naming `what` when it is blank or longer
+/// than `max` characters.
+#[cfg(feature = "ssr")]
+fn bounded<'a>(
+    value: &'a str,
+    what: &str,
+    max: usize,
+) -> Result<&'a str, ServerFnError> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Err(ServerFnError::ServerError(format!("a {what} is required")));
+    }
+    if value.chars().count() > max {
+        return Err(ServerFnError::ServerError(format!(
+            "a {what} is limited to {max} characters"
+        )));
+    }
+    Ok(value)
+}
+
+#[cfg(feature = "ssr")]
+async fn rule_count(pool: &PgPool, guild_id: i64) -> Result<usize, ServerFnError> {
+    let count = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) AS "count!" FROM guild_rule WHERE guild_id = $1"#,
+        guild_id,
+    )
+    .fetch_one(pool)
+    .await
+    .map_err(server_err)?;
+
+    Ok(usize::try_from(count).unwrap_or(usize::MAX))
+}
+
+#[server]
+pub async fn get_rules(guild: String) -> Result<RulesView, ServerFnError> {
+    let (guild_id, _user, _token) = guild_admin_context(&guild).await?;
+    let pool = db_pool()?;
+
+    let config = sqlx::query!(
+        "SELECT channel_id, message_id, title, description, colour, publish_requested
+        FROM guild_rules WHERE guild_id = $1",
+        guild_id,
+    )
+    .fetch_optional(&pool)
+    .await
+    .map_err(server_err)?;
+
+    let rules = sqlx::query_as!(
+        RuleInfo,
+        "SELECT id, title, body FROM guild_rule
+        WHERE guild_id = $1 ORDER BY position, id",
+        guild_id,
+    )
+    .fetch_all(&pool)
+    .await
+    .map_err(server_err)?;
+
+    let quiz = sqlx::query!(
+        "SELECT id, question, answers FROM verify_quiz_questions
+        WHERE guild_id = $1 ORDER BY position, id",
+        guild_id,
+    )
+    .fetch_all(&pool)
+    .await
+    .map_err(server_err)?
+    .into_iter()
+    .map(|row| QuizQuestionInfo {
+        id: row.id,
+        question: row.question,
+        answers: row.answers.join(", "),
+    })
+    .collect();
+
+    let config = config.map_or_else(
+        || RulesConfigInfo {
+            channel_id: String::new(),
+            title: DEFAULT_TITLE.to_string(),
+            description: String::new(),
+            colour: colour_hex(DEFAULT_COLOUR),
+            posted: false,
+            publish_pending: false,
+        },
+        |row| RulesConfigInfo {
+            channel_id: row
+                .channel_id
+                .map(|id| id.cast_unsigned().to_string())
+                .unwrap_or_default(),
+            title: row.title,
+            description: row.description.unwrap_or_default(),
+            colour: colour_hex(row.colour.cast_unsigned()),
+            posted: row.message_id.is_some(),
+            publish_pending: row.publish_requested,
+        },
+    );
+
+    Ok(RulesView { config, rules, quiz })
+}
+
+#[server]
+pub async fn save_rules_config(
+    guild: String,
+    channel_id: String,
+    title: String,
+    description: String,
+    colour: String,
+) -> Result<(), ServerFnError> {
+    let (guild_id, _user, _token) = guild_admin_context(&guild).await?;
+
+    let channel_id = match channel_id.trim() {
+        "" => None,
+        id => Some(id.parse::<i64>().map_err(|_e| invalid("channel"))?),
+    };
+    let title = bounded(&title, "title", MAX_TITLE_CHARS)?;
+    let description = description.trim();
+    if description.chars().count() > MAX_DESCRIPTION_CHARS {
+        return Err(ServerFnError::ServerError(format!(
+            "the description is limited to {MAX_DESCRIPTION_CHARS} characters"
+        )));
+    }
+    let colour = parse_colour(&colour).ok_or_else(|| invalid("colour"))?;
+
+    sqlx::query!(
+        "INSERT INTO guild_rules (guild_id, channel_id, title, description, colour)
+        VALUES ($1, $2, $3, $4, $5)
+        ON CONFLICT (guild_id) DO UPDATE SET
+            channel_id = EXCLUDED.channel_id,
+            title = EXCLUDED.title,
+            description = EXCLUDED.description,
+            colour = EXCLUDED.colour",
+        guild_id,
+        channel_id,
+        title,
+        (!description.is_empty()).then_some(description),
+        colour.cast_signed(),
+    )
+    .execute(&db_pool()?)
+    .await
+    .map_err(server_err)?;
+
+    Ok(())
+}
+
+#[server]
+pub async fn add_rule(
+    guild: String,
+    title: String,
+    body: String,
+) -> Result<(), ServerFnError> {
+    let (guild_id, _user, _token) = guild_admin_context(&guild).await?;
+    let pool = db_pool()?;
+
+    let title = bounded(&title, "rule title", MAX_RULE_TITLE_CHARS)?;
+    let body = bounded(&body, "rule body", MAX_RULE_BODY_CHARS)?;
+
+    if rule_count(&pool, guild_id).await? >= MAX_RULES {
+        return Err(ServerFnError::ServerError(format!(
+            "a rules embed holds at most {MAX_RULES} rules"
+        )));
+    }
+
+    let mut tx = pool.begin().await.map_err(server_err)?;
+
+    sqlx::query!(
+        "INSERT INTO guild_rules (guild_id) VALUES ($1) ON CONFLICT DO NOTHING",
+        guild_id,
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(server_err)?;
+
+    sqlx::query!(
+        "INSERT INTO guild_rule (guild_id, position, title, body)
+        VALUES (
+            $1,
+            (SELECT COALESCE(MAX(position), 0) + 1
+             FROM guild_rule WHERE guild_id = $1),
+            $2,
+            $3
+        )",
+        guild_id,
+        title,
+        body,
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(server_err)?;
+
+    tx.commit().await.map_err(server_err)
+}
+
+#[server]
+pub async fn edit_rule(
+    guild: String,
+    id: i32,
+    title: String,
+    body: String,
+) -> Result<(), ServerFnError> {
+    let (guild_id, _user, _token) = guild_admin_context(&guild).await?;
+
+    let title = bounded(&title, "rule title", MAX_RULE_TITLE_CHARS)?;
+    let body = bounded(&body, "rule body", MAX_RULE_BODY_CHARS)?;
+
+    let updated = sqlx::query!(
+        "UPDATE guild_rule SET title = $3, body = $4
+        WHERE guild_id = $1 AND id = $2",
+        guild_id,
+        id,
+        title,
+        body,
+    )
+    .execute(&db_pool()?)
+    .await
+    .map_err(server_err)?
+    .rows_affected();
+
+    if updated == 0 {
+        return Err(ServerFnError::ServerError("rule not found".to_string()));
+    }
+
+    Ok(())
+}
+
+#[server]
+pub async fn delete_rule(guild: String, id: i32) -> Result<(), ServerFnError> {
+    let (guild_id, _user, _token) = guild_admin_context(&guild).await?;
+    let pool = db_pool()?;
+
+    let mut tx = pool.begin().await.map_err(server_err)?;
+
+    let Some(position) = sqlx::query_scalar!(
+        "DELETE FROM guild_rule WHERE guild_id = $1 AND id = $2 RETURNING position",
+        guild_id,
+        id,
+    )
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(server_err)?
+    else {
+        return Err(ServerFnError::ServerError("rule not found".to_string()));
+    };
+
+    sqlx::query!(
+        "UPDATE guild_rule SET position = position - 1
+        WHERE guild_id = $1 AND position > $2",
+        guild_id,
+        position,
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(server_err)?;
+
+    tx.commit().await.map_err(server_err)
+}
+
+/// Renumbers the guild's rules in the order of `order`, a comma-separated
+/// list of every rule id.
+#[server]
+pub async fn reorder_rules(
+    guild: String,
+    order: String,
+) -> Result<(), ServerFnError> {
+    let (guild_id, _user, _token) = guild_admin_context(&guild).await?;
+    let pool = db_pool()?;
+
+    let ids = order
+        .split(',')
+        .map(|id| id.trim().parse::<i32>())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_e| invalid("order"))?;
+
+    let mut existing = sqlx::query_scalar!(
+        "SELECT id FROM guild_rule WHERE guild_id = $1",
+        guild_id,
+    )
+    .fetch_all(&pool)
+    .await
+    .map_err(server_err)?;
+
+    let mut sorted = ids.clone();
+    sorted.sort_unstable();
+    existing.sort_unstable();
+    if sorted != existing {
+        return Err(ServerFnError::ServerError(
+            "the rules changed in the meantime; reload and try again".to_string(),
+        ));
+    }
+
+    sqlx::query!(
+        "UPDATE guild_rule SET position = ordered.position::integer
+        FROM unnest($2::integer[]) WITH ORDINALITY AS ordered(id, position)
+        WHERE guild_rule.guild_id = $1 AND guild_rule.id = ordered.id",
+        guild_id,
+        &ids,
+    )
+    .execute(&pool)
+    .await
+    .map_err(server_err)?;
+
+    Ok(())
+}
+
+/// Asks the bot to post the rules, or refresh the message it already posted.
+/// The bot's rules sweep picks the request up within a minute.
+#[server]
+pub async fn publish_rules(guild: String) -> Result<(), ServerFnError> {
+    let (guild_id, _user, _token) = guild_admin_context(&guild).await?;
+    let pool = db_pool()?;
+
+    let count = rule_count(&pool, guild_id).await?;
+    if count == 0 {
+        return Err(ServerFnError::ServerError(
+            "add at least one rule before publishing".to_string(),
+        ));
+    }
+    if count > MAX_RULES {
+        return Err(ServerFnError::ServerError(format!(
+            "a rules embed holds at most {MAX_RULES} rules; you have {count}"
+        )));
+    }
+
+    let requested = sqlx::query!(
+        "UPDATE guild_rules SET publish_requested = true
+        WHERE guild_id = $1 AND channel_id IS NOT NULL",
+        guild_id,
+    )
+    .execute(&pool)
+    .await
+    .map_err(server_err)?
+    .rows_affected();
+
+    if requested == 0 {
+        return Err(ServerFnError::ServerError(
+            "choose a rules channel and save before publishing".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Adds a question to the verify quiz. `answers` is a comma-separated list of
+/// every answer accepted; members' answers are compared ignoring case and
+/// punctuation.
+#[server]
+pub async fn add_quiz_question(
+    guild: String,
+    question: String,
+    answers: String,
+) -> Result<(), ServerFnError> {
+    let (guild_id, _user, _token) = guild_admin_context(&guild).await?;
+    let pool = db_pool()?;
+
+    let question = bounded(&question, "question", MAX_QUESTION_CHARS)?;
+    let answers = answers
+        .split(',')
+        .map(str::trim)
+        .filter(|answer| !answer.is_empty())
+        .collect::<Vec<_>>();
+    if answers.is_empty() {
+        return Err(ServerFnError::ServerError(
+            "give at least one accepted answer".to_string(),
+        ));
+    }
+
+    let count = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) AS "count!" FROM verify_quiz_questions WHERE guild_id = $1"#,
+        guild_id,
+    )
+    .fetch_one(&pool)
+    .await
+    .map_err(server_err)?;
+
+    if usize::try_from(count).unwrap_or(usize::MAX) >= MAX_QUIZ_QUESTIONS {
+        return Err(ServerFnError::ServerError(format!(
+            "the quiz holds at most {MAX_QUIZ_QUESTIONS} questions"
+        )));
+    }
+
+    let mut tx = pool.begin().await.map_err(server_err)?;
+
+    sqlx::query!(
+        "INSERT INTO guild_rules (guild_id) VALUES ($1) ON CONFLICT DO NOTHING",
+        guild_id,
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(server_err)?;
+
+    sqlx::query!(
+        "INSERT INTO verify_quiz_questions (guild_id, position, question, answers)
+        VALUES (
+            $1,
+            (SELECT COALESCE(MAX(position), 0) + 1
+             FROM verify_quiz_questions WHERE guild_id = $1),
+            $2,
+            $3
+        )",
+        guild_id,
+        question,
+        &answers as &[&str],
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(server_err)?;
+
+    tx.commit().await.map_err(server_err)
+}
+
+#[server]
+pub async fn delete_quiz_question(
+    guild: String,
+    id: i32,
+) -> Result<(), ServerFnError> {
+    let (guild_id, _user, _token) = guild_admin_context(&guild).await?;
+
+    let deleted = sqlx::query!(
+        "DELETE FROM verify_quiz_questions WHERE guild_id = $1 AND id = $2",
+        guild_id,
+        id,
+    )
+    .execute(&db_pool()?)
+    .await
+    .map_err(server_err)?
+    .rows_affected();
+
+    if deleted == 0 {
+        return Err(ServerFnError::ServerError("question not found".to_string()));
+    }
+
+    Ok(())
+}
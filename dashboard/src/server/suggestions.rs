@@ -0,0 +1,114 @@
+use leptos::prelude::*;
+#[cfg(feature = "ssr")]
+use {
+    crate::server::auth::{
+        db_pool,
+        discord_client,
+        guild_admin_context,
+        server_err,
+    },
+    suggestions::SuggestionStatus,
+    twilight_model::id::Id,
+};
+
+use crate::dto::SuggestionEntry;
+
+#[cfg(feature = "ssr")]
+const PAGE_SIZE: i64 = 10;
+
+#[cfg(feature = "ssr")]
+struct SuggestionRow {
+    thread_id: i64,
+    author_id: i64,
+    anonymous: bool,
+    title: String,
+    status: String,
+    upvotes: i32,
+    downvotes: i32,
+    response: Option<String>,
+}
+
+/// The guild's suggestions, highest voted first. `status` narrows the board to
+/// one status id; `None` lists every suggestion.
+#[server]
+pub async fn get_suggestions(
+    guild: String,
+    status: Option<String>,
+    page: i32,
+) -> Result<Vec<SuggestionEntry>, ServerFnError> {
+    let (guild_id, _user, _token) = guild_admin_context(&guild).await?;
+    let pool = db_pool()?;
+
+    let status = status
+        .map(|s| {
+            s.parse::<SuggestionStatus>().map_err(|()| {
+                ServerFnError::ServerError(format!("unknown status: {s}"))
+            })
+        })
+        .transpose()?;
+
+    let page = i64::from(page).max(1);
+    let offset = (page - 1) * PAGE_SIZE;
+
+    let rows = sqlx::query_as!(
+        SuggestionRow,
+        r#"SELECT
+            thread_id,
+            author_id,
+            anonymous,
+            title,
+            status::text AS "status!",
+            upvotes,
+            downvotes,
+            response
+        FROM suggestions
+        WHERE guild_id = $1 AND ($2::text IS NULL OR status::text = $2)
+        ORDER BY upvotes - downvotes DESC, created_at DESC
+        LIMIT $3 OFFSET $4"#,
+        guild_id,
+        status.map(SuggestionStatus::id),
+        PAGE_SIZE,
+        offset
+    )
+    .fetch_all(&pool)
+    .await
+    .map_err(server_err)?;
+
+    let http = discord_client()?;
+    let mut entries = Vec::with_capacity(rows.len());
+    for row in rows {
+        // Anonymous posts stay anonymous to admins too.
+        let author = if row.anonymous {
+            "Anonymous".to_string()
+        } else {
+            let user_id = row.author_id.cast_unsigned();
+
+            let user = match http.user(Id::new(user_id)).await {
+                Ok(resp) => resp.model().await.ok(),
+                Err(_) => None,
+            };
+            user.map_or_else(
+                || format!("User {user_id}"),
+                |user| user.global_name.unwrap_or(user.name),
+            )
+        };
+
+        let status_label = row
+            .status
+            .parse::<SuggestionStatus>()
+            .map_or_else(|()| row.status.clone(), |s| s.label().to_string());
+
+        entries.push(SuggestionEntry {
+            thread_id: row.thread_id.cast_unsigned().to_string(),
+            title: row.title,
+            author,
+            status: row.status,
+            status_label,
+            upvotes: row.upvotes,
+            downvotes: row.downvotes,
+            response: row.response,
+        });
+    }
+
+    Ok(entries)
+}
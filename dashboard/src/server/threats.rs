@@ -0,0 +1,179 @@
+//! The shared honeypot threat list as one server sees it: recent hits from
+//! every sharing server, and the server's own whitelist. Whitelisting here
+//! does what `/honeypot allow` does, lifting a ban the list caused.
+
+use leptos::prelude::*;
+#[cfg(feature = "ssr")]
+use {
+    crate::dto::{SharedHitInfo, WhitelistEntryInfo},
+    crate::server::auth::{
+        db_pool,
+        discord_client,
+        guild_admin_context,
+        server_err,
+    },
+    jiff_sqlx::Timestamp as SqlxTimestamp,
+    twilight_model::id::Id,
+    zayden_app::services::threats::{self, SHARED_HIT_DAYS},
+};
+
+use crate::dto::ThreatListView;
+
+/// Users the page lists, most recent hit first.
+#[cfg(feature = "ssr")]
+const HIT_LIMIT: i64 = 100;
+
+#[cfg(feature = "ssr")]
+const MAX_NOTE_CHARS: usize = 200;
+
+/// Characters of the message hash shown.
+#[cfg(feature = "ssr")]
+const HASH_PREFIX: usize = 8;
+
+#[cfg(feature = "ssr")]
+const DATE_FORMAT: &str = "%Y-%m-%d %H:%M UTC";
+
+#[cfg(feature = "ssr")]
+fn parse_user(user_id: &str) -> Result<i64, ServerFnError> {
+    user_id
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .filter(|id| *id > 0)
+        .map(u64::cast_signed)
+        .ok_or_else(|| ServerFnError::ServerError("invalid user".to_string()))
+}
+
+#[server]
+pub async fn get_threat_list(
+    guild: String,
+) -> Result<ThreatListView, ServerFnError> {
+    let (guild_id, _user_id, _token) = guild_admin_context(&guild).await?;
+    let pool = db_pool()?;
+
+    let sharing = sqlx::query_scalar!(
+        "SELECT share_hits FROM honeypot_settings WHERE guild_id = $1",
+        guild_id,
+    )
+    .fetch_optional(&pool)
+    .await
+    .map_err(server_err)?
+    .unwrap_or(false);
+
+    let hits = if sharing {
+        sqlx::query!(
+            r#"SELECT
+                h.user_id,
+                COUNT(DISTINCT h.guild_id) AS "guilds!",
+                COUNT(*) AS "hits!",
+                MAX(h.created_at) AS "last_hit_at!: SqlxTimestamp",
+                (ARRAY_AGG(h.message_hash ORDER BY h.created_at DESC)
+                    FILTER (WHERE h.message_hash IS NOT NULL))[1]
+                    AS "message_hash?",
+                BOOL_OR(h.guild_id = $1) AS "hit_here!",
+                sc.action AS "screened?",
+                w.user_id IS NOT NULL AS "whitelisted!"
+            FROM honeypot_hits h
+            LEFT JOIN honeypot_screenings sc
+                ON sc.guild_id = $1 AND sc.user_id = h.user_id
+            LEFT JOIN honeypot_whitelist w
+                ON w.guild_id = $1 AND w.user_id = h.user_id
+            WHERE h.shared AND h.created_at > now() - make_interval(days => $2)
+            GROUP BY h.user_id, sc.action, w.user_id
+            ORDER BY MAX(h.created_at) DESC
+            LIMIT $3"#,
+            guild_id,
+            SHARED_HIT_DAYS,
+            HIT_LIMIT,
+        )
+        .fetch_all(&pool)
+        .await
+        .map_err(server_err)?
+        .into_iter()
+        .map(|row| SharedHitInfo {
+            user_id: row.user_id.cast_unsigned().to_string(),
+            guilds: row.guilds,
+            hits: row.hits,
+            last_hit_at: row.last_hit_at.to_jiff().strftime(DATE_FORMAT).to_string(),
+            message_hash: row
+                .message_hash
+                .map(|hash| hash.chars().take(HASH_PREFIX).collect()),
+            hit_here: row.hit_here,
+            screened: row.screened,
+            whitelisted: row.whitelisted,
+        })
+        .collect()
+    } else {
+        Vec::new()
+    };
+
+    let whitelist = sqlx::query!(
+        r#"SELECT user_id, note, added_by, created_at AS "created_at: SqlxTimestamp"
+        FROM honeypot_whitelist
+        WHERE guild_id = $1
+        ORDER BY created_at DESC"#,
+        guild_id,
+    )
+    .fetch_all(&pool)
+    .await
+    .map_err(server_err)?
+    .into_iter()
+    .map(|row| WhitelistEntryInfo {
+        user_id: row.user_id.cast_unsigned().to_string(),
+        note: row.note,
+        added_by: row.added_by.cast_unsigned().to_string(),
+        created_at: row.created_at.to_jiff().strftime(DATE_FORMAT).to_string(),
+    })
+    .collect();
+
+    Ok(ThreatListView { sharing, hits, whitelist })
+}
+
+/// Clears a user in this server. A ban the shared list made is lifted too.
+#[server]
+pub async fn whitelist_user(
+    guild: String,
+    user_id: String,
+    note: String,
+) -> Result<(), ServerFnError> {
+    let (guild_id, admin_id, _token) = guild_admin_context(&guild).await?;
+    let user_id = parse_user(&user_id)?;
+
+    let note = note.trim();
+    if note.chars().count() > MAX_NOTE_CHARS {
+        return Err(ServerFnError::ServerError(format!(
+            "notes are limited to {MAX_NOTE_CHARS} characters"
+        )));
+    }
+
+    let banned = threats::whitelist(&db_pool()?, guild_id, user_id, note, admin_id)
+        .await
+        .map_err(server_err)?;
+
+    if banned {
+        let http = discord_client()?;
+        let guild = Id::new(guild_id.cast_unsigned());
+        let user = Id::new(user_id.cast_unsigned());
+
+        // Already unbanned by hand, most likely.
+        if let Err(e) = http.delete_ban(guild, user).await {
+            tracing::warn!(error = ?e, "failed to lift a shared threat list ban");
+        }
+    }
+
+    Ok(())
+}
+
+#[server]
+pub async fn unwhitelist_user(
+    guild: String,
+    user_id: String,
+) -> Result<(), ServerFnError> {
+    let (guild_id, _admin_id, _token) = guild_admin_context(&guild).await?;
+    let user_id = parse_user(&user_id)?;
+
+    threats::unwhitelist(&db_pool()?, guild_id, user_id)
+        .await
+        .map(|_| ())
+        .map_err(server_err)
+}
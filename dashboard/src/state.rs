@@ -9,26 +9,37 @@ use oauth2::{
     RedirectUrl,
     TokenUrl,
 };
-use zayden_app::config::BotConfig;
+use zayden_app::config::{BotConfig, PatreonConfig};
 
 const DISCORD_OAUTH_AUTH_URL: &str = "https://discord.com/oauth2/authorize";
 const DISCORD_OAUTH_TOKEN_URL: &str = "https://discord.com/api/oauth2/token";
+const PATREON_OAUTH_AUTH_URL: &str = "https://www.patreon.com/oauth2/authorize";
+const PATREON_OAUTH_TOKEN_URL: &str = "https://www.patreon.com/api/oauth2/token";
+
+pub(crate) type OAuthClient = BasicClient<
+    EndpointSet,
+    EndpointNotSet,
+    EndpointNotSet,
+    EndpointNotSet,
+    EndpointSet,
+>;
 
 pub(crate) fn build_oauth_client(
     config: &BotConfig,
-) -> Result<
-    BasicClient<
-        EndpointSet,
-        EndpointNotSet,
-        EndpointNotSet,
-        EndpointNotSet,
-        EndpointSet,
-    >,
-    ParseError,
-> {
+) -> Result<OAuthClient, ParseError> {
     Ok(BasicClient::new(ClientId::new(config.zayden_id.to_string()))
         .set_client_secret(ClientSecret::new(config.discord_client_secret.clone()))
         .set_auth_uri(AuthUrl::new(DISCORD_OAUTH_AUTH_URL.to_string())?)
         .set_token_uri(TokenUrl::new(DISCORD_OAUTH_TOKEN_URL.to_string())?)
         .set_redirect_uri(RedirectUrl::new(config.redirect_uri.clone())?))
 }
+
+pub(crate) fn build_patreon_oauth_client(
+    config: &PatreonConfig,
+) -> Result<OAuthClient, ParseError> {
+    Ok(BasicClient::new(ClientId::new(config.client_id.clone()))
+        .set_client_secret(ClientSecret::new(config.client_secret.clone()))
+        .set_auth_uri(AuthUrl::new(PATREON_OAUTH_AUTH_URL.to_string())?)
+        .set_token_uri(TokenUrl::new(PATREON_OAUTH_TOKEN_URL.to_string())?)
+        .set_redirect_uri(RedirectUrl::new(config.redirect_uri.clone())?))
+}
@@ -81,6 +81,15 @@ fn icon_paths(name: &str) -> &'static str {
         "sparkles" => {
             r#"<path d="M9.937 15.5A2 2 0 0 0 8.5 14.063l-6.135-1.582a.5.5 0 0 1 0-.962L8.5 9.936A2 2 0 0 0 9.937 8.5l1.582-6.135a.5.5 0 0 1 .962 0L14.063 8.5A2 2 0 0 0 15.5 9.937l6.135 1.581a.5.5 0 0 1 0 .964L15.5 14.063a2 2 0 0 0-1.437 1.437l-1.582 6.135a.5.5 0 0 1-.962 0z"/><path d="M20 3v4"/><path d="M22 5h-4"/><path d="M4 17v2"/><path d="M5 18H3"/>"#
         },
+        "lightbulb" => {
+            r#"<path d="M15 14c.2-1 .7-1.7 1.5-2.5 1-.9 1.5-2.2 1.5-3.5A6 6 0 0 0 6 8c0 1 .2 2.2 1.5 3.5.7.7 1.3 1.5 1.5 2.5"/><path d="M9 18h6"/><path d="M10 22h4"/>"#
+        },
+        "list" => {
+            r#"<path d="M10 12h11"/><path d="M10 18h11"/><path d="M10 6h11"/><path d="M4 10h2"/><path d="M4 6h1v4"/><path d="M6 18H4c0-1 2-2 2-3s-1-1.5-2-1"/>"#
+        },
+        "grip" => {
+            r#"<circle cx="9" cy="12" r="1"/><circle cx="9" cy="5" r="1"/><circle cx="9" cy="19" r="1"/><circle cx="15" cy="12" r="1"/><circle cx="15" cy="5" r="1"/><circle cx="15" cy="19" r="1"/>"#
+        },
         _ => "",
     }
 }
@@ -94,6 +103,8 @@ pub(crate) fn module_icon(id: &str) -> &'static str {
         "family" => "heart",
         "ticket" => "ticket",
         "honeypot" => "shield",
+        "automod" => "shield",
+        "raid" => "users",
         "greetings" => "message",
         _ => "sparkles",
     }
@@ -108,6 +119,8 @@ pub(crate) fn module_tint(id: &str) -> &'static str {
         "family" => "#fb7185",
         "ticket" => "#38bdf8",
         "honeypot" => "#fbbf24",
+        "automod" => "#f87171",
+        "raid" => "#fb923c",
         "greetings" => "#818cf8",
         "ai" => "#22d3ee",
         _ => "#94a3b8",
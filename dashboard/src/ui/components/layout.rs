@@ -98,6 +98,12 @@ fn GuildSidebar(guild_id: String) -> impl IntoView {
     let levels_href = format!("/guild/{guild_id}/levels");
     let reaction_roles_href = format!("/guild/{guild_id}/reaction-roles");
     let greetings_href = format!("/guild/{guild_id}/greetings");
+    let ai_href = format!("/guild/{guild_id}/ai");
+    let suggestions_href = format!("/guild/{guild_id}/suggestions");
+    let moderation_href = format!("/guild/{guild_id}/moderation");
+    let rules_href = format!("/guild/{guild_id}/rules");
+    let threats_href = format!("/guild/{guild_id}/threats");
+    let activity_href = format!("/guild/{guild_id}/activity");
 
     view! {
         <aside class="app-sidebar">
@@ -107,7 +113,13 @@ fn GuildSidebar(guild_id: String) -> impl IntoView {
             <SidebarLink href=settings_href icon="settings" label="Settings"/>
             <SidebarLink href=reaction_roles_href icon="sparkles" label="Reaction Roles"/>
             <SidebarLink href=greetings_href icon="message" label="Greetings"/>
+            <SidebarLink href=ai_href icon="sparkles" label="AI Chat"/>
             <SidebarLink href=levels_href icon="trophy" label="Levels"/>
+            <SidebarLink href=suggestions_href icon="lightbulb" label="Suggestions"/>
+            <SidebarLink href=moderation_href icon="shield" label="Moderation"/>
+            <SidebarLink href=rules_href icon="list" label="Rules"/>
+            <SidebarLink href=threats_href icon="shield" label="Threat List"/>
+            <SidebarLink href=activity_href icon="gauge" label="Activity"/>
             <div class="app-sidebar-spacer"></div>
             <SidebarLink href="/guilds".to_string() icon="server" label="All servers" exact=true/>
             <SidebarLink href="/upgrade".to_string() icon="zap" label="Upgrade to Pro"/>
@@ -0,0 +1,108 @@
+use leptos::form::ActionForm;
+use leptos::prelude::*;
+use leptos_meta::Title;
+use leptos_router::hooks::use_params_map;
+
+use crate::server::activity::{RollbackSettingsChange, list_settings_activity};
+use crate::ui::components::layout::AppShell;
+use crate::ui::components::settings::save_feedback;
+
+const PAGE_SIZE: usize = 20;
+
+#[component]
+pub(crate) fn ActivityPage() -> impl IntoView {
+    let params = use_params_map();
+    let guild_id = move || params.with(|p| p.get("id").unwrap_or_default());
+
+    let (page, set_page) = signal(1i32);
+    let rollback = ServerAction::<RollbackSettingsChange>::new();
+
+    let activity = Resource::new(
+        move || (guild_id(), page.get(), rollback.version().get()),
+        |(gid, p, _)| list_settings_activity(gid, p),
+    );
+
+    view! {
+        <Title text="Activity - Zayden Dashboard"/>
+        <AppShell>
+            <div class="page">
+                <div class="page-header">
+                    <div>
+                        <h1>"Activity"</h1>
+                        <p class="page-lead">
+                            "Every settings change made from the dashboard or a slash command. "
+                            "Undo puts that section back to how it was before the change."
+                        </p>
+                    </div>
+                </div>
+
+                {move || rollback.value().get().map(save_feedback)}
+                <Suspense fallback=|| view! {
+                    <p class="loading">"Loading activity\u{2026}"</p>
+                }>
+                    {move || activity.get().map(|result| match result {
+                        Err(e) => view! {
+                            <p class="error">"Failed to load activity: " {e.to_string()}</p>
+                        }.into_any(),
+                        Ok(entries) if entries.is_empty() => view! {
+                            <div class="empty">
+                                {if page.get() > 1 {
+                                    "No more changes on this page."
+                                } else {
+                                    "No settings changes recorded yet."
+                                }}
+                            </div>
+                        }.into_any(),
+                        Ok(entries) => {
+                            let has_next = entries.len() == PAGE_SIZE;
+                            let gid = guild_id();
+                            view! {
+                                <div class="activity-list">
+                                    {entries.into_iter().map(|e| view! {
+                                        <div class="activity-row">
+                                            <div class="activity-meta">
+                                                <span class="activity-section">{e.section}</span>
+                                                <span>{format!("{} via {}", e.actor, e.source)}</span>
+                                                <span class="activity-time">{e.at}</span>
+                                                <ActionForm action=rollback attr:class="activity-undo">
+                                                    <input type="hidden" name="guild" value=gid.clone()/>
+                                                    <input type="hidden" name="change" value=e.id/>
+                                                    <button type="submit" class="btn btn-ghost">"Undo"</button>
+                                                </ActionForm>
+                                            </div>
+                                            <ul class="activity-fields">
+                                                {e.fields.into_iter().map(|f| view! {
+                                                    <li>
+                                                        <code>{f.field}</code>
+                                                        <span class="activity-before">{f.before}</span>
+                                                        "\u{2192}"
+                                                        <span class="activity-after">{f.after}</span>
+                                                    </li>
+                                                }).collect_view()}
+                                            </ul>
+                                        </div>
+                                    }).collect_view()}
+                                </div>
+                                <div class="pager">
+                                    <button
+                                        type="button"
+                                        class="btn btn-secondary"
+                                        prop:disabled=move || page.get() <= 1
+                                        on:click=move |_| set_page.update(|p| *p = (*p - 1).max(1))
+                                    >"Previous"</button>
+                                    <span class="pager-page">"Page " {move || page.get()}</span>
+                                    <button
+                                        type="button"
+                                        class="btn btn-secondary"
+                                        prop:disabled=!has_next
+                                        on:click=move |_| set_page.update(|p| *p += 1)
+                                    >"Next"</button>
+                                </div>
+                            }.into_any()
+                        },
+                    })}
+                </Suspense>
+            </div>
+        </AppShell>
+    }
+}
@@ -0,0 +1,541 @@
+use leptos::form::ActionForm;
+use leptos::prelude::*;
+use leptos_meta::Title;
+use leptos_router::components::A;
+
+use crate::dto::ai::short_tokens;
+use crate::dto::{AdminOverview, AiUsageView, ConfigOverridesView, Tier};
+use crate::server::admin::{
+    ExtendEntitlement,
+    GrantEntitlement,
+    RaiseAiLimit,
+    ResetAiLimit,
+    RevokeEntitlement,
+    SaveConfigOverrides,
+    get_admin_overview,
+    get_ai_usage,
+    get_config_overrides,
+    list_entitlements,
+};
+use crate::ui::components::icons::Icon;
+use crate::ui::components::layout::AppShell;
+use crate::ui::components::select::{SelectField, SelectOption};
+use crate::ui::components::settings::{SettingField, save_feedback};
+use crate::ui::pages::not_found::NotFound;
+
+#[component]
+pub(crate) fn AdminPage() -> impl IntoView {
+    let save = ServerAction::<SaveConfigOverrides>::new();
+    let overview = Resource::new(|| (), |()| get_admin_overview());
+    let config =
+        Resource::new(move || save.version().get(), |_| get_config_overrides());
+
+    view! {
+        <Title text="Admin - Zayden Dashboard"/>
+        <Suspense fallback=|| view! { <p class="loading">"Loading admin\u{2026}"</p> }>
+            {move || overview.get().map(|result| match result {
+                Err(_) => view! { <NotFound/> }.into_any(),
+                Ok(overview) => view! {
+                    <AppShell>
+                        <div class="page">
+                            <div class="page-header">
+                                <div>
+                                    <h1>"Admin"</h1>
+                                    <p class="page-lead">
+                                        {format!("Last {} days across every server.", overview.window_days)}
+                                    </p>
+                                </div>
+                            </div>
+
+                            <div class="admin-links">
+                                <A href="/admin/entitlements" attr:class="btn btn-ghost">
+                                    <Icon name="sparkles"/>"Entitlements"
+                                </A>
+                                <A href="/admin/ai-usage" attr:class="btn btn-ghost">
+                                    <Icon name="gauge"/>"AI usage"
+                                </A>
+                                <A href="/admin/gift-codes" attr:class="btn btn-ghost">
+                                    <Icon name="ticket"/>"Gift codes"
+                                </A>
+                                <A href="/admin/palworld/save" attr:class="btn btn-ghost">
+                                    <Icon name="palworld"/>"Palworld save"
+                                </A>
+                            </div>
+
+                            <OverviewSections overview=overview/>
+
+                            <fieldset class="settings-section">
+                                <legend><Icon name="settings"/>"Bot config"</legend>
+                                <p class="page-lead">
+                                    "Blank fields fall back to the environment. "
+                                    "Changes apply when the bot restarts."
+                                </p>
+                                {move || save.value().get().map(save_feedback)}
+                                <Transition fallback=|| ()>
+                                    {move || config.get().and_then(Result::ok).map(|config| {
+                                        view! { <ConfigForm action=save config=config/> }
+                                    })}
+                                </Transition>
+                            </fieldset>
+                        </div>
+                    </AppShell>
+                }.into_any(),
+            })}
+        </Suspense>
+    }
+}
+
+#[component]
+fn OverviewSections(overview: AdminOverview) -> impl IntoView {
+    let peak = overview
+        .growth
+        .iter()
+        .map(|d| d.joined.max(d.left))
+        .max()
+        .unwrap_or(0)
+        .max(1);
+
+    view! {
+        <div class="admin-stats">
+            <div class="admin-stat">
+                <span class="admin-stat-value">{overview.active_guilds}</span>
+                <span class="admin-stat-label">"Servers"</span>
+            </div>
+            <div class="admin-stat">
+                <span class="admin-stat-value">{format!("+{}", overview.joined)}</span>
+                <span class="admin-stat-label">"Joined"</span>
+            </div>
+            <div class="admin-stat">
+                <span class="admin-stat-value">{format!("-{}", overview.left)}</span>
+                <span class="admin-stat-label">"Left"</span>
+            </div>
+        </div>
+
+        <fieldset class="settings-section">
+            <legend><Icon name="gauge"/>"Growth"</legend>
+            <div class="admin-growth">
+                {overview.growth.into_iter().map(|d| {
+                    let joined = d.joined * 100 / peak;
+                    let left = d.left * 100 / peak;
+                    view! {
+                        <div
+                            class="admin-growth-day"
+                            title=format!("{}: +{} / -{}", d.day, d.joined, d.left)
+                        >
+                            <span class="admin-bar admin-bar-joined" style=format!("height: {joined}%")/>
+                            <span class="admin-bar admin-bar-left" style=format!("height: {left}%")/>
+                        </div>
+                    }
+                }).collect_view()}
+            </div>
+        </fieldset>
+
+        <fieldset class="settings-section">
+            <legend><Icon name="grid"/>"Usage by module"</legend>
+            {if overview.modules.is_empty() {
+                view! { <p class="empty">"No commands run yet."</p> }.into_any()
+            } else {
+                view! {
+                    <table class="admin-table">
+                        <thead><tr><th>"Module"</th><th>"Uses"</th></tr></thead>
+                        <tbody>
+                            {overview.modules.into_iter().map(|m| view! {
+                                <tr>
+                                    <td>{if m.module.is_empty() { "(core)".to_string() } else { m.module }}</td>
+                                    <td>{m.uses}</td>
+                                </tr>
+                            }).collect_view()}
+                        </tbody>
+                    </table>
+                    <table class="admin-table">
+                        <thead>
+                            <tr><th>"Command"</th><th>"Uses"</th><th>"Failures"</th></tr>
+                        </thead>
+                        <tbody>
+                            {overview.commands.into_iter().map(|c| view! {
+                                <tr>
+                                    <td><code>{format!("/{}", c.command)}</code></td>
+                                    <td>{c.uses}</td>
+                                    <td>{c.failures}</td>
+                                </tr>
+                            }).collect_view()}
+                        </tbody>
+                    </table>
+                }
+                .into_any()
+            }}
+        </fieldset>
+
+        <fieldset class="settings-section">
+            <legend><Icon name="zap"/>"Scheduled jobs"</legend>
+            {if overview.jobs.is_empty() {
+                view! { <p class="empty">"No jobs have run yet."</p> }.into_any()
+            } else {
+                view! {
+                    <table class="admin-table">
+                        <thead>
+                            <tr>
+                                <th>"Job"</th>
+                                <th>"Last run"</th>
+                                <th>"Took"</th>
+                                <th>"Next run"</th>
+                                <th>"Runs"</th>
+                            </tr>
+                        </thead>
+                        <tbody>
+                            {overview.jobs.into_iter().map(|j| view! {
+                                <tr>
+                                    <td><code>{j.id}</code></td>
+                                    <td>{j.last_run}</td>
+                                    <td>{format!("{} ms", j.duration_ms)}</td>
+                                    <td>{j.next_run.unwrap_or_else(|| "\u{2014}".to_string())}</td>
+                                    <td>{j.runs}</td>
+                                </tr>
+                            }).collect_view()}
+                        </tbody>
+                    </table>
+                }
+                .into_any()
+            }}
+        </fieldset>
+
+        <fieldset class="settings-section">
+            <legend><Icon name="shield"/>"Recent errors"</legend>
+            {if overview.logs.is_empty() {
+                view! { <p class="empty">"Nothing logged."</p> }.into_any()
+            } else {
+                view! {
+                    <div class="admin-logs">
+                        {overview.logs.into_iter().map(|l| view! {
+                            <div class=format!("admin-log admin-log-{}", l.level.to_lowercase())>
+                                <div class="admin-log-meta">
+                                    <span class="admin-log-level">{l.level}</span>
+                                    <code>{l.target}</code>
+                                    <span>{l.at}</span>
+                                </div>
+                                <pre>{l.message}</pre>
+                            </div>
+                        }).collect_view()}
+                    </div>
+                }
+                .into_any()
+            }}
+        </fieldset>
+    }
+}
+
+#[component]
+fn ConfigForm(
+    action: ServerAction<SaveConfigOverrides>,
+    config: ConfigOverridesView,
+) -> impl IntoView {
+    view! {
+        <ActionForm action=action>
+            <SettingField
+                label="Error log webhook"
+                name="error_log_webhook"
+                value=config.error_log_webhook
+                pattern=".*"
+            />
+            <SettingField
+                label="Log webhook"
+                name="normal_log_webhook"
+                value=config.normal_log_webhook
+                pattern=".*"
+            />
+            <SettingField
+                label="FlareSolverr URL"
+                name="flaresolverr_url"
+                value=config.flaresolverr_url
+                pattern=".*"
+            />
+            <SettingField
+                label="YouTube cookies file"
+                name="youtube_cookies_file"
+                value=config.youtube_cookies_file
+                pattern=".*"
+            />
+            <div class="form-actions">
+                <button type="submit" class="btn btn-primary">"Save"</button>
+            </div>
+        </ActionForm>
+    }
+}
+
+#[component]
+pub(crate) fn AdminEntitlementsPage() -> impl IntoView {
+    let grant = ServerAction::<GrantEntitlement>::new();
+    let revoke = ServerAction::<RevokeEntitlement>::new();
+    let extend = ServerAction::<ExtendEntitlement>::new();
+    let entitlements = Resource::new(
+        move || {
+            (grant.version().get(), revoke.version().get(), extend.version().get())
+        },
+        |_| list_entitlements(),
+    );
+
+    let scopes = vec![
+        SelectOption { value: "user".to_string(), label: "User".to_string() },
+        SelectOption { value: "guild".to_string(), label: "Server".to_string() },
+    ];
+    let tiers = [Tier::Pro, Tier::Ultra]
+        .into_iter()
+        .map(|tier| SelectOption {
+            value: tier.css_suffix().to_string(),
+            label: tier.label().to_string(),
+        })
+        .collect::<Vec<_>>();
+
+    view! {
+        <Title text="Entitlements - Zayden Dashboard"/>
+        <Suspense fallback=|| view! { <p class="loading">"Loading entitlements\u{2026}"</p> }>
+            {move || entitlements.get().map(|result| match result {
+                Err(_) => view! { <NotFound/> }.into_any(),
+                Ok(list) => view! {
+                    <AppShell>
+                        <div class="page">
+                            <div class="page-header">
+                                <div>
+                                    <h1>"Entitlements"</h1>
+                                    <p class="page-lead">
+                                        "Every active grant, from any provider. Manual grants "
+                                        "to the same user or server replace each other."
+                                    </p>
+                                </div>
+                            </div>
+
+                            <fieldset class="settings-section">
+                                <legend><Icon name="plus"/>"Grant"</legend>
+                                {move || grant.value().get().map(save_feedback)}
+                                <ActionForm action=grant>
+                                    <SelectField
+                                        label="For"
+                                        name="scope"
+                                        selected="user".to_string()
+                                        options=scopes.clone()
+                                    />
+                                    <SettingField
+                                        label="User or server ID"
+                                        name="target_id"
+                                        value=String::new()
+                                    />
+                                    <SelectField
+                                        label="Tier"
+                                        name="tier"
+                                        selected="pro".to_string()
+                                        options=tiers.clone()
+                                    />
+                                    <SettingField
+                                        label="Duration (days, blank = permanent)"
+                                        name="days"
+                                        value=String::new()
+                                    />
+                                    <div class="form-actions">
+                                        <button type="submit" class="btn btn-primary">
+                                            "Grant"
+                                        </button>
+                                    </div>
+                                </ActionForm>
+                            </fieldset>
+
+                            {move || revoke.value().get().map(save_feedback)}
+                            {move || extend.value().get().map(save_feedback)}
+                            {if list.is_empty() {
+                                view! { <p class="empty">"No active entitlements."</p> }
+                                    .into_any()
+                            } else {
+                                view! {
+                                    <div class="slot-list">
+                                        {list.into_iter().map(|e| view! {
+                                            <div class="slot-row">
+                                                <span class="slot-name">{e.scope}</span>
+                                                <span class=format!(
+                                                    "tier-badge tier-{}", e.tier.css_suffix(),
+                                                )>{e.tier.label()}</span>
+                                                <span class="slot-locked">
+                                                    {format!("{} \u{b7} since {}", e.provider, e.granted_at)}
+                                                    {e.expires_at.clone().map_or_else(
+                                                        || " \u{b7} permanent".to_string(),
+                                                        |at| format!(" \u{b7} until {at}"),
+                                                    )}
+                                                </span>
+                                                {e.expires_at.is_some().then(|| view! {
+                                                    <ActionForm action=extend attr:class="admin-inline-form">
+                                                        <input type="hidden" name="provider" value=e.provider.clone()/>
+                                                        <input type="hidden" name="external_id" value=e.external_id.clone()/>
+                                                        <input type="text" name="days" value="30" pattern="[0-9]*" size="3"/>
+                                                        <button type="submit" class="btn btn-ghost">
+                                                            "Extend"
+                                                        </button>
+                                                    </ActionForm>
+                                                })}
+                                                <ActionForm action=revoke>
+                                                    <input type="hidden" name="provider" value=e.provider/>
+                                                    <input type="hidden" name="external_id" value=e.external_id/>
+                                                    <button type="submit" class="btn btn-ghost">
+                                                        "Revoke"
+                                                    </button>
+                                                </ActionForm>
+                                            </div>
+                                        }).collect_view()}
+                                    </div>
+                                }
+                                .into_any()
+                            }}
+                        </div>
+                    </AppShell>
+                }.into_any(),
+            })}
+        </Suspense>
+    }
+}
+
+#[component]
+pub(crate) fn AdminAiUsagePage() -> impl IntoView {
+    let raise = ServerAction::<RaiseAiLimit>::new();
+    let reset = ServerAction::<ResetAiLimit>::new();
+    let usage = Resource::new(
+        move || (raise.version().get(), reset.version().get()),
+        |_| get_ai_usage(),
+    );
+
+    view! {
+        <Title text="AI usage - Zayden Dashboard"/>
+        <Suspense fallback=|| view! { <p class="loading">"Loading AI usage\u{2026}"</p> }>
+            {move || usage.get().map(|result| match result {
+                Err(_) => view! { <NotFound/> }.into_any(),
+                Ok(usage) => view! {
+                    <AppShell>
+                        <div class="page">
+                            <div class="page-header">
+                                <div>
+                                    <h1>"AI usage"</h1>
+                                    <p class="page-lead">
+                                        {format!(
+                                            "Tokens billed per server and model over the last {} days.",
+                                            usage.window_days,
+                                        )}
+                                    </p>
+                                </div>
+                            </div>
+
+                            <AiUsageReport usage=usage.clone()/>
+                            <AiLimitsSection usage=usage raise=raise reset=reset/>
+                        </div>
+                    </AppShell>
+                }.into_any(),
+            })}
+        </Suspense>
+    }
+}
+
+#[component]
+fn AiUsageReport(usage: AiUsageView) -> impl IntoView {
+    view! {
+        <fieldset class="settings-section">
+            <legend><Icon name="gauge"/>"Spend"</legend>
+            {if usage.rows.is_empty() {
+                view! { <p class="empty">"No AI replies in this window."</p> }.into_any()
+            } else {
+                view! {
+                    <table class="admin-table">
+                        <thead>
+                            <tr>
+                                <th>"Server"</th>
+                                <th>"Model"</th>
+                                <th>"Replies"</th>
+                                <th>"Prompt"</th>
+                                <th>"Completion"</th>
+                                <th>"Total"</th>
+                            </tr>
+                        </thead>
+                        <tbody>
+                            {usage.rows.into_iter().map(|u| view! {
+                                <tr>
+                                    <td title=u.guild_id.clone()>
+                                        {if u.guild_name.is_empty() { u.guild_id } else { u.guild_name }}
+                                    </td>
+                                    <td><code>{u.model}</code></td>
+                                    <td>{u.requests}</td>
+                                    <td>{short_tokens(u.prompt_tokens)}</td>
+                                    <td>{short_tokens(u.completion_tokens)}</td>
+                                    <td>{short_tokens(u.total_tokens)}</td>
+                                </tr>
+                            }).collect_view()}
+                        </tbody>
+                    </table>
+                }
+                .into_any()
+            }}
+        </fieldset>
+    }
+}
+
+#[component]
+fn AiLimitsSection(
+    usage: AiUsageView,
+    raise: ServerAction<RaiseAiLimit>,
+    reset: ServerAction<ResetAiLimit>,
+) -> impl IntoView {
+    let limit = |tokens: String| {
+        if tokens.is_empty() { "tier default".to_string() } else { tokens }
+    };
+
+    view! {
+        <fieldset class="settings-section">
+            <legend><Icon name="plus"/>"Raised limits"</legend>
+            <p class="page-lead">
+                "Lifts a server's daily or monthly token allowance above its "
+                "tier's. A limit below the tier's has no effect, and a blank one "
+                "keeps the tier's. Members keep their own daily allowance."
+            </p>
+            {move || raise.value().get().map(save_feedback)}
+            <ActionForm action=raise>
+                <SettingField label="Server ID" name="guild_id" value=String::new()/>
+                <SettingField
+                    label="Daily tokens (blank = tier default)"
+                    name="daily_tokens"
+                    value=String::new()
+                />
+                <SettingField
+                    label="Monthly tokens (blank = tier default)"
+                    name="monthly_tokens"
+                    value=String::new()
+                />
+                <div class="form-actions">
+                    <button type="submit" class="btn btn-primary">"Raise"</button>
+                </div>
+            </ActionForm>
+
+            {move || reset.value().get().map(save_feedback)}
+            {if usage.limits.is_empty() {
+                view! { <p class="empty">"Every server is on its tier's limits."</p> }
+                    .into_any()
+            } else {
+                view! {
+                    <div class="slot-list">
+                        {usage.limits.into_iter().map(|l| view! {
+                            <div class="slot-row">
+                                <span class="slot-name">{format!("Server {}", l.guild_id)}</span>
+                                <span class="slot-locked">
+                                    {format!(
+                                        "{} a day \u{b7} {} a month \u{b7} by {} on {}",
+                                        limit(l.daily_tokens),
+                                        limit(l.monthly_tokens),
+                                        l.set_by,
+                                        l.updated_at,
+                                    )}
+                                </span>
+                                <ActionForm action=reset>
+                                    <input type="hidden" name="guild_id" value=l.guild_id/>
+                                    <button type="submit" class="btn btn-ghost">"Reset"</button>
+                                </ActionForm>
+                            </div>
+                        }).collect_view()}
+                    </div>
+                }
+                .into_any()
+            }}
+        </fieldset>
+    }
+}
@@ -0,0 +1,536 @@
+use leptos::form::ActionForm;
+use leptos::prelude::*;
+use leptos_meta::Title;
+use leptos_router::hooks::use_params_map;
+use twilight_model::channel::ChannelType;
+
+use crate::dto::ai::short_tokens;
+use crate::dto::{AiView, ChannelInfo, ChannelPersonaInfo, PersonaInfo, Tier};
+use crate::server::ai::{
+    RemoveAiChannelPersona,
+    RemoveAiPersona,
+    SaveAiDefaultPersona,
+    SaveAiPersona,
+    SaveAiTrigger,
+    SetAiChannelPersona,
+    TestAiPersona,
+    get_ai_settings,
+};
+use crate::server::discord::list_guild_channels;
+use crate::ui::components::icons::Icon;
+use crate::ui::components::layout::AppShell;
+use crate::ui::components::select::{ChannelSelect, SelectField, SelectOption};
+use crate::ui::components::settings::{SaveButton, SettingField, save_feedback};
+
+const TEXT_KINDS: &[ChannelType] =
+    &[ChannelType::GuildText, ChannelType::GuildAnnouncement];
+
+fn trigger_options() -> Vec<SelectOption> {
+    [
+        ("mention", "Only when mentioned"),
+        ("reply", "Mentions and replies to the bot"),
+        ("keyword", "Mentions and keywords"),
+        ("channel", "Every message in the AI channel"),
+    ]
+    .into_iter()
+    .map(|(value, label)| SelectOption {
+        value: value.to_string(),
+        label: label.to_string(),
+    })
+    .collect()
+}
+
+fn persona_options(personas: &[PersonaInfo]) -> Vec<SelectOption> {
+    personas
+        .iter()
+        .map(|p| SelectOption { value: p.name.clone(), label: p.name.clone() })
+        .collect()
+}
+
+#[derive(Clone, Copy)]
+struct PersonaActions {
+    save: ServerAction<SaveAiPersona>,
+    remove: ServerAction<RemoveAiPersona>,
+    default: ServerAction<SaveAiDefaultPersona>,
+    set_channel: ServerAction<SetAiChannelPersona>,
+    remove_channel: ServerAction<RemoveAiChannelPersona>,
+    test: ServerAction<TestAiPersona>,
+}
+
+#[component]
+pub(crate) fn AiPage() -> impl IntoView {
+    let params = use_params_map();
+    let guild_id = move || params.with(|p| p.get("id").unwrap_or_default());
+
+    let save_trigger = ServerAction::<SaveAiTrigger>::new();
+    let actions = PersonaActions {
+        save: ServerAction::new(),
+        remove: ServerAction::new(),
+        default: ServerAction::new(),
+        set_channel: ServerAction::new(),
+        remove_channel: ServerAction::new(),
+        test: ServerAction::new(),
+    };
+
+    let data = Resource::new_blocking(
+        move || {
+            (
+                guild_id(),
+                save_trigger.version().get(),
+                actions.save.version().get(),
+                actions.remove.version().get(),
+                actions.default.version().get(),
+                actions.set_channel.version().get(),
+                actions.remove_channel.version().get(),
+            )
+        },
+        |(gid, ..)| async move {
+            let view = get_ai_settings(gid.clone()).await?;
+            let channels = list_guild_channels(gid).await.unwrap_or_default();
+            Ok::<(AiView, Vec<ChannelInfo>), ServerFnError>((view, channels))
+        },
+    );
+
+    view! {
+        <Title text="AI Chat - Zayden Dashboard"/>
+        <AppShell>
+            <div class="page">
+                <div class="page-header">
+                    <div>
+                        <h1>"AI Chat"</h1>
+                        <p class="page-lead">
+                            "When Zayden answers, and who he answers as. Turn AI "
+                            "responses on and pick their channel under Settings."
+                        </p>
+                    </div>
+                </div>
+                <Suspense fallback=|| view! {
+                    <p class="loading">"Loading AI settings\u{2026}"</p>
+                }>
+                    {move || data.get().map(|result| match result {
+                        Err(e) => view! {
+                            <p class="error">"Failed to load AI settings: " {e.to_string()}</p>
+                        }.into_any(),
+                        Ok((view, channels)) => {
+                            let gid = guild_id();
+                            let personas = view.has_personas().then(|| view! {
+                                <PersonaSections
+                                    guild_id=gid.clone()
+                                    ai=view.clone()
+                                    channels=channels
+                                    actions=actions
+                                />
+                            });
+                            let pitch = (!view.has_personas()).then(|| view! {
+                                <UpgradeSection next_tier=view.next_tier/>
+                            });
+                            let usage = view! { <UsageSection ai=view.clone()/> };
+
+                            view! {
+                                <TriggerSection
+                                    guild_id=gid
+                                    trigger_mode=view.trigger_mode
+                                    keywords=view.keywords
+                                    save=save_trigger
+                                />
+                                {usage}
+                                {personas}
+                                {pitch}
+                            }.into_any()
+                        },
+                    })}
+                </Suspense>
+            </div>
+        </AppShell>
+    }
+}
+
+#[component]
+fn TriggerSection(
+    guild_id: String,
+    trigger_mode: String,
+    keywords: String,
+    save: ServerAction<SaveAiTrigger>,
+) -> impl IntoView {
+    let result = save.value();
+
+    view! {
+        <fieldset class="settings-section ai-editor">
+            <legend><Icon name="message"/>"When to answer"</legend>
+            <p class="page-lead">
+                "A mention always gets an answer. Keywords match whole words or "
+                "phrases, ignoring case and punctuation. Answering every message "
+                "only works once an AI channel is picked, so he never talks over "
+                "the whole server."
+            </p>
+            {move || result.get().map(save_feedback)}
+            <ActionForm action=save>
+                <input type="hidden" name="guild" value=guild_id/>
+                <SelectField
+                    label="Answer"
+                    name="trigger_mode"
+                    selected=trigger_mode
+                    options=trigger_options()
+                />
+                <div class="setting-field">
+                    <label>"Keywords (one per line, up to 20)"</label>
+                    <textarea
+                        name="keywords"
+                        rows="4"
+                        placeholder="zayden\nhey bot"
+                    >{keywords}</textarea>
+                </div>
+                <SaveButton/>
+            </ActionForm>
+        </fieldset>
+    }
+}
+
+#[component]
+fn UsageSection(ai: AiView) -> impl IntoView {
+    let today = format!(
+        "{} of {} tokens",
+        short_tokens(ai.tokens_today),
+        short_tokens(ai.daily_limit)
+    );
+    let month = format!(
+        "{} of {} tokens",
+        short_tokens(ai.tokens_month),
+        short_tokens(ai.monthly_limit)
+    );
+
+    let upgrade = ai.next_tier.map(|next| {
+        view! {
+            <p class="page-lead">
+                {format!("{} servers get a much larger allowance. ", next.label())}
+                <a href="/upgrade">"See plans"</a>
+                "."
+            </p>
+        }
+    });
+
+    view! {
+        <fieldset class="settings-section">
+            <legend><Icon name="gauge"/>"Usage"</legend>
+            <p class="page-lead">
+                "Every reply, test chats included, spends tokens from this "
+                "server's allowance. Once it runs out Zayden tells whoever "
+                "mentions him, and stays quiet otherwise, until the day or month "
+                "resets at midnight UTC. Each member also has a daily allowance "
+                "of their own."
+            </p>
+            <div class="admin-stats">
+                <div class="admin-stat">
+                    <span class="admin-stat-value">{today}</span>
+                    <span class="admin-stat-label">"Today"</span>
+                </div>
+                <div class="admin-stat">
+                    <span class="admin-stat-value">{month}</span>
+                    <span class="admin-stat-label">"This month"</span>
+                </div>
+            </div>
+            {upgrade}
+        </fieldset>
+    }
+}
+
+#[component]
+fn UpgradeSection(next_tier: Option<Tier>) -> impl IntoView {
+    let plan = next_tier.unwrap_or(Tier::Pro).label();
+
+    view! {
+        <fieldset class="settings-section">
+            <legend><Icon name="sparkles"/>"Custom personas"</legend>
+            <p class="page-lead">
+                {format!(
+                    "On {plan}, write your own characters for Zayden to play: a \
+                     name, who they are, how they talk, how long they go on and \
+                     how adventurous they get, each picked per channel. "
+                )}
+                <a href="/upgrade">"See plans"</a>
+                "."
+            </p>
+        </fieldset>
+    }
+}
+
+#[component]
+fn PersonaSections(
+    guild_id: String,
+    ai: AiView,
+    channels: Vec<ChannelInfo>,
+    actions: PersonaActions,
+) -> impl IntoView {
+    let AiView { personas, default_persona, channel_personas, .. } = ai;
+
+    let save_result = actions.save.value();
+    let remove_result = actions.remove.value();
+
+    let cards = personas
+        .iter()
+        .cloned()
+        .map(|persona| {
+            view! { <PersonaCard guild_id=guild_id.clone() persona=persona actions=actions/> }
+        })
+        .collect_view();
+
+    let empty = personas.is_empty().then(|| {
+        view! {
+            <p class="empty">"No personas yet. Zayden plays himself everywhere."</p>
+        }
+    });
+
+    let new_gid = guild_id.clone();
+    let placement_gid = guild_id.clone();
+    let options = persona_options(&personas);
+
+    view! {
+        <fieldset class="settings-section ai-editor">
+            <legend><Icon name="sparkles"/>"Personas"</legend>
+            <p class="page-lead">
+                "Each persona is a character the bot plays instead of its own. "
+                "The prompt and style are handed to the model as notes about "
+                "the character; they can't switch off the server-safety rules, "
+                "and anything a member writes is never read as instructions."
+            </p>
+            {move || save_result.get().map(save_feedback)}
+            {move || remove_result.get().map(save_feedback)}
+            <div class="persona-list">{cards}</div>
+            {empty}
+        </fieldset>
+
+        <fieldset class="settings-section ai-editor">
+            <legend><Icon name="plus"/>"New persona"</legend>
+            <ActionForm action=actions.save>
+                <input type="hidden" name="guild" value=new_gid/>
+                <div class="setting-field">
+                    <label>"Name"</label>
+                    <input type="text" name="name" maxlength="32" required/>
+                </div>
+                <PersonaFields persona=None/>
+                <div class="form-actions">
+                    <button type="submit" class="btn btn-primary">"Add persona"</button>
+                </div>
+            </ActionForm>
+        </fieldset>
+
+        <PlacementSection
+            guild_id=placement_gid
+            options=options.clone()
+            default_persona=default_persona
+            channel_personas=channel_personas
+            channels=channels
+            actions=actions
+        />
+
+        <TestSection guild_id=guild_id options=options test=actions.test/>
+    }
+}
+
+#[component]
+fn PersonaFields(persona: Option<PersonaInfo>) -> impl IntoView {
+    let (prompt, style, word_limit, temperature) = persona.map_or_else(
+        || (String::new(), String::new(), "100".to_string(), "0.8".to_string()),
+        |p| (p.prompt, p.style, p.word_limit.to_string(), p.temperature),
+    );
+
+    view! {
+        <div class="setting-field">
+            <label>"Who they are"</label>
+            <textarea
+                name="prompt"
+                rows="6"
+                maxlength="2000"
+                placeholder="A retired sea captain who runs the server's tavern..."
+                required
+            >{prompt}</textarea>
+        </div>
+        <div class="setting-field">
+            <label>"Style rules"</label>
+            <textarea
+                name="style"
+                rows="3"
+                maxlength="1000"
+                placeholder="Short sentences. Nautical slang. Never uses emoji."
+            >{style}</textarea>
+        </div>
+        <SettingField label="Word limit (10-300)" name="word_limit" value=word_limit/>
+        <SettingField
+            label="Temperature (0-1.5, higher is more adventurous)"
+            name="temperature"
+            value=temperature
+            pattern="[0-9.]*"
+        />
+    }
+}
+
+#[component]
+fn PersonaCard(
+    guild_id: String,
+    persona: PersonaInfo,
+    actions: PersonaActions,
+) -> impl IntoView {
+    let name = persona.name.clone();
+    let remove_name = persona.name.clone();
+    let remove_gid = guild_id.clone();
+
+    view! {
+        <details class="persona-card">
+            <summary>{persona.name.clone()}</summary>
+            <ActionForm action=actions.save>
+                <input type="hidden" name="guild" value=guild_id/>
+                <input type="hidden" name="name" value=name/>
+                <PersonaFields persona=Some(persona)/>
+                <SaveButton/>
+            </ActionForm>
+            <ActionForm action=actions.remove>
+                <input type="hidden" name="guild" value=remove_gid/>
+                <input type="hidden" name="name" value=remove_name/>
+                <button type="submit" class="btn btn-ghost">
+                    <Icon name="x"/>
+                    "Remove"
+                </button>
+            </ActionForm>
+        </details>
+    }
+}
+
+#[component]
+fn PlacementSection(
+    guild_id: String,
+    options: Vec<SelectOption>,
+    default_persona: String,
+    channel_personas: Vec<ChannelPersonaInfo>,
+    channels: Vec<ChannelInfo>,
+    actions: PersonaActions,
+) -> impl IntoView {
+    let default_result = actions.default.value();
+    let set_result = actions.set_channel.value();
+    let remove_result = actions.remove_channel.value();
+
+    let default_gid = guild_id.clone();
+    let set_gid = guild_id.clone();
+    let set_options = options.clone();
+
+    let chips = channel_personas
+        .into_iter()
+        .map(|pick| {
+            let channel =
+                channels.iter().find(|c| c.id == pick.channel_id).map_or_else(
+                    || format!("#unknown ({})", pick.channel_id),
+                    |c| format!("#{}", c.name),
+                );
+            let persona = if pick.persona.is_empty() {
+                "Zayden"
+            } else {
+                pick.persona.as_str()
+            };
+            let label = format!("{channel} \u{2192} {persona}");
+            let gid = guild_id.clone();
+
+            view! {
+                <ActionForm action=actions.remove_channel attr:class="chip">
+                    <input type="hidden" name="guild" value=gid/>
+                    <input type="hidden" name="channel_id" value=pick.channel_id/>
+                    <span class="chip-label">{label}</span>
+                    <button type="submit" class="chip-remove" title="Remove">
+                        <Icon name="x"/>
+                    </button>
+                </ActionForm>
+            }
+        })
+        .collect_view();
+
+    view! {
+        <fieldset class="settings-section">
+            <legend><Icon name="grid"/>"Where each persona speaks"</legend>
+            <p class="page-lead">
+                "The default persona answers everywhere without a pick of its "
+                "own. Leave it unset for Zayden's own character. A channel pick "
+                "left unset keeps Zayden himself in that channel."
+            </p>
+            {move || default_result.get().map(save_feedback)}
+            <ActionForm action=actions.default>
+                <input type="hidden" name="guild" value=default_gid/>
+                <SelectField
+                    label="Default persona"
+                    name="persona"
+                    selected=default_persona
+                    options=options
+                />
+                <SaveButton/>
+            </ActionForm>
+            <div class="chip-list">{chips}</div>
+            {move || remove_result.get().map(save_feedback)}
+            {move || set_result.get().map(save_feedback)}
+            <ActionForm action=actions.set_channel>
+                <input type="hidden" name="guild" value=set_gid/>
+                <ChannelSelect
+                    label="Channel"
+                    name="channel_id"
+                    selected=String::new()
+                    channels=channels.clone()
+                    kinds=TEXT_KINDS
+                />
+                <SelectField
+                    label="Persona"
+                    name="persona"
+                    selected=String::new()
+                    options=set_options
+                />
+                <div class="form-actions">
+                    <button type="submit" class="btn btn-ghost">"Set channel persona"</button>
+                </div>
+            </ActionForm>
+        </fieldset>
+    }
+}
+
+#[component]
+fn TestSection(
+    guild_id: String,
+    options: Vec<SelectOption>,
+    test: ServerAction<TestAiPersona>,
+) -> impl IntoView {
+    let result = test.value();
+    let selected = options.first().map(|o| o.value.clone()).unwrap_or_default();
+
+    view! {
+        <fieldset class="settings-section ai-editor">
+            <legend><Icon name="message"/>"Test chat"</legend>
+            <p class="page-lead">
+                "Send a saved persona one message and see how it answers. Tests "
+                "skip lookups and chat history, and nothing is posted to Discord."
+            </p>
+            <ActionForm action=test>
+                <input type="hidden" name="guild" value=guild_id/>
+                <SelectField
+                    label="Persona"
+                    name="persona"
+                    selected=selected
+                    options=options
+                />
+                <div class="setting-field">
+                    <label>"Message"</label>
+                    <textarea name="message" rows="3" maxlength="500" required></textarea>
+                </div>
+                <div class="form-actions">
+                    <button
+                        type="submit"
+                        class="btn btn-primary"
+                        prop:disabled=move || test.pending().get()
+                    >"Send"</button>
+                </div>
+            </ActionForm>
+            {move || test.pending().get().then(|| view! {
+                <p class="loading">"Waiting for a reply\u{2026}"</p>
+            })}
+            {move || result.get().map(|r| match r {
+                Ok(reply) => view! { <p class="ai-test-reply">{reply}</p> }.into_any(),
+                Err(e) => view! {
+                    <p class="error">"Test failed: " {e.to_string()}</p>
+                }.into_any(),
+            })}
+        </fieldset>
+    }
+}
@@ -0,0 +1,121 @@
+use leptos::form::ActionForm;
+use leptos::prelude::*;
+use leptos_meta::Title;
+
+use crate::dto::Tier;
+use crate::server::premium::{DeleteGiftCode, MintGiftCode, list_gift_codes};
+use crate::ui::components::icons::Icon;
+use crate::ui::components::layout::AppShell;
+use crate::ui::components::select::{SelectField, SelectOption};
+use crate::ui::components::settings::{SettingField, save_feedback};
+use crate::ui::pages::not_found::NotFound;
+
+#[component]
+pub(crate) fn GiftCodesPage() -> impl IntoView {
+    let mint = ServerAction::<MintGiftCode>::new();
+    let delete = ServerAction::<DeleteGiftCode>::new();
+    let codes = Resource::new(
+        move || (mint.version().get(), delete.version().get()),
+        |_| list_gift_codes(),
+    );
+
+    let tiers = [Tier::Pro, Tier::Ultra]
+        .into_iter()
+        .map(|tier| SelectOption {
+            value: tier.css_suffix().to_string(),
+            label: tier.label().to_string(),
+        })
+        .collect::<Vec<_>>();
+
+    view! {
+        <Title text="Gift codes - Zayden Dashboard"/>
+        <Suspense fallback=|| view! { <p class="loading">"Loading codes\u{2026}"</p> }>
+            {move || codes.get().map(|result| match result {
+                Err(_) => view! { <NotFound/> }.into_any(),
+                Ok(list) => view! {
+                    <AppShell>
+                        <div class="page">
+                            <div class="page-header">
+                                <div>
+                                    <h1>"Gift codes"</h1>
+                                    <p class="page-lead">
+                                        "Each redemption grants the redeemer's account the "
+                                        "tier for the code's duration."
+                                    </p>
+                                </div>
+                            </div>
+
+                            <fieldset class="settings-section">
+                                <legend><Icon name="plus"/>"Mint a code"</legend>
+                                {move || mint.value().get().map(save_feedback)}
+                                <ActionForm action=mint>
+                                    <SelectField
+                                        label="Tier"
+                                        name="tier"
+                                        selected="pro".to_string()
+                                        options=tiers.clone()
+                                    />
+                                    <SettingField
+                                        label="Duration (days)"
+                                        name="duration_days"
+                                        value="30".to_string()
+                                    />
+                                    <SettingField
+                                        label="Max uses"
+                                        name="max_uses"
+                                        value="1".to_string()
+                                    />
+                                    <SettingField
+                                        label="Redeemable for (days, blank = forever)"
+                                        name="valid_days"
+                                        value=String::new()
+                                    />
+                                    <div class="form-actions">
+                                        <button type="submit" class="btn btn-primary">
+                                            "Mint"
+                                        </button>
+                                    </div>
+                                </ActionForm>
+                            </fieldset>
+
+                            {move || delete.value().get().map(save_feedback)}
+                            {if list.is_empty() {
+                                view! { <p class="empty">"No codes minted yet."</p> }
+                                    .into_any()
+                            } else {
+                                view! {
+                                    <div class="slot-list">
+                                        {list.into_iter().map(|c| view! {
+                                            <div class="slot-row">
+                                                <code class="slot-name">{c.code.clone()}</code>
+                                                <span class=format!(
+                                                    "tier-badge tier-{}", c.tier.css_suffix(),
+                                                )>{c.tier.label()}</span>
+                                                <span class="slot-locked">
+                                                    {format!(
+                                                        "{} days \u{b7} {}/{} used",
+                                                        c.duration_days, c.uses, c.max_uses,
+                                                    )}
+                                                    {c.expires_at.map(|at| {
+                                                        format!(" \u{b7} until {at}")
+                                                    })}
+                                                </span>
+                                                <ActionForm action=delete>
+                                                    <input type="hidden" name="code" value=c.code/>
+                                                    <button type="submit" class="btn btn-ghost">
+                                                        "Delete"
+                                                    </button>
+                                                </ActionForm>
+                                            </div>
+                                        }).collect_view()}
+                                    </div>
+                                }
+                                .into_any()
+                            }}
+                        </div>
+                    </AppShell>
+                }.into_any(),
+            })}
+        </Suspense>
+    }
+}
@@ -4,18 +4,33 @@ use leptos_meta::Title;
 use leptos_router::hooks::use_params_map;
 use twilight_model::channel::ChannelType;
 
-use crate::dto::{ChannelInfo, GuildSettings, RoleInfo};
+use crate::dto::{
+    AutomodFilterInfo,
+    ChannelInfo,
+    GuildSettings,
+    HoneypotTrapInfo,
+    RoleInfo,
+};
 use crate::server::discord::{list_guild_channels, list_guild_roles};
 use crate::server::guild::{
+    AddAutomodFilter,
+    AddHoneypotTrap,
     AddSupportRole,
     CreateTempVoiceCreatorChannel,
+    RemoveAutomodFilter,
+    RemoveHoneypotTrap,
     RemoveSupportRole,
     SaveAiSettings,
+    SaveAppealsSettings,
+    SaveAutomodRules,
+    SaveAutomodSettings,
     SaveChannelSettings,
     SaveFamilySettings,
     SaveHoneypotSettings,
+    SaveHoneypotSharing,
     SaveLfgSettings,
     SaveMusicSettings,
+    SaveRaidSettings,
     SaveRoleSettings,
     SaveSupportSettings,
     SaveTempVoiceSettings,
@@ -24,7 +39,12 @@ use crate::server::guild::{
 };
 use crate::ui::components::icons::Icon;
 use crate::ui::components::layout::AppShell;
-use crate::ui::components::select::{ChannelSelect, RoleSelect};
+use crate::ui::components::select::{
+    ChannelSelect,
+    RoleSelect,
+    SelectField,
+    SelectOption,
+};
 use crate::ui::components::settings::{
     SaveButton,
     SettingField,
@@ -33,6 +53,85 @@ use crate::ui::components::settings::{
     save_feedback,
 };
 
+fn captcha_fail_options() -> Vec<SelectOption> {
+    [("log", "Log only"), ("kick", "Kick")]
+        .into_iter()
+        .map(|(value, label)| SelectOption {
+            value: value.to_string(),
+            label: label.to_string(),
+        })
+        .collect()
+}
+
+fn shared_action_options() -> Vec<SelectOption> {
+    [("flag", "Flag in the log channel"), ("ban", "Ban")]
+        .into_iter()
+        .map(|(value, label)| SelectOption {
+            value: value.to_string(),
+            label: label.to_string(),
+        })
+        .collect()
+}
+
+fn trap_kind_options() -> Vec<SelectOption> {
+    [("channel", "Posting in a channel"), ("role", "Taking a bait role")]
+        .into_iter()
+        .map(|(value, label)| SelectOption {
+            value: value.to_string(),
+            label: label.to_string(),
+        })
+        .collect()
+}
+
+fn trap_action_options() -> Vec<SelectOption> {
+    [
+        ("soft_ban", "Soft-ban"),
+        ("ban", "Ban"),
+        ("timeout", "Timeout"),
+        ("quarantine", "Quarantine role"),
+    ]
+    .into_iter()
+    .map(|(value, label)| SelectOption {
+        value: value.to_string(),
+        label: label.to_string(),
+    })
+    .collect()
+}
+
+fn automod_action_options() -> Vec<SelectOption> {
+    [
+        ("delete", "Delete"),
+        ("warn", "Delete and warn"),
+        ("timeout", "Delete and time out"),
+    ]
+    .into_iter()
+    .map(|(value, label)| SelectOption {
+        value: value.to_string(),
+        label: label.to_string(),
+    })
+    .collect()
+}
+
+fn raid_action_options() -> Vec<SelectOption> {
+    [("none", "Report only"), ("timeout", "Time out"), ("kick", "Kick")]
+        .into_iter()
+        .map(|(value, label)| SelectOption {
+            value: value.to_string(),
+            label: label.to_string(),
+        })
+        .collect()
+}
+
+fn filter_kind_options() -> Vec<SelectOption> {
+    [("false", "Word list"), ("true", "Regular expression")]
+        .into_iter()
+        .map(|(value, label)| SelectOption {
+            value: value.to_string(),
+            label: label.to_string(),
+        })
+        .collect()
+}
+
 const TEXT_KINDS: &[ChannelType] = &[
     ChannelType::GuildText,
     ChannelType::GuildAnnouncement,
@@ -51,6 +150,10 @@ pub(crate) fn GuildSettingsPage() -> impl IntoView {
     let create_creator = ServerAction::<CreateTempVoiceCreatorChannel>::new();
     let add_support_role = ServerAction::<AddSupportRole>::new();
     let remove_support_role = ServerAction::<RemoveSupportRole>::new();
+    let add_trap = ServerAction::<AddHoneypotTrap>::new();
+    let remove_trap = ServerAction::<RemoveHoneypotTrap>::new();
+    let add_filter = ServerAction::<AddAutomodFilter>::new();
+    let remove_filter = ServerAction::<RemoveAutomodFilter>::new();
 
     let data = Resource::new_blocking(
         move || {
@@ -59,6 +162,10 @@ pub(crate) fn GuildSettingsPage() -> impl IntoView {
                 create_creator.version().get(),
                 add_support_role.version().get(),
                 remove_support_role.version().get(),
+                add_trap.version().get(),
+                remove_trap.version().get(),
+                add_filter.version().get(),
+                remove_filter.version().get(),
             )
         },
         |(gid, ..)| async move {
@@ -82,6 +189,11 @@ pub(crate) fn GuildSettingsPage() -> impl IntoView {
     let save_family = ServerAction::<SaveFamilySettings>::new();
     let save_music = ServerAction::<SaveMusicSettings>::new();
     let save_honeypot = ServerAction::<SaveHoneypotSettings>::new();
+    let save_sharing = ServerAction::<SaveHoneypotSharing>::new();
+    let save_automod = ServerAction::<SaveAutomodSettings>::new();
+    let save_automod_rules = ServerAction::<SaveAutomodRules>::new();
+    let save_raid = ServerAction::<SaveRaidSettings>::new();
+    let save_appeals = ServerAction::<SaveAppealsSettings>::new();
     let save_ai = ServerAction::<SaveAiSettings>::new();
     let save_lfg = ServerAction::<SaveLfgSettings>::new();
 
@@ -138,6 +250,13 @@ pub(crate) fn GuildSettingsPage() -> impl IntoView {
                                                 channels=channels.clone()
                                                 kinds=TEXT_KINDS
                                             />
+                                            <ChannelSelect
+                                                label="Ticket Transcript Channel"
+                                                name="transcript_channel_id"
+                                                selected=sel(s.transcript_channel_id.as_deref())
+                                                channels=channels.clone()
+                                                kinds=TEXT_KINDS
+                                            />
                                             <ChannelSelect
                                                 label="Suggestions Channel"
                                                 name="suggestions_channel_id"
@@ -214,6 +333,7 @@ pub(crate) fn GuildSettingsPage() -> impl IntoView {
                                 // Roles
                                 {let r = save_roles.value();
                                 let roles = roles.clone();
+                                let channels = channels.clone();
                                 view! {
                                     <fieldset class="settings-section">
                                         <legend><Icon name="users"/>"Roles"</legend>
@@ -238,6 +358,49 @@ pub(crate) fn GuildSettingsPage() -> impl IntoView {
                                                 selected=sel(s.verified_role_id.as_deref())
                                                 roles=roles.clone()
                                             />
+                                            <ToggleField
+                                                label="Accept Rules to Verify"
+                                                name="require_rules"
+                                                value=s.require_rules
+                                            />
+                                            <ToggleField
+                                                label="Re-verify on Rules Change"
+                                                name="reverify_on_rules_change"
+                                                value=s.reverify_on_rules_change
+                                            />
+                                            <ToggleField
+                                                label="CAPTCHA Challenge"
+                                                name="captcha_enabled"
+                                                value=s.captcha_enabled
+                                            />
+                                            <SettingField
+                                                label="CAPTCHA Attempts"
+                                                name="captcha_max_attempts"
+                                                value=s.captcha_max_attempts.clone()
+                                            />
+                                            <SettingField
+                                                label="Lockout (minutes)"
+                                                name="captcha_lockout_minutes"
+                                                value=s.captcha_lockout_minutes.clone()
+                                            />
+                                            <SettingField
+                                                label="Minimum Account Age (days)"
+                                                name="captcha_min_account_age_days"
+                                                value=s.captcha_min_account_age_days.clone()
+                                            />
+                                            <SelectField
+                                                label="On Repeated Failure"
+                                                name="captcha_fail_action"
+                                                selected=s.captcha_fail_action.clone()
+                                                options=captcha_fail_options()
+                                            />
+                                            <ChannelSelect
+                                                label="Verification Log Channel"
+                                                name="captcha_log_channel_id"
+                                                selected=sel(s.captcha_log_channel_id.as_deref())
+                                                channels=channels.clone()
+                                                kinds=TEXT_KINDS
+                                            />
                                             <SaveButton/>
                                         </ActionForm>
                                     </fieldset>
@@ -376,7 +539,7 @@ pub(crate) fn GuildSettingsPage() -> impl IntoView {
                                                 label="Honeypot Channel"
                                                 name="channel_id"
                                                 selected=sel(s.honeypot_channel_id.as_deref())
-                                                channels=channels
+                                                channels=channels.clone()
                                                 kinds=TEXT_KINDS
                                             />
                                             <ToggleField
@@ -388,13 +551,20 @@ pub(crate) fn GuildSettingsPage() -> impl IntoView {
                                                 label="Exempt Role"
                                                 name="exempt_role_id"
                                                 selected=sel(s.honeypot_exempt_role_id.as_deref())
-                                                roles=roles
+                                                roles=roles.clone()
                                             />
                                             <SettingField
                                                 label="Purge Window (seconds)"
                                                 name="purge_seconds"
                                                 value=s.honeypot_purge_seconds
                                             />
+                                            <ChannelSelect
+                                                label="Log Channel"
+                                                name="log_channel_id"
+                                                selected=sel(s.honeypot_log_channel_id.as_deref())
+                                                channels=channels.clone()
+                                                kinds=TEXT_KINDS
+                                            />
                                             <SaveButton/>
                                         </ActionForm>
                                         <p class="page-lead">
@@ -413,7 +583,320 @@ pub(crate) fn GuildSettingsPage() -> impl IntoView {
                                         <p class="page-lead">
                                             "The server owner is always exempt. Keep the channel "
                                             "postable by @everyone - the trap only catches "
-                                            "spam bots that can actually reach it."
+                                            "spam bots that can actually reach it. Every hit, "
+                                            "from any trap, is reported in the log channel."
+                                        </p>
+                                        <HoneypotTrapField
+                                            guild_id=guild_id()
+                                            traps=s.honeypot_traps
+                                            channels=channels
+                                            roles=roles
+                                            add=add_trap
+                                            remove=remove_trap
+                                        />
+                                    </fieldset>
+                                }}
+
+                                // Shared threat list — honeypot hits across servers.
+                                {let r = save_sharing.value();
+                                view! {
+                                    <fieldset class="settings-section">
+                                        <legend><Icon name="shield"/>"Shared Threat List"</legend>
+                                        {move || r.get().map(save_feedback)}
+                                        <ActionForm action=save_sharing>
+                                            <input type="hidden" name="guild" value=guild_id()/>
+                                            <ToggleField
+                                                label="Share Honeypot Hits"
+                                                name="share_hits"
+                                                value=s.honeypot_share_hits
+                                            />
+                                            <SettingField
+                                                label="Act on Joins Seen In (servers)"
+                                                name="shared_threshold"
+                                                value=s.honeypot_shared_threshold
+                                            />
+                                            <SelectField
+                                                label="Action"
+                                                name="shared_action"
+                                                selected=s.honeypot_shared_action
+                                                options=shared_action_options()
+                                            />
+                                            <SaveButton/>
+                                        </ActionForm>
+                                        <p class="page-lead">
+                                            "Servers that share see each other's honeypot hits. "
+                                            "A member who tripped honeypots in at least this many "
+                                            "other sharing servers in the last 90 days is flagged "
+                                            "or banned as they join; 0 shares without acting. "
+                                            "Whitelist false positives on the Threat List page."
+                                        </p>
+                                    </fieldset>
+                                }}
+
+                                // Automod — message filters.
+                                {let r = save_automod.value();
+                                let rules_result = save_automod_rules.value();
+                                let roles = roles.clone();
+                                let channels = channels.clone();
+                                view! {
+                                    <fieldset class="settings-section">
+                                        <legend><Icon name="shield"/>"Automod"</legend>
+                                        {move || r.get().map(save_feedback)}
+                                        <ActionForm action=save_automod>
+                                            <input type="hidden" name="guild" value=guild_id()/>
+                                            <ToggleField
+                                                label="Automod"
+                                                name="enabled"
+                                                value=s.automod_enabled
+                                            />
+                                            <ToggleField
+                                                label="Exempt Admins"
+                                                name="exempt_admins"
+                                                value=s.automod_exempt_admins
+                                            />
+                                            <RoleSelect
+                                                label="Exempt Role"
+                                                name="exempt_role_id"
+                                                selected=sel(s.automod_exempt_role_id.as_deref())
+                                                roles=roles
+                                            />
+                                            <ChannelSelect
+                                                label="Log Channel"
+                                                name="log_channel_id"
+                                                selected=sel(s.automod_log_channel_id.as_deref())
+                                                channels=channels
+                                                kinds=TEXT_KINDS
+                                            />
+                                            <SettingField
+                                                label="Timeout Length (minutes)"
+                                                name="timeout_minutes"
+                                                value=s.automod_timeout_minutes
+                                            />
+                                            <SaveButton/>
+                                        </ActionForm>
+                                        <p class="page-lead">
+                                            "Every rule deletes the message it catches; warnings "
+                                            "also go on the member's record, and timeouts last "
+                                            "as long as set here. A member is warned or timed "
+                                            "out at most once a minute - the rest of a flood is "
+                                            "just deleted. The server owner is always exempt."
+                                        </p>
+                                        {move || rules_result.get().map(save_feedback)}
+                                        <ActionForm action=save_automod_rules>
+                                            <input type="hidden" name="guild" value=guild_id()/>
+                                            <SelectField
+                                                label="Message Rate"
+                                                name="rate_action"
+                                                selected=s.automod_rate_action
+                                                options=automod_action_options()
+                                            />
+                                            <SettingField
+                                                label="Max Messages"
+                                                name="rate_max"
+                                                value=s.automod_rate_max
+                                            />
+                                            <SettingField
+                                                label="Per (seconds)"
+                                                name="rate_seconds"
+                                                value=s.automod_rate_seconds
+                                            />
+                                            <SelectField
+                                                label="Duplicate Messages"
+                                                name="duplicates_action"
+                                                selected=s.automod_duplicates_action
+                                                options=automod_action_options()
+                                            />
+                                            <SettingField
+                                                label="Max Repeats"
+                                                name="duplicates_max"
+                                                value=s.automod_duplicates_max
+                                            />
+                                            <SettingField
+                                                label="Per (seconds)"
+                                                name="duplicates_seconds"
+                                                value=s.automod_duplicates_seconds
+                                            />
+                                            <SelectField
+                                                label="Mass Mentions"
+                                                name="mentions_action"
+                                                selected=s.automod_mentions_action
+                                                options=automod_action_options()
+                                            />
+                                            <SettingField
+                                                label="Max Mentions"
+                                                name="mentions_max"
+                                                value=s.automod_mentions_max
+                                            />
+                                            <SelectField
+                                                label="Invite Links"
+                                                name="invites_action"
+                                                selected=s.automod_invites_action
+                                                options=automod_action_options()
+                                            />
+                                            <SelectField
+                                                label="Scam Links"
+                                                name="scam_links_action"
+                                                selected=s.automod_scam_links_action
+                                                options=automod_action_options()
+                                            />
+                                            <SelectField
+                                                label="Excessive Caps"
+                                                name="caps_action"
+                                                selected=s.automod_caps_action
+                                                options=automod_action_options()
+                                            />
+                                            <SettingField
+                                                label="Max Capitals (%)"
+                                                name="caps_percent"
+                                                value=s.automod_caps_percent
+                                            />
+                                            <SettingField
+                                                label="Min Letters"
+                                                name="caps_min_length"
+                                                value=s.automod_caps_min_length
+                                            />
+                                            <SaveButton/>
+                                        </ActionForm>
+                                        <p class="page-lead">
+                                            "Leave a rule's action unset to turn it off. Windows "
+                                            "are capped at 60 seconds. Scam links are checked "
+                                            "against the list of known phishing domains Zayden "
+                                            "keeps up to date."
+                                        </p>
+                                        <AutomodFilterField
+                                            guild_id=guild_id()
+                                            filters=s.automod_filters
+                                            add=add_filter
+                                            remove=remove_filter
+                                        />
+                                    </fieldset>
+                                }}
+
+                                // Raid protection — join-rate lockdowns.
+                                {let r = save_raid.value();
+                                let channels = channels.clone();
+                                view! {
+                                    <fieldset class="settings-section">
+                                        <legend><Icon name="users"/>"Raid Protection"</legend>
+                                        {move || r.get().map(save_feedback)}
+                                        <ActionForm action=save_raid>
+                                            <input type="hidden" name="guild" value=guild_id()/>
+                                            <ToggleField
+                                                label="Raid Detection"
+                                                name="enabled"
+                                                value=s.raid_enabled
+                                            />
+                                            <SettingField
+                                                label="Join Threshold"
+                                                name="join_threshold"
+                                                value=s.raid_join_threshold
+                                            />
+                                            <SettingField
+                                                label="Within (seconds)"
+                                                name="join_window_seconds"
+                                                value=s.raid_join_window_seconds
+                                            />
+                                            <SettingField
+                                                label="Lockdown Length (minutes)"
+                                                name="lockdown_minutes"
+                                                value=s.raid_lockdown_minutes
+                                            />
+                                            <SettingField
+                                                label="Minimum Account Age (days)"
+                                                name="min_account_age_days"
+                                                value=s.raid_min_account_age_days
+                                            />
+                                            <ToggleField
+                                                label="Flag Default Avatars"
+                                                name="flag_default_avatar"
+                                                value=s.raid_flag_default_avatar
+                                            />
+                                            <ToggleField
+                                                label="Flag Similar Names"
+                                                name="flag_similar_names"
+                                                value=s.raid_flag_similar_names
+                                            />
+                                            <SelectField
+                                                label="Suspicious Joiners"
+                                                name="joiner_action"
+                                                selected=s.raid_joiner_action
+                                                options=raid_action_options()
+                                            />
+                                            <SettingField
+                                                label="Timeout Length (minutes)"
+                                                name="timeout_minutes"
+                                                value=s.raid_timeout_minutes
+                                            />
+                                            <ToggleField
+                                                label="Raise Verification Level"
+                                                name="raise_verification"
+                                                value=s.raid_raise_verification
+                                            />
+                                            <SettingField
+                                                label="Slowmode (seconds)"
+                                                name="slowmode_seconds"
+                                                value=s.raid_slowmode_seconds
+                                            />
+                                            <ChannelSelect
+                                                label="Alert Channel"
+                                                name="alert_channel_id"
+                                                selected=sel(s.raid_alert_channel_id.as_deref())
+                                                channels=channels
+                                                kinds=TEXT_KINDS
+                                            />
+                                            <SaveButton/>
+                                        </ActionForm>
+                                        <p class="page-lead">
+                                            "More joins than the threshold inside the window "
+                                            "locks the server down: the verify panel pauses, "
+                                            "text channels get the slowmode above and the "
+                                            "verification level goes to High. A lockdown length "
+                                            "of 0 holds it until someone runs /lockdown end."
+                                        </p>
+                                        <p class="page-lead">
+                                            "During a lockdown, joiners with a young account, "
+                                            "no avatar or a name like other recent joiners' are "
+                                            "handled as chosen above. Moderators can start and "
+                                            "end a lockdown by hand with /lockdown; everything "
+                                            "it changed is put back when it ends."
+                                        </p>
+                                    </fieldset>
+                                }}
+
+                                // Appeals — staff review of ban and mute appeals.
+                                {let r = save_appeals.value();
+                                let channels = channels.clone();
+                                view! {
+                                    <fieldset class="settings-section">
+                                        <legend><Icon name="shield"/>"Appeals"</legend>
+                                        {move || r.get().map(save_feedback)}
+                                        <ActionForm action=save_appeals>
+                                            <input type="hidden" name="guild" value=guild_id()/>
+                                            <ToggleField
+                                                label="Accept Appeals"
+                                                name="enabled"
+                                                value=s.appeals_enabled
+                                            />
+                                            <ChannelSelect
+                                                label="Appeals Channel"
+                                                name="channel_id"
+                                                selected=sel(s.appeals_channel_id.as_deref())
+                                                channels=channels
+                                                kinds=TEXT_KINDS
+                                            />
+                                            <SaveButton/>
+                                        </ActionForm>
+                                        <p class="page-lead">
+                                            "Members who are muted or banned get an Appeal "
+                                            "button on the DM telling them so. Their appeal is "
+                                            "posted to the channel above, where anyone who can "
+                                            "time out or ban members can approve or deny it."
+                                        </p>
+                                        <p class="page-lead">
+                                            "Approving lifts the mute or ban and notes it on the "
+                                            "case; either way the member is told by DM. Each "
+                                            "case can be appealed once, and appeals stay off "
+                                            "until a channel is picked."
                                         </p>
                                     </fieldset>
                                 }}
@@ -443,7 +926,7 @@ pub(crate) fn GuildSettingsPage() -> impl IntoView {
                                         </ActionForm>
                                         <p class="page-lead">
                                             "With AI responses on, Zayden replies in character "
-                                            "whenever someone mentions him. Leave the channel "
+                                            "when someone mentions him. Leave the channel "
                                             "unset to let him answer anywhere he can see, or "
                                             "pick one to keep him to a single room."
                                         </p>
@@ -453,6 +936,12 @@ pub(crate) fn GuildSettingsPage() -> impl IntoView {
                                             "toggle here is the same switch as the AI Chat card "
                                             "on the Modules page."
                                         </p>
+                                        <p class="page-lead">
+                                            "Other ways to make him answer, and custom "
+                                            "personas on Pro, are on the "
+                                            <a href=format!("/guild/{}/ai", guild_id())>"AI Chat"</a>
+                                            " page."
+                                        </p>
                                     </fieldset>
                                 }}
 
@@ -557,3 +1046,190 @@ fn SupportRoleField(
         </div>
     }
 }
+
+#[component]
+fn HoneypotTrapField(
+    guild_id: String,
+    traps: Vec<HoneypotTrapInfo>,
+    channels: Vec<ChannelInfo>,
+    roles: Vec<RoleInfo>,
+    add: ServerAction<AddHoneypotTrap>,
+    remove: ServerAction<RemoveHoneypotTrap>,
+) -> impl IntoView {
+    let add_result = add.value();
+    let remove_result = remove.value();
+
+    let chips = traps
+        .into_iter()
+        .map(|trap| {
+            let target = if trap.kind == "role" {
+                roles.iter().find(|r| r.id == trap.target_id).map_or_else(
+                    || format!("@unknown ({})", trap.target_id),
+                    |r| format!("@{}", r.name),
+                )
+            } else {
+                channels.iter().find(|c| c.id == trap.target_id).map_or_else(
+                    || format!("#unknown ({})", trap.target_id),
+                    |c| format!("#{}", c.name),
+                )
+            };
+            let action = match trap.action.as_str() {
+                "soft_ban" => format!("soft-ban, purge {}s", trap.purge_seconds),
+                "ban" => format!("ban, purge {}s", trap.purge_seconds),
+                "timeout" => format!("timeout {} min", trap.timeout_minutes),
+                _ => {
+                    let role = trap.quarantine_role_id.as_deref().unwrap_or_default();
+                    roles.iter().find(|r| r.id == role).map_or_else(
+                        || "quarantine".to_string(),
+                        |r| format!("quarantine as @{}", r.name),
+                    )
+                },
+            };
+            let gid = guild_id.clone();
+
+            view! {
+                <ActionForm action=remove attr:class="chip">
+                    <input type="hidden" name="guild" value=gid/>
+                    <input type="hidden" name="kind" value=trap.kind/>
+                    <input type="hidden" name="target_id" value=trap.target_id/>
+                    <span class="chip-label">{format!("{target} \u{2192} {action}")}</span>
+                    <button type="submit" class="chip-remove" title="Remove">
+                        <Icon name="x"/>
+                    </button>
+                </ActionForm>
+            }
+        })
+        .collect_view();
+
+    view! {
+        <div class="setting-field">
+            <label>"More Traps"</label>
+            <p class="page-lead">
+                "Up to 10 more trap channels, or bait roles - self-assignable roles "
+                "like \"Free Nitro\" that only spam accounts pick. Each trap has its "
+                "own action. Timeouts and quarantines delete the bait post or take "
+                "the bait role back."
+            </p>
+            <div class="chip-list">{chips}</div>
+            {move || remove_result.get().map(save_feedback)}
+            {move || add_result.get().map(save_feedback)}
+            <ActionForm action=add>
+                <input type="hidden" name="guild" value=guild_id/>
+                <SelectField
+                    label="Trap"
+                    name="kind"
+                    selected="channel".to_string()
+                    options=trap_kind_options()
+                />
+                <ChannelSelect
+                    label="Trap Channel"
+                    name="channel_id"
+                    selected=String::new()
+                    channels=channels
+                    kinds=TEXT_KINDS
+                />
+                <RoleSelect
+                    label="Bait Role"
+                    name="role_id"
+                    selected=String::new()
+                    roles=roles.clone()
+                />
+                <SelectField
+                    label="Action"
+                    name="action"
+                    selected="soft_ban".to_string()
+                    options=trap_action_options()
+                />
+                <SettingField
+                    label="Purge Window (seconds)"
+                    name="purge_seconds"
+                    value="86400".to_string()
+                />
+                <SettingField
+                    label="Timeout (minutes)"
+                    name="timeout_minutes"
+                    value="1440".to_string()
+                />
+                <RoleSelect
+                    label="Quarantine Role"
+                    name="quarantine_role_id"
+                    selected=String::new()
+                    roles=roles
+                />
+                <button type="submit" class="btn btn-ghost">"Add trap"</button>
+            </ActionForm>
+        </div>
+    }
+}
+
+#[component]
+fn AutomodFilterField(
+    guild_id: String,
+    filters: Vec<AutomodFilterInfo>,
+    add: ServerAction<AddAutomodFilter>,
+    remove: ServerAction<RemoveAutomodFilter>,
+) -> impl IntoView {
+    let add_result = add.value();
+    let remove_result = remove.value();
+
+    let chips = filters
+        .into_iter()
+        .map(|filter| {
+            let kind = if filter.regex { "regex" } else { "words" };
+            let action = match filter.action.as_str() {
+                "warn" => "delete and warn",
+                "timeout" => "delete and time out",
+                _ => "delete",
+            };
+            let label = format!("{kind}: {} \u{2192} {action}", filter.pattern);
+            let gid = guild_id.clone();
+
+            view! {
+                <ActionForm action=remove attr:class="chip">
+                    <input type="hidden" name="guild" value=gid/>
+                    <input type="hidden" name="pattern" value=filter.pattern/>
+                    <span class="chip-label">{label}</span>
+                    <button type="submit" class="chip-remove" title="Remove">
+                        <Icon name="x"/>
+                    </button>
+                </ActionForm>
+            }
+        })
+        .collect_view();
+
+    view! {
+        <div class="setting-field">
+            <label>"Custom Filters"</label>
+            <p class="page-lead">
+                "Up to 20 word lists or regular expressions, each with its own "
+                "action. Word lists are comma-separated and match whole words or "
+                "phrases; both ignore case."
+            </p>
+            <div class="chip-list">{chips}</div>
+            {move || remove_result.get().map(save_feedback)}
+            {move || add_result.get().map(save_feedback)}
+            <ActionForm action=add>
+                <input type="hidden" name="guild" value=guild_id/>
+                <SelectField
+                    label="Filter"
+                    name="regex"
+                    selected="false".to_string()
+                    options=filter_kind_options()
+                />
+                <SettingField
+                    label="Words or Pattern"
+                    name="pattern"
+                    value=String::new()
+                    pattern=".*"
+                />
+                <SelectField
+                    label="Action"
+                    name="action"
+                    selected="delete".to_string()
+                    options=automod_action_options()
+                />
+                <button type="submit" class="btn btn-ghost">"Add filter"</button>
+            </ActionForm>
+        </div>
+    }
+}
@@ -1,11 +1,20 @@
+pub mod activity;
+pub mod admin;
+pub mod ai;
+pub mod gift_codes;
 pub mod greetings;
 pub mod guild_settings;
 pub mod guilds;
 pub mod landing;
 pub mod levels;
 pub mod login;
+pub mod moderation;
 pub mod modules;
 pub mod not_found;
 pub mod palworld_save;
+pub mod premium;
 pub mod reaction_roles;
+pub mod rules;
+pub mod suggestions;
+pub mod threats;
 pub mod upgrade;
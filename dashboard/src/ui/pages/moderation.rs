@@ -0,0 +1,343 @@
+use std::fmt::Write as _;
+
+use leptos::form::ActionForm;
+use leptos::prelude::*;
+use leptos_meta::Title;
+use leptos_router::components::A;
+use leptos_router::hooks::use_params_map;
+
+use crate::dto::{INFRACTION_KINDS, InfractionInfo};
+use crate::server::moderation::{
+    AddInfraction,
+    EditInfraction,
+    ExportInfractionsCsv,
+    ExpungeInfraction,
+    get_user_infractions,
+    search_infractions,
+};
+use crate::ui::components::icons::Icon;
+use crate::ui::components::layout::AppShell;
+use crate::ui::components::select::{SelectField, SelectOption};
+use crate::ui::components::settings::{SettingField, save_feedback};
+
+const PAGE_SIZE: usize = 25;
+
+/// `csv` as a `data:` URL, so the export downloads without a second request.
+fn csv_data_url(csv: &str) -> String {
+    let mut url = String::from("data:text/csv;charset=utf-8,");
+    for byte in csv.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-_.~".contains(&byte) {
+            url.push(char::from(byte));
+        } else {
+            let _ = write!(url, "%{byte:02X}");
+        }
+    }
+    url
+}
+
+fn kind_options() -> Vec<SelectOption> {
+    INFRACTION_KINDS
+        .into_iter()
+        .map(|kind| SelectOption {
+            value: kind.to_string(),
+            label: kind.to_string(),
+        })
+        .collect()
+}
+
+#[component]
+pub(crate) fn ModerationPage() -> impl IntoView {
+    let params = use_params_map();
+    let guild_id = move || params.with(|p| p.get("id").unwrap_or_default());
+
+    let (query, set_query) = signal(String::new());
+    let (kind, set_kind) = signal(String::new());
+    let (include_expunged, set_include_expunged) = signal(false);
+    let (page, set_page) = signal(1i32);
+
+    let add = ServerAction::<AddInfraction>::new();
+    let edit = ServerAction::<EditInfraction>::new();
+    let expunge = ServerAction::<ExpungeInfraction>::new();
+    let export = ServerAction::<ExportInfractionsCsv>::new();
+
+    let infractions = Resource::new(
+        move || {
+            (
+                guild_id(),
+                query.get(),
+                kind.get(),
+                include_expunged.get(),
+                page.get(),
+                (add.version().get(), edit.version().get(), expunge.version().get()),
+            )
+        },
+        |(gid, q, k, expunged, p, _)| search_infractions(gid, q, k, expunged, p),
+    );
+
+    view! {
+        <Title text="Moderation - Zayden Dashboard"/>
+        <AppShell>
+            <div class="page">
+                <div class="page-header">
+                    <div>
+                        <h1>"Moderation"</h1>
+                        <p class="page-lead">
+                            "Every infraction on record in this server. Expunged infractions "
+                            "stay listed but no longer count toward escalation."
+                        </p>
+                    </div>
+                </div>
+
+                <div class="mod-filters">
+                    <input
+                        type="search"
+                        placeholder="Search user, ID, moderator or reason"
+                        on:change=move |ev| { set_query.set(event_target_value(&ev)); set_page.set(1); }
+                    />
+                    <select on:change=move |ev| { set_kind.set(event_target_value(&ev)); set_page.set(1); }>
+                        <option value="">"All types"</option>
+                        {INFRACTION_KINDS.into_iter().map(|k| view! {
+                            <option value=k>{k}</option>
+                        }).collect_view()}
+                    </select>
+                    <label class="mod-toggle">
+                        <input
+                            type="checkbox"
+                            on:change=move |ev| { set_include_expunged.set(event_target_checked(&ev)); set_page.set(1); }
+                        />
+                        "Show expunged"
+                    </label>
+                    <button
+                        type="button"
+                        class="btn btn-secondary"
+                        on:click=move |_| {
+                            export.dispatch(ExportInfractionsCsv {
+                                guild: guild_id(),
+                                query: query.get_untracked(),
+                                kind: kind.get_untracked(),
+                                include_expunged: include_expunged.get_untracked(),
+                            });
+                        }
+                    >"Export CSV"</button>
+                    {move || export.value().get().map(|result| match result {
+                        Ok(csv) => view! {
+                            <a class="btn btn-primary" href=csv_data_url(&csv) download="infractions.csv">
+                                "Download CSV"
+                            </a>
+                        }.into_any(),
+                        Err(e) => view! { <p class="error">{e.to_string()}</p> }.into_any(),
+                    })}
+                </div>
+
+                <fieldset class="settings-section">
+                    <legend><Icon name="plus"/>"Record an infraction"</legend>
+                    <p class="page-lead">
+                        "Adds to the member's record only; nothing is sent to them and no "
+                        "timeout or ban is applied."
+                    </p>
+                    {move || add.value().get().map(save_feedback)}
+                    <ActionForm action=add>
+                        <input type="hidden" name="guild" value=guild_id/>
+                        <SettingField label="User ID" name="user_id" value=String::new()/>
+                        <SelectField
+                            label="Type"
+                            name="kind"
+                            selected="Warn".to_string()
+                            options=kind_options()
+                        />
+                        <SettingField label="Points" name="points" value="1".to_string()/>
+                        <SettingField label="Reason" name="reason" value=String::new() pattern=".*"/>
+                        <div class="form-actions">
+                            <button type="submit" class="btn btn-primary">"Record"</button>
+                        </div>
+                    </ActionForm>
+                </fieldset>
+
+                {move || edit.value().get().map(save_feedback)}
+                {move || expunge.value().get().map(save_feedback)}
+                <Suspense fallback=|| view! {
+                    <p class="loading">"Loading infractions\u{2026}"</p>
+                }>
+                    {move || infractions.get().map(|result| match result {
+                        Err(e) => view! {
+                            <p class="error">"Failed to load infractions: " {e.to_string()}</p>
+                        }.into_any(),
+                        Ok(rows) if rows.is_empty() => view! {
+                            <div class="empty">
+                                {if page.get() > 1 {
+                                    "No more infractions on this page."
+                                } else {
+                                    "No infractions match."
+                                }}
+                            </div>
+                        }.into_any(),
+                        Ok(rows) => {
+                            let has_next = rows.len() == PAGE_SIZE;
+                            let gid = guild_id();
+                            view! {
+                                <div class="mod-list">
+                                    {rows.into_iter().map(|row| view! {
+                                        <InfractionRow
+                                            guild=gid.clone()
+                                            row=row
+                                            edit=edit
+                                            expunge=expunge
+                                            show_user=true
+                                        />
+                                    }).collect_view()}
+                                </div>
+                                <div class="pager">
+                                    <button
+                                        type="button"
+                                        class="btn btn-secondary"
+                                        prop:disabled=move || page.get() <= 1
+                                        on:click=move |_| set_page.update(|p| *p = (*p - 1).max(1))
+                                    >"Previous"</button>
+                                    <span class="pager-page">"Page " {move || page.get()}</span>
+                                    <button
+                                        type="button"
+                                        class="btn btn-secondary"
+                                        prop:disabled=!has_next
+                                        on:click=move |_| set_page.update(|p| *p += 1)
+                                    >"Next"</button>
+                                </div>
+                            }.into_any()
+                        },
+                    })}
+                </Suspense>
+            </div>
+        </AppShell>
+    }
+}
+
+#[component]
+pub(crate) fn UserInfractionsPage() -> impl IntoView {
+    let params = use_params_map();
+    let guild_id = move || params.with(|p| p.get("id").unwrap_or_default());
+    let user_id = move || params.with(|p| p.get("user").unwrap_or_default());
+
+    let edit = ServerAction::<EditInfraction>::new();
+    let expunge = ServerAction::<ExpungeInfraction>::new();
+
+    let timeline = Resource::new(
+        move || {
+            (guild_id(), user_id(), edit.version().get(), expunge.version().get())
+        },
+        |(gid, uid, ..)| get_user_infractions(gid, uid),
+    );
+
+    view! {
+        <Title text="Member history - Zayden Dashboard"/>
+        <AppShell>
+            <div class="page">
+                <Suspense fallback=|| view! {
+                    <p class="loading">"Loading history\u{2026}"</p>
+                }>
+                    {move || timeline.get().map(|result| match result {
+                        Err(e) => view! {
+                            <p class="error">"Failed to load history: " {e.to_string()}</p>
+                        }.into_any(),
+                        Ok(timeline) => {
+                            let gid = guild_id();
+                            view! {
+                                <div class="page-header">
+                                    <div>
+                                        <h1>{timeline.username}</h1>
+                                        <p class="page-lead">
+                                            {format!(
+                                                "User {} \u{b7} {} active point(s) in the last six months",
+                                                timeline.user_id, timeline.active_points,
+                                            )}
+                                        </p>
+                                    </div>
+                                    <A href=format!("/guild/{gid}/moderation") attr:class="btn btn-ghost">
+                                        "All infractions"
+                                    </A>
+                                </div>
+                                {move || edit.value().get().map(save_feedback)}
+                                {move || expunge.value().get().map(save_feedback)}
+                                {if timeline.infractions.is_empty() {
+                                    view! { <p class="empty">"No infractions on record."</p> }.into_any()
+                                } else {
+                                    view! {
+                                        <div class="mod-list mod-timeline">
+                                            {timeline.infractions.into_iter().map(|row| view! {
+                                                <InfractionRow
+                                                    guild=gid.clone()
+                                                    row=row
+                                                    edit=edit
+                                                    expunge=expunge
+                                                    show_user=false
+                                                />
+                                            }).collect_view()}
+                                        </div>
+                                    }.into_any()
+                                }}
+                            }.into_any()
+                        },
+                    })}
+                </Suspense>
+            </div>
+        </AppShell>
+    }
+}
+
+#[component]
+fn InfractionRow(
+    guild: String,
+    row: InfractionInfo,
+    edit: ServerAction<EditInfraction>,
+    expunge: ServerAction<ExpungeInfraction>,
+    show_user: bool,
+) -> impl IntoView {
+    let user_href = format!("/guild/{guild}/moderation/user/{}", row.user_id);
+    let class = if row.expunged { "mod-row mod-expunged" } else { "mod-row" };
+    let id = row.id.to_string();
+
+    view! {
+        <div class=class>
+            <div class="mod-row-head">
+                <span class="mod-case">{format!("#{}", row.id)}</span>
+                <span class=format!("mod-kind mod-kind-{}", row.kind.to_lowercase())>
+                    {row.kind.clone()}
+                </span>
+                {show_user.then(|| view! {
+                    <A href=user_href attr:class="mod-user">{row.username.clone()}</A>
+                })}
+                <span class="mod-meta">
+                    {format!(
+                        "{} pt \u{b7} by {} \u{b7} {}",
+                        row.points, row.moderator_username, row.created_at,
+                    )}
+                </span>
+            </div>
+            <p class="mod-reason">{row.reason.clone()}</p>
+            {row.expunge_reason.clone().map(|reason| view! {
+                <p class="mod-expunge-reason">"Expunged: " {reason}</p>
+            })}
+            {(!row.expunged).then(|| view! {
+                <details class="mod-actions">
+                    <summary>"Edit or expunge"</summary>
+                    <ActionForm action=edit attr:class="mod-inline-form">
+                        <input type="hidden" name="guild" value=guild.clone()/>
+                        <input type="hidden" name="id" value=id.clone()/>
+                        <select name="kind">
+                            {INFRACTION_KINDS.into_iter().map(|k| view! {
+                                <option value=k selected=k == row.kind>{k}</option>
+                            }).collect_view()}
+                        </select>
+                        <input type="text" name="points" value=row.points.to_string() pattern="[0-9]*" size="3"/>
+                        <input type="text" name="reason" value=row.reason.clone() maxlength="255"/>
+                        <button type="submit" class="btn btn-secondary">"Save"</button>
+                    </ActionForm>
+                    <ActionForm action=expunge attr:class="mod-inline-form">
+                        <input type="hidden" name="guild" value=guild.clone()/>
+                        <input type="hidden" name="id" value=id.clone()/>
+                        <input type="text" name="reason" placeholder="Why it is being expunged" maxlength="255"/>
+                        <button type="submit" class="btn btn-ghost">"Expunge"</button>
+                    </ActionForm>
+                </details>
+            })}
+        </div>
+    }
+}
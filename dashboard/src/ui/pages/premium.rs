@@ -0,0 +1,124 @@
+use leptos::form::ActionForm;
+use leptos::prelude::*;
+use leptos_meta::Title;
+
+use crate::dto::SlotGuildInfo;
+use crate::server::premium::{
+    AssignPremiumSlot,
+    UnassignPremiumSlot,
+    get_premium_slots,
+};
+use crate::ui::components::layout::AppShell;
+use crate::ui::components::settings::save_feedback;
+
+#[component]
+pub(crate) fn PremiumSlotsPage() -> impl IntoView {
+    let assign = ServerAction::<AssignPremiumSlot>::new();
+    let unassign = ServerAction::<UnassignPremiumSlot>::new();
+    let slots = Resource::new(
+        move || (assign.version().get(), unassign.version().get()),
+        |_| get_premium_slots(),
+    );
+
+    view! {
+        <Title text="Premium servers - Zayden Dashboard"/>
+        <AppShell>
+            <div class="page">
+                <div class="page-header">
+                    <div>
+                        <h1>"Premium servers"</h1>
+                        <p class="page-lead">
+                            "Apply your plan to servers you manage. A slot stays on a "
+                            "server for 7 days before it can be moved."
+                        </p>
+                    </div>
+                </div>
+
+                {move || assign.value().get().map(save_feedback)}
+                {move || unassign.value().get().map(save_feedback)}
+
+                <Suspense fallback=|| view! { <p class="loading">"Loading slots\u{2026}"</p> }>
+                    {move || slots.get().map(|result| match result {
+                        Err(e) => view! {
+                            <p class="error">"Failed to load slots: " {e.to_string()}</p>
+                        }.into_any(),
+                        Ok(view) if view.limit == 0 => view! {
+                            <div class="card">
+                                <p class="page-lead">
+                                    "Your plan has no premium server slots. Pro includes "
+                                    "one and Ultra three."
+                                </p>
+                                <a href="/upgrade" class="btn btn-primary">"See plans"</a>
+                            </div>
+                        }.into_any(),
+                        Ok(view) => {
+                            let full = view.used >= view.limit;
+                            view! {
+                                <p class="slot-usage">
+                                    <span class=format!(
+                                        "tier-badge tier-{}", view.tier.css_suffix(),
+                                    )>{view.tier.label()}</span>
+                                    {format!(
+                                        " {} of {} slots in use", view.used, view.limit,
+                                    )}
+                                </p>
+                                <div class="slot-list">
+                                    {view.guilds.into_iter().map(|g| view! {
+                                        <SlotRow guild=g full assign unassign/>
+                                    }).collect_view()}
+                                </div>
+                            }.into_any()
+                        },
+                    })}
+                </Suspense>
+            </div>
+        </AppShell>
+    }
+}
+
+#[component]
+fn SlotRow(
+    guild: SlotGuildInfo,
+    full: bool,
+    assign: ServerAction<AssignPremiumSlot>,
+    unassign: ServerAction<UnassignPremiumSlot>,
+) -> impl IntoView {
+    let icon_url = guild.icon.map(|hash| {
+        format!("https://cdn.discordapp.com/icons/{}/{}.png?size=64", guild.id, hash)
+    });
+    let initial = guild.name.chars().next().unwrap_or('#').to_string();
+
+    let action = match (guild.assigned, guild.locked_until) {
+        (true, Some(until)) => view! {
+            <span class="slot-locked">{format!("Movable from {until}")}</span>
+        }
+        .into_any(),
+        (true, None) => view! {
+            <ActionForm action=unassign>
+                <input type="hidden" name="guild" value=guild.id.clone()/>
+                <button type="submit" class="btn btn-ghost">"Remove slot"</button>
+            </ActionForm>
+        }
+        .into_any(),
+        (false, _) => view! {
+            <ActionForm action=assign>
+                <input type="hidden" name="guild" value=guild.id.clone()/>
+                <button type="submit" class="btn btn-primary" disabled=full>
+                    "Use a slot"
+                </button>
+            </ActionForm>
+        }
+        .into_any(),
+    };
+
+    view! {
+        <div class="slot-row" class:slot-assigned=guild.assigned>
+            {icon_url.map_or_else(
+                || view! { <span class="guild-icon placeholder">{initial}</span> }.into_any(),
+                |url| view! { <img src=url alt="" class="guild-icon"/> }.into_any(),
+            )}
+            <span class="slot-name">{guild.name}</span>
+            {action}
+        </div>
+    }
+}
@@ -4,21 +4,102 @@ use leptos_meta::Title;
 use leptos_router::hooks::use_params_map;
 use twilight_model::channel::ChannelType;
 
-use crate::dto::{ChannelInfo, ReactionRoleInfo, RoleInfo};
+use crate::dto::{ChannelInfo, ReactionRoleInfo, RoleInfo, RolePanelInfo};
 use crate::server::discord::{list_guild_channels, list_guild_roles};
 use crate::server::reaction_roles::{
     AddReactionRole,
     RemoveReactionRole,
     list_reaction_roles,
 };
+use crate::server::role_panels::{
+    AddPanelRole,
+    CreateRolePanel,
+    DeleteRolePanel,
+    RemovePanelRole,
+    TogglePanelRequirement,
+    UpdateRolePanel,
+    list_role_panels,
+};
 use crate::ui::components::icons::Icon;
 use crate::ui::components::layout::AppShell;
-use crate::ui::components::select::{ChannelSelect, RoleSelect};
-use crate::ui::components::settings::{SettingField, save_feedback};
+use crate::ui::components::select::{
+    ChannelSelect,
+    RoleSelect,
+    SelectField,
+    SelectOption,
+};
+use crate::ui::components::settings::{SaveButton, SettingField, save_feedback};
 
 const TEXT_KINDS: &[ChannelType] =
     &[ChannelType::GuildText, ChannelType::GuildAnnouncement];
 
+const ANY_TEXT: &str = ".*";
+
+const PANEL_KINDS: &[(&str, &str)] =
+    &[("buttons", "Buttons"), ("select", "Select menu")];
+
+const PANEL_MODES: &[(&str, &str)] = &[
+    ("toggle", "Toggle"),
+    ("add_only", "Add only"),
+    ("remove_only", "Remove only"),
+    ("unique", "Unique"),
+    ("verify_once", "Verify once"),
+];
+
+fn options(choices: &[(&str, &str)]) -> Vec<SelectOption> {
+    choices
+        .iter()
+        .map(|(value, label)| SelectOption {
+            value: (*value).to_string(),
+            label: (*label).to_string(),
+        })
+        .collect()
+}
+
+fn choice_label(choices: &[(&str, &'static str)], value: &str) -> &'static str {
+    choices.iter().find(|(v, _)| *v == value).map_or("Unknown", |(_, label)| label)
+}
+
+fn role_name(roles: &[RoleInfo], id: &str) -> String {
+    roles
+        .iter()
+        .find(|r| r.id == id)
+        .map_or_else(|| format!("@unknown ({id})"), |r| format!("@{}", r.name))
+}
+
+/// Every role panel form, shared so one version bump reloads the page.
+#[derive(Clone, Copy)]
+struct PanelActions {
+    create: ServerAction<CreateRolePanel>,
+    update: ServerAction<UpdateRolePanel>,
+    delete: ServerAction<DeleteRolePanel>,
+    add_role: ServerAction<AddPanelRole>,
+    remove_role: ServerAction<RemovePanelRole>,
+    require: ServerAction<TogglePanelRequirement>,
+}
+
+impl PanelActions {
+    fn new() -> Self {
+        Self {
+            create: ServerAction::new(),
+            update: ServerAction::new(),
+            delete: ServerAction::new(),
+            add_role: ServerAction::new(),
+            remove_role: ServerAction::new(),
+            require: ServerAction::new(),
+        }
+    }
+
+    fn version(self) -> usize {
+        self.create.version().get()
+            + self.update.version().get()
+            + self.delete.version().get()
+            + self.add_role.version().get()
+            + self.remove_role.version().get()
+            + self.require.version().get()
+    }
+}
+
 fn custom_emoji_id(emoji: &str) -> Option<&str> {
     let inner = emoji.strip_prefix('<')?.strip_suffix('>')?;
     let id = inner.rsplit(':').next()?;
@@ -42,18 +123,32 @@ pub(crate) fn ReactionRolesPage() -> impl IntoView {
 
     let add = ServerAction::<AddReactionRole>::new();
     let remove = ServerAction::<RemoveReactionRole>::new();
+    let panel_actions = PanelActions::new();
 
     let data = Resource::new_blocking(
-        move || (guild_id(), add.version().get(), remove.version().get()),
+        move || {
+            (
+                guild_id(),
+                add.version().get(),
+                remove.version().get(),
+                panel_actions.version(),
+            )
+        },
         |(gid, ..)| async move {
             let maps = list_reaction_roles(gid.clone()).await?;
+            let panels = list_role_panels(gid.clone()).await?;
             let channels =
                 list_guild_channels(gid.clone()).await.unwrap_or_default();
             let roles = list_guild_roles(gid).await.unwrap_or_default();
             Ok::<
-                (Vec<ReactionRoleInfo>, Vec<ChannelInfo>, Vec<RoleInfo>),
+                (
+                    Vec<ReactionRoleInfo>,
+                    Vec<RolePanelInfo>,
+                    Vec<ChannelInfo>,
+                    Vec<RoleInfo>,
+                ),
                 ServerFnError,
-            >((maps, channels, roles))
+            >((maps, panels, channels, roles))
         },
     );
 
@@ -81,10 +176,12 @@ pub(crate) fn ReactionRolesPage() -> impl IntoView {
                         Err(e) => view! {
                             <p class="error">"Failed to load reaction roles: " {e.to_string()}</p>
                         }.into_any(),
-                        Ok((maps, channels, roles)) => {
+                        Ok((maps, panels, channels, roles)) => {
                             let gid = guild_id();
                             let form_channels = channels.clone();
                             let form_roles = roles.clone();
+                            let panel_channels = channels.clone();
+                            let panel_roles = roles.clone();
                             view! {
                                 {move || remove_result.get().map(save_feedback)}
                                 <MappingTable
@@ -139,6 +236,14 @@ pub(crate) fn ReactionRolesPage() -> impl IntoView {
                                         "- several emoji can share one message."
                                     </p>
                                 </fieldset>
+
+                                <RolePanels
+                                    guild_id=gid.clone()
+                                    panels=panels
+                                    channels=panel_channels
+                                    roles=panel_roles
+                                    actions=panel_actions
+                                />
                             }.into_any()
                         },
                     })}
@@ -221,3 +326,292 @@ fn MappingTable(
     }
     .into_any()
 }
+
+#[component]
+fn RolePanels(
+    guild_id: String,
+    panels: Vec<RolePanelInfo>,
+    channels: Vec<ChannelInfo>,
+    roles: Vec<RoleInfo>,
+    actions: PanelActions,
+) -> impl IntoView {
+    let create_result = actions.create.value();
+    let update_result = actions.update.value();
+    let delete_result = actions.delete.value();
+    let add_result = actions.add_role.value();
+    let remove_result = actions.remove_role.value();
+    let require_result = actions.require.value();
+
+    let cards = if panels.is_empty() {
+        view! {
+            <div class="empty">
+                "No role panels yet - create one below and Zayden posts it with a "
+                "button or menu option for every role you add."
+            </div>
+        }
+        .into_any()
+    } else {
+        panels
+            .into_iter()
+            .map(|panel| {
+                view! {
+                    <PanelCard
+                        guild_id=guild_id.clone()
+                        panel=panel
+                        channels=channels.clone()
+                        roles=roles.clone()
+                        actions=actions
+                    />
+                }
+            })
+            .collect_view()
+            .into_any()
+    };
+
+    view! {
+        <div class="page-header">
+            <div>
+                <h2>"Role Panels"</h2>
+                <p class="page-lead">
+                    "Messages with a button or select-menu option per role. Changes "
+                    "made here show up on the panel within a minute."
+                </p>
+            </div>
+        </div>
+        {move || update_result.get().map(save_feedback)}
+        {move || delete_result.get().map(save_feedback)}
+        {move || add_result.get().map(save_feedback)}
+        {move || remove_result.get().map(save_feedback)}
+        {move || require_result.get().map(save_feedback)}
+        {cards}
+
+        <fieldset class="settings-section">
+            <legend><Icon name="plus"/>"Create a panel"</legend>
+            {move || create_result.get().map(save_feedback)}
+            <ActionForm action=actions.create>
+                <input type="hidden" name="guild" value=guild_id.clone()/>
+                <ChannelSelect
+                    label="Channel"
+                    name="channel_id"
+                    selected=String::new()
+                    channels=channels
+                    kinds=TEXT_KINDS
+                />
+                <SettingField
+                    label="Title"
+                    name="title"
+                    value=String::new()
+                    pattern=ANY_TEXT
+                />
+                <SettingField
+                    label="Description"
+                    name="description"
+                    value=String::new()
+                    pattern=ANY_TEXT
+                />
+                <SelectField
+                    label="Kind"
+                    name="kind"
+                    selected="buttons".to_string()
+                    options=options(PANEL_KINDS)
+                />
+                <SelectField
+                    label="Mode"
+                    name="mode"
+                    selected="toggle".to_string()
+                    options=options(PANEL_MODES)
+                />
+                <SettingField
+                    label="Take roles back after (minutes, blank keeps them)"
+                    name="duration_minutes"
+                    value=String::new()
+                />
+                <div class="form-actions">
+                    <button type="submit" class="btn btn-primary">"Create panel"</button>
+                </div>
+            </ActionForm>
+        </fieldset>
+    }
+}
+
+#[component]
+fn PanelCard(
+    guild_id: String,
+    panel: RolePanelInfo,
+    channels: Vec<ChannelInfo>,
+    roles: Vec<RoleInfo>,
+    actions: PanelActions,
+) -> impl IntoView {
+    let channel = channels.iter().find(|c| c.id == panel.channel_id).map_or_else(
+        || format!("#unknown ({})", panel.channel_id),
+        |c| format!("#{}", c.name),
+    );
+    let summary = format!(
+        "{} \u{b7} {} \u{b7} {channel}",
+        choice_label(PANEL_KINDS, &panel.kind),
+        choice_label(PANEL_MODES, &panel.mode),
+    );
+    let link = panel.message_id.as_ref().map(|message| {
+        format!(
+            "https://discord.com/channels/{guild_id}/{}/{message}",
+            panel.channel_id
+        )
+    });
+    let id = panel.id;
+
+    let entries = panel
+        .entries
+        .iter()
+        .map(|entry| {
+            let gid = guild_id.clone();
+            let role = role_name(&roles, &entry.role_id);
+            view! {
+                <div class="rp-entry">
+                    <span class="rr-cell">
+                        {entry.emoji.as_deref().map(emoji_view)}
+                    </span>
+                    <span class="rp-label">{entry.label.clone()}</span>
+                    <span class="rr-role">{role}</span>
+                    <ActionForm action=actions.remove_role attr:class="rr-remove">
+                        <input type="hidden" name="guild" value=gid/>
+                        <input type="hidden" name="panel_id" value=id/>
+                        <input type="hidden" name="role_id" value=entry.role_id.clone()/>
+                        <button type="submit" class="btn btn-ghost">"Remove"</button>
+                    </ActionForm>
+                </div>
+            }
+        })
+        .collect_view();
+
+    let requirements = panel
+        .requirements
+        .iter()
+        .map(|req| {
+            let gid = guild_id.clone();
+            let text = format!(
+                "{} {}",
+                if req.forbidden { "Forbidden:" } else { "Requires:" },
+                role_name(&roles, &req.role_id)
+            );
+            view! {
+                <div class="rp-requirement">
+                    <span>{text}</span>
+                    <ActionForm action=actions.require attr:class="rr-remove">
+                        <input type="hidden" name="guild" value=gid/>
+                        <input type="hidden" name="panel_id" value=id/>
+                        <input type="hidden" name="role_id" value=req.role_id.clone()/>
+                        <input type="hidden" name="forbidden" value=req.forbidden.to_string()/>
+                        <button type="submit" class="btn btn-ghost">"Remove"</button>
+                    </ActionForm>
+                </div>
+            }
+        })
+        .collect_view();
+
+    let duration =
+        panel.role_duration_minutes.map(|m| m.to_string()).unwrap_or_default();
+
+    view! {
+        <details class="settings-section rp-card">
+            <summary>
+                <span class="rp-title">{panel.title.clone()}</span>
+                <span class="rp-summary">{summary}</span>
+            </summary>
+
+            {link.map(|href| view! {
+                <a class="rr-link" href=href rel="external noreferrer" target="_blank">
+                    "Message"
+                    <Icon name="external-link"/>
+                </a>
+            })}
+
+            <div class="rp-entries">{entries}</div>
+            <div class="rp-requirements">{requirements}</div>
+
+            <ActionForm action=actions.add_role>
+                <input type="hidden" name="guild" value=guild_id.clone()/>
+                <input type="hidden" name="panel_id" value=id/>
+                <RoleSelect
+                    label="Offer a role"
+                    name="role_id"
+                    selected=String::new()
+                    roles=roles.clone()
+                />
+                <SettingField label="Label" name="label" value=String::new() pattern=ANY_TEXT/>
+                <div class="setting-field">
+                    <label>"Emoji"</label>
+                    <input type="text" name="emoji" placeholder="\u{2705} or <:name:id>"/>
+                </div>
+                <SettingField
+                    label="Description (select menus)"
+                    name="description"
+                    value=String::new()
+                    pattern=ANY_TEXT
+                />
+                <div class="form-actions">
+                    <button type="submit" class="btn btn-primary">"Add role"</button>
+                </div>
+            </ActionForm>
+
+            <ActionForm action=actions.require>
+                <input type="hidden" name="guild" value=guild_id.clone()/>
+                <input type="hidden" name="panel_id" value=id/>
+                <RoleSelect
+                    label="Requirement role"
+                    name="role_id"
+                    selected=String::new()
+                    roles=roles
+                />
+                <div class="setting-field">
+                    <label>"Members with this role"</label>
+                    <div class="select">
+                        <select name="forbidden">
+                            <option value="false" selected=true>"Can use the panel"</option>
+                            <option value="true">"Cannot use the panel"</option>
+                        </select>
+                        <span class="select-chevron"><Icon name="chevron-down"/></span>
+                    </div>
+                </div>
+                <div class="form-actions">
+                    <button type="submit" class="btn btn-primary">"Toggle requirement"</button>
+                </div>
+            </ActionForm>
+
+            <ActionForm action=actions.update>
+                <input type="hidden" name="guild" value=guild_id.clone()/>
+                <input type="hidden" name="panel_id" value=id/>
+                <SettingField label="Title" name="title" value=panel.title pattern=ANY_TEXT/>
+                <SettingField
+                    label="Description"
+                    name="description"
+                    value=panel.description.unwrap_or_default()
+                    pattern=ANY_TEXT
+                />
+                <SelectField
+                    label="Kind"
+                    name="kind"
+                    selected=panel.kind
+                    options=options(PANEL_KINDS)
+                />
+                <SelectField
+                    label="Mode"
+                    name="mode"
+                    selected=panel.mode
+                    options=options(PANEL_MODES)
+                />
+                <SettingField
+                    label="Take roles back after (minutes, blank keeps them)"
+                    name="duration_minutes"
+                    value=duration
+                />
+                <SaveButton/>
+            </ActionForm>
+
+            <ActionForm action=actions.delete attr:class="rp-delete">
+                <input type="hidden" name="guild" value=guild_id/>
+                <input type="hidden" name="panel_id" value=id/>
+                <button type="submit" class="btn btn-ghost">"Delete panel"</button>
+            </ActionForm>
+        </details>
+    }
+}
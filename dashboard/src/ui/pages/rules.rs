@@ -0,0 +1,532 @@
+use leptos::form::ActionForm;
+use leptos::prelude::*;
+use leptos_meta::Title;
+use leptos_router::hooks::use_params_map;
+use twilight_model::channel::ChannelType;
+
+use crate::dto::rules::{
+    DEFAULT_COLOUR,
+    MAX_QUESTION_CHARS,
+    MAX_QUIZ_QUESTIONS,
+    MAX_RULES,
+    colour_hex,
+    discord_markdown,
+    parse_colour,
+};
+use crate::dto::{ChannelInfo, QuizQuestionInfo, RulesView};
+use crate::server::discord::list_guild_channels;
+use crate::server::rules::{
+    AddQuizQuestion,
+    AddRule,
+    DeleteQuizQuestion,
+    DeleteRule,
+    EditRule,
+    PublishRules,
+    ReorderRules,
+    SaveRulesConfig,
+    get_rules,
+};
+use crate::ui::components::icons::Icon;
+use crate::ui::components::layout::AppShell;
+use crate::ui::components::select::ChannelSelect;
+use crate::ui::components::settings::{SaveButton, save_feedback};
+
+const TEXT_KINDS: &[ChannelType] =
+    &[ChannelType::GuildText, ChannelType::GuildAnnouncement];
+
+/// Every rules form. Edits and reordering already show in the page's own
+/// state, so only adding, deleting and publishing reload it; a reload would
+/// otherwise throw away unsaved edits to other rules.
+#[derive(Clone, Copy)]
+struct RuleActions {
+    save_config: ServerAction<SaveRulesConfig>,
+    add: ServerAction<AddRule>,
+    edit: ServerAction<EditRule>,
+    delete: ServerAction<DeleteRule>,
+    reorder: ServerAction<ReorderRules>,
+    publish: ServerAction<PublishRules>,
+    add_question: ServerAction<AddQuizQuestion>,
+    delete_question: ServerAction<DeleteQuizQuestion>,
+}
+
+impl RuleActions {
+    fn new() -> Self {
+        Self {
+            save_config: ServerAction::new(),
+            add: ServerAction::new(),
+            edit: ServerAction::new(),
+            delete: ServerAction::new(),
+            reorder: ServerAction::new(),
+            publish: ServerAction::new(),
+            add_question: ServerAction::new(),
+            delete_question: ServerAction::new(),
+        }
+    }
+
+    fn version(self) -> usize {
+        self.add.version().get()
+            + self.delete.version().get()
+            + self.publish.version().get()
+            + self.add_question.version().get()
+            + self.delete_question.version().get()
+    }
+}
+
+/// A rule as it is being edited, so the preview follows every keystroke.
+#[derive(Clone, Copy)]
+struct RuleDraft {
+    id: i32,
+    title: RwSignal<String>,
+    body: RwSignal<String>,
+}
+
+/// Moves rule `id` to index `to`. Returns whether anything moved.
+fn move_rule(rules: &mut Vec<RuleDraft>, id: i32, to: usize) -> bool {
+    let Some(from) = rules.iter().position(|r| r.id == id) else {
+        return false;
+    };
+    let to = to.min(rules.len().saturating_sub(1));
+    if from == to {
+        return false;
+    }
+    let rule = rules.remove(from);
+    rules.insert(to, rule);
+    true
+}
+
+fn order_of(rules: &[RuleDraft]) -> String {
+    rules.iter().map(|r| r.id.to_string()).collect::<Vec<_>>().join(",")
+}
+
+fn publish_feedback(r: Result<(), ServerFnError>) -> AnyView {
+    match r {
+        Ok(()) => view! {
+            <p class="success">"Queued. The rules message updates within a minute."</p>
+        }
+        .into_any(),
+        Err(e) => view! { <p class="error">"Failed to publish: " {e.to_string()}</p> }
+            .into_any(),
+    }
+}
+
+#[cfg(feature = "hydrate")]
+fn start_drag(ev: &leptos::ev::DragEvent, id: i32) {
+    // Firefox only starts a drag that carries data.
+    if let Some(transfer) = ev.data_transfer() {
+        transfer.set_effect_allowed("move");
+        let _ = transfer.set_data("text/plain", &id.to_string());
+    }
+}
+
+#[cfg(not(feature = "hydrate"))]
+const fn start_drag(_ev: &leptos::ev::DragEvent, _id: i32) {}
+
+#[component]
+pub(crate) fn RulesPage() -> impl IntoView {
+    let params = use_params_map();
+    let guild_id = move || params.with(|p| p.get("id").unwrap_or_default());
+
+    let actions = RuleActions::new();
+
+    let data = Resource::new_blocking(
+        move || (guild_id(), actions.version()),
+        |(gid, _)| async move {
+            let rules = get_rules(gid.clone()).await?;
+            let channels = list_guild_channels(gid).await.unwrap_or_default();
+            Ok::<(RulesView, Vec<ChannelInfo>), ServerFnError>((rules, channels))
+        },
+    );
+
+    view! {
+        <Title text="Rules - Zayden Dashboard"/>
+        <AppShell>
+            <div class="page">
+                <div class="page-header">
+                    <div>
+                        <h1>"Rules"</h1>
+                        <p class="page-lead">
+                            "Write, reorder and style the server rules, then publish "
+                            "them to the rules channel. Publishing edits the message "
+                            "already posted, or posts a new one if it is gone."
+                        </p>
+                    </div>
+                    <ActionForm action=actions.publish>
+                        <input type="hidden" name="guild" value=guild_id/>
+                        <button type="submit" class="btn btn-primary">
+                            <Icon name="zap"/>"Publish"
+                        </button>
+                    </ActionForm>
+                </div>
+                {move || actions.publish.value().get().map(publish_feedback)}
+                <Suspense fallback=|| view! {
+                    <p class="loading">"Loading rules\u{2026}"</p>
+                }>
+                    {move || data.get().map(|result| match result {
+                        Err(e) => view! {
+                            <p class="error">"Failed to load rules: " {e.to_string()}</p>
+                        }.into_any(),
+                        Ok((rules, channels)) => view! {
+                            <RulesEditor
+                                guild=guild_id()
+                                saved=rules
+                                channels=channels
+                                actions=actions
+                            />
+                        }.into_any(),
+                    })}
+                </Suspense>
+            </div>
+        </AppShell>
+    }
+}
+
+#[component]
+fn RulesEditor(
+    guild: String,
+    saved: RulesView,
+    channels: Vec<ChannelInfo>,
+    actions: RuleActions,
+) -> impl IntoView {
+    let config = saved.config;
+    let title = RwSignal::new(config.title.clone());
+    let description = RwSignal::new(config.description.clone());
+    let colour = RwSignal::new(config.colour.clone());
+    let rules = RwSignal::new(
+        saved
+            .rules
+            .into_iter()
+            .map(|r| RuleDraft {
+                id: r.id,
+                title: RwSignal::new(r.title),
+                body: RwSignal::new(r.body),
+            })
+            .collect::<Vec<_>>(),
+    );
+    let dragging = RwSignal::new(None::<i32>);
+    let quiz = saved.quiz;
+
+    let reorder_guild = guild.clone();
+    let on_move = Callback::new(move |(id, to): (i32, usize)| {
+        let mut moved = false;
+        rules.update(|r| moved = move_rule(r, id, to));
+        if moved {
+            actions.reorder.dispatch(ReorderRules {
+                guild: reorder_guild.clone(),
+                order: rules.with_untracked(|r| order_of(r)),
+            });
+        }
+    });
+
+    let status = if config.publish_pending {
+        "Publish queued; the bot posts within a minute."
+    } else if config.posted {
+        "Posted. Publish again after editing to refresh the message."
+    } else {
+        "Not posted yet."
+    };
+    let row_guild = guild.clone();
+    let quiz_guild = guild.clone();
+
+    view! {
+        <p class="rules-status">{status}</p>
+        <div class="rules-layout">
+            <div class="rules-editor">
+                <fieldset class="settings-section">
+                    <legend><Icon name="settings"/>"Embed"</legend>
+                    {move || actions.save_config.value().get().map(save_feedback)}
+                    <ActionForm action=actions.save_config>
+                        <input type="hidden" name="guild" value=guild.clone()/>
+                        <ChannelSelect
+                            label="Rules channel"
+                            name="channel_id"
+                            selected=config.channel_id
+                            channels=channels
+                            kinds=TEXT_KINDS
+                        />
+                        <div class="setting-field">
+                            <label>"Title"</label>
+                            <input
+                                type="text"
+                                name="title"
+                                maxlength="256"
+                                value=config.title
+                                on:input=move |ev| title.set(event_target_value(&ev))
+                            />
+                        </div>
+                        <div class="setting-field">
+                            <label>"Description"</label>
+                            <textarea
+                                name="description"
+                                rows="4"
+                                maxlength="4096"
+                                placeholder="Text shown above the rules"
+                                on:input=move |ev| description.set(event_target_value(&ev))
+                            >{config.description}</textarea>
+                        </div>
+                        <div class="setting-field">
+                            <label>"Colour"</label>
+                            <input
+                                type="color"
+                                name="colour"
+                                value=config.colour
+                                on:input=move |ev| colour.set(event_target_value(&ev))
+                            />
+                        </div>
+                        <SaveButton/>
+                    </ActionForm>
+                </fieldset>
+
+                <fieldset class="settings-section">
+                    <legend><Icon name="list"/>"Rules"</legend>
+                    <p class="page-lead">
+                        "Drag a rule by its handle to reorder it; the new order saves "
+                        "straight away. Rules support Discord Markdown."
+                    </p>
+                    {move || actions.reorder.value().get().map(save_feedback)}
+                    {move || actions.edit.value().get().map(save_feedback)}
+                    {move || actions.delete.value().get().map(save_feedback)}
+                    <ol class="rules-list">
+                        <For
+                            each=move || rules.get()
+                            key=|rule| rule.id
+                            children=move |rule| view! {
+                                <RuleRow
+                                    guild=row_guild.clone()
+                                    rule=rule
+                                    rules=rules
+                                    dragging=dragging
+                                    on_move=on_move
+                                    actions=actions
+                                />
+                            }
+                        />
+                    </ol>
+                    {move || rules.with(Vec::is_empty).then(|| view! {
+                        <p class="empty">"No rules yet. Add the first one below."</p>
+                    })}
+                </fieldset>
+
+                <fieldset class="settings-section">
+                    <legend><Icon name="plus"/>"Add a rule"</legend>
+                    {move || actions.add.value().get().map(save_feedback)}
+                    <ActionForm action=actions.add>
+                        <input type="hidden" name="guild" value=guild/>
+                        <div class="setting-field">
+                            <label>"Heading"</label>
+                            <input type="text" name="title" maxlength="256"/>
+                        </div>
+                        <div class="setting-field">
+                            <label>"Rule"</label>
+                            <textarea name="body" rows="3" maxlength="1024"></textarea>
+                        </div>
+                        <div class="form-actions">
+                            <button
+                                type="submit"
+                                class="btn btn-primary"
+                                prop:disabled=move || rules.with(Vec::len) >= MAX_RULES
+                            >"Add rule"</button>
+                        </div>
+                    </ActionForm>
+                </fieldset>
+
+                <QuizEditor guild=quiz_guild questions=quiz actions=actions/>
+            </div>
+
+            <EmbedPreview title=title description=description colour=colour rules=rules/>
+        </div>
+    }
+}
+
+#[component]
+fn RuleRow(
+    guild: String,
+    rule: RuleDraft,
+    rules: RwSignal<Vec<RuleDraft>>,
+    dragging: RwSignal<Option<i32>>,
+    on_move: Callback<(i32, usize)>,
+    actions: RuleActions,
+) -> impl IntoView {
+    let id = rule.id;
+    let index =
+        move || rules.with(|r| r.iter().position(|d| d.id == id).unwrap_or(0));
+    let is_last = move || rules.with(|r| r.last().is_some_and(|d| d.id == id));
+    let class = move || {
+        if dragging.get() == Some(id) { "rule-row dragging" } else { "rule-row" }
+    };
+
+    view! {
+        <li
+            class=class
+            on:dragover=move |ev| ev.prevent_default()
+            on:drop=move |ev| {
+                ev.prevent_default();
+                if let Some(dragged) = dragging.get_untracked() {
+                    on_move.run((dragged, index()));
+                }
+                dragging.set(None);
+            }
+        >
+            <div class="rule-row-head">
+                <span
+                    class="rule-handle"
+                    draggable="true"
+                    title="Drag to reorder"
+                    on:dragstart=move |ev| {
+                        start_drag(&ev, id);
+                        dragging.set(Some(id));
+                    }
+                    on:dragend=move |_| dragging.set(None)
+                >
+                    <Icon name="grip"/>
+                </span>
+                <span class="rule-number">{move || format!("{}.", index() + 1)}</span>
+                <span class="rule-row-spacer"></span>
+                <button
+                    type="button"
+                    class="btn btn-ghost"
+                    title="Move up"
+                    prop:disabled=move || index() == 0
+                    on:click=move |_| on_move.run((id, index().saturating_sub(1)))
+                >"\u{2191}"</button>
+                <button
+                    type="button"
+                    class="btn btn-ghost"
+                    title="Move down"
+                    prop:disabled=is_last
+                    on:click=move |_| on_move.run((id, index() + 1))
+                >"\u{2193}"</button>
+                <ActionForm action=actions.delete>
+                    <input type="hidden" name="guild" value=guild.clone()/>
+                    <input type="hidden" name="id" value=id.to_string()/>
+                    <button type="submit" class="btn btn-ghost" title="Delete rule">
+                        <Icon name="x"/>
+                    </button>
+                </ActionForm>
+            </div>
+            <ActionForm action=actions.edit attr:class="rule-edit">
+                <input type="hidden" name="guild" value=guild/>
+                <input type="hidden" name="id" value=id.to_string()/>
+                <input
+                    type="text"
+                    name="title"
+                    maxlength="256"
+                    value=rule.title.get_untracked()
+                    on:input=move |ev| rule.title.set(event_target_value(&ev))
+                />
+                <textarea
+                    name="body"
+                    rows="3"
+                    maxlength="1024"
+                    on:input=move |ev| rule.body.set(event_target_value(&ev))
+                >{rule.body.get_untracked()}</textarea>
+                <div class="form-actions">
+                    <button type="submit" class="btn btn-secondary">"Save"</button>
+                </div>
+            </ActionForm>
+        </li>
+    }
+}
+
+/// Questions the verify button asks after a member accepts the rules, when
+/// accepting is required in the Roles settings.
+#[component]
+fn QuizEditor(
+    guild: String,
+    questions: Vec<QuizQuestionInfo>,
+    actions: RuleActions,
+) -> impl IntoView {
+    let full = questions.len() >= MAX_QUIZ_QUESTIONS;
+    let row_guild = guild.clone();
+
+    view! {
+        <fieldset class="settings-section">
+            <legend><Icon name="shield"/>"Verification quiz"</legend>
+            <p class="page-lead">
+                "When members must accept the rules to verify, they also answer "
+                "these questions. Answers are matched ignoring case and punctuation; "
+                "separate alternatives with commas. Leave the quiz empty to only ask "
+                "for acceptance."
+            </p>
+            {move || actions.add_question.value().get().map(save_feedback)}
+            {move || actions.delete_question.value().get().map(save_feedback)}
+            {if questions.is_empty() {
+                view! { <p class="empty">"No quiz questions."</p> }.into_any()
+            } else {
+                view! {
+                    <ol class="quiz-list">
+                        {questions.into_iter().map(|question| view! {
+                            <li class="quiz-row">
+                                <div class="quiz-text">
+                                    <span class="quiz-question">{question.question}</span>
+                                    <span class="quiz-answers">{question.answers}</span>
+                                </div>
+                                <ActionForm action=actions.delete_question>
+                                    <input type="hidden" name="guild" value=row_guild.clone()/>
+                                    <input type="hidden" name="id" value=question.id.to_string()/>
+                                    <button type="submit" class="btn btn-ghost" title="Delete question">
+                                        <Icon name="x"/>
+                                    </button>
+                                </ActionForm>
+                            </li>
+                        }).collect_view()}
+                    </ol>
+                }.into_any()
+            }}
+            <ActionForm action=actions.add_question>
+                <input type="hidden" name="guild" value=guild/>
+                <div class="setting-field">
+                    <label>"Question"</label>
+                    <input type="text" name="question" maxlength=MAX_QUESTION_CHARS.to_string()/>
+                </div>
+                <div class="setting-field">
+                    <label>"Accepted answers"</label>
+                    <input type="text" name="answers" placeholder="e.g. no, never"/>
+                </div>
+                <div class="form-actions">
+                    <button type="submit" class="btn btn-primary" disabled=full>
+                        "Add question"
+                    </button>
+                </div>
+            </ActionForm>
+        </fieldset>
+    }
+}
+
+/// The rules embed as Discord will show it, redrawn as the forms change.
+#[component]
+fn EmbedPreview(
+    title: RwSignal<String>,
+    description: RwSignal<String>,
+    colour: RwSignal<String>,
+    rules: RwSignal<Vec<RuleDraft>>,
+) -> impl IntoView {
+    let border = move || {
+        let colour = parse_colour(&colour.get()).unwrap_or(DEFAULT_COLOUR);
+        format!("border-left-color: {}", colour_hex(colour))
+    };
+
+    view! {
+        <aside class="rules-preview">
+            <div class="rules-preview-label">"Preview"</div>
+            <div class="embed" style=border>
+                <div class="embed-title">{move || title.get()}</div>
+                <div
+                    class="embed-description"
+                    inner_html=move || discord_markdown(&description.get())
+                ></div>
+                {move || rules.get().into_iter().map(|rule| view! {
+                    <div class="embed-field">
+                        <div
+                            class="embed-field-name"
+                            inner_html=move || discord_markdown(&rule.title.get())
+                        ></div>
+                        <div
+                            class="embed-field-value"
+                            inner_html=move || discord_markdown(&rule.body.get())
+                        ></div>
+                    </div>
+                }).collect_view()}
+            </div>
+        </aside>
+    }
+}
@@ -0,0 +1,135 @@
+use leptos::prelude::*;
+use leptos_meta::Title;
+use leptos_router::hooks::use_params_map;
+
+use crate::server::suggestions::get_suggestions;
+use crate::ui::components::layout::AppShell;
+
+const PAGE_SIZE: usize = 10;
+
+const STATUSES: [(&str, &str); 5] = [
+    ("open", "Open"),
+    ("under_review", "Under Review"),
+    ("accepted", "Accepted"),
+    ("implemented", "Implemented"),
+    ("denied", "Denied"),
+];
+
+#[component]
+pub(crate) fn SuggestionsPage() -> impl IntoView {
+    let params = use_params_map();
+    let guild_id = move || params.with(|p| p.get("id").unwrap_or_default());
+
+    let (status, set_status) = signal(None::<&'static str>);
+    let (page, set_page) = signal(1i32);
+
+    let board = Resource::new(
+        move || (guild_id(), status.get(), page.get()),
+        |(gid, s, p)| get_suggestions(gid, s.map(str::to_string), p),
+    );
+
+    let seg_class = move |id: Option<&'static str>| {
+        move || if status.get() == id { "seg active" } else { "seg" }
+    };
+
+    view! {
+        <Title text="Suggestions - Zayden Dashboard"/>
+        <AppShell>
+            <div class="page">
+                <div class="page-header">
+                    <div>
+                        <h1>"Suggestions"</h1>
+                        <p class="page-lead">
+                            "Member suggestions ranked by votes. Staff set statuses from the review channel."
+                        </p>
+                    </div>
+                    <div class="segmented" role="tablist">
+                        <button
+                            type="button"
+                            class=seg_class(None)
+                            on:click=move |_| { set_status.set(None); set_page.set(1); }
+                        >"All"</button>
+                        {STATUSES.into_iter().map(|(id, label)| view! {
+                            <button
+                                type="button"
+                                class=seg_class(Some(id))
+                                on:click=move |_| { set_status.set(Some(id)); set_page.set(1); }
+                            >{label}</button>
+                        }).collect_view()}
+                    </div>
+                </div>
+
+                <Suspense fallback=|| view! {
+                    <p class="loading">"Loading suggestions\u{2026}"</p>
+                }>
+                    {move || board.get().map(|result| match result {
+                        Err(e) => view! {
+                            <p class="error">"Failed to load suggestions: " {e.to_string()}</p>
+                        }.into_any(),
+                        Ok(entries) if entries.is_empty() => view! {
+                            <div class="empty">
+                                {if page.get() > 1 {
+                                    "No more suggestions on this page."
+                                } else if status.get().is_some() {
+                                    "No suggestions have this status."
+                                } else {
+                                    "No suggestions yet - they appear here once members post or vote."
+                                }}
+                            </div>
+                        }.into_any(),
+                        Ok(entries) => {
+                            let has_next = entries.len() == PAGE_SIZE;
+                            let gid = guild_id();
+                            view! {
+                                <div class="suggestion-board">
+                                    {entries.into_iter().map(|e| {
+                                        let href = format!(
+                                            "https://discord.com/channels/{gid}/{}",
+                                            e.thread_id
+                                        );
+                                        let status_class = format!("suggestion-status {}", e.status);
+                                        view! {
+                                            <div class="suggestion-row">
+                                                <div class="suggestion-votes">
+                                                    <span class="up">"\u{25b2} " {e.upvotes}</span>
+                                                    <span class="down">"\u{25bc} " {e.downvotes}</span>
+                                                </div>
+                                                <div class="suggestion-body">
+                                                    <a class="suggestion-title" href=href target="_blank" rel="noopener">
+                                                        {e.title}
+                                                    </a>
+                                                    <span class="suggestion-author">
+                                                        {e.author}
+                                                    </span>
+                                                    {e.response.map(|r| view! {
+                                                        <p class="suggestion-response">{r}</p>
+                                                    })}
+                                                </div>
+                                                <span class=status_class>{e.status_label}</span>
+                                            </div>
+                                        }
+                                    }).collect_view()}
+                                </div>
+                                <div class="pager">
+                                    <button
+                                        type="button"
+                                        class="btn btn-secondary"
+                                        prop:disabled=move || page.get() <= 1
+                                        on:click=move |_| set_page.update(|p| *p = (*p - 1).max(1))
+                                    >"Previous"</button>
+                                    <span class="pager-page">"Page " {move || page.get()}</span>
+                                    <button
+                                        type="button"
+                                        class="btn btn-secondary"
+                                        prop:disabled=!has_next
+                                        on:click=move |_| set_page.update(|p| *p += 1)
+                                    >"Next"</button>
+                                </div>
+                            }.into_any()
+                        },
+                    })}
+                </Suspense>
+            </div>
+        </AppShell>
+    }
+}
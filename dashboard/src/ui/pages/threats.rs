@@ -0,0 +1,186 @@
+use leptos::form::ActionForm;
+use leptos::prelude::*;
+use leptos_meta::Title;
+use leptos_router::components::A;
+use leptos_router::hooks::use_params_map;
+
+use crate::dto::{SharedHitInfo, WhitelistEntryInfo};
+use crate::server::threats::{UnwhitelistUser, WhitelistUser, get_threat_list};
+use crate::ui::components::icons::Icon;
+use crate::ui::components::layout::AppShell;
+use crate::ui::components::settings::{SettingField, save_feedback};
+
+#[component]
+pub(crate) fn ThreatListPage() -> impl IntoView {
+    let params = use_params_map();
+    let guild_id = move || params.with(|p| p.get("id").unwrap_or_default());
+
+    let allow = ServerAction::<WhitelistUser>::new();
+    let revoke = ServerAction::<UnwhitelistUser>::new();
+
+    let list = Resource::new(
+        move || (guild_id(), allow.version().get(), revoke.version().get()),
+        |(gid, ..)| get_threat_list(gid),
+    );
+
+    view! {
+        <Title text="Threat List - Zayden Dashboard"/>
+        <AppShell>
+            <div class="page">
+                <div class="page-header">
+                    <div>
+                        <h1>"Threat List"</h1>
+                        <p class="page-lead">
+                            "Honeypot hits from every server sharing them over the last "
+                            "90 days. Whitelisted users are never flagged or banned here "
+                            "because of other servers' hits."
+                        </p>
+                    </div>
+                </div>
+
+                <fieldset class="settings-section">
+                    <legend><Icon name="plus"/>"Whitelist a user"</legend>
+                    <p class="page-lead">
+                        "For appeals and false positives. If the shared list banned them "
+                        "here, the ban is lifted."
+                    </p>
+                    {move || allow.value().get().map(save_feedback)}
+                    <ActionForm action=allow>
+                        <input type="hidden" name="guild" value=guild_id/>
+                        <SettingField label="User ID" name="user_id" value=String::new()/>
+                        <SettingField label="Note" name="note" value=String::new() pattern=".*"/>
+                        <div class="form-actions">
+                            <button type="submit" class="btn btn-primary">"Whitelist"</button>
+                        </div>
+                    </ActionForm>
+                </fieldset>
+
+                {move || revoke.value().get().map(save_feedback)}
+                <Suspense fallback=|| view! {
+                    <p class="loading">"Loading the threat list\u{2026}"</p>
+                }>
+                    {move || list.get().map(|result| match result {
+                        Err(e) => view! {
+                            <p class="error">"Failed to load the threat list: " {e.to_string()}</p>
+                        }.into_any(),
+                        Ok(threats) => {
+                            let gid = guild_id();
+                            view! {
+                                <h2>"Recent shared hits"</h2>
+                                {if !threats.sharing {
+                                    view! {
+                                        <div class="empty">
+                                            "This server doesn't share its honeypot hits. Turn on "
+                                            <A href=format!("/guild/{gid}/settings")>"sharing in Settings"</A>
+                                            " to see the list."
+                                        </div>
+                                    }.into_any()
+                                } else if threats.hits.is_empty() {
+                                    view! { <div class="empty">"No shared hits recently."</div> }.into_any()
+                                } else {
+                                    view! {
+                                        <div class="mod-list">
+                                            {threats.hits.into_iter().map(|hit| view! {
+                                                <HitRow guild=gid.clone() hit=hit allow=allow/>
+                                            }).collect_view()}
+                                        </div>
+                                    }.into_any()
+                                }}
+                                <h2>"Whitelist"</h2>
+                                {if threats.whitelist.is_empty() {
+                                    view! { <div class="empty">"Nobody is whitelisted."</div> }.into_any()
+                                } else {
+                                    view! {
+                                        <div class="mod-list">
+                                            {threats.whitelist.into_iter().map(|entry| view! {
+                                                <WhitelistRow guild=gid.clone() entry=entry revoke=revoke/>
+                                            }).collect_view()}
+                                        </div>
+                                    }.into_any()
+                                }}
+                            }.into_any()
+                        },
+                    })}
+                </Suspense>
+            </div>
+        </AppShell>
+    }
+}
+
+#[component]
+fn HitRow(
+    guild: String,
+    hit: SharedHitInfo,
+    allow: ServerAction<WhitelistUser>,
+) -> impl IntoView {
+    let mut tags = Vec::new();
+    if hit.hit_here {
+        tags.push("hit this server".to_string());
+    }
+    if let Some(action) = &hit.screened {
+        tags.push(match action.as_str() {
+            "ban" => "banned on joining".to_string(),
+            _ => "flagged on joining".to_string(),
+        });
+    }
+
+    view! {
+        <div class="mod-row">
+            <div class="mod-row-head">
+                <span class="mod-user">{hit.user_id.clone()}</span>
+                {hit.whitelisted.then(|| view! {
+                    <span class="mod-kind mod-kind-warn">"Whitelisted"</span>
+                })}
+                <span class="mod-meta">
+                    {format!(
+                        "{} hit(s) in {} server(s) \u{b7} last {}{}",
+                        hit.hits,
+                        hit.guilds,
+                        hit.last_hit_at,
+                        hit.message_hash
+                            .as_ref()
+                            .map(|hash| format!(" \u{b7} message {hash}"))
+                            .unwrap_or_default(),
+                    )}
+                </span>
+            </div>
+            {(!tags.is_empty()).then(|| view! {
+                <p class="mod-reason">{tags.join(", ")}</p>
+            })}
+            {(!hit.whitelisted).then(|| view! {
+                <ActionForm action=allow attr:class="mod-inline-form">
+                    <input type="hidden" name="guild" value=guild/>
+                    <input type="hidden" name="user_id" value=hit.user_id/>
+                    <input type="text" name="note" placeholder="Why they are cleared" maxlength="200"/>
+                    <button type="submit" class="btn btn-secondary">"Whitelist"</button>
+                </ActionForm>
+            })}
+        </div>
+    }
+}
+
+#[component]
+fn WhitelistRow(
+    guild: String,
+    entry: WhitelistEntryInfo,
+    revoke: ServerAction<UnwhitelistUser>,
+) -> impl IntoView {
+    view! {
+        <div class="mod-row">
+            <div class="mod-row-head">
+                <span class="mod-user">{entry.user_id.clone()}</span>
+                <span class="mod-meta">
+                    {format!("by {} \u{b7} {}", entry.added_by, entry.created_at)}
+                </span>
+            </div>
+            {(!entry.note.is_empty()).then(|| view! {
+                <p class="mod-reason">{entry.note.clone()}</p>
+            })}
+            <ActionForm action=revoke attr:class="mod-inline-form">
+                <input type="hidden" name="guild" value=guild/>
+                <input type="hidden" name="user_id" value=entry.user_id/>
+                <button type="submit" class="btn btn-ghost">"Remove"</button>
+            </ActionForm>
+        </div>
+    }
+}
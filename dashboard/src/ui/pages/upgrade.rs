@@ -1,17 +1,23 @@
 use leptos::form::ActionForm;
 use leptos::prelude::*;
 use leptos_meta::Title;
+use leptos_router::hooks::use_query_map;
 
 use crate::dto::Tier;
 use crate::server::kofi::LinkKofiEmail;
+use crate::server::premium::RedeemGiftCode;
 use crate::server::tier::get_user_tier;
 use crate::ui::components::icons::Icon;
 use crate::ui::components::layout::AppShell;
 
 #[component]
 pub(crate) fn UpgradePage() -> impl IntoView {
-    let tier_info = Resource::new(|| (), |()| get_user_tier());
     let link = ServerAction::<LinkKofiEmail>::new();
+    let redeem = ServerAction::<RedeemGiftCode>::new();
+    let tier_info =
+        Resource::new(move || redeem.version().get(), |_| get_user_tier());
+    let query = use_query_map();
+    let patreon_outcome = move || query.with(|q| q.get("patreon"));
 
     view! {
         <Title text="Upgrade - Zayden Dashboard"/>
@@ -109,11 +115,42 @@ pub(crate) fn UpgradePage() -> impl IntoView {
                                 <a href="/invite" rel="external" class="btn btn-secondary">
                                     "Subscribe via Discord"
                                 </a>
+                                {(current > Tier::Free).then(|| view! {
+                                    <a href="/premium" class="btn btn-secondary">
+                                        "Manage premium servers"
+                                    </a>
+                                })}
                             </div>
                         }.into_any()
                     })}
                 </Suspense>
 
+                <div class="card">
+                    <p class="label">"Redeem a gift code"</p>
+                    <p class="page-lead">
+                        "Got a code? Redeem it for time on a paid plan. Server admins "
+                        "can also start a one-time free trial with "<code>"/premium trial"</code>"."
+                    </p>
+                    <ActionForm action=redeem>
+                        <div class="kofi-link-form">
+                            <input
+                                type="text"
+                                name="code"
+                                placeholder="ABCDE-FGHJK"
+                                autocomplete="off"
+                                required=true
+                            />
+                            <button type="submit" class="btn btn-primary">"Redeem"</button>
+                        </div>
+                    </ActionForm>
+                    {move || redeem.value().get().map(|r| match r {
+                        Ok(message) => view! { <p class="success">{message}</p> }.into_any(),
+                        Err(e) => view! {
+                            <p class="error">{e.to_string()}</p>
+                        }.into_any(),
+                    })}
+                </div>
+
                 <div class="card">
                     <p class="label">"Link your Ko-fi email"</p>
                     <p class="page-lead">
@@ -140,6 +177,29 @@ pub(crate) fn UpgradePage() -> impl IntoView {
                         }.into_any(),
                     })}
                 </div>
+
+                <div class="card">
+                    <p class="label">"Link your Patreon account"</p>
+                    <p class="page-lead">
+                        "Sign in with Patreon once and your pledge tier follows your "
+                        "Discord account, including upgrades and cancellations."
+                    </p>
+                    <div class="upgrade-actions">
+                        <a href="/patreon/link" rel="external" class="btn btn-primary">
+                            "Link Patreon"
+                        </a>
+                    </div>
+                    {move || patreon_outcome().map(|outcome| match outcome.as_str() {
+                        "linked" => view! {
+                            <p class="success">
+                                "Patreon linked. Your pledge applies within a minute."
+                            </p>
+                        }.into_any(),
+                        _ => view! {
+                            <p class="error">"Linking Patreon failed - please try again."</p>
+                        }.into_any(),
+                    })}
+                </div>
             </div>
         </AppShell>
     }
@@ -1,9 +1,11 @@
 mod routes_kofi;
 mod routes_login;
 mod routes_palworld_save;
+mod routes_patreon;
 
 pub(crate) const SESSION_COOKIE: &str = "session";
 pub(crate) const OAUTH_STATE_COOKIE: &str = "oauth_state";
+pub(crate) const PATREON_STATE_COOKIE: &str = "patreon_oauth_state";
 
 use axum::Router;
 use axum::middleware::from_fn_with_state;
@@ -18,6 +20,8 @@ use crate::middleware::auth::require_auth;
 pub(crate) fn routes(state: WebState) -> Router<WebState> {
     let protected = Router::new()
         .route("/kofi/link", post(routes_kofi::kofi_link_handler))
+        .route("/patreon/link", get(routes_patreon::patreon_link_handler))
+        .route("/patreon/callback", get(routes_patreon::patreon_callback_handler))
         .route(
             "/admin/palworld/save/export",
             post(routes_palworld_save::export_handler),
@@ -28,6 +32,7 @@ pub(crate) fn routes(state: WebState) -> Router<WebState> {
         .route("/auth/callback", get(discord_auth_callback_handler))
         .route("/logout", get(logout_handler))
         .route("/webhooks/kofi", post(routes_kofi::kofi_webhook_handler))
+        .route("/webhooks/patreon", post(routes_patreon::patreon_webhook_handler))
         .merge(protected)
 }
 
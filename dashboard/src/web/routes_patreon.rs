@@ -0,0 +1,241 @@
+use std::sync::Arc;
+
+use axum::Extension;
+use axum::body::Bytes;
+use axum::extract::{Query, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Redirect, Response};
+use oauth2::{AuthorizationCode, CsrfToken, Scope, TokenResponse};
+use serde::Deserialize;
+use tower_cookies::cookie::SameSite;
+use tower_cookies::{Cookie, Cookies};
+use tracing::warn;
+use zayden_app::entitlement::{
+    EntitlementProvider,
+    PATREON_API_BASE,
+    PatreonClient,
+    PatreonEvent,
+    PatreonMemberDocument,
+    PatreonProvider,
+};
+
+use super::PATREON_STATE_COOKIE;
+use crate::WebState;
+use crate::middleware::auth::AuthUser;
+
+const SIGNATURE_HEADER: &str = "x-patreon-signature";
+const EVENT_HEADER: &str = "x-patreon-event";
+
+fn upgrade_redirect(outcome: &str) -> Response {
+    Redirect::to(&format!("/upgrade?patreon={outcome}")).into_response()
+}
+
+pub(super) async fn patreon_webhook_handler(
+    State(state): State<WebState>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> StatusCode {
+    let Some(config) = state.patreon.as_ref() else {
+        return StatusCode::NOT_FOUND;
+    };
+
+    let signature = headers
+        .get(SIGNATURE_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default();
+    if !PatreonProvider::verify_signature(&config.webhook_secret, &body, signature) {
+        warn!("Patreon webhook rejected: signature missing or mismatched");
+        return StatusCode::UNAUTHORIZED;
+    }
+
+    let Some(event) = headers
+        .get(EVENT_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<PatreonEvent>().ok())
+    else {
+        return StatusCode::OK;
+    };
+
+    let member = match serde_json::from_slice::<PatreonMemberDocument>(&body) {
+        Ok(doc) => doc.data,
+        Err(e) => {
+            warn!(?e, "failed to parse Patreon webhook payload");
+            return StatusCode::OK;
+        },
+    };
+
+    let service = &state.app.entitlements;
+
+    if event == PatreonEvent::MemberDelete {
+        if let Err(e) = PatreonProvider.revoke(service, &member.id).await {
+            warn!(?e, member = %member.id, "failed to revoke Patreon entitlement");
+        }
+        return StatusCode::OK;
+    }
+
+    let Some(patreon_user_id) = member.user_id() else {
+        warn!(member = %member.id, "Patreon member event has no user relationship; skipping");
+        return StatusCode::OK;
+    };
+
+    let discord_user_id = match sqlx::query_scalar!(
+        "SELECT discord_user_id FROM patreon_links WHERE patreon_user_id = $1",
+        patreon_user_id,
+    )
+    .fetch_optional(&state.app.db)
+    .await
+    {
+        Ok(Some(id)) => id.cast_unsigned(),
+        Ok(None) => {
+            warn!(
+                member = %member.id,
+                "Patreon member event received but the patron has not linked a Discord account; skipping"
+            );
+            return StatusCode::OK;
+        },
+        Err(e) => {
+            warn!(?e, member = %member.id, "failed to query patreon_links");
+            return StatusCode::OK;
+        },
+    };
+
+    let result = match PatreonProvider::build_grant(
+        &member,
+        discord_user_id,
+        &config.tiers,
+    ) {
+        Some(data) => PatreonProvider.grant(service, data).await,
+        None => PatreonProvider.revoke(service, &member.id).await,
+    };
+    if let Err(e) = result {
+        warn!(?e, member = %member.id, "failed to record Patreon entitlement");
+    }
+
+    StatusCode::OK
+}
+
+pub(super) async fn patreon_link_handler(
+    cookies: Cookies,
+    State(state): State<WebState>,
+) -> Response {
+    let Some(oauth) = state.patreon_oauth.as_ref() else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+
+    let (auth_url, csrf_token) = oauth
+        .authorize_url(CsrfToken::new_random)
+        .add_scope(Scope::new("identity".to_string()))
+        .url();
+
+    let state_cookie =
+        Cookie::build((PATREON_STATE_COOKIE, csrf_token.secret().clone()))
+            .path("/")
+            .http_only(true)
+            .secure(!cfg!(debug_assertions))
+            .same_site(SameSite::Lax)
+            .max_age(tower_cookies::cookie::time::Duration::minutes(10))
+            .build();
+    cookies.add(state_cookie);
+
+    Redirect::to(auth_url.as_str()).into_response()
+}
+
+#[derive(Deserialize)]
+pub(super) struct PatreonCallback {
+    code: String,
+    state: String,
+}
+
+pub(super) async fn patreon_callback_handler(
+    Extension(user): Extension<AuthUser>,
+    Query(query): Query<PatreonCallback>,
+    cookies: Cookies,
+    State(state): State<WebState>,
+) -> Response {
+    let Some(oauth) = state.patreon_oauth.as_ref() else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+
+    let cookie_state =
+        cookies.get(PATREON_STATE_COOKIE).map(|c| c.value().to_owned());
+    let mut removal = Cookie::from(PATREON_STATE_COOKIE);
+    removal.set_path("/");
+    cookies.remove(removal);
+    if !matches!(&cookie_state, Some(s) if *s == query.state && !s.is_empty()) {
+        warn!("Patreon OAuth callback rejected: state cookie missing or mismatched");
+        return upgrade_redirect("error");
+    }
+
+    let Ok(discord_user_id) = user.id.parse::<i64>() else {
+        return StatusCode::BAD_REQUEST.into_response();
+    };
+
+    let access_token = match oauth
+        .exchange_code(AuthorizationCode::new(query.code))
+        .request_async(&state.http_oauth)
+        .await
+    {
+        Ok(t) => t.access_token().secret().clone(),
+        Err(e) => {
+            warn!(error = ?e, "OAuth token exchange with Patreon failed");
+            return upgrade_redirect("error");
+        },
+    };
+
+    let patreon_user_id = match PatreonClient::identity(
+        &state.app.http,
+        PATREON_API_BASE,
+        &access_token,
+    )
+    .await
+    {
+        Ok(id) => id,
+        Err(e) => {
+            warn!(?e, "request to Patreon identity failed");
+            return upgrade_redirect("error");
+        },
+    };
+
+    // Each side links once: relinking replaces whichever Patreon or Discord
+    // account was linked before, since the patron just proved they own both.
+    let linked = async {
+        let mut tx = state.app.db.begin().await?;
+        sqlx::query!(
+            "DELETE FROM patreon_links WHERE patreon_user_id = $1 OR discord_user_id = $2",
+            &patreon_user_id,
+            discord_user_id,
+        )
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query!(
+            "INSERT INTO patreon_links (patreon_user_id, discord_user_id) VALUES ($1, $2)",
+            &patreon_user_id,
+            discord_user_id,
+        )
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await
+    }
+    .await;
+
+    if let Err(e) = linked {
+        warn!(?e, "failed to insert patreon_links row");
+        return upgrade_redirect("error");
+    }
+
+    // Apply an existing pledge now instead of waiting for the next reconcile.
+    if let (Some(client), Some(config)) =
+        (state.patreon_client.clone(), state.patreon.clone())
+    {
+        let app = Arc::clone(&state.app);
+        tokio::spawn(async move {
+            if let Err(e) =
+                client.reconcile(&app.db, &app.entitlements, &config.tiers).await
+            {
+                warn!(?e, "Patreon reconcile after linking failed");
+            }
+        });
+    }
+
+    upgrade_redirect("linked")
+}
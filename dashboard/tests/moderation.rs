@@ -0,0 +1,37 @@
+//! Quoting infraction fields for the moderation page's CSV export.
+
+use dashboard::dto::moderation::csv_field;
+
+#[test]
+fn plain_fields_are_left_alone() {
+    assert_eq!(csv_field("Spamming invites"), "Spamming invites");
+}
+
+#[test]
+fn commas_and_newlines_are_quoted() {
+    assert_eq!(csv_field("spam, again"), "\"spam, again\"");
+    assert_eq!(csv_field("line one\nline two"), "\"line one\nline two\"");
+}
+
+#[test]
+fn quotes_are_doubled() {
+    assert_eq!(csv_field("said \"hi\""), "\"said \"\"hi\"\"\"");
+}
+
+#[test]
+fn formulas_are_escaped() {
+    assert_eq!(csv_field("=1+1"), "'=1+1");
+    assert_eq!(csv_field("@SUM(A1)"), "'@SUM(A1)");
+    assert_eq!(csv_field("+cmd"), "'+cmd");
+    assert_eq!(csv_field("-2"), "'-2");
+    assert_eq!(csv_field("\tx"), "'\tx");
+    assert_eq!(
+        csv_field("=HYPERLINK(\"http://a\", \"b\")"),
+        "\"'=HYPERLINK(\"\"http://a\"\", \"\"b\"\")\""
+    );
+}
+
+#[test]
+fn formula_characters_inside_a_field_are_left_alone() {
+    assert_eq!(csv_field("spam = bad"), "spam = bad");
+}
@@ -0,0 +1,59 @@
+//! The rules editor's embed preview: hex colours and the Discord Markdown
+//! subset it renders.
+
+use dashboard::dto::rules::{colour_hex, discord_markdown, parse_colour};
+
+#[test]
+fn colours_parse_with_or_without_hash() {
+    assert_eq!(parse_colour("FF0000"), Some(0x00ff_0000));
+    assert_eq!(parse_colour("#00ff00"), Some(0x0000_ff00));
+    assert_eq!(parse_colour("1000000"), None);
+    assert_eq!(parse_colour("red"), None);
+    assert_eq!(colour_hex(0x00ff_0000), "#ff0000");
+}
+
+#[test]
+fn html_is_escaped() {
+    assert_eq!(
+        discord_markdown("<script>alert('x')</script>"),
+        "&lt;script&gt;alert(&#39;x&#39;)&lt;/script&gt;"
+    );
+}
+
+#[test]
+fn inline_formatting() {
+    assert_eq!(
+        discord_markdown("**Be kind**, *always* and __never__ ~~spam~~"),
+        "<strong>Be kind</strong>, <em>always</em> and <u>never</u> <s>spam</s>"
+    );
+    assert_eq!(discord_markdown("use `<b>`"), "use <code>&lt;b&gt;</code>");
+    assert_eq!(discord_markdown("2 * 3 * 4"), "2 * 3 * 4");
+    assert_eq!(discord_markdown(r"\*not italic\*"), "*not italic*");
+}
+
+#[test]
+fn links_only_keep_web_urls() {
+    assert_eq!(
+        discord_markdown("[ToS](https://discord.com/terms)"),
+        "<a href=\"https://discord.com/terms\" target=\"_blank\" \
+         rel=\"noopener noreferrer\">ToS</a>"
+    );
+    assert_eq!(
+        discord_markdown("[x](javascript:alert(1))"),
+        "[x](javascript:alert(1))"
+    );
+}
+
+#[test]
+fn blocks() {
+    assert_eq!(
+        discord_markdown("# Heading\n- one\n- two\nafter"),
+        "<h1>Heading</h1><ul><li>one</li><li>two</li></ul>after"
+    );
+    assert_eq!(discord_markdown("> quoted"), "<blockquote>quoted</blockquote>");
+    assert_eq!(
+        discord_markdown("```\nlet x = 1 < 2;\n```"),
+        "<pre><code>let x = 1 &lt; 2;</code></pre>"
+    );
+    assert_eq!(discord_markdown("line one\nline two"), "line one<br/>line two");
+}
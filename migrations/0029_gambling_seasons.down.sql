@@ -0,0 +1,9 @@
+DROP TABLE IF EXISTS gambling_titles;
+
+DROP TABLE IF EXISTS gambling_season_rewards;
+
+DROP TABLE IF EXISTS gambling_season_archive;
+
+DROP TABLE IF EXISTS gambling_season_stats;
+
+DROP TABLE IF EXISTS gambling_seasons;
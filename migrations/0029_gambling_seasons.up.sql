@@ -0,0 +1,55 @@
+CREATE TABLE gambling_seasons(
+    id integer GENERATED ALWAYS AS IDENTITY PRIMARY KEY,
+    name text NOT NULL,
+    starts_at timestamptz NOT NULL,
+    ends_at timestamptz NOT NULL,
+    archived_at timestamptz,
+    CONSTRAINT gambling_seasons_range CHECK (starts_at < ends_at)
+);
+
+CREATE INDEX gambling_seasons_active_idx ON gambling_seasons(starts_at, ends_at)
+WHERE
+    archived_at IS NULL;
+
+-- Accumulates while a season is running; `coins` and `gems` are the net
+-- delta of every wager game played during the season.
+CREATE TABLE gambling_season_stats(
+    season_id integer NOT NULL REFERENCES gambling_seasons(id) ON DELETE CASCADE,
+    user_id bigint NOT NULL REFERENCES gambling(user_id) ON DELETE CASCADE,
+    coins bigint NOT NULL DEFAULT 0,
+    gems bigint NOT NULL DEFAULT 0,
+    higher_or_lower_score integer NOT NULL DEFAULT 0,
+    PRIMARY KEY (season_id, user_id)
+);
+
+-- Frozen global placements, written once when the season ends.
+CREATE TABLE gambling_season_archive(
+    season_id integer NOT NULL REFERENCES gambling_seasons(id) ON DELETE CASCADE,
+    leaderboard text NOT NULL,
+    placement integer NOT NULL,
+    user_id bigint NOT NULL,
+    value bigint NOT NULL,
+    PRIMARY KEY (season_id, leaderboard, placement)
+);
+
+CREATE TABLE gambling_season_rewards(
+    id integer GENERATED ALWAYS AS IDENTITY PRIMARY KEY,
+    season_id integer NOT NULL REFERENCES gambling_seasons(id) ON DELETE CASCADE,
+    leaderboard text NOT NULL,
+    placement_min integer NOT NULL,
+    placement_max integer NOT NULL,
+    coins bigint NOT NULL DEFAULT 0,
+    gems bigint NOT NULL DEFAULT 0,
+    item_id text,
+    title text,
+    CONSTRAINT gambling_season_rewards_placement CHECK (placement_min >= 1 AND placement_min <= placement_max),
+    CONSTRAINT gambling_season_rewards_non_negative CHECK (coins >= 0 AND gems >= 0)
+);
+
+CREATE TABLE gambling_titles(
+    user_id bigint NOT NULL REFERENCES gambling(user_id) ON DELETE CASCADE,
+    season_id integer NOT NULL REFERENCES gambling_seasons(id) ON DELETE CASCADE,
+    title text NOT NULL,
+    awarded_at timestamptz NOT NULL DEFAULT now(),
+    PRIMARY KEY (user_id, season_id, title)
+);
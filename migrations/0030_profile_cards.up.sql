@@ -0,0 +1,8 @@
+-- Per-user look for the rendered /rank and /profile cards. Backgrounds other
+-- than the default are unlocked by owning the matching gambling shop item.
+CREATE TABLE profile_cards(
+    user_id bigint PRIMARY KEY,
+    background text NOT NULL DEFAULT 'default',
+    accent integer NOT NULL DEFAULT 5793266,
+    CONSTRAINT profile_cards_accent_rgb CHECK (accent BETWEEN 0 AND 16777215)
+);
@@ -0,0 +1,4 @@
+DROP TABLE IF EXISTS support_tickets;
+
+ALTER TABLE support_settings
+    DROP COLUMN IF EXISTS transcript_channel_id;
@@ -0,0 +1,19 @@
+ALTER TABLE support_settings
+    ADD COLUMN transcript_channel_id bigint;
+
+-- One row per support thread. `opener_id` is NULL for threads opened before
+-- tickets were tracked; those can still be claimed and closed.
+CREATE TABLE support_tickets(
+    thread_id bigint PRIMARY KEY,
+    guild_id bigint NOT NULL REFERENCES guilds(id) ON DELETE CASCADE,
+    opener_id bigint,
+    claimed_by bigint,
+    claimed_at timestamptz,
+    opened_at timestamptz NOT NULL DEFAULT now(),
+    closed_at timestamptz,
+    closed_by bigint,
+    close_reason text,
+    transcript_message_id bigint
+);
+
+CREATE INDEX support_tickets_opener_idx ON support_tickets(guild_id, opener_id, opened_at DESC);
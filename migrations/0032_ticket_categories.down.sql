@@ -0,0 +1,11 @@
+DROP INDEX IF EXISTS support_tickets_open_idx;
+
+ALTER TABLE support_tickets
+    DROP COLUMN IF EXISTS sla_reminded_at,
+    DROP COLUMN IF EXISTS awaiting_reply_since,
+    DROP COLUMN IF EXISTS last_activity_at,
+    DROP COLUMN IF EXISTS category_id;
+
+DROP TABLE IF EXISTS ticket_category_questions;
+DROP TABLE IF EXISTS ticket_category_roles;
+DROP TABLE IF EXISTS ticket_categories;
@@ -0,0 +1,40 @@
+-- Admin-defined ticket categories, each with its own form, pinged roles,
+-- thread naming pattern, inactivity auto-close and first-response SLA.
+CREATE TABLE ticket_categories(
+    id serial PRIMARY KEY,
+    guild_id bigint NOT NULL REFERENCES guilds(id) ON DELETE CASCADE,
+    name text NOT NULL CHECK (char_length(name) BETWEEN 1 AND 100),
+    description text,
+    name_pattern text NOT NULL DEFAULT '{number} - {user} - {summary}',
+    auto_close_hours integer CHECK (auto_close_hours > 0),
+    sla_minutes integer CHECK (sla_minutes > 0)
+);
+
+CREATE UNIQUE INDEX ticket_categories_name_idx ON ticket_categories(guild_id, lower(name));
+
+CREATE TABLE ticket_category_roles(
+    category_id integer NOT NULL REFERENCES ticket_categories(id) ON DELETE CASCADE,
+    role_id bigint NOT NULL,
+    PRIMARY KEY (category_id, role_id)
+);
+
+-- Discord modals hold at most five inputs; `position` orders them.
+CREATE TABLE ticket_category_questions(
+    category_id integer NOT NULL REFERENCES ticket_categories(id) ON DELETE CASCADE,
+    position smallint NOT NULL CHECK (position BETWEEN 0 AND 4),
+    label text NOT NULL CHECK (char_length(label) BETWEEN 1 AND 45),
+    placeholder text CHECK (char_length(placeholder) <= 100),
+    paragraph boolean NOT NULL DEFAULT false,
+    required boolean NOT NULL DEFAULT true,
+    PRIMARY KEY (category_id, position)
+);
+
+ALTER TABLE support_tickets
+    ADD COLUMN category_id integer REFERENCES ticket_categories(id) ON DELETE SET NULL,
+    ADD COLUMN last_activity_at timestamptz NOT NULL DEFAULT now(),
+    -- Set while the opener's last message has no reply from anyone else.
+    ADD COLUMN awaiting_reply_since timestamptz DEFAULT now(),
+    ADD COLUMN sla_reminded_at timestamptz;
+
+CREATE INDEX support_tickets_open_idx ON support_tickets(category_id)
+    WHERE closed_at IS NULL AND category_id IS NOT NULL;
@@ -0,0 +1,3 @@
+DROP TABLE IF EXISTS suggestions;
+
+DROP TYPE IF EXISTS suggestion_status;
@@ -0,0 +1,28 @@
+CREATE TYPE suggestion_status AS ENUM(
+    'open',
+    'under_review',
+    'accepted',
+    'implemented',
+    'denied'
+);
+
+-- One row per suggestion forum post. `thread_id` is also the id of the
+-- post's starter message, which carries the votes.
+CREATE TABLE suggestions(
+    thread_id bigint PRIMARY KEY,
+    guild_id bigint NOT NULL REFERENCES guilds(id) ON DELETE CASCADE,
+    channel_id bigint NOT NULL,
+    author_id bigint NOT NULL,
+    anonymous boolean NOT NULL DEFAULT false,
+    title text NOT NULL,
+    content text NOT NULL,
+    upvotes integer NOT NULL DEFAULT 0,
+    downvotes integer NOT NULL DEFAULT 0,
+    status suggestion_status NOT NULL DEFAULT 'open',
+    response text,
+    responded_by bigint,
+    created_at timestamptz NOT NULL DEFAULT now(),
+    updated_at timestamptz NOT NULL DEFAULT now()
+);
+
+CREATE INDEX suggestions_board_idx ON suggestions(guild_id, status, (upvotes - downvotes) DESC);
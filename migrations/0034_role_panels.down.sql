@@ -0,0 +1,8 @@
+DROP TABLE IF EXISTS temporary_roles;
+DROP TABLE IF EXISTS role_panel_claims;
+DROP TABLE IF EXISTS role_panel_requirements;
+DROP TABLE IF EXISTS role_panel_entries;
+DROP TABLE IF EXISTS role_panels;
+
+DROP TYPE IF EXISTS role_panel_mode;
+DROP TYPE IF EXISTS role_panel_kind;
@@ -0,0 +1,66 @@
+CREATE TYPE role_panel_kind AS ENUM('buttons', 'select');
+
+CREATE TYPE role_panel_mode AS ENUM(
+    'toggle',
+    'add_only',
+    'remove_only',
+    'unique',
+    'verify_once'
+);
+
+-- Role panels are messages the bot posts and keeps in sync itself. `dirty`
+-- marks a panel whose message needs re-rendering after a dashboard edit.
+CREATE TABLE role_panels(
+    id serial PRIMARY KEY,
+    guild_id bigint NOT NULL REFERENCES guilds(id) ON DELETE CASCADE,
+    channel_id bigint NOT NULL,
+    message_id bigint,
+    title text NOT NULL CHECK (char_length(title) BETWEEN 1 AND 256),
+    description text CHECK (char_length(description) <= 4000),
+    kind role_panel_kind NOT NULL DEFAULT 'buttons',
+    mode role_panel_mode NOT NULL DEFAULT 'toggle',
+    role_duration_minutes integer CHECK (role_duration_minutes > 0),
+    dirty boolean NOT NULL DEFAULT true,
+    created_at timestamptz NOT NULL DEFAULT now()
+);
+
+CREATE INDEX role_panels_guild_idx ON role_panels(guild_id);
+
+-- A message holds at most 25 buttons or select options.
+CREATE TABLE role_panel_entries(
+    panel_id integer NOT NULL REFERENCES role_panels(id) ON DELETE CASCADE,
+    role_id bigint NOT NULL,
+    position smallint NOT NULL CHECK (position BETWEEN 0 AND 24),
+    label text NOT NULL CHECK (char_length(label) BETWEEN 1 AND 80),
+    emoji text,
+    description text CHECK (char_length(description) <= 100),
+    PRIMARY KEY (panel_id, role_id)
+);
+
+-- Roles a member must hold (`forbidden = false`) or must not hold
+-- (`forbidden = true`) to use a panel.
+CREATE TABLE role_panel_requirements(
+    panel_id integer NOT NULL REFERENCES role_panels(id) ON DELETE CASCADE,
+    role_id bigint NOT NULL,
+    forbidden boolean NOT NULL,
+    PRIMARY KEY (panel_id, role_id)
+);
+
+-- Members who have used a verify-once panel.
+CREATE TABLE role_panel_claims(
+    panel_id integer NOT NULL REFERENCES role_panels(id) ON DELETE CASCADE,
+    user_id bigint NOT NULL,
+    claimed_at timestamptz NOT NULL DEFAULT now(),
+    PRIMARY KEY (panel_id, user_id)
+);
+
+-- Roles handed out by a panel with a duration, removed by the role sweep.
+CREATE TABLE temporary_roles(
+    guild_id bigint NOT NULL REFERENCES guilds(id) ON DELETE CASCADE,
+    user_id bigint NOT NULL,
+    role_id bigint NOT NULL,
+    expires_at timestamptz NOT NULL,
+    PRIMARY KEY (guild_id, user_id, role_id)
+);
+
+CREATE INDEX temporary_roles_expiry_idx ON temporary_roles(expires_at);
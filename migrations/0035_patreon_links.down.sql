@@ -0,0 +1 @@
+DROP TABLE IF EXISTS patreon_links;
@@ -0,0 +1,8 @@
+-- A Patreon user linked to a Discord account through the dashboard's
+-- Patreon OAuth flow. Grants are keyed by campaign member id, which the
+-- webhook and the reconcile job both resolve back to this user id.
+CREATE TABLE patreon_links(
+    patreon_user_id text PRIMARY KEY,
+    discord_user_id bigint NOT NULL UNIQUE,
+    linked_at timestamptz NOT NULL DEFAULT now()
+);
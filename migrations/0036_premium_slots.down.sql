@@ -0,0 +1,5 @@
+DROP TRIGGER IF EXISTS notify_premium_slot_changed ON premium_slots;
+
+DROP FUNCTION IF EXISTS notify_premium_slot_changed ();
+
+DROP TABLE IF EXISTS premium_slots;
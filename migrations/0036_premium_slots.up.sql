@@ -0,0 +1,36 @@
+-- A subscriber applying their tier to a guild they manage. Each tier covers
+-- `Tier::premium_slots` guilds; past that, the oldest assignments win.
+CREATE TABLE premium_slots(
+    user_id bigint NOT NULL,
+    guild_id bigint NOT NULL,
+    assigned_at timestamptz NOT NULL DEFAULT now(),
+    PRIMARY KEY (user_id, guild_id)
+);
+
+CREATE INDEX premium_slots_guild_idx ON premium_slots(guild_id);
+
+-- Slots are ranked per user, so adding or removing one can change the tier of
+-- every guild that user has slotted, not only the one that changed.
+CREATE OR REPLACE FUNCTION notify_premium_slot_changed()
+RETURNS TRIGGER AS $$
+DECLARE
+    user_id_val  BIGINT;
+    guild_id_val BIGINT;
+BEGIN
+    IF TG_OP = 'DELETE' THEN
+        user_id_val  := OLD.user_id;
+        guild_id_val := OLD.guild_id;
+    ELSE
+        user_id_val  := NEW.user_id;
+        guild_id_val := NEW.guild_id;
+    END IF;
+    PERFORM pg_notify('entitlement_changed', 'guild:' || guild_id_val::text || ':0');
+    PERFORM pg_notify('entitlement_changed', 'guild:' || s.guild_id::text || ':0')
+    FROM premium_slots s
+    WHERE s.user_id = user_id_val AND s.guild_id <> guild_id_val;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER notify_premium_slot_changed AFTER INSERT
+OR DELETE ON premium_slots FOR EACH ROW EXECUTE FUNCTION notify_premium_slot_changed ();
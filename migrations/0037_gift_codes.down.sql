@@ -0,0 +1,5 @@
+DROP TABLE IF EXISTS guild_trials;
+
+DROP TABLE IF EXISTS gift_code_redemptions;
+
+DROP TABLE IF EXISTS gift_codes;
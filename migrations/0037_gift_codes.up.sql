@@ -0,0 +1,26 @@
+-- Codes the bot owner mints; each redemption grants the redeemer `tier` for
+-- `duration_days` through an expiring `gift` entitlement.
+CREATE TABLE gift_codes(
+    code text PRIMARY KEY,
+    tier text NOT NULL CHECK (tier IN ('pro', 'ultra')),
+    duration_days integer NOT NULL CHECK (duration_days > 0),
+    max_uses integer NOT NULL CHECK (max_uses > 0),
+    uses integer NOT NULL DEFAULT 0,
+    expires_at timestamptz,
+    created_by bigint NOT NULL,
+    created_at timestamptz NOT NULL DEFAULT now()
+);
+
+CREATE TABLE gift_code_redemptions(
+    code text NOT NULL REFERENCES gift_codes(code) ON DELETE CASCADE,
+    user_id bigint NOT NULL,
+    redeemed_at timestamptz NOT NULL DEFAULT now(),
+    PRIMARY KEY (code, user_id)
+);
+
+-- One free trial per guild, kept after the trial entitlement expires.
+CREATE TABLE guild_trials(
+    guild_id bigint PRIMARY KEY,
+    started_by bigint NOT NULL,
+    started_at timestamptz NOT NULL DEFAULT now()
+);
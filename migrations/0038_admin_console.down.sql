@@ -0,0 +1,11 @@
+ALTER TABLE bot_config
+    DROP COLUMN IF EXISTS youtube_cookies_file,
+    DROP COLUMN IF EXISTS flaresolverr_url;
+
+DROP TABLE IF EXISTS cron_job_runs;
+
+DROP TABLE IF EXISTS bot_log_entries;
+
+DROP TABLE IF EXISTS command_usage_daily;
+
+DROP TABLE IF EXISTS guild_membership;
@@ -0,0 +1,48 @@
+-- Operational data for the owner's admin console.
+-- Guilds the bot has been in; `left_at` is cleared when it is re-added.
+CREATE TABLE guild_membership(
+    guild_id bigint PRIMARY KEY,
+    name text NOT NULL DEFAULT '',
+    member_count integer NOT NULL DEFAULT 0,
+    joined_at timestamptz NOT NULL DEFAULT now(),
+    left_at timestamptz
+);
+
+CREATE INDEX guild_membership_joined_idx ON guild_membership(joined_at);
+
+CREATE INDEX guild_membership_left_idx ON guild_membership(left_at)
+WHERE
+    left_at IS NOT NULL;
+
+CREATE TABLE command_usage_daily(
+    day date NOT NULL DEFAULT CURRENT_DATE,
+    command text NOT NULL,
+    module text NOT NULL DEFAULT '',
+    uses bigint NOT NULL DEFAULT 0,
+    failures bigint NOT NULL DEFAULT 0,
+    PRIMARY KEY (day, command)
+);
+
+-- WARN and ERROR events from the bot's tracing stream, pruned by a cron.
+CREATE TABLE bot_log_entries(
+    id bigserial PRIMARY KEY,
+    level text NOT NULL,
+    target text NOT NULL,
+    message text NOT NULL,
+    created_at timestamptz NOT NULL DEFAULT now()
+);
+
+CREATE INDEX bot_log_entries_created_idx ON bot_log_entries(created_at);
+
+CREATE TABLE cron_job_runs(
+    job_id text PRIMARY KEY,
+    last_started_at timestamptz NOT NULL,
+    last_finished_at timestamptz NOT NULL,
+    next_run_at timestamptz,
+    runs bigint NOT NULL DEFAULT 1
+);
+
+-- Settings that used to need an env change and a restart to edit.
+ALTER TABLE bot_config
+    ADD COLUMN flaresolverr_url text,
+    ADD COLUMN youtube_cookies_file text;
@@ -0,0 +1 @@
+DROP TABLE IF EXISTS settings_audit_log;
@@ -0,0 +1,14 @@
+-- Every attributed settings change, with the row before and after, so a
+-- guild's activity can be listed and any change rolled back.
+CREATE TABLE settings_audit_log(
+    id bigserial PRIMARY KEY,
+    guild_id bigint NOT NULL,
+    actor_id bigint NOT NULL,
+    source text NOT NULL CHECK (source IN ('dashboard', 'command', 'rollback')),
+    table_name text NOT NULL,
+    before jsonb,
+    after jsonb NOT NULL,
+    created_at timestamptz NOT NULL DEFAULT now()
+);
+
+CREATE INDEX settings_audit_log_guild_idx ON settings_audit_log(guild_id, id DESC);
@@ -0,0 +1,8 @@
+DROP INDEX IF EXISTS infractions_guild_created_idx;
+
+ALTER TABLE infractions
+    DROP COLUMN IF EXISTS expunge_reason,
+    DROP COLUMN IF EXISTS expunged_by,
+    DROP COLUMN IF EXISTS expunged_at,
+    DROP COLUMN IF EXISTS updated_by,
+    DROP COLUMN IF EXISTS updated_at;
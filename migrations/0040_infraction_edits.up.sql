@@ -0,0 +1,10 @@
+-- Dashboard moderation: infractions can be edited and expunged. Expunged
+-- infractions stay on record but no longer count toward escalation.
+ALTER TABLE infractions
+    ADD COLUMN updated_at timestamptz,
+    ADD COLUMN updated_by bigint,
+    ADD COLUMN expunged_at timestamptz,
+    ADD COLUMN expunged_by bigint,
+    ADD COLUMN expunge_reason text;
+
+CREATE INDEX infractions_guild_created_idx ON infractions(guild_id, created_at DESC);
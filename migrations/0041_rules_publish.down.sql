@@ -0,0 +1 @@
+ALTER TABLE guild_rules DROP COLUMN IF EXISTS publish_requested;
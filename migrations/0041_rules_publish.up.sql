@@ -0,0 +1,4 @@
+-- The dashboard edits rules directly and asks the bot to publish them; the
+-- rules sweep posts or refreshes the message for every requested guild.
+ALTER TABLE guild_rules
+    ADD COLUMN publish_requested boolean NOT NULL DEFAULT false;
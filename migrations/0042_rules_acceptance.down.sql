@@ -0,0 +1,11 @@
+DROP TABLE IF EXISTS rules_acceptances;
+
+DROP TABLE IF EXISTS verify_quiz_questions;
+
+ALTER TABLE roles_settings
+    DROP COLUMN IF EXISTS reverify_on_rules_change,
+    DROP COLUMN IF EXISTS require_rules;
+
+ALTER TABLE guild_rules
+    DROP COLUMN IF EXISTS fingerprint,
+    DROP COLUMN IF EXISTS version;
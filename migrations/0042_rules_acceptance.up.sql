@@ -0,0 +1,33 @@
+-- The verify button can ask members to accept the server rules, and answer a
+-- short quiz, before granting the verified role. `version` goes up only when
+-- the rules change materially, judged by `fingerprint`.
+ALTER TABLE guild_rules
+    ADD COLUMN version integer NOT NULL DEFAULT 1,
+    ADD COLUMN fingerprint text;
+
+ALTER TABLE roles_settings
+    ADD COLUMN require_rules boolean NOT NULL DEFAULT false,
+    ADD COLUMN reverify_on_rules_change boolean NOT NULL DEFAULT false;
+
+-- Asked in a Discord modal: at most five, each label at most 45 characters.
+CREATE TABLE verify_quiz_questions(
+    id serial PRIMARY KEY,
+    guild_id bigint NOT NULL REFERENCES guild_rules(guild_id) ON DELETE CASCADE,
+    position integer NOT NULL,
+    question text NOT NULL CHECK (char_length(question) BETWEEN 1 AND 45),
+    answers text[] NOT NULL CHECK (cardinality(answers) > 0)
+);
+
+CREATE INDEX verify_quiz_questions_guild_idx ON verify_quiz_questions(guild_id, position);
+
+-- The rules version each member last accepted. `reprompted_version` is the
+-- version the member last lost the verified role for, so a rules change only
+-- re-prompts them once.
+CREATE TABLE rules_acceptances(
+    guild_id bigint NOT NULL,
+    user_id bigint NOT NULL,
+    version integer NOT NULL,
+    accepted_at timestamptz NOT NULL DEFAULT now(),
+    reprompted_version integer,
+    PRIMARY KEY (guild_id, user_id)
+);
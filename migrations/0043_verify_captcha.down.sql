@@ -0,0 +1,9 @@
+DROP TABLE IF EXISTS verify_captchas;
+
+ALTER TABLE roles_settings
+    DROP COLUMN IF EXISTS captcha_log_channel_id,
+    DROP COLUMN IF EXISTS captcha_fail_action,
+    DROP COLUMN IF EXISTS captcha_min_account_age_days,
+    DROP COLUMN IF EXISTS captcha_lockout_minutes,
+    DROP COLUMN IF EXISTS captcha_max_attempts,
+    DROP COLUMN IF EXISTS captcha_enabled;
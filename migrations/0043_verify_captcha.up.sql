@@ -0,0 +1,26 @@
+-- The verify button can hand out an image CAPTCHA first. Failures are counted
+-- per member; running out of attempts locks them out for a while and logs or
+-- kicks them, per guild.
+ALTER TABLE roles_settings
+    ADD COLUMN captcha_enabled boolean NOT NULL DEFAULT false,
+    ADD COLUMN captcha_max_attempts integer NOT NULL DEFAULT 3
+        CHECK (captcha_max_attempts BETWEEN 1 AND 10),
+    ADD COLUMN captcha_lockout_minutes integer NOT NULL DEFAULT 15
+        CHECK (captcha_lockout_minutes BETWEEN 0 AND 10080),
+    ADD COLUMN captcha_min_account_age_days integer NOT NULL DEFAULT 0
+        CHECK (captcha_min_account_age_days BETWEEN 0 AND 365),
+    ADD COLUMN captcha_fail_action text NOT NULL DEFAULT 'log'
+        CHECK (captcha_fail_action IN ('log', 'kick')),
+    ADD COLUMN captcha_log_channel_id bigint;
+
+-- One open challenge per member. `code` is cleared once answered or locked
+-- out; `attempts` counts wrong answers since the last lockout or success.
+CREATE TABLE verify_captchas(
+    guild_id bigint NOT NULL,
+    user_id bigint NOT NULL,
+    code text,
+    issued_at timestamptz NOT NULL DEFAULT now(),
+    attempts integer NOT NULL DEFAULT 0,
+    locked_until timestamptz,
+    PRIMARY KEY (guild_id, user_id)
+);
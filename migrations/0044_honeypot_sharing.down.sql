@@ -0,0 +1,9 @@
+DROP TABLE IF EXISTS honeypot_screenings;
+DROP TABLE IF EXISTS honeypot_whitelist;
+DROP TABLE IF EXISTS honeypot_hits;
+
+ALTER TABLE honeypot_settings
+    DROP COLUMN IF EXISTS alert_channel_id,
+    DROP COLUMN IF EXISTS shared_action,
+    DROP COLUMN IF EXISTS shared_threshold,
+    DROP COLUMN IF EXISTS share_hits;
@@ -0,0 +1,45 @@
+-- An opt-in threat list shared between guilds. Every honeypot hit is kept;
+-- hits from guilds that share count against the offender everywhere else,
+-- and a sharing guild can flag or ban members who tripped honeypots in
+-- enough other sharing guilds as they join.
+ALTER TABLE honeypot_settings
+    ADD COLUMN share_hits boolean NOT NULL DEFAULT false,
+    ADD COLUMN shared_threshold integer NOT NULL DEFAULT 0
+        CHECK (shared_threshold BETWEEN 0 AND 25),
+    ADD COLUMN shared_action text NOT NULL DEFAULT 'flag'
+        CHECK (shared_action IN ('flag', 'ban')),
+    ADD COLUMN alert_channel_id bigint;
+
+-- `message_hash` identifies the bait message without keeping its content.
+CREATE TABLE honeypot_hits(
+    id bigserial PRIMARY KEY,
+    guild_id bigint NOT NULL,
+    user_id bigint NOT NULL,
+    message_hash text NOT NULL,
+    created_at timestamptz NOT NULL DEFAULT now()
+);
+
+CREATE INDEX honeypot_hits_user_idx ON honeypot_hits (user_id, created_at);
+CREATE INDEX honeypot_hits_created_idx ON honeypot_hits (created_at);
+
+-- Members a guild has cleared: they are never flagged or banned there on the
+-- strength of other guilds' hits.
+CREATE TABLE honeypot_whitelist(
+    guild_id bigint NOT NULL,
+    user_id bigint NOT NULL,
+    note text NOT NULL DEFAULT '',
+    added_by bigint NOT NULL,
+    created_at timestamptz NOT NULL DEFAULT now(),
+    PRIMARY KEY (guild_id, user_id)
+);
+
+-- What a guild did to a joining member because of the shared list, so the
+-- dashboard can show it and whitelisting can lift a ban it caused.
+CREATE TABLE honeypot_screenings(
+    guild_id bigint NOT NULL,
+    user_id bigint NOT NULL,
+    action text NOT NULL CHECK (action IN ('flag', 'ban')),
+    guilds integer NOT NULL,
+    created_at timestamptz NOT NULL DEFAULT now(),
+    PRIMARY KEY (guild_id, user_id)
+);
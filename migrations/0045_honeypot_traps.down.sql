@@ -0,0 +1,6 @@
+DELETE FROM honeypot_hits WHERE message_hash IS NULL;
+ALTER TABLE honeypot_hits ALTER COLUMN message_hash SET NOT NULL;
+
+ALTER TABLE honeypot_settings RENAME COLUMN log_channel_id TO alert_channel_id;
+
+ALTER TABLE honeypot_settings DROP COLUMN IF EXISTS traps;
@@ -0,0 +1,12 @@
+-- Traps beyond the honeypot channel: more channels and bait roles, each with
+-- its own action. Kept on the settings row so they are cached, recorded in
+-- the settings history and rolled back with it.
+ALTER TABLE honeypot_settings
+    ADD COLUMN traps jsonb NOT NULL DEFAULT '[]'
+        CHECK (jsonb_typeof(traps) = 'array' AND jsonb_array_length(traps) <= 10);
+
+-- Every hit is reported there now, not only shared-list matches.
+ALTER TABLE honeypot_settings RENAME COLUMN alert_channel_id TO log_channel_id;
+
+-- A bait role hit has no message to hash.
+ALTER TABLE honeypot_hits ALTER COLUMN message_hash DROP NOT NULL;
@@ -0,0 +1,18 @@
+-- Message filters. Each rule in `rules` is off until set, and carries its
+-- own action; see `AutomodRules`. Timeouts share one duration per guild.
+CREATE TABLE automod_settings (
+    guild_id bigint PRIMARY KEY REFERENCES guilds (id) ON DELETE CASCADE,
+    enabled boolean NOT NULL DEFAULT FALSE,
+    exempt_admins boolean NOT NULL DEFAULT TRUE,
+    exempt_role_id bigint,
+    log_channel_id bigint,
+    timeout_minutes integer NOT NULL DEFAULT 10
+        CHECK (timeout_minutes BETWEEN 1 AND 40320),
+    rules jsonb NOT NULL DEFAULT '{}' CHECK (jsonb_typeof(rules) = 'object'),
+    updated_at timestamptz NOT NULL DEFAULT now()
+);
+
+CREATE OR REPLACE TRIGGER automod_settings_notify
+    AFTER INSERT OR UPDATE OR DELETE ON automod_settings
+    FOR EACH ROW
+    EXECUTE FUNCTION notify_config_changed ();
@@ -0,0 +1,3 @@
+DROP TABLE IF EXISTS raid_lockdown_channels;
+DROP TABLE IF EXISTS raid_lockdowns;
+DROP TABLE IF EXISTS raid_settings;
@@ -0,0 +1,54 @@
+-- Raid protection. Past `join_threshold` joins in `join_window_seconds` the
+-- guild goes into lockdown; see `RaidSettingsRow`.
+CREATE TABLE raid_settings (
+    guild_id bigint PRIMARY KEY REFERENCES guilds (id) ON DELETE CASCADE,
+    enabled boolean NOT NULL DEFAULT FALSE,
+    join_threshold integer NOT NULL DEFAULT 10
+        CHECK (join_threshold BETWEEN 2 AND 500),
+    join_window_seconds integer NOT NULL DEFAULT 10
+        CHECK (join_window_seconds BETWEEN 1 AND 300),
+    lockdown_minutes integer NOT NULL DEFAULT 30
+        CHECK (lockdown_minutes BETWEEN 0 AND 1440),
+    min_account_age_days integer NOT NULL DEFAULT 7
+        CHECK (min_account_age_days BETWEEN 0 AND 365),
+    flag_default_avatar boolean NOT NULL DEFAULT TRUE,
+    flag_similar_names boolean NOT NULL DEFAULT TRUE,
+    joiner_action text NOT NULL DEFAULT 'timeout'
+        CHECK (joiner_action IN ('none', 'timeout', 'kick')),
+    timeout_minutes integer NOT NULL DEFAULT 60
+        CHECK (timeout_minutes BETWEEN 1 AND 40320),
+    raise_verification boolean NOT NULL DEFAULT TRUE,
+    slowmode_seconds integer NOT NULL DEFAULT 30
+        CHECK (slowmode_seconds BETWEEN 0 AND 21600),
+    alert_channel_id bigint,
+    updated_at timestamptz NOT NULL DEFAULT now()
+);
+
+CREATE OR REPLACE TRIGGER raid_settings_notify
+    AFTER INSERT OR UPDATE OR DELETE ON raid_settings
+    FOR EACH ROW
+    EXECUTE FUNCTION notify_config_changed ();
+
+-- A guild's current lockdown, with what it changed so ending it can put
+-- things back. `ends_at` is NULL until ended by hand; `started_by` is NULL
+-- when the join rate started it.
+CREATE TABLE raid_lockdowns (
+    guild_id bigint PRIMARY KEY REFERENCES guilds (id) ON DELETE CASCADE,
+    started_at timestamptz NOT NULL DEFAULT now(),
+    ends_at timestamptz,
+    reason text NOT NULL,
+    started_by bigint,
+    previous_verification_level smallint,
+    joiners_actioned integer NOT NULL DEFAULT 0
+);
+
+CREATE INDEX raid_lockdowns_ends_at_idx ON raid_lockdowns (ends_at)
+    WHERE ends_at IS NOT NULL;
+
+CREATE TABLE raid_lockdown_channels (
+    guild_id bigint NOT NULL REFERENCES raid_lockdowns (guild_id)
+        ON DELETE CASCADE,
+    channel_id bigint NOT NULL,
+    previous_slowmode integer NOT NULL,
+    PRIMARY KEY (guild_id, channel_id)
+);
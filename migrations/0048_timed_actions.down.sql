@@ -0,0 +1,8 @@
+DROP TABLE IF EXISTS timed_actions;
+
+ALTER TABLE infractions
+    DROP COLUMN IF EXISTS lift_reason,
+    DROP COLUMN IF EXISTS lifted_at,
+    DROP COLUMN IF EXISTS expires_at;
+
+DROP TYPE IF EXISTS timed_action_kind;
@@ -0,0 +1,46 @@
+-- Timed moderation: tempbans, timed roles and timed slowmode. Each action is
+-- a row until the sweep reverses it or a moderator cancels it, so expiries
+-- survive restarts. Finished rows are kept as history.
+CREATE TYPE timed_action_kind AS ENUM ('ban', 'role', 'slowmode');
+
+-- A tempban's infraction records when it runs out and when it was lifted.
+ALTER TABLE infractions
+    ADD COLUMN expires_at timestamptz,
+    ADD COLUMN lifted_at timestamptz,
+    ADD COLUMN lift_reason text;
+
+CREATE TABLE timed_actions (
+    id int GENERATED ALWAYS AS IDENTITY PRIMARY KEY,
+    guild_id bigint NOT NULL REFERENCES guilds (id) ON DELETE CASCADE,
+    kind timed_action_kind NOT NULL,
+    -- The banned member, or the member holding the role.
+    user_id bigint,
+    role_id bigint,
+    channel_id bigint,
+    -- The slowmode to put back.
+    previous_slowmode int,
+    infraction_id int REFERENCES infractions (id) ON DELETE SET NULL,
+    moderator_id bigint NOT NULL,
+    reason text NOT NULL,
+    created_at timestamptz NOT NULL DEFAULT now(),
+    expires_at timestamptz NOT NULL,
+    completed_at timestamptz,
+    cancelled_at timestamptz,
+    cancelled_by bigint,
+    failures int NOT NULL DEFAULT 0,
+    last_error text,
+    CHECK (
+        CASE kind
+            WHEN 'ban' THEN user_id IS NOT NULL
+            WHEN 'role' THEN user_id IS NOT NULL AND role_id IS NOT NULL
+            WHEN 'slowmode' THEN channel_id IS NOT NULL
+                AND previous_slowmode IS NOT NULL
+        END
+    )
+);
+
+CREATE INDEX timed_actions_pending_idx ON timed_actions (guild_id, expires_at)
+WHERE completed_at IS NULL AND cancelled_at IS NULL;
+
+CREATE INDEX timed_actions_due_idx ON timed_actions (expires_at)
+WHERE completed_at IS NULL AND cancelled_at IS NULL;
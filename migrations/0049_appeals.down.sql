@@ -0,0 +1,3 @@
+DROP TABLE IF EXISTS appeals;
+
+DROP TABLE IF EXISTS appeals_settings;
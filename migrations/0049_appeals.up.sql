@@ -0,0 +1,32 @@
+-- Moderation appeals. Punished members appeal from the DM they were sent;
+-- staff approve or deny in the guild's appeals channel.
+CREATE TABLE appeals_settings (
+    guild_id bigint PRIMARY KEY REFERENCES guilds (id) ON DELETE CASCADE,
+    enabled boolean NOT NULL DEFAULT FALSE,
+    channel_id bigint,
+    updated_at timestamptz NOT NULL DEFAULT now()
+);
+
+CREATE OR REPLACE TRIGGER appeals_settings_notify
+    AFTER INSERT OR UPDATE OR DELETE ON appeals_settings
+    FOR EACH ROW
+    EXECUTE FUNCTION notify_config_changed ();
+
+-- One appeal per infraction. `message_id` is the staff channel post with the
+-- decision buttons.
+CREATE TABLE appeals (
+    id int GENERATED ALWAYS AS IDENTITY PRIMARY KEY,
+    infraction_id int NOT NULL UNIQUE REFERENCES infractions (id) ON DELETE CASCADE,
+    guild_id bigint NOT NULL REFERENCES guilds (id) ON DELETE CASCADE,
+    user_id bigint NOT NULL,
+    statement text NOT NULL,
+    channel_id bigint,
+    message_id bigint,
+    status text NOT NULL DEFAULT 'pending'
+        CHECK (status IN ('pending', 'approved', 'denied')),
+    created_at timestamptz NOT NULL DEFAULT now(),
+    decided_at timestamptz,
+    decided_by bigint
+);
+
+CREATE INDEX appeals_guild_status_idx ON appeals (guild_id, status);
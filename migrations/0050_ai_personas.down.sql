@@ -0,0 +1,4 @@
+ALTER TABLE ai_settings
+    DROP COLUMN IF EXISTS personas,
+    DROP COLUMN IF EXISTS keywords,
+    DROP COLUMN IF EXISTS trigger_mode;
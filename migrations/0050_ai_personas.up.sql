@@ -0,0 +1,5 @@
+ALTER TABLE ai_settings
+    ADD COLUMN trigger_mode text NOT NULL DEFAULT 'mention'
+        CHECK (trigger_mode IN ('mention', 'reply', 'keyword', 'channel')),
+    ADD COLUMN keywords text[] NOT NULL DEFAULT '{}',
+    ADD COLUMN personas jsonb NOT NULL DEFAULT '{}';
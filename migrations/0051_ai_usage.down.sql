@@ -0,0 +1,3 @@
+DROP TABLE IF EXISTS ai_quota_overrides;
+
+DROP TABLE IF EXISTS ai_usage_daily;
@@ -0,0 +1,27 @@
+-- Tokens the AI chat spent, per guild, member and model each day, from the
+-- provider's own usage figures. Quotas are checked against these rows. Days
+-- are UTC days, which is when the quotas reset, whatever the server's zone.
+CREATE TABLE ai_usage_daily (
+    day date NOT NULL DEFAULT (now() AT TIME ZONE 'UTC')::date,
+    guild_id bigint NOT NULL,
+    user_id bigint NOT NULL,
+    model text NOT NULL,
+    requests bigint NOT NULL DEFAULT 0,
+    prompt_tokens bigint NOT NULL DEFAULT 0,
+    completion_tokens bigint NOT NULL DEFAULT 0,
+    PRIMARY KEY (day, guild_id, user_id, model)
+);
+
+CREATE INDEX ai_usage_daily_guild_idx ON ai_usage_daily (guild_id, day);
+
+CREATE INDEX ai_usage_daily_user_idx ON ai_usage_daily (user_id, day);
+
+-- Limits the owner set by hand to lift a guild above its tier's quota. A
+-- NULL limit keeps the tier's.
+CREATE TABLE ai_quota_overrides (
+    guild_id bigint PRIMARY KEY,
+    daily_tokens bigint CHECK (daily_tokens > 0),
+    monthly_tokens bigint CHECK (monthly_tokens > 0),
+    set_by bigint NOT NULL,
+    updated_at timestamptz NOT NULL DEFAULT now()
+);
@@ -0,0 +1,3 @@
+ALTER TABLE temporary_roles
+    DROP COLUMN IF EXISTS retry_at,
+    DROP COLUMN IF EXISTS failures;
@@ -0,0 +1,5 @@
+-- A temporary role Discord wouldn't take back is retried a few minutes later
+-- rather than forgotten.
+ALTER TABLE temporary_roles
+    ADD COLUMN failures integer NOT NULL DEFAULT 0,
+    ADD COLUMN retry_at timestamptz;
@@ -0,0 +1 @@
+ALTER TABLE timed_actions DROP COLUMN IF EXISTS retry_at;
@@ -0,0 +1,3 @@
+-- A failed reversal waits for its retry here, so `expires_at` keeps the time
+-- the action was meant to end.
+ALTER TABLE timed_actions ADD COLUMN retry_at timestamptz;
@@ -0,0 +1,3 @@
+-- Why each finished action was undone, whatever its kind; a tempban's
+-- infraction also keeps its own copy.
+ALTER TABLE timed_actions ADD COLUMN lift_reason text;
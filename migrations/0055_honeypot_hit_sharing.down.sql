@@ -0,0 +1 @@
+ALTER TABLE honeypot_hits DROP COLUMN IF EXISTS shared;
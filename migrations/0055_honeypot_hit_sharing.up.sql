@@ -0,0 +1,8 @@
+-- Whether the guild shared its hits when the hit was recorded, so turning
+-- sharing on later doesn't publish hits taken while it was off. Existing hits
+-- go by the guild's setting now, the closest there is.
+ALTER TABLE honeypot_hits ADD COLUMN shared boolean NOT NULL DEFAULT false;
+
+UPDATE honeypot_hits h SET shared = true
+FROM honeypot_settings s
+WHERE s.guild_id = h.guild_id AND s.share_hits;
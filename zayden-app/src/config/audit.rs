@@ -0,0 +1,224 @@
+//! Version history for guild settings rows. Every attributed
+//! [`SettingsStore::update_by`](super::SettingsStore::update_by) stores the
+//! row before and after the change, so a guild's activity can be listed and
+//! any change undone.
+
+use jiff_sqlx::Timestamp as SqlxTimestamp;
+use serde_json::Value;
+use sqlx::PgPool;
+use thiserror::Error;
+
+/// Where a settings change came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeSource {
+    Dashboard,
+    Command,
+    Rollback,
+}
+
+impl ChangeSource {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Dashboard => "dashboard",
+            Self::Command => "command",
+            Self::Rollback => "rollback",
+        }
+    }
+
+    #[must_use]
+    pub fn from_key(key: &str) -> Option<Self> {
+        match key {
+            "dashboard" => Some(Self::Dashboard),
+            "command" => Some(Self::Command),
+            "rollback" => Some(Self::Rollback),
+            _ => None,
+        }
+    }
+}
+
+/// Who made a settings change, and through what.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Actor {
+    pub user_id: u64,
+    pub source: ChangeSource,
+}
+
+impl Actor {
+    #[must_use]
+    pub const fn dashboard(user_id: u64) -> Self {
+        Self { user_id, source: ChangeSource::Dashboard }
+    }
+
+    #[must_use]
+    pub const fn command(user_id: u64) -> Self {
+        Self { user_id, source: ChangeSource::Command }
+    }
+
+    #[must_use]
+    pub const fn rollback(user_id: u64) -> Self {
+        Self { user_id, source: ChangeSource::Rollback }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SettingsChange {
+    pub id: i64,
+    pub guild_id: u64,
+    pub actor_id: u64,
+    pub source: ChangeSource,
+    pub table: String,
+    /// The row before the change; `None` when the change created it.
+    pub before: Option<Value>,
+    pub after: Value,
+    pub created_at: jiff::Timestamp,
+}
+
+impl SettingsChange {
+    #[must_use]
+    pub fn fields(&self) -> Vec<FieldChange> {
+        diff(self.before.as_ref(), &self.after)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldChange {
+    pub field: String,
+    pub before: Value,
+    pub after: Value,
+}
+
+#[derive(Debug, Error)]
+pub enum AuditError {
+    #[error("that change no longer exists")]
+    NotFound,
+    #[error("settings table {0} cannot be rolled back")]
+    UnknownTable(String),
+    #[error("stored settings version no longer matches the row: {0}")]
+    Snapshot(#[from] serde_json::Error),
+    #[error(transparent)]
+    Sqlx(#[from] sqlx::Error),
+}
+
+/// The fields of `after` whose values differ from `before`, by field
+/// name. A missing `before` counts every field as `null` before.
+/// `guild_id` is never reported.
+#[must_use]
+pub fn diff(before: Option<&Value>, after: &Value) -> Vec<FieldChange> {
+    let Some(after) = after.as_object() else {
+        return Vec::new();
+    };
+    let before = before.and_then(Value::as_object);
+
+    after
+        .iter()
+        .filter(|(field, _)| *field != "guild_id")
+        .filter_map(|(field, new)| {
+            let old = before.and_then(|b| b.get(field)).unwrap_or(&Value::Null);
+            (old != new).then(|| FieldChange {
+                field: field.clone(),
+                before: old.clone(),
+                after: new.clone(),
+            })
+        })
+        .collect()
+}
+
+pub(crate) async fn record(
+    pool: &PgPool,
+    guild_id: i64,
+    actor: Actor,
+    table: &str,
+    before: Option<&Value>,
+    after: &Value,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        "INSERT INTO settings_audit_log
+            (guild_id, actor_id, source, table_name, before, after)
+        VALUES ($1, $2, $3, $4, $5::text::jsonb, $6::text::jsonb)",
+        guild_id,
+        i64::try_from(actor.user_id).unwrap_or(i64::MAX),
+        actor.source.as_str(),
+        table,
+        before.map(Value::to_string),
+        after.to_string(),
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// A guild's settings changes, newest first.
+pub async fn history(
+    pool: &PgPool,
+    guild_id: u64,
+    limit: i64,
+    offset: i64,
+) -> Result<Vec<SettingsChange>, AuditError> {
+    let rows = sqlx::query!(
+        r#"SELECT id, guild_id, actor_id, source, table_name,
+            before::text AS before, after::text AS "after!",
+            created_at AS "created_at: SqlxTimestamp"
+        FROM settings_audit_log
+        WHERE guild_id = $1
+        ORDER BY id DESC
+        LIMIT $2 OFFSET $3"#,
+        i64::try_from(guild_id).unwrap_or(i64::MAX),
+        limit,
+        offset,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    rows.into_iter()
+        .map(|row| {
+            Ok(SettingsChange {
+                id: row.id,
+                guild_id: u64::try_from(row.guild_id).unwrap_or(0),
+                actor_id: u64::try_from(row.actor_id).unwrap_or(0),
+                source: ChangeSource::from_key(&row.source)
+                    .unwrap_or(ChangeSource::Dashboard),
+                table: row.table_name,
+                before: row
+                    .before
+                    .as_deref()
+                    .map(serde_json::from_str)
+                    .transpose()?,
+                after: serde_json::from_str(&row.after)?,
+                created_at: row.created_at.to_jiff(),
+            })
+        })
+        .collect()
+}
+
+pub async fn change(
+    pool: &PgPool,
+    guild_id: u64,
+    id: i64,
+) -> Result<SettingsChange, AuditError> {
+    let row = sqlx::query!(
+        r#"SELECT id, guild_id, actor_id, source, table_name,
+            before::text AS before, after::text AS "after!",
+            created_at AS "created_at: SqlxTimestamp"
+        FROM settings_audit_log
+        WHERE guild_id = $1 AND id = $2"#,
+        i64::try_from(guild_id).unwrap_or(i64::MAX),
+        id,
+    )
+    .fetch_optional(pool)
+    .await?
+    .ok_or(AuditError::NotFound)?;
+
+    Ok(SettingsChange {
+        id: row.id,
+        guild_id: u64::try_from(row.guild_id).unwrap_or(0),
+        actor_id: u64::try_from(row.actor_id).unwrap_or(0),
+        source: ChangeSource::from_key(&row.source)
+            .unwrap_or(ChangeSource::Dashboard),
+        table: row.table_name,
+        before: row.before.as_deref().map(serde_json::from_str).transpose()?,
+        after: serde_json::from_str(&row.after)?,
+        created_at: row.created_at.to_jiff(),
+    })
+}
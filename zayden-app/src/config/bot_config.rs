@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::env;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
@@ -8,6 +9,7 @@ use tracing::warn;
 
 use crate::config::radio;
 use crate::config::radio::RadioStation;
+use crate::entitlement::Tier;
 use crate::{Error, Result};
 
 const DEFAULT_OSCAR_SIX: u64 = 211_486_447_369_322_506;
@@ -20,11 +22,14 @@ const DEFAULT_AI_MODEL: &str = "openrouter/free";
 const DEFAULT_AI_MODEL_PRO: &str = "google/gemini-2.5-flash";
 
 const DEFAULT_REDIRECT_URI: &str = "http://localhost:3000/auth/callback";
+const DEFAULT_PATREON_REDIRECT_URI: &str = "http://localhost:3000/patreon/callback";
 const DEFAULT_BIND_ADDR: &str = "0.0.0.0:3000";
 
 const DEFAULT_PALWORLD_SAVE_DIR: &str = "056C426C55974CFCA115EB695A224F67";
 const DEFAULT_PALWORLD_UPLOADS_DIR: &str = "palworld_uploads";
 
+const DEFAULT_SCAM_DOMAINS: &str = "scam_domains.txt";
+
 #[derive(Debug, Clone)]
 pub struct SpotifyCredentials {
     pub client_id: String,
@@ -39,6 +44,18 @@ pub struct PelicanConfig {
     pub save_path: String,
 }
 
+#[derive(Debug, Clone)]
+pub struct PatreonConfig {
+    pub webhook_secret: String,
+    pub client_id: String,
+    pub client_secret: String,
+    pub creator_token: String,
+    pub campaign_id: String,
+    pub redirect_uri: String,
+    /// Patreon tier id to the tier it unlocks. Unmapped tiers grant nothing.
+    pub tiers: HashMap<String, Tier>,
+}
+
 #[derive(Debug, Clone)]
 pub struct BotConfig {
     pub discord_token: String,
@@ -83,7 +100,12 @@ pub struct BotConfig {
     pub discord_sku_pro: Option<u64>,
     pub discord_sku_ultra: Option<u64>,
 
+    pub patreon: Option<PatreonConfig>,
+
     pub radio_stations: Arc<[RadioStation]>,
+
+    /// The automod scam-link list; see [`crate::services::scam_domains`].
+    pub scam_domains_path: PathBuf,
 }
 
 impl BotConfig {
@@ -115,6 +137,7 @@ impl BotConfig {
         let db = load_db_row(pool).await?;
 
         let pelican = load_pelican_config(&toml_cfg);
+        let patreon = load_patreon_config(&toml_cfg);
 
         Ok(Self {
             discord_token,
@@ -154,9 +177,16 @@ impl BotConfig {
                 .and_then(|r| r.normal_log_webhook.clone())
                 .or_else(|| env::var("NORMAL_LOG_WEBHOOK").ok()),
 
-            flaresolverr_url: env::var("FLARESOLVERR_URL").ok(),
+            flaresolverr_url: db
+                .as_ref()
+                .and_then(|r| r.flaresolverr_url.clone())
+                .or_else(|| env::var("FLARESOLVERR_URL").ok()),
 
-            youtube_cookies: youtube_cookies_path(),
+            youtube_cookies: youtube_cookies_path(
+                db.as_ref()
+                    .and_then(|r| r.youtube_cookies_file.clone())
+                    .or_else(|| env::var("YOUTUBE_COOKIES_FILE").ok()),
+            ),
 
             palworld_save_dir: Some(
                 toml_cfg.pelican.save_path.as_deref().map_or_else(
@@ -188,13 +218,20 @@ impl BotConfig {
             discord_sku_pro: toml_cfg.entitlements.discord.skus.pro,
             discord_sku_ultra: toml_cfg.entitlements.discord.skus.ultra,
 
+            patreon,
+
             radio_stations: radio::validate_all(radio::load()?),
+
+            scam_domains_path: toml_cfg
+                .automod
+                .scam_domains
+                .map_or_else(|| PathBuf::from(DEFAULT_SCAM_DOMAINS), PathBuf::from),
         })
     }
 }
 
-fn youtube_cookies_path() -> Option<PathBuf> {
-    let raw = env::var("YOUTUBE_COOKIES_FILE").ok()?;
+fn youtube_cookies_path(raw: Option<String>) -> Option<PathBuf> {
+    let raw = raw?;
     let path = PathBuf::from(raw.trim());
 
     if path.as_os_str().is_empty() {
@@ -240,6 +277,65 @@ fn load_pelican_config(toml_cfg: &TomlConfig) -> Option<PelicanConfig> {
     }
 }
 
+fn load_patreon_config(toml_cfg: &TomlConfig) -> Option<PatreonConfig> {
+    let toml = &toml_cfg.entitlements.patreon;
+
+    match (
+        env::var("PATREON_WEBHOOK_SECRET").ok(),
+        env::var("PATREON_CLIENT_ID").ok(),
+        env::var("PATREON_CLIENT_SECRET").ok(),
+        env::var("PATREON_CREATOR_TOKEN").ok(),
+        toml.campaign_id.clone(),
+    ) {
+        (
+            Some(webhook_secret),
+            Some(client_id),
+            Some(client_secret),
+            Some(creator_token),
+            Some(campaign_id),
+        ) => {
+            let tiers = toml
+                .tiers
+                .iter()
+                .filter_map(|(id, tier)| match tier.parse::<Tier>() {
+                    Ok(tier) => Some((id.clone(), tier)),
+                    Err(()) => {
+                        warn!(
+                            tier_id = %id,
+                            "[entitlements.patreon.tiers] maps a tier to {tier:?}, \
+                             expected \"pro\" or \"ultra\"; ignoring it"
+                        );
+                        None
+                    },
+                })
+                .collect();
+
+            Some(PatreonConfig {
+                webhook_secret,
+                client_id,
+                client_secret,
+                creator_token,
+                campaign_id,
+                redirect_uri: toml
+                    .redirect_uri
+                    .clone()
+                    .unwrap_or_else(|| DEFAULT_PATREON_REDIRECT_URI.to_owned()),
+                tiers,
+            })
+        },
+        (None, None, None, None, None) => None,
+        _ => {
+            warn!(
+                "Patreon config is incomplete; Patreon entitlements disabled \
+                 until PATREON_WEBHOOK_SECRET, PATREON_CLIENT_ID, \
+                 PATREON_CLIENT_SECRET and PATREON_CREATOR_TOKEN (env) plus \
+                 [entitlements.patreon].campaign_id (config.toml) are all set"
+            );
+            None
+        },
+    }
+}
+
 fn save_dir_from_path(save_path: &str) -> PathBuf {
     let name =
         save_path.trim_end_matches('/').rsplit('/').next().unwrap_or(save_path);
@@ -263,7 +359,8 @@ fn load_toml_config() -> Result<TomlConfig> {
 async fn load_db_row(pool: &PgPool) -> Result<Option<DbConfigRow>> {
     let row = sqlx::query_as!(
         DbConfigRow,
-        "SELECT error_log_webhook, normal_log_webhook FROM bot_config WHERE id = 1",
+        "SELECT error_log_webhook, normal_log_webhook, flaresolverr_url, \
+         youtube_cookies_file FROM bot_config WHERE id = 1",
     )
     .fetch_optional(pool)
     .await?;
@@ -271,6 +368,56 @@ async fn load_db_row(pool: &PgPool) -> Result<Option<DbConfigRow>> {
     Ok(row)
 }
 
+/// The `bot_config` row: values the owner can edit from the dashboard that
+/// take precedence over their env vars. `None` falls back to the env var.
+/// Read once at startup, so edits apply on the next restart.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConfigOverrides {
+    pub error_log_webhook: Option<String>,
+    pub normal_log_webhook: Option<String>,
+    pub flaresolverr_url: Option<String>,
+    pub youtube_cookies_file: Option<String>,
+}
+
+impl ConfigOverrides {
+    pub async fn load(pool: &PgPool) -> Result<Self> {
+        Ok(load_db_row(pool).await?.map(Self::from).unwrap_or_default())
+    }
+
+    pub async fn save(&self, pool: &PgPool) -> Result<()> {
+        sqlx::query!(
+            "INSERT INTO bot_config
+                (id, error_log_webhook, normal_log_webhook, flaresolverr_url,
+                 youtube_cookies_file)
+            VALUES (1, $1, $2, $3, $4)
+            ON CONFLICT (id) DO UPDATE SET
+                error_log_webhook = EXCLUDED.error_log_webhook,
+                normal_log_webhook = EXCLUDED.normal_log_webhook,
+                flaresolverr_url = EXCLUDED.flaresolverr_url,
+                youtube_cookies_file = EXCLUDED.youtube_cookies_file",
+            self.error_log_webhook,
+            self.normal_log_webhook,
+            self.flaresolverr_url,
+            self.youtube_cookies_file,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+}
+
+impl From<DbConfigRow> for ConfigOverrides {
+    fn from(row: DbConfigRow) -> Self {
+        Self {
+            error_log_webhook: row.error_log_webhook,
+            normal_log_webhook: row.normal_log_webhook,
+            flaresolverr_url: row.flaresolverr_url,
+            youtube_cookies_file: row.youtube_cookies_file,
+        }
+    }
+}
+
 #[derive(Debug, Default, Deserialize)]
 struct TomlConfig {
     #[serde(default)]
@@ -285,12 +432,29 @@ struct TomlConfig {
     pelican: TomlPelican,
     #[serde(default)]
     entitlements: TomlEntitlements,
+    #[serde(default)]
+    automod: TomlAutomod,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct TomlAutomod {
+    scam_domains: Option<String>,
 }
 
 #[derive(Debug, Default, Deserialize)]
 struct TomlEntitlements {
     #[serde(default)]
     discord: TomlDiscordEntitlements,
+    #[serde(default)]
+    patreon: TomlPatreonEntitlements,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct TomlPatreonEntitlements {
+    campaign_id: Option<String>,
+    redirect_uri: Option<String>,
+    #[serde(default)]
+    tiers: HashMap<String, String>,
 }
 
 #[derive(Debug, Default, Deserialize)]
@@ -344,4 +508,6 @@ struct TomlDashboard {
 struct DbConfigRow {
     error_log_webhook: Option<String>,
     normal_log_webhook: Option<String>,
+    flaresolverr_url: Option<String>,
+    youtube_cookies_file: Option<String>,
 }
@@ -1,20 +1,43 @@
+pub mod audit;
 pub mod bot_config;
 pub mod radio;
 pub mod registry;
 pub mod settings_store;
 pub mod tables;
 
-pub use bot_config::{BotConfig, PelicanConfig};
+pub use audit::{Actor, AuditError, ChangeSource, FieldChange, SettingsChange};
+pub use bot_config::{BotConfig, ConfigOverrides, PatreonConfig, PelicanConfig};
 pub use radio::{Genre, RadioStation};
 pub use registry::SettingsRegistry;
 pub use settings_store::{SettingsRow, SettingsStore};
 pub use tables::{
+    AiPersonas,
     AiSettingsRow,
+    AiTrigger,
+    AppealsSettingsRow,
+    AutomodAction,
+    AutomodRule,
+    AutomodRules,
+    AutomodSettingsRow,
+    CapsRule,
+    CaptchaFailAction,
+    ChannelPersona,
     Cooldowns,
+    CustomPersona,
     GreetingsSettingsRow,
     HoneypotSettingsRow,
+    HoneypotTrap,
+    MentionRule,
     MusicSettingsRow,
+    PersonaError,
+    RaidAction,
+    RaidSettingsRow,
+    RateRule,
     RolesSettingsRow,
+    SharedAction,
     SupportSettingsRow,
     TicketSettingsRow,
+    TrapAction,
+    TrapKind,
+    WordFilter,
 };
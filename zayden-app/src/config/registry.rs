@@ -3,21 +3,25 @@ use std::sync::Arc;
 use sqlx::PgPool;
 use tokio::sync::broadcast;
 
-use super::SettingsStore;
+use super::audit::{Actor, AuditError, SettingsChange};
 use super::tables::{
     AiSettingsRow,
+    AppealsSettingsRow,
+    AutomodSettingsRow,
     ChannelsSettingsRow,
     FamilySettingsRow,
     GreetingsSettingsRow,
     HoneypotSettingsRow,
     LfgSettingsRow,
     MusicSettingsRow,
+    RaidSettingsRow,
     RolesSettingsRow,
     SuggestionsSettingsRow,
     SupportSettingsRow,
     TempVoiceSettingsRow,
     TicketSettingsRow,
 };
+use super::{SettingsRow, SettingsStore};
 use crate::events::AppEvent;
 
 pub struct SettingsRegistry {
@@ -33,6 +37,9 @@ pub struct SettingsRegistry {
     pub honeypot: Arc<SettingsStore<HoneypotSettingsRow>>,
     pub greetings: Arc<SettingsStore<GreetingsSettingsRow>>,
     pub ai: Arc<SettingsStore<AiSettingsRow>>,
+    pub automod: Arc<SettingsStore<AutomodSettingsRow>>,
+    pub raid: Arc<SettingsStore<RaidSettingsRow>>,
+    pub appeals: Arc<SettingsStore<AppealsSettingsRow>>,
 }
 
 impl SettingsRegistry {
@@ -49,7 +56,10 @@ impl SettingsRegistry {
         let family = Arc::new(SettingsStore::new(db.clone(), events.clone()));
         let honeypot = Arc::new(SettingsStore::new(db.clone(), events.clone()));
         let greetings = Arc::new(SettingsStore::new(db.clone(), events.clone()));
-        let ai = Arc::new(SettingsStore::new(db, events.clone()));
+        let ai = Arc::new(SettingsStore::new(db.clone(), events.clone()));
+        let automod = Arc::new(SettingsStore::new(db.clone(), events.clone()));
+        let raid = Arc::new(SettingsStore::new(db.clone(), events.clone()));
+        let appeals = Arc::new(SettingsStore::new(db, events.clone()));
 
         SettingsStore::spawn_invalidator(Arc::clone(&support), events.subscribe());
         SettingsStore::spawn_invalidator(
@@ -69,6 +79,9 @@ impl SettingsRegistry {
         SettingsStore::spawn_invalidator(Arc::clone(&honeypot), events.subscribe());
         SettingsStore::spawn_invalidator(Arc::clone(&greetings), events.subscribe());
         SettingsStore::spawn_invalidator(Arc::clone(&ai), events.subscribe());
+        SettingsStore::spawn_invalidator(Arc::clone(&automod), events.subscribe());
+        SettingsStore::spawn_invalidator(Arc::clone(&raid), events.subscribe());
+        SettingsStore::spawn_invalidator(Arc::clone(&appeals), events.subscribe());
 
         Self {
             support,
@@ -83,6 +96,68 @@ impl SettingsRegistry {
             honeypot,
             greetings,
             ai,
+            automod,
+            raid,
+            appeals,
         }
     }
+
+    /// Undoes `change`, putting its row back to how it was beforehand.
+    pub async fn rollback(
+        &self,
+        change: &SettingsChange,
+        actor: Actor,
+    ) -> Result<(), AuditError> {
+        let guild_id = i64::try_from(change.guild_id).unwrap_or(i64::MAX);
+        let before = change.before.as_ref();
+
+        match change.table.as_str() {
+            t if t == SupportSettingsRow::TABLE => {
+                self.support.restore(guild_id, before, actor).await?;
+            },
+            t if t == SuggestionsSettingsRow::TABLE => {
+                self.suggestions.restore(guild_id, before, actor).await?;
+            },
+            t if t == ChannelsSettingsRow::TABLE => {
+                self.channels.restore(guild_id, before, actor).await?;
+            },
+            t if t == RolesSettingsRow::TABLE => {
+                self.roles.restore(guild_id, before, actor).await?;
+            },
+            t if t == TempVoiceSettingsRow::TABLE => {
+                self.temp_voice.restore(guild_id, before, actor).await?;
+            },
+            t if t == LfgSettingsRow::TABLE => {
+                self.lfg.restore(guild_id, before, actor).await?;
+            },
+            t if t == MusicSettingsRow::TABLE => {
+                self.music.restore(guild_id, before, actor).await?;
+            },
+            t if t == FamilySettingsRow::TABLE => {
+                self.family.restore(guild_id, before, actor).await?;
+            },
+            t if t == HoneypotSettingsRow::TABLE => {
+                self.honeypot.restore(guild_id, before, actor).await?;
+            },
+            t if t == GreetingsSettingsRow::TABLE => {
+                self.greetings.restore(guild_id, before, actor).await?;
+            },
+            t if t == AiSettingsRow::TABLE => {
+                self.ai.restore(guild_id, before, actor).await?;
+            },
+            t if t == AutomodSettingsRow::TABLE => {
+                self.automod.restore(guild_id, before, actor).await?;
+            },
+            t if t == RaidSettingsRow::TABLE => {
+                self.raid.restore(guild_id, before, actor).await?;
+            },
+            t if t == AppealsSettingsRow::TABLE => {
+                self.appeals.restore(guild_id, before, actor).await?;
+            },
+            // The ticket counter is bookkeeping, never recorded, so never undone.
+            other => return Err(AuditError::UnknownTable(other.to_string())),
+        }
+
+        Ok(())
+    }
 }
@@ -2,14 +2,20 @@ use std::future::Future;
 use std::sync::Arc;
 
 use moka::future::Cache;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
 use sqlx::PgPool;
 use tokio::sync::broadcast::error::RecvError;
 use tokio::sync::broadcast::{Receiver, Sender};
 use tracing::warn;
 
+use super::audit::{self, Actor, AuditError};
 use crate::events::AppEvent;
 
-pub trait SettingsRow: Sized + Send + Sync + Clone + 'static {
+pub trait SettingsRow:
+    Sized + Send + Sync + Clone + Serialize + DeserializeOwned + 'static
+{
     const TABLE: &'static str;
 
     fn empty(guild_id: i64) -> Self;
@@ -74,6 +80,8 @@ impl<Row: SettingsRow> SettingsStore<Row> {
         Ok(())
     }
 
+    /// Applies `f` to the guild's row without recording it in the settings
+    /// history. Use [`update_by`](Self::update_by) for changes a member made.
     pub async fn update<F>(
         &self,
         guild_id: i64,
@@ -82,16 +90,60 @@ impl<Row: SettingsRow> SettingsStore<Row> {
     where
         F: FnOnce(&mut Row),
     {
-        let mut row = self
-            .try_get(guild_id)
-            .await?
-            .map_or_else(|| Row::empty(guild_id), |arc| (*arc).clone());
+        self.write(guild_id, None, f).await
+    }
+
+    /// Applies `f` to the guild's row and records the change against `actor`.
+    pub async fn update_by<F>(
+        &self,
+        guild_id: i64,
+        actor: Actor,
+        f: F,
+    ) -> Result<Arc<Row>, sqlx::Error>
+    where
+        F: FnOnce(&mut Row),
+    {
+        self.write(guild_id, Some(actor), f).await
+    }
+
+    /// Puts the guild's row back to `snapshot`, a version from the settings
+    /// history, or to its defaults when `None`.
+    pub async fn restore(
+        &self,
+        guild_id: i64,
+        snapshot: Option<&Value>,
+        actor: Actor,
+    ) -> Result<Arc<Row>, AuditError> {
+        let restored = match snapshot {
+            Some(snapshot) => serde_json::from_value::<Row>(snapshot.clone())?,
+            None => Row::empty(guild_id),
+        };
+
+        Ok(self.write(guild_id, Some(actor), |row| *row = restored).await?)
+    }
+
+    async fn write<F>(
+        &self,
+        guild_id: i64,
+        actor: Option<Actor>,
+        f: F,
+    ) -> Result<Arc<Row>, sqlx::Error>
+    where
+        F: FnOnce(&mut Row),
+    {
+        let before = self.try_get(guild_id).await?;
+        let mut row =
+            before.as_deref().map_or_else(|| Row::empty(guild_id), Clone::clone);
         f(&mut row);
 
         self.seed_guild(guild_id).await?;
 
         let saved = row.upsert(&self.db).await?;
 
+        if let Some(actor) = actor {
+            self.record(guild_id, actor, before.as_deref(), &saved).await?;
+        }
+
         let entry = Arc::new(saved);
         self.cache.insert(guild_id, Arc::clone(&entry)).await;
         let _ = self
@@ -101,6 +153,26 @@ impl<Row: SettingsRow> SettingsStore<Row> {
         Ok(entry)
     }
 
+    async fn record(
+        &self,
+        guild_id: i64,
+        actor: Actor,
+        before: Option<&Row>,
+        after: &Row,
+    ) -> Result<(), sqlx::Error> {
+        let encode = |e: serde_json::Error| sqlx::Error::Encode(Box::new(e));
+        let before = before.map(serde_json::to_value).transpose().map_err(encode)?;
+        let after = serde_json::to_value(after).map_err(encode)?;
+
+        // A save that changed nothing is not worth a history entry.
+        if before.as_ref() == Some(&after) {
+            return Ok(());
+        }
+
+        audit::record(&self.db, guild_id, actor, Row::TABLE, before.as_ref(), &after)
+            .await
+    }
+
     pub fn spawn_invalidator(store: Arc<Self>, mut rx: Receiver<AppEvent>) {
         tokio::spawn(async move {
             loop {
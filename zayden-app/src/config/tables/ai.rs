@@ -1,26 +1,338 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
+use sqlx::types::Json;
 
 use crate::config::SettingsRow;
 
-#[derive(Debug, Clone, sqlx::FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct AiSettingsRow {
     pub guild_id: i64,
     pub enabled: bool,
     pub channel_id: Option<i64>,
+    /// As [`AiTrigger::as_str`]. Defaults as the column does, for snapshots
+    /// taken before there were trigger modes.
+    #[serde(default = "AiSettingsRow::default_trigger_mode")]
+    pub trigger_mode: String,
+    /// Lowercased words and phrases for [`AiTrigger::Keyword`].
+    #[serde(default)]
+    pub keywords: Vec<String>,
+    #[serde(default)]
+    pub personas: Json<AiPersonas>,
 }
 
 impl AiSettingsRow {
+    pub const MAX_KEYWORDS: usize = 20;
+    pub const MAX_KEYWORD_CHARS: usize = 50;
+
+    fn default_trigger_mode() -> String {
+        AiTrigger::Mention.as_str().to_string()
+    }
+
     #[must_use]
     pub fn responds_in(&self, channel_id: i64) -> bool {
         self.enabled && self.channel_id.is_none_or(|only| only == channel_id)
     }
+
+    #[must_use]
+    pub fn trigger(&self) -> AiTrigger {
+        AiTrigger::parse(&self.trigger_mode)
+    }
+
+    /// Whether `content` has one of the keywords as a whole word or phrase,
+    /// ignoring case and punctuation.
+    #[must_use]
+    pub fn matches_keyword(&self, content: &str) -> bool {
+        let content = format!(" {} ", words(content));
+
+        self.keywords.iter().any(|keyword| {
+            let keyword = words(keyword);
+            !keyword.is_empty() && content.contains(&format!(" {keyword} "))
+        })
+    }
+
+    /// The keyword list a form describes: comma or newline separated, blank
+    /// and repeated entries dropped, long ones cut short.
+    #[must_use]
+    pub fn parse_keywords(input: &str) -> Vec<String> {
+        let mut keywords: Vec<String> = Vec::new();
+
+        for keyword in input.split([',', '\n']) {
+            let keyword: String = words(keyword)
+                .chars()
+                .take(Self::MAX_KEYWORD_CHARS)
+                .collect::<String>()
+                .trim()
+                .to_string();
+
+            if !keyword.is_empty() && !keywords.contains(&keyword) {
+                keywords.push(keyword);
+            }
+        }
+
+        keywords.truncate(Self::MAX_KEYWORDS);
+        keywords
+    }
+}
+
+/// `input` lowercased, with anything but letters and digits squeezed to
+/// single spaces.
+fn words(input: &str) -> String {
+    input
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// What makes the bot answer a message, besides being mentioned, which always
+/// does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AiTrigger {
+    Mention,
+    /// Replying to one of the bot's messages.
+    Reply,
+    /// A message with one of the guild's keywords.
+    Keyword,
+    /// Every message in the AI channel. Without a channel set, only mentions.
+    Channel,
+}
+
+impl AiTrigger {
+    pub const ALL: [Self; 4] =
+        [Self::Mention, Self::Reply, Self::Keyword, Self::Channel];
+
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Mention => "mention",
+            Self::Reply => "reply",
+            Self::Keyword => "keyword",
+            Self::Channel => "channel",
+        }
+    }
+
+    /// Unknown values read as [`Self::Mention`], the quietest mode.
+    #[must_use]
+    pub fn parse(value: &str) -> Self {
+        Self::ALL
+            .into_iter()
+            .find(|trigger| trigger.as_str() == value.trim())
+            .unwrap_or(Self::Mention)
+    }
+}
+
+/// A character a guild wrote for the bot to play. Only used on Pro servers.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CustomPersona {
+    pub name: String,
+    /// Who the character is.
+    pub prompt: String,
+    /// How the character writes.
+    pub style: String,
+    /// Capped by the tier's own limit.
+    pub word_limit: u32,
+    pub temperature: f32,
+}
+
+impl CustomPersona {
+    pub const DEFAULT_TEMPERATURE: f32 = 0.8;
+    pub const DEFAULT_WORD_LIMIT: u32 = 100;
+    pub const MAX_NAME_CHARS: usize = 32;
+    pub const MAX_PROMPT_CHARS: usize = 2000;
+    pub const MAX_STYLE_CHARS: usize = 1000;
+    pub const MAX_TEMPERATURE: f32 = 1.5;
+    pub const MAX_WORD_LIMIT: u32 = 300;
+    pub const MIN_WORD_LIMIT: u32 = 10;
+
+    /// The persona a form describes. Blank or invalid numbers fall back to
+    /// the defaults; out-of-range ones are clamped.
+    pub fn from_form(
+        name: &str,
+        prompt: &str,
+        style: &str,
+        word_limit: &str,
+        temperature: &str,
+    ) -> Result<Self, PersonaError> {
+        let name = name.split_whitespace().collect::<Vec<_>>().join(" ");
+        if name.is_empty() {
+            return Err(PersonaError::MissingName);
+        }
+        if name.chars().count() > Self::MAX_NAME_CHARS {
+            return Err(PersonaError::TooLong("name", Self::MAX_NAME_CHARS));
+        }
+        if !name.chars().all(|c| c.is_alphanumeric() || " -_'.".contains(c)) {
+            return Err(PersonaError::InvalidName);
+        }
+
+        let prompt = prompt.trim();
+        if prompt.is_empty() {
+            return Err(PersonaError::MissingPrompt);
+        }
+        if prompt.chars().count() > Self::MAX_PROMPT_CHARS {
+            return Err(PersonaError::TooLong("prompt", Self::MAX_PROMPT_CHARS));
+        }
+
+        let style = style.trim();
+        if style.chars().count() > Self::MAX_STYLE_CHARS {
+            return Err(PersonaError::TooLong("style rules", Self::MAX_STYLE_CHARS));
+        }
+
+        let word_limit = word_limit
+            .trim()
+            .parse::<u32>()
+            .unwrap_or(Self::DEFAULT_WORD_LIMIT)
+            .clamp(Self::MIN_WORD_LIMIT, Self::MAX_WORD_LIMIT);
+
+        let temperature = temperature
+            .trim()
+            .parse::<f32>()
+            .ok()
+            .filter(|t| t.is_finite())
+            .unwrap_or(Self::DEFAULT_TEMPERATURE)
+            .clamp(0.0, Self::MAX_TEMPERATURE);
+
+        Ok(Self {
+            name,
+            prompt: prompt.to_string(),
+            style: style.to_string(),
+            word_limit,
+            temperature,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PersonaError {
+    MissingName,
+    InvalidName,
+    MissingPrompt,
+    /// The field, and the most characters it takes.
+    TooLong(&'static str, usize),
+    TooMany,
+}
+
+impl fmt::Display for PersonaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingName => f.write_str("the persona needs a name"),
+            Self::InvalidName => f.write_str(
+                "persona names can only use letters, numbers, spaces and - _ ' .",
+            ),
+            Self::MissingPrompt => f.write_str("the persona needs a prompt"),
+            Self::TooLong(field, max) => {
+                write!(f, "the {field} can be at most {max} characters")
+            },
+            Self::TooMany => write!(
+                f,
+                "a server can have at most {} personas; remove one first",
+                AiPersonas::MAX_PERSONAS
+            ),
+        }
+    }
+}
+
+impl std::error::Error for PersonaError {}
+
+/// A channel's own persona. `None` keeps the bot's own character there.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChannelPersona {
+    pub channel_id: i64,
+    pub persona: Option<String>,
+}
+
+/// A guild's personas and where each speaks. Names match ignoring case.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AiPersonas {
+    pub personas: Vec<CustomPersona>,
+    /// Used wherever no channel picks one. `None` is the bot's own character.
+    pub default: Option<String>,
+    pub channels: Vec<ChannelPersona>,
+}
+
+impl AiPersonas {
+    pub const MAX_PERSONAS: usize = 10;
+
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&CustomPersona> {
+        self.personas.iter().find(|p| p.name.eq_ignore_ascii_case(name.trim()))
+    }
+
+    /// The persona that speaks in `channel_id`, `None` for the bot's own.
+    #[must_use]
+    pub fn for_channel(&self, channel_id: i64) -> Option<&CustomPersona> {
+        let name = self
+            .channels
+            .iter()
+            .find(|c| c.channel_id == channel_id)
+            .map_or(self.default.as_deref(), |c| c.persona.as_deref())?;
+
+        self.get(name)
+    }
+
+    /// Adds `persona`, or replaces the one with its name.
+    pub fn save(&mut self, persona: CustomPersona) -> Result<(), PersonaError> {
+        if let Some(existing) = self
+            .personas
+            .iter_mut()
+            .find(|p| p.name.eq_ignore_ascii_case(&persona.name))
+        {
+            *existing = persona;
+            return Ok(());
+        }
+
+        if self.personas.len() >= Self::MAX_PERSONAS {
+            return Err(PersonaError::TooMany);
+        }
+
+        self.personas.push(persona);
+        Ok(())
+    }
+
+    /// Removes the persona called `name` and every place it was picked.
+    /// `false` if there was none.
+    pub fn remove(&mut self, name: &str) -> bool {
+        let before = self.personas.len();
+        self.personas.retain(|p| !p.name.eq_ignore_ascii_case(name.trim()));
+
+        if self.personas.len() == before {
+            return false;
+        }
+
+        let picks = |picked: &Option<String>| {
+            picked.as_deref().is_some_and(|p| p.eq_ignore_ascii_case(name.trim()))
+        };
+        if picks(&self.default) {
+            self.default = None;
+        }
+        self.channels.retain(|c| !picks(&c.persona));
+
+        true
+    }
+
+    /// Picks `persona` for `channel_id`, replacing any earlier pick there.
+    pub fn set_channel(&mut self, channel_id: i64, persona: Option<String>) {
+        self.channels.retain(|c| c.channel_id != channel_id);
+        self.channels.push(ChannelPersona { channel_id, persona });
+    }
 }
 
 impl SettingsRow for AiSettingsRow {
     const TABLE: &'static str = "ai_settings";
 
     fn empty(guild_id: i64) -> Self {
-        Self { guild_id, enabled: false, channel_id: None }
+        Self {
+            guild_id,
+            enabled: false,
+            channel_id: None,
+            trigger_mode: AiTrigger::Mention.as_str().to_string(),
+            keywords: Vec::new(),
+            personas: Json(AiPersonas::default()),
+        }
     }
 
     async fn select(
@@ -30,7 +342,8 @@ impl SettingsRow for AiSettingsRow {
         sqlx::query_as!(
             Self,
             r#"
-            SELECT guild_id, enabled, channel_id
+            SELECT guild_id, enabled, channel_id, trigger_mode, keywords,
+                   personas AS "personas: Json<AiPersonas>"
             FROM ai_settings
             WHERE guild_id = $1
             "#,
@@ -44,17 +357,25 @@ impl SettingsRow for AiSettingsRow {
         sqlx::query_as!(
             Self,
             r#"
-            INSERT INTO ai_settings (guild_id, enabled, channel_id)
-            VALUES ($1, $2, $3)
+            INSERT INTO ai_settings
+                (guild_id, enabled, channel_id, trigger_mode, keywords, personas)
+            VALUES ($1, $2, $3, $4, $5, $6)
             ON CONFLICT (guild_id) DO UPDATE SET
                 enabled = EXCLUDED.enabled,
                 channel_id = EXCLUDED.channel_id,
+                trigger_mode = EXCLUDED.trigger_mode,
+                keywords = EXCLUDED.keywords,
+                personas = EXCLUDED.personas,
                 updated_at = now()
-            RETURNING guild_id, enabled, channel_id
+            RETURNING guild_id, enabled, channel_id, trigger_mode, keywords,
+                      personas AS "personas: Json<AiPersonas>"
             "#,
             self.guild_id,
             self.enabled,
-            self.channel_id
+            self.channel_id,
+            self.trigger_mode,
+            &self.keywords,
+            &self.personas as &Json<AiPersonas>
         )
         .fetch_one(pool)
         .await
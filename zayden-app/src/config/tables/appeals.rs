@@ -0,0 +1,65 @@
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+
+use crate::config::SettingsRow;
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct AppealsSettingsRow {
+    pub guild_id: i64,
+    pub enabled: bool,
+    /// Where appeals are posted for staff to decide.
+    pub channel_id: Option<i64>,
+}
+
+impl AppealsSettingsRow {
+    /// Appeals need somewhere to go, so a guild without a channel takes none.
+    #[must_use]
+    pub const fn is_open(&self) -> bool {
+        self.enabled && self.channel_id.is_some()
+    }
+}
+
+impl SettingsRow for AppealsSettingsRow {
+    const TABLE: &'static str = "appeals_settings";
+
+    fn empty(guild_id: i64) -> Self {
+        Self { guild_id, enabled: false, channel_id: None }
+    }
+
+    async fn select(
+        pool: &PgPool,
+        guild_id: i64,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            SELECT guild_id, enabled, channel_id
+            FROM appeals_settings
+            WHERE guild_id = $1
+            "#,
+            guild_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    async fn upsert(&self, pool: &PgPool) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            INSERT INTO appeals_settings (guild_id, enabled, channel_id)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (guild_id) DO UPDATE SET
+                enabled = EXCLUDED.enabled,
+                channel_id = EXCLUDED.channel_id,
+                updated_at = now()
+            RETURNING guild_id, enabled, channel_id
+            "#,
+            self.guild_id,
+            self.enabled,
+            self.channel_id
+        )
+        .fetch_one(pool)
+        .await
+    }
+}
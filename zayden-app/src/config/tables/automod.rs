@@ -0,0 +1,306 @@
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use sqlx::types::Json;
+
+use crate::config::SettingsRow;
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct AutomodSettingsRow {
+    pub guild_id: i64,
+    pub enabled: bool,
+    pub exempt_admins: bool,
+    pub exempt_role_id: Option<i64>,
+    /// Where every automod action is reported.
+    pub log_channel_id: Option<i64>,
+    /// How long [`AutomodAction::Timeout`] lasts.
+    pub timeout_minutes: i32,
+    pub rules: Json<AutomodRules>,
+}
+
+/// What a rule does to the author of a message it catches. Every action
+/// deletes the message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AutomodAction {
+    Delete,
+    /// Also records a warning in the infraction system.
+    Warn,
+    /// Also times the author out.
+    Timeout,
+}
+
+impl AutomodAction {
+    pub const ALL: [Self; 3] = [Self::Delete, Self::Warn, Self::Timeout];
+
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Delete => "delete",
+            Self::Warn => "warn",
+            Self::Timeout => "timeout",
+        }
+    }
+
+    /// `None` for anything else, which the forms use for "off".
+    #[must_use]
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|action| action.as_str() == value.trim())
+    }
+}
+
+/// Which rule caught a message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AutomodRule {
+    Rate,
+    Duplicates,
+    Mentions,
+    Invites,
+    ScamLinks,
+    Caps,
+    Filter,
+}
+
+impl AutomodRule {
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Rate => "message rate",
+            Self::Duplicates => "duplicate messages",
+            Self::Mentions => "mass mentions",
+            Self::Invites => "invite links",
+            Self::ScamLinks => "scam links",
+            Self::Caps => "excessive caps",
+            Self::Filter => "word filter",
+        }
+    }
+}
+
+/// More than `max_messages` in `per_seconds`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RateRule {
+    pub max_messages: u32,
+    pub per_seconds: u32,
+    pub action: AutomodAction,
+}
+
+impl RateRule {
+    /// The rule the form describes, `None` when its action is off. Blank or
+    /// invalid numbers fall back to `default`'s.
+    #[must_use]
+    pub fn from_form(
+        action: &str,
+        max_messages: &str,
+        per_seconds: &str,
+        default: Self,
+    ) -> Option<Self> {
+        Some(Self {
+            max_messages: parse_bounded(max_messages, default.max_messages, 1, 50),
+            per_seconds: parse_bounded(
+                per_seconds,
+                default.per_seconds,
+                1,
+                AutomodRules::MAX_WINDOW_SECONDS,
+            ),
+            action: AutomodAction::parse(action)?,
+        })
+    }
+}
+
+/// More than `max_mentions` users, roles, @everyone or @here in one message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MentionRule {
+    pub max_mentions: u32,
+    pub action: AutomodAction,
+}
+
+impl MentionRule {
+    #[must_use]
+    pub fn from_form(action: &str, max_mentions: &str) -> Option<Self> {
+        Some(Self {
+            max_mentions: parse_bounded(
+                max_mentions,
+                AutomodRules::DEFAULT_MENTIONS.max_mentions,
+                1,
+                100,
+            ),
+            action: AutomodAction::parse(action)?,
+        })
+    }
+}
+
+/// Messages of at least `min_length` letters, more than `max_percent` of
+/// them capitals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CapsRule {
+    pub max_percent: u32,
+    pub min_length: u32,
+    pub action: AutomodAction,
+}
+
+impl CapsRule {
+    #[must_use]
+    pub fn from_form(
+        action: &str,
+        max_percent: &str,
+        min_length: &str,
+    ) -> Option<Self> {
+        let default = AutomodRules::DEFAULT_CAPS;
+
+        Some(Self {
+            max_percent: parse_bounded(max_percent, default.max_percent, 1, 100),
+            min_length: parse_bounded(min_length, default.min_length, 1, 2000),
+            action: AutomodAction::parse(action)?,
+        })
+    }
+}
+
+/// A custom filter: a regular expression, or a comma-separated list of words
+/// matched whole and ignoring case.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WordFilter {
+    pub pattern: String,
+    pub regex: bool,
+    pub action: AutomodAction,
+}
+
+impl WordFilter {
+    /// Compiled patterns are capped well below the regex crate's default,
+    /// since every guild message is run against them.
+    const REGEX_SIZE_LIMIT: usize = 64 * 1024;
+
+    /// The filter's regular expression, case-insensitive. Only meaningful
+    /// when [`Self::regex`] is set.
+    pub fn compile(&self) -> Result<Regex, regex::Error> {
+        RegexBuilder::new(&self.pattern)
+            .case_insensitive(true)
+            .size_limit(Self::REGEX_SIZE_LIMIT)
+            .build()
+    }
+
+    /// The words and phrases of a word list, lowercased.
+    #[must_use]
+    pub fn words(&self) -> Vec<String> {
+        self.pattern
+            .split([',', '\n'])
+            .map(|word| word.split_whitespace().collect::<Vec<_>>().join(" "))
+            .filter(|word| !word.is_empty())
+            .map(|word| word.to_lowercase())
+            .collect()
+    }
+}
+
+/// Every rule, `None` when it is off.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AutomodRules {
+    pub rate: Option<RateRule>,
+    /// The same message more than `max_messages` times in `per_seconds`.
+    pub duplicates: Option<RateRule>,
+    pub mentions: Option<MentionRule>,
+    pub invites: Option<AutomodAction>,
+    pub scam_links: Option<AutomodAction>,
+    pub caps: Option<CapsRule>,
+    pub filters: Vec<WordFilter>,
+}
+
+impl AutomodRules {
+    pub const DEFAULT_CAPS: CapsRule =
+        CapsRule { max_percent: 70, min_length: 10, action: AutomodAction::Delete };
+    pub const DEFAULT_DUPLICATES: RateRule =
+        RateRule { max_messages: 3, per_seconds: 30, action: AutomodAction::Delete };
+    pub const DEFAULT_MENTIONS: MentionRule =
+        MentionRule { max_mentions: 5, action: AutomodAction::Delete };
+    pub const DEFAULT_RATE: RateRule =
+        RateRule { max_messages: 5, per_seconds: 5, action: AutomodAction::Delete };
+    pub const MAX_FILTERS: usize = 20;
+    pub const MAX_PATTERN_CHARS: usize = 500;
+    /// The longest window the rate and duplicate rules look back over.
+    pub const MAX_WINDOW_SECONDS: u32 = 60;
+}
+
+fn parse_bounded(input: &str, default: u32, min: u32, max: u32) -> u32 {
+    input.trim().parse::<u32>().unwrap_or(default).clamp(min, max)
+}
+
+impl AutomodSettingsRow {
+    pub const DEFAULT_TIMEOUT_MINUTES: i32 = 10;
+    /// Discord's longest timeout, 28 days.
+    pub const MAX_TIMEOUT_MINUTES: i32 = 28 * 24 * 60;
+
+    #[must_use]
+    pub fn parse_timeout_minutes(input: &str) -> i32 {
+        input
+            .trim()
+            .parse::<i32>()
+            .unwrap_or(Self::DEFAULT_TIMEOUT_MINUTES)
+            .clamp(1, Self::MAX_TIMEOUT_MINUTES)
+    }
+}
+
+impl SettingsRow for AutomodSettingsRow {
+    const TABLE: &'static str = "automod_settings";
+
+    fn empty(guild_id: i64) -> Self {
+        Self {
+            guild_id,
+            enabled: false,
+            exempt_admins: true,
+            exempt_role_id: None,
+            log_channel_id: None,
+            timeout_minutes: Self::DEFAULT_TIMEOUT_MINUTES,
+            rules: Json(AutomodRules::default()),
+        }
+    }
+
+    async fn select(
+        pool: &PgPool,
+        guild_id: i64,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            SELECT guild_id, enabled, exempt_admins, exempt_role_id,
+                   log_channel_id, timeout_minutes,
+                   rules AS "rules: Json<AutomodRules>"
+            FROM automod_settings
+            WHERE guild_id = $1
+            "#,
+            guild_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    async fn upsert(&self, pool: &PgPool) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            INSERT INTO automod_settings
+                (guild_id, enabled, exempt_admins, exempt_role_id,
+                 log_channel_id, timeout_minutes, rules)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            ON CONFLICT (guild_id) DO UPDATE SET
+                enabled = EXCLUDED.enabled,
+                exempt_admins = EXCLUDED.exempt_admins,
+                exempt_role_id = EXCLUDED.exempt_role_id,
+                log_channel_id = EXCLUDED.log_channel_id,
+                timeout_minutes = EXCLUDED.timeout_minutes,
+                rules = EXCLUDED.rules,
+                updated_at = now()
+            RETURNING guild_id, enabled, exempt_admins, exempt_role_id,
+                      log_channel_id, timeout_minutes,
+                      rules AS "rules: Json<AutomodRules>"
+            "#,
+            self.guild_id,
+            self.enabled,
+            self.exempt_admins,
+            self.exempt_role_id,
+            self.log_channel_id,
+            self.timeout_minutes,
+            &self.rules as &Json<AutomodRules>
+        )
+        .fetch_one(pool)
+        .await
+    }
+}
@@ -1,8 +1,9 @@
+use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
 
 use crate::config::SettingsRow;
 
-#[derive(Debug, Clone, sqlx::FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct ChannelsSettingsRow {
     pub guild_id: i64,
     pub rules_channel_id: Option<i64>,
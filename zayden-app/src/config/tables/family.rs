@@ -1,8 +1,9 @@
+use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
 
 use crate::config::SettingsRow;
 
-#[derive(Debug, Clone, sqlx::FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct FamilySettingsRow {
     pub guild_id: i64,
     pub max_partners: i32,
@@ -1,3 +1,4 @@
+use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
 
 use crate::config::SettingsRow;
@@ -25,7 +26,7 @@ impl Cooldowns {
     }
 }
 
-#[derive(Debug, Clone, sqlx::FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct GreetingsSettingsRow {
     pub guild_id: i64,
     pub morning_message: Option<String>,
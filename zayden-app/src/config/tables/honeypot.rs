@@ -1,19 +1,231 @@
+use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
+use sqlx::types::Json;
 
 use crate::config::SettingsRow;
 
-#[derive(Debug, Clone, sqlx::FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct HoneypotSettingsRow {
     pub guild_id: i64,
     pub channel_id: Option<i64>,
     pub exempt_admins: bool,
     pub exempt_role_id: Option<i64>,
     pub purge_seconds: i32,
+    /// Hits here count on the shared threat list, and joins are screened
+    /// against it. Defaulted so activity snapshots from before the column
+    /// still restore.
+    #[serde(default)]
+    pub share_hits: bool,
+    /// How many other sharing guilds a joining member must have tripped a
+    /// honeypot in before [`Self::shared_action`] applies. 0 only shares.
+    #[serde(default)]
+    pub shared_threshold: i32,
+    /// As [`SharedAction::as_str`].
+    #[serde(default = "HoneypotSettingsRow::default_shared_action")]
+    pub shared_action: String,
+    /// Where every honeypot hit, and shared-list flags and bans, are
+    /// reported. Renamed from `alert_channel_id`, which old snapshots carry.
+    #[serde(default, alias = "alert_channel_id")]
+    pub log_channel_id: Option<i64>,
+    /// Traps beyond `channel_id`, each with its own action.
+    #[serde(default)]
+    pub traps: Json<Vec<HoneypotTrap>>,
+}
+
+/// What sets a trap off.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrapKind {
+    /// Posting in the channel.
+    Channel,
+    /// Being given the role, usually a self-assignable bait like "free nitro".
+    Role,
+}
+
+impl TrapKind {
+    pub const ALL: [Self; 2] = [Self::Channel, Self::Role];
+
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Channel => "channel",
+            Self::Role => "role",
+        }
+    }
+
+    #[must_use]
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.as_str() == value.trim())
+    }
+}
+
+/// What a trap does to whoever sets it off.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrapAction {
+    /// Ban, purging recent messages, then unban at once.
+    SoftBan,
+    Ban,
+    Timeout,
+    /// Give the trap's quarantine role.
+    Quarantine,
+}
+
+impl TrapAction {
+    pub const ALL: [Self; 4] =
+        [Self::SoftBan, Self::Ban, Self::Timeout, Self::Quarantine];
+
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::SoftBan => "soft_ban",
+            Self::Ban => "ban",
+            Self::Timeout => "timeout",
+            Self::Quarantine => "quarantine",
+        }
+    }
+
+    #[must_use]
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|action| action.as_str() == value.trim())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HoneypotTrap {
+    pub kind: TrapKind,
+    /// The channel or role, by [`Self::kind`].
+    pub target_id: i64,
+    pub action: TrapAction,
+    /// For [`TrapAction::SoftBan`] and [`TrapAction::Ban`].
+    #[serde(default)]
+    pub purge_seconds: i32,
+    /// For [`TrapAction::Timeout`].
+    #[serde(default)]
+    pub timeout_minutes: i32,
+    /// For [`TrapAction::Quarantine`].
+    #[serde(default)]
+    pub quarantine_role_id: Option<i64>,
+}
+
+impl HoneypotTrap {
+    pub const DEFAULT_TIMEOUT_MINUTES: i32 = 24 * 60;
+    /// Discord's longest timeout, 28 days.
+    pub const MAX_TIMEOUT_MINUTES: i32 = 28 * 24 * 60;
+
+    #[must_use]
+    pub fn parse_timeout_minutes(input: &str) -> i32 {
+        input
+            .trim()
+            .parse::<i32>()
+            .unwrap_or(Self::DEFAULT_TIMEOUT_MINUTES)
+            .clamp(1, Self::MAX_TIMEOUT_MINUTES)
+    }
+
+    /// How long [`TrapAction::Timeout`] times a member out.
+    #[must_use]
+    pub fn timeout(&self) -> jiff::SignedDuration {
+        jiff::SignedDuration::from_mins(i64::from(
+            self.timeout_minutes.clamp(1, Self::MAX_TIMEOUT_MINUTES),
+        ))
+    }
+
+    #[must_use]
+    pub fn purge_seconds_u32(&self) -> u32 {
+        u32::try_from(
+            self.purge_seconds.clamp(0, HoneypotSettingsRow::MAX_PURGE_SECONDS),
+        )
+        .unwrap_or(0)
+    }
+}
+
+/// What a sharing guild does to a joining member who is over the threshold.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SharedAction {
+    Flag,
+    Ban,
+}
+
+impl SharedAction {
+    pub const ALL: [Self; 2] = [Self::Flag, Self::Ban];
+
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Flag => "flag",
+            Self::Ban => "ban",
+        }
+    }
+
+    /// Unknown values read as [`Self::Flag`], the action that can't hurt.
+    #[must_use]
+    pub fn parse(value: &str) -> Self {
+        Self::ALL
+            .into_iter()
+            .find(|action| action.as_str() == value.trim())
+            .unwrap_or(Self::Flag)
+    }
 }
 
 impl HoneypotSettingsRow {
     pub const DEFAULT_PURGE_SECONDS: i32 = 24 * 60 * 60;
     pub const MAX_PURGE_SECONDS: i32 = 7 * 24 * 60 * 60;
+    pub const MAX_SHARED_THRESHOLD: i32 = 25;
+    pub const MAX_TRAPS: usize = 10;
+
+    fn default_shared_action() -> String {
+        SharedAction::Flag.as_str().to_string()
+    }
+
+    #[must_use]
+    pub fn parse_shared_threshold(input: &str) -> i32 {
+        input.trim().parse::<i32>().unwrap_or(0).clamp(0, Self::MAX_SHARED_THRESHOLD)
+    }
+
+    /// The trap set off by posting in `channel_id`, if any. The honeypot
+    /// channel is a soft-ban trap with the guild's purge window.
+    #[must_use]
+    pub fn channel_trap(&self, channel_id: i64) -> Option<HoneypotTrap> {
+        if self.channel_id == Some(channel_id) {
+            return Some(HoneypotTrap {
+                kind: TrapKind::Channel,
+                target_id: channel_id,
+                action: TrapAction::SoftBan,
+                purge_seconds: self.purge_seconds,
+                timeout_minutes: 0,
+                quarantine_role_id: None,
+            });
+        }
+
+        self.traps
+            .iter()
+            .find(|trap| {
+                trap.kind == TrapKind::Channel && trap.target_id == channel_id
+            })
+            .cloned()
+    }
+
+    /// The first bait-role trap among `role_ids`.
+    #[must_use]
+    pub fn role_trap(&self, role_ids: &[i64]) -> Option<&HoneypotTrap> {
+        self.traps.iter().find(|trap| {
+            trap.kind == TrapKind::Role && role_ids.contains(&trap.target_id)
+        })
+    }
+
+    #[must_use]
+    pub fn shared_action(&self) -> SharedAction {
+        SharedAction::parse(&self.shared_action)
+    }
+
+    /// Whether a joining member seen in `guilds` other sharing guilds is
+    /// acted on here.
+    #[must_use]
+    pub fn screens(&self, guilds: i64) -> bool {
+        self.share_hits
+            && self.shared_threshold > 0
+            && guilds >= i64::from(self.shared_threshold)
+    }
 
     #[must_use]
     pub fn parse_purge_seconds(input: &str) -> i32 {
@@ -41,6 +253,11 @@ impl SettingsRow for HoneypotSettingsRow {
             exempt_admins: false,
             exempt_role_id: None,
             purge_seconds: Self::DEFAULT_PURGE_SECONDS,
+            share_hits: false,
+            shared_threshold: 0,
+            shared_action: Self::default_shared_action(),
+            log_channel_id: None,
+            traps: Json(Vec::new()),
         }
     }
 
@@ -52,7 +269,8 @@ impl SettingsRow for HoneypotSettingsRow {
             Self,
             r#"
             SELECT guild_id, channel_id, exempt_admins, exempt_role_id,
-                   purge_seconds
+                   purge_seconds, share_hits, shared_threshold, shared_action,
+                   log_channel_id, traps AS "traps: Json<Vec<HoneypotTrap>>"
             FROM honeypot_settings
             WHERE guild_id = $1
             "#,
@@ -68,22 +286,34 @@ impl SettingsRow for HoneypotSettingsRow {
             r#"
             INSERT INTO honeypot_settings
                 (guild_id, channel_id, exempt_admins, exempt_role_id,
-                 purge_seconds)
-            VALUES ($1, $2, $3, $4, $5)
+                 purge_seconds, share_hits, shared_threshold, shared_action,
+                 log_channel_id, traps)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
             ON CONFLICT (guild_id) DO UPDATE SET
                 channel_id = EXCLUDED.channel_id,
                 exempt_admins = EXCLUDED.exempt_admins,
                 exempt_role_id = EXCLUDED.exempt_role_id,
                 purge_seconds = EXCLUDED.purge_seconds,
+                share_hits = EXCLUDED.share_hits,
+                shared_threshold = EXCLUDED.shared_threshold,
+                shared_action = EXCLUDED.shared_action,
+                log_channel_id = EXCLUDED.log_channel_id,
+                traps = EXCLUDED.traps,
                 updated_at = now()
             RETURNING guild_id, channel_id, exempt_admins, exempt_role_id,
-                      purge_seconds
+                      purge_seconds, share_hits, shared_threshold, shared_action,
+                      log_channel_id, traps AS "traps: Json<Vec<HoneypotTrap>>"
             "#,
             self.guild_id,
             self.channel_id,
             self.exempt_admins,
             self.exempt_role_id,
-            self.purge_seconds
+            self.purge_seconds,
+            self.share_hits,
+            self.shared_threshold,
+            self.shared_action,
+            self.log_channel_id,
+            &self.traps as &Json<Vec<HoneypotTrap>>
         )
         .fetch_one(pool)
         .await
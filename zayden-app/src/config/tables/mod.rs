@@ -1,24 +1,52 @@
 pub mod ai;
+pub mod appeals;
+pub mod automod;
 pub mod channels_settings;
 pub mod family;
 pub mod greetings;
 pub mod honeypot;
 pub mod lfg_settings;
 pub mod music;
+pub mod raid;
 pub mod roles_settings;
 pub mod suggestions_settings;
 pub mod support_settings;
 pub mod temp_voice_settings;
 pub mod ticket;
 
-pub use ai::AiSettingsRow;
+pub use ai::{
+    AiPersonas,
+    AiSettingsRow,
+    AiTrigger,
+    ChannelPersona,
+    CustomPersona,
+    PersonaError,
+};
+pub use appeals::AppealsSettingsRow;
+pub use automod::{
+    AutomodAction,
+    AutomodRule,
+    AutomodRules,
+    AutomodSettingsRow,
+    CapsRule,
+    MentionRule,
+    RateRule,
+    WordFilter,
+};
 pub use channels_settings::ChannelsSettingsRow;
 pub use family::FamilySettingsRow;
 pub use greetings::{Cooldowns, GreetingsSettingsRow};
-pub use honeypot::HoneypotSettingsRow;
+pub use honeypot::{
+    HoneypotSettingsRow,
+    HoneypotTrap,
+    SharedAction,
+    TrapAction,
+    TrapKind,
+};
 pub use lfg_settings::LfgSettingsRow;
 pub use music::MusicSettingsRow;
-pub use roles_settings::RolesSettingsRow;
+pub use raid::{RaidAction, RaidSettingsRow};
+pub use roles_settings::{CaptchaFailAction, RolesSettingsRow};
 pub use suggestions_settings::SuggestionsSettingsRow;
 pub use support_settings::SupportSettingsRow;
 pub use temp_voice_settings::TempVoiceSettingsRow;
@@ -0,0 +1,234 @@
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+
+use crate::config::SettingsRow;
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct RaidSettingsRow {
+    pub guild_id: i64,
+    pub enabled: bool,
+    /// More joins than this within `join_window_seconds` starts a lockdown.
+    pub join_threshold: i32,
+    pub join_window_seconds: i32,
+    /// How long an automatic lockdown lasts. 0 holds it until `/lockdown end`.
+    pub lockdown_minutes: i32,
+    /// Joiners whose accounts are younger than this look like raiders. 0 turns
+    /// the check off.
+    pub min_account_age_days: i32,
+    pub flag_default_avatar: bool,
+    /// Joiners whose names match other recent joiners' bar digits and
+    /// punctuation look like raiders.
+    pub flag_similar_names: bool,
+    /// As [`RaidAction::as_str`]. What happens to suspicious joiners during a
+    /// lockdown.
+    pub joiner_action: String,
+    pub timeout_minutes: i32,
+    /// Raise the server's verification level to High during a lockdown.
+    pub raise_verification: bool,
+    /// Slowmode put on every text channel during a lockdown. 0 leaves them.
+    pub slowmode_seconds: i32,
+    /// Where lockdowns are announced to staff.
+    pub alert_channel_id: Option<i64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RaidAction {
+    /// Only reported.
+    None,
+    Timeout,
+    Kick,
+}
+
+impl RaidAction {
+    pub const ALL: [Self; 3] = [Self::None, Self::Timeout, Self::Kick];
+
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::Timeout => "timeout",
+            Self::Kick => "kick",
+        }
+    }
+
+    /// Unknown values read as [`Self::None`], the action that can't hurt.
+    #[must_use]
+    pub fn parse(value: &str) -> Self {
+        Self::ALL
+            .into_iter()
+            .find(|action| action.as_str() == value.trim())
+            .unwrap_or(Self::None)
+    }
+}
+
+impl RaidSettingsRow {
+    pub const DEFAULT_JOIN_THRESHOLD: i32 = 10;
+    pub const DEFAULT_JOIN_WINDOW_SECONDS: i32 = 10;
+    pub const DEFAULT_LOCKDOWN_MINUTES: i32 = 30;
+    pub const DEFAULT_MIN_ACCOUNT_AGE_DAYS: i32 = 7;
+    pub const DEFAULT_SLOWMODE_SECONDS: i32 = 30;
+    pub const DEFAULT_TIMEOUT_MINUTES: i32 = 60;
+    pub const MAX_JOIN_THRESHOLD: i32 = 500;
+    pub const MAX_JOIN_WINDOW_SECONDS: i32 = 300;
+    /// A day.
+    pub const MAX_LOCKDOWN_MINUTES: i32 = 24 * 60;
+    pub const MAX_MIN_ACCOUNT_AGE_DAYS: i32 = 365;
+    /// Discord's longest slowmode, 6 hours.
+    pub const MAX_SLOWMODE_SECONDS: i32 = 6 * 60 * 60;
+    /// Discord's longest timeout, 28 days.
+    pub const MAX_TIMEOUT_MINUTES: i32 = 28 * 24 * 60;
+
+    #[must_use]
+    pub fn joiner_action(&self) -> RaidAction {
+        RaidAction::parse(&self.joiner_action)
+    }
+
+    #[must_use]
+    pub fn parse_join_threshold(input: &str) -> i32 {
+        parse_bounded(
+            input,
+            Self::DEFAULT_JOIN_THRESHOLD,
+            2,
+            Self::MAX_JOIN_THRESHOLD,
+        )
+    }
+
+    #[must_use]
+    pub fn parse_join_window_seconds(input: &str) -> i32 {
+        parse_bounded(
+            input,
+            Self::DEFAULT_JOIN_WINDOW_SECONDS,
+            1,
+            Self::MAX_JOIN_WINDOW_SECONDS,
+        )
+    }
+
+    #[must_use]
+    pub fn parse_lockdown_minutes(input: &str) -> i32 {
+        parse_bounded(
+            input,
+            Self::DEFAULT_LOCKDOWN_MINUTES,
+            0,
+            Self::MAX_LOCKDOWN_MINUTES,
+        )
+    }
+
+    #[must_use]
+    pub fn parse_min_account_age_days(input: &str) -> i32 {
+        parse_bounded(
+            input,
+            Self::DEFAULT_MIN_ACCOUNT_AGE_DAYS,
+            0,
+            Self::MAX_MIN_ACCOUNT_AGE_DAYS,
+        )
+    }
+
+    #[must_use]
+    pub fn parse_timeout_minutes(input: &str) -> i32 {
+        parse_bounded(
+            input,
+            Self::DEFAULT_TIMEOUT_MINUTES,
+            1,
+            Self::MAX_TIMEOUT_MINUTES,
+        )
+    }
+
+    #[must_use]
+    pub fn parse_slowmode_seconds(input: &str) -> i32 {
+        parse_bounded(
+            input,
+            Self::DEFAULT_SLOWMODE_SECONDS,
+            0,
+            Self::MAX_SLOWMODE_SECONDS,
+        )
+    }
+}
+
+fn parse_bounded(input: &str, default: i32, min: i32, max: i32) -> i32 {
+    input.trim().parse::<i32>().unwrap_or(default).clamp(min, max)
+}
+
+impl SettingsRow for RaidSettingsRow {
+    const TABLE: &'static str = "raid_settings";
+
+    fn empty(guild_id: i64) -> Self {
+        Self {
+            guild_id,
+            enabled: false,
+            join_threshold: Self::DEFAULT_JOIN_THRESHOLD,
+            join_window_seconds: Self::DEFAULT_JOIN_WINDOW_SECONDS,
+            lockdown_minutes: Self::DEFAULT_LOCKDOWN_MINUTES,
+            min_account_age_days: Self::DEFAULT_MIN_ACCOUNT_AGE_DAYS,
+            flag_default_avatar: true,
+            flag_similar_names: true,
+            joiner_action: RaidAction::Timeout.as_str().to_string(),
+            timeout_minutes: Self::DEFAULT_TIMEOUT_MINUTES,
+            raise_verification: true,
+            slowmode_seconds: Self::DEFAULT_SLOWMODE_SECONDS,
+            alert_channel_id: None,
+        }
+    }
+
+    async fn select(
+        pool: &PgPool,
+        guild_id: i64,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            "SELECT guild_id, enabled, join_threshold, join_window_seconds,
+                lockdown_minutes, min_account_age_days, flag_default_avatar,
+                flag_similar_names, joiner_action, timeout_minutes,
+                raise_verification, slowmode_seconds, alert_channel_id
+            FROM raid_settings
+            WHERE guild_id = $1",
+            guild_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    async fn upsert(&self, pool: &PgPool) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            "INSERT INTO raid_settings
+                (guild_id, enabled, join_threshold, join_window_seconds,
+                 lockdown_minutes, min_account_age_days, flag_default_avatar,
+                 flag_similar_names, joiner_action, timeout_minutes,
+                 raise_verification, slowmode_seconds, alert_channel_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+            ON CONFLICT (guild_id) DO UPDATE SET
+                enabled = EXCLUDED.enabled,
+                join_threshold = EXCLUDED.join_threshold,
+                join_window_seconds = EXCLUDED.join_window_seconds,
+                lockdown_minutes = EXCLUDED.lockdown_minutes,
+                min_account_age_days = EXCLUDED.min_account_age_days,
+                flag_default_avatar = EXCLUDED.flag_default_avatar,
+                flag_similar_names = EXCLUDED.flag_similar_names,
+                joiner_action = EXCLUDED.joiner_action,
+                timeout_minutes = EXCLUDED.timeout_minutes,
+                raise_verification = EXCLUDED.raise_verification,
+                slowmode_seconds = EXCLUDED.slowmode_seconds,
+                alert_channel_id = EXCLUDED.alert_channel_id,
+                updated_at = now()
+            RETURNING guild_id, enabled, join_threshold, join_window_seconds,
+                lockdown_minutes, min_account_age_days, flag_default_avatar,
+                flag_similar_names, joiner_action, timeout_minutes,
+                raise_verification, slowmode_seconds, alert_channel_id",
+            self.guild_id,
+            self.enabled,
+            self.join_threshold,
+            self.join_window_seconds,
+            self.lockdown_minutes,
+            self.min_account_age_days,
+            self.flag_default_avatar,
+            self.flag_similar_names,
+            self.joiner_action,
+            self.timeout_minutes,
+            self.raise_verification,
+            self.slowmode_seconds,
+            self.alert_channel_id,
+        )
+        .fetch_one(pool)
+        .await
+    }
+}
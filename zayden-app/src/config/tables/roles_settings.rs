@@ -1,13 +1,93 @@
+use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
 
 use crate::config::SettingsRow;
 
-#[derive(Debug, Clone, sqlx::FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct RolesSettingsRow {
     pub guild_id: i64,
     pub artist_role_id: Option<i64>,
     pub sleep_role_id: Option<i64>,
     pub verified_role_id: Option<i64>,
+    /// The verify button shows the rules and asks the member to accept them.
+    /// Defaulted so activity snapshots from before the column still restore.
+    #[serde(default)]
+    pub require_rules: bool,
+    /// A material rules change takes the verified role back from members who
+    /// accepted an older version, so they accept again.
+    #[serde(default)]
+    pub reverify_on_rules_change: bool,
+    /// The verify button first asks for the text in a CAPTCHA image.
+    #[serde(default)]
+    pub captcha_enabled: bool,
+    /// Wrong answers allowed before the member is locked out.
+    #[serde(default = "RolesSettingsRow::default_captcha_max_attempts")]
+    pub captcha_max_attempts: i32,
+    #[serde(default = "RolesSettingsRow::default_captcha_lockout_minutes")]
+    pub captcha_lockout_minutes: i32,
+    /// Accounts younger than this can't verify at all.
+    #[serde(default)]
+    pub captcha_min_account_age_days: i32,
+    /// What running out of attempts does, as [`CaptchaFailAction::as_str`].
+    #[serde(default = "RolesSettingsRow::default_captcha_fail_action")]
+    pub captcha_fail_action: String,
+    /// Where lockouts are reported, whichever the action.
+    #[serde(default)]
+    pub captcha_log_channel_id: Option<i64>,
+}
+
+/// What happens to a member who runs out of CAPTCHA attempts. Either way
+/// they are locked out for `captcha_lockout_minutes`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaptchaFailAction {
+    Log,
+    Kick,
+}
+
+impl CaptchaFailAction {
+    pub const ALL: [Self; 2] = [Self::Log, Self::Kick];
+
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Log => "log",
+            Self::Kick => "kick",
+        }
+    }
+
+    /// Unknown values read as [`Self::Log`], the action that can't hurt.
+    #[must_use]
+    pub fn parse(value: &str) -> Self {
+        Self::ALL
+            .into_iter()
+            .find(|action| action.as_str() == value.trim())
+            .unwrap_or(Self::Log)
+    }
+}
+
+impl RolesSettingsRow {
+    pub const DEFAULT_CAPTCHA_LOCKOUT_MINUTES: i32 = 15;
+    pub const DEFAULT_CAPTCHA_MAX_ATTEMPTS: i32 = 3;
+    pub const MAX_CAPTCHA_ACCOUNT_AGE_DAYS: i32 = 365;
+    pub const MAX_CAPTCHA_ATTEMPTS: i32 = 10;
+    pub const MAX_CAPTCHA_LOCKOUT_MINUTES: i32 = 7 * 24 * 60;
+
+    const fn default_captcha_max_attempts() -> i32 {
+        Self::DEFAULT_CAPTCHA_MAX_ATTEMPTS
+    }
+
+    const fn default_captcha_lockout_minutes() -> i32 {
+        Self::DEFAULT_CAPTCHA_LOCKOUT_MINUTES
+    }
+
+    fn default_captcha_fail_action() -> String {
+        CaptchaFailAction::Log.as_str().to_string()
+    }
+
+    #[must_use]
+    pub fn captcha_fail_action(&self) -> CaptchaFailAction {
+        CaptchaFailAction::parse(&self.captcha_fail_action)
+    }
 }
 
 impl SettingsRow for RolesSettingsRow {
@@ -19,6 +99,14 @@ impl SettingsRow for RolesSettingsRow {
             artist_role_id: None,
             sleep_role_id: None,
             verified_role_id: None,
+            require_rules: false,
+            reverify_on_rules_change: false,
+            captcha_enabled: false,
+            captcha_max_attempts: Self::DEFAULT_CAPTCHA_MAX_ATTEMPTS,
+            captcha_lockout_minutes: Self::DEFAULT_CAPTCHA_LOCKOUT_MINUTES,
+            captcha_min_account_age_days: 0,
+            captcha_fail_action: Self::default_captcha_fail_action(),
+            captcha_log_channel_id: None,
         }
     }
 
@@ -29,7 +117,11 @@ impl SettingsRow for RolesSettingsRow {
         sqlx::query_as!(
             Self,
             r#"
-            SELECT guild_id, artist_role_id, sleep_role_id, verified_role_id
+            SELECT guild_id, artist_role_id, sleep_role_id, verified_role_id,
+                require_rules, reverify_on_rules_change,
+                captcha_enabled, captcha_max_attempts, captcha_lockout_minutes,
+                captcha_min_account_age_days, captcha_fail_action,
+                captcha_log_channel_id
             FROM roles_settings
             WHERE guild_id = $1
             "#,
@@ -43,19 +135,45 @@ impl SettingsRow for RolesSettingsRow {
         sqlx::query_as!(
             Self,
             r#"
-            INSERT INTO roles_settings (guild_id, artist_role_id, sleep_role_id, verified_role_id)
-            VALUES ($1, $2, $3, $4)
+            INSERT INTO roles_settings (
+                guild_id, artist_role_id, sleep_role_id, verified_role_id,
+                require_rules, reverify_on_rules_change,
+                captcha_enabled, captcha_max_attempts, captcha_lockout_minutes,
+                captcha_min_account_age_days, captcha_fail_action,
+                captcha_log_channel_id
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
             ON CONFLICT (guild_id) DO UPDATE SET
                 artist_role_id = EXCLUDED.artist_role_id,
                 sleep_role_id = EXCLUDED.sleep_role_id,
                 verified_role_id = EXCLUDED.verified_role_id,
+                require_rules = EXCLUDED.require_rules,
+                reverify_on_rules_change = EXCLUDED.reverify_on_rules_change,
+                captcha_enabled = EXCLUDED.captcha_enabled,
+                captcha_max_attempts = EXCLUDED.captcha_max_attempts,
+                captcha_lockout_minutes = EXCLUDED.captcha_lockout_minutes,
+                captcha_min_account_age_days = EXCLUDED.captcha_min_account_age_days,
+                captcha_fail_action = EXCLUDED.captcha_fail_action,
+                captcha_log_channel_id = EXCLUDED.captcha_log_channel_id,
                 updated_at = now()
-            RETURNING guild_id, artist_role_id, sleep_role_id, verified_role_id
+            RETURNING guild_id, artist_role_id, sleep_role_id, verified_role_id,
+                require_rules, reverify_on_rules_change,
+                captcha_enabled, captcha_max_attempts, captcha_lockout_minutes,
+                captcha_min_account_age_days, captcha_fail_action,
+                captcha_log_channel_id
             "#,
             self.guild_id,
             self.artist_role_id,
             self.sleep_role_id,
-            self.verified_role_id
+            self.verified_role_id,
+            self.require_rules,
+            self.reverify_on_rules_change,
+            self.captcha_enabled,
+            self.captcha_max_attempts,
+            self.captcha_lockout_minutes,
+            self.captcha_min_account_age_days,
+            self.captcha_fail_action,
+            self.captcha_log_channel_id
         )
         .fetch_one(pool)
         .await
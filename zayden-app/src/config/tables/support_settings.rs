@@ -1,19 +1,26 @@
+use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
 
 use crate::config::SettingsRow;
 
-#[derive(Debug, Clone, sqlx::FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct SupportSettingsRow {
     pub guild_id: i64,
     pub support_channel_id: Option<i64>,
     pub faq_channel_id: Option<i64>,
+    pub transcript_channel_id: Option<i64>,
 }
 
 impl SettingsRow for SupportSettingsRow {
     const TABLE: &'static str = "support_settings";
 
     fn empty(guild_id: i64) -> Self {
-        Self { guild_id, support_channel_id: None, faq_channel_id: None }
+        Self {
+            guild_id,
+            support_channel_id: None,
+            faq_channel_id: None,
+            transcript_channel_id: None,
+        }
     }
 
     async fn select(
@@ -23,7 +30,7 @@ impl SettingsRow for SupportSettingsRow {
         sqlx::query_as!(
             Self,
             r#"
-            SELECT guild_id, support_channel_id, faq_channel_id
+            SELECT guild_id, support_channel_id, faq_channel_id, transcript_channel_id
             FROM support_settings
             WHERE guild_id = $1
             "#,
@@ -37,17 +44,19 @@ impl SettingsRow for SupportSettingsRow {
         sqlx::query_as!(
             Self,
             r#"
-            INSERT INTO support_settings (guild_id, support_channel_id, faq_channel_id)
-            VALUES ($1, $2, $3)
+            INSERT INTO support_settings (guild_id, support_channel_id, faq_channel_id, transcript_channel_id)
+            VALUES ($1, $2, $3, $4)
             ON CONFLICT (guild_id) DO UPDATE SET
                 support_channel_id = EXCLUDED.support_channel_id,
                 faq_channel_id = EXCLUDED.faq_channel_id,
+                transcript_channel_id = EXCLUDED.transcript_channel_id,
                 updated_at = now()
-            RETURNING guild_id, support_channel_id, faq_channel_id
+            RETURNING guild_id, support_channel_id, faq_channel_id, transcript_channel_id
             "#,
             self.guild_id,
             self.support_channel_id,
-            self.faq_channel_id
+            self.faq_channel_id,
+            self.transcript_channel_id
         )
         .fetch_one(pool)
         .await
@@ -0,0 +1,115 @@
+use jiff::{SignedDuration, Timestamp};
+use thiserror::Error;
+
+use super::types::Tier;
+
+/// Symbols a code is drawn from: no `0`/`O` or `1`/`I` to misread, and 32 of
+/// them so a random byte maps onto one without bias.
+pub const CODE_ALPHABET: &[u8; 32] = b"ABCDEFGHJKLMNPQRSTUVWXYZ23456789";
+
+/// Random bytes a code is built from; see [`format_code`].
+pub const CODE_LEN: usize = 10;
+
+pub const GIFT_PROVIDER: &str = "gift";
+pub const TRIAL_PROVIDER: &str = "trial";
+
+/// How long the one-time guild trial lasts.
+pub const TRIAL_DAYS: i64 = 7;
+pub const TRIAL_TIER: Tier = Tier::Pro;
+
+/// Turns random bytes into a code like `ABCDE-FGHJK`.
+#[must_use]
+pub fn format_code(random: [u8; CODE_LEN]) -> String {
+    let mut code = String::with_capacity(CODE_LEN + 1);
+    for (i, byte) in random.into_iter().enumerate() {
+        if i == CODE_LEN / 2 {
+            code.push('-');
+        }
+        code.push(char::from(
+            CODE_ALPHABET[usize::from(byte) % CODE_ALPHABET.len()],
+        ));
+    }
+    code
+}
+
+/// Uppercases a code as typed and drops spaces, so `abcde fghjk` redeems
+/// `ABCDE-FGHJK`.
+#[must_use]
+pub fn normalize_code(input: &str) -> String {
+    let compact: String = input
+        .chars()
+        .filter(char::is_ascii_alphanumeric)
+        .map(|c| c.to_ascii_uppercase())
+        .collect();
+
+    if compact.len() == CODE_LEN {
+        format!("{}-{}", &compact[..CODE_LEN / 2], &compact[CODE_LEN / 2..])
+    } else {
+        compact
+    }
+}
+
+/// When an entitlement granted `days` from `from` runs out.
+#[must_use]
+pub fn grant_expiry(from: Timestamp, days: i64) -> Timestamp {
+    from.checked_add(SignedDuration::from_secs(days.saturating_mul(86_400)))
+        .unwrap_or(Timestamp::MAX)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GiftCode {
+    pub code: String,
+    pub tier: Tier,
+    pub duration_days: i32,
+    pub max_uses: i32,
+    pub uses: i32,
+    pub expires_at: Option<Timestamp>,
+    pub created_by: u64,
+}
+
+impl GiftCode {
+    /// Why the code can't be redeemed right now, if anything.
+    pub fn check_redeemable(&self, now: Timestamp) -> Result<(), GiftError> {
+        if self.expires_at.is_some_and(|at| at <= now) {
+            return Err(GiftError::Expired);
+        }
+        if self.uses >= self.max_uses {
+            return Err(GiftError::Exhausted);
+        }
+        Ok(())
+    }
+}
+
+/// What a successful redemption or trial granted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GiftGrant {
+    pub tier: Tier,
+    pub expires_at: Timestamp,
+}
+
+#[derive(Debug, Error)]
+pub enum GiftError {
+    #[error("that code does not exist")]
+    NotFound,
+
+    #[error("that code has expired")]
+    Expired,
+
+    #[error("that code has no uses left")]
+    Exhausted,
+
+    #[error("you have already redeemed that code")]
+    AlreadyRedeemed,
+
+    #[error("gift codes must grant a paid tier for at least one day and one use")]
+    InvalidCode,
+
+    #[error("this server has already used its free trial")]
+    TrialUsed,
+
+    #[error("this server already has premium")]
+    AlreadyPremium,
+
+    #[error(transparent)]
+    Sqlx(#[from] sqlx::Error),
+}
@@ -1,7 +1,23 @@
+pub mod gift;
 pub mod provider;
 pub mod service;
+pub mod slots;
 pub mod types;
 
+pub use gift::{
+    CODE_ALPHABET,
+    CODE_LEN,
+    GIFT_PROVIDER,
+    GiftCode,
+    GiftError,
+    GiftGrant,
+    TRIAL_DAYS,
+    TRIAL_PROVIDER,
+    TRIAL_TIER,
+    format_code,
+    grant_expiry,
+    normalize_code,
+};
 pub use provider::{
     DiscordProvider,
     EntitlementProvider,
@@ -9,6 +25,16 @@ pub use provider::{
     KoFiPayload,
     KoFiProvider,
     KoFiType,
+    PATREON_API_BASE,
+    PatreonClient,
+    PatreonError,
+    PatreonEvent,
+    PatreonMember,
+    PatreonMemberDocument,
+    PatreonProvider,
+    PatronStatus,
+    ReconcileSummary,
 };
-pub use service::EntitlementService;
-pub use types::{EntitlementScope, Tier};
+pub use service::{EntitlementService, MANUAL_PROVIDER};
+pub use slots::{SLOT_COOLDOWN, SlotAssignment, SlotError, SlotHolder};
+pub use types::{EntitlementRecord, EntitlementScope, Tier};
@@ -1,10 +1,22 @@
 mod discord;
 mod kofi;
+mod patreon;
 
 use async_trait::async_trait;
 pub use discord::DiscordProvider;
 use jiff::Timestamp;
 pub use kofi::{KoFiPayload, KoFiProvider, KoFiType};
+pub use patreon::{
+    PATREON_API_BASE,
+    PatreonClient,
+    PatreonError,
+    PatreonEvent,
+    PatreonMember,
+    PatreonMemberDocument,
+    PatreonProvider,
+    PatronStatus,
+    ReconcileSummary,
+};
 
 use super::service::EntitlementService;
 use super::types::{EntitlementScope, Tier};
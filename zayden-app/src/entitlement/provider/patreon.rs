@@ -0,0 +1,374 @@
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+
+use async_trait::async_trait;
+use hmac::{Hmac, KeyInit, Mac};
+use jiff::{SignedDuration, Timestamp};
+use md5::Md5;
+use serde::Deserialize;
+use sqlx::PgPool;
+use thiserror::Error;
+use tracing::{info, warn};
+
+use super::{EntitlementProvider, GrantData};
+use crate::entitlement::service::EntitlementService;
+use crate::entitlement::types::{EntitlementScope, Tier};
+
+pub const PATREON_API_BASE: &str = "https://www.patreon.com";
+
+pub struct PatreonProvider;
+
+impl PatreonProvider {
+    /// Patreon charges on the billing date and retries declined cards for a
+    /// few days, so a grant runs this long past `next_charge_date`.
+    pub const GRACE_DAYS: i64 = 3;
+    pub const PROVIDER: &str = "patreon";
+
+    /// Checks `X-Patreon-Signature`: the hex HMAC-MD5 of the raw body, keyed
+    /// with the webhook secret.
+    #[must_use]
+    pub fn verify_signature(secret: &str, body: &[u8], signature: &str) -> bool {
+        let Ok(expected) = hex::decode(signature.trim()) else {
+            return false;
+        };
+        let Ok(mut mac) = Hmac::<Md5>::new_from_slice(secret.as_bytes()) else {
+            return false;
+        };
+        mac.update(body);
+        mac.verify_slice(&expected).is_ok()
+    }
+
+    /// The grant for a linked member, or `None` when they are not an active
+    /// patron of a mapped tier and whatever they had should be revoked.
+    #[must_use]
+    pub fn build_grant(
+        member: &PatreonMember,
+        discord_user_id: u64,
+        tiers: &HashMap<String, Tier>,
+    ) -> Option<GrantData> {
+        if member.attributes.patron_status != Some(PatronStatus::ActivePatron) {
+            return None;
+        }
+
+        let tier = member
+            .tier_ids()
+            .filter_map(|id| tiers.get(id).copied())
+            .max()
+            .filter(|tier| *tier > Tier::Free)?;
+
+        let expires_at = member
+            .attributes
+            .next_charge_date
+            .as_deref()
+            .and_then(|date| {
+                date.parse::<Timestamp>()
+                    .map_err(|e| warn!(%e, member = %member.id, "invalid next_charge_date"))
+                    .ok()
+            })
+            .and_then(|ts| {
+                ts.checked_add(SignedDuration::from_hours(Self::GRACE_DAYS * 24)).ok()
+            });
+
+        Some(GrantData {
+            external_id: member.id.clone(),
+            scope: EntitlementScope::User(discord_user_id),
+            tier,
+            expires_at,
+        })
+    }
+}
+
+#[async_trait]
+impl EntitlementProvider for PatreonProvider {
+    async fn grant(
+        &self,
+        service: &EntitlementService,
+        data: GrantData,
+    ) -> Result<(), sqlx::Error> {
+        service
+            .grant(
+                data.scope,
+                data.tier,
+                Self::PROVIDER,
+                &data.external_id,
+                data.expires_at,
+            )
+            .await
+    }
+
+    async fn revoke(
+        &self,
+        service: &EntitlementService,
+        external_id: &str,
+    ) -> Result<(), sqlx::Error> {
+        service.revoke(Self::PROVIDER, external_id).await
+    }
+}
+
+/// The `X-Patreon-Event` header. The legacy `pledge` events carry the same
+/// member document, so they are folded into the member ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PatreonEvent {
+    MemberCreate,
+    MemberUpdate,
+    MemberDelete,
+}
+
+impl FromStr for PatreonEvent {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "members:create" | "members:pledge:create" => Ok(Self::MemberCreate),
+            "members:update" | "members:pledge:update" => Ok(Self::MemberUpdate),
+            "members:delete" | "members:pledge:delete" => Ok(Self::MemberDelete),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PatronStatus {
+    ActivePatron,
+    DeclinedPatron,
+    FormerPatron,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PatreonMemberDocument {
+    pub data: PatreonMember,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PatreonMember {
+    pub id: String,
+    #[serde(default)]
+    pub attributes: MemberAttributes,
+    #[serde(default)]
+    pub relationships: MemberRelationships,
+}
+
+impl PatreonMember {
+    /// The Patreon user behind this campaign membership, which is what a
+    /// dashboard link records.
+    #[must_use]
+    pub fn user_id(&self) -> Option<&str> {
+        self.relationships.user.as_ref().map(|user| user.data.id.as_str())
+    }
+
+    pub fn tier_ids(&self) -> impl Iterator<Item = &str> {
+        self.relationships
+            .currently_entitled_tiers
+            .data
+            .iter()
+            .map(|t| t.id.as_str())
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct MemberAttributes {
+    #[serde(default)]
+    pub patron_status: Option<PatronStatus>,
+    #[serde(default)]
+    pub next_charge_date: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct MemberRelationships {
+    #[serde(default)]
+    pub currently_entitled_tiers: Relationship<Vec<ResourceRef>>,
+    #[serde(default)]
+    pub user: Option<Relationship<ResourceRef>>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct Relationship<T> {
+    pub data: T,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct ResourceRef {
+    pub id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct MembersPage {
+    data: Vec<PatreonMember>,
+    #[serde(default)]
+    links: Option<PageLinks>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PageLinks {
+    next: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct IdentityDocument {
+    data: ResourceRef,
+}
+
+#[derive(Debug, Error)]
+pub enum PatreonError {
+    #[error("Patreon API request failed: {0}")]
+    Http(#[from] reqwest::Error),
+
+    #[error(transparent)]
+    Sqlx(#[from] sqlx::Error),
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ReconcileSummary {
+    pub granted: usize,
+    pub revoked: usize,
+}
+
+/// Reads the campaign through the creator's access token so entitlements
+/// recover from webhooks Patreon dropped or we failed to process.
+pub struct PatreonClient {
+    http: reqwest::Client,
+    base_url: String,
+    creator_token: String,
+    campaign_id: String,
+}
+
+impl PatreonClient {
+    #[must_use]
+    pub fn new(
+        http: reqwest::Client,
+        creator_token: impl Into<String>,
+        campaign_id: impl Into<String>,
+    ) -> Self {
+        Self {
+            http,
+            base_url: PATREON_API_BASE.to_owned(),
+            creator_token: creator_token.into(),
+            campaign_id: campaign_id.into(),
+        }
+    }
+
+    #[must_use]
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into().trim_end_matches('/').to_owned();
+        self
+    }
+
+    /// Every member of the campaign, following the cursor pagination.
+    pub async fn members(&self) -> Result<Vec<PatreonMember>, PatreonError> {
+        let mut url = format!(
+            "{}/api/oauth2/v2/campaigns/{}/members\
+             ?include=currently_entitled_tiers,user\
+             &fields%5Bmember%5D=patron_status,next_charge_date\
+             &page%5Bcount%5D=500",
+            self.base_url, self.campaign_id
+        );
+        let mut members = Vec::new();
+
+        loop {
+            let page: MembersPage = self
+                .http
+                .get(&url)
+                .bearer_auth(&self.creator_token)
+                .send()
+                .await?
+                .error_for_status()?
+                .json()
+                .await?;
+
+            members.extend(page.data);
+
+            match page.links.and_then(|links| links.next) {
+                Some(next) => url = next,
+                None => break,
+            }
+        }
+
+        Ok(members)
+    }
+
+    /// The Patreon user id behind a patron's own OAuth access token.
+    pub async fn identity(
+        http: &reqwest::Client,
+        base_url: &str,
+        access_token: &str,
+    ) -> Result<String, PatreonError> {
+        let identity: IdentityDocument = http
+            .get(format!("{base_url}/api/oauth2/v2/identity"))
+            .bearer_auth(access_token)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        Ok(identity.data.id)
+    }
+
+    /// Brings every `patreon` entitlement in line with the campaign: linked
+    /// active patrons are granted their tier, and anything else, including
+    /// grants for members who have left the campaign, is revoked.
+    pub async fn reconcile(
+        &self,
+        pool: &PgPool,
+        service: &EntitlementService,
+        tiers: &HashMap<String, Tier>,
+    ) -> Result<ReconcileSummary, PatreonError> {
+        let members = self.members().await?;
+
+        let links = sqlx::query!(
+            "SELECT patreon_user_id, discord_user_id FROM patreon_links"
+        )
+        .fetch_all(pool)
+        .await?
+        .into_iter()
+        .map(|row| (row.patreon_user_id, row.discord_user_id.cast_unsigned()))
+        .collect::<HashMap<_, _>>();
+
+        let granted: HashSet<String> = service
+            .external_ids(PatreonProvider::PROVIDER)
+            .await?
+            .into_iter()
+            .collect();
+
+        let mut summary = ReconcileSummary::default();
+        let mut seen = HashSet::with_capacity(members.len());
+
+        for member in &members {
+            seen.insert(member.id.as_str());
+
+            let grant = member.user_id().and_then(|user| links.get(user)).and_then(
+                |discord| PatreonProvider::build_grant(member, *discord, tiers),
+            );
+
+            match grant {
+                Some(data) => {
+                    PatreonProvider.grant(service, data).await?;
+                    summary.granted += 1;
+                },
+                // Inactive or unlinked: only worth a write if it was granted.
+                None if granted.contains(&member.id) => {
+                    PatreonProvider.revoke(service, &member.id).await?;
+                    summary.revoked += 1;
+                },
+                None => {},
+            }
+        }
+
+        for external_id in &granted {
+            if !seen.contains(external_id.as_str()) {
+                PatreonProvider.revoke(service, external_id).await?;
+                summary.revoked += 1;
+            }
+        }
+
+        info!(
+            members = members.len(),
+            granted = summary.granted,
+            revoked = summary.revoked,
+            "Patreon reconcile finished"
+        );
+
+        Ok(summary)
+    }
+}
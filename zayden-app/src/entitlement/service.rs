@@ -1,18 +1,35 @@
 use std::sync::Arc;
 use std::time::Duration;
 
+use jiff::SignedDuration;
 use jiff_sqlx::Timestamp;
 use moka::future::Cache;
-use sqlx::PgPool;
+use sqlx::{PgExecutor, PgPool};
 use tokio::sync::broadcast;
 use tracing::{info, warn};
 
-use super::types::{EntitlementScope, Tier};
+use super::gift::{
+    GIFT_PROVIDER,
+    GiftCode,
+    GiftError,
+    GiftGrant,
+    TRIAL_DAYS,
+    TRIAL_PROVIDER,
+    TRIAL_TIER,
+    grant_expiry,
+    normalize_code,
+};
+use super::slots::{SlotAssignment, SlotError, SlotHolder};
+use super::types::{EntitlementRecord, EntitlementScope, Tier};
 use crate::events::AppEvent;
 
+/// Provider for grants the owner makes by hand from the admin console.
+pub const MANUAL_PROVIDER: &str = "manual";
+
 pub struct EntitlementService {
     db: PgPool,
     cache: Cache<EntitlementScope, Tier>,
+    slot_holders: Cache<u64, Arc<[SlotHolder]>>,
     events: broadcast::Sender<AppEvent>,
 }
 
@@ -23,7 +40,11 @@ impl EntitlementService {
             .max_capacity(4096)
             .time_to_live(Duration::from_mins(10))
             .build();
-        Self { db, cache, events }
+        let slot_holders = Cache::builder()
+            .max_capacity(4096)
+            .time_to_live(Duration::from_mins(10))
+            .build();
+        Self { db, cache, slot_holders, events }
     }
 
     pub async fn user_tier(&self, user_id: u64) -> Tier {
@@ -31,9 +52,15 @@ impl EntitlementService {
         self.tier_for_scope(scope).await
     }
 
+    /// The guild's own entitlements, or the best tier among subscribers who
+    /// assigned it one of their premium slots.
     pub async fn guild_tier(&self, guild_id: u64) -> Tier {
         let scope = EntitlementScope::Guild(guild_id);
-        self.tier_for_scope(scope).await
+        let own = self.tier_for_scope(scope).await;
+        if own == Tier::Ultra {
+            return own;
+        }
+        own.max(self.slot_tier(guild_id).await)
     }
 
     pub async fn server_tier(&self, guild_id: u64, owner_id: u64) -> Tier {
@@ -66,6 +93,28 @@ impl EntitlementService {
         provider: &str,
         external_id: &str,
         expires_at: Option<jiff::Timestamp>,
+    ) -> Result<(), sqlx::Error> {
+        Self::insert_grant(
+            &self.db,
+            &scope,
+            tier,
+            provider,
+            external_id,
+            expires_at,
+        )
+        .await?;
+        self.announce(scope).await
+    }
+
+    /// Writes a grant without touching the cache, so it can share a
+    /// transaction with other writes. [`Self::announce`] it once committed.
+    async fn insert_grant(
+        db: impl PgExecutor<'_>,
+        scope: &EntitlementScope,
+        tier: Tier,
+        provider: &str,
+        external_id: &str,
+        expires_at: Option<jiff::Timestamp>,
     ) -> Result<(), sqlx::Error> {
         let expires_at_pg = expires_at.map(Timestamp::from);
 
@@ -88,9 +137,15 @@ impl EntitlementService {
                 granted_at         = now()
             ", provider, external_id, scope.scope_type(), scope.scope_id(), scope.scope_secondary_id(), tier.as_str(), expires_at_pg as Option<Timestamp>
         )
-        .execute(&self.db)
+        .execute(db)
         .await?;
 
+        Ok(())
+    }
+
+    /// Brings the cache up to date with a grant to `scope` and sends
+    /// [`AppEvent::EntitlementChanged`].
+    async fn announce(&self, scope: EntitlementScope) -> Result<(), sqlx::Error> {
         self.refresh_cache_row_from_db(&scope).await?;
         self.cache.invalidate(&scope).await;
         let _ = self.events.send(AppEvent::EntitlementChanged(scope));
@@ -146,6 +201,392 @@ impl EntitlementService {
         Ok(())
     }
 
+    /// The most recently granted entitlements, newest first.
+    pub async fn list_entitlements(
+        &self,
+        limit: i64,
+    ) -> Result<Vec<EntitlementRecord>, sqlx::Error> {
+        let rows = sqlx::query!(
+            r#"SELECT provider, external_id, scope_type, scope_id, scope_secondary_id,
+                tier,
+                granted_at AS "granted_at: Timestamp",
+                expires_at AS "expires_at: Timestamp"
+            FROM entitlements
+            ORDER BY granted_at DESC
+            LIMIT $1"#,
+            limit,
+        )
+        .fetch_all(&self.db)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| EntitlementRecord {
+                scope: row_to_scope(
+                    &row.scope_type,
+                    row.scope_id,
+                    row.scope_secondary_id,
+                ),
+                provider: row.provider,
+                external_id: row.external_id,
+                tier: row.tier.parse().unwrap_or(Tier::Free),
+                granted_at: row.granted_at.to_jiff(),
+                expires_at: row.expires_at.map(Timestamp::to_jiff),
+            })
+            .collect())
+    }
+
+    /// Pushes an expiring entitlement's end date back by `by`, counting from
+    /// now if it has already lapsed. Entitlements without an expiry are left
+    /// alone. Returns the new expiry, or `None` when nothing changed.
+    pub async fn extend(
+        &self,
+        provider: &str,
+        external_id: &str,
+        by: SignedDuration,
+    ) -> Result<Option<jiff::Timestamp>, sqlx::Error> {
+        let row = sqlx::query!(
+            r#"UPDATE entitlements
+            SET expires_at = GREATEST(expires_at, now()) + $3::bigint * interval '1 second'
+            WHERE provider = $1 AND external_id = $2 AND expires_at IS NOT NULL
+            RETURNING scope_type, scope_id, scope_secondary_id,
+                expires_at AS "expires_at!: Timestamp""#,
+            provider,
+            external_id,
+            by.as_secs(),
+        )
+        .fetch_optional(&self.db)
+        .await?;
+
+        let Some(row) = row else {
+            return Ok(None);
+        };
+
+        let scope =
+            row_to_scope(&row.scope_type, row.scope_id, row.scope_secondary_id);
+        self.refresh_cache_row_from_db(&scope).await?;
+        self.cache.invalidate(&scope).await;
+        let _ = self.events.send(AppEvent::EntitlementChanged(scope));
+        Ok(Some(row.expires_at.to_jiff()))
+    }
+
+    /// Every external id `provider` currently holds a grant under.
+    pub async fn external_ids(
+        &self,
+        provider: &str,
+    ) -> Result<Vec<String>, sqlx::Error> {
+        sqlx::query_scalar!(
+            "SELECT external_id FROM entitlements WHERE provider = $1",
+            provider,
+        )
+        .fetch_all(&self.db)
+        .await
+    }
+
+    /// The user's slot assignments, oldest first.
+    pub async fn slots(
+        &self,
+        user_id: u64,
+    ) -> Result<Vec<SlotAssignment>, sqlx::Error> {
+        let rows = sqlx::query!(
+            r#"SELECT guild_id, assigned_at AS "assigned_at: Timestamp"
+            FROM premium_slots
+            WHERE user_id = $1
+            ORDER BY assigned_at, guild_id"#,
+            i64::try_from(user_id).unwrap_or(i64::MAX),
+        )
+        .fetch_all(&self.db)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| SlotAssignment {
+                guild_id: u64::try_from(row.guild_id).unwrap_or(0),
+                assigned_at: row.assigned_at.to_jiff(),
+            })
+            .collect())
+    }
+
+    /// Applies one of the user's premium slots to `guild_id`.
+    pub async fn assign_slot(
+        &self,
+        user_id: u64,
+        guild_id: u64,
+    ) -> Result<(), SlotError> {
+        let limit = self.user_tier(user_id).await.premium_slots();
+        if limit == 0 {
+            return Err(SlotError::NoSlots);
+        }
+
+        let user = i64::try_from(user_id).unwrap_or(i64::MAX);
+        let guild = i64::try_from(guild_id).unwrap_or(i64::MAX);
+
+        let mut tx = self.db.begin().await?;
+
+        // Serialises a user's concurrent assigns so both can't take the last slot.
+        sqlx::query!("SELECT pg_advisory_xact_lock($1)", user)
+            .execute(&mut *tx)
+            .await?;
+
+        let guilds = sqlx::query_scalar!(
+            "SELECT guild_id FROM premium_slots WHERE user_id = $1",
+            user
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        if guilds.contains(&guild) {
+            return Err(SlotError::AlreadyAssigned);
+        }
+        if guilds.len() >= limit {
+            return Err(SlotError::SlotsFull { limit });
+        }
+
+        sqlx::query!(
+            "INSERT INTO premium_slots (user_id, guild_id) VALUES ($1, $2)",
+            user,
+            guild
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        self.slots_changed(guild_id).await;
+        Ok(())
+    }
+
+    /// Takes the user's slot back from `guild_id` once
+    /// [`SLOT_COOLDOWN`](super::SLOT_COOLDOWN) has passed since it was assigned.
+    pub async fn unassign_slot(
+        &self,
+        user_id: u64,
+        guild_id: u64,
+    ) -> Result<(), SlotError> {
+        let user = i64::try_from(user_id).unwrap_or(i64::MAX);
+        let guild = i64::try_from(guild_id).unwrap_or(i64::MAX);
+
+        let assigned_at = sqlx::query_scalar!(
+            r#"SELECT assigned_at AS "assigned_at: Timestamp" FROM premium_slots
+            WHERE user_id = $1 AND guild_id = $2"#,
+            user,
+            guild
+        )
+        .fetch_optional(&self.db)
+        .await?
+        .ok_or(SlotError::NotAssigned)?;
+
+        let slot = SlotAssignment { guild_id, assigned_at: assigned_at.to_jiff() };
+        let until = slot.unlocks_at();
+        if jiff::Timestamp::now() < until {
+            return Err(SlotError::Cooldown { until });
+        }
+
+        sqlx::query!(
+            "DELETE FROM premium_slots WHERE user_id = $1 AND guild_id = $2",
+            user,
+            guild
+        )
+        .execute(&self.db)
+        .await?;
+
+        // The rest of the user's slots move up a rank, which can reactivate
+        // one that a downgrade had left over the limit.
+        for other in self.slots(user_id).await? {
+            self.slots_changed(other.guild_id).await;
+        }
+        self.slots_changed(guild_id).await;
+        Ok(())
+    }
+
+    /// Stores a freshly minted code. The caller generates `code.code`.
+    #[expect(
+        trivial_casts,
+        reason = "not a cast: `as T` is sqlx's bind-param type-override syntax, required because TIMESTAMPTZ has no built-in jiff mapping"
+    )]
+    pub async fn mint_gift_code(&self, code: &GiftCode) -> Result<(), GiftError> {
+        if code.tier == Tier::Free || code.duration_days < 1 || code.max_uses < 1 {
+            return Err(GiftError::InvalidCode);
+        }
+
+        let expires_at = code.expires_at.map(Timestamp::from);
+
+        sqlx::query!(
+            "INSERT INTO gift_codes
+                (code, tier, duration_days, max_uses, expires_at, created_by)
+            VALUES ($1, $2, $3, $4, $5, $6)",
+            code.code,
+            code.tier.as_str(),
+            code.duration_days,
+            code.max_uses,
+            expires_at as Option<Timestamp>,
+            i64::try_from(code.created_by).unwrap_or(i64::MAX),
+        )
+        .execute(&self.db)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Every minted code, newest first.
+    pub async fn gift_codes(&self) -> Result<Vec<GiftCode>, sqlx::Error> {
+        let rows = sqlx::query!(
+            r#"SELECT code, tier, duration_days, max_uses, uses, created_by,
+                expires_at AS "expires_at: Timestamp"
+            FROM gift_codes
+            ORDER BY created_at DESC"#
+        )
+        .fetch_all(&self.db)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| GiftCode {
+                code: row.code,
+                tier: row.tier.parse().unwrap_or(Tier::Free),
+                duration_days: row.duration_days,
+                max_uses: row.max_uses,
+                uses: row.uses,
+                expires_at: row.expires_at.map(Timestamp::to_jiff),
+                created_by: u64::try_from(row.created_by).unwrap_or(0),
+            })
+            .collect())
+    }
+
+    pub async fn delete_gift_code(&self, code: &str) -> Result<bool, sqlx::Error> {
+        let deleted = sqlx::query!("DELETE FROM gift_codes WHERE code = $1", code)
+            .execute(&self.db)
+            .await?
+            .rows_affected();
+        Ok(deleted > 0)
+    }
+
+    /// Spends one use of `input` on the user and grants its tier for the
+    /// code's duration.
+    pub async fn redeem_gift_code(
+        &self,
+        user_id: u64,
+        input: &str,
+    ) -> Result<GiftGrant, GiftError> {
+        let code = normalize_code(input);
+        let user = i64::try_from(user_id).unwrap_or(i64::MAX);
+
+        let mut tx = self.db.begin().await?;
+
+        let row = sqlx::query!(
+            r#"SELECT tier, duration_days, max_uses, uses, created_by,
+                expires_at AS "expires_at: Timestamp"
+            FROM gift_codes
+            WHERE code = $1
+            FOR UPDATE"#,
+            code
+        )
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or(GiftError::NotFound)?;
+
+        let gift = GiftCode {
+            code,
+            tier: row.tier.parse().unwrap_or(Tier::Free),
+            duration_days: row.duration_days,
+            max_uses: row.max_uses,
+            uses: row.uses,
+            expires_at: row.expires_at.map(Timestamp::to_jiff),
+            created_by: u64::try_from(row.created_by).unwrap_or(0),
+        };
+
+        let now = jiff::Timestamp::now();
+        gift.check_redeemable(now)?;
+
+        let redeemed = sqlx::query!(
+            "INSERT INTO gift_code_redemptions (code, user_id) VALUES ($1, $2)
+            ON CONFLICT DO NOTHING",
+            gift.code,
+            user
+        )
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+        if redeemed == 0 {
+            return Err(GiftError::AlreadyRedeemed);
+        }
+
+        sqlx::query!(
+            "UPDATE gift_codes SET uses = uses + 1 WHERE code = $1",
+            gift.code
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        let grant = GiftGrant {
+            tier: gift.tier,
+            expires_at: grant_expiry(now, i64::from(gift.duration_days)),
+        };
+
+        // Granted in the same transaction as the use, so neither lands
+        // without the other.
+        let scope = EntitlementScope::User(user_id);
+        Self::insert_grant(
+            &mut *tx,
+            &scope,
+            grant.tier,
+            GIFT_PROVIDER,
+            &format!("{}:{user_id}", gift.code),
+            Some(grant.expires_at),
+        )
+        .await?;
+
+        tx.commit().await?;
+        self.announce(scope).await?;
+        Ok(grant)
+    }
+
+    /// Starts the guild's one free trial of [`TRIAL_TIER`].
+    pub async fn start_trial(
+        &self,
+        guild_id: u64,
+        user_id: u64,
+    ) -> Result<GiftGrant, GiftError> {
+        if self.guild_tier(guild_id).await > Tier::Free {
+            return Err(GiftError::AlreadyPremium);
+        }
+
+        let mut tx = self.db.begin().await?;
+
+        let started = sqlx::query!(
+            "INSERT INTO guild_trials (guild_id, started_by) VALUES ($1, $2)
+            ON CONFLICT DO NOTHING",
+            i64::try_from(guild_id).unwrap_or(i64::MAX),
+            i64::try_from(user_id).unwrap_or(i64::MAX),
+        )
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+        if started == 0 {
+            return Err(GiftError::TrialUsed);
+        }
+
+        let grant = GiftGrant {
+            tier: TRIAL_TIER,
+            expires_at: grant_expiry(jiff::Timestamp::now(), TRIAL_DAYS),
+        };
+
+        let scope = EntitlementScope::Guild(guild_id);
+        Self::insert_grant(
+            &mut *tx,
+            &scope,
+            grant.tier,
+            TRIAL_PROVIDER,
+            &guild_id.to_string(),
+            Some(grant.expires_at),
+        )
+        .await?;
+
+        tx.commit().await?;
+        self.announce(scope).await?;
+        Ok(grant)
+    }
+
     pub fn spawn_invalidator(
         this: Arc<Self>,
         mut rx: broadcast::Receiver<AppEvent>,
@@ -154,6 +595,9 @@ impl EntitlementService {
             loop {
                 match rx.recv().await {
                     Ok(AppEvent::EntitlementChanged(scope)) => {
+                        if let EntitlementScope::Guild(guild_id) = scope {
+                            this.slot_holders.invalidate(&guild_id).await;
+                        }
                         this.cache.invalidate(&scope).await;
                     },
                     Ok(_) => {},
@@ -163,6 +607,7 @@ impl EntitlementService {
                             "entitlement invalidator lagged; clearing full cache"
                         );
                         this.cache.invalidate_all();
+                        this.slot_holders.invalidate_all();
                     },
                     Err(broadcast::error::RecvError::Closed) => break,
                 }
@@ -216,6 +661,76 @@ impl EntitlementService {
 
     // Private helpers
 
+    async fn slot_tier(&self, guild_id: u64) -> Tier {
+        let holders = match self.slot_holders.get(&guild_id).await {
+            Some(holders) => holders,
+            None => match self.load_slot_holders(guild_id).await {
+                Ok(holders) => {
+                    let holders: Arc<[SlotHolder]> = holders.into();
+                    self.slot_holders.insert(guild_id, Arc::clone(&holders)).await;
+                    holders
+                },
+                Err(err) => {
+                    warn!(?err, "failed to load premium slots; ignoring them");
+                    return Tier::Free;
+                },
+            },
+        };
+
+        let mut best = Tier::Free;
+        for holder in holders.iter() {
+            let tier = self.user_tier(holder.user_id).await;
+            if holder.is_active(tier) {
+                best = best.max(tier);
+            }
+        }
+        best
+    }
+
+    async fn load_slot_holders(
+        &self,
+        guild_id: u64,
+    ) -> Result<Vec<SlotHolder>, sqlx::Error> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT user_id, rank AS "rank!"
+            FROM (
+                SELECT
+                    user_id,
+                    guild_id,
+                    ROW_NUMBER() OVER (
+                        PARTITION BY user_id ORDER BY assigned_at, guild_id
+                    ) AS rank
+                FROM premium_slots
+                WHERE user_id IN (
+                    SELECT user_id FROM premium_slots WHERE guild_id = $1
+                )
+            ) ranked
+            WHERE guild_id = $1
+            "#,
+            i64::try_from(guild_id).unwrap_or(i64::MAX),
+        )
+        .fetch_all(&self.db)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| SlotHolder {
+                user_id: u64::try_from(row.user_id).unwrap_or(0),
+                rank: usize::try_from(row.rank).unwrap_or(0),
+            })
+            .collect())
+    }
+
+    /// Drops this process's view of the guild's slots right away; the
+    /// `premium_slots` trigger tells every other process through NOTIFY.
+    async fn slots_changed(&self, guild_id: u64) {
+        let scope = EntitlementScope::Guild(guild_id);
+        self.slot_holders.invalidate(&guild_id).await;
+        self.cache.invalidate(&scope).await;
+        let _ = self.events.send(AppEvent::EntitlementChanged(scope));
+    }
+
     async fn tier_for_scope(&self, scope: EntitlementScope) -> Tier {
         if let Some(tier) = self.cache.get(&scope).await {
             return tier;
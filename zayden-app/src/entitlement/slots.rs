@@ -0,0 +1,60 @@
+use jiff::{SignedDuration, Timestamp};
+use thiserror::Error;
+
+use super::types::Tier;
+
+/// How long a slot stays on a guild before it can be moved, so one purchase
+/// can't be rotated between servers on demand.
+pub const SLOT_COOLDOWN: SignedDuration = SignedDuration::from_hours(7 * 24);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SlotAssignment {
+    pub guild_id: u64,
+    pub assigned_at: Timestamp,
+}
+
+impl SlotAssignment {
+    /// When the slot can next be unassigned.
+    #[must_use]
+    pub fn unlocks_at(&self) -> Timestamp {
+        self.assigned_at.checked_add(SLOT_COOLDOWN).unwrap_or(Timestamp::MAX)
+    }
+}
+
+/// A subscriber holding a slot on a guild, and where that guild falls in the
+/// subscriber's assignments, oldest first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SlotHolder {
+    pub user_id: u64,
+    pub rank: usize,
+}
+
+impl SlotHolder {
+    /// Whether a holder on `tier` still covers this guild. After a downgrade
+    /// only the oldest `premium_slots` assignments stay active.
+    #[must_use]
+    pub const fn is_active(self, tier: Tier) -> bool {
+        self.rank >= 1 && self.rank <= tier.premium_slots()
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum SlotError {
+    #[error("your plan does not include premium servers")]
+    NoSlots,
+
+    #[error("all {limit} of your premium server slots are in use")]
+    SlotsFull { limit: usize },
+
+    #[error("that server already has one of your slots")]
+    AlreadyAssigned,
+
+    #[error("that server does not have one of your slots")]
+    NotAssigned,
+
+    #[error("that slot can be moved from {until}")]
+    Cooldown { until: Timestamp },
+
+    #[error(transparent)]
+    Sqlx(#[from] sqlx::Error),
+}
@@ -33,6 +33,16 @@ impl Tier {
             Self::Ultra => "ultra",
         }
     }
+
+    /// How many guilds a subscriber on this tier can apply it to.
+    #[must_use]
+    pub const fn premium_slots(self) -> usize {
+        match self {
+            Self::Free => 0,
+            Self::Pro => 1,
+            Self::Ultra => 3,
+        }
+    }
 }
 
 impl std::str::FromStr for Tier {
@@ -48,6 +58,17 @@ impl std::str::FromStr for Tier {
     }
 }
 
+/// An `entitlements` row, as the admin console lists them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntitlementRecord {
+    pub provider: String,
+    pub external_id: String,
+    pub scope: EntitlementScope,
+    pub tier: Tier,
+    pub granted_at: jiff::Timestamp,
+    pub expires_at: Option<jiff::Timestamp>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum EntitlementScope {
     User(u64),
@@ -0,0 +1,324 @@
+//! Token accounting for the AI chat. Every reply records what the provider
+//! billed against its guild, member and model; before replying, the bot
+//! checks those totals against the quota for the server's tier, which the
+//! owner can raise per guild from the admin console.
+
+use jiff_sqlx::Timestamp as SqlxTimestamp;
+use sqlx::PgPool;
+
+use crate::entitlement::Tier;
+
+/// Token budgets. Guild limits cover everyone in the server; the member
+/// limit covers one member across every server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AiQuota {
+    pub guild_daily: i64,
+    pub guild_monthly: i64,
+    pub user_daily: i64,
+}
+
+impl AiQuota {
+    #[must_use]
+    pub const fn for_tier(tier: Tier) -> Self {
+        match tier {
+            Tier::Free => Self {
+                guild_daily: 100_000,
+                guild_monthly: 1_500_000,
+                user_daily: 20_000,
+            },
+            Tier::Pro => Self {
+                guild_daily: 1_000_000,
+                guild_monthly: 20_000_000,
+                user_daily: 200_000,
+            },
+            Tier::Ultra => Self {
+                guild_daily: 3_000_000,
+                guild_monthly: 60_000_000,
+                user_daily: 600_000,
+            },
+        }
+    }
+
+    /// The guild limits lifted to `limit`'s where those are higher. An
+    /// override never lowers a guild below its tier.
+    #[must_use]
+    pub fn raised_by(self, limit: Option<&QuotaOverride>) -> Self {
+        let Some(limit) = limit else {
+            return self;
+        };
+
+        Self {
+            guild_daily: limit
+                .daily_tokens
+                .map_or(self.guild_daily, |daily| daily.max(self.guild_daily)),
+            guild_monthly: limit
+                .monthly_tokens
+                .map_or(self.guild_monthly, |monthly| {
+                    monthly.max(self.guild_monthly)
+                }),
+            user_daily: self.user_daily,
+        }
+    }
+}
+
+/// The limit a reply would go over.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuotaLimit {
+    UserDaily,
+    GuildDaily,
+    GuildMonthly,
+}
+
+/// Tokens spent so far in the current day and month, UTC.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Spent {
+    pub guild_today: i64,
+    pub guild_month: i64,
+    pub user_today: i64,
+}
+
+impl Spent {
+    /// The limit already reached, if any. Reaching a limit exactly counts,
+    /// and the one that takes longest to reset is reported first.
+    #[must_use]
+    pub const fn exceeded(self, quota: AiQuota) -> Option<QuotaLimit> {
+        if self.guild_month >= quota.guild_monthly {
+            Some(QuotaLimit::GuildMonthly)
+        } else if self.guild_today >= quota.guild_daily {
+            Some(QuotaLimit::GuildDaily)
+        } else if self.user_today >= quota.user_daily {
+            Some(QuotaLimit::UserDaily)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuotaOverride {
+    pub guild_id: i64,
+    pub daily_tokens: Option<i64>,
+    pub monthly_tokens: Option<i64>,
+    pub set_by: i64,
+    pub updated_at: jiff::Timestamp,
+}
+
+/// One guild's spend on one model over the report's window.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModelUsage {
+    pub guild_id: i64,
+    /// Empty for a guild the bot has no record of joining.
+    pub guild_name: String,
+    pub model: String,
+    pub requests: i64,
+    pub prompt_tokens: i64,
+    pub completion_tokens: i64,
+}
+
+impl ModelUsage {
+    #[must_use]
+    pub const fn total_tokens(&self) -> i64 {
+        self.prompt_tokens.saturating_add(self.completion_tokens)
+    }
+}
+
+pub async fn record(
+    pool: &PgPool,
+    guild_id: i64,
+    user_id: i64,
+    model: &str,
+    prompt_tokens: u32,
+    completion_tokens: u32,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        "INSERT INTO ai_usage_daily
+            (day, guild_id, user_id, model, requests, prompt_tokens,
+             completion_tokens)
+        VALUES ((now() AT TIME ZONE 'UTC')::date, $1, $2, $3, 1, $4, $5)
+        ON CONFLICT (day, guild_id, user_id, model) DO UPDATE SET
+            requests = ai_usage_daily.requests + 1,
+            prompt_tokens = ai_usage_daily.prompt_tokens + EXCLUDED.prompt_tokens,
+            completion_tokens =
+                ai_usage_daily.completion_tokens + EXCLUDED.completion_tokens",
+        guild_id,
+        user_id,
+        model,
+        i64::from(prompt_tokens),
+        i64::from(completion_tokens),
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn spent(
+    pool: &PgPool,
+    guild_id: i64,
+    user_id: i64,
+) -> Result<Spent, sqlx::Error> {
+    let row = sqlx::query!(
+        r#"SELECT
+            COALESCE(SUM(prompt_tokens + completion_tokens) FILTER (
+                WHERE guild_id = $1 AND day = (now() AT TIME ZONE 'UTC')::date
+            ), 0)::bigint AS "guild_today!",
+            COALESCE(SUM(prompt_tokens + completion_tokens) FILTER (
+                WHERE guild_id = $1
+            ), 0)::bigint AS "guild_month!",
+            COALESCE(SUM(prompt_tokens + completion_tokens) FILTER (
+                WHERE user_id = $2 AND day = (now() AT TIME ZONE 'UTC')::date
+            ), 0)::bigint AS "user_today!"
+        FROM ai_usage_daily
+        WHERE day >= date_trunc('month', now() AT TIME ZONE 'UTC')::date
+            AND (guild_id = $1 OR user_id = $2)"#,
+        guild_id,
+        user_id,
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(Spent {
+        guild_today: row.guild_today,
+        guild_month: row.guild_month,
+        user_today: row.user_today,
+    })
+}
+
+pub async fn quota_override(
+    pool: &PgPool,
+    guild_id: i64,
+) -> Result<Option<QuotaOverride>, sqlx::Error> {
+    sqlx::query!(
+        r#"SELECT guild_id, daily_tokens, monthly_tokens, set_by,
+            updated_at AS "updated_at: SqlxTimestamp"
+        FROM ai_quota_overrides
+        WHERE guild_id = $1"#,
+        guild_id,
+    )
+    .fetch_optional(pool)
+    .await
+    .map(|row| {
+        row.map(|row| QuotaOverride {
+            guild_id: row.guild_id,
+            daily_tokens: row.daily_tokens,
+            monthly_tokens: row.monthly_tokens,
+            set_by: row.set_by,
+            updated_at: row.updated_at.to_jiff(),
+        })
+    })
+}
+
+/// The quota for a member on `member_tier` in a guild on `server_tier`, with
+/// any override of the guild's applied.
+pub async fn quota(
+    pool: &PgPool,
+    guild_id: i64,
+    server_tier: Tier,
+    member_tier: Tier,
+) -> Result<AiQuota, sqlx::Error> {
+    let limit = quota_override(pool, guild_id).await?;
+
+    Ok(AiQuota {
+        user_daily: AiQuota::for_tier(member_tier).user_daily,
+        ..AiQuota::for_tier(server_tier).raised_by(limit.as_ref())
+    })
+}
+
+/// Tokens per guild and model over the last `days`, biggest spend first.
+pub async fn usage_by_guild_and_model(
+    pool: &PgPool,
+    days: i32,
+) -> Result<Vec<ModelUsage>, sqlx::Error> {
+    let rows = sqlx::query!(
+        r#"SELECT u.guild_id, COALESCE(m.name, '') AS "guild_name!", u.model,
+            SUM(u.requests)::bigint AS "requests!",
+            SUM(u.prompt_tokens)::bigint AS "prompt_tokens!",
+            SUM(u.completion_tokens)::bigint AS "completion_tokens!"
+        FROM ai_usage_daily u
+        LEFT JOIN guild_membership m ON m.guild_id = u.guild_id
+        WHERE u.day > (now() AT TIME ZONE 'UTC')::date - $1::integer
+        GROUP BY u.guild_id, m.name, u.model
+        ORDER BY SUM(u.prompt_tokens + u.completion_tokens) DESC, u.guild_id, u.model"#,
+        days,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| ModelUsage {
+            guild_id: row.guild_id,
+            guild_name: row.guild_name,
+            model: row.model,
+            requests: row.requests,
+            prompt_tokens: row.prompt_tokens,
+            completion_tokens: row.completion_tokens,
+        })
+        .collect())
+}
+
+pub async fn overrides(pool: &PgPool) -> Result<Vec<QuotaOverride>, sqlx::Error> {
+    let rows = sqlx::query!(
+        r#"SELECT guild_id, daily_tokens, monthly_tokens, set_by,
+            updated_at AS "updated_at: SqlxTimestamp"
+        FROM ai_quota_overrides
+        ORDER BY updated_at DESC"#
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| QuotaOverride {
+            guild_id: row.guild_id,
+            daily_tokens: row.daily_tokens,
+            monthly_tokens: row.monthly_tokens,
+            set_by: row.set_by,
+            updated_at: row.updated_at.to_jiff(),
+        })
+        .collect())
+}
+
+/// Sets a guild's override, replacing any earlier one.
+pub async fn set_override(
+    pool: &PgPool,
+    guild_id: i64,
+    daily_tokens: Option<i64>,
+    monthly_tokens: Option<i64>,
+    set_by: i64,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        "INSERT INTO ai_quota_overrides
+            (guild_id, daily_tokens, monthly_tokens, set_by)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (guild_id) DO UPDATE SET
+            daily_tokens = EXCLUDED.daily_tokens,
+            monthly_tokens = EXCLUDED.monthly_tokens,
+            set_by = EXCLUDED.set_by,
+            updated_at = now()",
+        guild_id,
+        daily_tokens,
+        monthly_tokens,
+        set_by,
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Puts a guild back on its tier's quota. `false` if it had no override.
+pub async fn clear_override(
+    pool: &PgPool,
+    guild_id: i64,
+) -> Result<bool, sqlx::Error> {
+    let cleared = sqlx::query!(
+        "DELETE FROM ai_quota_overrides WHERE guild_id = $1",
+        guild_id,
+    )
+    .execute(pool)
+    .await?
+    .rows_affected();
+
+    Ok(cleared > 0)
+}
@@ -0,0 +1,300 @@
+//! Moderation appeals. A punished member appeals their latest mute or ban
+//! from the DM they were sent; staff decide in the guild's appeals channel.
+//! Each infraction can be appealed once.
+
+use sqlx::PgPool;
+
+/// What an appeal asks to have undone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Punishment {
+    Mute,
+    SoftBan,
+    Ban,
+}
+
+impl Punishment {
+    /// Parses an `infraction_kind`. `None` for the kinds with nothing to
+    /// appeal.
+    #[must_use]
+    pub fn from_kind(kind: &str) -> Option<Self> {
+        match kind {
+            "Mute" => Some(Self::Mute),
+            "SoftBan" => Some(Self::SoftBan),
+            "Ban" => Some(Self::Ban),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Mute => "mute",
+            Self::SoftBan => "soft-ban",
+            Self::Ban => "ban",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AppealStatus {
+    Pending,
+    Approved,
+    Denied,
+}
+
+impl AppealStatus {
+    fn parse(status: &str) -> Self {
+        match status {
+            "approved" => Self::Approved,
+            "denied" => Self::Denied,
+            _ => Self::Pending,
+        }
+    }
+
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Approved => "approved",
+            Self::Denied => "denied",
+        }
+    }
+}
+
+/// A member's latest mute or ban that is still in force.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Appealable {
+    pub infraction_id: i32,
+    pub punishment: Punishment,
+    pub reason: String,
+    /// Whether it has been appealed already.
+    pub appealed: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Appeal {
+    pub id: i32,
+    pub infraction_id: i32,
+    pub guild_id: i64,
+    pub user_id: i64,
+    pub punishment: Punishment,
+    /// The infraction's reason.
+    pub reason: String,
+    pub statement: String,
+    pub status: AppealStatus,
+}
+
+struct AppealRow {
+    id: i32,
+    infraction_id: i32,
+    guild_id: i64,
+    user_id: i64,
+    kind: String,
+    reason: String,
+    statement: String,
+    status: String,
+}
+
+impl AppealRow {
+    fn into_appeal(self) -> Option<Appeal> {
+        Some(Appeal {
+            id: self.id,
+            infraction_id: self.infraction_id,
+            guild_id: self.guild_id,
+            user_id: self.user_id,
+            punishment: Punishment::from_kind(&self.kind)?,
+            reason: self.reason,
+            statement: self.statement,
+            status: AppealStatus::parse(&self.status),
+        })
+    }
+}
+
+/// The member's most recent mute, soft-ban or ban in the guild that hasn't
+/// been lifted, expunged or run out.
+pub async fn appealable(
+    pool: &PgPool,
+    guild_id: i64,
+    user_id: i64,
+) -> sqlx::Result<Option<Appealable>> {
+    let row = sqlx::query!(
+        r#"SELECT
+            i.id,
+            i.infraction_type::text AS "kind!",
+            i.reason,
+            EXISTS (SELECT 1 FROM appeals a WHERE a.infraction_id = i.id)
+                AS "appealed!"
+        FROM infractions i
+        WHERE i.guild_id = $1
+            AND i.user_id = $2
+            AND i.infraction_type IN ('Mute', 'SoftBan', 'Ban')
+            AND i.expunged_at IS NULL
+            AND i.lifted_at IS NULL
+            AND (i.expires_at IS NULL OR i.expires_at > now())
+        ORDER BY i.created_at DESC
+        LIMIT 1"#,
+        guild_id,
+        user_id,
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(row.and_then(|row| {
+        Some(Appealable {
+            infraction_id: row.id,
+            punishment: Punishment::from_kind(&row.kind)?,
+            reason: row.reason,
+            appealed: row.appealed,
+        })
+    }))
+}
+
+/// Files an appeal against `infraction_id` and returns its id, or `None` if
+/// the infraction was appealed already.
+pub async fn open(
+    pool: &PgPool,
+    guild_id: i64,
+    user_id: i64,
+    infraction_id: i32,
+    statement: &str,
+) -> sqlx::Result<Option<i32>> {
+    sqlx::query_scalar!(
+        "INSERT INTO appeals (infraction_id, guild_id, user_id, statement)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (infraction_id) DO NOTHING
+        RETURNING id",
+        infraction_id,
+        guild_id,
+        user_id,
+        statement,
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+/// Remembers the staff channel post an appeal was sent to.
+pub async fn set_message(
+    pool: &PgPool,
+    id: i32,
+    channel_id: i64,
+    message_id: i64,
+) -> sqlx::Result<()> {
+    sqlx::query!(
+        "UPDATE appeals SET channel_id = $2, message_id = $3 WHERE id = $1",
+        id,
+        channel_id,
+        message_id,
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Drops an appeal that never reached staff, so the member can try again.
+pub async fn withdraw(pool: &PgPool, id: i32) -> sqlx::Result<()> {
+    sqlx::query!("DELETE FROM appeals WHERE id = $1 AND message_id IS NULL", id)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
+
+pub async fn get(
+    pool: &PgPool,
+    guild_id: i64,
+    id: i32,
+) -> sqlx::Result<Option<Appeal>> {
+    let row = sqlx::query_as!(
+        AppealRow,
+        r#"SELECT
+            a.id,
+            a.infraction_id,
+            a.guild_id,
+            a.user_id,
+            i.infraction_type::text AS "kind!",
+            i.reason,
+            a.statement,
+            a.status
+        FROM appeals a
+        JOIN infractions i ON i.id = a.infraction_id
+        WHERE a.guild_id = $1 AND a.id = $2"#,
+        guild_id,
+        id,
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(row.and_then(AppealRow::into_appeal))
+}
+
+/// Records a decision on a pending appeal and on its infraction. An approved
+/// appeal lifts the infraction and drops any timed action still waiting to
+/// undo it. `false` if the appeal was already decided.
+pub async fn decide(
+    pool: &PgPool,
+    appeal: &Appeal,
+    approved: bool,
+    decided_by: i64,
+    note: &str,
+) -> sqlx::Result<bool> {
+    let status =
+        if approved { AppealStatus::Approved } else { AppealStatus::Denied };
+
+    let mut tx = pool.begin().await?;
+
+    let decided = sqlx::query!(
+        "UPDATE appeals SET status = $2, decided_at = now(), decided_by = $3
+        WHERE id = $1 AND status = 'pending'",
+        appeal.id,
+        status.as_str(),
+        decided_by,
+    )
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+
+    if decided == 0 {
+        return Ok(false);
+    }
+
+    if approved {
+        sqlx::query!(
+            "UPDATE infractions SET
+                lifted_at = now(),
+                lift_reason = $2,
+                updated_at = now(),
+                updated_by = $3
+            WHERE id = $1",
+            appeal.infraction_id,
+            note,
+            decided_by,
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query!(
+            "UPDATE timed_actions SET cancelled_at = now(), cancelled_by = $2
+            WHERE infraction_id = $1
+                AND completed_at IS NULL
+                AND cancelled_at IS NULL",
+            appeal.infraction_id,
+            decided_by,
+        )
+        .execute(&mut *tx)
+        .await?;
+    } else {
+        sqlx::query!(
+            "UPDATE infractions SET updated_at = now(), updated_by = $2
+            WHERE id = $1",
+            appeal.infraction_id,
+            decided_by,
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    tx.commit().await?;
+
+    Ok(true)
+}
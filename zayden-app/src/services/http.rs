@@ -6,10 +6,19 @@ pub const HTTP_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
 pub trait ClientBuilderExt {
     #[must_use = "the budget applies to the returned builder, not to this one"]
     fn with_timeouts(self) -> Self;
+
+    /// As [`Self::with_timeouts`], but for streamed responses, which can run
+    /// well past [`HTTP_TIMEOUT`]: only a stall between reads times out.
+    #[must_use = "the budget applies to the returned builder, not to this one"]
+    fn with_stream_timeouts(self) -> Self;
 }
 
 impl ClientBuilderExt for reqwest::ClientBuilder {
     fn with_timeouts(self) -> Self {
         self.timeout(HTTP_TIMEOUT).connect_timeout(HTTP_CONNECT_TIMEOUT)
     }
+
+    fn with_stream_timeouts(self) -> Self {
+        self.read_timeout(HTTP_TIMEOUT).connect_timeout(HTTP_CONNECT_TIMEOUT)
+    }
 }
@@ -0,0 +1,196 @@
+//! Raid lockdowns. A guild has at most one at a time; while it lasts the
+//! verify panel is paused and suspicious joiners are actioned. What the
+//! lockdown changed on Discord is recorded here so ending it can undo it.
+
+use jiff_sqlx::Timestamp as SqlxTimestamp;
+use sqlx::PgPool;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lockdown {
+    pub guild_id: i64,
+    pub started_at: jiff::Timestamp,
+    /// `None` until ended by hand.
+    pub ends_at: Option<jiff::Timestamp>,
+    pub reason: String,
+    /// `None` when the join rate started it.
+    pub started_by: Option<i64>,
+    /// The verification level to restore, if the lockdown raised it.
+    pub previous_verification_level: Option<i16>,
+    /// Suspicious joiners seen, whether or not the raid action did anything
+    /// to them.
+    pub joiners_actioned: i32,
+}
+
+/// A channel's slowmode before the lockdown changed it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SlowmodeChange {
+    pub channel_id: i64,
+    pub previous_slowmode: i32,
+}
+
+pub async fn active(pool: &PgPool, guild_id: i64) -> sqlx::Result<Option<Lockdown>> {
+    let row = sqlx::query!(
+        r#"SELECT guild_id, started_at AS "started_at: SqlxTimestamp",
+            ends_at AS "ends_at: SqlxTimestamp", reason, started_by,
+            previous_verification_level, joiners_actioned
+        FROM raid_lockdowns
+        WHERE guild_id = $1"#,
+        guild_id,
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(row.map(|row| Lockdown {
+        guild_id: row.guild_id,
+        started_at: row.started_at.to_jiff(),
+        ends_at: row.ends_at.map(|t| t.to_jiff()),
+        reason: row.reason,
+        started_by: row.started_by,
+        previous_verification_level: row.previous_verification_level,
+        joiners_actioned: row.joiners_actioned,
+    }))
+}
+
+pub async fn is_active(pool: &PgPool, guild_id: i64) -> sqlx::Result<bool> {
+    sqlx::query_scalar!(
+        r#"SELECT EXISTS (
+            SELECT 1 FROM raid_lockdowns WHERE guild_id = $1
+        ) AS "active!""#,
+        guild_id,
+    )
+    .fetch_one(pool)
+    .await
+}
+
+/// Starts a lockdown lasting `minutes`, or until ended by hand when `None`.
+/// Returns `false` if the guild is already locked down.
+pub async fn start(
+    pool: &PgPool,
+    guild_id: i64,
+    minutes: Option<i32>,
+    reason: &str,
+    started_by: Option<i64>,
+) -> sqlx::Result<bool> {
+    let started = sqlx::query!(
+        "INSERT INTO raid_lockdowns (guild_id, ends_at, reason, started_by)
+        VALUES ($1, now() + make_interval(mins => $2), $3, $4)
+        ON CONFLICT (guild_id) DO NOTHING",
+        guild_id,
+        minutes,
+        reason,
+        started_by,
+    )
+    .execute(pool)
+    .await?
+    .rows_affected();
+
+    Ok(started > 0)
+}
+
+pub async fn record_verification_level(
+    pool: &PgPool,
+    guild_id: i64,
+    previous: i16,
+) -> sqlx::Result<()> {
+    sqlx::query!(
+        "UPDATE raid_lockdowns SET previous_verification_level = $2
+        WHERE guild_id = $1",
+        guild_id,
+        previous,
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Keeps the first recorded slowmode if a channel is changed twice, so the
+/// original is what gets restored.
+pub async fn record_slowmode(
+    pool: &PgPool,
+    guild_id: i64,
+    change: SlowmodeChange,
+) -> sqlx::Result<()> {
+    sqlx::query!(
+        "INSERT INTO raid_lockdown_channels (guild_id, channel_id, previous_slowmode)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (guild_id, channel_id) DO NOTHING",
+        guild_id,
+        change.channel_id,
+        change.previous_slowmode,
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Counts a suspicious joiner towards the lockdown and returns how many
+/// there have been. 0 if it already ended.
+pub async fn count_actioned(pool: &PgPool, guild_id: i64) -> sqlx::Result<i32> {
+    let count = sqlx::query_scalar!(
+        "UPDATE raid_lockdowns SET joiners_actioned = joiners_actioned + 1
+        WHERE guild_id = $1
+        RETURNING joiners_actioned",
+        guild_id,
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(count.unwrap_or_default())
+}
+
+/// Ends the guild's lockdown, returning it and the slowmodes to restore.
+/// `None` if it wasn't locked down.
+pub async fn end(
+    pool: &PgPool,
+    guild_id: i64,
+) -> sqlx::Result<Option<(Lockdown, Vec<SlowmodeChange>)>> {
+    let mut tx = pool.begin().await?;
+
+    let channels = sqlx::query_as!(
+        SlowmodeChange,
+        "DELETE FROM raid_lockdown_channels
+        WHERE guild_id = $1
+        RETURNING channel_id, previous_slowmode",
+        guild_id,
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+
+    let row = sqlx::query!(
+        r#"DELETE FROM raid_lockdowns
+        WHERE guild_id = $1
+        RETURNING guild_id, started_at AS "started_at: SqlxTimestamp",
+            ends_at AS "ends_at: SqlxTimestamp", reason, started_by,
+            previous_verification_level, joiners_actioned"#,
+        guild_id,
+    )
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(row.map(|row| {
+        let lockdown = Lockdown {
+            guild_id: row.guild_id,
+            started_at: row.started_at.to_jiff(),
+            ends_at: row.ends_at.map(|t| t.to_jiff()),
+            reason: row.reason,
+            started_by: row.started_by,
+            previous_verification_level: row.previous_verification_level,
+            joiners_actioned: row.joiners_actioned,
+        };
+
+        (lockdown, channels)
+    }))
+}
+
+/// Guilds whose timed lockdown has run out.
+pub async fn expired(pool: &PgPool) -> sqlx::Result<Vec<i64>> {
+    sqlx::query_scalar!(
+        "SELECT guild_id FROM raid_lockdowns WHERE ends_at <= now()",
+    )
+    .fetch_all(pool)
+    .await
+}
@@ -1 +1,9 @@
+pub mod ai_usage;
+pub mod appeals;
 pub mod http;
+pub mod lockdown;
+pub mod ops;
+pub mod rules;
+pub mod scam_domains;
+pub mod threats;
+pub mod timed_actions;
@@ -0,0 +1,356 @@
+//! Operational records the bot writes and the owner's admin console reads:
+//! guild joins and leaves, command usage, recent warnings and errors, and
+//! cron job runs.
+
+use jiff::civil::Date;
+use jiff_sqlx::{Date as SqlxDate, Timestamp as SqlxTimestamp};
+use sqlx::PgPool;
+
+/// Longest a log line is stored; the rest is cut off.
+pub const MAX_LOG_MESSAGE: usize = 4000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct GuildStats {
+    pub active: i64,
+    pub joined: i64,
+    pub left: i64,
+}
+
+impl GuildStats {
+    #[must_use]
+    pub const fn net_growth(self) -> i64 {
+        self.joined - self.left
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GrowthDay {
+    pub day: Date,
+    pub joined: i64,
+    pub left: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandUsage {
+    pub command: String,
+    pub module: String,
+    pub uses: i64,
+    pub failures: i64,
+}
+
+/// Sums `usage` per module, busiest first. Commands without a module are
+/// grouped under `""`.
+#[must_use]
+pub fn usage_by_module(usage: &[CommandUsage]) -> Vec<(String, i64)> {
+    let mut modules: Vec<(String, i64)> = Vec::new();
+    for row in usage {
+        match modules.iter_mut().find(|(module, _)| *module == row.module) {
+            Some((_, uses)) => *uses += row.uses,
+            None => modules.push((row.module.clone(), row.uses)),
+        }
+    }
+    modules.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    modules
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogEntry {
+    pub level: String,
+    pub target: String,
+    pub message: String,
+    pub created_at: jiff::Timestamp,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronRun {
+    pub job_id: String,
+    pub last_started_at: jiff::Timestamp,
+    pub last_finished_at: jiff::Timestamp,
+    pub next_run_at: Option<jiff::Timestamp>,
+    pub runs: i64,
+}
+
+/// Cuts `message` to [`MAX_LOG_MESSAGE`] bytes on a char boundary.
+#[must_use]
+pub fn truncate_log(message: &str) -> &str {
+    if message.len() <= MAX_LOG_MESSAGE {
+        return message;
+    }
+    let mut end = MAX_LOG_MESSAGE;
+    while !message.is_char_boundary(end) {
+        end -= 1;
+    }
+    &message[..end]
+}
+
+#[expect(
+    trivial_casts,
+    reason = "not a cast: `as T` is sqlx's bind-param type-override syntax, required because TIMESTAMPTZ has no built-in jiff mapping"
+)]
+pub async fn record_guild_join(
+    pool: &PgPool,
+    guild_id: u64,
+    name: &str,
+    member_count: u64,
+    joined_at: jiff::Timestamp,
+) -> Result<(), sqlx::Error> {
+    let joined_at = SqlxTimestamp::from(joined_at);
+
+    sqlx::query!(
+        "INSERT INTO guild_membership (guild_id, name, member_count, joined_at)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (guild_id) DO UPDATE SET
+            name = EXCLUDED.name,
+            member_count = EXCLUDED.member_count,
+            joined_at = EXCLUDED.joined_at,
+            left_at = NULL",
+        i64::try_from(guild_id).unwrap_or(i64::MAX),
+        name,
+        i32::try_from(member_count).unwrap_or(i32::MAX),
+        joined_at as SqlxTimestamp,
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn record_guild_leave(
+    pool: &PgPool,
+    guild_id: u64,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        "UPDATE guild_membership SET left_at = now()
+        WHERE guild_id = $1 AND left_at IS NULL",
+        i64::try_from(guild_id).unwrap_or(i64::MAX),
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn record_command(
+    pool: &PgPool,
+    command: &str,
+    module: Option<&str>,
+    succeeded: bool,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        "INSERT INTO command_usage_daily (command, module, uses, failures)
+        VALUES ($1, $2, 1, $3)
+        ON CONFLICT (day, command) DO UPDATE SET
+            uses = command_usage_daily.uses + 1,
+            failures = command_usage_daily.failures + EXCLUDED.failures",
+        command,
+        module.unwrap_or_default(),
+        i64::from(!succeeded),
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn record_log(
+    pool: &PgPool,
+    level: &str,
+    target: &str,
+    message: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        "INSERT INTO bot_log_entries (level, target, message) VALUES ($1, $2, $3)",
+        level,
+        target,
+        truncate_log(message),
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Drops log entries older than `days`, returning how many went.
+pub async fn prune_logs(pool: &PgPool, days: i32) -> Result<u64, sqlx::Error> {
+    let deleted = sqlx::query!(
+        "DELETE FROM bot_log_entries WHERE created_at < now() - make_interval(days => $1)",
+        days,
+    )
+    .execute(pool)
+    .await?
+    .rows_affected();
+
+    Ok(deleted)
+}
+
+#[expect(
+    trivial_casts,
+    reason = "not a cast: `as T` is sqlx's bind-param type-override syntax, required because TIMESTAMPTZ has no built-in jiff mapping"
+)]
+pub async fn record_cron_run(
+    pool: &PgPool,
+    job_id: &str,
+    started_at: jiff::Timestamp,
+    finished_at: jiff::Timestamp,
+    next_run_at: Option<jiff::Timestamp>,
+) -> Result<(), sqlx::Error> {
+    let started_at = SqlxTimestamp::from(started_at);
+    let finished_at = SqlxTimestamp::from(finished_at);
+    let next_run_at = next_run_at.map(SqlxTimestamp::from);
+
+    sqlx::query!(
+        "INSERT INTO cron_job_runs
+            (job_id, last_started_at, last_finished_at, next_run_at)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (job_id) DO UPDATE SET
+            last_started_at = EXCLUDED.last_started_at,
+            last_finished_at = EXCLUDED.last_finished_at,
+            next_run_at = EXCLUDED.next_run_at,
+            runs = cron_job_runs.runs + 1",
+        job_id,
+        started_at as SqlxTimestamp,
+        finished_at as SqlxTimestamp,
+        next_run_at as Option<SqlxTimestamp>,
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Current guild count plus joins and leaves over the last `days`.
+pub async fn guild_stats(
+    pool: &PgPool,
+    days: i32,
+) -> Result<GuildStats, sqlx::Error> {
+    let row = sqlx::query!(
+        r#"SELECT
+            COUNT(*) FILTER (WHERE left_at IS NULL) AS "active!",
+            COUNT(*) FILTER (
+                WHERE joined_at >= now() - make_interval(days => $1)
+            ) AS "joined!",
+            COUNT(*) FILTER (
+                WHERE left_at >= now() - make_interval(days => $1)
+            ) AS "left!"
+        FROM guild_membership"#,
+        days,
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(GuildStats { active: row.active, joined: row.joined, left: row.left })
+}
+
+/// Joins and leaves per day over the last `days`, oldest first, with empty
+/// days included.
+pub async fn guild_growth(
+    pool: &PgPool,
+    days: i32,
+) -> Result<Vec<GrowthDay>, sqlx::Error> {
+    let rows = sqlx::query!(
+        r#"SELECT
+            d.day::date AS "day!: SqlxDate",
+            (
+                SELECT COUNT(*) FROM guild_membership
+                WHERE joined_at::date = d.day::date
+            ) AS "joined!",
+            (
+                SELECT COUNT(*) FROM guild_membership
+                WHERE left_at::date = d.day::date
+            ) AS "left!"
+        FROM generate_series(
+            CURRENT_DATE - ($1 - 1), CURRENT_DATE, interval '1 day'
+        ) AS d(day)
+        ORDER BY d.day"#,
+        days,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| GrowthDay {
+            day: row.day.to_jiff(),
+            joined: row.joined,
+            left: row.left,
+        })
+        .collect())
+}
+
+/// Uses per command over the last `days`, busiest first.
+pub async fn command_usage(
+    pool: &PgPool,
+    days: i32,
+) -> Result<Vec<CommandUsage>, sqlx::Error> {
+    let rows = sqlx::query!(
+        r#"SELECT command, module,
+            SUM(uses)::bigint AS "uses!", SUM(failures)::bigint AS "failures!"
+        FROM command_usage_daily
+        WHERE day > CURRENT_DATE - $1::integer
+        GROUP BY command, module
+        ORDER BY 3 DESC, command"#,
+        days,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| CommandUsage {
+            command: row.command,
+            module: row.module,
+            uses: row.uses,
+            failures: row.failures,
+        })
+        .collect())
+}
+
+pub async fn recent_logs(
+    pool: &PgPool,
+    limit: i64,
+) -> Result<Vec<LogEntry>, sqlx::Error> {
+    let rows = sqlx::query!(
+        r#"SELECT level, target, message, created_at AS "created_at: SqlxTimestamp"
+        FROM bot_log_entries
+        ORDER BY created_at DESC
+        LIMIT $1"#,
+        limit,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| LogEntry {
+            level: row.level,
+            target: row.target,
+            message: row.message,
+            created_at: row.created_at.to_jiff(),
+        })
+        .collect())
+}
+
+pub async fn cron_runs(pool: &PgPool) -> Result<Vec<CronRun>, sqlx::Error> {
+    let rows = sqlx::query!(
+        r#"SELECT job_id,
+            last_started_at AS "last_started_at: SqlxTimestamp",
+            last_finished_at AS "last_finished_at: SqlxTimestamp",
+            next_run_at AS "next_run_at: SqlxTimestamp",
+            runs
+        FROM cron_job_runs
+        ORDER BY job_id"#
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| CronRun {
+            job_id: row.job_id,
+            last_started_at: row.last_started_at.to_jiff(),
+            last_finished_at: row.last_finished_at.to_jiff(),
+            next_run_at: row.next_run_at.map(SqlxTimestamp::to_jiff),
+            runs: row.runs,
+        })
+        .collect())
+}
@@ -0,0 +1,247 @@
+//! Server rules as members accept them. A guild's rules carry a version that
+//! only goes up when their wording changes materially; the verify button
+//! records which version each member accepted, optionally after a short quiz.
+
+use md5::{Digest, Md5};
+use sqlx::PgPool;
+
+/// A Discord modal holds at most five inputs.
+pub const MAX_QUIZ_QUESTIONS: usize = 5;
+
+/// Modal input labels are limited to 45 characters.
+pub const MAX_QUESTION_CHARS: usize = 45;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rule {
+    pub title: String,
+    pub body: String,
+}
+
+/// A guild's rules and embed styling at one version.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RulesSnapshot {
+    pub version: i32,
+    pub title: String,
+    pub description: Option<String>,
+    pub colour: i32,
+    pub rules: Vec<Rule>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuizQuestion {
+    pub id: i32,
+    pub question: String,
+    pub answers: Vec<String>,
+}
+
+impl QuizQuestion {
+    /// Whether `given` is one of the accepted answers, ignoring case,
+    /// punctuation and spacing.
+    #[must_use]
+    pub fn accepts(&self, given: &str) -> bool {
+        let given = normalize(given);
+        !given.is_empty() && self.answers.iter().any(|a| normalize(a) == given)
+    }
+}
+
+/// A member whose accepted version is older than their guild's rules.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StaleAcceptance {
+    pub guild_id: i64,
+    pub user_id: i64,
+    pub version: i32,
+    pub verified_role_id: Option<i64>,
+}
+
+/// `text`'s words, lowercased and single-spaced, with punctuation, Markdown
+/// and line breaks dropped.
+fn normalize(text: &str) -> String {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// What counts as a material change: the words of the description and of
+/// every rule. Case, punctuation, formatting and the order of the rules are
+/// ignored, so fixing a comma or reordering doesn't re-prompt anyone.
+#[must_use]
+pub fn fingerprint(description: Option<&str>, rules: &[Rule]) -> String {
+    let mut rules = rules
+        .iter()
+        .map(|rule| normalize(&format!("{}\n{}", rule.title, rule.body)))
+        .collect::<Vec<_>>();
+    rules.sort_unstable();
+
+    let mut hasher = Md5::new();
+    hasher.update(normalize(description.unwrap_or_default()));
+    for rule in rules {
+        hasher.update([0]);
+        hasher.update(rule);
+    }
+    hex::encode(hasher.finalize())
+}
+
+/// The guild's rules at their current version, or `None` when it has none.
+/// A material change since the version was last taken bumps it first; the
+/// first fingerprint a guild gets keeps version 1.
+pub async fn current(
+    pool: &PgPool,
+    guild_id: i64,
+) -> sqlx::Result<Option<RulesSnapshot>> {
+    let Some(config) = sqlx::query!(
+        "SELECT title, description, colour, version, fingerprint
+        FROM guild_rules WHERE guild_id = $1",
+        guild_id,
+    )
+    .fetch_optional(pool)
+    .await?
+    else {
+        return Ok(None);
+    };
+
+    let rules = sqlx::query_as!(
+        Rule,
+        "SELECT title, body FROM guild_rule WHERE guild_id = $1 ORDER BY position",
+        guild_id,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    if rules.is_empty() {
+        return Ok(None);
+    }
+
+    let fingerprint = fingerprint(config.description.as_deref(), &rules);
+
+    let version = if config.fingerprint.as_deref() == Some(fingerprint.as_str()) {
+        config.version
+    } else {
+        let bumped = sqlx::query_scalar!(
+            "UPDATE guild_rules SET
+                version = CASE WHEN fingerprint IS NULL THEN version ELSE version + 1 END,
+                fingerprint = $2
+            WHERE guild_id = $1 AND fingerprint IS DISTINCT FROM $2
+            RETURNING version",
+            guild_id,
+            fingerprint,
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        match bumped {
+            Some(version) => version,
+            // Another caller took the same change first.
+            None => {
+                sqlx::query_scalar!(
+                    "SELECT version FROM guild_rules WHERE guild_id = $1",
+                    guild_id,
+                )
+                .fetch_one(pool)
+                .await?
+            },
+        }
+    };
+
+    Ok(Some(RulesSnapshot {
+        version,
+        title: config.title,
+        description: config.description,
+        colour: config.colour,
+        rules,
+    }))
+}
+
+pub async fn accepted_version(
+    pool: &PgPool,
+    guild_id: i64,
+    user_id: i64,
+) -> sqlx::Result<Option<i32>> {
+    sqlx::query_scalar!(
+        "SELECT version FROM rules_acceptances WHERE guild_id = $1 AND user_id = $2",
+        guild_id,
+        user_id,
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+pub async fn record_acceptance(
+    pool: &PgPool,
+    guild_id: i64,
+    user_id: i64,
+    version: i32,
+) -> sqlx::Result<()> {
+    sqlx::query!(
+        "INSERT INTO rules_acceptances (guild_id, user_id, version)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (guild_id, user_id) DO UPDATE SET
+            version = EXCLUDED.version,
+            accepted_at = now(),
+            reprompted_version = NULL",
+        guild_id,
+        user_id,
+        version,
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// The guild's quiz, in the order it is asked.
+pub async fn quiz(pool: &PgPool, guild_id: i64) -> sqlx::Result<Vec<QuizQuestion>> {
+    sqlx::query_as!(
+        QuizQuestion,
+        "SELECT id, question, answers FROM verify_quiz_questions
+        WHERE guild_id = $1 ORDER BY position, id",
+        guild_id,
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Guilds that take the verified role back after a material rules change.
+pub async fn reverify_guilds(pool: &PgPool) -> sqlx::Result<Vec<i64>> {
+    sqlx::query_scalar!(
+        "SELECT guild_id FROM roles_settings
+        WHERE require_rules AND reverify_on_rules_change",
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Members of re-verifying guilds who accepted an older version of the rules
+/// and haven't yet been re-prompted for the current one.
+pub async fn stale_acceptances(pool: &PgPool) -> sqlx::Result<Vec<StaleAcceptance>> {
+    sqlx::query_as!(
+        StaleAcceptance,
+        "SELECT a.guild_id, a.user_id, r.version, s.verified_role_id
+        FROM rules_acceptances a
+        JOIN guild_rules r ON r.guild_id = a.guild_id
+        JOIN roles_settings s ON s.guild_id = a.guild_id
+        WHERE s.require_rules AND s.reverify_on_rules_change
+            AND a.version < r.version
+            AND (a.reprompted_version IS NULL OR a.reprompted_version < r.version)",
+    )
+    .fetch_all(pool)
+    .await
+}
+
+pub async fn mark_reprompted(
+    pool: &PgPool,
+    stale: StaleAcceptance,
+) -> sqlx::Result<()> {
+    sqlx::query!(
+        "UPDATE rules_acceptances SET reprompted_version = $3
+        WHERE guild_id = $1 AND user_id = $2",
+        stale.guild_id,
+        stale.user_id,
+        stale.version,
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,98 @@
+//! Known scam and phishing domains, for automod's scam-link rule. The list is
+//! a local file, one domain per line with `#` comments, so it can be updated
+//! without a release; [`ScamDomains::reload`] picks edits up.
+
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::RwLock;
+
+use tracing::warn;
+
+#[derive(Debug, Default)]
+pub struct ScamDomains {
+    path: PathBuf,
+    domains: RwLock<HashSet<String>>,
+}
+
+impl ScamDomains {
+    /// Reads the list at `path`. A missing or unreadable file is an empty
+    /// list, so the scam-link rule matches nothing until it appears.
+    #[must_use]
+    pub fn load(path: PathBuf) -> Self {
+        let list = Self { path, domains: RwLock::default() };
+
+        if let Err(e) = list.reload() {
+            warn!(
+                path = %list.path.display(),
+                error = %e,
+                "scam domain list not loaded; the scam-link rule matches nothing",
+            );
+        }
+
+        list
+    }
+
+    /// A fixed list, for tests.
+    #[must_use]
+    pub fn from_content(content: &str) -> Self {
+        Self { path: PathBuf::new(), domains: RwLock::new(Self::parse(content)) }
+    }
+
+    /// Re-reads the file, keeping the old list if it can't be read. Returns
+    /// how many domains are loaded.
+    pub fn reload(&self) -> std::io::Result<usize> {
+        let domains = Self::parse(&std::fs::read_to_string(&self.path)?);
+        let count = domains.len();
+
+        if let Ok(mut current) = self.domains.write() {
+            *current = domains;
+        }
+
+        Ok(count)
+    }
+
+    #[must_use]
+    pub fn parse(content: &str) -> HashSet<String> {
+        content
+            .lines()
+            .map(|line| line.split('#').next().unwrap_or_default().trim())
+            .filter(|line| !line.is_empty())
+            .map(|line| {
+                line.trim_start_matches("*.").trim_end_matches('.').to_lowercase()
+            })
+            .collect()
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.domains.read().map_or(0, |domains| domains.len())
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Whether `host` or any domain it is under is on the list, so listing
+    /// `scam.example` also catches `login.scam.example`.
+    #[must_use]
+    pub fn contains(&self, host: &str) -> bool {
+        let Ok(domains) = self.domains.read() else {
+            return false;
+        };
+
+        let host = host.trim_end_matches('.').to_lowercase();
+        let mut rest = host.as_str();
+
+        loop {
+            if domains.contains(rest) {
+                return true;
+            }
+
+            match rest.split_once('.') {
+                Some((_, parent)) if parent.contains('.') => rest = parent,
+                _ => return false,
+            }
+        }
+    }
+}
@@ -0,0 +1,188 @@
+//! The shared honeypot threat list. Every honeypot hit is recorded; hits in
+//! guilds that had opted in to sharing at the time count against the offender
+//! in every other sharing guild, which can flag or ban them as they join. A
+//! guild's whitelist overrides the list there. Hits are kept for
+//! [`SHARED_HIT_DAYS`].
+
+use md5::{Digest, Md5};
+use sqlx::PgPool;
+
+use crate::config::SharedAction;
+
+/// How long a hit counts against a user on the shared list.
+pub const SHARED_HIT_DAYS: i32 = 90;
+
+/// A hash of the bait message, so identical spam can be matched across guilds
+/// without storing what it said. Case and spacing are ignored.
+#[must_use]
+pub fn message_hash(content: &str) -> String {
+    let normalized = content
+        .split_whitespace()
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    hex::encode(Md5::digest(normalized))
+}
+
+/// `message_hash` is `None` for traps that aren't set off by a message. The
+/// hit is shared if the guild shares its hits now.
+pub async fn record_hit(
+    pool: &PgPool,
+    guild_id: i64,
+    user_id: i64,
+    message_hash: Option<&str>,
+) -> sqlx::Result<()> {
+    sqlx::query!(
+        "INSERT INTO honeypot_hits (guild_id, user_id, message_hash, shared)
+        VALUES ($1, $2, $3, COALESCE(
+            (SELECT share_hits FROM honeypot_settings WHERE guild_id = $1),
+            false
+        ))",
+        guild_id,
+        user_id,
+        message_hash,
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// How many sharing guilds other than `guild_id` the user has tripped a
+/// honeypot in recently.
+pub async fn shared_guild_count(
+    pool: &PgPool,
+    guild_id: i64,
+    user_id: i64,
+) -> sqlx::Result<i64> {
+    sqlx::query_scalar!(
+        r#"SELECT COUNT(DISTINCT h.guild_id) AS "guilds!"
+        FROM honeypot_hits h
+        WHERE h.user_id = $2
+            AND h.guild_id <> $1
+            AND h.shared
+            AND h.created_at > now() - make_interval(days => $3)"#,
+        guild_id,
+        user_id,
+        SHARED_HIT_DAYS,
+    )
+    .fetch_one(pool)
+    .await
+}
+
+/// Drops hits too old to count any more, returning how many went.
+pub async fn prune_hits(pool: &PgPool) -> sqlx::Result<u64> {
+    let deleted = sqlx::query!(
+        "DELETE FROM honeypot_hits
+        WHERE created_at <= now() - make_interval(days => $1)",
+        SHARED_HIT_DAYS,
+    )
+    .execute(pool)
+    .await?
+    .rows_affected();
+
+    Ok(deleted)
+}
+
+pub async fn is_whitelisted(
+    pool: &PgPool,
+    guild_id: i64,
+    user_id: i64,
+) -> sqlx::Result<bool> {
+    sqlx::query_scalar!(
+        r#"SELECT EXISTS (
+            SELECT 1 FROM honeypot_whitelist WHERE guild_id = $1 AND user_id = $2
+        ) AS "whitelisted!""#,
+        guild_id,
+        user_id,
+    )
+    .fetch_one(pool)
+    .await
+}
+
+/// Clears the user in this guild. Returns whether the shared list had banned
+/// them here, in which case the caller should lift the ban.
+pub async fn whitelist(
+    pool: &PgPool,
+    guild_id: i64,
+    user_id: i64,
+    note: &str,
+    added_by: i64,
+) -> sqlx::Result<bool> {
+    let mut tx = pool.begin().await?;
+
+    sqlx::query!(
+        "INSERT INTO honeypot_whitelist (guild_id, user_id, note, added_by)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (guild_id, user_id) DO UPDATE SET
+            note = EXCLUDED.note,
+            added_by = EXCLUDED.added_by,
+            created_at = now()",
+        guild_id,
+        user_id,
+        note,
+        added_by,
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    let banned = sqlx::query_scalar!(
+        "DELETE FROM honeypot_screenings
+        WHERE guild_id = $1 AND user_id = $2
+        RETURNING action",
+        guild_id,
+        user_id,
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .is_some_and(|action| SharedAction::parse(&action) == SharedAction::Ban);
+
+    tx.commit().await?;
+
+    Ok(banned)
+}
+
+/// Returns whether the user was on the whitelist.
+pub async fn unwhitelist(
+    pool: &PgPool,
+    guild_id: i64,
+    user_id: i64,
+) -> sqlx::Result<bool> {
+    let removed = sqlx::query!(
+        "DELETE FROM honeypot_whitelist WHERE guild_id = $1 AND user_id = $2",
+        guild_id,
+        user_id,
+    )
+    .execute(pool)
+    .await?
+    .rows_affected();
+
+    Ok(removed > 0)
+}
+
+/// Notes what the guild did to a joining member because of the list.
+pub async fn record_screening(
+    pool: &PgPool,
+    guild_id: i64,
+    user_id: i64,
+    action: SharedAction,
+    guilds: i64,
+) -> sqlx::Result<()> {
+    sqlx::query!(
+        "INSERT INTO honeypot_screenings (guild_id, user_id, action, guilds)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (guild_id, user_id) DO UPDATE SET
+            action = EXCLUDED.action,
+            guilds = EXCLUDED.guilds,
+            created_at = now()",
+        guild_id,
+        user_id,
+        action.as_str(),
+        i32::try_from(guilds).unwrap_or(i32::MAX),
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,410 @@
+//! Moderation actions that undo themselves: tempbans, timed roles and timed
+//! slowmode. Each is a row until the sweep reverses it or a moderator cancels
+//! it, so expiries outlast restarts. Finished rows stay as history.
+
+use jiff_sqlx::Timestamp as SqlxTimestamp;
+use sqlx::PgPool;
+
+/// A reversal that keeps failing stops being retried after this many tries
+/// and waits, still pending, for a moderator.
+pub const MAX_FAILURES: i32 = 3;
+
+/// How long a failed reversal waits before it's retried.
+const RETRY_MINUTES: i32 = 5;
+
+/// Most pending actions listed at once.
+const PENDING_LIMIT: i64 = 25;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "timed_action_kind", rename_all = "snake_case")]
+pub enum TimedActionKind {
+    Ban,
+    Role,
+    Slowmode,
+}
+
+/// What a timed action changed, and so what undoing it touches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Target {
+    /// `infraction_id` is the ban's infraction, updated when the ban lifts.
+    Ban {
+        user_id: i64,
+        infraction_id: Option<i32>,
+    },
+    Role {
+        user_id: i64,
+        role_id: i64,
+    },
+    /// `previous` is the slowmode, in seconds, to put back.
+    Slowmode {
+        channel_id: i64,
+        previous: i32,
+    },
+}
+
+impl Target {
+    #[must_use]
+    pub const fn kind(self) -> TimedActionKind {
+        match self {
+            Self::Ban { .. } => TimedActionKind::Ban,
+            Self::Role { .. } => TimedActionKind::Role,
+            Self::Slowmode { .. } => TimedActionKind::Slowmode,
+        }
+    }
+
+    const fn columns(self) -> (Option<i64>, Option<i64>, Option<i64>) {
+        match self {
+            Self::Ban { user_id, .. } => (Some(user_id), None, None),
+            Self::Role { user_id, role_id } => (Some(user_id), Some(role_id), None),
+            Self::Slowmode { channel_id, .. } => (None, None, Some(channel_id)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimedAction {
+    pub id: i32,
+    pub guild_id: i64,
+    pub target: Target,
+    pub moderator_id: i64,
+    pub reason: String,
+    pub expires_at: jiff::Timestamp,
+    /// Failed attempts to undo it so far.
+    pub failures: i32,
+    pub last_error: Option<String>,
+}
+
+impl TimedAction {
+    /// Whether the sweep stopped retrying it, leaving it to a moderator.
+    #[must_use]
+    pub const fn given_up(&self) -> bool {
+        self.failures >= MAX_FAILURES
+    }
+}
+
+struct TimedActionRow {
+    id: i32,
+    guild_id: i64,
+    kind: TimedActionKind,
+    user_id: Option<i64>,
+    role_id: Option<i64>,
+    channel_id: Option<i64>,
+    previous_slowmode: Option<i32>,
+    infraction_id: Option<i32>,
+    moderator_id: i64,
+    reason: String,
+    expires_at: SqlxTimestamp,
+    failures: i32,
+    last_error: Option<String>,
+}
+
+impl TimedActionRow {
+    /// `None` only for a row the table's check constraint would reject.
+    fn into_action(self) -> Option<TimedAction> {
+        let target = match self.kind {
+            TimedActionKind::Ban => Target::Ban {
+                user_id: self.user_id?,
+                infraction_id: self.infraction_id,
+            },
+            TimedActionKind::Role => {
+                Target::Role { user_id: self.user_id?, role_id: self.role_id? }
+            },
+            TimedActionKind::Slowmode => Target::Slowmode {
+                channel_id: self.channel_id?,
+                previous: self.previous_slowmode?,
+            },
+        };
+
+        Some(TimedAction {
+            id: self.id,
+            guild_id: self.guild_id,
+            target,
+            moderator_id: self.moderator_id,
+            reason: self.reason,
+            expires_at: self.expires_at.to_jiff(),
+            failures: self.failures,
+            last_error: self.last_error,
+        })
+    }
+}
+
+fn into_actions(rows: Vec<TimedActionRow>) -> Vec<TimedAction> {
+    rows.into_iter().filter_map(TimedActionRow::into_action).collect()
+}
+
+/// Schedules `target` to be undone at `expires_at` and returns the action's
+/// id. A pending action on the same target is replaced, keeping its
+/// slowmode so the original is what gets put back.
+pub async fn schedule(
+    pool: &PgPool,
+    guild_id: i64,
+    target: Target,
+    moderator_id: i64,
+    reason: &str,
+    expires_at: jiff::Timestamp,
+) -> sqlx::Result<i32> {
+    let (user_id, role_id, channel_id) = target.columns();
+    let (previous_slowmode, infraction_id) = match target {
+        Target::Ban { infraction_id, .. } => (None, infraction_id),
+        Target::Role { .. } => (None, None),
+        Target::Slowmode { previous, .. } => (Some(previous), None),
+    };
+    let expires_at = SqlxTimestamp::from(expires_at);
+
+    let mut tx = pool.begin().await?;
+
+    sqlx::query!(
+        "INSERT INTO guilds (id) VALUES ($1) ON CONFLICT (id) DO NOTHING",
+        guild_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    let replaced = sqlx::query_scalar!(
+        "UPDATE timed_actions SET cancelled_at = now(), cancelled_by = $6
+        WHERE guild_id = $1
+            AND kind = $2
+            AND user_id IS NOT DISTINCT FROM $3
+            AND role_id IS NOT DISTINCT FROM $4
+            AND channel_id IS NOT DISTINCT FROM $5
+            AND completed_at IS NULL
+            AND cancelled_at IS NULL
+        RETURNING previous_slowmode",
+        guild_id,
+        target.kind() as _,
+        user_id,
+        role_id,
+        channel_id,
+        moderator_id,
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+
+    let previous_slowmode =
+        replaced.into_iter().flatten().next().or(previous_slowmode);
+
+    let id = sqlx::query_scalar!(
+        "INSERT INTO timed_actions
+            (guild_id, kind, user_id, role_id, channel_id, previous_slowmode,
+             infraction_id, moderator_id, reason, expires_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+        RETURNING id",
+        guild_id,
+        target.kind() as _,
+        user_id,
+        role_id,
+        channel_id,
+        previous_slowmode,
+        infraction_id,
+        moderator_id,
+        reason,
+        expires_at as SqlxTimestamp,
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    if let Some(infraction_id) = infraction_id {
+        sqlx::query!(
+            "UPDATE infractions SET expires_at = $2 WHERE id = $1",
+            infraction_id,
+            expires_at as SqlxTimestamp,
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    tx.commit().await?;
+
+    Ok(id)
+}
+
+/// The guild's pending actions, soonest first.
+pub async fn pending(
+    pool: &PgPool,
+    guild_id: i64,
+) -> sqlx::Result<Vec<TimedAction>> {
+    let rows = sqlx::query_as!(
+        TimedActionRow,
+        r#"SELECT id, guild_id, kind AS "kind: TimedActionKind", user_id, role_id,
+            channel_id, previous_slowmode, infraction_id, moderator_id, reason,
+            expires_at AS "expires_at: SqlxTimestamp", failures, last_error
+        FROM timed_actions
+        WHERE guild_id = $1 AND completed_at IS NULL AND cancelled_at IS NULL
+        ORDER BY expires_at
+        LIMIT $2"#,
+        guild_id,
+        PENDING_LIMIT,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(into_actions(rows))
+}
+
+pub async fn get_pending(
+    pool: &PgPool,
+    guild_id: i64,
+    id: i32,
+) -> sqlx::Result<Option<TimedAction>> {
+    let row = sqlx::query_as!(
+        TimedActionRow,
+        r#"SELECT id, guild_id, kind AS "kind: TimedActionKind", user_id, role_id,
+            channel_id, previous_slowmode, infraction_id, moderator_id, reason,
+            expires_at AS "expires_at: SqlxTimestamp", failures, last_error
+        FROM timed_actions
+        WHERE guild_id = $1
+            AND id = $2
+            AND completed_at IS NULL
+            AND cancelled_at IS NULL"#,
+        guild_id,
+        id,
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(row.and_then(TimedActionRow::into_action))
+}
+
+/// Pending actions whose time is up, oldest first. One that failed waits for
+/// its retry, and one that failed [`MAX_FAILURES`] times is left out.
+pub async fn due(pool: &PgPool) -> sqlx::Result<Vec<TimedAction>> {
+    let rows = sqlx::query_as!(
+        TimedActionRow,
+        r#"SELECT id, guild_id, kind AS "kind: TimedActionKind", user_id, role_id,
+            channel_id, previous_slowmode, infraction_id, moderator_id, reason,
+            expires_at AS "expires_at: SqlxTimestamp", failures, last_error
+        FROM timed_actions
+        WHERE COALESCE(retry_at, expires_at) <= now()
+            AND failures < $1
+            AND completed_at IS NULL
+            AND cancelled_at IS NULL
+        ORDER BY expires_at
+        LIMIT 100"#,
+        MAX_FAILURES,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(into_actions(rows))
+}
+
+/// Drops a pending action without undoing it, so a tempban becomes a ban.
+/// `false` if there was no such pending action.
+pub async fn cancel(
+    pool: &PgPool,
+    guild_id: i64,
+    id: i32,
+    cancelled_by: i64,
+) -> sqlx::Result<bool> {
+    let mut tx = pool.begin().await?;
+
+    let cancelled = sqlx::query_scalar!(
+        "UPDATE timed_actions SET cancelled_at = now(), cancelled_by = $3
+        WHERE guild_id = $1
+            AND id = $2
+            AND completed_at IS NULL
+            AND cancelled_at IS NULL
+        RETURNING infraction_id",
+        guild_id,
+        id,
+        cancelled_by,
+    )
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    let Some(infraction_id) = cancelled else {
+        return Ok(false);
+    };
+
+    if let Some(infraction_id) = infraction_id {
+        sqlx::query!(
+            "UPDATE infractions SET expires_at = NULL WHERE id = $1",
+            infraction_id
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    tx.commit().await?;
+
+    Ok(true)
+}
+
+/// Drops the pending slowmode reset on a channel, when its slowmode is set
+/// for good. `false` if it had none.
+pub async fn cancel_slowmode(
+    pool: &PgPool,
+    guild_id: i64,
+    channel_id: i64,
+    cancelled_by: i64,
+) -> sqlx::Result<bool> {
+    let cancelled = sqlx::query!(
+        "UPDATE timed_actions SET cancelled_at = now(), cancelled_by = $3
+        WHERE guild_id = $1
+            AND kind = 'slowmode'
+            AND channel_id = $2
+            AND completed_at IS NULL
+            AND cancelled_at IS NULL",
+        guild_id,
+        channel_id,
+        cancelled_by,
+    )
+    .execute(pool)
+    .await?
+    .rows_affected();
+
+    Ok(cancelled > 0)
+}
+
+/// Marks an action undone, with `note` as why. A tempban's infraction is
+/// updated with when it was lifted and the same note.
+pub async fn complete(
+    pool: &PgPool,
+    action: &TimedAction,
+    note: &str,
+) -> sqlx::Result<()> {
+    let mut tx = pool.begin().await?;
+
+    sqlx::query!(
+        "UPDATE timed_actions SET
+            completed_at = now(), lift_reason = $2, last_error = NULL
+        WHERE id = $1",
+        action.id,
+        note,
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    if let Target::Ban { infraction_id: Some(infraction_id), .. } = action.target {
+        sqlx::query!(
+            "UPDATE infractions SET lifted_at = now(), lift_reason = $2
+            WHERE id = $1",
+            infraction_id,
+            note,
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    tx.commit().await
+}
+
+/// Records a failed reversal and schedules a retry. Returns `true` once the
+/// action has failed [`MAX_FAILURES`] times; it then stays pending, with the
+/// error, until a moderator undoes or cancels it.
+pub async fn fail(pool: &PgPool, id: i32, error: &str) -> sqlx::Result<bool> {
+    sqlx::query_scalar!(
+        r#"UPDATE timed_actions SET
+            failures = failures + 1,
+            last_error = $2,
+            retry_at = now() + make_interval(mins => $3)
+        WHERE id = $1
+        RETURNING failures >= $4 AS "given_up!""#,
+        id,
+        error,
+        RETRY_MINUTES,
+        MAX_FAILURES,
+    )
+    .fetch_one(pool)
+    .await
+}
@@ -9,6 +9,7 @@ use crate::config::{BotConfig, RadioStation, SettingsRegistry};
 use crate::entitlement::{EntitlementService, Tier};
 use crate::events::AppEvent;
 use crate::services::http::ClientBuilderExt;
+use crate::services::scam_domains::ScamDomains;
 
 fn http_client() -> reqwest::Client {
     reqwest::Client::builder().with_timeouts().build().unwrap_or_else(|e| {
@@ -43,6 +44,7 @@ pub struct AppState {
 
     pub sku_tiers: HashMap<u64, Tier>,
     pub radio_stations: Arc<[RadioStation]>,
+    pub scam_domains: Arc<ScamDomains>,
 }
 
 impl AppState {
@@ -89,6 +91,9 @@ impl AppState {
             upgrade_url: config.upgrade_url.clone(),
             sku_tiers,
             radio_stations: Arc::clone(&config.radio_stations),
+            scam_domains: Arc::new(ScamDomains::load(
+                config.scam_domains_path.clone(),
+            )),
         }
     }
 
@@ -0,0 +1,76 @@
+//! Coverage for the AI quota rules: tier budgets, owner overrides and which
+//! limit a reply runs into.
+
+use zayden_app::entitlement::Tier;
+use zayden_app::services::ai_usage::{AiQuota, QuotaLimit, QuotaOverride, Spent};
+
+fn limit(daily: Option<i64>, monthly: Option<i64>) -> QuotaOverride {
+    QuotaOverride {
+        guild_id: 1,
+        daily_tokens: daily,
+        monthly_tokens: monthly,
+        set_by: 2,
+        updated_at: jiff::Timestamp::UNIX_EPOCH,
+    }
+}
+
+#[test]
+fn higher_tiers_get_bigger_budgets() {
+    let free = AiQuota::for_tier(Tier::Free);
+    let pro = AiQuota::for_tier(Tier::Pro);
+    let ultra = AiQuota::for_tier(Tier::Ultra);
+
+    for (lower, higher) in [(free, pro), (pro, ultra)] {
+        assert!(lower.guild_daily < higher.guild_daily);
+        assert!(lower.guild_monthly < higher.guild_monthly);
+        assert!(lower.user_daily < higher.user_daily);
+    }
+}
+
+#[test]
+fn an_override_raises_the_guild_limits_it_sets() {
+    let free = AiQuota::for_tier(Tier::Free);
+
+    let raised = free.raised_by(Some(&limit(Some(5_000_000), None)));
+
+    assert_eq!(raised.guild_daily, 5_000_000);
+    assert_eq!(raised.guild_monthly, free.guild_monthly, "unset keeps the tier's");
+    assert_eq!(raised.user_daily, free.user_daily, "members are not raised");
+}
+
+#[test]
+fn an_override_never_lowers_a_tier() {
+    let pro = AiQuota::for_tier(Tier::Pro);
+
+    assert_eq!(pro.raised_by(Some(&limit(Some(1), Some(1)))), pro);
+    assert_eq!(pro.raised_by(None), pro);
+}
+
+#[test]
+fn nothing_spent_is_within_every_quota() {
+    assert_eq!(Spent::default().exceeded(AiQuota::for_tier(Tier::Free)), None);
+}
+
+#[test]
+fn reaching_a_limit_exactly_counts() {
+    let quota = AiQuota::for_tier(Tier::Free);
+    let spent = Spent { user_today: quota.user_daily, ..Spent::default() };
+
+    assert_eq!(spent.exceeded(quota), Some(QuotaLimit::UserDaily));
+}
+
+#[test]
+fn the_longest_wait_is_reported_first() {
+    let quota = AiQuota::for_tier(Tier::Free);
+    let spent = Spent {
+        guild_today: quota.guild_daily,
+        guild_month: quota.guild_monthly,
+        user_today: quota.user_daily,
+    };
+
+    assert_eq!(spent.exceeded(quota), Some(QuotaLimit::GuildMonthly));
+    assert_eq!(
+        Spent { guild_month: 0, ..spent }.exceeded(quota),
+        Some(QuotaLimit::GuildDaily)
+    );
+}
@@ -0,0 +1,177 @@
+//! Coverage for the appeal records: which punishment a member can appeal,
+//! that each can be appealed once, and what a decision does to it.
+//!
+//! These are `#[sqlx::test]`, so each test gets its own migrated database and
+//! `DATABASE_URL` must point at a server the runner may create databases on.
+//! Infractions are inserted with unchecked queries, the way the bot records
+//! them, so the `.sqlx` cache is left as the release build expects it.
+
+use jiff::{SignedDuration, Timestamp};
+use sqlx::PgPool;
+use zayden_app::services::appeals::{self, AppealStatus, Punishment};
+use zayden_app::services::timed_actions::{self, Target};
+
+const GUILD: i64 = 1_234_567_890;
+const MEMBER: i64 = 2_345_678_901;
+const MODERATOR: i64 = 3_456_789_012;
+
+async fn seed(pool: &PgPool) {
+    sqlx::query("INSERT INTO guilds (id) VALUES ($1)")
+        .bind(GUILD)
+        .execute(pool)
+        .await
+        .expect("guild row");
+
+    for (id, name) in [(MEMBER, "member"), (MODERATOR, "moderator")] {
+        sqlx::query("INSERT INTO users (id, username) VALUES ($1, $2)")
+            .bind(id)
+            .bind(name)
+            .execute(pool)
+            .await
+            .expect("user row");
+    }
+}
+
+/// Records an infraction on the member, newer than any before it. A mute
+/// with `timeout` runs out that many minutes from now.
+async fn infraction(pool: &PgPool, kind: &str, timeout: Option<i32>) -> i32 {
+    sqlx::query_scalar(
+        "INSERT INTO infractions
+            (user_id, username, guild_id, infraction_type, moderator_id,
+             moderator_username, points, reason, created_at, expires_at)
+        VALUES ($1, 'member', $2, $3::infraction_kind, $4, 'moderator', 1,
+            'testing', clock_timestamp(), now() + make_interval(mins => $5))
+        RETURNING id",
+    )
+    .bind(MEMBER)
+    .bind(GUILD)
+    .bind(kind)
+    .bind(MODERATOR)
+    .bind(timeout)
+    .fetch_one(pool)
+    .await
+    .expect("infraction row")
+}
+
+async fn appealable_id(pool: &PgPool) -> Option<i32> {
+    appeals::appealable(pool, GUILD, MEMBER)
+        .await
+        .expect("appealable")
+        .map(|appealable| appealable.infraction_id)
+}
+
+async fn open_appeal(pool: &PgPool, infraction_id: i32) -> appeals::Appeal {
+    let id = appeals::open(pool, GUILD, MEMBER, infraction_id, "please")
+        .await
+        .expect("appeal opened")
+        .expect("not appealed before");
+
+    appeals::get(pool, GUILD, id).await.expect("appeal").expect("appeal row")
+}
+
+#[sqlx::test(migrations = "../migrations")]
+async fn the_latest_mute_or_ban_is_appealable(pool: PgPool) {
+    seed(&pool).await;
+    infraction(&pool, "Mute", None).await;
+    let ban = infraction(&pool, "Ban", None).await;
+    infraction(&pool, "Warn", None).await;
+
+    let appealable = appeals::appealable(&pool, GUILD, MEMBER).await.unwrap();
+
+    let appealable = appealable.expect("the ban");
+    assert_eq!(appealable.infraction_id, ban);
+    assert_eq!(appealable.punishment, Punishment::Ban);
+    assert!(!appealable.appealed);
+}
+
+#[sqlx::test(migrations = "../migrations")]
+async fn a_mute_that_ran_out_is_not_appealable(pool: PgPool) {
+    seed(&pool).await;
+    infraction(&pool, "Mute", Some(-1)).await;
+
+    assert_eq!(appealable_id(&pool).await, None);
+
+    let running = infraction(&pool, "Mute", Some(60)).await;
+    assert_eq!(appealable_id(&pool).await, Some(running));
+}
+
+#[sqlx::test(migrations = "../migrations")]
+async fn nothing_is_appealable_without_a_mute_or_ban(pool: PgPool) {
+    seed(&pool).await;
+    infraction(&pool, "Warn", None).await;
+    infraction(&pool, "Kick", None).await;
+
+    assert_eq!(appealable_id(&pool).await, None);
+}
+
+#[sqlx::test(migrations = "../migrations")]
+async fn an_infraction_is_appealed_once(pool: PgPool) {
+    seed(&pool).await;
+    let ban = infraction(&pool, "Ban", None).await;
+
+    let appeal = open_appeal(&pool, ban).await;
+    assert_eq!(appeal.status, AppealStatus::Pending);
+    assert_eq!(appeal.punishment, Punishment::Ban);
+
+    let again = appeals::open(&pool, GUILD, MEMBER, ban, "please").await.unwrap();
+    assert_eq!(again, None);
+
+    let appealable = appeals::appealable(&pool, GUILD, MEMBER).await.unwrap();
+    assert!(appealable.expect("still in force").appealed);
+}
+
+#[sqlx::test(migrations = "../migrations")]
+async fn approving_lifts_the_infraction_and_its_timed_action(pool: PgPool) {
+    seed(&pool).await;
+    let ban = infraction(&pool, "Ban", None).await;
+    let expires_at = Timestamp::now()
+        .checked_add(SignedDuration::from_hours(1))
+        .expect("timestamp in range");
+    let timed = timed_actions::schedule(
+        &pool,
+        GUILD,
+        Target::Ban { user_id: MEMBER, infraction_id: Some(ban) },
+        MODERATOR,
+        "testing",
+        expires_at,
+    )
+    .await
+    .unwrap();
+
+    let appeal = open_appeal(&pool, ban).await;
+    assert!(appeals::decide(&pool, &appeal, true, MODERATOR, "ok").await.unwrap());
+
+    assert_eq!(appealable_id(&pool).await, None);
+    assert!(
+        timed_actions::get_pending(&pool, GUILD, timed).await.unwrap().is_none()
+    );
+
+    let decided = appeals::get(&pool, GUILD, appeal.id).await.unwrap();
+    assert_eq!(decided.map(|appeal| appeal.status), Some(AppealStatus::Approved));
+}
+
+#[sqlx::test(migrations = "../migrations")]
+async fn denying_keeps_the_infraction(pool: PgPool) {
+    seed(&pool).await;
+    let ban = infraction(&pool, "Ban", None).await;
+
+    let appeal = open_appeal(&pool, ban).await;
+    assert!(appeals::decide(&pool, &appeal, false, MODERATOR, "no").await.unwrap());
+
+    assert_eq!(appealable_id(&pool).await, Some(ban));
+}
+
+#[sqlx::test(migrations = "../migrations")]
+async fn an_appeal_is_decided_once(pool: PgPool) {
+    seed(&pool).await;
+    let ban = infraction(&pool, "Ban", None).await;
+
+    let appeal = open_appeal(&pool, ban).await;
+    assert!(appeals::decide(&pool, &appeal, false, MODERATOR, "no").await.unwrap());
+    assert!(
+        !appeals::decide(&pool, &appeal, true, MODERATOR, "ok").await.unwrap(),
+        "already denied"
+    );
+
+    assert_eq!(appealable_id(&pool).await, Some(ban));
+}
@@ -0,0 +1,62 @@
+//! Coverage for the settings history's field diff and the snapshot round trip
+//! that rollback relies on.
+
+use serde_json::json;
+use zayden_app::config::audit::diff;
+use zayden_app::config::{ChangeSource, MusicSettingsRow, SettingsRow};
+
+#[test]
+fn diff_reports_only_changed_fields() {
+    let before = json!({ "guild_id": 1, "autoplay": false, "default_volume": 100 });
+    let after = json!({ "guild_id": 1, "autoplay": true, "default_volume": 100 });
+
+    let fields = diff(Some(&before), &after);
+
+    assert_eq!(fields.len(), 1);
+    assert_eq!(fields[0].field, "autoplay");
+    assert_eq!(fields[0].before, json!(false));
+    assert_eq!(fields[0].after, json!(true));
+}
+
+#[test]
+fn diff_of_a_new_row_treats_every_field_as_unset() {
+    let after = json!({ "guild_id": 1, "channel_id": 42, "enabled": true });
+
+    let fields = diff(None, &after);
+
+    let names = fields.iter().map(|f| f.field.as_str()).collect::<Vec<_>>();
+    assert_eq!(names, ["channel_id", "enabled"], "guild_id is never reported");
+    assert!(fields.iter().all(|f| f.before.is_null()));
+}
+
+#[test]
+fn diff_of_identical_rows_is_empty() {
+    let row = json!({ "guild_id": 1, "enabled": true });
+
+    assert!(diff(Some(&row), &row).is_empty());
+}
+
+#[test]
+fn snapshots_restore_the_same_row() {
+    let mut row = MusicSettingsRow::empty(7);
+    row.dj_role_id = Some(99);
+    row.autoplay = true;
+
+    let snapshot = serde_json::to_value(&row).unwrap();
+    let restored: MusicSettingsRow = serde_json::from_value(snapshot).unwrap();
+
+    assert_eq!(restored.guild_id, 7);
+    assert_eq!(restored.dj_role_id, Some(99));
+    assert!(restored.autoplay);
+    assert_eq!(restored.default_volume, row.default_volume);
+}
+
+#[test]
+fn change_sources_round_trip() {
+    for source in
+        [ChangeSource::Dashboard, ChangeSource::Command, ChangeSource::Rollback]
+    {
+        assert_eq!(ChangeSource::from_key(source.as_str()), Some(source));
+    }
+    assert_eq!(ChangeSource::from_key("cron"), None);
+}
@@ -10,9 +10,18 @@
 
 use zayden_app::config::SettingsRow;
 use zayden_app::config::tables::{
+    AiPersonas,
     AiSettingsRow,
+    AiTrigger,
+    AppealsSettingsRow,
+    CaptchaFailAction,
+    CustomPersona,
     FamilySettingsRow,
+    HoneypotSettingsRow,
     MusicSettingsRow,
+    PersonaError,
+    RolesSettingsRow,
+    SharedAction,
 };
 
 #[test]
@@ -114,14 +123,18 @@ fn ai_settings_empty_is_off_everywhere() {
 
 #[test]
 fn ai_disabled_ignores_its_configured_channel() {
-    let row = AiSettingsRow { guild_id: 123, enabled: false, channel_id: Some(456) };
+    let row = AiSettingsRow {
+        enabled: false,
+        channel_id: Some(456),
+        ..AiSettingsRow::empty(123)
+    };
 
     assert!(!row.responds_in(456));
 }
 
 #[test]
 fn ai_enabled_without_a_channel_answers_anywhere() {
-    let row = AiSettingsRow { guild_id: 123, enabled: true, channel_id: None };
+    let row = AiSettingsRow { enabled: true, ..AiSettingsRow::empty(123) };
 
     assert!(row.responds_in(456));
     assert!(row.responds_in(789));
@@ -129,8 +142,233 @@ fn ai_enabled_without_a_channel_answers_anywhere() {
 
 #[test]
 fn ai_enabled_with_a_channel_answers_only_there() {
-    let row = AiSettingsRow { guild_id: 123, enabled: true, channel_id: Some(456) };
+    let row = AiSettingsRow {
+        enabled: true,
+        channel_id: Some(456),
+        ..AiSettingsRow::empty(123)
+    };
 
     assert!(row.responds_in(456));
     assert!(!row.responds_in(789));
 }
+
+#[test]
+fn ai_settings_empty_only_answers_mentions_as_itself() {
+    let row = AiSettingsRow::empty(123);
+
+    // Must match the column DEFAULTs, 'mention' and '{}'.
+    assert_eq!(row.trigger(), AiTrigger::Mention);
+    assert!(row.keywords.is_empty());
+    assert_eq!(row.personas.for_channel(456), None);
+}
+
+#[test]
+fn ai_snapshots_from_before_trigger_modes_still_restore() {
+    let snapshot = serde_json::json!({
+        "guild_id": 123,
+        "enabled": true,
+        "channel_id": 456,
+    });
+
+    let row: AiSettingsRow =
+        serde_json::from_value(snapshot).expect("old snapshot deserialises");
+
+    assert!(row.responds_in(456));
+    assert_eq!(row.trigger(), AiTrigger::Mention);
+    assert!(row.keywords.is_empty());
+    assert_eq!(row.personas.for_channel(456), None);
+}
+
+#[test]
+fn unknown_ai_trigger_reads_as_mention() {
+    assert_eq!(AiTrigger::parse(" keyword "), AiTrigger::Keyword);
+    assert_eq!(AiTrigger::parse("everything"), AiTrigger::Mention);
+}
+
+#[test]
+fn ai_keywords_match_whole_words_ignoring_case() {
+    let row = AiSettingsRow {
+        keywords: AiSettingsRow::parse_keywords("Zayden, hey bot,\nzayden, ,"),
+        ..AiSettingsRow::empty(123)
+    };
+
+    assert_eq!(row.keywords, ["zayden", "hey bot"]);
+    assert!(row.matches_keyword("ZAYDEN, help!"));
+    assert!(row.matches_keyword("well hey... bot?"));
+    assert!(!row.matches_keyword("zaydens"));
+    assert!(!row.matches_keyword("they bot"));
+}
+
+fn persona(name: &str) -> CustomPersona {
+    CustomPersona::from_form(name, "A pirate.", "", "", "").expect("valid persona")
+}
+
+#[test]
+fn a_persona_form_falls_back_and_clamps() {
+    let pirate =
+        CustomPersona::from_form("  Captain   Hook ", " Arr. ", "", "x", "9")
+            .expect("valid persona");
+
+    assert_eq!(pirate.name, "Captain Hook");
+    assert_eq!(pirate.prompt, "Arr.");
+    assert_eq!(pirate.word_limit, CustomPersona::DEFAULT_WORD_LIMIT);
+    assert!(
+        (pirate.temperature - CustomPersona::MAX_TEMPERATURE).abs() < f32::EPSILON
+    );
+}
+
+#[test]
+fn a_persona_form_rejects_what_it_cannot_fix() {
+    let form = |name: &str, prompt: &str| {
+        CustomPersona::from_form(name, prompt, "", "", "").map(|_| ())
+    };
+
+    assert_eq!(form(" ", "A pirate."), Err(PersonaError::MissingName));
+    assert_eq!(form("<@123>", "A pirate."), Err(PersonaError::InvalidName));
+    assert_eq!(form("Hook", "  "), Err(PersonaError::MissingPrompt));
+    assert_eq!(
+        form("Hook", &"a".repeat(CustomPersona::MAX_PROMPT_CHARS + 1)),
+        Err(PersonaError::TooLong("prompt", CustomPersona::MAX_PROMPT_CHARS))
+    );
+}
+
+#[test]
+fn a_channel_pick_overrides_the_default_persona() {
+    let mut personas = AiPersonas::default();
+    personas.save(persona("Hook")).expect("room for a persona");
+    personas.save(persona("Smee")).expect("room for a persona");
+    personas.default = Some(String::from("hook"));
+    personas.set_channel(1, Some(String::from("Smee")));
+    personas.set_channel(2, None);
+
+    let speaker = |channel| personas.for_channel(channel).map(|p| p.name.as_str());
+    assert_eq!(speaker(1), Some("Smee"));
+    // A channel can keep the bot's own character under a custom default.
+    assert_eq!(speaker(2), None);
+    assert_eq!(speaker(3), Some("Hook"));
+}
+
+#[test]
+fn removing_a_persona_drops_its_picks() {
+    let mut personas = AiPersonas::default();
+    personas.save(persona("Hook")).expect("room for a persona");
+    personas.default = Some(String::from("Hook"));
+    personas.set_channel(1, Some(String::from("Hook")));
+
+    assert!(personas.remove("HOOK"));
+    assert!(!personas.remove("Hook"));
+    assert_eq!(personas.default, None);
+    assert!(personas.channels.is_empty());
+}
+
+#[test]
+fn saving_a_persona_by_name_replaces_it_and_the_list_is_capped() {
+    let mut personas = AiPersonas::default();
+    for i in 0..AiPersonas::MAX_PERSONAS {
+        personas.save(persona(&format!("P{i}"))).expect("room for a persona");
+    }
+
+    assert_eq!(personas.save(persona("p0")), Ok(()));
+    assert_eq!(personas.personas.len(), AiPersonas::MAX_PERSONAS);
+    assert_eq!(personas.save(persona("One too many")), Err(PersonaError::TooMany));
+}
+
+#[test]
+fn appeals_settings_empty_takes_no_appeals() {
+    let row = AppealsSettingsRow::empty(123);
+
+    // Must match the `appeals_settings.enabled` column DEFAULT (FALSE): DMs
+    // only offer an appeal button once a guild has turned appeals on.
+    assert!(!row.enabled);
+    assert!(!row.is_open());
+    assert_eq!(AppealsSettingsRow::TABLE, "appeals_settings");
+}
+
+#[test]
+fn appeals_need_a_channel_to_open() {
+    let row = AppealsSettingsRow { guild_id: 123, enabled: true, channel_id: None };
+    assert!(!row.is_open());
+
+    let row =
+        AppealsSettingsRow { guild_id: 123, enabled: true, channel_id: Some(456) };
+    assert!(row.is_open());
+}
+
+#[test]
+fn roles_settings_empty_matches_the_captcha_column_defaults() {
+    let row = RolesSettingsRow::empty(123);
+
+    // Must match the `roles_settings.captcha_*` column DEFAULTs: off, three
+    // attempts, a 15 minute lockout, no account-age minimum, log only.
+    assert!(!row.captcha_enabled);
+    assert_eq!(row.captcha_max_attempts, 3);
+    assert_eq!(row.captcha_lockout_minutes, 15);
+    assert_eq!(row.captcha_min_account_age_days, 0);
+    assert_eq!(row.captcha_fail_action(), CaptchaFailAction::Log);
+}
+
+#[test]
+fn roles_snapshots_from_before_the_captcha_columns_still_restore() {
+    let snapshot = serde_json::json!({
+        "guild_id": 123,
+        "artist_role_id": null,
+        "sleep_role_id": null,
+        "verified_role_id": 456,
+    });
+
+    let row: RolesSettingsRow =
+        serde_json::from_value(snapshot).expect("old snapshot deserialises");
+
+    assert_eq!(row.verified_role_id, Some(456));
+    assert_eq!(row.captcha_max_attempts, 3);
+    assert_eq!(row.captcha_fail_action(), CaptchaFailAction::Log);
+}
+
+#[test]
+fn unknown_captcha_fail_action_reads_as_log() {
+    assert_eq!(CaptchaFailAction::parse("kick"), CaptchaFailAction::Kick);
+    assert_eq!(CaptchaFailAction::parse(" log "), CaptchaFailAction::Log);
+    assert_eq!(CaptchaFailAction::parse("ban"), CaptchaFailAction::Log);
+    assert_eq!(CaptchaFailAction::parse(""), CaptchaFailAction::Log);
+}
+
+#[test]
+fn honeypot_snapshots_from_before_sharing_restore_as_not_sharing() {
+    let snapshot = serde_json::json!({
+        "guild_id": 123,
+        "channel_id": 555,
+        "exempt_admins": true,
+        "exempt_role_id": null,
+        "purge_seconds": 3600,
+    });
+
+    let row: HoneypotSettingsRow =
+        serde_json::from_value(snapshot).expect("old snapshot deserialises");
+
+    assert_eq!(row.channel_id, Some(555));
+    assert!(!row.share_hits);
+    assert_eq!(row.shared_threshold, 0);
+    assert_eq!(row.shared_action(), SharedAction::Flag);
+    assert_eq!(row.log_channel_id, None);
+    assert!(row.traps.is_empty());
+}
+
+#[test]
+fn honeypot_alert_channel_snapshots_restore_as_the_log_channel() {
+    let snapshot = serde_json::json!({
+        "guild_id": 123,
+        "channel_id": 555,
+        "exempt_admins": false,
+        "exempt_role_id": null,
+        "purge_seconds": 3600,
+        "share_hits": true,
+        "shared_threshold": 2,
+        "shared_action": "ban",
+        "alert_channel_id": 777,
+    });
+
+    let row: HoneypotSettingsRow =
+        serde_json::from_value(snapshot).expect("renamed field deserialises");
+
+    assert_eq!(row.log_channel_id, Some(777));
+}
@@ -0,0 +1,70 @@
+//! Coverage for gift codes and trials: code formatting and normalisation,
+//! redeemability checks, and how long a grant runs.
+
+use jiff::Timestamp;
+use zayden_app::entitlement::{
+    CODE_ALPHABET,
+    CODE_LEN,
+    GiftCode,
+    GiftError,
+    Tier,
+    format_code,
+    grant_expiry,
+    normalize_code,
+};
+
+fn gift(uses: i32, max_uses: i32, expires_at: Option<&str>) -> GiftCode {
+    GiftCode {
+        code: "ABCDE-FGHJK".to_owned(),
+        tier: Tier::Pro,
+        duration_days: 30,
+        max_uses,
+        uses,
+        expires_at: expires_at.map(|at| at.parse().unwrap()),
+        created_by: 1,
+    }
+}
+
+fn now() -> Timestamp {
+    "2026-05-01T00:00:00Z".parse().unwrap()
+}
+
+#[test]
+fn codes_use_the_unambiguous_alphabet() {
+    let code = format_code([0, 1, 31, 32, 255, 7, 8, 9, 10, 11]);
+
+    assert_eq!(code, "AB9A9-HJKLM");
+    assert_eq!(code.len(), CODE_LEN + 1);
+    assert!(code.bytes().filter(|b| *b != b'-').all(|b| CODE_ALPHABET.contains(&b)));
+}
+
+#[test]
+fn typed_codes_normalise() {
+    assert_eq!(normalize_code(" abcde-fghjk "), "ABCDE-FGHJK");
+    assert_eq!(normalize_code("abcdefghjk"), "ABCDE-FGHJK");
+    assert_eq!(normalize_code("ABCDE FGHJK"), "ABCDE-FGHJK");
+    assert_eq!(normalize_code("short"), "SHORT");
+}
+
+#[test]
+fn redeemable_until_used_up_or_expired() {
+    assert!(gift(0, 1, None).check_redeemable(now()).is_ok());
+    assert!(
+        gift(4, 5, Some("2026-05-02T00:00:00Z")).check_redeemable(now()).is_ok()
+    );
+
+    assert!(matches!(
+        gift(1, 1, None).check_redeemable(now()),
+        Err(GiftError::Exhausted)
+    ));
+    assert!(matches!(
+        gift(0, 1, Some("2026-05-01T00:00:00Z")).check_redeemable(now()),
+        Err(GiftError::Expired)
+    ));
+}
+
+#[test]
+fn grants_run_whole_days() {
+    assert_eq!(grant_expiry(now(), 7), "2026-05-08T00:00:00Z".parse().unwrap());
+    assert_eq!(grant_expiry(now(), i64::MAX), Timestamp::MAX);
+}
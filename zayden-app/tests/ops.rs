@@ -0,0 +1,67 @@
+//! Coverage for the admin console's pure helpers: per-module usage totals,
+//! log truncation and net guild growth.
+
+use zayden_app::services::ops::{
+    CommandUsage,
+    GuildStats,
+    MAX_LOG_MESSAGE,
+    truncate_log,
+    usage_by_module,
+};
+
+fn usage(command: &str, module: &str, uses: i64) -> CommandUsage {
+    CommandUsage {
+        command: command.to_string(),
+        module: module.to_string(),
+        uses,
+        failures: 0,
+    }
+}
+
+#[test]
+fn usage_sums_per_module_busiest_first() {
+    let rows = [
+        usage("rank", "levels", 4),
+        usage("play", "music", 7),
+        usage("levels", "levels", 5),
+        usage("ping", "", 1),
+    ];
+
+    assert_eq!(usage_by_module(&rows), [
+        ("levels".to_string(), 9),
+        ("music".to_string(), 7),
+        (String::new(), 1),
+    ]);
+}
+
+#[test]
+fn usage_ties_break_by_name() {
+    let rows = [usage("b", "beta", 2), usage("a", "alpha", 2)];
+
+    let modules = usage_by_module(&rows);
+
+    assert_eq!(modules[0].0, "alpha");
+    assert_eq!(modules[1].0, "beta");
+}
+
+#[test]
+fn short_logs_are_kept_whole() {
+    assert_eq!(truncate_log("boom"), "boom");
+}
+
+#[test]
+fn long_logs_are_cut_on_a_char_boundary() {
+    let message = format!("{}é", "a".repeat(MAX_LOG_MESSAGE - 1));
+
+    let cut = truncate_log(&message);
+
+    assert_eq!(cut.len(), MAX_LOG_MESSAGE - 1, "the split 'é' is dropped");
+    assert!(cut.chars().all(|c| c == 'a'));
+}
+
+#[test]
+fn net_growth_can_be_negative() {
+    let stats = GuildStats { active: 10, joined: 2, left: 5 };
+
+    assert_eq!(stats.net_growth(), -3);
+}
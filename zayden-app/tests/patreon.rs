@@ -0,0 +1,198 @@
+//! Coverage for the Patreon entitlement provider.
+//!
+//! Signature checks run against the RFC 2104 HMAC-MD5 vectors, the grant
+//! mapping against hand-built member documents, and the members API client
+//! against a throwaway local HTTP server that serves two pages of a campaign.
+
+use std::collections::HashMap;
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use zayden_app::entitlement::{
+    EntitlementScope,
+    PatreonClient,
+    PatreonEvent,
+    PatreonMemberDocument,
+    PatreonProvider,
+    Tier,
+};
+
+const BODY: &[u8] = b"what do ya want for nothing?";
+const BODY_MAC: &str = "750c783e6ab0b503eaa86e310a5db738";
+
+fn member(status: &str, tiers: &[&str], next_charge: Option<&str>) -> String {
+    let tiers = tiers
+        .iter()
+        .map(|id| format!(r#"{{"id": "{id}", "type": "tier"}}"#))
+        .collect::<Vec<_>>()
+        .join(",");
+    let next_charge =
+        next_charge.map_or_else(|| "null".to_owned(), |date| format!("\"{date}\""));
+
+    format!(
+        r#"{{
+            "data": {{
+                "id": "member-1",
+                "type": "member",
+                "attributes": {{
+                    "patron_status": {status},
+                    "next_charge_date": {next_charge}
+                }},
+                "relationships": {{
+                    "currently_entitled_tiers": {{ "data": [{tiers}] }},
+                    "user": {{ "data": {{ "id": "user-1", "type": "user" }} }}
+                }}
+            }}
+        }}"#
+    )
+}
+
+fn tiers() -> HashMap<String, Tier> {
+    HashMap::from([("100".to_owned(), Tier::Pro), ("200".to_owned(), Tier::Ultra)])
+}
+
+// ── Webhook signatures ───────────────────────────────────────────────────────
+
+#[test]
+fn signature_matches_rfc_2104_vector() {
+    assert!(PatreonProvider::verify_signature("Jefe", BODY, BODY_MAC));
+    assert!(PatreonProvider::verify_signature(
+        "Jefe",
+        BODY,
+        &BODY_MAC.to_uppercase()
+    ));
+}
+
+#[test]
+fn signature_rejects_tampering() {
+    assert!(!PatreonProvider::verify_signature(
+        "Jefe",
+        b"what do ya want?",
+        BODY_MAC
+    ));
+    assert!(!PatreonProvider::verify_signature("jefe", BODY, BODY_MAC));
+    assert!(!PatreonProvider::verify_signature("Jefe", BODY, ""));
+    assert!(!PatreonProvider::verify_signature("Jefe", BODY, "not-hex"));
+}
+
+#[test]
+fn events_parse_from_header() {
+    assert_eq!("members:create".parse(), Ok(PatreonEvent::MemberCreate));
+    assert_eq!("members:pledge:update".parse(), Ok(PatreonEvent::MemberUpdate));
+    assert_eq!("members:delete".parse(), Ok(PatreonEvent::MemberDelete));
+    assert!("posts:publish".parse::<PatreonEvent>().is_err());
+}
+
+// ── Tier mapping ─────────────────────────────────────────────────────────────
+
+#[test]
+fn active_patron_gets_highest_mapped_tier() {
+    let doc: PatreonMemberDocument = serde_json::from_str(&member(
+        "\"active_patron\"",
+        &["100", "200", "999"],
+        Some("2026-08-01T00:00:00.000+00:00"),
+    ))
+    .unwrap();
+
+    let grant = PatreonProvider::build_grant(&doc.data, 42, &tiers()).unwrap();
+
+    assert_eq!(grant.external_id, "member-1");
+    assert_eq!(grant.scope, EntitlementScope::User(42));
+    assert_eq!(grant.tier, Tier::Ultra);
+    assert_eq!(
+        grant.expires_at,
+        Some("2026-08-04T00:00:00Z".parse().unwrap()),
+        "grant runs GRACE_DAYS past the next charge"
+    );
+}
+
+#[test]
+fn inactive_or_unmapped_members_are_not_granted() {
+    for raw in [
+        member("\"declined_patron\"", &["100"], None),
+        member("\"former_patron\"", &["200"], None),
+        member("null", &["100"], None),
+        member("\"active_patron\"", &["999"], None),
+        member("\"active_patron\"", &[], None),
+    ] {
+        let doc: PatreonMemberDocument = serde_json::from_str(&raw).unwrap();
+        assert!(PatreonProvider::build_grant(&doc.data, 42, &tiers()).is_none());
+    }
+}
+
+#[test]
+fn member_exposes_linked_user() {
+    let doc: PatreonMemberDocument =
+        serde_json::from_str(&member("\"active_patron\"", &["100"], None)).unwrap();
+
+    assert_eq!(doc.data.user_id(), Some("user-1"));
+    assert_eq!(doc.data.tier_ids().collect::<Vec<_>>(), ["100"]);
+}
+
+// ── Members API ──────────────────────────────────────────────────────────────
+
+/// Serves the campaign in two pages, answering 401 to requests without the
+/// creator token.
+async fn mock_patreon() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base = format!("http://{}", listener.local_addr().unwrap());
+    let next =
+        format!("{base}/api/oauth2/v2/campaigns/7/members?page%5Bcursor%5D=2");
+
+    tokio::spawn(async move {
+        loop {
+            let Ok((mut socket, _)) = listener.accept().await else {
+                return;
+            };
+
+            let mut buf = vec![0; 8192];
+            let n = socket.read(&mut buf).await.unwrap_or(0);
+            let request = String::from_utf8_lossy(&buf[..n]).to_lowercase();
+
+            let (status, body) = if !request
+                .contains("authorization: bearer creator")
+            {
+                ("401 Unauthorized", String::new())
+            } else if request.contains("cursor%5d=2") {
+                ("200 OK", r#"{"data": [{"id": "member-2"}]}"#.to_owned())
+            } else {
+                (
+                    "200 OK",
+                    format!(
+                        r#"{{"data": [{{"id": "member-1"}}], "links": {{"next": "{next}"}}}}"#
+                    ),
+                )
+            };
+
+            let response = format!(
+                "HTTP/1.1 {status}\r\ncontent-type: application/json\r\n\
+                 content-length: {}\r\nconnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            let _ = socket.write_all(response.as_bytes()).await;
+        }
+    });
+
+    base
+}
+
+#[tokio::test]
+async fn members_follows_pagination() {
+    let base = mock_patreon().await;
+    let client = PatreonClient::new(reqwest::Client::new(), "creator", "7")
+        .with_base_url(base);
+
+    let members = client.members().await.unwrap();
+    let ids = members.iter().map(|m| m.id.as_str()).collect::<Vec<_>>();
+
+    assert_eq!(ids, ["member-1", "member-2"]);
+}
+
+#[tokio::test]
+async fn members_surfaces_auth_failures() {
+    let base = mock_patreon().await;
+    let client =
+        PatreonClient::new(reqwest::Client::new(), "wrong", "7").with_base_url(base);
+
+    assert!(client.members().await.is_err());
+}
@@ -0,0 +1,70 @@
+//! Coverage for what counts as a material rules change, which decides when
+//! members are asked to accept the rules again, and for quiz answer matching.
+
+use zayden_app::services::rules::{QuizQuestion, Rule, fingerprint};
+
+fn rule(title: &str, body: &str) -> Rule {
+    Rule { title: title.to_string(), body: body.to_string() }
+}
+
+fn rules() -> Vec<Rule> {
+    vec![
+        rule("Be respectful", "No harassment or hate speech."),
+        rule("No spam", "Keep self-promotion to #promo."),
+    ]
+}
+
+#[test]
+fn formatting_and_order_are_not_material() {
+    let before = fingerprint(Some("Read these before posting."), &rules());
+
+    let after = fingerprint(Some("**Read these   before posting**"), &[
+        rule("no spam", "Keep *self-promotion* to #promo"),
+        rule("BE RESPECTFUL", "No harassment\nor hate speech!"),
+    ]);
+
+    assert_eq!(before, after);
+}
+
+#[test]
+fn changed_wording_is_material() {
+    let before = fingerprint(None, &rules());
+
+    let mut changed = rules();
+    changed[1].body = "Keep self-promotion to #media.".to_string();
+
+    assert_ne!(before, fingerprint(None, &changed));
+}
+
+#[test]
+fn added_rule_is_material() {
+    let before = fingerprint(None, &rules());
+
+    let mut added = rules();
+    added.push(rule("English only", "Use English in public channels."));
+
+    assert_ne!(before, fingerprint(None, &added));
+}
+
+#[test]
+fn words_moving_between_rules_are_material() {
+    let split = fingerprint(None, &[rule("a", "b c"), rule("d", "e")]);
+    let moved = fingerprint(None, &[rule("a", "b"), rule("c d", "e")]);
+
+    assert_ne!(split, moved);
+}
+
+#[test]
+fn quiz_answers_ignore_case_and_punctuation() {
+    let question = QuizQuestion {
+        id: 1,
+        question: "Where does self-promotion go?".to_string(),
+        answers: vec!["#promo".to_string(), "the promo channel".to_string()],
+    };
+
+    assert!(question.accepts("promo"));
+    assert!(question.accepts("  The Promo   channel. "));
+    assert!(!question.accepts("general"));
+    assert!(!question.accepts(""));
+    assert!(!question.accepts("!!!"));
+}
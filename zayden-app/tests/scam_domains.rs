@@ -0,0 +1,38 @@
+//! Parsing the scam domain list and matching link hosts against it.
+
+use zayden_app::services::scam_domains::ScamDomains;
+
+const LIST: &str = "\
+# Known phishing domains
+steamcommunity-gift.ru
+*.Discord-Nitro.gift.   # wildcard and trailing dot
+
+";
+
+#[test]
+fn parse_skips_comments_and_normalizes() {
+    let domains = ScamDomains::parse(LIST);
+
+    assert_eq!(domains.len(), 2);
+    assert!(domains.contains("steamcommunity-gift.ru"));
+    assert!(domains.contains("discord-nitro.gift"));
+}
+
+#[test]
+fn contains_matches_subdomains_but_not_parents() {
+    let list = ScamDomains::from_content(LIST);
+
+    assert!(list.contains("steamcommunity-gift.ru"));
+    assert!(list.contains("login.STEAMCOMMUNITY-GIFT.ru."));
+    assert!(!list.contains("gift.ru"));
+    assert!(!list.contains("ru"));
+    assert!(!list.contains("steamcommunity.com"));
+}
+
+#[test]
+fn missing_file_loads_an_empty_list() {
+    let list = ScamDomains::load("/nonexistent/scam_domains.txt".into());
+
+    assert!(list.is_empty());
+    assert!(list.reload().is_err());
+}
@@ -0,0 +1,34 @@
+//! Coverage for premium server slots: how many each tier gets, which of a
+//! subscriber's assignments stay active after a downgrade, and the
+//! reassignment cooldown.
+
+use jiff::Timestamp;
+use zayden_app::entitlement::{SLOT_COOLDOWN, SlotAssignment, SlotHolder, Tier};
+
+#[test]
+fn tiers_grant_slots() {
+    assert_eq!(Tier::Free.premium_slots(), 0);
+    assert_eq!(Tier::Pro.premium_slots(), 1);
+    assert_eq!(Tier::Ultra.premium_slots(), 3);
+}
+
+#[test]
+fn oldest_assignments_stay_active() {
+    let holder = |rank| SlotHolder { user_id: 1, rank };
+
+    assert!(holder(1).is_active(Tier::Pro));
+    assert!(!holder(2).is_active(Tier::Pro), "Pro covers one server");
+    assert!(holder(3).is_active(Tier::Ultra));
+    assert!(!holder(4).is_active(Tier::Ultra));
+    assert!(!holder(1).is_active(Tier::Free), "a lapsed plan covers nothing");
+    assert!(!holder(0).is_active(Tier::Ultra));
+}
+
+#[test]
+fn slots_unlock_after_cooldown() {
+    let assigned_at: Timestamp = "2026-03-01T12:00:00Z".parse().unwrap();
+    let slot = SlotAssignment { guild_id: 7, assigned_at };
+
+    assert_eq!(slot.unlocks_at(), "2026-03-08T12:00:00Z".parse().unwrap());
+    assert_eq!(slot.unlocks_at().duration_since(assigned_at), SLOT_COOLDOWN);
+}
@@ -0,0 +1,209 @@
+//! Coverage for the timed moderation records the expiry sweep works from:
+//! which actions are due, and what cancelling, completing and failing does to
+//! them.
+//!
+//! These are `#[sqlx::test]`, so each test gets its own migrated database and
+//! `DATABASE_URL` must point at a server the runner may create databases on.
+//! Rows are written through the service itself; the one infraction a tempban
+//! needs is inserted with an unchecked query so the `.sqlx` cache is left as
+//! the release build expects it.
+
+use jiff::{SignedDuration, Timestamp};
+use sqlx::PgPool;
+use zayden_app::services::timed_actions::{self, MAX_FAILURES, Target};
+
+const GUILD: i64 = 1_234_567_890;
+const MEMBER: i64 = 2_345_678_901;
+const MODERATOR: i64 = 3_456_789_012;
+const ROLE: i64 = 4_567_890_123;
+
+fn in_minutes(minutes: i64) -> Timestamp {
+    Timestamp::now()
+        .checked_add(SignedDuration::from_mins(minutes))
+        .expect("timestamp in range")
+}
+
+async fn schedule_role(pool: &PgPool, expires_at: Timestamp) -> i32 {
+    timed_actions::schedule(
+        pool,
+        GUILD,
+        Target::Role { user_id: MEMBER, role_id: ROLE },
+        MODERATOR,
+        "testing",
+        expires_at,
+    )
+    .await
+    .expect("action scheduled")
+}
+
+/// A tempban's infraction, as `/tempban` records it before scheduling.
+async fn insert_ban(pool: &PgPool) -> i32 {
+    for (id, name) in [(MEMBER, "member"), (MODERATOR, "moderator")] {
+        sqlx::query("INSERT INTO users (id, username) VALUES ($1, $2)")
+            .bind(id)
+            .bind(name)
+            .execute(pool)
+            .await
+            .expect("user row");
+    }
+
+    sqlx::query_scalar(
+        "INSERT INTO infractions
+            (user_id, username, guild_id, infraction_type,
+             moderator_id, moderator_username, points, reason)
+        VALUES ($1, 'member', $2, 'Ban', $3, 'moderator', 1, 'testing')
+        RETURNING id",
+    )
+    .bind(MEMBER)
+    .bind(GUILD)
+    .bind(MODERATOR)
+    .fetch_one(pool)
+    .await
+    .expect("infraction row")
+}
+
+async fn due_ids(pool: &PgPool) -> Vec<i32> {
+    let due = timed_actions::due(pool).await.expect("due actions");
+    due.into_iter().map(|action| action.id).collect()
+}
+
+#[sqlx::test(migrations = "../migrations")]
+async fn only_actions_whose_time_is_up_are_due(pool: PgPool) {
+    let expired = schedule_role(&pool, in_minutes(-1)).await;
+    timed_actions::schedule(
+        &pool,
+        GUILD,
+        Target::Slowmode { channel_id: 42, previous: 0 },
+        MODERATOR,
+        "testing",
+        in_minutes(60),
+    )
+    .await
+    .expect("action scheduled");
+
+    assert_eq!(due_ids(&pool).await, [expired]);
+}
+
+#[sqlx::test(migrations = "../migrations")]
+async fn a_cancelled_action_is_never_due(pool: PgPool) {
+    let id = schedule_role(&pool, in_minutes(-1)).await;
+
+    assert!(timed_actions::cancel(&pool, GUILD, id, MODERATOR).await.unwrap());
+    assert!(due_ids(&pool).await.is_empty());
+    assert!(
+        !timed_actions::cancel(&pool, GUILD, id, MODERATOR).await.unwrap(),
+        "already cancelled"
+    );
+}
+
+#[sqlx::test(migrations = "../migrations")]
+async fn cancelling_a_tempban_makes_its_ban_permanent(pool: PgPool) {
+    let infraction_id = insert_ban(&pool).await;
+    let id = timed_actions::schedule(
+        &pool,
+        GUILD,
+        Target::Ban { user_id: MEMBER, infraction_id: Some(infraction_id) },
+        MODERATOR,
+        "testing",
+        in_minutes(60),
+    )
+    .await
+    .expect("action scheduled");
+
+    assert!(timed_actions::cancel(&pool, GUILD, id, MODERATOR).await.unwrap());
+
+    let expires_at: Option<jiff_sqlx::Timestamp> =
+        sqlx::query_scalar("SELECT expires_at FROM infractions WHERE id = $1")
+            .bind(infraction_id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert!(expires_at.is_none());
+}
+
+#[sqlx::test(migrations = "../migrations")]
+async fn completing_a_tempban_lifts_its_infraction(pool: PgPool) {
+    let infraction_id = insert_ban(&pool).await;
+    timed_actions::schedule(
+        &pool,
+        GUILD,
+        Target::Ban { user_id: MEMBER, infraction_id: Some(infraction_id) },
+        MODERATOR,
+        "testing",
+        in_minutes(-1),
+    )
+    .await
+    .expect("action scheduled");
+
+    let due = timed_actions::due(&pool).await.unwrap();
+    let [action] = due.as_slice() else { panic!("one due action: {due:?}") };
+    timed_actions::complete(&pool, action, "Timed action expired").await.unwrap();
+
+    assert!(due_ids(&pool).await.is_empty());
+    assert!(timed_actions::pending(&pool, GUILD).await.unwrap().is_empty());
+
+    let lift_reason: Option<String> =
+        sqlx::query_scalar("SELECT lift_reason FROM infractions WHERE id = $1")
+            .bind(infraction_id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_eq!(lift_reason.as_deref(), Some("Timed action expired"));
+}
+
+#[sqlx::test(migrations = "../migrations")]
+async fn a_failed_action_waits_for_its_retry_and_keeps_its_expiry(pool: PgPool) {
+    let id = schedule_role(&pool, in_minutes(-1)).await;
+    let before = timed_actions::get_pending(&pool, GUILD, id).await.unwrap();
+
+    assert!(!timed_actions::fail(&pool, id, "503").await.unwrap());
+    assert!(due_ids(&pool).await.is_empty(), "the retry isn't due yet");
+
+    let after = timed_actions::get_pending(&pool, GUILD, id).await.unwrap();
+    assert_eq!(
+        after.map(|action| action.expires_at),
+        before.map(|action| action.expires_at)
+    );
+}
+
+#[sqlx::test(migrations = "../migrations")]
+async fn completing_a_timed_role_records_why(pool: PgPool) {
+    let id = schedule_role(&pool, in_minutes(-1)).await;
+
+    let due = timed_actions::due(&pool).await.unwrap();
+    let [action] = due.as_slice() else { panic!("one due action: {due:?}") };
+    timed_actions::complete(&pool, action, "Timed action expired").await.unwrap();
+
+    let lift_reason: Option<String> =
+        sqlx::query_scalar("SELECT lift_reason FROM timed_actions WHERE id = $1")
+            .bind(id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_eq!(lift_reason.as_deref(), Some("Timed action expired"));
+}
+
+#[sqlx::test(migrations = "../migrations")]
+async fn an_action_stays_pending_but_stops_retrying_after_max_failures(
+    pool: PgPool,
+) {
+    let id = schedule_role(&pool, in_minutes(-1)).await;
+
+    for _ in 1..MAX_FAILURES {
+        assert!(!timed_actions::fail(&pool, id, "503").await.unwrap());
+    }
+    assert!(timed_actions::fail(&pool, id, "403").await.unwrap());
+
+    // The retry is past, but it is left to a moderator now.
+    sqlx::query("UPDATE timed_actions SET retry_at = now() WHERE id = $1")
+        .bind(id)
+        .execute(&pool)
+        .await
+        .unwrap();
+    assert!(due_ids(&pool).await.is_empty());
+
+    let action = timed_actions::get_pending(&pool, GUILD, id).await.unwrap();
+    let action = action.expect("still pending");
+    assert!(action.given_up());
+    assert_eq!(action.last_error.as_deref(), Some("403"));
+}